@@ -15,6 +15,9 @@ pub fn main() -> anyhow::Result<()> {
     compile_tex_frag("tex.frag.spv", false, false)?;
     compile_tex_frag("tex.frag.mult+opaque.spv", false, true)?;
     compile_tex_frag("tex.frag.mult+alpha.spv", true, true)?;
+    compile_gamma_lut_frag("tex.frag.gamma_lut.spv")?;
+    compile_color_transform_frag("tex.frag.color+opaque.spv", false)?;
+    compile_color_transform_frag("tex.frag.color+alpha.spv", true)?;
     Ok(())
 }
 
@@ -30,6 +33,21 @@ fn compile_tex_frag(out: &str, alpha: bool, alpha_multiplier: bool) -> anyhow::R
     Ok(())
 }
 
+fn compile_gamma_lut_frag(out: &str) -> anyhow::Result<()> {
+    let mut opts = CompileOptions::new().unwrap();
+    opts.add_macro_definition("GAMMA_LUT", None);
+    compile_shader("tex.frag", out, Some(&opts)).with_context(|| out.to_string())
+}
+
+fn compile_color_transform_frag(out: &str, alpha: bool) -> anyhow::Result<()> {
+    let mut opts = CompileOptions::new().unwrap();
+    opts.add_macro_definition("COLOR_TRANSFORM", None);
+    if alpha {
+        opts.add_macro_definition("ALPHA", None);
+    }
+    compile_shader("tex.frag", out, Some(&opts)).with_context(|| out.to_string())
+}
+
 fn compile_simple(name: &str) -> anyhow::Result<()> {
     compile_shader(name, &format!("{name}.spv"), None).with_context(|| name.to_string())
 }
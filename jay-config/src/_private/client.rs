@@ -9,6 +9,7 @@ use {
             },
             logging, Config, ConfigEntry, ConfigEntryGen, PollableId, WireMode, VERSION,
         },
+        client::Client as ClientHandle,
         exec::Command,
         input::{
             acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
@@ -21,13 +22,19 @@ use {
         },
         logging::LogLevel,
         tasks::{JoinHandle, JoinSlot},
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{
+            animations::{Animation, Curve},
+            colors::Colorable,
+            sized::Resizable,
+            Color,
+        },
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
             Connector, DrmDevice, GfxApi, Mode, TearingMode, Transform, VrrMode,
         },
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        window::{Window, WindowActions, WindowMatch, WindowRule},
+        Axis, Direction, Layout, ModifiedKeySym, PciId, Workspace,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -98,6 +105,9 @@ pub(crate) struct Client {
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_new_window: RefCell<Option<Callback<Window>>>,
+    on_window_closed: RefCell<Option<Callback<Window>>>,
+    on_window_title_changed: RefCell<Option<Callback<Window>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
     read_interests: RefCell<HashMap<PollableId, Interest>>,
@@ -228,6 +238,9 @@ pub unsafe extern "C" fn init(
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
         on_switch_event: Default::default(),
+        on_new_window: Default::default(),
+        on_window_closed: Default::default(),
+        on_window_title_changed: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
         read_interests: Default::default(),
@@ -443,6 +456,16 @@ impl Client {
         axis
     }
 
+    pub fn layout(&self, seat: Seat) -> Layout {
+        let res = self.send_with_response(&ClientMessage::GetLayout { seat });
+        get_response!(res, Layout::Split, GetLayout { layout });
+        layout
+    }
+
+    pub fn set_layout(&self, seat: Seat, layout: Layout) {
+        self.send(&ClientMessage::SetLayout { seat, layout });
+    }
+
     pub fn disable_pointer_constraint(&self, seat: Seat) {
         self.send(&ClientMessage::DisablePointerConstraint { seat });
     }
@@ -531,6 +554,25 @@ impl Client {
         self.send(&ClientMessage::SetSize { sized, size })
     }
 
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAnimationsEnabled { enabled })
+    }
+
+    pub fn set_animation_duration(&self, animation: Animation, duration: Duration) {
+        self.send(&ClientMessage::SetAnimationDuration {
+            animation,
+            duration,
+        })
+    }
+
+    pub fn set_animation_curve(&self, animation: Animation, curve: Curve) {
+        self.send(&ClientMessage::SetAnimationCurve { animation, curve })
+    }
+
+    pub fn reset_animations(&self) {
+        self.send(&ClientMessage::ResetAnimations);
+    }
+
     pub fn set_mono(&self, seat: Seat, mono: bool) {
         self.send(&ClientMessage::SetMono { seat, mono });
     }
@@ -812,6 +854,39 @@ impl Client {
         self.send(&ClientMessage::SetTearingMode { connector, mode })
     }
 
+    pub fn connector_set_color_temperature(&self, connector: Connector, kelvin: u32) {
+        self.send(&ClientMessage::ConnectorSetColorTemperature { connector, kelvin })
+    }
+
+    pub fn connector_set_brightness(&self, connector: Connector, brightness: f64) {
+        self.send(&ClientMessage::ConnectorSetBrightness {
+            connector,
+            brightness,
+        })
+    }
+
+    pub fn connector_set_hdr_enabled(&self, connector: Connector, enabled: bool) {
+        self.send(&ClientMessage::ConnectorSetHdrEnabled { connector, enabled })
+    }
+
+    pub fn connector_set_sdr_reference_white(&self, connector: Connector, nits: f64) {
+        self.send(&ClientMessage::ConnectorSetSdrReferenceWhite { connector, nits })
+    }
+
+    pub fn connector_set_mirror(&self, connector: Connector, source: Option<Connector>) {
+        self.send(&ClientMessage::ConnectorSetMirror { connector, source })
+    }
+
+    pub fn create_virtual_output(&self, mode: WireMode) -> Connector {
+        let res = self.send_with_response(&ClientMessage::CreateVirtualOutput { mode });
+        get_response!(res, Connector(0), CreateVirtualOutput { connector });
+        connector
+    }
+
+    pub fn remove_virtual_output(&self, connector: Connector) {
+        self.send(&ClientMessage::RemoveVirtualOutput { connector })
+    }
+
     pub fn drm_devices(&self) -> Vec<DrmDevice> {
         let res = self.send_with_response(&ClientMessage::GetDrmDevices);
         get_response!(res, vec![], GetDrmDevices { devices });
@@ -996,6 +1071,136 @@ impl Client {
         self.send(&ClientMessage::SetEiSocketEnabled { enabled })
     }
 
+    pub fn set_i3_ipc_socket_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetI3IpcSocketEnabled { enabled })
+    }
+
+    pub fn add_window_rule(&self, criteria: WindowMatch, actions: WindowActions) -> WindowRule {
+        let res = self.send_with_response(&ClientMessage::AddWindowRule { criteria, actions });
+        get_response!(res, WindowRule(0), AddWindowRule { rule });
+        rule
+    }
+
+    pub fn remove_window_rule(&self, rule: WindowRule) {
+        self.send(&ClientMessage::RemoveWindowRule { rule })
+    }
+
+    pub fn clear_window_rules(&self) {
+        self.send(&ClientMessage::ClearWindowRules)
+    }
+
+    pub fn windows(&self) -> Vec<Window> {
+        let res = self.send_with_response(&ClientMessage::GetWindows);
+        get_response!(res, vec![], GetWindows { windows });
+        windows
+    }
+
+    pub fn window_exists(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::WindowExists { window });
+        get_response!(res, false, WindowExists { exists });
+        exists
+    }
+
+    pub fn window_title(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowTitle { window });
+        get_response!(res, String::new(), GetWindowTitle { title });
+        title
+    }
+
+    pub fn window_app_id(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowAppId { window });
+        get_response!(res, String::new(), GetWindowAppId { app_id });
+        app_id
+    }
+
+    pub fn window_client(&self, window: Window) -> ClientHandle {
+        let res = self.send_with_response(&ClientMessage::GetWindowClient { window });
+        get_response!(res, ClientHandle(0), GetWindowClient { client });
+        client
+    }
+
+    pub fn window_workspace(&self, window: Window) -> Workspace {
+        let res = self.send_with_response(&ClientMessage::GetWindowWorkspace { window });
+        get_response!(res, Workspace(0), GetWindowWorkspace { workspace });
+        workspace
+    }
+
+    pub fn window_floating(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFloating { window });
+        get_response!(res, false, GetWindowFloating { floating });
+        floating
+    }
+
+    pub fn set_window_floating(&self, window: Window, floating: bool) {
+        self.send(&ClientMessage::SetWindowFloating { window, floating });
+    }
+
+    pub fn close_window(&self, window: Window) {
+        self.send(&ClientMessage::CloseWindow { window });
+    }
+
+    pub fn set_window_workspace(&self, window: Window, workspace: Workspace) {
+        self.send(&ClientMessage::SetWindowWorkspace { window, workspace });
+    }
+
+    pub fn focus_window(&self, seat: Seat, window: Window) {
+        self.send(&ClientMessage::FocusWindow { seat, window });
+    }
+
+    pub fn on_new_window<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_new_window.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn on_window_closed<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_window_closed.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn on_window_title_changed<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_window_title_changed.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn client_exists(&self, client: ClientHandle) -> bool {
+        let res = self.send_with_response(&ClientMessage::ClientExists { client });
+        get_response!(res, false, ClientExists { exists });
+        exists
+    }
+
+    pub fn client_pid(&self, client: ClientHandle) -> i32 {
+        let res = self.send_with_response(&ClientMessage::GetClientPid { client });
+        get_response!(res, 0, GetClientPid { pid });
+        pid
+    }
+
+    pub fn client_is_xwayland(&self, client: ClientHandle) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetClientIsXwayland { client });
+        get_response!(res, false, GetClientIsXwayland { xwayland });
+        xwayland
+    }
+
+    pub fn kill_client(&self, client: ClientHandle) {
+        self.send(&ClientMessage::KillClient { client });
+    }
+
+    pub fn set_client_shortcuts_inhibit_allowed(&self, client: ClientHandle, allowed: bool) {
+        self.send(&ClientMessage::SetClientShortcutsInhibitAllowed { client, allowed });
+    }
+
+    pub fn seat_set_shortcuts_inhibit_escape(&self, seat: Seat, escape: Option<ModifiedKeySym>) {
+        self.send(&ClientMessage::SeatSetShortcutsInhibitEscape { seat, escape });
+    }
+
+    pub fn move_to_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::MoveToScratchpad { seat });
+    }
+
+    pub fn show_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::ShowScratchpad { seat });
+    }
+
+    pub fn toggle_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::ToggleScratchpad { seat });
+    }
+
     pub fn latch<F: FnOnce() + 'static>(&self, seat: Seat, f: F) {
         if !self.feat_mod_mask.get() {
             log::error!("compositor does not support latching");
@@ -1413,6 +1618,24 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::NewWindow { window } => {
+                let handler = self.on_new_window.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("new window", &handler, window);
+                }
+            }
+            ServerMessage::WindowClosed { window } => {
+                let handler = self.on_window_closed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("window closed", &handler, window);
+                }
+            }
+            ServerMessage::WindowTitleChanged { window } => {
+                let handler = self.on_window_title_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("window title changed", &handler, window);
+                }
+            }
         }
     }
 
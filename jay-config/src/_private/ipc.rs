@@ -1,18 +1,25 @@
 use {
     crate::{
+        client::Client,
         input::{
             acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
             Seat, SwitchEvent,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, ModifiedKeySym},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{
+            animations::{Animation, Curve},
+            colors::Colorable,
+            sized::Resizable,
+            Color,
+        },
         timer::Timer,
         video::{
             connector_type::ConnectorType, Connector, DrmDevice, GfxApi, TearingMode, Transform,
             VrrMode,
         },
-        Axis, Direction, PciId, Workspace,
+        window::{Window, WindowActions, WindowMatch, WindowRule},
+        Axis, Direction, Layout, PciId, Workspace,
         _private::{PollableId, WireMode},
     },
     serde::{Deserialize, Serialize},
@@ -91,6 +98,15 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    NewWindow {
+        window: Window,
+    },
+    WindowClosed {
+        window: Window,
+    },
+    WindowTitleChanged {
+        window: Window,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -509,6 +525,125 @@ pub enum ClientMessage<'a> {
     SetEiSocketEnabled {
         enabled: bool,
     },
+    AddWindowRule {
+        criteria: WindowMatch,
+        actions: WindowActions,
+    },
+    RemoveWindowRule {
+        rule: WindowRule,
+    },
+    ClearWindowRules,
+    GetWindows,
+    WindowExists {
+        window: Window,
+    },
+    GetWindowTitle {
+        window: Window,
+    },
+    GetWindowAppId {
+        window: Window,
+    },
+    GetWindowClient {
+        window: Window,
+    },
+    GetWindowWorkspace {
+        window: Window,
+    },
+    GetWindowFloating {
+        window: Window,
+    },
+    SetWindowFloating {
+        window: Window,
+        floating: bool,
+    },
+    CloseWindow {
+        window: Window,
+    },
+    SetWindowWorkspace {
+        window: Window,
+        workspace: Workspace,
+    },
+    FocusWindow {
+        seat: Seat,
+        window: Window,
+    },
+    ClientExists {
+        client: Client,
+    },
+    GetClientPid {
+        client: Client,
+    },
+    GetClientIsXwayland {
+        client: Client,
+    },
+    KillClient {
+        client: Client,
+    },
+    MoveToScratchpad {
+        seat: Seat,
+    },
+    ShowScratchpad {
+        seat: Seat,
+    },
+    ToggleScratchpad {
+        seat: Seat,
+    },
+    GetLayout {
+        seat: Seat,
+    },
+    SetLayout {
+        seat: Seat,
+        layout: Layout,
+    },
+    SetI3IpcSocketEnabled {
+        enabled: bool,
+    },
+    ConnectorSetColorTemperature {
+        connector: Connector,
+        kelvin: u32,
+    },
+    ConnectorSetBrightness {
+        connector: Connector,
+        brightness: f64,
+    },
+    SetClientShortcutsInhibitAllowed {
+        client: Client,
+        allowed: bool,
+    },
+    SeatSetShortcutsInhibitEscape {
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    },
+    ConnectorSetHdrEnabled {
+        connector: Connector,
+        enabled: bool,
+    },
+    ConnectorSetSdrReferenceWhite {
+        connector: Connector,
+        nits: f64,
+    },
+    CreateVirtualOutput {
+        mode: WireMode,
+    },
+    RemoveVirtualOutput {
+        connector: Connector,
+    },
+    ConnectorSetMirror {
+        connector: Connector,
+        source: Option<Connector>,
+    },
+    SetAnimationsEnabled {
+        enabled: bool,
+    },
+    SetAnimationDuration {
+        animation: Animation,
+        duration: Duration,
+    },
+    SetAnimationCurve {
+        animation: Animation,
+        curve: Curve,
+    },
+    ResetAnimations,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -657,6 +792,45 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    AddWindowRule {
+        rule: WindowRule,
+    },
+    GetWindows {
+        windows: Vec<Window>,
+    },
+    WindowExists {
+        exists: bool,
+    },
+    GetWindowTitle {
+        title: String,
+    },
+    GetWindowAppId {
+        app_id: String,
+    },
+    GetWindowClient {
+        client: Client,
+    },
+    GetWindowWorkspace {
+        workspace: Workspace,
+    },
+    GetWindowFloating {
+        floating: bool,
+    },
+    ClientExists {
+        exists: bool,
+    },
+    GetClientPid {
+        pid: i32,
+    },
+    GetClientIsXwayland {
+        xwayland: bool,
+    },
+    GetLayout {
+        layout: Layout,
+    },
+    CreateVirtualOutput {
+        connector: Connector,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
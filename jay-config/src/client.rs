@@ -0,0 +1,45 @@
+//! Tools for inspecting and manipulating clients.
+
+use serde::{Deserialize, Serialize};
+
+/// A client connected to the compositor.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Client(pub u64);
+
+impl Client {
+    /// Returns whether the client is still connected.
+    pub fn exists(self) -> bool {
+        self.0 != 0 && get!(false).client_exists(self)
+    }
+
+    /// Returns the process ID of the client.
+    ///
+    /// Returns 0 if the process ID cannot be determined.
+    pub fn pid(self) -> i32 {
+        get!(0).client_pid(self)
+    }
+
+    /// Returns whether the client is Xwayland.
+    pub fn is_xwayland(self) -> bool {
+        get!(false).client_is_xwayland(self)
+    }
+
+    /// Disconnects the client.
+    pub fn kill(self) {
+        get!().kill_client(self)
+    }
+
+    /// Sets whether the client can inhibit the compositor shortcuts.
+    ///
+    /// Clients such as virtual machines and remote desktop viewers use the
+    /// keyboard-shortcuts-inhibit protocol to receive all key combinations while they
+    /// have the keyboard focus. The escape shortcut configured with
+    /// [`Seat::set_shortcuts_inhibit_escape`](crate::input::Seat::set_shortcuts_inhibit_escape)
+    /// is never inhibited. Clients cannot inhibit the shortcuts of a seat that has no
+    /// escape shortcut.
+    ///
+    /// Default: `true`
+    pub fn set_shortcuts_inhibit_allowed(self, allowed: bool) {
+        get!().set_client_shortcuts_inhibit_allowed(self, allowed)
+    }
+}
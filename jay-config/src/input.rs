@@ -7,7 +7,7 @@ use {
     crate::{
         input::{acceleration::AccelProfile, capability::Capability},
         keyboard::{mods::Modifiers, Keymap},
-        Axis, Direction, ModifiedKeySym, Workspace,
+        Axis, Direction, Layout, ModifiedKeySym, Workspace,
         _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
         video::Connector,
     },
@@ -257,6 +257,25 @@ impl Seat {
         get!().unbind(self, mod_sym.into())
     }
 
+    /// Sets the shortcut that is never inhibited by clients.
+    ///
+    /// While the focused surface inhibits the compositor shortcuts via the
+    /// keyboard-shortcuts-inhibit protocol, all key combinations are sent to the client
+    /// except for this one. Bind it to an action that moves the focus away from the
+    /// client to regain control over the compositor.
+    ///
+    /// Clients can only inhibit the shortcuts of seats that have an escape shortcut.
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(self, mod_sym: T) {
+        get!().seat_set_shortcuts_inhibit_escape(self, Some(mod_sym.into()))
+    }
+
+    /// Removes the shortcut set with [`Seat::set_shortcuts_inhibit_escape`].
+    ///
+    /// Active inhibitors of this seat are deactivated.
+    pub fn unset_shortcuts_inhibit_escape(self) {
+        get!().seat_set_shortcuts_inhibit_escape(self, None)
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn focus(self, direction: Direction) {
         get!().focus(self, direction)
@@ -315,6 +334,29 @@ impl Seat {
         self.set_split(self.split().other());
     }
 
+    /// Returns the layout of the parent-container of the currently focused window.
+    pub fn layout(self) -> Layout {
+        get!(Layout::Split).layout(self)
+    }
+
+    /// Sets the layout of the parent-container of the currently focused window.
+    ///
+    /// If the layout is tabbed or stacked, the focused window becomes the visible child.
+    pub fn set_layout(self, layout: Layout) {
+        get!().set_layout(self, layout)
+    }
+
+    /// Toggles the layout of the parent-container of the currently focused window.
+    ///
+    /// If the container is tabbed or stacked, it is changed to the split layout. Otherwise
+    /// the split axis is toggled.
+    pub fn toggle_layout(self) {
+        match self.layout() {
+            Layout::Split => self.toggle_split(),
+            _ => self.set_layout(Layout::Split),
+        }
+    }
+
     /// Returns the input devices assigned to this seat.
     pub fn input_devices(self) -> Vec<InputDevice> {
         get!().get_input_devices(Some(self))
@@ -351,6 +393,27 @@ impl Seat {
         get!().toggle_floating(self);
     }
 
+    /// Moves the currently focused window to the scratchpad.
+    ///
+    /// Windows in the scratchpad are hidden until they are shown with
+    /// [`Seat::show_scratchpad`].
+    pub fn move_to_scratchpad(self) {
+        get!().move_to_scratchpad(self);
+    }
+
+    /// Shows the window that has been in the scratchpad for the longest time.
+    ///
+    /// The window is shown floating on the output that contains the seat's cursor.
+    pub fn show_scratchpad(self) {
+        get!().show_scratchpad(self);
+    }
+
+    /// Hides the currently focused window if it is a scratchpad window. Otherwise shows
+    /// the next scratchpad window.
+    pub fn toggle_scratchpad(self) {
+        get!().toggle_scratchpad(self);
+    }
+
     /// Returns the workspace that is currently active on the output that contains the seat's
     /// cursor.
     ///
@@ -55,6 +55,7 @@ use {
 mod macros;
 #[doc(hidden)]
 pub mod _private;
+pub mod client;
 pub mod embedded;
 pub mod exec;
 pub mod input;
@@ -66,6 +67,7 @@ pub mod tasks;
 pub mod theme;
 pub mod timer;
 pub mod video;
+pub mod window;
 
 /// A planar direction.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
@@ -93,6 +95,18 @@ impl Axis {
     }
 }
 
+/// The layout of a container.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Layout {
+    /// The children are arranged next to each other along the split axis of the container.
+    Split,
+    /// Only one child is visible. The titles of all children are shown in a single row.
+    Tabbed,
+    /// Only one child is visible. The titles of all children are stacked on top of each
+    /// other.
+    Stacked,
+}
+
 /// Exits the compositor.
 pub fn quit() {
     get!().quit()
@@ -234,3 +248,18 @@ pub fn set_idle(timeout: Option<Duration>) {
 pub fn set_explicit_sync_enabled(enabled: bool) {
     get!().set_explicit_sync_enabled(enabled);
 }
+
+/// Enables or disables the i3/sway-compatible IPC socket.
+///
+/// The socket allows status bars and scripts written for i3 or sway to query the
+/// workspaces, outputs, and windows of the compositor, to subscribe to events, and to
+/// run a subset of the i3 commands. Its path is exported to applications via the
+/// `I3SOCK` and `SWAYSOCK` environment variables.
+///
+/// Any process that can connect to the socket can control the compositor. The socket
+/// does not support `exec` commands.
+///
+/// The default is `false`.
+pub fn set_i3_ipc_socket_enabled(enabled: bool) {
+    get!().set_i3_ipc_socket_enabled(enabled);
+}
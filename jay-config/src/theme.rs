@@ -314,3 +314,124 @@ pub mod sized {
         const 02 => BORDER_WIDTH,
     }
 }
+
+/// Animations of windows and workspaces.
+///
+/// Animations are enabled by default.
+pub mod animations {
+    use {
+        serde::{Deserialize, Serialize},
+        std::time::Duration,
+    };
+
+    /// A cubic bezier curve that maps the elapsed time of an animation to its progress.
+    ///
+    /// The curve starts at `(0, 0)` and ends at `(1, 1)`. The two control points are the
+    /// same as in the CSS `cubic-bezier` function.
+    #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+    pub struct Curve {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    }
+
+    impl Curve {
+        /// Progress proportional to the elapsed time.
+        pub const LINEAR: Self = Self::new_unchecked(0.0, 0.0, 1.0, 1.0);
+        /// The CSS `ease` curve.
+        pub const EASE: Self = Self::new_unchecked(0.25, 0.1, 0.25, 1.0);
+        /// The CSS `ease-in` curve.
+        pub const EASE_IN: Self = Self::new_unchecked(0.42, 0.0, 1.0, 1.0);
+        /// The CSS `ease-out` curve.
+        pub const EASE_OUT: Self = Self::new_unchecked(0.0, 0.0, 0.58, 1.0);
+        /// The CSS `ease-in-out` curve.
+        pub const EASE_IN_OUT: Self = Self::new_unchecked(0.42, 0.0, 0.58, 1.0);
+
+        const fn new_unchecked(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+            Self { x1, y1, x2, y2 }
+        }
+
+        /// Creates a curve from the control points `(x1, y1)` and `(x2, y2)`.
+        ///
+        /// The x coordinates must be in the range `[0, 1]`. If the points are invalid,
+        /// a warning is logged and the linear curve is used instead.
+        pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+            let valid = [x1, y1, x2, y2].iter().all(|v| v.is_finite())
+                && (0.0..=1.0).contains(&x1)
+                && (0.0..=1.0).contains(&x2);
+            if !valid {
+                log::warn!(
+                    "Control points {:?} do not describe a valid curve. Using the linear curve instead.",
+                    [x1, y1, x2, y2]
+                );
+                return Self::LINEAR;
+            }
+            Self::new_unchecked(x1, y1, x2, y2)
+        }
+
+        /// Returns the control points `[x1, y1, x2, y2]`.
+        pub fn control_points(self) -> [f64; 4] {
+            [self.x1, self.y1, self.x2, self.y2]
+        }
+    }
+
+    /// An animation whose duration and curve can be changed.
+    #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+    pub struct Animation(#[doc(hidden)] pub u32);
+
+    impl Animation {
+        /// Sets the duration of the animation.
+        ///
+        /// A duration of zero disables this animation.
+        pub fn set_duration(self, duration: Duration) {
+            get!().set_animation_duration(self, duration)
+        }
+
+        /// Sets the curve of the animation.
+        pub fn set_curve(self, curve: Curve) {
+            get!().set_animation_curve(self, curve)
+        }
+    }
+
+    macro_rules! animations {
+        ($($(#[$attr:meta])* const $n:expr => $name:ident,)*) => {
+            $(
+                $(#[$attr])*
+                pub const $name: Animation = Animation($n);
+            )*
+        }
+    }
+
+    animations! {
+        /// Windows and layer surfaces fading in when they are mapped.
+        ///
+        /// Default: 150 ms, `ease-out`
+        const 01 => WINDOW_OPEN,
+        /// Windows fading out when they are closed.
+        ///
+        /// Default: 150 ms, `ease-in`
+        const 02 => WINDOW_CLOSE,
+        /// Windows moving to a new position or size.
+        ///
+        /// Only tiled windows and windows that become floating are animated. Moving or
+        /// resizing a floating window is never animated.
+        ///
+        /// Default: 150 ms, `ease-out`
+        const 03 => WINDOW_MOVE,
+        /// Workspaces sliding in and out when switching workspaces.
+        ///
+        /// Default: 200 ms, `ease-in-out`
+        const 04 => WORKSPACE_SWITCH,
+    }
+
+    /// Enables or disables all animations.
+    pub fn set_enabled(enabled: bool) {
+        get!().set_animations_enabled(enabled)
+    }
+
+    /// Resets all animations to their defaults and enables them.
+    pub fn reset_animations() {
+        get!().reset_animations()
+    }
+}
@@ -267,6 +267,126 @@ impl Connector {
     pub fn set_tearing_mode(self, mode: TearingMode) {
         get!().set_tearing_mode(Some(self), mode)
     }
+
+    /// Sets the color temperature of the connector in Kelvin.
+    ///
+    /// The value must be between 1000 and 25000. Lower values make the output appear
+    /// warmer. The default is 6500 which displays colors unmodified.
+    ///
+    /// This setting has no effect while an application such as gammastep controls the
+    /// gamma ramps of the connector.
+    pub fn set_color_temperature(self, kelvin: u32) {
+        if !self.exists() {
+            log::warn!("set_color_temperature called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_color_temperature(self, kelvin);
+    }
+
+    /// Sets the brightness of the connector.
+    ///
+    /// The value must be between 0.1 and 1.0. The default is 1.0.
+    ///
+    /// The brightness is applied by scaling the gamma ramps of the connector and does not
+    /// affect the backlight.
+    pub fn set_brightness(self, brightness: f64) {
+        if !self.exists() {
+            log::warn!("set_brightness called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_brightness(self, brightness);
+    }
+
+    /// Enables or disables HDR on the connector.
+    ///
+    /// HDR is only enabled if the display advertises support for the PQ transfer function
+    /// and the driver supports the required connector properties. While HDR is enabled,
+    /// the output is rendered with BT.2020 primaries and the PQ transfer function.
+    ///
+    /// The default is `false`.
+    pub fn set_hdr_enabled(self, enabled: bool) {
+        if !self.exists() {
+            log::warn!("set_hdr_enabled called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_hdr_enabled(self, enabled);
+    }
+
+    /// Sets the luminance in cd/m² at which SDR white is displayed while HDR is enabled.
+    ///
+    /// The value must be between 40 and 1000. The default is 203.
+    pub fn set_sdr_reference_white(self, nits: f64) {
+        if !self.exists() {
+            log::warn!("set_sdr_reference_white called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_sdr_reference_white(self, nits);
+    }
+
+    /// Removes the connector if it belongs to a virtual output.
+    ///
+    /// See [`create_virtual_output`].
+    pub fn remove_virtual_output(self) {
+        if !self.exists() {
+            log::warn!("remove_virtual_output called on a connector that does not exist");
+            return;
+        }
+        get!().remove_virtual_output(self);
+    }
+
+    /// Makes this connector mirror another connector.
+    ///
+    /// While a connector mirrors another connector, it is removed from the desktop and shows
+    /// the contents of the source connector instead, scaled to fit while preserving the
+    /// aspect ratio. Settings such as the mode of the connector still apply.
+    ///
+    /// The source connector can be connected to a different GPU.
+    ///
+    /// Mirrors cannot be chained. The request is ignored if the source connector is itself
+    /// mirroring another connector or if this connector is being mirrored.
+    pub fn mirror(self, source: Connector) {
+        if !self.exists() {
+            log::warn!("mirror called on a connector that does not exist");
+            return;
+        }
+        if !source.exists() {
+            log::warn!("mirror called with a source connector that does not exist");
+            return;
+        }
+        get!().connector_set_mirror(self, Some(source));
+    }
+
+    /// Stops mirroring another connector.
+    ///
+    /// See [`Connector::mirror`].
+    pub fn unmirror(self) {
+        if !self.exists() {
+            log::warn!("unmirror called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_mirror(self, None);
+    }
+}
+
+/// Creates a virtual output.
+///
+/// Virtual outputs behave like outputs connected to a display but their contents are only
+/// visible via screencasts and screenshots. Their connectors have the type [`CON_VIRTUAL`].
+///
+/// The connector is announced asynchronously like any other connector, that is, the callbacks
+/// registered with [`on_new_connector`] and [`on_connector_connected`] are invoked for it.
+///
+/// If the refresh rate is not specified, it defaults to 60 Hz.
+///
+/// The width and height must be between 1 and 8192 and the refresh rate must be between
+/// 1 mHz and 1000 Hz. Otherwise no output is created and the returned connector is
+/// invalid.
+pub fn create_virtual_output(width: i32, height: i32, refresh_millihz: Option<u32>) -> Connector {
+    get!(Connector(0)).create_virtual_output(WireMode {
+        width,
+        height,
+        refresh_millihz: refresh_millihz.unwrap_or(60_000),
+    })
 }
 
 /// Returns all available DRM devices.
@@ -0,0 +1,222 @@
+//! Tools for inspecting and manipulating windows.
+
+use {
+    crate::{client::Client, input::Seat, video::Connector, Workspace},
+    serde::{Deserialize, Serialize},
+};
+
+/// A toplevel window.
+///
+/// Windows are identified by an id that is valid from the time the window is mapped
+/// until it is unmapped. Using the handle of a window that no longer exists has no effect.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Window(pub u64);
+
+impl Window {
+    /// Returns whether the window still exists.
+    pub fn exists(self) -> bool {
+        self.0 != 0 && get!(false).window_exists(self)
+    }
+
+    /// Returns the title of the window.
+    pub fn title(self) -> String {
+        get!(String::new()).window_title(self)
+    }
+
+    /// Returns the app-id of the window.
+    ///
+    /// For Xwayland windows, this is the class of the window.
+    pub fn app_id(self) -> String {
+        get!(String::new()).window_app_id(self)
+    }
+
+    /// Returns the client that created the window.
+    pub fn client(self) -> Client {
+        get!(Client(0)).window_client(self)
+    }
+
+    /// Returns the workspace the window is on.
+    ///
+    /// If the window is not on a workspace, the returned workspace does not exist.
+    pub fn workspace(self) -> Workspace {
+        get!(Workspace(0)).window_workspace(self)
+    }
+
+    /// Returns whether the window is floating.
+    pub fn is_floating(self) -> bool {
+        get!(false).window_floating(self)
+    }
+
+    /// Sets whether the window is floating.
+    pub fn set_floating(self, floating: bool) {
+        get!().set_window_floating(self, floating)
+    }
+
+    /// Toggles whether the window is floating.
+    pub fn toggle_floating(self) {
+        let get = get!();
+        get.set_window_floating(self, !get.window_floating(self));
+    }
+
+    /// Requests the window to be closed.
+    pub fn close(self) {
+        get!().close_window(self)
+    }
+
+    /// Moves the window to a workspace.
+    ///
+    /// If the workspace does not exist, it is created on the output of the window.
+    pub fn move_to_workspace(self, workspace: Workspace) {
+        get!().set_window_workspace(self, workspace)
+    }
+
+    /// Gives the keyboard focus of the seat to the window.
+    ///
+    /// If the workspace of the window is not visible, it is shown first.
+    pub fn focus(self, seat: Seat) {
+        get!().focus_window(seat, self)
+    }
+}
+
+/// Returns all windows.
+pub fn windows() -> Vec<Window> {
+    get!().windows()
+}
+
+/// Sets the callback to be called when a window is mapped.
+pub fn on_new_window<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_new_window(f)
+}
+
+/// Sets the callback to be called when a window is unmapped.
+///
+/// At the time this callback is invoked, the window no longer exists.
+pub fn on_window_closed<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_window_closed(f)
+}
+
+/// Sets the callback to be called when the title of a window changes.
+pub fn on_title_changed<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_window_title_changed(f)
+}
+
+/// A window rule.
+///
+/// Window rules are evaluated when a window is mapped for the first time. See
+/// [`add_window_rule`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct WindowRule(pub u64);
+
+impl WindowRule {
+    /// Removes the rule.
+    ///
+    /// Windows that have already been mapped are not affected.
+    pub fn remove(self) {
+        get!().remove_window_rule(self)
+    }
+}
+
+/// Criteria that select the windows a rule applies to.
+///
+/// A window matches if all of the specified criteria match. If no criteria are
+/// specified, all windows match.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct WindowMatch {
+    /// The app-id of a wayland window.
+    pub app_id: Option<String>,
+    /// A regular expression that must match the title of the window.
+    ///
+    /// The expression is not anchored. Use `^` and `$` to match the entire title.
+    pub title: Option<String>,
+    /// The class of an Xwayland window. (The second part of `WM_CLASS`.)
+    pub class: Option<String>,
+    /// The instance of an Xwayland window. (The first part of `WM_CLASS`.)
+    pub instance: Option<String>,
+    /// The role of an Xwayland window. (`WM_WINDOW_ROLE`.)
+    pub role: Option<String>,
+    /// The process ID of the client that created the window.
+    ///
+    /// For Xwayland windows, this is the value of the `_NET_WM_PID` property.
+    pub pid: Option<i32>,
+    /// The path of the executable of the client that created the window.
+    pub exe: Option<String>,
+    /// Whether the window initially wants to be floating.
+    ///
+    /// Wayland windows with a parent and Xwayland dialogs, utility windows, etc. are
+    /// mapped floating by default.
+    pub floating: Option<bool>,
+    /// Whether the window is a dialog.
+    pub dialog: Option<bool>,
+}
+
+/// The actions applied to the windows matched by a rule.
+///
+/// If multiple rules match a window, the actions are applied in the order in which
+/// the rules were added. Actions of later rules override actions of earlier rules.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct WindowActions {
+    /// The workspace the window is mapped on.
+    ///
+    /// If the workspace does not exist, it is created on the output specified by
+    /// `output` or on the output of the seat that was last active.
+    pub workspace: Option<Workspace>,
+    /// The output the window is mapped on.
+    ///
+    /// If `workspace` is not set, the window is mapped on the active workspace of this
+    /// output.
+    pub output: Option<Connector>,
+    /// Whether the window is floating.
+    pub floating: Option<bool>,
+    /// The size of a floating window in logical pixels.
+    ///
+    /// This size does not include the border and title.
+    pub size: Option<(i32, i32)>,
+    /// The position of a floating window relative to the top-left corner of its output.
+    pub position: Option<(i32, i32)>,
+    /// Whether the window is fullscreen.
+    pub fullscreen: Option<bool>,
+    /// Sets whether the workspace the window is mapped on can be captured.
+    ///
+    /// This changes the setting of the whole workspace, not only of this window, and the
+    /// setting is not restored when the window is closed. It has the same effect as
+    /// calling [`Workspace::set_capture`](crate::Workspace::set_capture).
+    pub workspace_capture: Option<bool>,
+    /// Whether the client of the window can inhibit the compositor shortcuts.
+    ///
+    /// This applies to the client and therefore to all of its windows. See
+    /// [`Client::set_shortcuts_inhibit_allowed`](crate::client::Client::set_shortcuts_inhibit_allowed).
+    pub shortcuts_inhibit: Option<bool>,
+    /// Whether the rule is evaluated again when the title of the window changes.
+    ///
+    /// If this is `true` and a window starts to match the rule after its title has
+    /// changed, the actions are applied to the already-mapped window. This is useful
+    /// for applications that set their title only after the window has been mapped.
+    pub reevaluate_on_title_change: bool,
+}
+
+/// Adds a window rule.
+///
+/// The rule is applied to all windows mapped after this function returns.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// add_window_rule(
+///     WindowMatch {
+///         app_id: Some("firefox".to_string()),
+///         ..Default::default()
+///     },
+///     WindowActions {
+///         workspace: Some(get_workspace("2")),
+///         ..Default::default()
+///     },
+/// );
+/// ```
+pub fn add_window_rule(criteria: WindowMatch, actions: WindowActions) -> WindowRule {
+    get!(WindowRule(0)).add_window_rule(criteria, actions)
+}
+
+/// Removes all window rules.
+pub fn clear_window_rules() {
+    get!().clear_window_rules()
+}
@@ -0,0 +1,504 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        format::ARGB8888,
+        gfx_api::{AcquireSync, GfxError, ReleaseSync, SyncFile},
+        offscreen_image::{OffscreenImage, OffscreenImageError},
+        rect::Rect,
+        renderer::Renderer,
+        scale::Scale,
+        state::State,
+        theme::Color,
+        tree::{NodeId, OutputNodeId, WorkspaceNodeId},
+        utils::{clonecell::UnsafeCellCloneSafe, copyhashmap::CopyHashMap},
+    },
+    jay_config::{theme::animations::Curve, video::Transform},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum AnimationError {
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Render device does not support ARGB8888 format")]
+    ARGB8888,
+    #[error(transparent)]
+    Image(#[from] OffscreenImageError),
+    #[error("Could not render the snapshot")]
+    Render(#[source] GfxError),
+}
+
+/// A cubic bezier curve from `(0, 0)` to `(1, 1)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BezierCurve {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl BezierCurve {
+    pub const EASE_IN: Self = Self {
+        x1: 0.42,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 1.0,
+    };
+    pub const EASE_OUT: Self = Self {
+        x1: 0.0,
+        y1: 0.0,
+        x2: 0.58,
+        y2: 1.0,
+    };
+    pub const EASE_IN_OUT: Self = Self {
+        x1: 0.42,
+        y1: 0.0,
+        x2: 0.58,
+        y2: 1.0,
+    };
+
+    /// Creates a new curve.
+    ///
+    /// Returns `None` if the x coordinates are not in `[0, 1]`. This ensures that the
+    /// curve is a function of the time.
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Option<Self> {
+        let valid = [x1, y1, x2, y2].iter().all(|v| v.is_finite())
+            && (0.0..=1.0).contains(&x1)
+            && (0.0..=1.0).contains(&x2);
+        valid.then_some(Self { x1, y1, x2, y2 })
+    }
+
+    pub fn from_curve(curve: Curve) -> Option<Self> {
+        let [x1, y1, x2, y2] = curve.control_points();
+        Self::new(x1, y1, x2, y2)
+    }
+
+    /// Maps the elapsed fraction of the duration to the progress of the animation.
+    pub fn apply(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if t >= 1.0 {
+            return 1.0;
+        }
+        fn sample(p1: f64, p2: f64, s: f64) -> f64 {
+            let r = 1.0 - s;
+            3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+        }
+        // x(s) is monotonic since both x coordinates are in [0, 1].
+        let mut lo = 0.0;
+        let mut hi = 1.0;
+        for _ in 0..32 {
+            let mid = (lo + hi) / 2.0;
+            if sample(self.x1, self.x2, mid) < t {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        sample(self.y1, self.y2, (lo + hi) / 2.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AnimationKind {
+    WindowOpen,
+    WindowClose,
+    WindowMove,
+    WorkspaceSwitch,
+}
+
+impl AnimationKind {
+    fn default_params(self) -> Params {
+        let (duration_msec, curve) = match self {
+            AnimationKind::WindowOpen => (150, BezierCurve::EASE_OUT),
+            AnimationKind::WindowClose => (150, BezierCurve::EASE_IN),
+            AnimationKind::WindowMove => (150, BezierCurve::EASE_OUT),
+            AnimationKind::WorkspaceSwitch => (200, BezierCurve::EASE_IN_OUT),
+        };
+        Params {
+            duration_nsec: duration_msec * 1_000_000,
+            curve,
+        }
+    }
+}
+
+const KINDS: [AnimationKind; 4] = [
+    AnimationKind::WindowOpen,
+    AnimationKind::WindowClose,
+    AnimationKind::WindowMove,
+    AnimationKind::WorkspaceSwitch,
+];
+
+#[derive(Copy, Clone, Debug)]
+struct Params {
+    duration_nsec: u64,
+    curve: BezierCurve,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Timing {
+    start_nsec: u64,
+    duration_nsec: u64,
+    curve: BezierCurve,
+}
+
+unsafe impl UnsafeCellCloneSafe for Timing {}
+
+impl Timing {
+    /// Returns the progress of the animation or `None` if the animation has finished.
+    fn progress(&self, now_nsec: u64) -> Option<f64> {
+        let elapsed = now_nsec.saturating_sub(self.start_nsec);
+        if elapsed >= self.duration_nsec {
+            return None;
+        }
+        Some(self.curve.apply(elapsed as f64 / self.duration_nsec as f64))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Opening {
+    timing: Timing,
+    output: OutputNodeId,
+}
+
+unsafe impl UnsafeCellCloneSafe for Opening {}
+
+#[derive(Copy, Clone, Debug)]
+struct Move {
+    timing: Timing,
+    output: OutputNodeId,
+    from: Rect,
+    to: Rect,
+}
+
+unsafe impl UnsafeCellCloneSafe for Move {}
+
+/// An image of the contents of a node that no longer exist in their old form.
+pub struct Snapshot {
+    image: OffscreenImage,
+    sync_file: Option<SyncFile>,
+    width: i32,
+    height: i32,
+}
+
+impl Snapshot {
+    /// Renders the contents of a `width`x`height` rectangle into a new snapshot.
+    pub fn new(
+        state: &State,
+        scale: Scale,
+        width: i32,
+        height: i32,
+        f: impl FnOnce(&mut Renderer),
+    ) -> Result<Self, AnimationError> {
+        let Some(ctx) = state.render_ctx.get() else {
+            return Err(AnimationError::NoRenderContext);
+        };
+        let formats = ctx.formats();
+        let format = match formats.get(&ARGB8888.drm) {
+            Some(f) => f,
+            None => return Err(AnimationError::ARGB8888),
+        };
+        let (pixel_width, pixel_height) = scale.pixel_size(width, height);
+        let image = OffscreenImage::new(
+            &ctx,
+            &state.dma_buf_ids,
+            pixel_width,
+            pixel_height,
+            ARGB8888,
+            &format.write_modifiers,
+        )?;
+        let fb = &image.fb;
+        let mut ops = fb.take_render_ops();
+        let mut renderer = Renderer {
+            base: fb.renderer_base(&mut ops, scale, Transform::None),
+            state,
+            result: None,
+            logical_extents: Rect::new_sized_unchecked(0, 0, width, height),
+            pixel_extents: Rect::new_sized_unchecked(0, 0, pixel_width, pixel_height),
+            color_description: state.color_manager.srgb().clone(),
+            opacity: 1.0,
+        };
+        f(&mut renderer);
+        let sync_file = fb
+            .render(ops, Some(&Color::TRANSPARENT))
+            .map_err(AnimationError::Render)?;
+        Ok(Self {
+            image,
+            sync_file,
+            width,
+            height,
+        })
+    }
+
+    pub fn render(&self, renderer: &mut Renderer, x: i32, y: i32, alpha: f32) {
+        let rect = Rect::new_sized_unchecked(x, y, self.width, self.height);
+        let rect = renderer.base.scale_rect(rect);
+        renderer.base.render_texture(
+            &self.image.tex,
+            Some(alpha),
+            rect.x1(),
+            rect.y1(),
+            None,
+            Some((rect.width(), rect.height())),
+            renderer.base.scale,
+            None,
+            None,
+            AcquireSync::from_sync_file(self.sync_file.clone()),
+            ReleaseSync::None,
+        );
+    }
+}
+
+pub struct ClosingWindow {
+    timing: Timing,
+    output: OutputNodeId,
+    pub rect: Rect,
+    pub workspace: WorkspaceNodeId,
+    pub snapshot: Snapshot,
+}
+
+pub struct WorkspaceSlide {
+    timing: Timing,
+    pub workspace: WorkspaceNodeId,
+    pub forward: bool,
+    pub snapshot: Snapshot,
+}
+
+/// The running animations and their settings.
+///
+/// Animations never affect the layout. They only change where and how nodes are
+/// rendered while the animation is running.
+pub struct Animations {
+    enabled: Cell<bool>,
+    params: [Cell<Params>; KINDS.len()],
+    opening: CopyHashMap<NodeId, Opening>,
+    moving: CopyHashMap<NodeId, Move>,
+    closing: RefCell<Vec<Rc<ClosingWindow>>>,
+    slides: CopyHashMap<OutputNodeId, Rc<WorkspaceSlide>>,
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(true),
+            params: KINDS.map(|k| Cell::new(k.default_params())),
+            opening: Default::default(),
+            moving: Default::default(),
+            closing: Default::default(),
+            slides: Default::default(),
+        }
+    }
+}
+
+impl Animations {
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    pub fn set_duration(&self, kind: AnimationKind, duration_nsec: u64) {
+        let params = &self.params[kind as usize];
+        params.set(Params {
+            duration_nsec,
+            ..params.get()
+        });
+    }
+
+    pub fn set_curve(&self, kind: AnimationKind, curve: BezierCurve) {
+        let params = &self.params[kind as usize];
+        params.set(Params {
+            curve,
+            ..params.get()
+        });
+    }
+
+    pub fn reset(&self) {
+        self.enabled.set(true);
+        for kind in KINDS {
+            self.params[kind as usize].set(kind.default_params());
+        }
+    }
+
+    /// Stops all running animations.
+    pub fn clear(&self) {
+        self.opening.clear();
+        self.moving.clear();
+        self.closing.borrow_mut().clear();
+        self.slides.clear();
+    }
+
+    /// Returns whether animations of this kind should be started.
+    pub fn enabled(&self, kind: AnimationKind) -> bool {
+        self.enabled.get() && self.params[kind as usize].get().duration_nsec > 0
+    }
+
+    fn timing(&self, kind: AnimationKind, now_nsec: u64) -> Option<Timing> {
+        if !self.enabled(kind) {
+            return None;
+        }
+        let params = self.params[kind as usize].get();
+        Some(Timing {
+            start_nsec: now_nsec,
+            duration_nsec: params.duration_nsec,
+            curve: params.curve,
+        })
+    }
+
+    pub fn window_opened(&self, id: NodeId, output: OutputNodeId, now_nsec: u64) {
+        if let Some(timing) = self.timing(AnimationKind::WindowOpen, now_nsec) {
+            self.opening.set(id, Opening { timing, output });
+        }
+    }
+
+    /// Starts moving the node from `from` to `to`.
+    ///
+    /// If the node is already moving, the new animation starts at its current position.
+    pub fn window_moved(
+        &self,
+        id: NodeId,
+        output: OutputNodeId,
+        from: Rect,
+        to: Rect,
+        now_nsec: u64,
+    ) {
+        let Some(timing) = self.timing(AnimationKind::WindowMove, now_nsec) else {
+            return;
+        };
+        let from = match self.window_rect(id, now_nsec) {
+            Some((current, _)) => current,
+            None => from,
+        };
+        if from == to {
+            self.moving.remove(&id);
+            return;
+        }
+        self.moving.set(
+            id,
+            Move {
+                timing,
+                output,
+                from,
+                to,
+            },
+        );
+    }
+
+    pub fn window_closed(
+        &self,
+        rect: Rect,
+        output: OutputNodeId,
+        workspace: WorkspaceNodeId,
+        snapshot: Snapshot,
+        now_nsec: u64,
+    ) {
+        if let Some(timing) = self.timing(AnimationKind::WindowClose, now_nsec) {
+            self.closing.borrow_mut().push(Rc::new(ClosingWindow {
+                timing,
+                output,
+                rect,
+                workspace,
+                snapshot,
+            }));
+        }
+    }
+
+    pub fn workspace_switched(
+        &self,
+        output: OutputNodeId,
+        workspace: WorkspaceNodeId,
+        forward: bool,
+        snapshot: Snapshot,
+        now_nsec: u64,
+    ) {
+        if let Some(timing) = self.timing(AnimationKind::WorkspaceSwitch, now_nsec) {
+            let slide = WorkspaceSlide {
+                timing,
+                workspace,
+                forward,
+                snapshot,
+            };
+            self.slides.set(output, Rc::new(slide));
+        }
+    }
+
+    pub fn remove_window(&self, id: NodeId) {
+        self.opening.remove(&id);
+        self.moving.remove(&id);
+    }
+
+    /// Returns the current and the final position of a moving node.
+    pub fn window_rect(&self, id: NodeId, now_nsec: u64) -> Option<(Rect, Rect)> {
+        let mv = self.moving.get(&id)?;
+        let p = mv.timing.progress(now_nsec)?;
+        let lerp = |a: i32, b: i32| a + ((b - a) as f64 * p).round() as i32;
+        let current = Rect::new(
+            lerp(mv.from.x1(), mv.to.x1()),
+            lerp(mv.from.y1(), mv.to.y1()),
+            lerp(mv.from.x2(), mv.to.x2()),
+            lerp(mv.from.y2(), mv.to.y2()),
+        )?;
+        Some((current, mv.to))
+    }
+
+    pub fn window_opacity(&self, id: NodeId, now_nsec: u64) -> f32 {
+        self.opening
+            .get(&id)
+            .and_then(|o| o.timing.progress(now_nsec))
+            .unwrap_or(1.0) as f32
+    }
+
+    /// Returns the closing windows of the workspace and their opacity.
+    pub fn closing_windows(
+        &self,
+        workspace: WorkspaceNodeId,
+        now_nsec: u64,
+    ) -> Vec<(Rc<ClosingWindow>, f32)> {
+        let mut res = vec![];
+        for cw in self.closing.borrow().iter() {
+            if cw.workspace == workspace {
+                if let Some(p) = cw.timing.progress(now_nsec) {
+                    res.push((cw.clone(), 1.0 - p as f32));
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns the slide that shows `workspace` on the output and its progress.
+    pub fn workspace_slide(
+        &self,
+        output: OutputNodeId,
+        workspace: WorkspaceNodeId,
+        now_nsec: u64,
+    ) -> Option<(Rc<WorkspaceSlide>, f64)> {
+        let slide = self.slides.get(&output)?;
+        if slide.workspace != workspace {
+            return None;
+        }
+        let p = slide.timing.progress(now_nsec)?;
+        Some((slide, p))
+    }
+
+    /// Removes finished animations and returns whether animations are still running on
+    /// the output.
+    pub fn tick(&self, output: OutputNodeId, now_nsec: u64) -> bool {
+        let running = |t: &Timing| t.progress(now_nsec).is_some();
+        self.opening.lock().retain(|_, o| running(&o.timing));
+        self.moving.lock().retain(|_, m| running(&m.timing));
+        self.closing.borrow_mut().retain(|c| running(&c.timing));
+        self.slides.lock().retain(|_, s| running(&s.timing));
+        self.opening.lock().values().any(|o| o.output == output)
+            || self.moving.lock().values().any(|m| m.output == output)
+            || self.closing.borrow().iter().any(|c| c.output == output)
+            || self.slides.contains(&output)
+    }
+}
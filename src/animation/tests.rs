@@ -0,0 +1,85 @@
+use {
+    crate::{
+        animation::{Animations, BezierCurve},
+        rect::Rect,
+        tree::{NodeId, OutputNodeId},
+    },
+    jay_config::theme::animations::Curve,
+};
+
+#[test]
+fn bezier_endpoints() {
+    let linear = BezierCurve::from_curve(Curve::LINEAR).unwrap();
+    for curve in [
+        linear,
+        BezierCurve::EASE_IN,
+        BezierCurve::EASE_OUT,
+        BezierCurve::EASE_IN_OUT,
+    ] {
+        assert_eq!(curve.apply(-1.0), 0.0);
+        assert_eq!(curve.apply(0.0), 0.0);
+        assert_eq!(curve.apply(1.0), 1.0);
+        assert_eq!(curve.apply(2.0), 1.0);
+    }
+}
+
+#[test]
+fn bezier_shape() {
+    let linear = BezierCurve::from_curve(Curve::LINEAR).unwrap();
+    for t in [0.1, 0.25, 0.5, 0.75, 0.9] {
+        assert!((linear.apply(t) - t).abs() < 1e-6);
+        assert!(BezierCurve::EASE_IN.apply(t) < t);
+        assert!(BezierCurve::EASE_OUT.apply(t) > t);
+    }
+    assert!((BezierCurve::EASE_IN_OUT.apply(0.5) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn bezier_validation() {
+    assert!(BezierCurve::new(0.0, -1.0, 1.0, 2.0).is_some());
+    assert!(BezierCurve::new(-0.1, 0.0, 1.0, 1.0).is_none());
+    assert!(BezierCurve::new(0.0, 0.0, 1.1, 1.0).is_none());
+    assert!(BezierCurve::new(0.0, f64::NAN, 1.0, 1.0).is_none());
+    assert_eq!(
+        BezierCurve::from_curve(Curve::EASE_IN_OUT),
+        Some(BezierCurve::EASE_IN_OUT)
+    );
+}
+
+#[test]
+fn window_move() {
+    let animations = Animations::default();
+    let id = NodeId(1);
+    let from = Rect::new_sized(0, 0, 100, 100).unwrap();
+    let to = Rect::new_sized(100, 0, 100, 100).unwrap();
+    let output = OutputNodeId::from(NodeId(1));
+    animations.window_moved(id, output, from, to, 0);
+    let (current, target) = animations.window_rect(id, 75_000_000).unwrap();
+    assert_eq!(target, to);
+    assert!(current.x1() > 0 && current.x1() < 100);
+    assert_eq!(current.size(), (100, 100));
+    assert!(animations.tick(output, 75_000_000));
+    assert!(!animations.tick(output, 150_000_000));
+    assert!(animations.window_rect(id, 150_000_000).is_none());
+}
+
+#[test]
+fn disabled() {
+    let animations = Animations::default();
+    animations.set_enabled(false);
+    let id = NodeId(1);
+    let output = OutputNodeId::from(NodeId(1));
+    animations.window_opened(id, output, 0);
+    assert_eq!(animations.window_opacity(id, 0), 1.0);
+    assert!(!animations.tick(output, 0));
+}
+
+#[test]
+fn tick_per_output() {
+    let animations = Animations::default();
+    let output1 = OutputNodeId::from(NodeId(1));
+    let output2 = OutputNodeId::from(NodeId(2));
+    animations.window_opened(NodeId(1), output1, 0);
+    assert!(animations.tick(output1, 0));
+    assert!(!animations.tick(output2, 0));
+}
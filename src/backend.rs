@@ -1,7 +1,9 @@
 use {
     crate::{
         async_engine::SpawnedFuture,
+        color_management::Primaries,
         drm_feedback::DrmFeedback,
+        edid::HdrStaticMetadata,
         fixed::Fixed,
         gfx_api::{GfxFramebuffer, SyncFile},
         ifs::wl_seat::{
@@ -52,6 +54,36 @@ pub trait Backend {
     fn supports_presentation_feedback(&self) -> bool {
         false
     }
+
+    /// Applies the changes to all connectors at once.
+    ///
+    /// If any of the changes cannot be applied, all connectors are restored to their
+    /// previous state and an error is returned.
+    ///
+    /// If `test_only` is set, nothing is changed. The metal backend checks that the
+    /// requested modes are supported by the connectors and performs a test-only atomic
+    /// commit of the resulting configuration. Other backends accept all changes.
+    fn apply_connector_changes(
+        self: Rc<Self>,
+        changes: &[ConnectorChange],
+        test_only: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        if !test_only {
+            for change in changes {
+                change.connector.set_enabled(change.enabled);
+                if let Some(mode) = change.mode {
+                    change.connector.set_mode(mode);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct ConnectorChange {
+    pub connector: Rc<dyn Connector>,
+    pub enabled: bool,
+    pub mode: Option<Mode>,
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -61,6 +93,98 @@ pub struct Mode {
     pub refresh_rate_millihz: u32,
 }
 
+/// The color temperature at which outputs are displayed unmodified.
+pub const DEFAULT_COLOR_TEMPERATURE: u32 = 6500;
+pub const MIN_COLOR_TEMPERATURE: u32 = 1000;
+pub const MAX_COLOR_TEMPERATURE: u32 = 25000;
+pub const MIN_BRIGHTNESS: f64 = 0.1;
+
+/// The luminance in cd/m² at which SDR white is displayed in HDR mode.
+pub const DEFAULT_SDR_REFERENCE_WHITE: f64 = 203.0;
+pub const MIN_SDR_REFERENCE_WHITE: f64 = 40.0;
+pub const MAX_SDR_REFERENCE_WHITE: f64 = 1000.0;
+
+/// A gamma ramp for each color channel. All ramps have the same length.
+#[derive(Debug, PartialEq)]
+pub struct GammaLut {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl GammaLut {
+    /// Returns the ramps linearly interpolated to `size` entries.
+    pub fn resample(&self, size: usize) -> Vec<[u16; 3]> {
+        let len = self.red.len();
+        if len == 0 {
+            return vec![];
+        }
+        let mut res = Vec::with_capacity(size);
+        for i in 0..size {
+            let pos = match size {
+                0 | 1 => 0.0,
+                _ => i as f64 * (len - 1) as f64 / (size - 1) as f64,
+            };
+            let lo = (pos as usize).min(len - 1);
+            let hi = (lo + 1).min(len - 1);
+            let t = pos - lo as f64;
+            let lerp = |ramp: &[u16]| {
+                let v = ramp[lo] as f64 * (1.0 - t) + ramp[hi] as f64 * t;
+                v.round() as u16
+            };
+            res.push([lerp(&self.red), lerp(&self.green), lerp(&self.blue)]);
+        }
+        res
+    }
+
+    /// Creates linear ramps that tint the output towards the white point of a black body
+    /// at `kelvin` and dim it by `brightness`.
+    pub fn color_transform(kelvin: u32, brightness: f64, size: usize) -> Self {
+        let white = black_body_white_point(kelvin);
+        let neutral = black_body_white_point(DEFAULT_COLOR_TEMPERATURE);
+        let ramp = |c: usize| {
+            let factor = (white[c] / neutral[c]).min(1.0) * brightness;
+            (0..size)
+                .map(|i| {
+                    let v = match size {
+                        0 | 1 => 1.0,
+                        _ => i as f64 / (size - 1) as f64,
+                    };
+                    (v * factor * u16::MAX as f64).round() as u16
+                })
+                .collect()
+        };
+        Self {
+            red: ramp(0),
+            green: ramp(1),
+            blue: ramp(2),
+        }
+    }
+}
+
+/// Approximates the RGB color of a black body at the given temperature.
+///
+/// See https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html
+fn black_body_white_point(kelvin: u32) -> [f64; 3] {
+    let t = kelvin as f64 / 100.0;
+    let (r, g) = if t <= 66.0 {
+        (255.0, 99.4708025861 * t.ln() - 161.1195681661)
+    } else {
+        (
+            329.698727446 * (t - 60.0).powf(-0.1332047592),
+            288.1221695283 * (t - 60.0).powf(-0.0755148492),
+        )
+    };
+    let b = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (t - 10.0).ln() - 305.0447927307
+    };
+    [r, g, b].map(|c| (c / 255.0).clamp(0.0, 1.0))
+}
+
 #[derive(Clone, Debug)]
 pub struct MonitorInfo {
     pub modes: Vec<Mode>,
@@ -72,6 +196,8 @@ pub struct MonitorInfo {
     pub height_mm: i32,
     pub non_desktop: bool,
     pub vrr_capable: bool,
+    pub primaries: Option<Primaries>,
+    pub hdr_metadata: Option<Box<HdrStaticMetadata>>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -115,6 +241,15 @@ pub trait Connector {
     fn set_tearing_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+    fn gamma_lut_size(&self) -> Option<u32> {
+        None
+    }
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        let _ = lut;
+    }
+    fn set_hdr_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
 }
 
 #[derive(Debug)]
@@ -127,6 +262,7 @@ pub enum ConnectorEvent {
     Unavailable,
     Available,
     VrrChanged(bool),
+    HdrChanged(bool),
 }
 
 pub trait HardwareCursor: Debug {
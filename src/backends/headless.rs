@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        backend::{Backend, BackendDrmDevice, BackendEvent, DrmDeviceId, DrmEvent, Mode},
+        gfx_api::{GfxContext, GfxError},
+        state::State,
+        utils::oserror::OsError,
+        video::drm::{Drm, DrmError, DrmVersion},
+        virtual_output::{self, VirtualOutput},
+    },
+    bstr::ByteSlice,
+    jay_config::video::GfxApi,
+    std::{any::Any, error::Error, future::pending, io, os::unix::ffi::OsStrExt, rc::Rc},
+    thiserror::Error,
+    uapi::{
+        c::{self, dev_t},
+        Errno,
+    },
+};
+
+const DEFAULT_MODE: Mode = Mode {
+    width: 1920,
+    height: 1080,
+    refresh_rate_millihz: 60_000,
+};
+
+#[derive(Debug, Error)]
+pub enum HeadlessBackendError {
+    #[error("Could not parse output mode `{0}`")]
+    InvalidMode(String),
+    #[error("Could not read /dev/dri")]
+    ReadDri(#[source] io::Error),
+    #[error("There are no render nodes in /dev/dri")]
+    NoDrmNode,
+    #[error("Could not open drm node {0}")]
+    OpenDrmNode(String, #[source] OsError),
+    #[error("Could not fstat the drm device")]
+    DrmDeviceFstat(#[source] Errno),
+    #[error("Could not create a graphics API context")]
+    CreateContext(#[source] GfxError),
+}
+
+pub async fn create(state: &Rc<State>) -> Result<Rc<HeadlessBackend>, HeadlessBackendError> {
+    let modes = parse_outputs(&state.run_args.headless_outputs)?;
+    let drm = open_drm_node()?;
+    let drm_dev = match uapi::fstat(drm.raw()) {
+        Ok(s) => s.st_rdev,
+        Err(e) => return Err(HeadlessBackendError::DrmDeviceFstat(e)),
+    };
+    let ctx = match state.create_gfx_context(&drm, None) {
+        Ok(r) => r,
+        Err(e) => return Err(HeadlessBackendError::CreateContext(e)),
+    };
+    let drm_device_id = state.drm_dev_ids.next();
+    let outputs = modes
+        .into_iter()
+        .map(|mode| VirtualOutput::new(state, mode, Some(drm_device_id)))
+        .collect();
+    let backend = Rc::new(HeadlessBackend {
+        state: state.clone(),
+        ctx,
+        outputs,
+        drm_device_id,
+        drm_dev,
+    });
+    Ok(backend)
+}
+
+fn parse_outputs(outputs: &[String]) -> Result<Vec<Mode>, HeadlessBackendError> {
+    let mut modes = vec![];
+    for mode in outputs {
+        match virtual_output::parse_mode(mode) {
+            Some(m) => modes.push(m),
+            _ => return Err(HeadlessBackendError::InvalidMode(mode.clone())),
+        }
+    }
+    if modes.is_empty() {
+        modes.push(DEFAULT_MODE);
+    }
+    Ok(modes)
+}
+
+fn open_drm_node() -> Result<Drm, HeadlessBackendError> {
+    let dri = match std::fs::read_dir("/dev/dri") {
+        Ok(d) => d,
+        Err(e) => return Err(HeadlessBackendError::ReadDri(e)),
+    };
+    let mut files = vec![];
+    for f in dri {
+        match f {
+            Ok(f) => files.push(f.path()),
+            Err(e) => return Err(HeadlessBackendError::ReadDri(e)),
+        }
+    }
+    files.sort();
+    // Only render nodes are used. Opening a primary node could make the compositor the
+    // DRM master of a GPU that is driving real displays.
+    let node = 'node: {
+        for f in &files {
+            if let Some(file) = f.file_name() {
+                if file.as_bytes().starts_with_str("renderD") {
+                    break 'node f;
+                }
+            }
+        }
+        return Err(HeadlessBackendError::NoDrmNode);
+    };
+    log::info!("Using drm node {}", node.display());
+    match uapi::open(node.as_path(), c::O_RDWR | c::O_CLOEXEC, 0) {
+        Ok(f) => Ok(Drm::open_existing(Rc::new(f))),
+        Err(e) => Err(HeadlessBackendError::OpenDrmNode(
+            node.as_os_str().as_bytes().as_bstr().to_string(),
+            e.into(),
+        )),
+    }
+}
+
+pub struct HeadlessBackend {
+    state: Rc<State>,
+    ctx: Rc<dyn GfxContext>,
+    outputs: Vec<Rc<VirtualOutput>>,
+    drm_device_id: DrmDeviceId,
+    drm_dev: dev_t,
+}
+
+impl Backend for HeadlessBackend {
+    fn run(self: Rc<Self>) -> SpawnedFuture<Result<(), Box<dyn Error>>> {
+        let slf = self.clone();
+        self.state.eng.spawn(async move {
+            slf.run().await;
+            Ok(())
+        })
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
+impl HeadlessBackend {
+    async fn run(self: Rc<Self>) {
+        self.state.set_render_ctx(Some(self.ctx.clone()));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewDrmDevice(Rc::new(HeadlessDrmDevice {
+                backend: self.clone(),
+            })));
+        for output in &self.outputs {
+            output.connect();
+        }
+        self.state
+            .backend_events
+            .push(BackendEvent::DevicesEnumerated);
+        pending().await
+    }
+}
+
+struct HeadlessDrmDevice {
+    backend: Rc<HeadlessBackend>,
+}
+
+impl BackendDrmDevice for HeadlessDrmDevice {
+    fn id(&self) -> DrmDeviceId {
+        self.backend.drm_device_id
+    }
+
+    fn event(&self) -> Option<DrmEvent> {
+        None
+    }
+
+    fn on_change(&self, _cb: Rc<dyn Fn()>) {
+        // nothing
+    }
+
+    fn dev_t(&self) -> dev_t {
+        self.backend.drm_dev
+    }
+
+    fn make_render_device(&self) {
+        log::warn!("make_render_device is not supported by the headless backend");
+    }
+
+    fn set_gfx_api(&self, _api: GfxApi) {
+        log::warn!("set_gfx_api is not supported by the headless backend");
+    }
+
+    fn gtx_api(&self) -> GfxApi {
+        self.backend.ctx.gfx_api()
+    }
+
+    fn version(&self) -> Result<DrmVersion, DrmError> {
+        self.backend.ctx.gbm().drm.version()
+    }
+
+    fn set_direct_scanout_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn is_render_device(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,62 @@
+use {
+    crate::{
+        backend::Mode,
+        backends::headless::{parse_outputs, HeadlessBackendError, DEFAULT_MODE},
+        cli::{CliBackend, RunArgs},
+    },
+    clap::Parser,
+};
+
+#[derive(Parser)]
+struct Cli {
+    #[clap(flatten)]
+    run: RunArgs,
+}
+
+fn run_args(args: &[&str]) -> RunArgs {
+    Cli::try_parse_from(["jay"].iter().chain(args)).unwrap().run
+}
+
+#[test]
+fn headless_outputs() {
+    let args = run_args(&[
+        "--backends",
+        "headless",
+        "--headless-outputs",
+        "2560x1440@144,1280x720",
+    ]);
+    assert_eq!(args.backends, [CliBackend::Headless]);
+    let modes = parse_outputs(&args.headless_outputs).unwrap();
+    assert_eq!(
+        modes,
+        [
+            Mode {
+                width: 2560,
+                height: 1440,
+                refresh_rate_millihz: 144_000,
+            },
+            Mode {
+                width: 1280,
+                height: 720,
+                refresh_rate_millihz: 60_000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn default_output() {
+    let args = run_args(&["--backends", "headless"]);
+    assert_eq!(
+        parse_outputs(&args.headless_outputs).unwrap(),
+        [DEFAULT_MODE]
+    );
+}
+
+#[test]
+fn invalid_output() {
+    for mode in ["1920", "1920x", "0x1080", "1920x1080@0", "1920x1080@abc"] {
+        let res = parse_outputs(&[mode.to_string()]);
+        assert!(matches!(res, Err(HeadlessBackendError::InvalidMode(m)) if m == mode));
+    }
+}
@@ -6,8 +6,9 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, TransformMatrix,
+            Backend, ConnectorChange, ConnectorId, InputDevice, InputDeviceAccelProfile,
+            InputDeviceCapability, InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, Mode,
+            TransformMatrix,
         },
         backends::metal::video::{
             MetalDrmDeviceData, MetalLeaseData, MetalRenderContext, PendingDrmDevice,
@@ -129,6 +130,14 @@ pub enum MetalError {
     Commit(#[source] DrmError),
     #[error("Could not clear framebuffer")]
     Clear(#[source] GfxError),
+    #[error("Connector {0} does not exist")]
+    UnknownConnector(ConnectorId),
+    #[error("Cannot change the mode of a connector that is not connected")]
+    ModeOfInactiveConnector,
+    #[error("Connector does not support mode {0:?}")]
+    UnsupportedMode(Mode),
+    #[error("Could not upload the gamma LUT")]
+    GammaLutTexture(#[source] GfxError),
 }
 
 pub struct MetalBackend {
@@ -224,6 +233,7 @@ impl Backend for MetalBackend {
     }
 
     fn switch_to(&self, vtnr: u32) {
+        self.restore_original_gamma_luts();
         self.session.switch_to(vtnr, move |res| {
             if let Err(e) = res {
                 log::error!("Could not switch to VT {}: {}", vtnr, ErrorFmt(e));
@@ -268,6 +278,15 @@ impl Backend for MetalBackend {
     fn supports_presentation_feedback(&self) -> bool {
         true
     }
+
+    fn apply_connector_changes(
+        self: Rc<Self>,
+        changes: &[ConnectorChange],
+        test_only: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        MetalBackend::apply_connector_changes(&self, changes, test_only)?;
+        Ok(())
+    }
 }
 
 fn dup_fd(fd: c::c_int) -> Result<Rc<OwnedFd>, MetalError> {
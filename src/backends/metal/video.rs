@@ -3,18 +3,20 @@ use {
         async_engine::{Phase, SpawnedFuture},
         backend::{
             BackendDrmDevice, BackendDrmLease, BackendDrmLessee, BackendEvent, Connector,
-            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, HardwareCursor, Mode,
-            MonitorInfo,
+            ConnectorChange, ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, GammaLut,
+            HardwareCursor, Mode, MonitorInfo,
         },
         backends::metal::{MetalBackend, MetalError},
+        color_management::Primaries,
         drm_feedback::DrmFeedback,
-        edid::Descriptor,
-        format::{Format, ARGB8888, XRGB8888},
+        edid::{Descriptor, HdrStaticMetadata},
+        format::{Format, ARGB8888, XBGR16161616F, XBGR2101010, XRGB2101010, XRGB8888},
         gfx_api::{
             AcquireSync, BufferResv, GfxApiOpt, GfxContext, GfxFramebuffer, GfxRenderPass,
             GfxTexture, ReleaseSync, SyncFile,
         },
         ifs::wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC},
+        output_mirror::OutputMirror,
         renderer::RenderResult,
         state::State,
         theme::Color,
@@ -23,18 +25,19 @@ use {
         utils::{
             asyncevent::AsyncEvent, bitflags::BitflagsExt, cell_ext::CellExt, clonecell::CloneCell,
             copyhashmap::CopyHashMap, debug_fn::debug_fn, errorfmt::ErrorFmt, numcell::NumCell,
-            on_change::OnChange, opaque_cell::OpaqueCell, oserror::OsError,
+            on_change::OnChange, opaque_cell::OpaqueCell, oserror::OsError, rc_eq::rc_eq,
             transform_ext::TransformExt,
         },
         video::{
             dmabuf::DmaBufId,
             drm::{
-                drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
-                DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease, DrmMaster,
-                DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
-                DrmPropertyType, DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC,
-                DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
-                DRM_MODE_PAGE_FLIP_EVENT,
+                drm_color_lut, drm_mode_modeinfo, hdr_metadata_infoframe, hdr_output_metadata,
+                Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector, DrmCrtc, DrmEncoder,
+                DrmError, DrmEvent, DrmFramebuffer, DrmLease, DrmMaster, DrmModeInfo, DrmObject,
+                DrmPlane, DrmProperty, DrmPropertyDefinition, DrmPropertyType, DrmVersion,
+                PropBlob, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
+                DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT,
+                HDMI_EOTF_SMPTE_ST2084, HDMI_STATIC_METADATA_TYPE1,
             },
             gbm::{GbmBo, GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING, GBM_BO_USE_SCANOUT},
             Modifier, INVALID_MODIFIER,
@@ -304,10 +307,15 @@ pub struct ConnectorDisplayData {
     pub non_desktop_effective: bool,
     pub vrr_capable: bool,
     pub vrr_requested: bool,
+    pub hdr_output_metadata: Option<MutableProperty<DrmBlob>>,
+    pub colorspace: Option<ConnectorColorspace>,
+    pub hdr_requested: bool,
 
     pub monitor_manufacturer: String,
     pub monitor_name: String,
     pub monitor_serial_number: String,
+    pub monitor_primaries: Option<Primaries>,
+    pub monitor_hdr_metadata: Option<HdrStaticMetadata>,
 
     pub connection: ConnectorStatus,
     pub mm_width: u32,
@@ -328,6 +336,60 @@ impl ConnectorDisplayData {
     fn should_enable_vrr(&self) -> bool {
         self.vrr_requested && self.vrr_capable
     }
+
+    fn hdr_capable(&self) -> bool {
+        self.hdr_output_metadata.is_some()
+            && self.colorspace.is_some()
+            && self.monitor_hdr_metadata.is_some_and(|m| m.eotf_st2084)
+    }
+
+    fn should_enable_hdr(&self) -> bool {
+        self.hdr_requested && self.hdr_capable()
+    }
+
+    fn hdr_enabled(&self) -> bool {
+        self.hdr_output_metadata
+            .as_ref()
+            .is_some_and(|p| p.value.get().is_some())
+    }
+
+    fn hdr_output_metadata(&self) -> hdr_output_metadata {
+        let mut infoframe = hdr_metadata_infoframe {
+            eotf: HDMI_EOTF_SMPTE_ST2084,
+            metadata_type: HDMI_STATIC_METADATA_TYPE1,
+            ..Default::default()
+        };
+        if let Some(p) = &self.monitor_primaries {
+            let c =
+                |(x, y): (f64, f64)| [(x * 50000.0).round() as u16, (y * 50000.0).round() as u16];
+            infoframe.display_primaries = [c(p.r), c(p.g), c(p.b)];
+            infoframe.white_point = c(p.wp);
+        }
+        if let Some(m) = &self.monitor_hdr_metadata {
+            if let Some(max) = m.max_luminance {
+                infoframe.max_display_mastering_luminance = max.round() as u16;
+                infoframe.max_cll = max.round() as u16;
+            }
+            if let Some(min) = m.min_luminance {
+                infoframe.min_display_mastering_luminance = (min * 10000.0).round() as u16;
+            }
+            if let Some(max_fall) = m.max_frame_average_luminance {
+                infoframe.max_fall = max_fall.round() as u16;
+            }
+        }
+        hdr_output_metadata {
+            metadata_type: HDMI_STATIC_METADATA_TYPE1 as u32,
+            hdmi_metadata_type1: infoframe,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConnectorColorspace {
+    pub prop: MutableProperty<u64>,
+    pub default: u64,
+    pub bt2020_rgb: u64,
 }
 
 linear_ids!(MetalLeaseIds, MetalLeaseId, u64);
@@ -460,6 +522,27 @@ pub struct MetalConnector {
     pub direct_scanout_active: Cell<bool>,
 
     pub tearing_requested: Cell<bool>,
+
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    /// Whether `gamma_lut` must be applied to the CRTC with the next commit.
+    pub gamma_lut_changed: Cell<bool>,
+    pub gamma_lut_fallback: CloneCell<Option<Rc<GammaLutFallback>>>,
+
+    pub hdr_output_metadata_blob: CloneCell<Option<Rc<PropBlob>>>,
+}
+
+/// The size of gamma LUTs that are applied in the renderer.
+const FALLBACK_GAMMA_LUT_SIZE: u32 = 256;
+
+/// Resources to apply a gamma LUT in the renderer if the CRTC has no gamma LUT.
+pub struct GammaLutFallback {
+    lut: Rc<GammaLut>,
+    ctx: Rc<dyn GfxContext>,
+    format: &'static Format,
+    lut_tex: Rc<dyn GfxTexture>,
+    _bo: GbmBo,
+    fb: Rc<dyn GfxFramebuffer>,
+    tex: Rc<dyn GfxTexture>,
 }
 
 impl Debug for MetalConnector {
@@ -641,6 +724,18 @@ impl MetalConnector {
         }
     }
 
+    fn send_hdr_enabled(&self) {
+        match self.frontend_state.get() {
+            FrontState::Removed
+            | FrontState::Disconnected
+            | FrontState::Unavailable
+            | FrontState::Connected { non_desktop: true } => return,
+            FrontState::Connected { non_desktop: false } => {}
+        }
+        let enabled = self.display.borrow().hdr_enabled();
+        self.send_event(ConnectorEvent::HdrChanged(enabled));
+    }
+
     fn send_hardware_cursor(self: &Rc<Self>) {
         match self.frontend_state.get() {
             FrontState::Removed
@@ -707,6 +802,10 @@ impl MetalConnector {
                 // Direct scanout with alpha factor is not supported.
                 return None;
             }
+            if ct.color_transform.is_some() {
+                // Direct scanout of buffers that require color conversion is not supported.
+                return None;
+            }
             if !ct.tex.format().has_alpha && ct.target.is_covering() {
                 // Texture covers the entire screen and is opaque.
                 break 'ct ct;
@@ -852,6 +951,141 @@ impl MetalConnector {
             .unwrap_or(self.state.direct_scanout_enabled.get())
     }
 
+    fn gamma_lut_blob(&self, crtc: &MetalCrtc) -> Result<Option<Rc<PropBlob>>, DrmError> {
+        let Some(lut) = self.gamma_lut.get() else {
+            return Ok(crtc.original_gamma_lut.clone());
+        };
+        let entries: Vec<_> = lut
+            .resample(crtc.gamma_lut_size as usize)
+            .into_iter()
+            .map(|[red, green, blue]| drm_color_lut {
+                red,
+                green,
+                blue,
+                reserved: 0,
+            })
+            .collect();
+        let blob = self.master.create_blob(&entries[..])?;
+        Ok(Some(Rc::new(blob)))
+    }
+
+    fn commit_gamma_lut(&self, crtc: &MetalCrtc) {
+        self.gamma_lut_changed.set(false);
+        let Some(gamma_lut) = &crtc.gamma_lut else {
+            return;
+        };
+        if self.dev.paused.get() {
+            return;
+        }
+        let blob = match self.gamma_lut_blob(crtc) {
+            Ok(b) => b,
+            Err(e) => {
+                log::error!("Could not create gamma LUT blob: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let id = match &blob {
+            Some(b) => b.id(),
+            None => DrmBlob::NONE,
+        };
+        let mut change = self.master.change();
+        change.change_object(crtc.id, |c| {
+            c.change(gamma_lut.id, id.0 as _);
+        });
+        if let Err(e) = change.commit(0, 0) {
+            log::error!("Could not change gamma LUT: {}", ErrorFmt(e));
+            return;
+        }
+        gamma_lut.value.set(id);
+        crtc.gamma_lut_blob.set(blob);
+    }
+
+    fn gamma_lut_fallback(
+        &self,
+        buffer_fb: &Rc<dyn GfxFramebuffer>,
+    ) -> Result<Option<Rc<GammaLutFallback>>, MetalError> {
+        let lut = match self.crtc.get() {
+            Some(crtc) if crtc.gamma_lut.is_none() => self.gamma_lut.get(),
+            _ => None,
+        };
+        let (Some(lut), Some(ctx)) = (lut, self.backend.ctx.get()) else {
+            self.gamma_lut_fallback.take();
+            return Ok(None);
+        };
+        let (width, height) = buffer_fb.physical_size();
+        let format = buffer_fb.format();
+        if let Some(fallback) = self.gamma_lut_fallback.get() {
+            if rc_eq(&fallback.lut, &lut)
+                && rc_eq(&fallback.ctx, &ctx.gfx)
+                && fallback.format == format
+                && fallback.fb.physical_size() == (width, height)
+            {
+                return Ok(Some(fallback));
+            }
+        }
+        self.gamma_lut_fallback.take();
+        let mut data = vec![];
+        for [r, g, b] in lut.resample(FALLBACK_GAMMA_LUT_SIZE as usize) {
+            data.extend_from_slice(&[(b >> 8) as u8, (g >> 8) as u8, (r >> 8) as u8, 255]);
+        }
+        let data: Vec<_> = data.into_iter().map(Cell::new).collect();
+        let lut_tex = ctx
+            .gfx
+            .clone()
+            .shmem_texture(
+                None,
+                &data,
+                XRGB8888,
+                FALLBACK_GAMMA_LUT_SIZE as i32,
+                1,
+                FALLBACK_GAMMA_LUT_SIZE as i32 * 4,
+                None,
+            )
+            .map_err(MetalError::GammaLutTexture)?;
+        let formats = ctx.gfx.formats();
+        let Some(gfx_format) = formats.get(&format.drm) else {
+            return Err(MetalError::MissingRenderFormat(format.name));
+        };
+        let modifiers: Vec<_> = gfx_format
+            .write_modifiers
+            .intersection(&gfx_format.read_modifiers)
+            .copied()
+            .collect();
+        if modifiers.is_empty() {
+            return Err(MetalError::MissingRenderModifier(format.name));
+        }
+        let bo = ctx
+            .gfx
+            .gbm()
+            .create_bo(
+                &self.state.dma_buf_ids,
+                width,
+                height,
+                format,
+                &modifiers,
+                GBM_BO_USE_RENDERING,
+            )
+            .map_err(MetalError::ScanoutBuffer)?;
+        let img = ctx
+            .gfx
+            .clone()
+            .dmabuf_img(bo.dmabuf())
+            .map_err(MetalError::ImportImage)?;
+        let fb = img.clone().to_framebuffer().map_err(MetalError::ImportFb)?;
+        let tex = img.to_texture().map_err(MetalError::ImportTexture)?;
+        let fallback = Rc::new(GammaLutFallback {
+            lut,
+            ctx: ctx.gfx.clone(),
+            format,
+            lut_tex,
+            _bo: bo,
+            fb,
+            tex,
+        });
+        self.gamma_lut_fallback.set(Some(fallback.clone()));
+        Ok(Some(fallback))
+    }
+
     fn prepare_present_fb(
         &self,
         rr: &mut RenderResult,
@@ -862,8 +1096,13 @@ impl MetalConnector {
     ) -> Result<PresentFb, MetalError> {
         self.trim_scanout_cache();
         let buffer_fb = buffer.render_fb();
+        let gamma_lut_fallback = self.gamma_lut_fallback(&buffer_fb)?;
+        let render_fb = match &gamma_lut_fallback {
+            Some(f) => f.fb.clone(),
+            None => buffer_fb.clone(),
+        };
         let render_hw_cursor = !self.cursor_enabled.get();
-        let pass = buffer_fb.create_render_pass(
+        let pass = render_fb.create_render_pass(
             output,
             &self.state,
             Some(output.global.pos.get()),
@@ -873,9 +1112,11 @@ impl MetalConnector {
             render_hw_cursor,
             output.has_fullscreen(),
             output.global.persistent.transform.get(),
+            &output.color_description.get(),
             Some(&self.state.damage_visualizer),
         );
         let try_direct_scanout = try_direct_scanout
+            && gamma_lut_fallback.is_none()
             && self.direct_scanout_enabled()
             // at least on AMD, using a FB on a different device for rendering will fail
             // and destroy the render context. it's possible to work around this by waiting
@@ -900,9 +1141,19 @@ impl MetalConnector {
         let tex;
         match &direct_scanout_data {
             None => {
-                let sf = buffer_fb
+                let mut sf = render_fb
                     .perform_render_pass(pass)
                     .map_err(MetalError::RenderFrame)?;
+                if let Some(f) = &gamma_lut_fallback {
+                    sf = buffer_fb
+                        .copy_texture_with_gamma_lut(
+                            &f.tex,
+                            &f.lut_tex,
+                            AcquireSync::from_sync_file(sf),
+                            ReleaseSync::Implicit,
+                        )
+                        .map_err(MetalError::RenderFrame)?;
+                }
                 sync_file = buffer.copy_to_dev(sf)?;
                 fb = buffer.drm.clone();
                 tex = buffer.render_tex.clone();
@@ -926,6 +1177,23 @@ impl MetalConnector {
         })
     }
 
+    fn prepare_mirror_fb(
+        &self,
+        buffer: &RenderBuffer,
+        mirror: &OutputMirror,
+    ) -> Result<PresentFb, MetalError> {
+        let sf = mirror
+            .present(&buffer.render_fb())
+            .map_err(MetalError::RenderFrame)?;
+        let sync_file = buffer.copy_to_dev(sf)?;
+        Ok(PresentFb {
+            fb: buffer.drm.clone(),
+            tex: buffer.render_tex.clone(),
+            direct_scanout_data: None,
+            sync_file,
+        })
+    }
+
     pub fn present(&self, try_direct_scanout: bool) -> Result<(), MetalError> {
         let crtc = match self.crtc.get() {
             Some(crtc) => crtc,
@@ -945,8 +1213,13 @@ impl MetalConnector {
             Some(b) => b,
             _ => return Ok(()),
         };
-        let Some(node) = self.state.root.outputs.get(&self.connector_id) else {
-            return Ok(());
+        let node = self.state.root.outputs.get(&self.connector_id);
+        let mirror = match node {
+            Some(_) => None,
+            None => match self.state.mirrors.get(&self.connector_id) {
+                Some(m) => Some(m),
+                None => return Ok(()),
+            },
         };
         let cursor = self.cursor_plane.get();
         let mut new_fb = None;
@@ -966,10 +1239,18 @@ impl MetalConnector {
                 return Ok(());
             }
             let buffer = &buffers[self.next_buffer.get() % buffers.len()];
-            let mut rr = self.render_result.borrow_mut();
-            rr.output_id = node.id;
-            let fb = self.prepare_present_fb(&mut rr, buffer, &plane, &node, try_direct_scanout)?;
-            rr.dispatch_frame_requests(self.state.now_msec());
+            let fb = match (&node, &mirror) {
+                (Some(node), _) => {
+                    let mut rr = self.render_result.borrow_mut();
+                    rr.output_id = node.id;
+                    let fb =
+                        self.prepare_present_fb(&mut rr, buffer, &plane, node, try_direct_scanout)?;
+                    rr.dispatch_frame_requests(self.state.now_msec());
+                    fb
+                }
+                (_, Some(mirror)) => self.prepare_mirror_fb(buffer, mirror)?,
+                _ => unreachable!(),
+            };
             let (crtc_x, crtc_y, crtc_w, crtc_h, src_width, src_height) =
                 match &fb.direct_scanout_data {
                     None => {
@@ -1013,6 +1294,28 @@ impl MetalConnector {
                 }
             }
         }
+        let mut new_gamma_lut = None;
+        if self.gamma_lut_changed.get() {
+            if let Some(gamma_lut) = &crtc.gamma_lut {
+                match self.gamma_lut_blob(&crtc) {
+                    Ok(blob) => {
+                        let id = match &blob {
+                            Some(b) => b.id(),
+                            None => DrmBlob::NONE,
+                        };
+                        changes.change_object(crtc.id, |c| {
+                            c.change(gamma_lut.id, id.0 as _);
+                        });
+                        try_async_flip = false;
+                        new_gamma_lut = Some((id, blob));
+                    }
+                    Err(e) => {
+                        log::error!("Could not create gamma LUT blob: {}", ErrorFmt(e));
+                        self.gamma_lut_changed.set(false);
+                    }
+                }
+            }
+        }
         let mut cursor_swap_buffer = false;
         let mut cursor_sync_file = None;
         if self.cursor_changed.get() && cursor.is_some() {
@@ -1108,8 +1411,17 @@ impl MetalConnector {
             apply_change!(plane.crtc_y);
             apply_change!(plane.crtc_w);
             apply_change!(plane.crtc_h);
-            node.schedule.presented();
-            self.perform_screencopies(&new_fb, &node);
+            if let Some((id, blob)) = new_gamma_lut {
+                if let Some(gamma_lut) = &crtc.gamma_lut {
+                    gamma_lut.value.set(id);
+                }
+                crtc.gamma_lut_blob.set(blob);
+                self.gamma_lut_changed.set(false);
+            }
+            if let Some(node) = &node {
+                node.schedule.presented();
+                self.perform_screencopies(&new_fb, node);
+            }
             if let Some(fb) = new_fb {
                 if fb.direct_scanout_data.is_none() {
                     self.next_buffer.fetch_add(1);
@@ -1264,6 +1576,17 @@ impl MetalConnector {
                     log::error!("Tried to send vrr-changed event in invalid state: {state:?}");
                 }
             },
+            ConnectorEvent::HdrChanged(_) => match state {
+                FrontState::Connected { non_desktop: false } => {
+                    self.on_change.send_event(event);
+                }
+                FrontState::Connected { non_desktop: true }
+                | FrontState::Removed
+                | FrontState::Disconnected
+                | FrontState::Unavailable => {
+                    log::error!("Tried to send hdr-changed event in invalid state: {state:?}");
+                }
+            },
         }
     }
 }
@@ -1426,6 +1749,55 @@ impl Connector for MetalConnector {
             log::debug!("{msg} tearing on output {}", self.kernel_id());
         }
     }
+
+    fn gamma_lut_size(&self) -> Option<u32> {
+        let crtc = self.crtc.get()?;
+        match crtc.gamma_lut.is_some() {
+            true => Some(crtc.gamma_lut_size),
+            false => Some(FALLBACK_GAMMA_LUT_SIZE),
+        }
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+        let Some(crtc) = self.crtc.get() else {
+            return;
+        };
+        if crtc.gamma_lut.is_some() {
+            self.gamma_lut_changed.set(true);
+            if !crtc.active.value.get() {
+                // There is no page flip that could apply the LUT.
+                self.commit_gamma_lut(&crtc);
+                return;
+            }
+        }
+        self.damage();
+    }
+
+    fn set_hdr_enabled(&self, enabled: bool) {
+        if self.frontend_state.get() != (FrontState::Connected { non_desktop: false }) {
+            return;
+        }
+        let mut dd = self.display.borrow_mut();
+        let old_enabled = dd.should_enable_hdr();
+        let old_requested = mem::replace(&mut dd.hdr_requested, enabled);
+        if old_enabled == dd.should_enable_hdr() {
+            return;
+        }
+        drop(dd);
+        let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) else {
+            return;
+        };
+        if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+            log::error!("Could not change HDR mode: {}", ErrorFmt(e));
+            self.display.borrow_mut().hdr_requested = old_requested;
+            if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+                dev.unprocessed_change.set(true);
+                log::error!("Could not restore the previous HDR mode: {}", ErrorFmt(e));
+            }
+        }
+        self.send_hdr_enabled();
+    }
 }
 
 pub struct MetalCrtc {
@@ -1443,8 +1815,22 @@ pub struct MetalCrtc {
     pub mode_id: MutableProperty<DrmBlob>,
     pub out_fence_ptr: DrmProperty,
     pub vrr_enabled: MutableProperty<bool>,
+    pub gamma_lut: Option<MutableProperty<DrmBlob>>,
+    pub gamma_lut_size: u32,
 
     pub mode_blob: CloneCell<Option<Rc<PropBlob>>>,
+    pub gamma_lut_blob: CloneCell<Option<Rc<PropBlob>>>,
+    /// A copy of the gamma LUT that was active before the compositor took over.
+    pub original_gamma_lut: Option<Rc<PropBlob>>,
+}
+
+impl MetalCrtc {
+    fn original_gamma_lut_id(&self) -> DrmBlob {
+        match &self.original_gamma_lut {
+            Some(b) => b.id(),
+            None => DrmBlob::NONE,
+        }
+    }
 }
 
 impl Debug for MetalCrtc {
@@ -1577,6 +1963,10 @@ fn create_connector(
         direct_scanout_active: Cell::new(false),
         next_flip_nsec: Cell::new(0),
         tearing_requested: Cell::new(false),
+        gamma_lut: Default::default(),
+        gamma_lut_changed: Cell::new(false),
+        gamma_lut_fallback: Default::default(),
+        hdr_output_metadata_blob: Default::default(),
     });
     let futures = ConnectorFutures {
         _present: backend
@@ -1606,6 +1996,8 @@ fn create_connector_display_data(
     let mut name = String::new();
     let mut manufacturer = String::new();
     let mut serial_number = String::new();
+    let mut primaries = None;
+    let mut hdr_metadata = None;
     let mode = info.modes.first().cloned();
     let refresh = mode
         .as_ref()
@@ -1650,6 +2042,8 @@ fn create_connector_display_data(
             }
         };
         manufacturer = edid.base_block.id_manufacturer_name.to_string();
+        primaries = edid.base_block.chromaticity_coordinates.primaries();
+        hdr_metadata = edid.hdr_static_metadata();
         for descriptor in edid.base_block.descriptors.iter().flatten() {
             match descriptor {
                 Descriptor::DisplayProductSerialNumber(s) => {
@@ -1682,6 +2076,26 @@ fn create_connector_display_data(
         Ok(c) => c.value.get() == 1,
         Err(_) => false,
     };
+    let hdr_output_metadata = props
+        .get("HDR_OUTPUT_METADATA")
+        .ok()
+        .map(|p| p.map(|v| DrmBlob(v as _)));
+    let colorspace = 'colorspace: {
+        let Ok(prop) = props.get("Colorspace") else {
+            break 'colorspace None;
+        };
+        let (Some(default), Some(bt2020_rgb)) = (
+            props.get_enum_value("Colorspace", "Default"),
+            props.get_enum_value("Colorspace", "BT2020_RGB"),
+        ) else {
+            break 'colorspace None;
+        };
+        Some(ConnectorColorspace {
+            prop,
+            default,
+            bt2020_rgb,
+        })
+    };
     Ok(ConnectorDisplayData {
         crtc_id: props.get("CRTC_ID")?.map(|v| DrmCrtc(v as _)),
         crtcs,
@@ -1692,9 +2106,14 @@ fn create_connector_display_data(
         non_desktop_effective: non_desktop_override.unwrap_or(non_desktop),
         vrr_capable,
         vrr_requested: false,
+        hdr_output_metadata,
+        colorspace,
+        hdr_requested: false,
         monitor_manufacturer: manufacturer,
         monitor_name: name,
         monitor_serial_number: serial_number,
+        monitor_primaries: primaries,
+        monitor_hdr_metadata: hdr_metadata,
         connection,
         mm_width: info.mm_width,
         mm_height: info.mm_height,
@@ -1736,6 +2155,26 @@ fn create_crtc(
         }
     }
     let props = collect_properties(master, crtc)?;
+    let mut gamma_lut = None;
+    let mut gamma_lut_size = 0;
+    let mut original_gamma_lut = None;
+    if let (Ok(lut), Ok(size)) = (props.get("GAMMA_LUT"), props.get("GAMMA_LUT_SIZE")) {
+        let lut = lut.map(|v| DrmBlob(v as u32));
+        let original = lut.value.get();
+        if original.is_some() {
+            let copy = master
+                .getblob_vec::<drm_color_lut>(original)
+                .and_then(|entries| master.create_blob(&entries[..]));
+            match copy {
+                Ok(b) => original_gamma_lut = Some(Rc::new(b)),
+                Err(e) => log::warn!("Could not copy the original gamma LUT: {}", ErrorFmt(e)),
+            }
+        }
+        if size.value.get() > 0 {
+            gamma_lut = Some(lut);
+            gamma_lut_size = size.value.get() as u32;
+        }
+    }
     Ok(MetalCrtc {
         id: crtc,
         idx,
@@ -1747,7 +2186,11 @@ fn create_crtc(
         mode_id: props.get("MODE_ID")?.map(|v| DrmBlob(v as u32)),
         out_fence_ptr: props.get("OUT_FENCE_PTR")?.id,
         vrr_enabled: props.get("VRR_ENABLED")?.map(|v| v == 1),
+        gamma_lut,
+        gamma_lut_size,
         mode_blob: Default::default(),
+        gamma_lut_blob: Default::default(),
+        original_gamma_lut,
     })
 }
 
@@ -1868,6 +2311,17 @@ impl CollectedProperties {
             _ => Err(DrmError::MissingProperty(name.to_string().into_boxed_str())),
         }
     }
+
+    fn get_enum_value(&self, name: &str, value: &str) -> Option<u64> {
+        let (def, _) = self.props.get(name.as_bytes().as_bstr())?;
+        let DrmPropertyType::Enum { values, .. } = &def.ty else {
+            return None;
+        };
+        values
+            .iter()
+            .find(|v| v.name.as_bytes() == value.as_bytes())
+            .map(|v| v.value)
+    }
 }
 
 #[derive(Debug)]
@@ -2020,6 +2474,7 @@ impl MetalBackend {
                     }
                 }
                 dd.vrr_requested = old.vrr_requested;
+                dd.hdr_requested = old.hdr_requested;
             }
             mem::swap(old.deref_mut(), &mut dd);
             match c.frontend_state.get() {
@@ -2085,6 +2540,247 @@ impl MetalBackend {
         Ok(())
     }
 
+    /// Restores the gamma LUTs that were active before the compositor took over.
+    ///
+    /// This must happen before switching to another VT since the DRM master is lost
+    /// afterwards. The gamma LUTs of the connectors are re-applied when the devices are
+    /// resumed.
+    pub fn restore_original_gamma_luts(&self) {
+        for dev in self.device_holder.drm_devices.lock().values() {
+            let mut change = dev.dev.master.change();
+            for crtc in dev.dev.crtcs.values() {
+                let Some(gamma_lut) = &crtc.gamma_lut else {
+                    continue;
+                };
+                let original = crtc.original_gamma_lut_id();
+                if gamma_lut.value.replace(original) != original {
+                    change.change_object(crtc.id, |c| {
+                        c.change(gamma_lut.id, original.0 as _);
+                    });
+                }
+            }
+            if let Err(e) = change.commit(0, 0) {
+                log::error!("Could not restore the original gamma LUTs: {}", ErrorFmt(e));
+            }
+        }
+    }
+
+    pub fn apply_connector_changes(
+        self: &Rc<Self>,
+        changes: &[ConnectorChange],
+        test_only: bool,
+    ) -> Result<(), MetalError> {
+        let mut connectors = vec![];
+        for change in changes {
+            let id = change.connector.id();
+            let connector = self
+                .device_holder
+                .drm_devices
+                .lock()
+                .values()
+                .find_map(|dev| {
+                    dev.connectors
+                        .lock()
+                        .values()
+                        .find(|c| c.connector_id == id)
+                        .cloned()
+                });
+            let Some(connector) = connector else {
+                return Err(MetalError::UnknownConnector(id));
+            };
+            let mut mode = None;
+            if let (true, Some(be_mode)) = (change.enabled, change.mode) {
+                if connector.frontend_state.get() != (FrontState::Connected { non_desktop: false })
+                {
+                    return Err(MetalError::ModeOfInactiveConnector);
+                }
+                let dd = connector.display.borrow_mut();
+                match dd.modes.iter().find(|m| m.to_backend() == be_mode) {
+                    Some(m) => mode = Some(m.clone()),
+                    _ => return Err(MetalError::UnsupportedMode(be_mode)),
+                }
+            }
+            connectors.push((connector, change.enabled, mode));
+        }
+        if test_only {
+            let mut devs = AHashMap::new();
+            for (connector, _, _) in &connectors {
+                if let Some(dev) = self.device_holder.drm_devices.get(&connector.dev.devnum) {
+                    devs.insert(connector.dev.devnum, dev);
+                }
+            }
+            for dev in devs.values() {
+                self.test_connector_changes(dev, &connectors)?;
+            }
+            return Ok(());
+        }
+        let mut devs = AHashMap::new();
+        let mut prev = vec![];
+        for (connector, enabled, mode) in &connectors {
+            let prev_enabled = connector.enabled.replace(*enabled);
+            let mut dd = connector.display.borrow_mut();
+            let prev_mode = dd.mode.clone();
+            if mode.is_some() {
+                dd.mode = mode.clone();
+            }
+            let changed = prev_enabled != *enabled || (mode.is_some() && *mode != prev_mode);
+            prev.push((connector, prev_enabled, prev_mode));
+            if !changed {
+                continue;
+            }
+            if let Some(dev) = self.device_holder.drm_devices.get(&connector.dev.devnum) {
+                devs.insert(connector.dev.devnum, dev);
+            }
+        }
+        let mut res = Ok(());
+        for dev in devs.values() {
+            if let Err(e) = self.handle_drm_change_(dev, true) {
+                res = Err(e);
+                break;
+            }
+        }
+        if let Err(e) = res {
+            log::warn!("Could not apply connector changes: {}", ErrorFmt(&e));
+            for (connector, enabled, mode) in prev {
+                connector.enabled.set(enabled);
+                connector.display.borrow_mut().mode = mode;
+            }
+            for dev in devs.values() {
+                if let Err(e) = self.handle_drm_change_(dev, true) {
+                    dev.unprocessed_change.set(true);
+                    log::error!(
+                        "Could not restore the previous connector state: {}",
+                        ErrorFmt(e)
+                    );
+                }
+            }
+            return Err(e);
+        }
+        for ((connector, _, mode), (_, _, prev_mode)) in connectors.iter().zip(prev.iter()) {
+            let Some(mode) = mode else {
+                continue;
+            };
+            if Some(mode) == prev_mode.as_ref() {
+                continue;
+            }
+            if connector.frontend_state.get() == (FrontState::Connected { non_desktop: false }) {
+                connector.send_event(ConnectorEvent::ModeChanged(mode.to_backend()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Performs a test-only modeset of a device with the connector configuration that
+    /// would result from applying `changes`.
+    ///
+    /// This does not modify the state of the device or of its connectors.
+    fn test_connector_changes(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        changes: &[(Rc<MetalConnector>, bool, Option<DrmModeInfo>)],
+    ) -> Result<(), MetalError> {
+        let ctx = match self.ctx.get() {
+            Some(ctx) => ctx,
+            _ => return Ok(()),
+        };
+        let mut used_crtcs = AHashMap::new();
+        let mut used_planes = AHashMap::new();
+        let mut connector_crtcs = AHashMap::new();
+        for connector in dev.connectors.lock().values() {
+            if connector.lease.get().is_some() {
+                continue;
+            }
+            let dd = connector.display.borrow();
+            let (enabled, mode) = match changes.iter().find(|(c, _, _)| c.id == connector.id) {
+                Some((_, enabled, mode)) => (*enabled, mode.clone().or_else(|| dd.mode.clone())),
+                _ => (connector.enabled.get(), dd.mode.clone()),
+            };
+            if !enabled || dd.connection != ConnectorStatus::Connected || dd.non_desktop_effective {
+                connector_crtcs.insert(connector.id, DrmCrtc::NONE);
+                continue;
+            }
+            let Some(mode) = mode else {
+                return Err(MetalError::NoModeForConnector);
+            };
+            let crtc = dd
+                .crtcs
+                .values()
+                .find(|c| c.lease.is_none() && !used_crtcs.contains_key(&c.id))
+                .ok_or(MetalError::NoCrtcForConnector)?;
+            let plane = crtc
+                .possible_planes
+                .values()
+                .find(|p| {
+                    p.ty == PlaneType::Primary
+                        && p.lease.is_none()
+                        && !used_planes.contains_key(&p.id)
+                        && p.formats.contains_key(&XRGB8888.drm)
+                })
+                .ok_or(MetalError::NoPrimaryPlaneForConnector)?;
+            let mode_blob = mode.create_blob(&connector.master)?;
+            let buffer = self.create_scanout_buffer(
+                &connector.dev,
+                XRGB8888,
+                &plane.formats[&XRGB8888.drm].modifiers,
+                mode.hdisplay as _,
+                mode.vdisplay as _,
+                &ctx,
+                false,
+            )?;
+            connector_crtcs.insert(connector.id, crtc.id);
+            used_crtcs.insert(crtc.id, mode_blob);
+            used_planes.insert(plane.id, (crtc.id, mode, buffer));
+        }
+        let mut change = dev.dev.master.change();
+        for connector in dev.connectors.lock().values() {
+            if let Some(crtc) = connector_crtcs.get(&connector.id) {
+                let dd = connector.display.borrow();
+                change.change_object(connector.id, |c| {
+                    c.change(dd.crtc_id.id, crtc.0 as _);
+                });
+            }
+        }
+        for crtc in dev.dev.crtcs.values() {
+            if crtc.lease.is_some() {
+                continue;
+            }
+            let mode_blob = used_crtcs.get(&crtc.id);
+            change.change_object(crtc.id, |c| {
+                c.change(crtc.active.id, mode_blob.is_some() as _);
+                c.change(
+                    crtc.mode_id.id,
+                    mode_blob.map(|b| b.id().0).unwrap_or(0) as _,
+                );
+            });
+        }
+        for plane in dev.dev.planes.values() {
+            if plane.lease.is_some() {
+                continue;
+            }
+            change.change_object(plane.id, |c| match used_planes.get(&plane.id) {
+                Some((crtc, mode, buffer)) => {
+                    c.change(plane.fb_id, buffer.drm.id().0 as _);
+                    c.change(plane.crtc_id.id, crtc.0 as _);
+                    c.change(plane.crtc_x.id, 0);
+                    c.change(plane.crtc_y.id, 0);
+                    c.change(plane.crtc_w.id, mode.hdisplay as _);
+                    c.change(plane.crtc_h.id, mode.vdisplay as _);
+                    c.change(plane.src_x.id, 0);
+                    c.change(plane.src_y.id, 0);
+                    c.change(plane.src_w.id, (mode.hdisplay as u64) << 16);
+                    c.change(plane.src_h.id, (mode.vdisplay as u64) << 16);
+                }
+                _ => {
+                    c.change(plane.crtc_id.id, 0);
+                    c.change(plane.fb_id, 0);
+                }
+            });
+        }
+        change
+            .test(DRM_MODE_ATOMIC_ALLOW_MODESET)
+            .map_err(MetalError::Modeset)
+    }
+
     fn send_connected(&self, connector: &Rc<MetalConnector>, dd: &ConnectorDisplayData) {
         match connector.frontend_state.get() {
             FrontState::Removed | FrontState::Connected { .. } | FrontState::Unavailable => {
@@ -2109,9 +2805,15 @@ impl MetalBackend {
             height_mm: dd.mm_height as _,
             non_desktop: dd.non_desktop_effective,
             vrr_capable: dd.vrr_capable,
+            primaries: dd.monitor_primaries,
+            hdr_metadata: match dd.hdr_capable() {
+                true => dd.monitor_hdr_metadata.map(Box::new),
+                false => None,
+            },
         }));
         connector.send_hardware_cursor();
         connector.send_vrr_enabled();
+        connector.send_hdr_enabled();
     }
 
     pub fn create_drm_device(
@@ -2278,6 +2980,11 @@ impl MetalBackend {
             c.mode_id
                 .value
                 .set(DrmBlob(get(&props, c.mode_id.id)? as _));
+            if let Some(gamma_lut) = &c.gamma_lut {
+                gamma_lut
+                    .value
+                    .set(DrmBlob(get(&props, gamma_lut.id)? as _));
+            }
         }
         for c in dev.dev.planes.values() {
             let props = collect_untyped_properties(master, c.id)?;
@@ -2388,6 +3095,9 @@ impl MetalBackend {
             } else {
                 rr.discard_presentation_feedback();
             }
+            if let Some(g) = &global {
+                g.vblank();
+            }
         }
     }
 
@@ -2422,10 +3132,19 @@ impl MetalBackend {
             connector.cursor_plane.set(None);
             connector.cursor_enabled.set(false);
             connector.crtc.set(None);
+            connector.hdr_output_metadata_blob.set(None);
             let dd = connector.display.borrow_mut();
             dd.crtc_id.value.set(DrmCrtc::NONE);
             changes.change_object(connector.id, |c| {
                 c.change(dd.crtc_id.id, 0);
+                if let Some(hdr_output_metadata) = &dd.hdr_output_metadata {
+                    hdr_output_metadata.value.set(DrmBlob::NONE);
+                    c.change(hdr_output_metadata.id, 0);
+                }
+                if let Some(colorspace) = &dd.colorspace {
+                    colorspace.prop.value.set(colorspace.default);
+                    c.change(colorspace.prop.id, colorspace.default);
+                }
             })
         }
         for crtc in dev.dev.crtcs.values() {
@@ -2436,11 +3155,17 @@ impl MetalBackend {
             crtc.active.value.set(false);
             crtc.mode_id.value.set(DrmBlob::NONE);
             crtc.vrr_enabled.value.set(false);
+            crtc.gamma_lut_blob.set(None);
             changes.change_object(crtc.id, |c| {
                 c.change(crtc.active.id, 0);
                 c.change(crtc.mode_id.id, 0);
                 c.change(crtc.out_fence_ptr, 0);
                 c.change(crtc.vrr_enabled.id, 0);
+                if let Some(gamma_lut) = &crtc.gamma_lut {
+                    let original = crtc.original_gamma_lut_id();
+                    gamma_lut.value.set(original);
+                    c.change(gamma_lut.id, original.0 as _);
+                }
             })
         }
     }
@@ -2489,6 +3214,10 @@ impl MetalBackend {
                     log::warn!("Cannot preserve connector whose crtc is inactive");
                     fail!(c.id);
                 }
+                if dd.hdr_enabled() != dd.should_enable_hdr() {
+                    log::warn!("Cannot preserve connector whose HDR mode differs");
+                    fail!(c.id);
+                }
                 if let Some(plane) = c.primary_plane.get() {
                     if plane.crtc_id.value.get() != crtc.id {
                         log::warn!("Cannot preserve connector whose primary plane is attached to a different crtc");
@@ -2639,6 +3368,7 @@ impl MetalBackend {
             }
             connector.send_hardware_cursor();
             connector.send_vrr_enabled();
+            connector.send_hdr_enabled();
             connector.update_drm_feedback();
         }
         Ok(())
@@ -2696,6 +3426,10 @@ impl MetalBackend {
                 log::debug!("Connector mode differs from desired mode");
                 return false;
             }
+            if dd.hdr_enabled() != dd.should_enable_hdr() {
+                log::debug!("Connector HDR mode differs from desired HDR mode");
+                return false;
+            }
             let mut have_primary_plane = false;
             for plane in crtc.possible_planes.values() {
                 if plane.ty == PlaneType::Primary && used_planes.insert(plane.id) {
@@ -2907,21 +3641,69 @@ impl MetalBackend {
             _ => return Err(MetalError::NoModeForConnector),
         };
         let mode_blob = mode.create_blob(&connector.master)?;
+        let gamma_lut_blob = match crtc.gamma_lut.is_some() {
+            true => connector.gamma_lut_blob(&crtc)?,
+            false => None,
+        };
+        let gamma_lut_id = match &gamma_lut_blob {
+            Some(b) => b.id(),
+            None => DrmBlob::NONE,
+        };
+        let hdr_output_metadata_blob = match dd.should_enable_hdr() {
+            true => Some(Rc::new(
+                connector.master.create_blob(&dd.hdr_output_metadata())?,
+            )),
+            false => None,
+        };
+        let hdr_output_metadata_id = match &hdr_output_metadata_blob {
+            Some(b) => b.id(),
+            None => DrmBlob::NONE,
+        };
         changes.change_object(connector.id, |c| {
             c.change(dd.crtc_id.id, crtc.id.0 as _);
+            if let Some(hdr_output_metadata) = &dd.hdr_output_metadata {
+                c.change(hdr_output_metadata.id, hdr_output_metadata_id.0 as _);
+            }
+            if let Some(colorspace) = &dd.colorspace {
+                let value = match dd.should_enable_hdr() {
+                    true => colorspace.bt2020_rgb,
+                    false => colorspace.default,
+                };
+                c.change(colorspace.prop.id, value);
+            }
         });
         changes.change_object(crtc.id, |c| {
             c.change(crtc.active.id, 1);
             c.change(crtc.mode_id.id, mode_blob.id().0 as _);
             c.change(crtc.vrr_enabled.id, dd.should_enable_vrr() as _);
+            if let Some(gamma_lut) = &crtc.gamma_lut {
+                c.change(gamma_lut.id, gamma_lut_id.0 as _);
+            }
         });
         connector.crtc.set(Some(crtc.clone()));
         dd.crtc_id.value.set(crtc.id);
+        if let Some(hdr_output_metadata) = &dd.hdr_output_metadata {
+            hdr_output_metadata.value.set(hdr_output_metadata_id);
+        }
+        if let Some(colorspace) = &dd.colorspace {
+            colorspace.prop.value.set(match dd.should_enable_hdr() {
+                true => colorspace.bt2020_rgb,
+                false => colorspace.default,
+            });
+        }
+        connector
+            .hdr_output_metadata_blob
+            .set(hdr_output_metadata_blob);
         crtc.connector.set(Some(connector.clone()));
         crtc.active.value.set(true);
         crtc.mode_id.value.set(mode_blob.id());
         crtc.mode_blob.set(Some(Rc::new(mode_blob)));
         crtc.vrr_enabled.value.set(dd.should_enable_vrr() as _);
+        if let Some(gamma_lut) = &crtc.gamma_lut {
+            gamma_lut.value.set(gamma_lut_id);
+        }
+        crtc.gamma_lut_blob.set(gamma_lut_blob);
+        connector.gamma_lut_changed.set(false);
         Ok(())
     }
 
@@ -2944,26 +3726,51 @@ impl MetalBackend {
                 return Ok(());
             }
         };
-        let (primary_plane, primary_modifiers) = 'primary_plane: {
+        let primary_plane = 'primary_plane: {
             for plane in crtc.possible_planes.values() {
-                if plane.ty == PlaneType::Primary && !plane.assigned.get() && plane.lease.is_none()
+                if plane.ty == PlaneType::Primary
+                    && !plane.assigned.get()
+                    && plane.lease.is_none()
+                    && plane.formats.contains_key(&XRGB8888.drm)
                 {
-                    if let Some(format) = plane.formats.get(&XRGB8888.drm) {
-                        break 'primary_plane (plane.clone(), &format.modifiers);
-                    }
+                    break 'primary_plane plane.clone();
                 }
             }
             return Err(MetalError::NoPrimaryPlaneForConnector);
         };
-        let buffers = Rc::new(self.create_scanout_buffers(
-            &connector.dev,
-            XRGB8888,
-            primary_modifiers,
-            mode.hdisplay as _,
-            mode.vdisplay as _,
-            ctx,
-            false,
-        )?);
+        let mut primary_formats = vec![];
+        if dd.should_enable_hdr() {
+            primary_formats.extend([XRGB2101010, XBGR2101010, XBGR16161616F]);
+        }
+        primary_formats.push(XRGB8888);
+        let buffers = 'buffers: {
+            for format in primary_formats {
+                let Some(plane_format) = primary_plane.formats.get(&format.drm) else {
+                    continue;
+                };
+                let res = self.create_scanout_buffers(
+                    &connector.dev,
+                    format,
+                    &plane_format.modifiers,
+                    mode.hdisplay as _,
+                    mode.vdisplay as _,
+                    ctx,
+                    false,
+                );
+                match res {
+                    Ok(b) => break 'buffers Rc::new(b),
+                    Err(e) if format != XRGB8888 => {
+                        log::warn!(
+                            "Could not allocate {} buffers for the primary plane: {}",
+                            format.name,
+                            ErrorFmt(e),
+                        );
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            unreachable!();
+        };
         let mut cursor_plane = None;
         let mut cursor_modifiers = &IndexSet::new();
         for plane in crtc.possible_planes.values() {
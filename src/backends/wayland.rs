@@ -0,0 +1,1317 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        async_engine::{Phase, SpawnedFuture},
+        backend::{
+            AxisSource, Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorEvent,
+            ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, InputDevice,
+            InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent, KeyState,
+            Mode, MonitorInfo, ScrollAxis, TransformMatrix,
+        },
+        compositor::WAYLAND_DISPLAY,
+        format::XRGB8888,
+        gfx_api::{GfxContext, GfxError, GfxFramebuffer, GfxTexture},
+        ifs::wl_seat::{
+            wl_keyboard,
+            wl_pointer::{
+                PendingScroll, CONTINUOUS, FINGER, HORIZONTAL_SCROLL, PRESSED, VERTICAL_SCROLL,
+            },
+            WlSeatGlobal, KEYBOARD, POINTER,
+        },
+        object::{Interface, Version},
+        offscreen_image::{OffscreenImage, OffscreenImageError},
+        renderer::RenderResult,
+        state::State,
+        utils::{
+            asyncevent::AsyncEvent, bitflags::BitflagsExt, clonecell::CloneCell,
+            copyhashmap::CopyHashMap, errorfmt::ErrorFmt, hash_map_ext::HashMapExt,
+            syncqueue::SyncQueue, xrd::xrd,
+        },
+        video::{
+            drm::{ConnectorType, Drm, DrmError, DrmVersion},
+            gbm::GbmBo,
+            Modifier,
+        },
+        virtual_output,
+        wire::{
+            wl_keyboard::{Key, Leave as KeyboardLeave},
+            wl_pointer::{Button, Enter, Leave, Motion},
+            zwp_relative_pointer_v1::RelativeMotion,
+            WlCompositor, WlSeat, WlSurfaceId, XdgWmBase, ZwpLinuxBufferParamsV1Id,
+            ZwpLinuxDmabufV1, ZwpPointerConstraintsV1, ZwpRelativePointerManagerV1,
+        },
+        wl_usr::{
+            usr_ifs::{
+                usr_linux_buffer_params::{UsrLinuxBufferParams, UsrLinuxBufferParamsOwner},
+                usr_linux_dmabuf::UsrLinuxDmabuf,
+                usr_linux_dmabuf_feedback::UsrLinuxDmabufFeedbackOwner,
+                usr_wl_buffer::{UsrWlBuffer, UsrWlBufferOwner},
+                usr_wl_compositor::UsrWlCompositor,
+                usr_wl_keyboard::{UsrWlKeyboard, UsrWlKeyboardOwner},
+                usr_wl_pointer::{UsrWlPointer, UsrWlPointerOwner},
+                usr_wl_registry::{UsrWlRegistry, UsrWlRegistryOwner},
+                usr_wl_seat::{UsrWlSeat, UsrWlSeatOwner},
+                usr_wl_surface::UsrWlSurface,
+                usr_xdg_surface::{UsrXdgSurface, UsrXdgSurfaceOwner},
+                usr_xdg_toplevel::{UsrXdgToplevel, UsrXdgToplevelOwner},
+                usr_xdg_wm_base::UsrXdgWmBase,
+                usr_zwp_locked_pointer::UsrZwpLockedPointer,
+                usr_zwp_pointer_constraints::{UsrZwpPointerConstraints, LIFETIME_PERSISTENT},
+                usr_zwp_relative_pointer::{UsrZwpRelativePointer, UsrZwpRelativePointerOwner},
+                usr_zwp_relative_pointer_manager::UsrZwpRelativePointerManager,
+            },
+            UsrCon, UsrConError, UsrConOwner,
+        },
+    },
+    ahash::{AHashMap, AHashSet},
+    jay_config::video::GfxApi,
+    std::{
+        any::Any,
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        error::Error,
+        future::pending,
+        ops::Deref,
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::c::dev_t,
+};
+
+const DEFAULT_MODE: Mode = Mode {
+    width: 1280,
+    height: 720,
+    refresh_rate_millihz: 60_000,
+};
+
+const NUM_BUFFERS: usize = 3;
+
+#[derive(Debug, Error)]
+pub enum WaylandBackendError {
+    #[error("WAYLAND_DISPLAY is not set")]
+    WaylandDisplayNotSet,
+    #[error("XDG_RUNTIME_DIR is not set")]
+    XrdNotSet,
+    #[error("Could not parse output size `{0}`")]
+    InvalidMode(String),
+    #[error("Could not connect to the wayland compositor")]
+    Connect(#[source] UsrConError),
+    #[error("The connection to the wayland compositor was closed")]
+    Disconnected,
+    #[error("The wayland compositor does not support {0} version {1}")]
+    MissingGlobal(&'static str, u32),
+    #[error("The wayland compositor did not send dmabuf feedback")]
+    NoDmabufFeedback,
+    #[error("Could not open the drm device of the wayland compositor")]
+    OpenDrmDevice(#[source] DrmError),
+    #[error("Could not create a graphics API context")]
+    CreateContext(#[source] GfxError),
+    #[error("The render device and the wayland compositor have no XRGB8888 modifier in common")]
+    XRGB8888,
+    #[error(transparent)]
+    Image(#[from] OffscreenImageError),
+}
+
+pub async fn create(state: &Rc<State>) -> Result<Rc<WaylandBackend>, WaylandBackendError> {
+    let modes = parse_outputs(&state.run_args.wayland_outputs)?;
+    let wd = match std::env::var(WAYLAND_DISPLAY) {
+        Ok(d) => d,
+        Err(_) => return Err(WaylandBackendError::WaylandDisplayNotSet),
+    };
+    let path = match wd.starts_with('/') {
+        true => wd,
+        false => match xrd() {
+            Some(xrd) => format!("{}/{}", xrd, wd),
+            _ => return Err(WaylandBackendError::XrdNotSet),
+        },
+    };
+    let con = match UsrCon::new(
+        &state.ring,
+        &state.wheel,
+        &state.eng,
+        &state.dma_buf_ids,
+        &path,
+        0,
+    )
+    .await
+    {
+        Ok(c) => c,
+        Err(e) => return Err(WaylandBackendError::Connect(e)),
+    };
+    let prelude = Rc::new(WaylandPrelude {
+        con: con.clone(),
+        registry: con.get_registry(),
+        globals: Default::default(),
+        dead: Cell::new(false),
+        sync: Default::default(),
+        feedback: Default::default(),
+    });
+    let res = prelude.clone().connect(state, modes).await;
+    if res.is_err() {
+        con.kill();
+    }
+    res
+}
+
+fn parse_outputs(outputs: &[String]) -> Result<Vec<Mode>, WaylandBackendError> {
+    let mut modes = vec![];
+    for mode in outputs {
+        match virtual_output::parse_mode(mode) {
+            Some(m) => modes.push(m),
+            _ => return Err(WaylandBackendError::InvalidMode(mode.clone())),
+        }
+    }
+    if modes.is_empty() {
+        modes.push(DEFAULT_MODE);
+    }
+    Ok(modes)
+}
+
+struct WaylandPrelude {
+    con: Rc<UsrCon>,
+    registry: Rc<UsrWlRegistry>,
+    globals: RefCell<AHashMap<String, Vec<(u32, u32)>>>,
+    dead: Cell<bool>,
+    sync: AsyncEvent,
+    feedback: RefCell<Option<(dev_t, Vec<(u32, Modifier)>)>>,
+}
+
+impl WaylandPrelude {
+    async fn roundtrip(self: &Rc<Self>) -> Result<(), WaylandBackendError> {
+        let slf = self.clone();
+        self.con.sync(move || slf.sync.trigger());
+        self.sync.triggered().await;
+        if self.dead.get() {
+            return Err(WaylandBackendError::Disconnected);
+        }
+        Ok(())
+    }
+
+    fn find_global(
+        &self,
+        interface: Interface,
+        min: u32,
+    ) -> Result<(u32, u32), WaylandBackendError> {
+        let global = self
+            .globals
+            .borrow()
+            .get(interface.name())
+            .and_then(|g| g.first().copied());
+        match global {
+            Some((name, version)) if version >= min => Ok((name, version)),
+            _ => Err(WaylandBackendError::MissingGlobal(interface.name(), min)),
+        }
+    }
+
+    async fn connect(
+        self: Rc<Self>,
+        state: &Rc<State>,
+        modes: Vec<Mode>,
+    ) -> Result<Rc<WaylandBackend>, WaylandBackendError> {
+        self.con.owner.set(Some(self.clone()));
+        self.registry.owner.set(Some(self.clone()));
+        self.roundtrip().await?;
+        let compositor = {
+            let (name, version) = self.find_global(WlCompositor, 4)?;
+            let comp = Rc::new(UsrWlCompositor {
+                id: self.con.id(),
+                con: self.con.clone(),
+                version: Version(version.min(6)),
+            });
+            self.con.add_object(comp.clone());
+            self.registry
+                .request_bind(name, comp.version.0, comp.deref());
+            comp
+        };
+        let wm_base = {
+            let (name, _) = self.find_global(XdgWmBase, 1)?;
+            let wm_base = Rc::new(UsrXdgWmBase {
+                id: self.con.id(),
+                con: self.con.clone(),
+                version: Version(1),
+            });
+            self.con.add_object(wm_base.clone());
+            self.registry
+                .request_bind(name, wm_base.version.0, wm_base.deref());
+            wm_base
+        };
+        let dmabuf = {
+            let (name, version) = self.find_global(ZwpLinuxDmabufV1, 4)?;
+            let dmabuf = Rc::new(UsrLinuxDmabuf {
+                id: self.con.id(),
+                con: self.con.clone(),
+                owner: Default::default(),
+                version: Version(version.min(5)),
+            });
+            self.con.add_object(dmabuf.clone());
+            self.registry
+                .request_bind(name, dmabuf.version.0, dmabuf.deref());
+            dmabuf
+        };
+        let relative_pointer_manager = match self.find_global(ZwpRelativePointerManagerV1, 1) {
+            Ok((name, _)) => {
+                let rpm = Rc::new(UsrZwpRelativePointerManager {
+                    id: self.con.id(),
+                    con: self.con.clone(),
+                    version: Version(1),
+                });
+                self.con.add_object(rpm.clone());
+                self.registry.request_bind(name, rpm.version.0, rpm.deref());
+                Some(rpm)
+            }
+            Err(e) => {
+                log::warn!("{}", ErrorFmt(e));
+                None
+            }
+        };
+        let pointer_constraints = match self.find_global(ZwpPointerConstraintsV1, 1) {
+            Ok((name, _)) => {
+                let pc = Rc::new(UsrZwpPointerConstraints {
+                    id: self.con.id(),
+                    con: self.con.clone(),
+                    version: Version(1),
+                });
+                self.con.add_object(pc.clone());
+                self.registry.request_bind(name, pc.version.0, pc.deref());
+                Some(pc)
+            }
+            Err(e) => {
+                log::warn!("{}", ErrorFmt(e));
+                None
+            }
+        };
+        let feedback = dmabuf.get_default_feedback();
+        feedback.owner.set(Some(self.clone()));
+        self.roundtrip().await?;
+        self.con.remove_obj(feedback.deref());
+        let Some((main_device, host_formats)) = self.feedback.take() else {
+            return Err(WaylandBackendError::NoDmabufFeedback);
+        };
+        let drm = match Drm::open_dev(main_device) {
+            Ok(d) => d,
+            Err(e) => return Err(WaylandBackendError::OpenDrmDevice(e)),
+        };
+        let ctx = match state.create_gfx_context(&drm, None) {
+            Ok(c) => c,
+            Err(e) => return Err(WaylandBackendError::CreateContext(e)),
+        };
+        let modifiers: Vec<Modifier> = match ctx.formats().get(&XRGB8888.drm) {
+            Some(f) => host_formats
+                .iter()
+                .filter(|(format, _)| *format == XRGB8888.drm)
+                .map(|(_, modifier)| *modifier)
+                .filter(|modifier| f.write_modifiers.contains(modifier))
+                .collect(),
+            _ => vec![],
+        };
+        if modifiers.is_empty() {
+            return Err(WaylandBackendError::XRGB8888);
+        }
+        let backend = Rc::new(WaylandBackend {
+            state: state.clone(),
+            con: self.con.clone(),
+            registry: self.registry.clone(),
+            compositor,
+            wm_base,
+            dmabuf,
+            relative_pointer_manager,
+            pointer_constraints,
+            ctx,
+            modifiers,
+            outputs: Default::default(),
+            seats: Default::default(),
+            running: Cell::new(false),
+            drm_device_id: state.drm_dev_ids.next(),
+            drm_dev: main_device,
+        });
+        self.con.owner.set(Some(backend.clone()));
+        self.registry.owner.set(Some(backend.clone()));
+        for &(name, version) in self
+            .globals
+            .borrow()
+            .get(WlSeat.name())
+            .into_iter()
+            .flatten()
+        {
+            backend.add_seat(name, version);
+        }
+        for mode in modes {
+            backend.add_output(mode);
+        }
+        Ok(backend)
+    }
+}
+
+impl UsrWlRegistryOwner for WaylandPrelude {
+    fn global(self: Rc<Self>, name: u32, interface: &str, version: u32) {
+        self.globals
+            .borrow_mut()
+            .entry(interface.to_string())
+            .or_default()
+            .push((name, version));
+    }
+}
+
+impl UsrConOwner for WaylandPrelude {
+    fn killed(&self) {
+        self.dead.set(true);
+        self.sync.trigger();
+    }
+}
+
+impl UsrLinuxDmabufFeedbackOwner for WaylandPrelude {
+    fn done(&self, main_device: dev_t, formats: &[(u32, Modifier)]) {
+        *self.feedback.borrow_mut() = Some((main_device, formats.to_vec()));
+    }
+}
+
+pub struct WaylandBackend {
+    state: Rc<State>,
+    con: Rc<UsrCon>,
+    registry: Rc<UsrWlRegistry>,
+    compositor: Rc<UsrWlCompositor>,
+    wm_base: Rc<UsrXdgWmBase>,
+    dmabuf: Rc<UsrLinuxDmabuf>,
+    relative_pointer_manager: Option<Rc<UsrZwpRelativePointerManager>>,
+    pointer_constraints: Option<Rc<UsrZwpPointerConstraints>>,
+    ctx: Rc<dyn GfxContext>,
+    modifiers: Vec<Modifier>,
+    outputs: CopyHashMap<WlSurfaceId, Rc<WaylandOutput>>,
+    seats: CopyHashMap<u32, Rc<WaylandSeat>>,
+    running: Cell<bool>,
+    drm_device_id: DrmDeviceId,
+    drm_dev: dev_t,
+}
+
+impl Backend for WaylandBackend {
+    fn run(self: Rc<Self>) -> SpawnedFuture<Result<(), Box<dyn Error>>> {
+        let slf = self.clone();
+        self.state.eng.spawn(async move {
+            slf.run().await;
+            Ok(())
+        })
+    }
+
+    fn clear(&self) {
+        for output in self.outputs.lock().drain_values() {
+            output.clear();
+        }
+        for seat in self.seats.lock().drain_values() {
+            seat.clear();
+        }
+        self.con.kill();
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
+impl WaylandBackend {
+    async fn run(self: Rc<Self>) {
+        self.state.set_render_ctx(Some(self.ctx.clone()));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewDrmDevice(Rc::new(WaylandDrmDevice {
+                backend: self.clone(),
+            })));
+        for output in self.outputs.lock().values() {
+            self.state
+                .backend_events
+                .push(BackendEvent::NewConnector(output.clone()));
+            output.connect();
+        }
+        self.running.set(true);
+        for seat in self.seats.lock().values() {
+            seat.announce();
+        }
+        self.state
+            .backend_events
+            .push(BackendEvent::DevicesEnumerated);
+        pending().await
+    }
+
+    fn add_output(self: &Rc<Self>, mode: Mode) {
+        let surface = self.compositor.create_surface();
+        let xdg_surface = self.wm_base.get_xdg_surface(&surface);
+        let xdg_toplevel = xdg_surface.get_toplevel();
+        let output = Rc::new(WaylandOutput {
+            id: self.state.connector_ids.next(),
+            backend: self.clone(),
+            surface,
+            xdg_surface,
+            xdg_toplevel,
+            events: Default::default(),
+            cb: Default::default(),
+            mode: Cell::new(mode),
+            pending_size: Cell::new((0, 0)),
+            configured: Cell::new(false),
+            buffers: Default::default(),
+            pending_buffers: Default::default(),
+            have_frame: Cell::new(true),
+            needs_present: Cell::new(true),
+            present_trigger: Default::default(),
+            present_task: Default::default(),
+            render_result: Default::default(),
+        });
+        output.xdg_surface.owner.set(Some(output.clone()));
+        output.xdg_toplevel.owner.set(Some(output.clone()));
+        output
+            .xdg_toplevel
+            .set_title(&format!("Jay - {}", output.kernel_id()));
+        output.xdg_toplevel.set_app_id("jay");
+        output.surface.commit();
+        let task = self
+            .state
+            .eng
+            .spawn2(Phase::Present, output.clone().present_loop());
+        output.present_task.set(Some(task));
+        self.outputs.set(output.surface.id, output);
+    }
+
+    fn add_seat(self: &Rc<Self>, name: u32, version: u32) {
+        let wl = Rc::new(UsrWlSeat {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: Version(version.min(8)),
+        });
+        self.con.add_object(wl.clone());
+        self.registry.request_bind(name, wl.version.0, wl.deref());
+        let seat = Rc::new(WaylandSeat {
+            kb_id: self.state.input_device_ids.next(),
+            mouse_id: self.state.input_device_ids.next(),
+            backend: self.clone(),
+            wl,
+            removed: Cell::new(false),
+            capabilities: Cell::new(0),
+            keyboard: Default::default(),
+            pointer: Default::default(),
+            relative_pointer: Default::default(),
+            locked_pointer: Default::default(),
+            pointer_focus: Default::default(),
+            pressed_keys: Default::default(),
+            kb_cb: Default::default(),
+            mouse_cb: Default::default(),
+            kb_events: Default::default(),
+            mouse_events: Default::default(),
+            kb_name: Rc::new(format!("kb{}", name)),
+            mouse_name: Rc::new(format!("mouse{}", name)),
+        });
+        seat.wl.owner.set(Some(seat.clone()));
+        self.seats.set(name, seat.clone());
+        if self.running.get() {
+            seat.announce();
+        }
+    }
+
+    fn allocate_buffers(&self, output: &Rc<WaylandOutput>) -> Result<(), WaylandBackendError> {
+        let mode = output.mode.get();
+        for _ in 0..NUM_BUFFERS {
+            let OffscreenImage { bo, fb, tex } = OffscreenImage::new(
+                &self.ctx,
+                &self.state.dma_buf_ids,
+                mode.width,
+                mode.height,
+                XRGB8888,
+                &self.modifiers,
+            )?;
+            let params = self.dmabuf.create_params();
+            params.create(bo.dmabuf());
+            let pending = Rc::new(WaylandBufferPending {
+                bo: Cell::new(Some(bo)),
+                output: output.clone(),
+                fb,
+                tex,
+                params,
+                size: (mode.width, mode.height),
+            });
+            pending.params.owner.set(Some(pending.clone()));
+            output.pending_buffers.set(pending.params.id, pending);
+        }
+        Ok(())
+    }
+}
+
+impl UsrWlRegistryOwner for WaylandBackend {
+    fn global(self: Rc<Self>, name: u32, interface: &str, version: u32) {
+        if interface == WlSeat.name() {
+            self.add_seat(name, version);
+        }
+    }
+
+    fn global_remove(&self, name: u32) {
+        if let Some(seat) = self.seats.remove(&name) {
+            seat.clear();
+            seat.removed.set(true);
+            seat.kb_changed();
+            seat.mouse_changed();
+        }
+    }
+}
+
+impl UsrConOwner for WaylandBackend {
+    fn killed(&self) {
+        log::error!("Fatal error: The connection to the wayland compositor was closed");
+        self.state.ring.stop();
+    }
+}
+
+struct WaylandDrmDevice {
+    backend: Rc<WaylandBackend>,
+}
+
+impl BackendDrmDevice for WaylandDrmDevice {
+    fn id(&self) -> DrmDeviceId {
+        self.backend.drm_device_id
+    }
+
+    fn event(&self) -> Option<DrmEvent> {
+        None
+    }
+
+    fn on_change(&self, _cb: Rc<dyn Fn()>) {
+        // nothing
+    }
+
+    fn dev_t(&self) -> dev_t {
+        self.backend.drm_dev
+    }
+
+    fn make_render_device(&self) {
+        log::warn!("make_render_device is not supported by the wayland backend");
+    }
+
+    fn set_gfx_api(&self, _api: GfxApi) {
+        log::warn!("set_gfx_api is not supported by the wayland backend");
+    }
+
+    fn gtx_api(&self) -> GfxApi {
+        self.backend.ctx.gfx_api()
+    }
+
+    fn version(&self) -> Result<DrmVersion, DrmError> {
+        self.backend.ctx.gbm().drm.version()
+    }
+
+    fn set_direct_scanout_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn is_render_device(&self) -> bool {
+        true
+    }
+}
+
+struct WaylandOutput {
+    id: ConnectorId,
+    backend: Rc<WaylandBackend>,
+    surface: Rc<UsrWlSurface>,
+    xdg_surface: Rc<UsrXdgSurface>,
+    xdg_toplevel: Rc<UsrXdgToplevel>,
+    events: SyncQueue<ConnectorEvent>,
+    cb: CloneCell<Option<Rc<dyn Fn()>>>,
+    mode: Cell<Mode>,
+    pending_size: Cell<(i32, i32)>,
+    configured: Cell<bool>,
+    buffers: RefCell<Vec<Rc<WaylandBuffer>>>,
+    pending_buffers: CopyHashMap<ZwpLinuxBufferParamsV1Id, Rc<WaylandBufferPending>>,
+    have_frame: Cell<bool>,
+    needs_present: Cell<bool>,
+    present_trigger: AsyncEvent,
+    present_task: Cell<Option<SpawnedFuture<()>>>,
+    render_result: RefCell<RenderResult>,
+}
+
+struct WaylandBuffer {
+    wl: Rc<UsrWlBuffer>,
+    output: Rc<WaylandOutput>,
+    fb: Rc<dyn GfxFramebuffer>,
+    tex: Rc<dyn GfxTexture>,
+    _bo: Option<GbmBo>,
+    free: Cell<bool>,
+    size: (i32, i32),
+}
+
+struct WaylandBufferPending {
+    bo: Cell<Option<GbmBo>>,
+    output: Rc<WaylandOutput>,
+    fb: Rc<dyn GfxFramebuffer>,
+    tex: Rc<dyn GfxTexture>,
+    params: Rc<UsrLinuxBufferParams>,
+    size: (i32, i32),
+}
+
+impl WaylandOutput {
+    fn changed(&self) {
+        if let Some(cb) = self.cb.get() {
+            cb();
+        }
+    }
+
+    fn connect(&self) {
+        let mode = self.mode.get();
+        self.events.push(ConnectorEvent::Connected(MonitorInfo {
+            modes: vec![],
+            manufacturer: "jay".to_string(),
+            product: format!("Wayland-Window-{}", self.id),
+            serial_number: self.id.to_string(),
+            initial_mode: mode,
+            width_mm: mode.width,
+            height_mm: mode.height,
+            non_desktop: false,
+            vrr_capable: false,
+            primaries: None,
+            hdr_metadata: None,
+        }));
+        self.changed();
+    }
+
+    fn clear(&self) {
+        self.present_task.take();
+        self.clear_buffers();
+        let con = &self.backend.con;
+        con.remove_obj(self.xdg_toplevel.deref());
+        con.remove_obj(self.xdg_surface.deref());
+        con.remove_obj(self.surface.deref());
+    }
+
+    fn clear_buffers(&self) {
+        let con = &self.backend.con;
+        for pending in self.pending_buffers.lock().drain_values() {
+            con.remove_obj(pending.params.deref());
+        }
+        for buffer in self.buffers.borrow_mut().drain(..) {
+            con.remove_obj(buffer.wl.deref());
+        }
+    }
+
+    fn schedule_present(&self) {
+        self.needs_present.set(true);
+        self.present_trigger.trigger();
+    }
+
+    async fn present_loop(self: Rc<Self>) {
+        loop {
+            self.present_trigger.triggered().await;
+            self.present();
+        }
+    }
+
+    fn present(self: &Rc<Self>) {
+        if !self.configured.get() || !self.have_frame.get() || !self.needs_present.get() {
+            return;
+        }
+        let mode = self.mode.get();
+        let buffer = {
+            let buffers = self.buffers.borrow();
+            let buffer = buffers
+                .iter()
+                .find(|b| b.free.get() && b.size == (mode.width, mode.height));
+            match buffer {
+                Some(b) => b.clone(),
+                _ => return,
+            }
+        };
+        if let Some(node) = self.backend.state.root.outputs.get(&self.id) {
+            let res = self.backend.state.present_output(
+                &node,
+                &buffer.fb,
+                &buffer.tex,
+                &mut self.render_result.borrow_mut(),
+                true,
+            );
+            if let Err(e) = res {
+                log::error!("Could not render screen: {}", ErrorFmt(e));
+                return;
+            }
+        } else if let Some(mirror) = self.backend.state.mirrors.get(&self.id) {
+            if let Err(e) = mirror.present(&buffer.fb) {
+                log::error!("Could not render screen: {}", ErrorFmt(e));
+                return;
+            }
+        }
+        self.needs_present.set(false);
+        self.have_frame.set(false);
+        self.surface.frame({
+            let slf = self.clone();
+            move || {
+                slf.have_frame.set(true);
+                if let Some(node) = slf.backend.state.root.outputs.get(&slf.id) {
+                    node.vblank();
+                }
+                if slf.needs_present.get() {
+                    slf.present_trigger.trigger();
+                }
+            }
+        });
+        buffer.free.set(false);
+        self.surface.attach(&buffer.wl);
+        self.surface.damage_buffer(mode.width, mode.height);
+        self.surface.commit();
+        self.backend.state.set_backend_idle(false);
+        if let Some(node) = self.backend.state.root.outputs.get(&self.id) {
+            node.schedule.presented();
+        }
+    }
+
+    fn resize(self: &Rc<Self>, width: i32, height: i32) {
+        let mode = Mode {
+            width,
+            height,
+            ..self.mode.get()
+        };
+        let changed = self.mode.replace(mode) != mode;
+        if changed || !self.configured.replace(true) {
+            self.clear_buffers();
+            if let Err(e) = self.backend.allocate_buffers(self) {
+                log::error!("Could not allocate buffers: {}", ErrorFmt(e));
+            }
+        }
+        if changed {
+            self.events.push(ConnectorEvent::ModeChanged(mode));
+            self.changed();
+        }
+        self.schedule_present();
+    }
+}
+
+impl UsrXdgToplevelOwner for WaylandOutput {
+    fn configure(&self, width: i32, height: i32) {
+        self.pending_size.set((width, height));
+    }
+
+    fn close(&self) {
+        let Some(output) = self.backend.outputs.remove(&self.surface.id) else {
+            return;
+        };
+        output.clear();
+        output.events.push(ConnectorEvent::Disconnected);
+        output.events.push(ConnectorEvent::Removed);
+        output.changed();
+        if self.backend.outputs.is_empty() {
+            log::info!("All windows have been closed");
+            self.backend.state.ring.stop();
+        }
+    }
+}
+
+impl UsrXdgSurfaceOwner for WaylandOutput {
+    fn configure(&self) {
+        let Some(slf) = self.backend.outputs.get(&self.surface.id) else {
+            return;
+        };
+        let (mut width, mut height) = self.pending_size.get();
+        let mode = self.mode.get();
+        if width <= 0 || height <= 0 {
+            (width, height) = (mode.width, mode.height);
+        }
+        slf.resize(width, height);
+    }
+}
+
+impl UsrLinuxBufferParamsOwner for WaylandBufferPending {
+    fn created(&self, buffer: Rc<UsrWlBuffer>) {
+        let buf = Rc::new(WaylandBuffer {
+            wl: buffer,
+            output: self.output.clone(),
+            fb: self.fb.clone(),
+            tex: self.tex.clone(),
+            _bo: self.bo.take(),
+            free: Cell::new(true),
+            size: self.size,
+        });
+        buf.wl.owner.set(Some(buf.clone()));
+        self.output.buffers.borrow_mut().push(buf);
+        self.params.con.remove_obj(self.params.deref());
+        self.output.pending_buffers.remove(&self.params.id);
+        if self.output.needs_present.get() {
+            self.output.present_trigger.trigger();
+        }
+    }
+
+    fn failed(&self) {
+        log::error!("The wayland compositor could not import a buffer");
+        self.params.con.remove_obj(self.params.deref());
+        self.output.pending_buffers.remove(&self.params.id);
+    }
+}
+
+impl UsrWlBufferOwner for WaylandBuffer {
+    fn release(&self) {
+        self.free.set(true);
+        if self.output.needs_present.get() {
+            self.output.present_trigger.trigger();
+        }
+    }
+}
+
+impl Connector for WaylandOutput {
+    fn id(&self) -> ConnectorId {
+        self.id
+    }
+
+    fn kernel_id(&self) -> ConnectorKernelId {
+        ConnectorKernelId {
+            ty: ConnectorType::EmbeddedWindow,
+            idx: self.id.raw(),
+        }
+    }
+
+    fn event(&self) -> Option<ConnectorEvent> {
+        self.events.pop()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.cb.set(Some(cb));
+    }
+
+    fn damage(&self) {
+        self.schedule_present();
+    }
+
+    fn drm_dev(&self) -> Option<DrmDeviceId> {
+        Some(self.backend.drm_device_id)
+    }
+
+    fn set_mode(&self, _mode: Mode) {
+        log::warn!("Wayland backend doesn't support changing the connector mode. Resize the window instead.");
+    }
+}
+
+struct WaylandSeat {
+    kb_id: InputDeviceId,
+    mouse_id: InputDeviceId,
+    backend: Rc<WaylandBackend>,
+    wl: Rc<UsrWlSeat>,
+    removed: Cell<bool>,
+    capabilities: Cell<u32>,
+    keyboard: CloneCell<Option<Rc<UsrWlKeyboard>>>,
+    pointer: CloneCell<Option<Rc<UsrWlPointer>>>,
+    relative_pointer: CloneCell<Option<Rc<UsrZwpRelativePointer>>>,
+    locked_pointer: CloneCell<Option<Rc<UsrZwpLockedPointer>>>,
+    pointer_focus: CloneCell<Option<Rc<WaylandOutput>>>,
+    pressed_keys: RefCell<AHashSet<u32>>,
+    kb_cb: CloneCell<Option<Rc<dyn Fn()>>>,
+    mouse_cb: CloneCell<Option<Rc<dyn Fn()>>>,
+    kb_events: RefCell<VecDeque<InputEvent>>,
+    mouse_events: RefCell<VecDeque<InputEvent>>,
+    kb_name: Rc<String>,
+    mouse_name: Rc<String>,
+}
+
+struct WaylandSeatKeyboard(Rc<WaylandSeat>);
+
+struct WaylandSeatMouse(Rc<WaylandSeat>);
+
+impl WaylandSeat {
+    fn announce(self: &Rc<Self>) {
+        self.backend
+            .state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(Rc::new(WaylandSeatMouse(
+                self.clone(),
+            ))));
+        self.backend
+            .state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(Rc::new(WaylandSeatKeyboard(
+                self.clone(),
+            ))));
+    }
+
+    fn clear(&self) {
+        self.remove_pointer();
+        self.remove_keyboard();
+        self.backend.con.remove_obj(self.wl.deref());
+    }
+
+    fn remove_pointer(&self) {
+        let con = &self.backend.con;
+        self.pointer_focus.take();
+        if let Some(lp) = self.locked_pointer.take() {
+            con.remove_obj(lp.deref());
+        }
+        if let Some(rp) = self.relative_pointer.take() {
+            con.remove_obj(rp.deref());
+        }
+        if let Some(pointer) = self.pointer.take() {
+            con.remove_obj(pointer.deref());
+        }
+    }
+
+    fn remove_keyboard(&self) {
+        self.release_keys();
+        if let Some(kb) = self.keyboard.take() {
+            self.backend.con.remove_obj(kb.deref());
+        }
+    }
+
+    fn release_keys(&self) {
+        let keys: Vec<_> = self.pressed_keys.borrow_mut().drain().collect();
+        for key in keys {
+            self.kb_event(InputEvent::Key {
+                time_usec: self.backend.state.now_usec(),
+                key,
+                state: KeyState::Released,
+            });
+        }
+    }
+
+    fn kb_changed(&self) {
+        if let Some(cb) = self.kb_cb.get() {
+            cb();
+        }
+    }
+
+    fn mouse_changed(&self) {
+        if let Some(cb) = self.mouse_cb.get() {
+            cb();
+        }
+    }
+
+    fn mouse_event(&self, event: InputEvent) {
+        self.mouse_events.borrow_mut().push_back(event);
+        self.mouse_changed();
+    }
+
+    fn kb_event(&self, event: InputEvent) {
+        self.kb_events.borrow_mut().push_back(event);
+        self.kb_changed();
+    }
+
+    fn jay_seat(&self) -> Option<Rc<WlSeatGlobal>> {
+        let handlers = self.backend.state.input_device_handlers.borrow();
+        handlers.get(&self.mouse_id).and_then(|h| h.data.seat.get())
+    }
+
+    /// Locks the pointer of the parent compositor while the pointer of the seat is
+    /// constrained. Returns whether relative motion should be used.
+    fn update_pointer_lock(&self) -> bool {
+        let constrained = self
+            .jay_seat()
+            .map(|s| s.has_pointer_constraint())
+            .unwrap_or(false);
+        if !constrained || self.relative_pointer.is_none() {
+            if let Some(lp) = self.locked_pointer.take() {
+                self.backend.con.remove_obj(lp.deref());
+            }
+            return false;
+        }
+        if self.locked_pointer.is_none() {
+            if let (Some(pc), Some(pointer), Some(output)) = (
+                &self.backend.pointer_constraints,
+                self.pointer.get(),
+                self.pointer_focus.get(),
+            ) {
+                let lp = pc.lock_pointer(&output.surface, &pointer, LIFETIME_PERSISTENT);
+                self.locked_pointer.set(Some(lp));
+            }
+        }
+        true
+    }
+}
+
+impl UsrWlSeatOwner for WaylandSeat {
+    fn capabilities(self: Rc<Self>, value: u32) {
+        let old = self.capabilities.replace(value);
+        if old.contains(POINTER) != value.contains(POINTER) {
+            if old.contains(POINTER) {
+                self.remove_pointer();
+            } else {
+                let pointer = self.wl.get_pointer();
+                pointer.owner.set(Some(self.clone()));
+                if let Some(rpm) = &self.backend.relative_pointer_manager {
+                    let rp = rpm.get_relative_pointer(&pointer);
+                    rp.owner.set(Some(self.clone()));
+                    self.relative_pointer.set(Some(rp));
+                }
+                self.pointer.set(Some(pointer));
+            }
+        }
+        if old.contains(KEYBOARD) != value.contains(KEYBOARD) {
+            if old.contains(KEYBOARD) {
+                self.remove_keyboard();
+            } else {
+                let kb = self.wl.get_keyboard();
+                kb.owner.set(Some(self.clone()));
+                self.keyboard.set(Some(kb));
+            }
+        }
+    }
+}
+
+impl UsrWlKeyboardOwner for WaylandSeat {
+    fn leave(&self, _ev: &KeyboardLeave) {
+        self.release_keys();
+    }
+
+    fn key(&self, ev: &Key) {
+        let state = match ev.state {
+            wl_keyboard::RELEASED => {
+                if !self.pressed_keys.borrow_mut().remove(&ev.key) {
+                    return;
+                }
+                KeyState::Released
+            }
+            wl_keyboard::PRESSED => {
+                if !self.pressed_keys.borrow_mut().insert(ev.key) {
+                    return;
+                }
+                KeyState::Pressed
+            }
+            _ => return,
+        };
+        self.kb_event(InputEvent::Key {
+            time_usec: self.backend.state.now_usec(),
+            key: ev.key,
+            state,
+        });
+    }
+}
+
+impl UsrWlPointerOwner for WaylandSeat {
+    fn enter(&self, ev: &Enter) {
+        let Some(output) = self.backend.outputs.get(&ev.surface) else {
+            return;
+        };
+        if let Some(pointer) = self.pointer.get() {
+            pointer.hide_cursor(ev.serial);
+        }
+        self.pointer_focus.set(Some(output.clone()));
+        if !self.update_pointer_lock() {
+            self.mouse_event(InputEvent::ConnectorPosition {
+                time_usec: self.backend.state.now_usec(),
+                connector: output.id,
+                x: ev.surface_x,
+                y: ev.surface_y,
+            });
+        }
+    }
+
+    fn leave(&self, _ev: &Leave) {
+        self.pointer_focus.take();
+    }
+
+    fn motion(&self, ev: &Motion) {
+        let Some(output) = self.pointer_focus.get() else {
+            return;
+        };
+        if !self.update_pointer_lock() {
+            self.mouse_event(InputEvent::ConnectorPosition {
+                time_usec: self.backend.state.now_usec(),
+                connector: output.id,
+                x: ev.surface_x,
+                y: ev.surface_y,
+            });
+        }
+    }
+
+    fn button(&self, ev: &Button) {
+        let state = match ev.state {
+            PRESSED => KeyState::Pressed,
+            _ => KeyState::Released,
+        };
+        self.mouse_event(InputEvent::Button {
+            time_usec: self.backend.state.now_usec(),
+            button: ev.button,
+            state,
+        });
+    }
+
+    fn scroll(&self, ps: &PendingScroll) {
+        if let Some(source) = ps.source.get() {
+            let source = match source {
+                FINGER => AxisSource::Finger,
+                CONTINUOUS => AxisSource::Continuous,
+                _ => AxisSource::Wheel,
+            };
+            self.mouse_event(InputEvent::AxisSource { source });
+        }
+        let axes = [
+            (VERTICAL_SCROLL, ScrollAxis::Vertical),
+            (HORIZONTAL_SCROLL, ScrollAxis::Horizontal),
+        ];
+        for (idx, axis) in axes {
+            let idx = idx as usize;
+            let event = if ps.stop[idx].get() {
+                InputEvent::AxisStop { axis }
+            } else if let Some(dist) = ps.v120[idx].get() {
+                InputEvent::Axis120 {
+                    dist,
+                    axis,
+                    inverted: false,
+                }
+            } else if let Some(dist) = ps.px[idx].get() {
+                InputEvent::AxisPx {
+                    dist,
+                    axis,
+                    inverted: false,
+                }
+            } else {
+                continue;
+            };
+            self.mouse_event(event);
+        }
+        self.mouse_event(InputEvent::AxisFrame {
+            time_usec: self.backend.state.now_usec(),
+        });
+    }
+}
+
+impl UsrZwpRelativePointerOwner for WaylandSeat {
+    fn relative_motion(&self, ev: &RelativeMotion) {
+        if self.pointer_focus.is_none() {
+            return;
+        }
+        if self.update_pointer_lock() {
+            self.mouse_event(InputEvent::Motion {
+                time_usec: self.backend.state.now_usec(),
+                dx: ev.dx,
+                dy: ev.dy,
+                dx_unaccelerated: ev.dx_unaccelerated,
+                dy_unaccelerated: ev.dy_unaccelerated,
+            });
+        }
+    }
+}
+
+impl InputDevice for WaylandSeatKeyboard {
+    fn id(&self) -> InputDeviceId {
+        self.0.kb_id
+    }
+
+    fn removed(&self) -> bool {
+        self.0.removed.get()
+    }
+
+    fn event(&self) -> Option<InputEvent> {
+        self.0.kb_events.borrow_mut().pop_front()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.0.kb_cb.set(Some(cb));
+    }
+
+    fn grab(&self, _grab: bool) {
+        log::warn!("Wayland backend does not support grabbing keyboards");
+    }
+
+    fn has_capability(&self, cap: InputDeviceCapability) -> bool {
+        match cap {
+            InputDeviceCapability::Keyboard => true,
+            _ => false,
+        }
+    }
+
+    fn set_left_handed(&self, left_handed: bool) {
+        let _ = left_handed;
+    }
+
+    fn set_accel_profile(&self, profile: InputDeviceAccelProfile) {
+        let _ = profile;
+    }
+
+    fn set_accel_speed(&self, speed: f64) {
+        let _ = speed;
+    }
+
+    fn set_transform_matrix(&self, matrix: TransformMatrix) {
+        let _ = matrix;
+    }
+
+    fn name(&self) -> Rc<String> {
+        self.0.kb_name.clone()
+    }
+
+    fn dev_t(&self) -> Option<dev_t> {
+        None
+    }
+
+    fn set_tap_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_lock_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_natural_scrolling_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+}
+
+impl InputDevice for WaylandSeatMouse {
+    fn id(&self) -> InputDeviceId {
+        self.0.mouse_id
+    }
+
+    fn removed(&self) -> bool {
+        self.0.removed.get()
+    }
+
+    fn event(&self) -> Option<InputEvent> {
+        self.0.mouse_events.borrow_mut().pop_front()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.0.mouse_cb.set(Some(cb));
+    }
+
+    fn grab(&self, _grab: bool) {
+        log::warn!("Wayland backend does not support grabbing pointers");
+    }
+
+    fn has_capability(&self, cap: InputDeviceCapability) -> bool {
+        match cap {
+            InputDeviceCapability::Pointer => true,
+            _ => false,
+        }
+    }
+
+    fn set_left_handed(&self, left_handed: bool) {
+        let _ = left_handed;
+    }
+
+    fn set_accel_profile(&self, profile: InputDeviceAccelProfile) {
+        let _ = profile;
+    }
+
+    fn set_accel_speed(&self, speed: f64) {
+        let _ = speed;
+    }
+
+    fn set_transform_matrix(&self, matrix: TransformMatrix) {
+        let _ = matrix;
+    }
+
+    fn name(&self) -> Rc<String> {
+        self.0.mouse_name.clone()
+    }
+
+    fn dev_t(&self) -> Option<dev_t> {
+        None
+    }
+
+    fn set_tap_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_lock_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_natural_scrolling_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        backend::Mode,
+        backends::wayland::{parse_outputs, WaylandBackendError, DEFAULT_MODE},
+        cli::{CliBackend, RunArgs},
+    },
+    clap::Parser,
+};
+
+#[derive(Parser)]
+struct Cli {
+    #[clap(flatten)]
+    run: RunArgs,
+}
+
+fn run_args(args: &[&str]) -> RunArgs {
+    Cli::try_parse_from(["jay"].iter().chain(args)).unwrap().run
+}
+
+#[test]
+fn wayland_outputs() {
+    let args = run_args(&[
+        "--backends",
+        "wayland,metal",
+        "--wayland-outputs",
+        "1920x1080,800x600",
+    ]);
+    assert_eq!(args.backends, [CliBackend::Wayland, CliBackend::Metal]);
+    let modes = parse_outputs(&args.wayland_outputs).unwrap();
+    assert_eq!(
+        modes,
+        [
+            Mode {
+                width: 1920,
+                height: 1080,
+                refresh_rate_millihz: 60_000,
+            },
+            Mode {
+                width: 800,
+                height: 600,
+                refresh_rate_millihz: 60_000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn default_output() {
+    let args = run_args(&[]);
+    assert!(args.backends.is_empty());
+    assert_eq!(
+        parse_outputs(&args.wayland_outputs).unwrap(),
+        [DEFAULT_MODE]
+    );
+}
+
+#[test]
+fn invalid_output() {
+    for mode in ["1280", "x720", "0x720", "1280x720@0"] {
+        let res = parse_outputs(&[mode.to_string()]);
+        assert!(matches!(res, Err(WaylandBackendError::InvalidMode(m)) if m == mode));
+    }
+}
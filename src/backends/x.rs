@@ -576,6 +576,8 @@ impl XBackend {
             height_mm: output.height.get(),
             non_desktop: false,
             vrr_capable: false,
+            primaries: None,
+            hdr_metadata: None,
         }));
         output.changed();
         self.present(output).await;
@@ -691,6 +693,9 @@ impl XBackend {
             _ => return Ok(()),
         };
         output.next_msc.set(event.msc + 1);
+        if let Some(node) = self.state.root.outputs.get(&output.id) {
+            node.vblank();
+        }
         let image = &output.images[output.next_image.get() % output.images.len()];
         if image.idle.get() {
             self.schedule_present(&output);
@@ -751,6 +756,11 @@ impl XBackend {
                 log::error!("Could not render screen: {}", ErrorFmt(e));
                 return;
             }
+        } else if let Some(mirror) = self.state.mirrors.get(&output.id) {
+            if let Err(e) = mirror.present(&image.fb.get()) {
+                log::error!("Could not render screen: {}", ErrorFmt(e));
+                return;
+            }
         }
 
         let pp = PresentPixmap {
@@ -160,6 +160,25 @@ pub struct RunArgs {
     /// which they will be tried. Multiple backends can be supplied as a comma-separated list.
     #[clap(value_enum, use_value_delimiter = true, long)]
     pub backends: Vec<CliBackend>,
+    /// The outputs to create when using the headless backend.
+    ///
+    /// Each output is described as WIDTHxHEIGHT or WIDTHxHEIGHT@REFRESH where REFRESH is the
+    /// refresh rate in Hz, e.g. 2560x1440@60. Multiple outputs can be supplied as a
+    /// comma-separated list.
+    ///
+    /// By default, a single 1920x1080@60 output is created.
+    #[clap(use_value_delimiter = true, long)]
+    pub headless_outputs: Vec<String>,
+    /// The outputs to create when using the wayland backend.
+    ///
+    /// Each output is shown in a window of the parent compositor. Outputs are described as
+    /// WIDTHxHEIGHT, e.g. 1280x720, which is the initial size of the window. Resizing the
+    /// window changes the mode of the output. Multiple outputs can be supplied as a
+    /// comma-separated list.
+    ///
+    /// By default, a single 1280x720 output is created.
+    #[clap(use_value_delimiter = true, long)]
+    pub wayland_outputs: Vec<String>,
 }
 
 #[derive(Args, Debug)]
@@ -193,8 +212,10 @@ pub struct SeatTestArgs {
 
 #[derive(ValueEnum, Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub enum CliBackend {
+    Wayland,
     X11,
     Metal,
+    Headless,
 }
 
 #[derive(ValueEnum, Debug, Copy, Clone, Hash)]
@@ -1,9 +1,11 @@
 use {
     crate::{
+        backend::{DEFAULT_COLOR_TEMPERATURE, DEFAULT_SDR_REFERENCE_WHITE},
         cli::GlobalArgs,
         scale::Scale,
         tools::tool_client::{with_tool_client, Handle, ToolClient},
         utils::{errorfmt::ErrorFmt, transform_ext::TransformExt},
+        virtual_output::parse_mode,
         wire::{jay_compositor, jay_randr, JayRandrId},
     },
     clap::{Args, Subcommand, ValueEnum},
@@ -31,6 +33,8 @@ pub enum RandrCmd {
     Card(CardArgs),
     /// Modify the settings of an output.
     Output(OutputArgs),
+    /// Create or remove virtual outputs.
+    Virtual(VirtualArgs),
 }
 
 impl Default for RandrCmd {
@@ -94,6 +98,37 @@ pub enum DirectScanoutCmd {
     Disable,
 }
 
+#[derive(Args, Debug)]
+pub struct VirtualArgs {
+    #[clap(subcommand)]
+    pub command: VirtualCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum VirtualCommand {
+    /// Create a virtual output.
+    ///
+    /// Virtual outputs behave like normal outputs but are not displayed anywhere. Their
+    /// contents can be captured via screencasts.
+    Create(CreateVirtualArgs),
+    /// Remove a virtual output.
+    Remove(RemoveVirtualArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CreateVirtualArgs {
+    /// The mode of the output, e.g. 1920x1080 or 1920x1080@60.
+    ///
+    /// The refresh rate defaults to 60 Hz.
+    pub mode: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RemoveVirtualArgs {
+    /// The virtual output to remove, e.g. Virtual-1.
+    pub output: String,
+}
+
 #[derive(Args, Debug)]
 pub struct OutputArgs {
     /// The output to modify, e.g. DP-1.
@@ -122,6 +157,64 @@ pub enum OutputCommand {
     Vrr(VrrArgs),
     /// Change tearing settings.
     Tearing(TearingArgs),
+    /// Change the color temperature of the output.
+    ColorTemperature(ColorTemperatureArgs),
+    /// Change the brightness of the output.
+    Brightness(BrightnessArgs),
+    /// Change HDR settings.
+    Hdr(HdrArgs),
+    /// Show the contents of another output on this output.
+    ///
+    /// The output is removed from the desktop while it is mirroring.
+    Mirror(MirrorArgs),
+    /// Stop mirroring another output.
+    Unmirror,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MirrorArgs {
+    /// The output to mirror, e.g. eDP-1.
+    pub source: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ColorTemperatureArgs {
+    /// The color temperature in Kelvin, between 1000 and 25000.
+    ///
+    /// The default is 6500 which displays colors unmodified.
+    pub kelvin: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct BrightnessArgs {
+    /// The brightness, between 0.1 and 1.0.
+    ///
+    /// The default is 1.0.
+    pub brightness: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct HdrArgs {
+    #[clap(subcommand)]
+    pub command: HdrCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum HdrCommand {
+    /// Enables HDR if the display supports it.
+    Enable,
+    /// Disables HDR.
+    Disable,
+    /// Sets the luminance at which SDR content is displayed in HDR mode.
+    SetSdrReferenceWhite(SdrReferenceWhiteArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SdrReferenceWhiteArgs {
+    /// The luminance of SDR white in cd/m², between 40 and 1000.
+    ///
+    /// The default is 203.
+    pub nits: f64,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -313,11 +406,18 @@ struct Output {
     pub current_mode: Option<Mode>,
     pub modes: Vec<Mode>,
     pub non_desktop: bool,
+    pub mirror: Option<String>,
     pub vrr_capable: bool,
     pub vrr_enabled: bool,
     pub vrr_mode: VrrMode,
     pub vrr_cursor_hz: Option<f64>,
     pub tearing_mode: TearingMode,
+    pub color_temperature: u32,
+    pub brightness: f64,
+    pub hdr_capable: bool,
+    pub hdr_enabled: bool,
+    pub hdr_active: bool,
+    pub sdr_reference_white: f64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -370,6 +470,7 @@ impl Randr {
             RandrCmd::Show(args) => self.show(randr, args).await,
             RandrCmd::Card(args) => self.card(randr, args).await,
             RandrCmd::Output(args) => self.output(randr, args).await,
+            RandrCmd::Virtual(args) => self.virtual_output(randr, args).await,
         }
     }
 
@@ -546,6 +647,99 @@ impl Randr {
                     }
                 }
             }
+            OutputCommand::ColorTemperature(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the color temperature: {}", msg);
+                });
+                tc.send(jay_randr::SetColorTemperature {
+                    self_id: randr,
+                    output: &args.output,
+                    kelvin: a.kelvin,
+                });
+            }
+            OutputCommand::Brightness(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the brightness: {}", msg);
+                });
+                tc.send(jay_randr::SetBrightness {
+                    self_id: randr,
+                    output: &args.output,
+                    brightness: a.brightness,
+                });
+            }
+            OutputCommand::Hdr(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the HDR setting: {}", msg);
+                });
+                match a.command {
+                    HdrCommand::Enable | HdrCommand::Disable => {
+                        tc.send(jay_randr::SetHdrEnabled {
+                            self_id: randr,
+                            output: &args.output,
+                            enabled: matches!(a.command, HdrCommand::Enable) as _,
+                        });
+                    }
+                    HdrCommand::SetSdrReferenceWhite(a) => {
+                        tc.send(jay_randr::SetSdrReferenceWhite {
+                            self_id: randr,
+                            output: &args.output,
+                            nits: a.nits,
+                        });
+                    }
+                }
+            }
+            OutputCommand::Mirror(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not mirror the output: {}", msg);
+                });
+                tc.send(jay_randr::SetMirror {
+                    self_id: randr,
+                    output: &args.output,
+                    source: &a.source,
+                });
+            }
+            OutputCommand::Unmirror => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not stop mirroring: {}", msg);
+                });
+                tc.send(jay_randr::UnsetMirror {
+                    self_id: randr,
+                    output: &args.output,
+                });
+            }
+        }
+        tc.round_trip().await;
+    }
+
+    async fn virtual_output(self: &Rc<Self>, randr: JayRandrId, args: VirtualArgs) {
+        let tc = &self.tc;
+        match args.command {
+            VirtualCommand::Create(a) => {
+                let Some(mode) = parse_mode(&a.mode) else {
+                    fatal!("Could not parse mode `{}`", a.mode);
+                };
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not create the virtual output: {}", msg);
+                });
+                jay_randr::VirtualOutputCreated::handle(tc, randr, (), |_, msg| {
+                    println!("{}", msg.name);
+                });
+                tc.send(jay_randr::CreateVirtualOutput {
+                    self_id: randr,
+                    width: mode.width,
+                    height: mode.height,
+                    refresh_rate_millihz: mode.refresh_rate_millihz,
+                });
+            }
+            VirtualCommand::Remove(a) => {
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not remove the virtual output: {}", msg);
+                });
+                tc.send(jay_randr::RemoveVirtualOutput {
+                    self_id: randr,
+                    output: &a.output,
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -660,6 +854,15 @@ impl Randr {
             println!("        non-desktop");
             return;
         }
+        if let Some(source) = &o.mirror {
+            println!("        mirroring: {}", source);
+            if let Some(mode) = &o.current_mode {
+                print!("        mode: ");
+                self.print_mode(mode, false);
+            }
+            self.print_modes(o, modes);
+            return;
+        }
         println!("        VRR capable: {}", o.vrr_capable);
         if o.vrr_capable {
             println!("        VRR enabled: {}", o.vrr_enabled);
@@ -695,6 +898,24 @@ impl Randr {
             };
             println!("        Tearing mode: {}", mode);
         }
+        if o.color_temperature != DEFAULT_COLOR_TEMPERATURE {
+            println!("        color temperature: {}K", o.color_temperature);
+        }
+        if o.brightness != 1.0 {
+            println!("        brightness: {}", o.brightness);
+        }
+        if o.hdr_capable {
+            println!("        HDR enabled: {}", o.hdr_enabled);
+            if o.hdr_enabled != o.hdr_active {
+                println!("        HDR active: {}", o.hdr_active);
+            }
+            if o.sdr_reference_white != DEFAULT_SDR_REFERENCE_WHITE {
+                println!(
+                    "        SDR reference white: {} cd/m²",
+                    o.sdr_reference_white
+                );
+            }
+        }
         println!("        position: {} x {}", o.x, o.y);
         println!("        logical size: {} x {}", o.width, o.height);
         if let Some(mode) = &o.current_mode {
@@ -717,6 +938,10 @@ impl Randr {
             };
             println!("        transform: {}", name);
         }
+        self.print_modes(o, modes);
+    }
+
+    fn print_modes(&self, o: &Output, modes: bool) {
         if o.modes.is_not_empty() && modes {
             println!("        modes:");
             for mode in &o.modes {
@@ -783,11 +1008,18 @@ impl Randr {
                 modes: Default::default(),
                 current_mode: None,
                 non_desktop: false,
+                mirror: None,
                 vrr_capable: false,
                 vrr_enabled: false,
                 vrr_mode: VrrMode::NEVER,
                 vrr_cursor_hz: None,
                 tearing_mode: TearingMode::NEVER,
+                color_temperature: DEFAULT_COLOR_TEMPERATURE,
+                brightness: 1.0,
+                hdr_capable: false,
+                hdr_enabled: false,
+                hdr_active: false,
+                sdr_reference_white: DEFAULT_SDR_REFERENCE_WHITE,
             });
         });
         jay_randr::NonDesktopOutput::handle(tc, randr, data.clone(), |data, msg| {
@@ -808,11 +1040,50 @@ impl Randr {
                 modes: Default::default(),
                 current_mode: None,
                 non_desktop: true,
+                mirror: None,
                 vrr_capable: false,
                 vrr_enabled: false,
                 vrr_mode: VrrMode::NEVER,
                 vrr_cursor_hz: None,
                 tearing_mode: TearingMode::NEVER,
+                color_temperature: DEFAULT_COLOR_TEMPERATURE,
+                brightness: 1.0,
+                hdr_capable: false,
+                hdr_enabled: false,
+                hdr_active: false,
+                sdr_reference_white: DEFAULT_SDR_REFERENCE_WHITE,
+            });
+        });
+        jay_randr::MirrorOutput::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            c.output = Some(Output {
+                scale: 1.0,
+                width: 0,
+                height: 0,
+                x: 0,
+                y: 0,
+                transform: Transform::None,
+                manufacturer: msg.manufacturer.to_string(),
+                product: msg.product.to_string(),
+                serial_number: msg.serial_number.to_string(),
+                width_mm: msg.width_mm,
+                height_mm: msg.height_mm,
+                modes: Default::default(),
+                current_mode: None,
+                non_desktop: false,
+                mirror: Some(msg.source.to_string()),
+                vrr_capable: false,
+                vrr_enabled: false,
+                vrr_mode: VrrMode::NEVER,
+                vrr_cursor_hz: None,
+                tearing_mode: TearingMode::NEVER,
+                color_temperature: DEFAULT_COLOR_TEMPERATURE,
+                brightness: 1.0,
+                hdr_capable: false,
+                hdr_enabled: false,
+                hdr_active: false,
+                sdr_reference_white: DEFAULT_SDR_REFERENCE_WHITE,
             });
         });
         jay_randr::VrrState::handle(tc, randr, data.clone(), |data, msg| {
@@ -835,6 +1106,22 @@ impl Randr {
             let output = c.output.as_mut().unwrap();
             output.tearing_mode = TearingMode(msg.mode);
         });
+        jay_randr::ColorTransform::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.color_temperature = msg.temperature;
+            output.brightness = msg.brightness;
+        });
+        jay_randr::HdrState::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.hdr_capable = msg.capable != 0;
+            output.hdr_enabled = msg.enabled != 0;
+            output.hdr_active = msg.active != 0;
+            output.sdr_reference_white = msg.sdr_reference_white;
+        });
         jay_randr::Mode::handle(tc, randr, data.clone(), |data, msg| {
             let mut data = data.borrow_mut();
             let c = data.connectors.last_mut().unwrap();
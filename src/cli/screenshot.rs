@@ -6,8 +6,8 @@ use {
         utils::{errorfmt::ErrorFmt, queue::AsyncQueue, windows::WindowsExt},
         video::{
             dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
-            drm::Drm,
-            gbm::{GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
+            drm::{Drm, DrmError},
+            gbm::{GbmDevice, GbmError, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
         },
         wire::{
             jay_compositor::TakeScreenshot,
@@ -18,6 +18,7 @@ use {
     jay_algorithms::qoi::xrgb8888_encode_qoi,
     png::{BitDepth, ColorType, Encoder, SrgbRenderingIntent},
     std::rc::Rc,
+    thiserror::Error,
 };
 
 pub fn main(global: GlobalArgs, args: ScreenshotArgs) {
@@ -75,18 +76,44 @@ async fn run(screenshot: Rc<Screenshot>) {
 }
 
 pub fn buf_to_bytes(dma_buf_ids: &DmaBufIds, buf: &Dmabuf, format: ScreenshotFormat) -> Vec<u8> {
-    let drm = match Drm::reopen(buf.drm_dev.raw(), false) {
-        Ok(drm) => drm,
+    let image = match read_dmabuf(dma_buf_ids, buf) {
+        Ok(i) => i,
         Err(e) => {
-            fatal!("Could not open the drm device: {}", ErrorFmt(e));
-        }
-    };
-    let gbm = match GbmDevice::new(&drm) {
-        Ok(g) => g,
-        Err(e) => {
-            fatal!("Could not create a gbm device: {}", ErrorFmt(e));
+            fatal!("{}", ErrorFmt(e));
         }
     };
+    if format == ScreenshotFormat::Qoi {
+        return xrgb8888_encode_qoi(&image.data, image.width, image.height, image.stride);
+    }
+    encode_png(&image.data, image.width, image.height, image.stride)
+}
+
+#[derive(Debug, Error)]
+pub enum ScreenshotError {
+    #[error("Could not open the drm device")]
+    OpenDrm(#[source] DrmError),
+    #[error("Could not create a gbm device")]
+    CreateGbm(#[source] GbmError),
+    #[error("Could not import screenshot dmabuf")]
+    Import(#[source] GbmError),
+    #[error("Could not map dmabuf")]
+    Map(#[source] GbmError),
+}
+
+/// A screenshot in XRGB8888.
+pub struct ScreenshotImage {
+    pub width: u32,
+    pub height: u32,
+    pub stride: u32,
+    pub data: Vec<u8>,
+}
+
+pub fn read_dmabuf(
+    dma_buf_ids: &DmaBufIds,
+    buf: &Dmabuf,
+) -> Result<ScreenshotImage, ScreenshotError> {
+    let drm = Drm::reopen(buf.drm_dev.raw(), false).map_err(ScreenshotError::OpenDrm)?;
+    let gbm = GbmDevice::new(&drm).map_err(ScreenshotError::CreateGbm)?;
     let mut planes = PlaneVec::new();
     planes.push(DmaBufPlane {
         offset: buf.offset,
@@ -101,34 +128,34 @@ pub fn buf_to_bytes(dma_buf_ids: &DmaBufIds, buf: &Dmabuf, format: ScreenshotFor
         modifier: (buf.modifier_hi as u64) << 32 | (buf.modifier_lo as u64),
         planes,
     };
-    let bo = match gbm.import_dmabuf(&dmabuf, GBM_BO_USE_LINEAR | GBM_BO_USE_RENDERING) {
-        Ok(bo) => Rc::new(bo),
-        Err(e) => {
-            fatal!("Could not import screenshot dmabuf: {}", ErrorFmt(e));
-        }
-    };
-    let bo_map = match bo.map_read() {
-        Ok(map) => map,
-        Err(e) => {
-            fatal!("Could not map dmabuf: {}", ErrorFmt(e));
-        }
-    };
+    let bo = gbm
+        .import_dmabuf(&dmabuf, GBM_BO_USE_LINEAR | GBM_BO_USE_RENDERING)
+        .map_err(ScreenshotError::Import)?;
+    let bo_map = Rc::new(bo).map_read().map_err(ScreenshotError::Map)?;
+    let stride = bo_map.stride() as usize;
     let data = unsafe { bo_map.data() };
-    if format == ScreenshotFormat::Qoi {
-        return xrgb8888_encode_qoi(data, buf.width, buf.height, bo_map.stride() as u32);
-    }
+    Ok(ScreenshotImage {
+        width: buf.width,
+        height: buf.height,
+        stride: stride as u32,
+        data: data[..buf.height as usize * stride].to_vec(),
+    })
+}
 
+/// Encodes an XRGB8888 image as PNG.
+///
+/// `data` must start at the first pixel of the image. Rows are `stride` bytes apart.
+pub fn encode_png(data: &[u8], width: u32, height: u32, stride: u32) -> Vec<u8> {
     let mut out = vec![];
     {
-        let mut image_data = Vec::with_capacity((buf.width * buf.height * 4) as usize);
-        let lines = data[..(buf.height as usize * bo_map.stride() as usize)]
-            .chunks_exact(bo_map.stride() as usize);
+        let mut image_data = Vec::with_capacity((width * height * 4) as usize);
+        let lines = data.chunks(stride as usize).take(height as usize);
         for line in lines {
-            for pixel in line[..(buf.width as usize * 4)].array_chunks_ext::<4>() {
+            for pixel in line[..(width as usize * 4)].array_chunks_ext::<4>() {
                 image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
             }
         }
-        let mut encoder = Encoder::new(&mut out, buf.width, buf.height);
+        let mut encoder = Encoder::new(&mut out, width, height);
         encoder.set_color(ColorType::Rgba);
         encoder.set_depth(BitDepth::Eight);
         encoder.set_srgb(SrgbRenderingIntent::Perceptual);
@@ -45,17 +45,23 @@ mod tasks;
 
 bitflags! {
     ClientCaps: u32;
-        CAP_DATA_CONTROL_MANAGER     = 1 << 0,
-        CAP_VIRTUAL_KEYBOARD_MANAGER = 1 << 1,
-        CAP_FOREIGN_TOPLEVEL_LIST    = 1 << 2,
-        CAP_IDLE_NOTIFIER            = 1 << 3,
-        CAP_SESSION_LOCK_MANAGER     = 1 << 4,
-        CAP_JAY_COMPOSITOR           = 1 << 5,
-        CAP_LAYER_SHELL              = 1 << 6,
-        CAP_SCREENCOPY_MANAGER       = 1 << 7,
-        CAP_SEAT_MANAGER             = 1 << 8,
-        CAP_DRM_LEASE                = 1 << 9,
-        CAP_INPUT_METHOD             = 1 << 10,
+        CAP_DATA_CONTROL_MANAGER       = 1 << 0,
+        CAP_VIRTUAL_KEYBOARD_MANAGER   = 1 << 1,
+        CAP_FOREIGN_TOPLEVEL_LIST      = 1 << 2,
+        CAP_IDLE_NOTIFIER              = 1 << 3,
+        CAP_SESSION_LOCK_MANAGER       = 1 << 4,
+        CAP_JAY_COMPOSITOR             = 1 << 5,
+        CAP_LAYER_SHELL                = 1 << 6,
+        CAP_SCREENCOPY_MANAGER         = 1 << 7,
+        CAP_SEAT_MANAGER               = 1 << 8,
+        CAP_DRM_LEASE                  = 1 << 9,
+        CAP_INPUT_METHOD               = 1 << 10,
+        CAP_OUTPUT_MANAGER             = 1 << 11,
+        CAP_GAMMA_CONTROL_MANAGER      = 1 << 12,
+        CAP_IMAGE_COPY_CAPTURE_MANAGER = 1 << 13,
+        CAP_FOREIGN_TOPLEVEL_MANAGER   = 1 << 14,
+        CAP_WORKSPACE_MANAGER          = 1 << 15,
+        CAP_VIRTUAL_POINTER_MANAGER    = 1 << 16,
 }
 
 pub const CAPS_DEFAULT: ClientCaps = ClientCaps(CAP_LAYER_SHELL.0 | CAP_DRM_LEASE.0);
@@ -170,6 +176,7 @@ impl Clients {
             surfaces_by_xwayland_serial: Default::default(),
             activation_tokens: Default::default(),
             commit_timelines: Rc::new(CommitTimelines::new(&global.wait_for_sync_obj)),
+            shortcuts_inhibit_allowed: Cell::new(true),
         });
         track!(data, data);
         let display = Rc::new(WlDisplay::new(&data));
@@ -280,6 +287,7 @@ pub struct Client {
     pub surfaces_by_xwayland_serial: CopyHashMap<u64, Rc<WlSurface>>,
     pub activation_tokens: RefCell<VecDeque<ActivationToken>>,
     pub commit_timelines: Rc<CommitTimelines>,
+    pub shortcuts_inhibit_allowed: Cell<bool>,
 }
 
 pub const NUM_CACHED_SERIAL_RANGES: usize = 64;
@@ -290,6 +298,13 @@ pub struct SerialRange {
 }
 
 impl Client {
+    pub fn set_shortcuts_inhibit_allowed(&self, allowed: bool) {
+        self.shortcuts_inhibit_allowed.set(allowed);
+        for inhibitor in self.objects.shortcuts_inhibitors.lock().values() {
+            inhibitor.update();
+        }
+    }
+
     pub fn invalid_request(&self, obj: &dyn Object, request: u32) {
         log::error!(
             "Client {} sent an invalid request {} on object {} of type {}",
@@ -2,6 +2,9 @@ use {
     crate::{
         client::{Client, ClientError},
         ifs::{
+            ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
+            ext_image_capture_source_v1::ExtImageCaptureSourceV1,
+            ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
             ipc::{
                 wl_data_source::WlDataSource, zwlr_data_control_source_v1::ZwlrDataControlSourceV1,
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
@@ -18,12 +21,17 @@ use {
             wl_seat::{tablet::zwp_tablet_tool_v2::ZwpTabletToolV2, wl_pointer::WlPointer, WlSeat},
             wl_surface::{
                 xdg_surface::{xdg_popup::XdgPopup, xdg_toplevel::XdgToplevel, XdgSurface},
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
                 WlSurface,
             },
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
+            wp_image_description_v1::WpImageDescriptionV1,
             wp_linux_drm_syncobj_timeline_v1::WpLinuxDrmSyncobjTimelineV1,
             xdg_positioner::XdgPositioner,
+            xdg_toplevel_icon_v1::XdgToplevelIconV1,
             xdg_wm_base::XdgWmBase,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+            zwlr_output_mode_v1::ZwlrOutputModeV1,
         },
         object::{Object, ObjectId},
         utils::{
@@ -31,10 +39,13 @@ use {
             copyhashmap::{CopyHashMap, Locked},
         },
         wire::{
+            ExtForeignToplevelHandleV1Id, ExtImageCaptureSourceV1Id, ExtWorkspaceGroupHandleV1Id,
             JayOutputId, JayScreencastId, JayToplevelId, JayWorkspaceId, WlBufferId,
             WlDataSourceId, WlOutputId, WlPointerId, WlRegionId, WlRegistryId, WlSeatId,
-            WlSurfaceId, WpDrmLeaseConnectorV1Id, WpLinuxDrmSyncobjTimelineV1Id, XdgPopupId,
-            XdgPositionerId, XdgSurfaceId, XdgToplevelId, XdgWmBaseId, ZwlrDataControlSourceV1Id,
+            WlSurfaceId, WpDrmLeaseConnectorV1Id, WpImageDescriptionV1Id,
+            WpLinuxDrmSyncobjTimelineV1Id, XdgPopupId, XdgPositionerId, XdgSurfaceId,
+            XdgToplevelIconV1Id, XdgToplevelId, XdgWmBaseId, ZwlrDataControlSourceV1Id,
+            ZwlrOutputHeadV1Id, ZwlrOutputModeV1Id, ZwpKeyboardShortcutsInhibitorV1Id,
             ZwpPrimarySelectionSourceV1Id, ZwpTabletToolV2Id,
         },
     },
@@ -67,6 +78,16 @@ pub struct Objects {
     pub drm_lease_outputs: CopyHashMap<WpDrmLeaseConnectorV1Id, Rc<WpDrmLeaseConnectorV1>>,
     pub tablet_tools: CopyHashMap<ZwpTabletToolV2Id, Rc<ZwpTabletToolV2>>,
     pub xdg_popups: CopyHashMap<XdgPopupId, Rc<XdgPopup>>,
+    pub output_heads: CopyHashMap<ZwlrOutputHeadV1Id, Rc<ZwlrOutputHeadV1>>,
+    pub output_modes: CopyHashMap<ZwlrOutputModeV1Id, Rc<ZwlrOutputModeV1>>,
+    pub foreign_toplevel_handles:
+        CopyHashMap<ExtForeignToplevelHandleV1Id, Rc<ExtForeignToplevelHandleV1>>,
+    pub image_capture_sources: CopyHashMap<ExtImageCaptureSourceV1Id, Rc<ExtImageCaptureSourceV1>>,
+    pub shortcuts_inhibitors:
+        CopyHashMap<ZwpKeyboardShortcutsInhibitorV1Id, Rc<ZwpKeyboardShortcutsInhibitorV1>>,
+    pub workspace_groups: CopyHashMap<ExtWorkspaceGroupHandleV1Id, Rc<ExtWorkspaceGroupHandleV1>>,
+    pub image_descriptions: CopyHashMap<WpImageDescriptionV1Id, Rc<WpImageDescriptionV1>>,
+    pub toplevel_icons: CopyHashMap<XdgToplevelIconV1Id, Rc<XdgToplevelIconV1>>,
     ids: RefCell<Vec<usize>>,
 }
 
@@ -100,6 +121,14 @@ impl Objects {
             drm_lease_outputs: Default::default(),
             tablet_tools: Default::default(),
             xdg_popups: Default::default(),
+            output_heads: Default::default(),
+            output_modes: Default::default(),
+            foreign_toplevel_handles: Default::default(),
+            image_capture_sources: Default::default(),
+            shortcuts_inhibitors: Default::default(),
+            workspace_groups: Default::default(),
+            image_descriptions: Default::default(),
+            toplevel_icons: Default::default(),
             ids: RefCell::new(vec![]),
         }
     }
@@ -137,6 +166,14 @@ impl Objects {
         self.drm_lease_outputs.clear();
         self.tablet_tools.clear();
         self.xdg_popups.clear();
+        self.output_heads.clear();
+        self.output_modes.clear();
+        self.foreign_toplevel_handles.clear();
+        self.image_capture_sources.clear();
+        self.shortcuts_inhibitors.clear();
+        self.workspace_groups.clear();
+        self.image_descriptions.clear();
+        self.toplevel_icons.clear();
     }
 
     pub fn id<T>(&self, client_data: &Client) -> Result<T, ClientError>
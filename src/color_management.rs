@@ -0,0 +1,506 @@
+use std::{cell::Cell, rc::Rc};
+
+pub mod icc;
+#[cfg(test)]
+mod tests;
+
+linear_ids!(ColorDescriptionIds, ColorDescriptionId);
+
+type Mat3 = [[f64; 3]; 3];
+
+/// CIE 1931 xy chromaticity coordinates of the primaries and the white point of a color
+/// space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Primaries {
+    pub r: (f64, f64),
+    pub g: (f64, f64),
+    pub b: (f64, f64),
+    pub wp: (f64, f64),
+}
+
+const D65: (f64, f64) = (0.3127, 0.3290);
+const ILLUMINANT_C: (f64, f64) = (0.310, 0.316);
+
+impl Primaries {
+    pub const SRGB: Self = Self {
+        r: (0.64, 0.33),
+        g: (0.30, 0.60),
+        b: (0.15, 0.06),
+        wp: D65,
+    };
+    pub const PAL_M: Self = Self {
+        r: (0.67, 0.33),
+        g: (0.21, 0.71),
+        b: (0.14, 0.08),
+        wp: ILLUMINANT_C,
+    };
+    pub const PAL: Self = Self {
+        r: (0.64, 0.33),
+        g: (0.29, 0.60),
+        b: (0.15, 0.06),
+        wp: D65,
+    };
+    pub const NTSC: Self = Self {
+        r: (0.630, 0.340),
+        g: (0.310, 0.595),
+        b: (0.155, 0.070),
+        wp: D65,
+    };
+    pub const GENERIC_FILM: Self = Self {
+        r: (0.681, 0.319),
+        g: (0.243, 0.692),
+        b: (0.145, 0.049),
+        wp: ILLUMINANT_C,
+    };
+    pub const BT2020: Self = Self {
+        r: (0.708, 0.292),
+        g: (0.170, 0.797),
+        b: (0.131, 0.046),
+        wp: D65,
+    };
+    pub const CIE1931_XYZ: Self = Self {
+        r: (1.0, 0.0),
+        g: (0.0, 1.0),
+        b: (0.0, 0.0),
+        wp: (1.0 / 3.0, 1.0 / 3.0),
+    };
+    pub const DCI_P3: Self = Self {
+        r: (0.680, 0.320),
+        g: (0.265, 0.690),
+        b: (0.150, 0.060),
+        wp: (0.314, 0.351),
+    };
+    pub const DISPLAY_P3: Self = Self {
+        r: (0.680, 0.320),
+        g: (0.265, 0.690),
+        b: (0.150, 0.060),
+        wp: D65,
+    };
+    pub const ADOBE_RGB: Self = Self {
+        r: (0.64, 0.33),
+        g: (0.21, 0.71),
+        b: (0.15, 0.06),
+        wp: D65,
+    };
+
+    /// Returns whether the primaries span a triangle with a positive area and the white
+    /// point lies within the visible spectrum.
+    pub fn is_valid(&self) -> bool {
+        let [r, g, b] = [self.r, self.g, self.b];
+        let area = (g.0 - r.0) * (b.1 - r.1) - (b.0 - r.0) * (g.1 - r.1);
+        area.abs() > 1e-9 && self.wp.1 > 0.0
+    }
+
+    /// Returns the matrix that converts linear RGB values to CIE XYZ.
+    fn rgb_to_xyz(&self) -> Mat3 {
+        let xyz = |(x, y): (f64, f64)| [x / y, 1.0, (1.0 - x - y) / y];
+        let [r, g, b] = [xyz(self.r), xyz(self.g), xyz(self.b)];
+        let m = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+        let s = mul_vec(&invert(&m), &xyz(self.wp));
+        let mut res = m;
+        for row in &mut res {
+            for (col, s) in row.iter_mut().zip(s) {
+                *col *= s;
+            }
+        }
+        res
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NamedPrimaries {
+    Srgb,
+    PalM,
+    Pal,
+    Ntsc,
+    GenericFilm,
+    Bt2020,
+    Cie1931Xyz,
+    DciP3,
+    DisplayP3,
+    AdobeRgb,
+}
+
+impl NamedPrimaries {
+    pub const ALL: [Self; 10] = [
+        Self::Srgb,
+        Self::PalM,
+        Self::Pal,
+        Self::Ntsc,
+        Self::GenericFilm,
+        Self::Bt2020,
+        Self::Cie1931Xyz,
+        Self::DciP3,
+        Self::DisplayP3,
+        Self::AdobeRgb,
+    ];
+
+    pub fn primaries(self) -> Primaries {
+        match self {
+            Self::Srgb => Primaries::SRGB,
+            Self::PalM => Primaries::PAL_M,
+            Self::Pal => Primaries::PAL,
+            Self::Ntsc => Primaries::NTSC,
+            Self::GenericFilm => Primaries::GENERIC_FILM,
+            Self::Bt2020 => Primaries::BT2020,
+            Self::Cie1931Xyz => Primaries::CIE1931_XYZ,
+            Self::DciP3 => Primaries::DCI_P3,
+            Self::DisplayP3 => Primaries::DISPLAY_P3,
+            Self::AdobeRgb => Primaries::ADOBE_RGB,
+        }
+    }
+}
+
+/// The transfer function that maps the encoded values of a buffer to linear light.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferFunction {
+    Srgb,
+    ExtSrgb,
+    Bt1886,
+    Gamma22,
+    Gamma28,
+    ExtLinear,
+    St2084Pq,
+    /// A pure power function with the given exponent.
+    Power(f64),
+}
+
+pub const TF_LINEAR: u32 = 0;
+pub const TF_SRGB: u32 = 1;
+pub const TF_PQ: u32 = 2;
+pub const TF_POWER: u32 = 3;
+
+impl TransferFunction {
+    pub const NAMED: [Self; 7] = [
+        Self::Srgb,
+        Self::ExtSrgb,
+        Self::Bt1886,
+        Self::Gamma22,
+        Self::Gamma28,
+        Self::ExtLinear,
+        Self::St2084Pq,
+    ];
+
+    /// Returns the shader representation of this transfer function and its exponent.
+    fn shader_params(self) -> (u32, f32) {
+        match self {
+            Self::Srgb | Self::ExtSrgb => (TF_SRGB, 0.0),
+            Self::Bt1886 => (TF_POWER, 2.4),
+            Self::Gamma22 => (TF_POWER, 2.2),
+            Self::Gamma28 => (TF_POWER, 2.8),
+            Self::ExtLinear => (TF_LINEAR, 0.0),
+            Self::St2084Pq => (TF_PQ, 0.0),
+            Self::Power(e) => (TF_POWER, e as f32),
+        }
+    }
+
+    fn is_pq(self) -> bool {
+        self == Self::St2084Pq
+    }
+}
+
+/// Luminances in cd/m².
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Luminance {
+    pub min: f64,
+    pub max: f64,
+    pub white: f64,
+}
+
+impl Luminance {
+    pub const SRGB: Self = Self {
+        min: 0.2,
+        max: 80.0,
+        white: 80.0,
+    };
+    pub const ST2084_PQ: Self = Self {
+        min: 0.005,
+        max: 10000.0,
+        white: 203.0,
+    };
+
+    pub fn default_for(tf: TransferFunction) -> Self {
+        match tf {
+            TransferFunction::St2084Pq => Self::ST2084_PQ,
+            _ => Self::SRGB,
+        }
+    }
+}
+
+/// The luminance range of a mastering display in cd/m².
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TargetLuminance {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorDescriptionData {
+    pub named_primaries: Option<NamedPrimaries>,
+    pub primaries: Primaries,
+    pub transfer_function: TransferFunction,
+    pub luminance: Luminance,
+    pub target_primaries: Option<Primaries>,
+    pub target_luminance: Option<TargetLuminance>,
+    pub max_cll: Option<u32>,
+    pub max_fall: Option<u32>,
+}
+
+impl ColorDescriptionData {
+    pub const SRGB: Self = Self {
+        named_primaries: Some(NamedPrimaries::Srgb),
+        primaries: Primaries::SRGB,
+        transfer_function: TransferFunction::Srgb,
+        luminance: Luminance::SRGB,
+        target_primaries: None,
+        target_luminance: None,
+        max_cll: None,
+        max_fall: None,
+    };
+}
+
+/// An immutable description of the color space of a buffer or an output.
+pub struct ColorDescription {
+    pub id: ColorDescriptionId,
+    pub data: ColorDescriptionData,
+    transforms: Cell<Option<(ColorDescriptionId, Option<ColorTransform>)>>,
+}
+
+/// The parameters of the conversion between two color descriptions.
+///
+/// The shader first decodes the source values with the source transfer function, then
+/// applies the matrix to the linear values, and finally encodes them with the
+/// destination transfer function.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorTransform {
+    pub src_tf: u32,
+    pub src_exp: f32,
+    pub dst_tf: u32,
+    pub dst_exp: f32,
+    /// Row-major 3x3 matrix.
+    pub matrix: [f32; 9],
+}
+
+impl ColorDescription {
+    /// Returns the transform that converts values in this color space to values in the
+    /// target color space or `None` if no conversion is necessary.
+    pub fn transform_to(&self, target: &ColorDescription) -> Option<ColorTransform> {
+        if self.id == target.id {
+            return None;
+        }
+        if let Some((id, transform)) = self.transforms.get() {
+            if id == target.id {
+                return transform;
+            }
+        }
+        let transform = self.data.transform_to(&target.data);
+        self.transforms.set(Some((target.id, transform)));
+        transform
+    }
+}
+
+impl ColorTransform {
+    /// Applies the transform to a premultiplied color.
+    ///
+    /// This is the CPU equivalent of the shader and is used for solid fills.
+    pub fn apply(&self, [r, g, b, a]: [f32; 4]) -> [f32; 4] {
+        if a <= 0.0 {
+            return [0.0; 4];
+        }
+        let rgb = [r / a, g / a, b / a].map(|c| eotf(c, self.src_tf, self.src_exp));
+        let m = &self.matrix;
+        let rgb = [0, 1, 2].map(|row| {
+            let c = m[row * 3] * rgb[0] + m[row * 3 + 1] * rgb[1] + m[row * 3 + 2] * rgb[2];
+            inv_eotf(c, self.dst_tf, self.dst_exp) * a
+        });
+        [rgb[0], rgb[1], rgb[2], a]
+    }
+}
+
+const PQ_M1: f32 = 2610.0 / 16384.0;
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+fn eotf(c: f32, tf: u32, exp: f32) -> f32 {
+    match tf {
+        TF_SRGB => {
+            let a = c.abs();
+            let v = match a <= 0.04045 {
+                true => a / 12.92,
+                false => ((a + 0.055) / 1.055).powf(2.4),
+            };
+            v.copysign(c)
+        }
+        TF_PQ => {
+            let p = c.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+            ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1)
+        }
+        TF_POWER => c.max(0.0).powf(exp),
+        _ => c,
+    }
+}
+
+fn inv_eotf(c: f32, tf: u32, exp: f32) -> f32 {
+    match tf {
+        TF_SRGB => {
+            let a = c.abs();
+            let v = match a <= 0.0031308 {
+                true => a * 12.92,
+                false => 1.055 * a.powf(1.0 / 2.4) - 0.055,
+            };
+            v.copysign(c)
+        }
+        TF_PQ => {
+            let p = c.clamp(0.0, 1.0).powf(PQ_M1);
+            ((PQ_C1 + PQ_C2 * p) / (1.0 + PQ_C3 * p)).powf(PQ_M2)
+        }
+        TF_POWER => c.max(0.0).powf(1.0 / exp),
+        _ => c,
+    }
+}
+
+impl ColorDescriptionData {
+    /// Returns the factor by which linear values must be multiplied such that 1.0
+    /// corresponds to the reference white.
+    fn relative_luminance(&self) -> f64 {
+        let max = match self.transfer_function.is_pq() {
+            true => Luminance::ST2084_PQ.max,
+            false => self.luminance.max,
+        };
+        max / self.luminance.white
+    }
+
+    fn transform_to(&self, target: &Self) -> Option<ColorTransform> {
+        let scale = self.relative_luminance() / target.relative_luminance();
+        if self.primaries == target.primaries
+            && self.transfer_function == target.transfer_function
+            && (scale - 1.0).abs() < 1e-6
+        {
+            return None;
+        }
+        let src = self.primaries.rgb_to_xyz();
+        let adaptation = bradford(self.primaries.wp, target.primaries.wp);
+        let dst = invert(&target.primaries.rgb_to_xyz());
+        let m = mul(&dst, &mul(&adaptation, &src));
+        let mut matrix = [0.0; 9];
+        for (row, m) in m.iter().enumerate() {
+            for (col, m) in m.iter().enumerate() {
+                matrix[row * 3 + col] = (m * scale) as f32;
+            }
+        }
+        let (src_tf, src_exp) = self.transfer_function.shader_params();
+        let (dst_tf, dst_exp) = target.transfer_function.shader_params();
+        Some(ColorTransform {
+            src_tf,
+            src_exp,
+            dst_tf,
+            dst_exp,
+            matrix,
+        })
+    }
+}
+
+pub struct ColorManager {
+    ids: ColorDescriptionIds,
+    srgb: Rc<ColorDescription>,
+}
+
+impl Default for ColorManager {
+    fn default() -> Self {
+        let ids = ColorDescriptionIds::default();
+        let srgb = Rc::new(ColorDescription {
+            id: ids.next(),
+            data: ColorDescriptionData::SRGB,
+            transforms: Default::default(),
+        });
+        Self { ids, srgb }
+    }
+}
+
+impl ColorManager {
+    /// The description of buffers that don't have an image description attached.
+    pub fn srgb(&self) -> &Rc<ColorDescription> {
+        &self.srgb
+    }
+
+    pub fn get_description(&self, data: ColorDescriptionData) -> Rc<ColorDescription> {
+        if data == self.srgb.data {
+            return self.srgb.clone();
+        }
+        Rc::new(ColorDescription {
+            id: self.ids.next(),
+            data,
+            transforms: Default::default(),
+        })
+    }
+}
+
+fn mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut res = [[0.0; 3]; 3];
+    for (row, res) in res.iter_mut().enumerate() {
+        for (col, res) in res.iter_mut().enumerate() {
+            *res = (0..3).map(|i| a[row][i] * b[i][col]).sum();
+        }
+    }
+    res
+}
+
+fn mul_vec(a: &Mat3, v: &[f64; 3]) -> [f64; 3] {
+    a.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn invert(m: &Mat3) -> Mat3 {
+    let [[a, b, c], [d, e, f], [g, h, i]] = *m;
+    let det = a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g);
+    let inv = 1.0 / det;
+    [
+        [
+            (e * i - f * h) * inv,
+            (c * h - b * i) * inv,
+            (b * f - c * e) * inv,
+        ],
+        [
+            (f * g - d * i) * inv,
+            (a * i - c * g) * inv,
+            (c * d - a * f) * inv,
+        ],
+        [
+            (d * h - e * g) * inv,
+            (b * g - a * h) * inv,
+            (a * e - b * d) * inv,
+        ],
+    ]
+}
+
+/// Returns the Bradford chromatic adaptation matrix from the source to the target white
+/// point.
+fn bradford(src: (f64, f64), dst: (f64, f64)) -> Mat3 {
+    const IDENTITY: Mat3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    const BRADFORD: Mat3 = [
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ];
+    if src == dst {
+        return IDENTITY;
+    }
+    let xyz = |(x, y): (f64, f64)| [x / y, 1.0, (1.0 - x - y) / y];
+    let src = mul_vec(&BRADFORD, &xyz(src));
+    let dst = mul_vec(&BRADFORD, &xyz(dst));
+    let mut scale = IDENTITY;
+    for i in 0..3 {
+        scale[i][i] = dst[i] / src[i];
+    }
+    mul(&invert(&BRADFORD), &mul(&scale, &BRADFORD))
+}
+
+/// Converts a CIE XYZ color to xy chromaticity coordinates.
+fn xyz_to_xy(xyz: [f64; 3]) -> (f64, f64) {
+    let sum = xyz[0] + xyz[1] + xyz[2];
+    if sum == 0.0 {
+        return (0.0, 0.0);
+    }
+    (xyz[0] / sum, xyz[1] / sum)
+}
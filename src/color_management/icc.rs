@@ -0,0 +1,253 @@
+use {
+    crate::color_management::{
+        bradford, invert, mul_vec, xyz_to_xy, Mat3, Primaries, TransferFunction,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum IccError {
+    #[error("The profile is truncated")]
+    Truncated,
+    #[error("The profile does not have the ICC signature")]
+    NoSignature,
+    #[error("Only RGB profiles are supported")]
+    NotRgb,
+    #[error("Only profiles with an XYZ connection space are supported")]
+    NotXyz,
+    #[error("The profile has no {0} tag")]
+    MissingTag(&'static str),
+    #[error("The {0} tag has an unsupported type")]
+    UnsupportedTagType(&'static str),
+    #[error("The tone curves of the profile cannot be represented")]
+    UnsupportedCurve,
+    #[error("The primaries of the profile are degenerate")]
+    InvalidPrimaries,
+}
+
+/// The properties of an ICC matrix/TRC profile.
+#[derive(Debug)]
+pub struct IccProfile {
+    pub primaries: Primaries,
+    pub transfer_function: TransferFunction,
+}
+
+const D50: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+/// Parses an RGB matrix/TRC display profile.
+///
+/// LUT-based profiles are not supported. The tone curves must be equal for all channels
+/// and must be approximately the sRGB curve or a pure power function.
+pub fn parse(icc: &[u8]) -> Result<IccProfile, IccError> {
+    let parser = Parser { icc };
+    if parser.slice(36, 4)? != b"acsp" {
+        return Err(IccError::NoSignature);
+    }
+    if parser.slice(16, 4)? != b"RGB " {
+        return Err(IccError::NotRgb);
+    }
+    if parser.slice(20, 4)? != b"XYZ " {
+        return Err(IccError::NotXyz);
+    }
+    let r = parser.xyz("rXYZ")?;
+    let g = parser.xyz("gXYZ")?;
+    let b = parser.xyz("bXYZ")?;
+    let white = match parser.tag("chad")? {
+        Some(chad) => mul_vec(&invert(&parser.chad(chad)?), &D50),
+        None => match parser.tag("wtpt")? {
+            Some(_) => parser.xyz("wtpt")?,
+            None => D50,
+        },
+    };
+    let wp = xyz_to_xy(white);
+    let adaptation = bradford(xyz_to_xy(D50), wp);
+    let primary = |c: [f64; 3]| xyz_to_xy(mul_vec(&adaptation, &c));
+    let primaries = Primaries {
+        r: primary(r),
+        g: primary(g),
+        b: primary(b),
+        wp,
+    };
+    if !primaries.is_valid() {
+        return Err(IccError::InvalidPrimaries);
+    }
+    let r = parser.curve("rTRC")?;
+    let g = parser.curve("gTRC")?;
+    let b = parser.curve("bTRC")?;
+    if r != g || r != b {
+        return Err(IccError::UnsupportedCurve);
+    }
+    Ok(IccProfile {
+        primaries,
+        transfer_function: r,
+    })
+}
+
+struct Parser<'a> {
+    icc: &'a [u8],
+}
+
+impl Parser<'_> {
+    fn slice(&self, offset: usize, len: usize) -> Result<&[u8], IccError> {
+        self.icc
+            .get(offset..offset.saturating_add(len))
+            .ok_or(IccError::Truncated)
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, IccError> {
+        let s = self.slice(offset, 2)?;
+        Ok(u16::from_be_bytes([s[0], s[1]]))
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, IccError> {
+        let s = self.slice(offset, 4)?;
+        Ok(u32::from_be_bytes([s[0], s[1], s[2], s[3]]))
+    }
+
+    fn s15f16(&self, offset: usize) -> Result<f64, IccError> {
+        Ok(self.u32(offset)? as i32 as f64 / 65536.0)
+    }
+
+    fn tag(&self, name: &'static str) -> Result<Option<usize>, IccError> {
+        let count = self.u32(128)? as usize;
+        for i in 0..count {
+            let entry = 132 + 12 * i;
+            if self.slice(entry, 4)? == name.as_bytes() {
+                let offset = self.u32(entry + 4)? as usize;
+                let size = self.u32(entry + 8)? as usize;
+                self.slice(offset, size)?;
+                return Ok(Some(offset));
+            }
+        }
+        Ok(None)
+    }
+
+    fn typed_tag(
+        &self,
+        name: &'static str,
+        types: &[&[u8; 4]],
+    ) -> Result<(usize, usize), IccError> {
+        let Some(offset) = self.tag(name)? else {
+            return Err(IccError::MissingTag(name));
+        };
+        let ty = self.slice(offset, 4)?;
+        match types.iter().position(|t| ty == *t) {
+            Some(idx) => Ok((offset, idx)),
+            _ => Err(IccError::UnsupportedTagType(name)),
+        }
+    }
+
+    fn xyz(&self, name: &'static str) -> Result<[f64; 3], IccError> {
+        let (offset, _) = self.typed_tag(name, &[b"XYZ "])?;
+        Ok([
+            self.s15f16(offset + 8)?,
+            self.s15f16(offset + 12)?,
+            self.s15f16(offset + 16)?,
+        ])
+    }
+
+    fn chad(&self, offset: usize) -> Result<Mat3, IccError> {
+        if self.slice(offset, 4)? != b"sf32" {
+            return Err(IccError::UnsupportedTagType("chad"));
+        }
+        let mut m = [[0.0; 3]; 3];
+        for (i, v) in m.iter_mut().flatten().enumerate() {
+            *v = self.s15f16(offset + 8 + 4 * i)?;
+        }
+        Ok(m)
+    }
+
+    fn curve(&self, name: &'static str) -> Result<TransferFunction, IccError> {
+        let (offset, ty) = self.typed_tag(name, &[b"curv", b"para"])?;
+        let samples = if ty == 0 {
+            let count = self.u32(offset + 8)? as usize;
+            match count {
+                0 => return Ok(TransferFunction::ExtLinear),
+                1 => {
+                    let gamma = self.u16(offset + 12)? as f64 / 256.0;
+                    return Ok(TransferFunction::Power(gamma));
+                }
+                _ => {
+                    let mut table = Vec::with_capacity(count);
+                    for i in 0..count {
+                        table.push(self.u16(offset + 12 + 2 * i)? as f64 / 65535.0);
+                    }
+                    sample(|x| {
+                        let pos = x * (count - 1) as f64;
+                        let idx = (pos as usize).min(count - 2);
+                        let frac = pos - idx as f64;
+                        table[idx] * (1.0 - frac) + table[idx + 1] * frac
+                    })
+                }
+            }
+        } else {
+            let function = self.u16(offset + 8)?;
+            let num_params = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(IccError::UnsupportedTagType(name)),
+            };
+            let mut p = [0.0; 7];
+            for (i, p) in p.iter_mut().take(num_params).enumerate() {
+                *p = self.s15f16(offset + 12 + 4 * i)?;
+            }
+            let [g, a, b, c, d, e, f] = p;
+            if function == 0 {
+                return Ok(TransferFunction::Power(g));
+            }
+            sample(|x| match function {
+                1 if x >= -b / a => (a * x + b).powf(g),
+                1 => 0.0,
+                2 if x >= -b / a => (a * x + b).powf(g) + c,
+                2 => c,
+                3 if x >= d => (a * x + b).powf(g),
+                3 => c * x,
+                _ if x >= d => (a * x + b).powf(g) + e,
+                _ => c * x + f,
+            })
+        };
+        fit(&samples).ok_or(IccError::UnsupportedCurve)
+    }
+}
+
+const NUM_SAMPLES: usize = 64;
+
+fn sample(f: impl Fn(f64) -> f64) -> [f64; NUM_SAMPLES] {
+    let mut res = [0.0; NUM_SAMPLES];
+    for (i, v) in res.iter_mut().enumerate() {
+        *v = f(i as f64 / (NUM_SAMPLES - 1) as f64);
+    }
+    res
+}
+
+/// Finds a transfer function that approximates the sampled curve.
+fn fit(samples: &[f64; NUM_SAMPLES]) -> Option<TransferFunction> {
+    const TOLERANCE: f64 = 0.005;
+    let matches = |f: fn(f64, f64) -> f64, param: f64| {
+        let expected = sample(|x| f(x, param));
+        samples
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, b)| (a - b).abs() < TOLERANCE)
+    };
+    let srgb = |x: f64, _: f64| match x <= 0.04045 {
+        true => x / 12.92,
+        false => ((x + 0.055) / 1.055).powf(2.4),
+    };
+    if matches(srgb, 0.0) {
+        return Some(TransferFunction::Srgb);
+    }
+    let mid = samples[NUM_SAMPLES / 2];
+    let x = (NUM_SAMPLES / 2) as f64 / (NUM_SAMPLES - 1) as f64;
+    if mid <= 0.0 || mid >= 1.0 {
+        return None;
+    }
+    let gamma = mid.ln() / x.ln();
+    if matches(|x, g| x.powf(g), gamma) {
+        return Some(TransferFunction::Power(gamma));
+    }
+    None
+}
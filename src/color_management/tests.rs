@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn srgb_to_xyz() {
+    let m = Primaries::SRGB.rgb_to_xyz();
+    let expected = [
+        [0.4124, 0.3576, 0.1805],
+        [0.2126, 0.7152, 0.0722],
+        [0.0193, 0.1192, 0.9505],
+    ];
+    for (m, e) in m.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((m - e).abs() < 1e-3, "{m} != {e}");
+    }
+}
+
+#[test]
+fn identity_transform() {
+    let srgb = ColorDescriptionData::SRGB;
+    assert!(srgb.transform_to(&srgb).is_none());
+    let bt2020 = ColorDescriptionData {
+        named_primaries: Some(NamedPrimaries::Bt2020),
+        primaries: Primaries::BT2020,
+        ..srgb.clone()
+    };
+    let t = bt2020.transform_to(&srgb).unwrap();
+    // white stays white
+    let row_sum = |r: usize| t.matrix[r * 3..r * 3 + 3].iter().sum::<f32>();
+    for r in 0..3 {
+        assert!((row_sum(r) - 1.0).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn srgb_white_to_pq() {
+    let pq = ColorDescriptionData {
+        named_primaries: Some(NamedPrimaries::Bt2020),
+        primaries: Primaries::BT2020,
+        transfer_function: TransferFunction::St2084Pq,
+        luminance: Luminance::ST2084_PQ,
+        ..ColorDescriptionData::SRGB
+    };
+    let t = ColorDescriptionData::SRGB.transform_to(&pq).unwrap();
+    let [r, g, b, a] = t.apply([1.0, 1.0, 1.0, 1.0]);
+    // 203 cd/m² is encoded as ~0.58 in PQ
+    for c in [r, g, b] {
+        assert!((c - 0.58).abs() < 0.01, "{c}");
+    }
+    assert_eq!(a, 1.0);
+    let [r, g, b, a] = t.apply([0.5, 0.5, 0.5, 0.5]);
+    for c in [r, g, b] {
+        assert!((c - 0.29).abs() < 0.01, "{c}");
+    }
+    assert_eq!(a, 0.5);
+}
@@ -4,10 +4,12 @@ use {
     crate::{
         acceptor::{Acceptor, AcceptorError},
         async_engine::{AsyncEngine, Phase, SpawnedFuture},
-        backend::{self, Backend, Connector},
+        backend::{
+            self, Backend, Connector, DEFAULT_COLOR_TEMPERATURE, DEFAULT_SDR_REFERENCE_WHITE,
+        },
         backends::{
             dummy::{DummyBackend, DummyOutput},
-            metal, x,
+            headless, metal, wayland, x,
         },
         cli::{CliBackend, GlobalArgs, RunArgs},
         client::{ClientId, Clients},
@@ -19,6 +21,8 @@ use {
         forker,
         globals::Globals,
         ifs::{
+            ext_image_copy_capture_session_v1::perform_toplevel_image_copies,
+            ext_workspace_manager_v1::workspace_manager_done,
             jay_screencast::{perform_screencast_realloc, perform_toplevel_screencasts},
             wl_output::{OutputId, PersistentOutputState, WlOutputGlobal},
             wl_surface::{zwp_input_popup_surface_v2::input_popup_positioning, NoneSurfaceExt},
@@ -111,6 +115,8 @@ pub enum CompositorError {
 
 pub const WAYLAND_DISPLAY: &str = "WAYLAND_DISPLAY";
 pub const LIBEI_SOCKET: &str = "LIBEI_SOCKET";
+pub const I3SOCK: &str = "I3SOCK";
+pub const SWAYSOCK: &str = "SWAYSOCK";
 pub const DISPLAY: &str = "DISPLAY";
 
 const STATIC_VARS: &[(&str, &str)] = &[
@@ -173,6 +179,7 @@ fn start_compositor2(
         input_device_ids: Default::default(),
         input_device_handlers: Default::default(),
         theme: Default::default(),
+        animations: Default::default(),
         pending_container_layout: Default::default(),
         pending_container_render_data: Default::default(),
         pending_output_render_data: Default::default(),
@@ -181,12 +188,17 @@ fn start_compositor2(
         pending_input_popup_positioning: Default::default(),
         pending_toplevel_screencasts: Default::default(),
         pending_toplevel_screencast_reallocs: Default::default(),
+        pending_toplevel_image_copies: Default::default(),
+        pending_workspace_manager_done: Default::default(),
+        image_copy_sessions: Default::default(),
         dbus: Dbus::new(&engine, &ring, &run_toplevel),
         fdcloser: FdCloser::new(),
         logger: logger.clone(),
         connectors: Default::default(),
         outputs: Default::default(),
         drm_devs: Default::default(),
+        virtual_outputs: Default::default(),
+        mirrors: Default::default(),
         status: Default::default(),
         idle: IdleState {
             input: Default::default(),
@@ -225,10 +237,15 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        global_shortcuts: Default::default(),
         default_workspace_capture: Cell::new(true),
         default_gfx_api: Cell::new(GfxApi::OpenGl),
         activation_tokens: Default::default(),
         toplevel_lists: Default::default(),
+        foreign_toplevel_managers: Default::default(),
+        workspace_managers: Default::default(),
+        xdg_foreign_exports: Default::default(),
+        color_manager: Default::default(),
         dma_buf_ids: Default::default(),
         drm_feedback_ids: Default::default(),
         direct_scanout_enabled: Cell::new(true),
@@ -258,6 +275,14 @@ fn start_compositor2(
         enable_ei_acceptor: Default::default(),
         ei_clients: EiClients::new(),
         slow_ei_clients: Default::default(),
+        window_rules: Default::default(),
+        toplevels: Default::default(),
+        scratchpad: Default::default(),
+        i3_ipc_acceptor: Default::default(),
+        i3_ipc_acceptor_future: Default::default(),
+        enable_i3_ipc_acceptor: Cell::new(false),
+        i3_ipc_clients: Default::default(),
+        output_managers: Default::default(),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
@@ -313,6 +338,7 @@ async fn start_compositor3(state: Rc<State>, test_future: Option<TestFuture>) {
         state.create_seat(DEFAULT_SEAT_NAME);
     }
     state.update_ei_acceptor();
+    state.update_i3_ipc_acceptor();
 
     let _geh = start_global_event_handlers(&state, &backend);
     state.start_xwayland();
@@ -357,6 +383,8 @@ fn start_global_event_handlers(
         eng.spawn2(Phase::PostLayout, idle(state.clone(), backend.clone())),
         eng.spawn2(Phase::PostLayout, input_popup_positioning(state.clone())),
         eng.spawn2(Phase::Present, perform_toplevel_screencasts(state.clone())),
+        eng.spawn2(Phase::Present, perform_toplevel_image_copies(state.clone())),
+        eng.spawn2(Phase::PostLayout, workspace_manager_done(state.clone())),
         eng.spawn2(Phase::PostLayout, perform_screencast_realloc(state.clone())),
         eng.spawn2(Phase::PostLayout, visualize_damage(state.clone())),
         eng.spawn(tasks::handle_slow_ei_clients(state.clone())),
@@ -381,6 +409,15 @@ async fn create_backend(
             continue;
         }
         match backend {
+            CliBackend::Wayland => {
+                log::info!("Trying to create wayland backend");
+                match wayland::create(state).await {
+                    Ok(b) => return Some(b),
+                    Err(e) => {
+                        log::error!("Could not create wayland backend: {}", ErrorFmt(e));
+                    }
+                }
+            }
             CliBackend::X11 => {
                 log::info!("Trying to create X backend");
                 match x::create(state).await {
@@ -399,6 +436,15 @@ async fn create_backend(
                     }
                 }
             }
+            CliBackend::Headless => {
+                log::info!("Trying to create headless backend");
+                match headless::create(state).await {
+                    Ok(b) => return Some(b),
+                    Err(e) => {
+                        log::error!("Could not create headless backend: {}", ErrorFmt(e));
+                    }
+                }
+            }
         }
     }
     None
@@ -437,6 +483,10 @@ fn create_dummy_output(state: &Rc<State>) {
         vrr_mode: Cell::new(VrrMode::NEVER),
         vrr_cursor_hz: Default::default(),
         tearing_mode: Cell::new(&TearingMode::Never),
+        color_temperature: Cell::new(DEFAULT_COLOR_TEMPERATURE),
+        brightness: Cell::new(1.0),
+        hdr_enabled: Cell::new(false),
+        sdr_reference_white: Cell::new(DEFAULT_SDR_REFERENCE_WHITE),
     });
     let connector = Rc::new(DummyOutput {
         id: state.connector_ids.next(),
@@ -459,6 +509,9 @@ fn create_dummy_output(state: &Rc<State>) {
                 name: "Dummy".to_string(),
                 drm_dev: None,
                 async_event: Default::default(),
+                gamma_control: Default::default(),
+                color_gamma_lut: Default::default(),
+                mirror: Default::default(),
             }),
             Vec::new(),
             &backend::Mode {
@@ -492,6 +545,12 @@ fn create_dummy_output(state: &Rc<State>) {
         screencasts: Default::default(),
         hardware_cursor_needs_render: Cell::new(false),
         screencopies: Default::default(),
+        image_copy_sessions: Default::default(),
+        ext_workspace_groups: Default::default(),
+        color_description: CloneCell::new(state.color_manager.srgb().clone()),
+        color_management_outputs: Default::default(),
+        hdr_active: Cell::new(false),
+        fifo_barriers: Default::default(),
         title_visible: Cell::new(false),
         schedule,
     });
@@ -511,6 +570,7 @@ fn create_dummy_output(state: &Rc<State>) {
         visible_on_desired_output: Default::default(),
         desired_output: CloneCell::new(dummy_output.global.output_id.clone()),
         jay_workspaces: Default::default(),
+        ext_workspaces: Default::default(),
         may_capture: Cell::new(false),
         has_capture: Cell::new(false),
         title_texture: Cell::new(None),
@@ -8,6 +8,7 @@ use {
         config::handler::ConfigProxyHandler,
         ifs::wl_seat::SeatId,
         state::State,
+        tree::NodeId,
         utils::{
             clonecell::CloneCell, numcell::NumCell, ptr_ext::PtrExt, unlink_on_drop::UnlinkOnDrop,
             xrd::xrd,
@@ -23,6 +24,7 @@ use {
         input::{InputDevice, Seat, SwitchEvent},
         keyboard::{mods::Modifiers, syms::KeySym},
         video::{Connector, DrmDevice},
+        window::Window,
     },
     libloading::Library,
     std::{cell::Cell, io, mem, ptr, rc::Rc},
@@ -152,6 +154,24 @@ impl ConfigProxy {
             event,
         });
     }
+
+    pub fn new_window(&self, window: NodeId) {
+        self.send(&ServerMessage::NewWindow {
+            window: Window(window.raw() as _),
+        });
+    }
+
+    pub fn window_closed(&self, window: NodeId) {
+        self.send(&ServerMessage::WindowClosed {
+            window: Window(window.raw() as _),
+        });
+    }
+
+    pub fn window_title_changed(&self, window: NodeId) {
+        self.send(&ServerMessage::WindowTitleChanged {
+            window: Window(window.raw() as _),
+        });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -1,10 +1,13 @@
 use {
     crate::{
+        animation::{AnimationKind, BezierCurve},
         async_engine::SpawnedFuture,
         backend::{
             self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceId,
+            InputDeviceId, MAX_COLOR_TEMPERATURE, MAX_SDR_REFERENCE_WHITE, MIN_BRIGHTNESS,
+            MIN_COLOR_TEMPERATURE, MIN_SDR_REFERENCE_WHITE,
         },
+        client::{Client, ClientId},
         compositor::MAX_EXTENTS,
         config::ConfigProxy,
         ifs::wl_seat::{SeatId, WlSeatGlobal},
@@ -14,8 +17,8 @@ use {
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized, DEFAULT_FONT},
         tree::{
-            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, TearingMode, VrrMode, WsMoveConfig,
+            move_ws_to_output, ContainerLayout, ContainerNode, ContainerSplit, FloatNode, Node,
+            NodeId, NodeVisitorBase, OutputNode, TearingMode, ToplevelNode, VrrMode, WsMoveConfig,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -27,6 +30,8 @@ use {
             stack::Stack,
             timer::{TimerError, TimerFd},
         },
+        virtual_output::validate_mode,
+        window_rules::{self, WindowCriteria, WindowRuleActions},
         xkbcommon::{XkbCommonError, XkbKeymap},
     },
     bincode::Options,
@@ -44,18 +49,25 @@ use {
             },
             FocusFollowsMouseMode, InputDevice, Seat,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, ModifiedKeySym},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable},
+        theme::{
+            animations::{Animation, Curve},
+            colors::Colorable,
+            sized::Resizable,
+        },
         timer::Timer as JayTimer,
         video::{
             Connector, DrmDevice, GfxApi, TearingMode as ConfigTearingMode, Transform,
             VrrMode as ConfigVrrMode,
         },
-        Axis, Direction, Workspace,
+        client::Client as ConfigClient,
+        window::{Window, WindowActions, WindowMatch, WindowRule},
+        Axis, Direction, Layout, Workspace,
     },
     libloading::Library,
     log::Level,
+    regex::Regex,
     std::{cell::Cell, ops::Deref, rc::Rc, time::Duration},
     thiserror::Error,
     uapi::{c, fcntl_dupfd_cloexec, OwnedFd},
@@ -109,6 +121,8 @@ impl ConfigProxyHandler {
 
         self.pollables.clear();
 
+        self.state.window_rules.clear();
+
         if let Some(path) = &self.path {
             if let Err(e) = uapi::unlink(path.as_str()) {
                 log::error!("Could not unlink {}: {}", path, ErrorFmt(OsError(e.0)));
@@ -404,20 +418,24 @@ impl ConfigProxyHandler {
         self.respond(Response::GetConfigDir { dir });
     }
 
+    fn get_workspace_id(&self, name: &String) -> Workspace {
+        let id = match self.workspaces_by_name.get(name) {
+            None => {
+                let id = self.workspace_ids.fetch_add(1);
+                let name = Rc::new(name.clone());
+                self.workspaces_by_name.set(name.clone(), id);
+                self.workspaces_by_id.set(id, name);
+                id
+            }
+            Some(id) => id,
+        };
+        Workspace(id)
+    }
+
     fn handle_get_workspaces(&self) {
         let mut workspaces = vec![];
         for ws in self.state.workspaces.lock().values() {
-            let id = match self.workspaces_by_name.get(&ws.name) {
-                None => {
-                    let id = self.workspace_ids.fetch_add(1);
-                    let name = Rc::new(ws.name.clone());
-                    self.workspaces_by_name.set(name.clone(), id);
-                    self.workspaces_by_id.set(id, name);
-                    id
-                }
-                Some(id) => id,
-            };
-            workspaces.push(Workspace(id));
+            workspaces.push(self.get_workspace_id(&ws.name));
         }
         self.respond(Response::GetWorkspaces { workspaces });
     }
@@ -521,6 +539,20 @@ impl ConfigProxyHandler {
         }
     }
 
+    fn get_window(&self, window: Window) -> Result<Rc<dyn ToplevelNode>, CphError> {
+        match self.state.toplevels.get(&NodeId(window.0 as _)) {
+            Some(tl) => Ok(tl),
+            _ => Err(CphError::WindowDoesNotExist(window)),
+        }
+    }
+
+    fn get_client(&self, client: ConfigClient) -> Result<Rc<Client>, CphError> {
+        match self.state.clients.get(ClientId::from_raw(client.0)) {
+            Ok(c) => Ok(c),
+            _ => Err(CphError::ClientDoesNotExist(client)),
+        }
+    }
+
     fn get_device_handler_data(
         &self,
         device: InputDevice,
@@ -704,6 +736,216 @@ impl ConfigProxyHandler {
         self.state.update_ei_acceptor();
     }
 
+    fn handle_set_i3_ipc_socket_enabled(&self, enabled: bool) {
+        self.state.enable_i3_ipc_acceptor.set(enabled);
+        self.state.update_i3_ipc_acceptor();
+    }
+
+    fn handle_add_window_rule(
+        &self,
+        criteria: WindowMatch,
+        actions: WindowActions,
+    ) -> Result<(), CphError> {
+        let res = self.add_window_rule(criteria, actions);
+        let rule = match &res {
+            Ok(id) => WindowRule(*id),
+            Err(_) => WindowRule(0),
+        };
+        self.respond(Response::AddWindowRule { rule });
+        res.map(drop)
+    }
+
+    fn add_window_rule(
+        &self,
+        criteria: WindowMatch,
+        actions: WindowActions,
+    ) -> Result<u64, CphError> {
+        let title = match &criteria.title {
+            Some(title) => match Regex::new(title) {
+                Ok(r) => Some(r),
+                Err(e) => return Err(CphError::InvalidRegex(title.clone(), e)),
+            },
+            None => None,
+        };
+        let workspace = match actions.workspace {
+            Some(ws) => Some(self.get_workspace(ws)?),
+            None => None,
+        };
+        let id = self.id();
+        self.state.window_rules.add(window_rules::WindowRule {
+            id,
+            criteria: WindowCriteria {
+                app_id: criteria.app_id,
+                title,
+                class: criteria.class,
+                instance: criteria.instance,
+                role: criteria.role,
+                pid: criteria.pid,
+                exe: criteria.exe,
+                floating: criteria.floating,
+                dialog: criteria.dialog,
+            },
+            actions: WindowRuleActions {
+                workspace,
+                output: actions
+                    .output
+                    .map(|c| ConnectorId::from_raw(c.0 as _)),
+                floating: actions.floating,
+                size: actions.size,
+                position: actions.position,
+                fullscreen: actions.fullscreen,
+                workspace_capture: actions.workspace_capture,
+                shortcuts_inhibit: actions.shortcuts_inhibit,
+                reevaluate_on_title_change: actions.reevaluate_on_title_change,
+            },
+        });
+        Ok(id)
+    }
+
+    fn handle_remove_window_rule(&self, rule: WindowRule) {
+        self.state.window_rules.remove(rule.0);
+    }
+
+    fn handle_clear_window_rules(&self) {
+        self.state.window_rules.clear();
+    }
+
+    fn handle_get_windows(&self) {
+        let mut windows: Vec<_> = self
+            .state
+            .toplevels
+            .lock()
+            .keys()
+            .map(|id| Window(id.raw() as _))
+            .collect();
+        windows.sort_by_key(|w| w.0);
+        self.respond(Response::GetWindows { windows });
+    }
+
+    fn handle_window_exists(&self, window: Window) {
+        let exists = self.get_window(window).is_ok();
+        self.respond(Response::WindowExists { exists });
+    }
+
+    fn handle_get_window_title(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let title = tl.tl_data().title.borrow().clone();
+        self.respond(Response::GetWindowTitle { title });
+        Ok(())
+    }
+
+    fn handle_get_window_app_id(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let app_id = tl.tl_data().app_id.borrow().clone();
+        self.respond(Response::GetWindowAppId { app_id });
+        Ok(())
+    }
+
+    fn handle_get_window_client(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let client = match &tl.tl_data().client {
+            Some(c) => ConfigClient(c.id.raw()),
+            None => ConfigClient(0),
+        };
+        self.respond(Response::GetWindowClient { client });
+        Ok(())
+    }
+
+    fn handle_get_window_workspace(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let workspace = match tl.tl_data().workspace.get() {
+            Some(ws) => self.get_workspace_id(&ws.name),
+            None => Workspace(0),
+        };
+        self.respond(Response::GetWindowWorkspace { workspace });
+        Ok(())
+    }
+
+    fn handle_get_window_floating(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetWindowFloating {
+            floating: tl.tl_data().is_floating.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_window_floating(&self, window: Window, floating: bool) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.state.set_tl_floating(tl, floating);
+        Ok(())
+    }
+
+    fn handle_close_window(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        tl.tl_close();
+        Ok(())
+    }
+
+    fn handle_set_window_workspace(&self, window: Window, ws: Workspace) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let name = self.get_workspace(ws)?;
+        let workspace = match self.state.workspaces.get(name.deref()) {
+            Some(ws) => ws,
+            _ => {
+                let output = tl
+                    .tl_data()
+                    .workspace
+                    .get()
+                    .map(|ws| ws.output.get())
+                    .filter(|o| !o.is_dummy)
+                    .or_else(|| self.state.seat_queue.last().map(|s| s.get_output()));
+                match output {
+                    Some(output) if !output.is_dummy => output.create_workspace(name.deref()),
+                    _ => {
+                        log::warn!("Not moving window because there is no output");
+                        return Ok(());
+                    }
+                }
+            }
+        };
+        self.state.set_tl_workspace(tl, &workspace);
+        Ok(())
+    }
+
+    fn handle_focus_window(&self, seat: Seat, window: Window) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let tl = self.get_window(window)?;
+        if let Some(ws) = tl.tl_data().workspace.get() {
+            if !ws.visible.get() {
+                self.state.show_workspace(&seat, &ws.name);
+            }
+        }
+        seat.focus_toplevel(tl);
+        Ok(())
+    }
+
+    fn handle_client_exists(&self, client: ConfigClient) {
+        let exists = self.get_client(client).is_ok();
+        self.respond(Response::ClientExists { exists });
+    }
+
+    fn handle_get_client_pid(&self, client: ConfigClient) -> Result<(), CphError> {
+        let client = self.get_client(client)?;
+        self.respond(Response::GetClientPid {
+            pid: client.pid_info.pid,
+        });
+        Ok(())
+    }
+
+    fn handle_get_client_is_xwayland(&self, client: ConfigClient) -> Result<(), CphError> {
+        let client = self.get_client(client)?;
+        self.respond(Response::GetClientIsXwayland {
+            xwayland: client.is_xwayland,
+        });
+        Ok(())
+    }
+
+    fn handle_kill_client(&self, client: ConfigClient) -> Result<(), CphError> {
+        let client = self.get_client(client)?;
+        self.state.clients.kill(client.id);
+        Ok(())
+    }
+
     fn handle_get_workspace(&self, name: &str) {
         let name = Rc::new(name.to_owned());
         let ws = match self.workspaces_by_name.get(&name) {
@@ -946,10 +1188,10 @@ impl ConfigProxyHandler {
     }
 
     fn handle_connector_modes(&self, connector: Connector) -> Result<(), CphError> {
-        let connector = self.get_output_node(connector)?;
+        let output = self.get_output(connector)?;
         self.respond(Response::ConnectorModes {
-            modes: connector
-                .global
+            modes: output
+                .monitor_info
                 .modes
                 .iter()
                 .map(|m| WireMode {
@@ -1064,6 +1306,7 @@ impl ConfigProxyHandler {
                 let connector = self.get_output_node(c)?;
                 connector.global.persistent.vrr_mode.set(mode);
                 connector.update_presentation_type();
+                self.state.update_output_managers();
             }
             _ => self.state.default_vrr_mode.set(mode),
         }
@@ -1109,6 +1352,106 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_color_temperature(
+        &self,
+        connector: Connector,
+        kelvin: u32,
+    ) -> Result<(), CphError> {
+        if !(MIN_COLOR_TEMPERATURE..=MAX_COLOR_TEMPERATURE).contains(&kelvin) {
+            return Err(CphError::InvalidColorTemperature(kelvin));
+        }
+        let connector = self.get_output_node(connector)?;
+        connector.set_color_temperature(kelvin);
+        Ok(())
+    }
+
+    fn handle_connector_set_brightness(
+        &self,
+        connector: Connector,
+        brightness: f64,
+    ) -> Result<(), CphError> {
+        if !(MIN_BRIGHTNESS..=1.0).contains(&brightness) {
+            return Err(CphError::InvalidBrightness(brightness));
+        }
+        let connector = self.get_output_node(connector)?;
+        connector.set_brightness(brightness);
+        Ok(())
+    }
+
+    fn handle_connector_set_hdr_enabled(
+        &self,
+        connector: Connector,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output_node(connector)?;
+        connector.set_hdr_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_create_virtual_output(&self, mode: WireMode) -> Result<(), CphError> {
+        let mode = backend::Mode {
+            width: mode.width,
+            height: mode.height,
+            refresh_rate_millihz: mode.refresh_millihz,
+        };
+        if !validate_mode(mode) {
+            self.respond(Response::CreateVirtualOutput {
+                connector: Connector(0),
+            });
+            return Err(CphError::InvalidVirtualOutputMode(mode));
+        }
+        let output = self.state.create_virtual_output(mode);
+        self.respond(Response::CreateVirtualOutput {
+            connector: Connector(output.id.raw() as _),
+        });
+        Ok(())
+    }
+
+    fn handle_remove_virtual_output(&self, connector: Connector) -> Result<(), CphError> {
+        let data = self.get_connector(connector)?;
+        if !self.state.remove_virtual_output(data.connector.id()) {
+            return Err(CphError::NotAVirtualOutput(connector));
+        }
+        Ok(())
+    }
+
+    fn handle_connector_set_mirror(
+        &self,
+        connector: Connector,
+        source: Option<Connector>,
+    ) -> Result<(), CphError> {
+        let data = self.get_connector(connector)?;
+        let source = match source {
+            Some(source) => {
+                let source_data = self.get_connector(source)?;
+                let source_id = source_data.connector.id();
+                if source_id == data.connector.id() {
+                    return Err(CphError::MirrorSelf(connector));
+                }
+                if !self.state.can_mirror(data.connector.id(), source_id) {
+                    return Err(CphError::MirrorChain(connector, source));
+                }
+                Some(source_id)
+            }
+            None => None,
+        };
+        data.set_mirror(source);
+        Ok(())
+    }
+
+    fn handle_connector_set_sdr_reference_white(
+        &self,
+        connector: Connector,
+        nits: f64,
+    ) -> Result<(), CphError> {
+        if !(MIN_SDR_REFERENCE_WHITE..=MAX_SDR_REFERENCE_WHITE).contains(&nits) {
+            return Err(CphError::InvalidSdrReferenceWhite(nits));
+        }
+        let connector = self.get_output_node(connector)?;
+        connector.set_sdr_reference_white(nits);
+        Ok(())
+    }
+
     fn handle_connector_set_transform(
         &self,
         connector: Connector,
@@ -1147,6 +1490,7 @@ impl ConfigProxyHandler {
     ) -> Result<(), CphError> {
         let connector = self.get_connector(connector)?;
         connector.connector.set_enabled(enabled);
+        self.state.update_output_managers();
         Ok(())
     }
 
@@ -1210,6 +1554,20 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_layout(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetLayout {
+            layout: seat.get_layout().unwrap_or(ContainerLayout::Split).into(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_layout(&self, seat: Seat, layout: Layout) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_layout(layout.into());
+        Ok(())
+    }
+
     fn handle_get_split(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         self.respond(Response::GetSplit {
@@ -1239,6 +1597,26 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_client_shortcuts_inhibit_allowed(
+        &self,
+        client: ConfigClient,
+        allowed: bool,
+    ) -> Result<(), CphError> {
+        let client = self.get_client(client)?;
+        client.set_shortcuts_inhibit_allowed(allowed);
+        Ok(())
+    }
+
+    fn handle_seat_set_shortcuts_inhibit_escape(
+        &self,
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcuts_inhibit_escape(escape.map(|e| (e.mods, e.sym)));
+        Ok(())
+    }
+
     fn handle_remove_shortcut(
         &self,
         seat: Seat,
@@ -1336,6 +1714,24 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_move_to_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_to_scratchpad();
+        Ok(())
+    }
+
+    fn handle_show_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.show_scratchpad();
+        Ok(())
+    }
+
+    fn handle_toggle_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.toggle_scratchpad();
+        Ok(())
+    }
+
     fn handle_quit(&self) {
         log::info!("Quitting");
         self.state.ring.stop();
@@ -1463,6 +1859,50 @@ impl ConfigProxyHandler {
         self.state.damage(self.state.root.extents.get());
     }
 
+    fn get_animation(&self, animation: Animation) -> Result<AnimationKind, CphError> {
+        use jay_config::theme::animations::*;
+        let kind = match animation {
+            WINDOW_OPEN => AnimationKind::WindowOpen,
+            WINDOW_CLOSE => AnimationKind::WindowClose,
+            WINDOW_MOVE => AnimationKind::WindowMove,
+            WORKSPACE_SWITCH => AnimationKind::WorkspaceSwitch,
+            _ => return Err(CphError::UnknownAnimation(animation.0)),
+        };
+        Ok(kind)
+    }
+
+    fn handle_set_animations_enabled(&self, enabled: bool) {
+        self.state.animations.set_enabled(enabled);
+    }
+
+    fn handle_set_animation_duration(
+        &self,
+        animation: Animation,
+        duration: Duration,
+    ) -> Result<(), CphError> {
+        let kind = self.get_animation(animation)?;
+        let nsec = duration.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.state.animations.set_duration(kind, nsec);
+        Ok(())
+    }
+
+    fn handle_set_animation_curve(
+        &self,
+        animation: Animation,
+        curve: Curve,
+    ) -> Result<(), CphError> {
+        let kind = self.get_animation(animation)?;
+        let Some(curve) = BezierCurve::from_curve(curve) else {
+            return Err(CphError::InvalidCurve(curve));
+        };
+        self.state.animations.set_curve(kind, curve);
+        Ok(())
+    }
+
+    fn handle_reset_animations(&self) {
+        self.state.animations.reset();
+    }
+
     fn get_sized(&self, sized: Resizable) -> Result<ThemeSized, CphError> {
         use jay_config::theme::sized::*;
         let sized = match sized {
@@ -1919,6 +2359,109 @@ impl ConfigProxyHandler {
             ClientMessage::SetEiSocketEnabled { enabled } => {
                 self.handle_set_ei_socket_enabled(enabled)
             }
+            ClientMessage::AddWindowRule { criteria, actions } => self
+                .handle_add_window_rule(criteria, actions)
+                .wrn("add_window_rule")?,
+            ClientMessage::RemoveWindowRule { rule } => self.handle_remove_window_rule(rule),
+            ClientMessage::ClearWindowRules => self.handle_clear_window_rules(),
+            ClientMessage::GetWindows => self.handle_get_windows(),
+            ClientMessage::WindowExists { window } => self.handle_window_exists(window),
+            ClientMessage::GetWindowTitle { window } => self
+                .handle_get_window_title(window)
+                .wrn("get_window_title")?,
+            ClientMessage::GetWindowAppId { window } => self
+                .handle_get_window_app_id(window)
+                .wrn("get_window_app_id")?,
+            ClientMessage::GetWindowClient { window } => self
+                .handle_get_window_client(window)
+                .wrn("get_window_client")?,
+            ClientMessage::GetWindowWorkspace { window } => self
+                .handle_get_window_workspace(window)
+                .wrn("get_window_workspace")?,
+            ClientMessage::GetWindowFloating { window } => self
+                .handle_get_window_floating(window)
+                .wrn("get_window_floating")?,
+            ClientMessage::SetWindowFloating { window, floating } => self
+                .handle_set_window_floating(window, floating)
+                .wrn("set_window_floating")?,
+            ClientMessage::CloseWindow { window } => {
+                self.handle_close_window(window).wrn("close_window")?
+            }
+            ClientMessage::SetWindowWorkspace { window, workspace } => self
+                .handle_set_window_workspace(window, workspace)
+                .wrn("set_window_workspace")?,
+            ClientMessage::FocusWindow { seat, window } => self
+                .handle_focus_window(seat, window)
+                .wrn("focus_window")?,
+            ClientMessage::ClientExists { client } => self.handle_client_exists(client),
+            ClientMessage::GetClientPid { client } => self
+                .handle_get_client_pid(client)
+                .wrn("get_client_pid")?,
+            ClientMessage::GetClientIsXwayland { client } => self
+                .handle_get_client_is_xwayland(client)
+                .wrn("get_client_is_xwayland")?,
+            ClientMessage::KillClient { client } => {
+                self.handle_kill_client(client).wrn("kill_client")?
+            }
+            ClientMessage::MoveToScratchpad { seat } => self
+                .handle_move_to_scratchpad(seat)
+                .wrn("move_to_scratchpad")?,
+            ClientMessage::ShowScratchpad { seat } => {
+                self.handle_show_scratchpad(seat).wrn("show_scratchpad")?
+            }
+            ClientMessage::ToggleScratchpad { seat } => self
+                .handle_toggle_scratchpad(seat)
+                .wrn("toggle_scratchpad")?,
+            ClientMessage::GetLayout { seat } => self.handle_get_layout(seat).wrn("get_layout")?,
+            ClientMessage::SetLayout { seat, layout } => {
+                self.handle_set_layout(seat, layout).wrn("set_layout")?
+            }
+            ClientMessage::SetI3IpcSocketEnabled { enabled } => {
+                self.handle_set_i3_ipc_socket_enabled(enabled)
+            }
+            ClientMessage::ConnectorSetColorTemperature { connector, kelvin } => self
+                .handle_connector_set_color_temperature(connector, kelvin)
+                .wrn("connector_set_color_temperature")?,
+            ClientMessage::ConnectorSetBrightness {
+                connector,
+                brightness,
+            } => self
+                .handle_connector_set_brightness(connector, brightness)
+                .wrn("connector_set_brightness")?,
+            ClientMessage::SetClientShortcutsInhibitAllowed { client, allowed } => self
+                .handle_set_client_shortcuts_inhibit_allowed(client, allowed)
+                .wrn("set_client_shortcuts_inhibit_allowed")?,
+            ClientMessage::SeatSetShortcutsInhibitEscape { seat, escape } => self
+                .handle_seat_set_shortcuts_inhibit_escape(seat, escape)
+                .wrn("seat_set_shortcuts_inhibit_escape")?,
+            ClientMessage::ConnectorSetHdrEnabled { connector, enabled } => self
+                .handle_connector_set_hdr_enabled(connector, enabled)
+                .wrn("connector_set_hdr_enabled")?,
+            ClientMessage::ConnectorSetSdrReferenceWhite { connector, nits } => self
+                .handle_connector_set_sdr_reference_white(connector, nits)
+                .wrn("connector_set_sdr_reference_white")?,
+            ClientMessage::CreateVirtualOutput { mode } => self
+                .handle_create_virtual_output(mode)
+                .wrn("create_virtual_output")?,
+            ClientMessage::RemoveVirtualOutput { connector } => self
+                .handle_remove_virtual_output(connector)
+                .wrn("remove_virtual_output")?,
+            ClientMessage::ConnectorSetMirror { connector, source } => self
+                .handle_connector_set_mirror(connector, source)
+                .wrn("connector_set_mirror")?,
+            ClientMessage::SetAnimationsEnabled { enabled } => {
+                self.handle_set_animations_enabled(enabled)
+            }
+            ClientMessage::SetAnimationDuration {
+                animation,
+                duration,
+            } => self
+                .handle_set_animation_duration(animation, duration)
+                .wrn("set_animation_duration")?,
+            ClientMessage::SetAnimationCurve { animation, curve } => self
+                .handle_set_animation_curve(animation, curve)
+                .wrn("set_animation_curve")?,
+            ClientMessage::ResetAnimations => self.handle_reset_animations(),
         }
         Ok(())
     }
@@ -1986,6 +2529,30 @@ enum CphError {
     InvalidCursorHz(f64),
     #[error("Unknown tearing mode {0:?}")]
     UnknownTearingMode(ConfigTearingMode),
+    #[error("Window {0:?} does not exist")]
+    WindowDoesNotExist(Window),
+    #[error("Client {0:?} does not exist")]
+    ClientDoesNotExist(ConfigClient),
+    #[error("Could not parse regex `{0}`")]
+    InvalidRegex(String, #[source] regex::Error),
+    #[error("Color temperature {0}K is outside the supported range")]
+    InvalidColorTemperature(u32),
+    #[error("Brightness {0} is outside the supported range")]
+    InvalidBrightness(f64),
+    #[error("SDR reference white {0} cd/m² is outside the supported range")]
+    InvalidSdrReferenceWhite(f64),
+    #[error("Invalid virtual output mode {0:?}")]
+    InvalidVirtualOutputMode(backend::Mode),
+    #[error("Connector {0:?} is not a virtual output")]
+    NotAVirtualOutput(Connector),
+    #[error("Connector {0:?} cannot mirror itself")]
+    MirrorSelf(Connector),
+    #[error("Connector {0:?} cannot mirror {1:?} because mirrors cannot be chained")]
+    MirrorChain(Connector, Connector),
+    #[error("Animation {0} is not known")]
+    UnknownAnimation(u32),
+    #[error("{0:?} is not a valid animation curve")]
+    InvalidCurve(Curve),
 }
 
 trait WithRequestName {
@@ -1,7 +1,9 @@
 use {
     crate::{
+        client::ClientId,
         cursor::{Cursor, KnownCursor, DEFAULT_CURSOR_SIZE},
         fixed::Fixed,
+        ifs::ext_image_copy_capture_cursor_session_v1::ExtImageCopyCaptureCursorSessionV1,
         rect::Rect,
         scale::Scale,
         state::State,
@@ -10,6 +12,7 @@ use {
             clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
             hash_map_ext::HashMapExt, rc_eq::rc_eq, transform_ext::TransformExt,
         },
+        wire::ExtImageCopyCaptureCursorSessionV1Id,
     },
     std::{cell::Cell, ops::Deref, rc::Rc},
 };
@@ -30,6 +33,10 @@ pub struct CursorUserGroup {
     hardware_cursor: Cell<bool>,
     size: Cell<u32>,
     latest_output: CloneCell<Rc<OutputNode>>,
+    pub capture_sessions: CopyHashMap<
+        (ClientId, ExtImageCopyCaptureCursorSessionV1Id),
+        Rc<ExtImageCopyCaptureCursorSessionV1>,
+    >,
 }
 
 pub struct CursorUser {
@@ -64,6 +71,7 @@ impl CursorUserGroup {
             hardware_cursor: Cell::new(hardware_cursor),
             size: Cell::new(*DEFAULT_CURSOR_SIZE),
             latest_output: CloneCell::new(output),
+            capture_sessions: Default::default(),
         });
         state.add_cursor_size(*DEFAULT_CURSOR_SIZE);
         state.cursor_user_groups.set(group.id, group.clone());
@@ -95,6 +103,16 @@ impl CursorUserGroup {
         }
         self.active_id.take();
         self.active.take();
+        self.update_capture_sessions(false);
+    }
+
+    fn update_capture_sessions(&self, image_changed: bool) {
+        if self.capture_sessions.is_empty() {
+            return;
+        }
+        for session in self.capture_sessions.lock().values() {
+            session.update(image_changed);
+        }
     }
 
     pub fn latest_output(&self) -> Rc<OutputNode> {
@@ -116,6 +134,11 @@ impl CursorUserGroup {
         for user in self.users.lock().drain_values() {
             user.detach();
         }
+        for session in self.capture_sessions.clear().into_values() {
+            if let Some(session) = session.session.take() {
+                session.stop();
+            }
+        }
     }
 
     pub fn create_user(self: &Rc<Self>) -> Rc<CursorUser> {
@@ -263,6 +286,7 @@ impl CursorUser {
         if self.software_cursor() {
             self.group.damage_active();
         }
+        self.group.update_capture_sessions(true);
     }
 
     #[cfg_attr(not(feature = "it"), allow(dead_code))]
@@ -373,6 +397,9 @@ impl CursorUser {
         if self.software_cursor() {
             self.group.damage_active();
         }
+        if self.is_active() {
+            self.group.update_capture_sessions(true);
+        }
     }
 
     pub fn position(&self) -> (Fixed, Fixed) {
@@ -407,6 +434,9 @@ impl CursorUser {
         }
         self.pos.set((x, y));
         self.update_hardware_cursor_(false);
+        if self.is_active() {
+            self.group.update_capture_sessions(false);
+        }
         (x, y)
     }
 
@@ -480,6 +510,7 @@ impl CursorUser {
                             &self.group.state,
                             scale,
                             transform,
+                            &output.color_description.get(),
                         );
                         match res {
                             Ok(sync_file) => {
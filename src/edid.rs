@@ -1,6 +1,9 @@
 use {
-    crate::utils::{
-        bitflags::BitflagsExt, clonecell::UnsafeCellCloneSafe, ptr_ext::PtrExt, stack::Stack,
+    crate::{
+        color_management::Primaries,
+        utils::{
+            bitflags::BitflagsExt, clonecell::UnsafeCellCloneSafe, ptr_ext::PtrExt, stack::Stack,
+        },
     },
     bstr::{BString, ByteSlice},
     std::{
@@ -87,6 +90,21 @@ pub struct ChromaticityCoordinates {
     pub white_y: u16,
 }
 
+impl ChromaticityCoordinates {
+    /// Returns the primaries of the display or `None` if the EDID does not contain
+    /// meaningful values.
+    pub fn primaries(&self) -> Option<Primaries> {
+        let c = |v: u16| v as f64 / 1024.0;
+        let primaries = Primaries {
+            r: (c(self.red_x), c(self.red_y)),
+            g: (c(self.green_x), c(self.green_y)),
+            b: (c(self.blue_x), c(self.blue_y)),
+            wp: (c(self.white_x), c(self.white_y)),
+        };
+        primaries.is_valid().then_some(primaries)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 #[allow(dead_code)]
 pub struct EstablishedTimings {
@@ -1002,8 +1020,11 @@ impl<'a> EdidParser<'a> {
 
     fn parse_extension(&mut self) -> Result<EdidExtension, EdidError> {
         let _ctx = self.push_ctx(EdidParseContext::Extension);
-        self.read_n::<128>()?;
-        Ok(EdidExtension::Unknown)
+        let block = self.read_n::<128>()?;
+        match block[0] {
+            0x02 => Ok(EdidExtension::Cta(parse_cta_extension(block))),
+            _ => Ok(EdidExtension::Unknown),
+        }
     }
 
     fn parse(&mut self) -> Result<EdidFile, EdidError> {
@@ -1080,6 +1101,119 @@ pub struct EdidBaseBlock {
 #[derive(Debug)]
 pub enum EdidExtension {
     Unknown,
+    Cta(CtaExtension),
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[allow(dead_code)]
+pub struct Colorimetry {
+    pub xv_ycc_601: bool,
+    pub xv_ycc_709: bool,
+    pub s_ycc_601: bool,
+    pub op_ycc_601: bool,
+    pub op_rgb: bool,
+    pub bt2020_c_ycc: bool,
+    pub bt2020_ycc: bool,
+    pub bt2020_rgb: bool,
+    pub dci_p3: bool,
+}
+
+/// The HDR static metadata data block.
+///
+/// Luminances are in cd/m².
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[allow(dead_code)]
+pub struct HdrStaticMetadata {
+    pub eotf_traditional_sdr: bool,
+    pub eotf_traditional_hdr: bool,
+    pub eotf_st2084: bool,
+    pub eotf_hlg: bool,
+    pub static_metadata_type1: bool,
+    pub max_luminance: Option<f64>,
+    pub max_frame_average_luminance: Option<f64>,
+    pub min_luminance: Option<f64>,
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct CtaExtension {
+    pub revision: u8,
+    pub colorimetry: Option<Colorimetry>,
+    pub hdr_static_metadata: Option<HdrStaticMetadata>,
+}
+
+fn parse_cta_extension(block: &[u8; 128]) -> CtaExtension {
+    let mut ext = CtaExtension {
+        revision: block[1],
+        colorimetry: None,
+        hdr_static_metadata: None,
+    };
+    if ext.revision < 3 {
+        return ext;
+    }
+    let end = match block[2] {
+        d @ 4..=127 => d as usize,
+        _ => 4,
+    };
+    let mut pos = 4;
+    while pos < end {
+        let tag = block[pos] >> 5;
+        let len = (block[pos] & 0x1f) as usize;
+        pos += 1;
+        if pos + len > end {
+            break;
+        }
+        let data = &block[pos..pos + len];
+        pos += len;
+        const EXTENDED_TAG: u8 = 7;
+        if tag != EXTENDED_TAG || data.is_empty() {
+            continue;
+        }
+        match data[0] {
+            5 => ext.colorimetry = parse_colorimetry(&data[1..]),
+            6 => ext.hdr_static_metadata = parse_hdr_static_metadata(&data[1..]),
+            _ => {}
+        }
+    }
+    ext
+}
+
+fn parse_colorimetry(data: &[u8]) -> Option<Colorimetry> {
+    let a = *data.first()?;
+    let b = data.get(1).copied().unwrap_or(0);
+    Some(Colorimetry {
+        xv_ycc_601: a.contains(1 << 0),
+        xv_ycc_709: a.contains(1 << 1),
+        s_ycc_601: a.contains(1 << 2),
+        op_ycc_601: a.contains(1 << 3),
+        op_rgb: a.contains(1 << 4),
+        bt2020_c_ycc: a.contains(1 << 5),
+        bt2020_ycc: a.contains(1 << 6),
+        bt2020_rgb: a.contains(1 << 7),
+        dci_p3: b.contains(1 << 7),
+    })
+}
+
+fn parse_hdr_static_metadata(data: &[u8]) -> Option<HdrStaticMetadata> {
+    let eotfs = *data.first()?;
+    let descriptors = data.get(1).copied().unwrap_or(0);
+    let luminance = |cv: u8| 50.0 * 2.0f64.powf(cv as f64 / 32.0);
+    let max_luminance = data.get(2).filter(|&&cv| cv != 0).map(|&cv| luminance(cv));
+    let max_frame_average_luminance = data.get(3).filter(|&&cv| cv != 0).map(|&cv| luminance(cv));
+    let min_luminance = match (max_luminance, data.get(4)) {
+        (Some(max), Some(&cv)) => Some(max * (cv as f64 / 255.0).powi(2) / 100.0),
+        _ => None,
+    };
+    Some(HdrStaticMetadata {
+        eotf_traditional_sdr: eotfs.contains(1 << 0),
+        eotf_traditional_hdr: eotfs.contains(1 << 1),
+        eotf_st2084: eotfs.contains(1 << 2),
+        eotf_hlg: eotfs.contains(1 << 3),
+        static_metadata_type1: descriptors.contains(1 << 0),
+        max_luminance,
+        max_frame_average_luminance,
+        min_luminance,
+    })
 }
 
 #[derive(Debug)]
@@ -1089,6 +1223,19 @@ pub struct EdidFile {
     pub extension_blocks: Vec<EdidExtension>,
 }
 
+impl EdidFile {
+    fn cta_extensions(&self) -> impl Iterator<Item = &CtaExtension> {
+        self.extension_blocks.iter().filter_map(|e| match e {
+            EdidExtension::Cta(cta) => Some(cta),
+            _ => None,
+        })
+    }
+
+    pub fn hdr_static_metadata(&self) -> Option<HdrStaticMetadata> {
+        self.cta_extensions().find_map(|e| e.hdr_static_metadata)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum EdidError {
     #[error("Unexpected end-of-file")]
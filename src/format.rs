@@ -327,7 +327,7 @@ static ARGB2101010: &Format = &Format {
     ..default()
 };
 
-static XRGB2101010: &Format = &Format {
+pub static XRGB2101010: &Format = &Format {
     name: "xrgb2101010",
     vk_format: vk::Format::A2R10G10B10_UNORM_PACK32,
     drm: fourcc_code('X', 'R', '3', '0'),
@@ -345,7 +345,7 @@ static ABGR2101010: &Format = &Format {
     ..default()
 };
 
-static XBGR2101010: &Format = &Format {
+pub static XBGR2101010: &Format = &Format {
     name: "xbgr2101010",
     vk_format: vk::Format::A2B10G10R10_UNORM_PACK32,
     drm: fourcc_code('X', 'B', '3', '0'),
@@ -378,7 +378,7 @@ static ABGR16161616F: &Format = &Format {
     ..default()
 };
 
-static XBGR16161616F: &Format = &Format {
+pub static XBGR16161616F: &Format = &Format {
     name: "xbgr16161616f",
     vk_format: vk::Format::R16G16B16A16_SFLOAT,
     drm: fourcc_code('X', 'B', '4', 'H'),
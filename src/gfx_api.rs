@@ -1,5 +1,6 @@
 use {
     crate::{
+        color_management::{ColorDescription, ColorTransform},
         cursor::Cursor,
         damage::DamageVisualizer,
         fixed::Fixed,
@@ -179,6 +180,12 @@ pub struct CopyTexture {
     pub acquire_sync: AcquireSync,
     pub release_sync: ReleaseSync,
     pub alpha: Option<f32>,
+    /// An Nx1 texture whose red, green, and blue channels contain the gamma ramps that
+    /// are applied to the respective channels of the texture.
+    pub gamma_lut: Option<Rc<dyn GfxTexture>>,
+    /// The conversion from the color space of the texture to the color space of the
+    /// framebuffer.
+    pub color_transform: Option<ColorTransform>,
 }
 
 #[derive(Clone, Debug)]
@@ -306,6 +313,7 @@ impl dyn GfxFramebuffer {
             transform,
             fb_width: width as _,
             fb_height: height as _,
+            srgb_transform: None,
         }
     }
 
@@ -316,6 +324,28 @@ impl dyn GfxFramebuffer {
         release_sync: ReleaseSync,
         x: i32,
         y: i32,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        self.copy_texture_(texture, None, acquire_sync, release_sync, x, y)
+    }
+
+    pub fn copy_texture_with_gamma_lut(
+        &self,
+        texture: &Rc<dyn GfxTexture>,
+        gamma_lut: &Rc<dyn GfxTexture>,
+        acquire_sync: AcquireSync,
+        release_sync: ReleaseSync,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        self.copy_texture_(texture, Some(gamma_lut), acquire_sync, release_sync, 0, 0)
+    }
+
+    fn copy_texture_(
+        &self,
+        texture: &Rc<dyn GfxTexture>,
+        gamma_lut: Option<&Rc<dyn GfxTexture>>,
+        acquire_sync: AcquireSync,
+        release_sync: ReleaseSync,
+        x: i32,
+        y: i32,
     ) -> Result<Option<SyncFile>, GfxError> {
         let mut ops = self.take_render_ops();
         let scale = Scale::from_int(1);
@@ -333,6 +363,9 @@ impl dyn GfxFramebuffer {
             acquire_sync,
             release_sync,
         );
+        if let Some(GfxApiOpt::CopyTexture(ct)) = ops.last_mut() {
+            ct.gamma_lut = gamma_lut.cloned();
+        }
         let clear = self.format().has_alpha.then_some(&Color::TRANSPARENT);
         self.render(ops, clear)
     }
@@ -360,6 +393,7 @@ impl dyn GfxFramebuffer {
         render_hardware_cursor: bool,
         black_background: bool,
         transform: Transform,
+        color_description: &Rc<ColorDescription>,
         visualizer: Option<&DamageVisualizer>,
     ) -> GfxRenderPass {
         let mut ops = self.take_render_ops();
@@ -372,7 +406,13 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            color_description: color_description.clone(),
+            opacity: 1.0,
         };
+        renderer.base.srgb_transform = state
+            .color_manager
+            .srgb()
+            .transform_to(color_description);
         node.node_render(&mut renderer, 0, 0, None);
         if let Some(rect) = cursor_rect {
             let seats = state.globals.lock_seats();
@@ -419,7 +459,7 @@ impl dyn GfxFramebuffer {
         }
         let c = match black_background {
             true => Color::SOLID_BLACK,
-            false => state.theme.colors.background.get(),
+            false => renderer.base.map_color(&state.theme.colors.background.get()),
         };
         GfxRenderPass {
             ops,
@@ -440,7 +480,7 @@ impl dyn GfxFramebuffer {
         scale: Scale,
         render_hardware_cursor: bool,
     ) -> Result<Option<SyncFile>, GfxError> {
-        self.render_node(
+        let pass = self.create_render_pass(
             node,
             state,
             cursor_rect,
@@ -450,7 +490,10 @@ impl dyn GfxFramebuffer {
             render_hardware_cursor,
             node.has_fullscreen(),
             node.global.persistent.transform.get(),
-        )
+            &node.color_description.get(),
+            None,
+        );
+        self.perform_render_pass(pass)
     }
 
     pub fn render_node(
@@ -475,6 +518,7 @@ impl dyn GfxFramebuffer {
             render_hardware_cursor,
             black_background,
             transform,
+            state.color_manager.srgb(),
             None,
         );
         self.perform_render_pass(pass)
@@ -486,6 +530,7 @@ impl dyn GfxFramebuffer {
         state: &State,
         scale: Scale,
         transform: Transform,
+        color_description: &Rc<ColorDescription>,
     ) -> Result<Option<SyncFile>, GfxError> {
         let mut ops = self.take_render_ops();
         let mut renderer = Renderer {
@@ -497,7 +542,13 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            color_description: color_description.clone(),
+            opacity: 1.0,
         };
+        renderer.base.srgb_transform = state
+            .color_manager
+            .srgb()
+            .transform_to(color_description);
         cursor.render_hardware_cursor(&mut renderer);
         self.render(ops, Some(&Color::TRANSPARENT))
     }
@@ -79,8 +79,8 @@ use {
                 texture::Texture,
             },
             sys::{
-                GL_BLEND, GL_FALSE, GL_FLOAT, GL_LINEAR, GL_TEXTURE0, GL_TEXTURE_MIN_FILTER,
-                GL_TRIANGLES, GL_TRIANGLE_STRIP,
+                GL_BLEND, GL_FALSE, GL_FLOAT, GL_LINEAR, GL_TEXTURE0, GL_TEXTURE1, GL_TEXTURE_2D,
+                GL_TEXTURE_MIN_FILTER, GL_TRIANGLES, GL_TRIANGLE_STRIP,
             },
         },
         theme::Color,
@@ -330,32 +330,54 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
         (gles.glBindTexture)(target, texture.gl.tex);
         (gles.glTexParameteri)(target, GL_TEXTURE_MIN_FILTER, GL_LINEAR);
 
-        let progs = match texture.gl.external_only {
-            true => match &ctx.tex_external {
-                Some(p) => p,
-                _ => {
-                    log::error!("Trying to render an external-only texture but context does not support the required extension");
-                    return;
-                }
-            },
-            false => &ctx.tex_internal,
-        };
-        let copy_type = match tex.alpha.is_some() {
-            true => TexCopyType::Multiply,
-            false => TexCopyType::Identity,
-        };
-        let source_type = match texture.gl.format.has_alpha {
-            true => TexSourceType::HasAlpha,
-            false => TexSourceType::Opaque,
-        };
-        if (copy_type, source_type) == (TexCopyType::Identity, TexSourceType::Opaque) {
+        let gamma_lut = tex.gamma_lut.as_ref().map(|l| l.as_gl());
+        let prog = if let Some(gamma_lut) = gamma_lut {
+            if texture.gl.external_only {
+                log::error!("Trying to apply a gamma LUT to an external-only texture");
+                return;
+            }
             (gles.glDisable)(GL_BLEND);
+            let prog = &ctx.tex_gamma_lut;
+            (gles.glUseProgram)(prog.tex.prog.prog);
+
+            (gles.glActiveTexture)(GL_TEXTURE1);
+            (gles.glBindTexture)(GL_TEXTURE_2D, gamma_lut.gl.tex);
+            (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, GL_LINEAR);
+            (gles.glUniform1i)(prog.gamma_lut, 1);
+            (gles.glUniform1f)(prog.gamma_lut_size, gamma_lut.gl.width as _);
+            (gles.glActiveTexture)(GL_TEXTURE0);
+
+            &prog.tex
         } else {
-            (gles.glEnable)(GL_BLEND);
-        }
-        let prog = &progs[copy_type][source_type];
+            let progs = match texture.gl.external_only {
+                true => match &ctx.tex_external {
+                    Some(p) => p,
+                    _ => {
+                        log::error!("Trying to render an external-only texture but context does not support the required extension");
+                        return;
+                    }
+                },
+                false => &ctx.tex_internal,
+            };
+            let copy_type = match tex.alpha.is_some() {
+                true => TexCopyType::Multiply,
+                false => TexCopyType::Identity,
+            };
+            let source_type = match texture.gl.format.has_alpha {
+                true => TexSourceType::HasAlpha,
+                false => TexSourceType::Opaque,
+            };
+            if (copy_type, source_type) == (TexCopyType::Identity, TexSourceType::Opaque) {
+                (gles.glDisable)(GL_BLEND);
+            } else {
+                (gles.glEnable)(GL_BLEND);
+            }
+            let prog = &progs[copy_type][source_type];
 
-        (gles.glUseProgram)(prog.prog.prog);
+            (gles.glUseProgram)(prog.prog.prog);
+
+            prog
+        };
 
         (gles.glUniform1i)(prog.tex, 0);
 
@@ -385,6 +407,12 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
         (gles.glDisableVertexAttribArray)(prog.pos as _);
 
         (gles.glBindTexture)(target, 0);
+
+        if gamma_lut.is_some() {
+            (gles.glActiveTexture)(GL_TEXTURE1);
+            (gles.glBindTexture)(GL_TEXTURE_2D, 0);
+            (gles.glActiveTexture)(GL_TEXTURE0);
+        }
     }
 }
 
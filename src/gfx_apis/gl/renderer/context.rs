@@ -56,6 +56,12 @@ impl TexProg {
     }
 }
 
+pub(crate) struct GammaLutProg {
+    pub(crate) tex: TexProg,
+    pub(crate) gamma_lut: GLint,
+    pub(crate) gamma_lut_size: GLint,
+}
+
 #[derive(Copy, Clone, PartialEq, Enum)]
 pub(in crate::gfx_apis::gl) enum TexCopyType {
     Identity,
@@ -77,6 +83,7 @@ pub(in crate::gfx_apis::gl) struct GlRenderContext {
 
     pub(crate) tex_internal: EnumMap<TexCopyType, EnumMap<TexSourceType, TexProg>>,
     pub(crate) tex_external: Option<EnumMap<TexCopyType, EnumMap<TexSourceType, TexProg>>>,
+    pub(crate) tex_gamma_lut: GammaLutProg,
 
     pub(crate) fill_prog: GlProgram,
     pub(crate) fill_prog_pos: GLint,
@@ -148,6 +155,15 @@ impl GlRenderContext {
         } else {
             None
         };
+        let tex_gamma_lut = {
+            let tex_frac_src = format!("#define GAMMA_LUT\n{}", tex_frag);
+            let prog = GlProgram::from_shaders(ctx, tex_vert, &tex_frac_src)?;
+            GammaLutProg {
+                gamma_lut: prog.get_uniform_location(c"gamma_lut"),
+                gamma_lut_size: prog.get_uniform_location(c"gamma_lut_size"),
+                tex: TexProg::from(prog, false),
+            }
+        };
         let fill_prog = GlProgram::from_shaders(
             ctx,
             include_str!("../shaders/fill.vert.glsl"),
@@ -162,6 +178,7 @@ impl GlRenderContext {
 
             tex_internal,
             tex_external,
+            tex_gamma_lut,
 
             fill_prog_pos: fill_prog.get_attrib_location(c"pos"),
             fill_prog_color: fill_prog.get_uniform_location(c"color"),
@@ -12,8 +12,25 @@ uniform sampler2D tex;
 #ifdef ALPHA_MULTIPLIER
 uniform float alpha;
 #endif
+#ifdef GAMMA_LUT
+uniform sampler2D gamma_lut;
+uniform float gamma_lut_size;
+
+vec3 apply_gamma_lut(vec3 c) {
+	vec3 pos = (c * (gamma_lut_size - 1.0) + 0.5) / gamma_lut_size;
+	return vec3(
+		texture2D(gamma_lut, vec2(pos.r, 0.5)).r,
+		texture2D(gamma_lut, vec2(pos.g, 0.5)).g,
+		texture2D(gamma_lut, vec2(pos.b, 0.5)).b
+	);
+}
+#endif
 
 void main() {
+#ifdef GAMMA_LUT
+	gl_FragColor = vec4(apply_gamma_lut(texture2D(tex, v_texcoord).rgb), 1.0);
+#else // !GAMMA_LUT
+
 #ifdef ALPHA
 
 #ifdef ALPHA_MULTIPLIER
@@ -31,4 +48,6 @@ void main() {
 #endif // ALPHA_MULTIPLIER
 
 #endif // ALPHA
+
+#endif // GAMMA_LUT
 }
@@ -4,7 +4,7 @@ use {
         DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateFlags,
         DescriptorSetLayoutCreateInfo, DescriptorType, ShaderStageFlags,
     },
-    std::{rc::Rc, slice},
+    std::rc::Rc,
 };
 
 pub(super) struct VulkanDescriptorSetLayout {
@@ -27,15 +27,21 @@ impl VulkanDevice {
     pub(super) fn create_descriptor_set_layout(
         &self,
         sampler: &Rc<VulkanSampler>,
+        num_textures: u32,
     ) -> Result<Rc<VulkanDescriptorSetLayout>, VulkanError> {
         let immutable_sampler = [sampler.sampler];
-        let binding = DescriptorSetLayoutBinding::default()
-            .stage_flags(ShaderStageFlags::FRAGMENT)
-            .immutable_samplers(&immutable_sampler)
-            .descriptor_count(1)
-            .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER);
+        let bindings: Vec<_> = (0..num_textures)
+            .map(|binding| {
+                DescriptorSetLayoutBinding::default()
+                    .binding(binding)
+                    .stage_flags(ShaderStageFlags::FRAGMENT)
+                    .immutable_samplers(&immutable_sampler)
+                    .descriptor_count(1)
+                    .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+            })
+            .collect();
         let create_info = DescriptorSetLayoutCreateInfo::default()
-            .bindings(slice::from_ref(&binding))
+            .bindings(&bindings)
             .flags(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR);
         let layout = unsafe { self.device.create_descriptor_set_layout(&create_info, None) };
         let layout = layout.map_err(VulkanError::CreateDescriptorSetLayout)?;
@@ -15,8 +15,9 @@ use {
             pipeline::{PipelineCreateInfo, VulkanPipeline},
             semaphore::VulkanSemaphore,
             shaders::{
-                FillFragPushConstants, FillVertPushConstants, TexFragPushConstants,
-                TexVertPushConstants, VulkanShader, FILL_FRAG, FILL_VERT, TEX_FRAG,
+                FillFragPushConstants, FillVertPushConstants, TexColorFragPushConstants,
+                TexFragPushConstants, TexVertPushConstants, VulkanShader, FILL_FRAG, FILL_VERT,
+                TEX_FRAG, TEX_FRAG_COLOR_ALPHA, TEX_FRAG_COLOR_OPAQUE, TEX_FRAG_GAMMA_LUT,
                 TEX_FRAG_MULT_ALPHA, TEX_FRAG_MULT_OPAQUE, TEX_VERT,
             },
             VulkanError,
@@ -27,6 +28,7 @@ use {
         video::dmabuf::{dma_buf_export_sync_file, DMA_BUF_SYNC_READ, DMA_BUF_SYNC_WRITE},
     },
     ahash::AHashMap,
+    arrayvec::ArrayVec,
     ash::{
         vk::{
             AccessFlags2, AttachmentLoadOp, AttachmentStoreOp, BufferImageCopy,
@@ -58,6 +60,8 @@ pub struct VulkanRenderer {
     pub(super) device: Rc<VulkanDevice>,
     pub(super) fill_pipeline: Rc<VulkanPipeline>,
     pub(super) tex_pipelines: EnumMap<TexCopyType, EnumMap<TexSourceType, Rc<VulkanPipeline>>>,
+    pub(super) gamma_lut_pipeline: Rc<VulkanPipeline>,
+    pub(super) color_transform_pipelines: EnumMap<TexSourceType, Rc<VulkanPipeline>>,
     pub(super) command_pool: Rc<VulkanCommandPool>,
     pub(super) command_buffers: Stack<Rc<VulkanCommandBuffer>>,
     pub(super) wait_semaphores: Stack<Rc<VulkanSemaphore>>,
@@ -121,7 +125,7 @@ impl VulkanDevice {
             },
         )?;
         let sampler = self.create_sampler()?;
-        let tex_descriptor_set_layout = self.create_descriptor_set_layout(&sampler)?;
+        let tex_descriptor_set_layout = self.create_descriptor_set_layout(&sampler, 1)?;
         let tex_vert_shader = self.create_shader(TEX_VERT)?;
         let tex_frag_shader = self.create_shader(TEX_FRAG)?;
         let tex_frag_mult_opaque_shader = self.create_shader(TEX_FRAG_MULT_OPAQUE)?;
@@ -146,6 +150,27 @@ impl VulkanDevice {
         let tex_alpha_pipeline = create_tex_pipeline(true)?;
         let tex_mult_opaque_pipeline = create_tex_mult_pipeline(&tex_frag_mult_opaque_shader)?;
         let tex_mult_alpha_pipeline = create_tex_mult_pipeline(&tex_frag_mult_alpha_shader)?;
+        let gamma_lut_pipeline =
+            self.create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
+                vert: tex_vert_shader.clone(),
+                frag: self.create_shader(TEX_FRAG_GAMMA_LUT)?,
+                alpha: false,
+                frag_descriptor_set_layout: Some(self.create_descriptor_set_layout(&sampler, 2)?),
+            })?;
+        let create_color_transform_pipeline = |frag: &[u8]| {
+            self.create_pipeline::<TexVertPushConstants, TexColorFragPushConstants>(
+                PipelineCreateInfo {
+                    vert: tex_vert_shader.clone(),
+                    frag: self.create_shader(frag)?,
+                    alpha: true,
+                    frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
+                },
+            )
+        };
+        let color_transform_pipelines = enum_map! {
+            TexSourceType::HasAlpha => create_color_transform_pipeline(TEX_FRAG_COLOR_ALPHA)?,
+            TexSourceType::Opaque => create_color_transform_pipeline(TEX_FRAG_COLOR_OPAQUE)?,
+        };
         let command_pool = self.create_command_pool()?;
         let formats: AHashMap<u32, _> = self
             .formats
@@ -186,6 +211,8 @@ impl VulkanDevice {
                     TexSourceType::Opaque => tex_mult_opaque_pipeline.clone(),
                 },
             },
+            gamma_lut_pipeline,
+            color_transform_pipelines,
             command_pool,
             command_buffers: Default::default(),
             wait_semaphores: Default::default(),
@@ -220,6 +247,18 @@ impl VulkanRenderer {
                     acquire_sync: c.acquire_sync.clone(),
                     release_sync: c.release_sync,
                 });
+                if let Some(gamma_lut) = &c.gamma_lut {
+                    let tex = gamma_lut.clone().into_vk(&self.device.device);
+                    if let VulkanImageMemory::DmaBuf(_) = &tex.ty {
+                        memory.sample.push(tex.clone())
+                    }
+                    memory.textures.push(UsedTexture {
+                        tex,
+                        resv: None,
+                        acquire_sync: AcquireSync::Unnecessary,
+                        release_sync: ReleaseSync::None,
+                    });
+                }
             }
         }
     }
@@ -392,7 +431,13 @@ impl VulkanRenderer {
                         true => TexSourceType::HasAlpha,
                         false => TexSourceType::Opaque,
                     };
-                    let pipeline = &self.tex_pipelines[copy_type][source_type];
+                    let pipeline = if c.gamma_lut.is_some() {
+                        &self.gamma_lut_pipeline
+                    } else if c.color_transform.is_some() {
+                        &self.color_transform_pipelines[source_type]
+                    } else {
+                        &self.tex_pipelines[copy_type][source_type]
+                    };
                     bind(pipeline);
                     let vert = TexVertPushConstants {
                         pos: c.target.to_points(),
@@ -401,16 +446,33 @@ impl VulkanRenderer {
                     let image_info = DescriptorImageInfo::default()
                         .image_view(tex.texture_view)
                         .image_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL);
-                    let write_descriptor_set = WriteDescriptorSet::default()
-                        .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
-                        .image_info(slice::from_ref(&image_info));
+                    let gamma_lut_info = c.gamma_lut.as_ref().map(|lut| {
+                        DescriptorImageInfo::default()
+                            .image_view(lut.as_vk(&self.device.device).texture_view)
+                            .image_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    });
+                    let mut write_descriptor_sets = ArrayVec::<_, 2>::new();
+                    write_descriptor_sets.push(
+                        WriteDescriptorSet::default()
+                            .dst_binding(0)
+                            .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+                            .image_info(slice::from_ref(&image_info)),
+                    );
+                    if let Some(gamma_lut_info) = &gamma_lut_info {
+                        write_descriptor_sets.push(
+                            WriteDescriptorSet::default()
+                                .dst_binding(1)
+                                .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+                                .image_info(slice::from_ref(gamma_lut_info)),
+                        );
+                    }
                     unsafe {
                         self.device.push_descriptor.cmd_push_descriptor_set(
                             buf,
                             PipelineBindPoint::GRAPHICS,
                             pipeline.pipeline_layout,
                             0,
-                            slice::from_ref(&write_descriptor_set),
+                            &write_descriptor_sets,
                         );
                         dev.cmd_push_constants(
                             buf,
@@ -419,7 +481,23 @@ impl VulkanRenderer {
                             0,
                             uapi::as_bytes(&vert),
                         );
-                        if let Some(alpha) = c.alpha {
+                        if let (None, Some(ct)) = (&c.gamma_lut, &c.color_transform) {
+                            let frag = TexColorFragPushConstants {
+                                alpha: c.alpha.unwrap_or(1.0),
+                                src_tf: ct.src_tf,
+                                dst_tf: ct.dst_tf,
+                                src_exp: ct.src_exp,
+                                dst_exp: ct.dst_exp,
+                                matrix: ct.matrix,
+                            };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                mem::size_of_val(&vert) as _,
+                                uapi::as_bytes(&frag),
+                            );
+                        } else if let Some(alpha) = c.alpha {
                             let frag = TexFragPushConstants { alpha };
                             dev.cmd_push_constants(
                                 buf,
@@ -13,6 +13,12 @@ pub const TEX_FRAG_MULT_OPAQUE: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+opaque.spv"));
 pub const TEX_FRAG_MULT_ALPHA: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+alpha.spv"));
+pub const TEX_FRAG_GAMMA_LUT: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.gamma_lut.spv"));
+pub const TEX_FRAG_COLOR_OPAQUE: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.color+opaque.spv"));
+pub const TEX_FRAG_COLOR_ALPHA: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.color+alpha.spv"));
 
 pub struct VulkanShader {
     pub(super) device: Rc<VulkanDevice>,
@@ -48,8 +54,20 @@ pub struct TexFragPushConstants {
     pub alpha: f32,
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TexColorFragPushConstants {
+    pub alpha: f32,
+    pub src_tf: u32,
+    pub dst_tf: u32,
+    pub src_exp: f32,
+    pub dst_exp: f32,
+    pub matrix: [f32; 9],
+}
+
 unsafe impl Packed for TexVertPushConstants {}
 unsafe impl Packed for TexFragPushConstants {}
+unsafe impl Packed for TexColorFragPushConstants {}
 
 impl VulkanDevice {
     pub(super) fn create_shader(
@@ -1,16 +1,104 @@
 #version 450
 
-#ifdef ALPHA_MULTIPLIER
+#if defined(COLOR_TRANSFORM)
+layout(push_constant, std430) uniform Data {
+	layout(offset = 64) float mul;
+	uint src_tf;
+	uint dst_tf;
+	float src_exp;
+	float dst_exp;
+	float matrix[9];
+} data;
+#elif defined(ALPHA_MULTIPLIER)
 layout(push_constant, std430) uniform Data {
 	layout(offset = 64) float mul;
 } data;
 #endif
 layout(set = 0, binding = 0) uniform sampler2D tex;
+#ifdef GAMMA_LUT
+layout(set = 0, binding = 1) uniform sampler2D gamma_lut;
+#endif
 layout(location = 0) in vec2 tex_pos;
 layout(location = 0) out vec4 out_color;
 
+#ifdef GAMMA_LUT
+vec3 apply_gamma_lut(vec3 c) {
+	float size = float(textureSize(gamma_lut, 0).x);
+	vec3 pos = (c * (size - 1.0) + 0.5) / size;
+	return vec3(
+		textureLod(gamma_lut, vec2(pos.r, 0.5), 0).r,
+		textureLod(gamma_lut, vec2(pos.g, 0.5), 0).g,
+		textureLod(gamma_lut, vec2(pos.b, 0.5), 0).b
+	);
+}
+#endif
+
+#ifdef COLOR_TRANSFORM
+// Must be kept in sync with the TF_* constants in color_management.rs.
+#define TF_LINEAR 0
+#define TF_SRGB 1
+#define TF_PQ 2
+#define TF_POWER 3
+
+#define PQ_M1 0.1593017578125
+#define PQ_M2 78.84375
+#define PQ_C1 0.8359375
+#define PQ_C2 18.8515625
+#define PQ_C3 18.6875
+
+vec3 eotf(vec3 c, uint tf, float exp) {
+	if (tf == TF_SRGB) {
+		vec3 a = abs(c);
+		vec3 lo = a / 12.92;
+		vec3 hi = pow((a + 0.055) / 1.055, vec3(2.4));
+		return sign(c) * mix(hi, lo, lessThanEqual(a, vec3(0.04045)));
+	} else if (tf == TF_PQ) {
+		vec3 p = pow(clamp(c, 0.0, 1.0), vec3(1.0 / PQ_M2));
+		return pow(max(p - PQ_C1, 0.0) / (PQ_C2 - PQ_C3 * p), vec3(1.0 / PQ_M1));
+	} else if (tf == TF_POWER) {
+		return pow(max(c, 0.0), vec3(exp));
+	}
+	return c;
+}
+
+vec3 inv_eotf(vec3 c, uint tf, float exp) {
+	if (tf == TF_SRGB) {
+		vec3 a = abs(c);
+		vec3 lo = a * 12.92;
+		vec3 hi = 1.055 * pow(a, vec3(1.0 / 2.4)) - 0.055;
+		return sign(c) * mix(hi, lo, lessThanEqual(a, vec3(0.0031308)));
+	} else if (tf == TF_PQ) {
+		vec3 p = pow(clamp(c, 0.0, 1.0), vec3(PQ_M1));
+		return pow((PQ_C1 + PQ_C2 * p) / (1.0 + PQ_C3 * p), vec3(PQ_M2));
+	} else if (tf == TF_POWER) {
+		return pow(max(c, 0.0), vec3(1.0 / exp));
+	}
+	return c;
+}
+
+vec4 apply_color_transform(vec4 c) {
+	vec3 rgb = c.a > 0.0 ? c.rgb / c.a : vec3(0.0);
+	rgb = eotf(rgb, data.src_tf, data.src_exp);
+	mat3 m = mat3(
+		data.matrix[0], data.matrix[3], data.matrix[6],
+		data.matrix[1], data.matrix[4], data.matrix[7],
+		data.matrix[2], data.matrix[5], data.matrix[8]
+	);
+	rgb = inv_eotf(m * rgb, data.dst_tf, data.dst_exp);
+	return vec4(rgb * c.a, c.a);
+}
+#endif
+
 void main() {
-#ifdef ALPHA_MULTIPLIER
+#if defined(GAMMA_LUT)
+	out_color = vec4(apply_gamma_lut(textureLod(tex, tex_pos, 0).rgb), 1.0);
+#elif defined(COLOR_TRANSFORM)
+	vec4 c = textureLod(tex, tex_pos, 0);
+#ifndef ALPHA
+	c.a = 1.0;
+#endif
+	out_color = apply_color_transform(c) * data.mul;
+#elif defined(ALPHA_MULTIPLIER)
 #ifdef ALPHA
 	out_color = textureLod(tex, tex_pos, 0) * data.mul;
 #endif // !ALPHA
@@ -3,9 +3,13 @@ use {
         backend::Backend,
         client::{Client, ClientCaps},
         ifs::{
+            ext_foreign_toplevel_image_capture_source_manager_v1::ExtForeignToplevelImageCaptureSourceManagerV1Global,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1Global,
             ext_idle_notifier_v1::ExtIdleNotifierV1Global,
+            ext_image_copy_capture_manager_v1::ExtImageCopyCaptureManagerV1Global,
+            ext_output_image_capture_source_manager_v1::ExtOutputImageCaptureSourceManagerV1Global,
             ext_session_lock_manager_v1::ExtSessionLockManagerV1Global,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1Global,
             ipc::{
                 wl_data_device_manager::WlDataDeviceManagerGlobal,
                 zwlr_data_control_manager_v1::ZwlrDataControlManagerV1Global,
@@ -24,6 +28,7 @@ use {
                     zwp_input_method_manager_v2::ZwpInputMethodManagerV2Global,
                     zwp_text_input_manager_v3::ZwpTextInputManagerV3Global,
                 },
+                zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1Global,
                 zwp_pointer_constraints_v1::ZwpPointerConstraintsV1Global,
                 zwp_pointer_gestures_v1::ZwpPointerGesturesV1Global,
                 zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1Global,
@@ -34,8 +39,11 @@ use {
             wl_subcompositor::WlSubcompositorGlobal,
             wl_surface::xwayland_shell_v1::XwaylandShellV1Global,
             wp_alpha_modifier_v1::WpAlphaModifierV1Global,
+            wp_color_manager_v1::WpColorManagerV1Global,
+            wp_commit_timing_manager_v1::WpCommitTimingManagerV1Global,
             wp_content_type_manager_v1::WpContentTypeManagerV1Global,
             wp_cursor_shape_manager_v1::WpCursorShapeManagerV1Global,
+            wp_fifo_manager_v1::WpFifoManagerV1Global,
             wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1Global,
             wp_presentation::WpPresentationGlobal,
             wp_security_context_manager_v1::WpSecurityContextManagerV1Global,
@@ -43,12 +51,21 @@ use {
             wp_tearing_control_manager_v1::WpTearingControlManagerV1Global,
             wp_viewporter::WpViewporterGlobal,
             xdg_activation_v1::XdgActivationV1Global,
+            xdg_foreign::{
+                zxdg_exporter_v1::ZxdgExporterV1Global, zxdg_exporter_v2::ZxdgExporterV2Global,
+                zxdg_importer_v1::ZxdgImporterV1Global, zxdg_importer_v2::ZxdgImporterV2Global,
+            },
             xdg_toplevel_drag_manager_v1::XdgToplevelDragManagerV1Global,
+            xdg_toplevel_icon_manager_v1::XdgToplevelIconManagerV1Global,
             xdg_wm_base::XdgWmBaseGlobal,
             xdg_wm_dialog_v1::XdgWmDialogV1Global,
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1Global,
+            zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1Global,
             zwlr_layer_shell_v1::ZwlrLayerShellV1Global,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1Global,
             zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1Global,
             zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1Global,
+            zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1Global,
             zxdg_decoration_manager_v1::ZxdgDecorationManagerV1Global,
             zxdg_output_manager_v1::ZxdgOutputManagerV1Global,
         },
@@ -184,8 +201,10 @@ impl Globals {
         add_singleton!(XdgActivationV1Global);
         add_singleton!(ExtForeignToplevelListV1Global);
         add_singleton!(ZwpIdleInhibitManagerV1Global);
+        add_singleton!(ZwpKeyboardShortcutsInhibitManagerV1Global);
         add_singleton!(ExtIdleNotifierV1Global);
         add_singleton!(XdgToplevelDragManagerV1Global);
+        add_singleton!(XdgToplevelIconManagerV1Global);
         add_singleton!(ZwlrDataControlManagerV1Global);
         add_singleton!(WpAlphaModifierV1Global);
         add_singleton!(ZwpVirtualKeyboardManagerV1Global);
@@ -197,6 +216,21 @@ impl Globals {
         add_singleton!(ZwpPointerGesturesV1Global);
         add_singleton!(ZwpTabletManagerV2Global);
         add_singleton!(JayDamageTrackingGlobal);
+        add_singleton!(ZwlrOutputManagerV1Global);
+        add_singleton!(ZwlrGammaControlManagerV1Global);
+        add_singleton!(ZwlrForeignToplevelManagerV1Global);
+        add_singleton!(ExtOutputImageCaptureSourceManagerV1Global);
+        add_singleton!(ExtForeignToplevelImageCaptureSourceManagerV1Global);
+        add_singleton!(ExtImageCopyCaptureManagerV1Global);
+        add_singleton!(ExtWorkspaceManagerV1Global);
+        add_singleton!(ZwlrVirtualPointerManagerV1Global);
+        add_singleton!(ZxdgExporterV1Global);
+        add_singleton!(ZxdgExporterV2Global);
+        add_singleton!(ZxdgImporterV1Global);
+        add_singleton!(ZxdgImporterV2Global);
+        add_singleton!(WpColorManagerV1Global);
+        add_singleton!(WpFifoManagerV1Global);
+        add_singleton!(WpCommitTimingManagerV1Global);
     }
 
     pub fn add_backend_singletons(&self, backend: &Rc<dyn Backend>) {
@@ -0,0 +1,85 @@
+use {
+    crate::{
+        i3_ipc::i3_ipc_json::{binding_event, output_event, window_event, workspace_event},
+        ifs::wl_seat::WlSeatGlobal,
+        state::State,
+        tree::{ToplevelNode, WorkspaceNode},
+    },
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
+    serde::Serialize,
+    std::rc::Rc,
+};
+pub use {
+    i3_ipc_client::I3IpcClients,
+    i3_ipc_json::{I3WindowChange, I3WorkspaceChange},
+};
+
+pub mod i3_ipc_acceptor;
+mod i3_ipc_client;
+mod i3_ipc_command;
+mod i3_ipc_json;
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+
+const RUN_COMMAND: u32 = 0;
+const GET_WORKSPACES: u32 = 1;
+const SUBSCRIBE: u32 = 2;
+const GET_OUTPUTS: u32 = 3;
+const GET_TREE: u32 = 4;
+const GET_VERSION: u32 = 7;
+
+const EVENT_WORKSPACE: u32 = 0x80000000;
+const EVENT_OUTPUT: u32 = 0x80000001;
+const EVENT_WINDOW: u32 = 0x80000003;
+const EVENT_BINDING: u32 = 0x80000005;
+
+fn event_mask(event: u32) -> u32 {
+    1 << (event & 0x1f)
+}
+
+fn default_seat(state: &State) -> Option<Rc<WlSeatGlobal>> {
+    state
+        .globals
+        .seats
+        .lock()
+        .values()
+        .min_by_key(|s| s.id().raw())
+        .cloned()
+}
+
+impl I3IpcClients {
+    fn emit<T: Serialize, F: FnOnce() -> T>(&self, event: u32, f: F) {
+        if !self.any_subscribed(event) {
+            return;
+        }
+        let payload = match serde_json::to_vec(&f()) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Could not serialize i3 IPC event: {}", e);
+                return;
+            }
+        };
+        self.broadcast(event, &payload);
+    }
+
+    pub fn workspace_event(
+        &self,
+        change: I3WorkspaceChange,
+        current: &Rc<WorkspaceNode>,
+        old: Option<&Rc<WorkspaceNode>>,
+    ) {
+        self.emit(EVENT_WORKSPACE, || workspace_event(change, current, old));
+    }
+
+    pub fn window_event(&self, change: I3WindowChange, tl: &Rc<dyn ToplevelNode>) {
+        self.emit(EVENT_WINDOW, || window_event(change, tl));
+    }
+
+    pub fn output_event(&self) {
+        self.emit(EVENT_OUTPUT, output_event);
+    }
+
+    pub fn binding_event(&self, mods: Modifiers, sym: KeySym) {
+        self.emit(EVENT_BINDING, || binding_event(mods, sym));
+    }
+}
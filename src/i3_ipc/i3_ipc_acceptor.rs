@@ -0,0 +1,148 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        state::State,
+        utils::{errorfmt::ErrorFmt, oserror::OsError, xrd::xrd},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+    uapi::{c, format_ustr, Errno, OwnedFd, Ustring},
+};
+
+#[derive(Debug, Error)]
+pub enum I3IpcAcceptorError {
+    #[error("XDG_RUNTIME_DIR is not set")]
+    XrdNotSet,
+    #[error("XDG_RUNTIME_DIR ({0:?}) is too long to form a unix socket address")]
+    XrdTooLong(String),
+    #[error("Could not create an i3 IPC socket")]
+    SocketFailed(#[source] OsError),
+    #[error("Could not stat the existing socket")]
+    SocketStat(#[source] OsError),
+    #[error("Could not start listening for incoming connections")]
+    ListenFailed(#[source] OsError),
+    #[error("Could not open the lock file")]
+    OpenLockFile(#[source] OsError),
+    #[error("Could not lock the lock file")]
+    LockLockFile(#[source] OsError),
+    #[error("Could not bind the socket to an address")]
+    BindFailed(#[source] OsError),
+    #[error("All i3 IPC addresses in the range 0..1000 are already in use")]
+    AddressesInUse,
+}
+
+pub struct I3IpcAcceptor {
+    socket: I3IpcAllocatedSocket,
+}
+
+struct I3IpcAllocatedSocket {
+    // /run/user/1000/jay-i3-ipc-x.sock
+    path: Ustring,
+    insecure: Rc<OwnedFd>,
+    // /run/user/1000/jay-i3-ipc-x.sock.lock
+    lock_path: Ustring,
+    _lock_fd: OwnedFd,
+}
+
+impl Drop for I3IpcAllocatedSocket {
+    fn drop(&mut self) {
+        let _ = uapi::unlink(&self.path);
+        let _ = uapi::unlink(&self.lock_path);
+    }
+}
+
+fn bind_socket(
+    insecure: &Rc<OwnedFd>,
+    xrd: &str,
+    id: u32,
+) -> Result<I3IpcAllocatedSocket, I3IpcAcceptorError> {
+    let mut addr: c::sockaddr_un = uapi::pod_zeroed();
+    addr.sun_family = c::AF_UNIX as _;
+    let path = format_ustr!("{}/jay-i3-ipc-{}.sock", xrd, id);
+    let lock_path = format_ustr!("{}.lock", path.display());
+    if path.len() + 1 > addr.sun_path.len() {
+        return Err(I3IpcAcceptorError::XrdTooLong(xrd.to_string()));
+    }
+    let lock_fd = match uapi::open(&*lock_path, c::O_CREAT | c::O_CLOEXEC | c::O_RDWR, 0o644) {
+        Ok(l) => l,
+        Err(e) => return Err(I3IpcAcceptorError::OpenLockFile(e.into())),
+    };
+    if let Err(e) = uapi::flock(lock_fd.raw(), c::LOCK_EX | c::LOCK_NB) {
+        return Err(I3IpcAcceptorError::LockLockFile(e.into()));
+    }
+    match uapi::lstat(&path) {
+        Ok(_) => {
+            log::info!("Unlinking {}", path.display());
+            let _ = uapi::unlink(&path);
+        }
+        Err(Errno(c::ENOENT)) => {}
+        Err(e) => return Err(I3IpcAcceptorError::SocketStat(e.into())),
+    }
+    let sun_path = uapi::as_bytes_mut(&mut addr.sun_path[..]);
+    sun_path[..path.len()].copy_from_slice(path.as_bytes());
+    sun_path[path.len()] = 0;
+    if let Err(e) = uapi::bind(insecure.raw(), &addr) {
+        return Err(I3IpcAcceptorError::BindFailed(e.into()));
+    }
+    Ok(I3IpcAllocatedSocket {
+        path,
+        insecure: insecure.clone(),
+        lock_path,
+        _lock_fd: lock_fd,
+    })
+}
+
+fn allocate_socket() -> Result<I3IpcAllocatedSocket, I3IpcAcceptorError> {
+    let xrd = match xrd() {
+        Some(d) => d,
+        _ => return Err(I3IpcAcceptorError::XrdNotSet),
+    };
+    let socket = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
+        Ok(f) => Rc::new(f),
+        Err(e) => return Err(I3IpcAcceptorError::SocketFailed(e.into())),
+    };
+    for i in 1..1000 {
+        match bind_socket(&socket, &xrd, i) {
+            Ok(s) => return Ok(s),
+            Err(e) => {
+                log::warn!("Cannot use the jay-i3-ipc-{} socket: {}", i, ErrorFmt(e));
+            }
+        }
+    }
+    Err(I3IpcAcceptorError::AddressesInUse)
+}
+
+impl I3IpcAcceptor {
+    pub fn spawn(
+        state: &Rc<State>,
+    ) -> Result<(Rc<I3IpcAcceptor>, SpawnedFuture<()>), I3IpcAcceptorError> {
+        let socket = allocate_socket()?;
+        log::info!("bound to i3 IPC socket {}", socket.path.display());
+        if let Err(e) = uapi::listen(socket.insecure.raw(), 4096) {
+            return Err(I3IpcAcceptorError::ListenFailed(e.into()));
+        }
+        let acc = Rc::new(I3IpcAcceptor { socket });
+        let future = state
+            .eng
+            .spawn(accept(acc.socket.insecure.clone(), state.clone()));
+        Ok((acc, future))
+    }
+
+    pub fn socket_path(&self) -> &Ustring {
+        &self.socket.path
+    }
+}
+
+async fn accept(fd: Rc<OwnedFd>, state: Rc<State>) {
+    loop {
+        let fd = match state.ring.accept(&fd, c::SOCK_CLOEXEC).await {
+            Ok(fd) => fd,
+            Err(e) => {
+                log::error!("Could not accept a client: {}", ErrorFmt(e));
+                break;
+            }
+        };
+        state.i3_ipc_clients.spawn(&state, fd);
+    }
+    state.ring.stop();
+}
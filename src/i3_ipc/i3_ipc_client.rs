@@ -0,0 +1,266 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        i3_ipc::{
+            event_mask,
+            i3_ipc_command::run_commands,
+            i3_ipc_json::{outputs, tree, workspaces, I3Success, I3Version},
+            EVENT_BINDING, EVENT_OUTPUT, EVENT_WINDOW, EVENT_WORKSPACE, GET_OUTPUTS, GET_TREE,
+            GET_VERSION, GET_WORKSPACES, MAGIC, RUN_COMMAND, SUBSCRIBE,
+        },
+        io_uring::IoUringError,
+        state::State,
+        utils::{
+            buf::Buf,
+            buffd::{BufFdError, BufFdIn},
+            errorfmt::ErrorFmt,
+            queue::AsyncQueue,
+        },
+        version::VERSION,
+    },
+    ahash::AHashMap,
+    futures_util::{select, FutureExt},
+    serde::Serialize,
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        ops::DerefMut,
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+const MAX_PAYLOAD_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Error)]
+enum I3IpcClientError {
+    #[error("Could not read from the socket")]
+    Read(#[source] BufFdError),
+    #[error("Could not write to the socket")]
+    Write(#[source] IoUringError),
+    #[error("The client sent an invalid magic string")]
+    InvalidMagic,
+    #[error("The client sent a message larger than {} bytes", MAX_PAYLOAD_SIZE)]
+    MessageTooLarge,
+    #[error("The client sent an unknown message type {0}")]
+    UnknownMessageType(u32),
+    #[error("Could not serialize a reply")]
+    Serialize(#[source] serde_json::Error),
+}
+
+linear_ids!(I3IpcClientIds, I3IpcClientId, u64);
+
+#[derive(Default)]
+pub struct I3IpcClients {
+    ids: I3IpcClientIds,
+    clients: RefCell<AHashMap<I3IpcClientId, I3IpcClientHolder>>,
+}
+
+struct I3IpcClientHolder {
+    data: Rc<I3IpcClient>,
+    _handler: SpawnedFuture<()>,
+}
+
+impl Drop for I3IpcClientHolder {
+    fn drop(&mut self) {
+        self.data.messages.clear();
+    }
+}
+
+struct I3IpcClient {
+    id: I3IpcClientId,
+    state: Rc<State>,
+    socket: Rc<OwnedFd>,
+    subscriptions: Cell<u32>,
+    messages: AsyncQueue<Vec<u8>>,
+}
+
+impl I3IpcClients {
+    pub fn clear(&self) {
+        mem::take(self.clients.borrow_mut().deref_mut());
+    }
+
+    pub fn spawn(&self, state: &Rc<State>, socket: Rc<OwnedFd>) {
+        let data = Rc::new(I3IpcClient {
+            id: self.ids.next(),
+            state: state.clone(),
+            socket,
+            subscriptions: Cell::new(0),
+            messages: Default::default(),
+        });
+        log::info!("i3 IPC client {} connected", data.id);
+        let holder = I3IpcClientHolder {
+            _handler: state.eng.spawn(client(data.clone())),
+            data: data.clone(),
+        };
+        self.clients.borrow_mut().insert(data.id, holder);
+    }
+
+    fn kill(&self, id: I3IpcClientId) {
+        log::info!("Removing i3 IPC client {}", id);
+        let client = self.clients.borrow_mut().remove(&id);
+        drop(client);
+    }
+
+    pub(super) fn any_subscribed(&self, event: u32) -> bool {
+        let mask = event_mask(event);
+        self.clients
+            .borrow()
+            .values()
+            .any(|c| c.data.subscriptions.get() & mask != 0)
+    }
+
+    pub(super) fn broadcast(&self, event: u32, payload: &[u8]) {
+        let mask = event_mask(event);
+        for client in self.clients.borrow().values() {
+            if client.data.subscriptions.get() & mask != 0 {
+                client.data.send(event, payload);
+            }
+        }
+    }
+}
+
+impl I3IpcClient {
+    fn send(&self, ty: u32, payload: &[u8]) {
+        let mut msg = Vec::with_capacity(MAGIC.len() + 8 + payload.len());
+        msg.extend_from_slice(MAGIC);
+        msg.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+        msg.extend_from_slice(&ty.to_ne_bytes());
+        msg.extend_from_slice(payload);
+        self.messages.push(msg);
+    }
+
+    fn reply<T: Serialize>(&self, ty: u32, t: &T) -> Result<(), I3IpcClientError> {
+        let payload = serde_json::to_vec(t).map_err(I3IpcClientError::Serialize)?;
+        self.send(ty, &payload);
+        Ok(())
+    }
+
+    fn handle_message(&self, ty: u32, payload: &[u8]) -> Result<(), I3IpcClientError> {
+        let payload = String::from_utf8_lossy(payload);
+        match ty {
+            RUN_COMMAND => self.reply(ty, &run_commands(&self.state, &payload)),
+            GET_WORKSPACES => self.reply(ty, &workspaces(&self.state)),
+            SUBSCRIBE => self.handle_subscribe(&payload),
+            GET_OUTPUTS => self.reply(ty, &outputs(&self.state)),
+            GET_TREE => self.reply(ty, &tree(&self.state)),
+            GET_VERSION => self.handle_get_version(),
+            _ => Err(I3IpcClientError::UnknownMessageType(ty)),
+        }
+    }
+
+    fn handle_subscribe(&self, payload: &str) -> Result<(), I3IpcClientError> {
+        let events: Vec<String> = match serde_json::from_str(payload) {
+            Ok(e) => e,
+            Err(e) => {
+                log::warn!(
+                    "i3 IPC client {} sent an invalid subscription: {}",
+                    self.id,
+                    ErrorFmt(e)
+                );
+                return self.reply(SUBSCRIBE, &I3Success { success: false });
+            }
+        };
+        let mut mask = self.subscriptions.get();
+        for event in &events {
+            let event = match event.as_str() {
+                "workspace" => EVENT_WORKSPACE,
+                "output" => EVENT_OUTPUT,
+                "window" => EVENT_WINDOW,
+                "binding" => EVENT_BINDING,
+                _ => return self.reply(SUBSCRIBE, &I3Success { success: false }),
+            };
+            mask |= event_mask(event);
+        }
+        self.subscriptions.set(mask);
+        self.reply(SUBSCRIBE, &I3Success { success: true })
+    }
+
+    fn handle_get_version(&self) -> Result<(), I3IpcClientError> {
+        let parse = |s: &str| s.parse().unwrap_or(0);
+        let version = I3Version {
+            major: parse(env!("CARGO_PKG_VERSION_MAJOR")),
+            minor: parse(env!("CARGO_PKG_VERSION_MINOR")),
+            patch: parse(env!("CARGO_PKG_VERSION_PATCH")),
+            human_readable: format!("Jay {}", VERSION),
+            loaded_config_file_name: String::new(),
+        };
+        self.reply(GET_VERSION, &version)
+    }
+}
+
+async fn client(data: Rc<I3IpcClient>) {
+    let mut recv = data.state.eng.spawn(receive(data.clone())).fuse();
+    let mut send = data.state.eng.spawn(send(data.clone())).fuse();
+    select! {
+        _ = recv => { },
+        _ = send => { },
+    }
+    data.state.i3_ipc_clients.kill(data.id);
+}
+
+async fn receive(data: Rc<I3IpcClient>) {
+    let recv = async {
+        let mut buf = BufFdIn::new(&data.socket, &data.state.ring);
+        let mut payload = Vec::new();
+        loop {
+            let mut hdr = [0u8; 14];
+            buf.read_full(&mut hdr[..])
+                .await
+                .map_err(I3IpcClientError::Read)?;
+            if hdr[..6] != MAGIC[..] {
+                return Err(I3IpcClientError::InvalidMagic);
+            }
+            let len = u32::from_ne_bytes([hdr[6], hdr[7], hdr[8], hdr[9]]) as usize;
+            let ty = u32::from_ne_bytes([hdr[10], hdr[11], hdr[12], hdr[13]]);
+            if len > MAX_PAYLOAD_SIZE {
+                return Err(I3IpcClientError::MessageTooLarge);
+            }
+            payload.clear();
+            payload.resize(len, 0u8);
+            buf.read_full(&mut payload[..])
+                .await
+                .map_err(I3IpcClientError::Read)?;
+            data.handle_message(ty, &payload)?;
+        }
+    };
+    let res: Result<(), I3IpcClientError> = recv.await;
+    if let Err(e) = res {
+        if let I3IpcClientError::Read(BufFdError::Closed) = e {
+            log::info!("i3 IPC client {} terminated the connection", data.id);
+        } else {
+            log::error!(
+                "An error occurred while handling a message from i3 IPC client {}: {}",
+                data.id,
+                ErrorFmt(e)
+            );
+        }
+    }
+}
+
+async fn send(data: Rc<I3IpcClient>) {
+    let send = async {
+        loop {
+            let msg = data.messages.pop().await;
+            let mut buf = Buf::from_slice(&msg);
+            let mut start = 0;
+            while start < buf.len() {
+                start += data
+                    .state
+                    .ring
+                    .write(&data.socket, buf.slice(start..), None)
+                    .await
+                    .map_err(I3IpcClientError::Write)?;
+            }
+        }
+    };
+    let res: Result<(), I3IpcClientError> = send.await;
+    if let Err(e) = res {
+        log::error!(
+            "An error occurred while sending data to i3 IPC client {}: {}",
+            data.id,
+            ErrorFmt(e)
+        );
+    }
+}
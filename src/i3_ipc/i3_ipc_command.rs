@@ -0,0 +1,142 @@
+use {
+    crate::{
+        i3_ipc::{default_seat, i3_ipc_json::I3CommandResult},
+        ifs::wl_seat::WlSeatGlobal,
+        state::State,
+        tree::{ContainerLayout, ContainerSplit, Direction},
+    },
+    std::rc::Rc,
+};
+
+/// Runs a list of i3 commands separated by `;` or `,`.
+///
+/// Only a subset of the i3 command language is supported. Criteria and `exec` are not
+/// supported.
+pub fn run_commands(state: &Rc<State>, commands: &str) -> Vec<I3CommandResult> {
+    let mut results = vec![];
+    let mut rest = commands.trim();
+    while !rest.is_empty() {
+        let end = rest.find([';', ',']).unwrap_or(rest.len());
+        let cmd = rest[..end].trim();
+        rest = rest[end..].trim_start_matches([';', ',']).trim();
+        if cmd.is_empty() {
+            continue;
+        }
+        let res = run_command(state, cmd);
+        results.push(I3CommandResult {
+            success: res.is_ok(),
+            error: res.err(),
+        });
+    }
+    results
+}
+
+fn run_command(state: &Rc<State>, cmd: &str) -> Result<(), String> {
+    if cmd.starts_with('[') {
+        return Err("Criteria are not supported".to_string());
+    }
+    if cmd.starts_with("exec ") {
+        return Err("exec is not supported".to_string());
+    }
+    let Some(seat) = default_seat(state) else {
+        return Err("There is no seat".to_string());
+    };
+    let args: Vec<_> = cmd.split_whitespace().collect();
+    match args[..] {
+        ["nop", ..] => {}
+        ["kill"] => seat.close(),
+        ["workspace", ..] => {
+            let name = cmd["workspace".len()..].trim();
+            let name = name.strip_prefix("number ").unwrap_or(name).trim();
+            state.show_workspace(&seat, name);
+        }
+        ["focus", dir] => match dir {
+            "parent" => seat.focus_parent(),
+            _ => seat.move_focus(parse_direction(dir)?),
+        },
+        ["move", "scratchpad"]
+        | ["move", "to", "scratchpad"]
+        | ["move", "container" | "window", "to", "scratchpad"] => seat.move_to_scratchpad(),
+        ["move", dir] | ["move", dir, _, "px" | "ppt"] => seat.move_focused(parse_direction(dir)?),
+        ["move", "container" | "window", "to", "workspace", ..]
+        | ["move", "to", "workspace", ..] => {
+            let idx = cmd.find("workspace").unwrap() + "workspace".len();
+            let name = cmd[idx..].trim();
+            move_to_workspace(state, &seat, name);
+        }
+        ["scratchpad", "show"] => seat.show_scratchpad(),
+        ["floating", arg] => {
+            let floating = seat.get_floating().unwrap_or(false);
+            seat.set_floating(parse_toggle(arg, floating)?);
+        }
+        ["fullscreen"] => seat.set_fullscreen(!seat.get_fullscreen()),
+        ["fullscreen", arg] => {
+            let fullscreen = seat.get_fullscreen();
+            seat.set_fullscreen(parse_toggle(arg, fullscreen)?);
+        }
+        ["split", axis] => seat.create_split(parse_split(axis)?),
+        ["splith"] => seat.create_split(ContainerSplit::Horizontal),
+        ["splitv"] => seat.create_split(ContainerSplit::Vertical),
+        ["layout", "tabbed"] => seat.set_layout(ContainerLayout::Tabbed),
+        ["layout", "stacking" | "stacked"] => seat.set_layout(ContainerLayout::Stacked),
+        ["layout", "splith"] => {
+            seat.set_layout(ContainerLayout::Split);
+            seat.set_split(ContainerSplit::Horizontal);
+        }
+        ["layout", "splitv"] => {
+            seat.set_layout(ContainerLayout::Split);
+            seat.set_split(ContainerSplit::Vertical);
+        }
+        ["layout", "toggle", "split"] => match seat.get_layout() {
+            Some(ContainerLayout::Split) => {
+                let split = match seat.get_split() {
+                    Some(ContainerSplit::Horizontal) => ContainerSplit::Vertical,
+                    _ => ContainerSplit::Horizontal,
+                };
+                seat.set_split(split);
+            }
+            _ => seat.set_layout(ContainerLayout::Split),
+        },
+        _ => return Err(format!("Unsupported command: {}", cmd)),
+    }
+    Ok(())
+}
+
+fn move_to_workspace(state: &Rc<State>, seat: &Rc<WlSeatGlobal>, name: &str) {
+    let name = name.strip_prefix("number ").unwrap_or(name).trim();
+    let ws = match state.workspaces.get(name) {
+        Some(ws) => ws,
+        _ => seat.get_output().create_workspace(name),
+    };
+    seat.set_workspace(&ws);
+}
+
+fn parse_direction(dir: &str) -> Result<Direction, String> {
+    let dir = match dir {
+        "left" => Direction::Left,
+        "right" => Direction::Right,
+        "up" => Direction::Up,
+        "down" => Direction::Down,
+        _ => return Err(format!("Unknown direction: {}", dir)),
+    };
+    Ok(dir)
+}
+
+fn parse_toggle(arg: &str, current: bool) -> Result<bool, String> {
+    let res = match arg {
+        "enable" => true,
+        "disable" => false,
+        "toggle" => !current,
+        _ => return Err(format!("Expected enable, disable, or toggle: {}", arg)),
+    };
+    Ok(res)
+}
+
+fn parse_split(axis: &str) -> Result<ContainerSplit, String> {
+    let split = match axis {
+        "h" | "horizontal" => ContainerSplit::Horizontal,
+        "v" | "vertical" => ContainerSplit::Vertical,
+        _ => return Err(format!("Unknown split axis: {}", axis)),
+    };
+    Ok(split)
+}
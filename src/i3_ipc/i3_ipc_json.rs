@@ -0,0 +1,479 @@
+use {
+    crate::{
+        i3_ipc::default_seat,
+        ifs::wl_surface::x_surface::xwindow::Xwindow,
+        rect::Rect,
+        state::State,
+        tree::{
+            ContainerLayout, ContainerNode, ContainerSplit, NodeVisitorBase, OutputNode,
+            ToplevelNode, WorkspaceNode,
+        },
+        xkbcommon::keysym_name,
+    },
+    jay_config::keyboard::{
+        mods::{Modifiers, ALT, CTRL, LOCK, LOGO, MOD2, MOD3, MOD5, SHIFT},
+        syms::KeySym,
+    },
+    serde::Serialize,
+    std::{ops::Deref, rc::Rc},
+};
+
+#[derive(Serialize)]
+pub struct I3Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl From<Rect> for I3Rect {
+    fn from(rect: Rect) -> Self {
+        Self {
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct I3Workspace {
+    id: u32,
+    num: i32,
+    name: String,
+    visible: bool,
+    focused: bool,
+    urgent: bool,
+    rect: I3Rect,
+    output: String,
+}
+
+#[derive(Serialize)]
+pub struct I3Output {
+    name: String,
+    make: String,
+    model: String,
+    serial: String,
+    active: bool,
+    primary: bool,
+    scale: f64,
+    rect: I3Rect,
+    current_workspace: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct I3WindowProperties {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    window_role: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct I3Node {
+    id: u32,
+    name: Option<String>,
+    #[serde(rename = "type")]
+    ty: &'static str,
+    layout: &'static str,
+    orientation: &'static str,
+    rect: I3Rect,
+    urgent: bool,
+    focused: bool,
+    focus: Vec<u32>,
+    fullscreen_mode: u32,
+    nodes: Vec<I3Node>,
+    floating_nodes: Vec<I3Node>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visible: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<i32>,
+    window: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    window_properties: Option<I3WindowProperties>,
+}
+
+impl I3Node {
+    fn new(id: u32, ty: &'static str, name: Option<String>, rect: Rect) -> Self {
+        Self {
+            id,
+            name,
+            ty,
+            layout: "none",
+            orientation: "none",
+            rect: rect.into(),
+            urgent: false,
+            focused: false,
+            focus: vec![],
+            fullscreen_mode: 0,
+            nodes: vec![],
+            floating_nodes: vec![],
+            num: None,
+            output: None,
+            visible: None,
+            app_id: None,
+            pid: None,
+            window: None,
+            window_properties: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct I3CommandResult {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct I3Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub human_readable: String,
+    pub loaded_config_file_name: String,
+}
+
+#[derive(Serialize)]
+pub struct I3Success {
+    pub success: bool,
+}
+
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum I3WorkspaceChange {
+    Init,
+    Empty,
+    Focus,
+    Move,
+}
+
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum I3WindowChange {
+    New,
+    Close,
+    Focus,
+    Title,
+    FullscreenMode,
+    Move,
+    Floating,
+}
+
+#[derive(Serialize)]
+pub struct I3WorkspaceEvent {
+    change: I3WorkspaceChange,
+    current: I3Node,
+    old: Option<I3Node>,
+}
+
+#[derive(Serialize)]
+pub struct I3WindowEvent {
+    change: I3WindowChange,
+    container: I3Node,
+}
+
+#[derive(Serialize)]
+pub struct I3OutputEvent {
+    change: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct I3Binding {
+    command: String,
+    event_state_mask: Vec<&'static str>,
+    input_code: u32,
+    symbol: Option<String>,
+    input_type: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct I3BindingEvent {
+    change: &'static str,
+    binding: I3Binding,
+}
+
+/// Returns the leading number of a workspace name, as i3 does, or -1.
+fn workspace_num(name: &str) -> i32 {
+    let end = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+    name[..end].parse().unwrap_or(-1)
+}
+
+fn focused_workspace(state: &State) -> Option<Rc<WorkspaceNode>> {
+    default_seat(state)?.get_output().workspace.get()
+}
+
+fn workspace_focused(ws: &WorkspaceNode) -> bool {
+    match focused_workspace(&ws.state) {
+        Some(f) => f.id == ws.id,
+        _ => false,
+    }
+}
+
+pub fn workspaces(state: &State) -> Vec<I3Workspace> {
+    let focused = focused_workspace(state);
+    let mut res = vec![];
+    for output in state.root.outputs.lock().values() {
+        for ws in output.workspaces.iter() {
+            res.push(I3Workspace {
+                id: ws.id.raw(),
+                num: workspace_num(&ws.name),
+                name: ws.name.clone(),
+                visible: ws.visible.get(),
+                focused: focused.as_ref().map(|f| f.id) == Some(ws.id),
+                urgent: ws.attention_requests.active(),
+                rect: ws.position.get().into(),
+                output: output.global.connector.name.clone(),
+            });
+        }
+    }
+    res
+}
+
+pub fn outputs(state: &State) -> Vec<I3Output> {
+    let mut res = vec![];
+    for output in state.root.outputs.lock().values() {
+        let id = &output.global.output_id;
+        res.push(I3Output {
+            name: output.global.connector.name.clone(),
+            make: id.manufacturer.clone(),
+            model: id.model.clone(),
+            serial: id.serial_number.clone(),
+            active: true,
+            primary: false,
+            scale: output.global.persistent.scale.get().to_f64(),
+            rect: output.global.pos.get().into(),
+            current_workspace: output.workspace.get().map(|ws| ws.name.clone()),
+        });
+    }
+    res
+}
+
+pub fn tree(state: &State) -> I3Node {
+    let mut root = I3Node::new(
+        state.root.id.raw(),
+        "root",
+        Some("root".to_string()),
+        state.root.extents.get(),
+    );
+    root.layout = "splith";
+    root.orientation = "horizontal";
+    for output in state.root.outputs.lock().values() {
+        root.nodes.push(output_node(output));
+    }
+    root.focus = root.nodes.iter().map(|n| n.id).collect();
+    root
+}
+
+fn output_node(output: &Rc<OutputNode>) -> I3Node {
+    let name = output.global.connector.name.clone();
+    let mut node = I3Node::new(
+        output.id.raw(),
+        "output",
+        Some(name),
+        output.global.pos.get(),
+    );
+    node.layout = "output";
+    for ws in output.workspaces.iter() {
+        node.nodes.push(workspace_node(&ws));
+    }
+    if let Some(ws) = output.workspace.get() {
+        node.focus.push(ws.id.raw());
+    }
+    node
+}
+
+fn workspace_node(ws: &Rc<WorkspaceNode>) -> I3Node {
+    let mut node = I3Node::new(
+        ws.id.raw(),
+        "workspace",
+        Some(ws.name.clone()),
+        ws.position.get(),
+    );
+    node.num = Some(workspace_num(&ws.name));
+    node.output = Some(ws.output.get().global.connector.name.clone());
+    node.visible = Some(ws.visible.get());
+    node.urgent = ws.attention_requests.active();
+    node.focused = ws.is_empty() && workspace_focused(ws);
+    node.layout = "splith";
+    node.orientation = "horizontal";
+    if let Some(container) = ws.container.get() {
+        let (layout, orientation) = container_layout(&container);
+        node.layout = layout;
+        node.orientation = orientation;
+        node.nodes = container_children(&container, &mut node.focus);
+    }
+    if let Some(fs) = ws.fullscreen.get() {
+        let mut fs = toplevel_node(&fs);
+        fs.fullscreen_mode = 1;
+        node.focus.insert(0, fs.id);
+        node.nodes.push(fs);
+    }
+    for stacked in ws.stacked.iter() {
+        let Some(float) = stacked
+            .deref()
+            .clone()
+            .stacked_into_node()
+            .node_into_float()
+        else {
+            continue;
+        };
+        let Some(child) = float.child.get() else {
+            continue;
+        };
+        let mut child = toplevel_node(&child);
+        child.ty = "floating_con";
+        node.floating_nodes.push(child);
+    }
+    node
+}
+
+fn container_layout(container: &ContainerNode) -> (&'static str, &'static str) {
+    let orientation = match container.split.get() {
+        ContainerSplit::Horizontal => "horizontal",
+        ContainerSplit::Vertical => "vertical",
+    };
+    let layout = match container.layout() {
+        ContainerLayout::Tabbed => "tabbed",
+        ContainerLayout::Stacked => "stacked",
+        ContainerLayout::Split => match container.split.get() {
+            ContainerSplit::Horizontal => "splith",
+            ContainerSplit::Vertical => "splitv",
+        },
+    };
+    (layout, orientation)
+}
+
+fn container_children(container: &ContainerNode, focus: &mut Vec<u32>) -> Vec<I3Node> {
+    let mut nodes = vec![];
+    let mut inactive = vec![];
+    for child in container.children.iter() {
+        if child.node.node_is_placeholder() {
+            continue;
+        }
+        let id = child.node.node_id().raw();
+        match child.active.get() {
+            true => focus.push(id),
+            false => inactive.push(id),
+        }
+        nodes.push(toplevel_node(&child.node));
+    }
+    focus.extend(inactive);
+    nodes
+}
+
+pub fn toplevel_node(tl: &Rc<dyn ToplevelNode>) -> I3Node {
+    let data = tl.tl_data();
+    let mut node = I3Node::new(
+        tl.node_id().raw(),
+        "con",
+        Some(data.title.borrow().clone()),
+        data.pos.get(),
+    );
+    node.urgent = data.wants_attention.get();
+    node.focused = data.active();
+    node.visible = Some(data.visible.get());
+    node.fullscreen_mode = data.is_fullscreen.get() as u32;
+    if let Some(container) = tl.clone().node_into_container() {
+        let (layout, orientation) = container_layout(&container);
+        node.layout = layout;
+        node.orientation = orientation;
+        node.nodes = container_children(&container, &mut node.focus);
+        return node;
+    }
+    let app_id = data.app_id.borrow();
+    if !app_id.is_empty() {
+        node.app_id = Some(app_id.clone());
+    }
+    if let Some(client) = &data.client {
+        node.pid = Some(client.pid_info.pid);
+    }
+    let mut props = I3WindowProperties {
+        title: data.title.borrow().clone(),
+        class: None,
+        instance: None,
+        window_role: None,
+    };
+    struct XwindowVisitor<'a>(&'a mut I3Node, &'a mut I3WindowProperties);
+    impl NodeVisitorBase for XwindowVisitor<'_> {
+        fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+            let p = node.window_properties();
+            self.0.window = Some(node.data.window_id);
+            self.0.pid = p.pid.or(self.0.pid);
+            self.1.class = p.class;
+            self.1.instance = p.instance;
+            self.1.window_role = p.role;
+        }
+    }
+    tl.clone()
+        .tl_into_node()
+        .node_visit(&mut XwindowVisitor(&mut node, &mut props));
+    node.window_properties = Some(props);
+    node
+}
+
+pub fn workspace_event(
+    change: I3WorkspaceChange,
+    current: &Rc<WorkspaceNode>,
+    old: Option<&Rc<WorkspaceNode>>,
+) -> I3WorkspaceEvent {
+    I3WorkspaceEvent {
+        change,
+        current: workspace_node(current),
+        old: old.map(workspace_node),
+    }
+}
+
+pub fn window_event(change: I3WindowChange, tl: &Rc<dyn ToplevelNode>) -> I3WindowEvent {
+    I3WindowEvent {
+        change,
+        container: toplevel_node(tl),
+    }
+}
+
+pub fn output_event() -> I3OutputEvent {
+    I3OutputEvent {
+        change: "unspecified",
+    }
+}
+
+pub fn binding_event(mods: Modifiers, sym: KeySym) -> I3BindingEvent {
+    let names = [
+        (SHIFT, "shift"),
+        (LOCK, "lock"),
+        (CTRL, "ctrl"),
+        (ALT, "Mod1"),
+        (MOD2, "Mod2"),
+        (MOD3, "Mod3"),
+        (LOGO, "Mod4"),
+        (MOD5, "Mod5"),
+    ];
+    let event_state_mask = names
+        .iter()
+        .filter(|(m, _)| mods.0 & m.0 != 0)
+        .map(|(_, n)| *n)
+        .collect();
+    I3BindingEvent {
+        change: "run",
+        binding: I3Binding {
+            command: String::new(),
+            event_state_mask,
+            input_code: 0,
+            symbol: keysym_name(sym.0),
+            input_type: "keyboard",
+        },
+    }
+}
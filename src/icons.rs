@@ -0,0 +1,208 @@
+use {
+    crate::{format::ARGB8888, gfx_api::GfxTexture, state::State, utils::errorfmt::ErrorFmt},
+    png::{ColorType, Decoder, Limits, Transformations},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        fs::File,
+        io::{self, BufReader},
+        path::{Path, PathBuf},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const XDG_DATA_DIRS: &str = "XDG_DATA_DIRS";
+const XDG_DATA_DIRS_DEFAULT: &str = "/usr/local/share:/usr/share";
+const FALLBACK_THEME: &str = "hicolor";
+const THEME_SIZES: [i32; 11] = [16, 22, 24, 32, 48, 64, 96, 128, 192, 256, 512];
+const MAX_ICON_SIZE: u32 = 1024;
+
+#[derive(Debug, Error)]
+pub enum IconError {
+    #[error("An IO error occurred")]
+    Io(#[from] io::Error),
+    #[error("Could not decode the PNG file")]
+    Png(#[from] png::DecodingError),
+    #[error("The icon name is invalid")]
+    InvalidName,
+    #[error("The icon could not be found in the icon theme")]
+    NotFound,
+    #[error("The icon is not square")]
+    NotSquare,
+    #[error("The icon is larger than {}x{}", MAX_ICON_SIZE, MAX_ICON_SIZE)]
+    TooLarge,
+    #[error("The PNG file has an unsupported color type")]
+    UnsupportedColorType,
+}
+
+/// An icon consisting of square images of different sizes.
+pub struct Icon {
+    images: Vec<IconImage>,
+}
+
+/// A square image in premultiplied ARGB8888.
+pub struct IconImage {
+    pub size: i32,
+    pixels: Vec<Cell<u8>>,
+    texture: RefCell<Option<(u32, Rc<dyn GfxTexture>)>>,
+}
+
+impl Icon {
+    pub fn new(mut images: Vec<IconImage>) -> Option<Rc<Self>> {
+        if images.is_empty() {
+            return None;
+        }
+        images.sort_by_key(|i| i.size);
+        Some(Rc::new(Self { images }))
+    }
+
+    /// Loads a named icon from the icon theme.
+    ///
+    /// Only PNG icons are supported. The image whose size is closest to, but not smaller
+    /// than, `size` is loaded.
+    pub fn load(name: &str, size: i32) -> Result<Rc<Self>, IconError> {
+        if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+            return Err(IconError::InvalidName);
+        }
+        let path = find_icon(name, size).ok_or(IconError::NotFound)?;
+        let image = load_png(&path)?;
+        Ok(Rc::new(Self {
+            images: vec![image],
+        }))
+    }
+
+    /// Returns the smallest image that is at least `size` pixels large or the largest
+    /// image if there is no such image.
+    pub fn image(&self, size: i32) -> &IconImage {
+        self.images
+            .iter()
+            .find(|i| i.size >= size)
+            .unwrap_or_else(|| self.images.last().unwrap())
+    }
+}
+
+impl IconImage {
+    pub fn new(size: i32, pixels: Vec<Cell<u8>>) -> Self {
+        Self {
+            size,
+            pixels,
+            texture: Default::default(),
+        }
+    }
+
+    /// Returns a texture of this image in the current render context.
+    pub fn texture(&self, state: &State) -> Option<Rc<dyn GfxTexture>> {
+        let ctx = state.render_ctx.get()?;
+        let version = state.render_ctx_version.get();
+        if let Some((v, tex)) = &*self.texture.borrow() {
+            if *v == version {
+                return Some(tex.clone());
+            }
+        }
+        let res = ctx.shmem_texture(
+            None,
+            &self.pixels,
+            ARGB8888,
+            self.size,
+            self.size,
+            self.size * 4,
+            None,
+        );
+        match res {
+            Ok(tex) => {
+                *self.texture.borrow_mut() = Some((version, tex.clone()));
+                Some(tex)
+            }
+            Err(e) => {
+                log::error!("Could not upload icon: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+}
+
+/// Returns the size of the icons displayed in title bars.
+pub fn title_icon_size(title_height: i32) -> i32 {
+    (title_height - 2).max(0)
+}
+
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(dir) = dirs::data_dir() {
+        dirs.push(dir);
+    }
+    let data_dirs = env::var(XDG_DATA_DIRS).unwrap_or_default();
+    let data_dirs = match data_dirs.is_empty() {
+        true => XDG_DATA_DIRS_DEFAULT,
+        false => &data_dirs,
+    };
+    for dir in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+fn find_icon(name: &str, size: i32) -> Option<PathBuf> {
+    let data_dirs = data_dirs();
+    let mut icon_dirs = vec![];
+    if let Some(home) = dirs::home_dir() {
+        icon_dirs.push(home.join(".icons"));
+    }
+    icon_dirs.extend(data_dirs.iter().map(|d| d.join("icons")));
+    let larger = THEME_SIZES.iter().filter(|s| **s >= size);
+    let smaller = THEME_SIZES.iter().rev().filter(|s| **s < size);
+    let file = format!("{name}.png");
+    for s in larger.chain(smaller) {
+        let sub = format!("{FALLBACK_THEME}/{s}x{s}/apps");
+        for dir in &icon_dirs {
+            let path = dir.join(&sub).join(&file);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+    data_dirs
+        .iter()
+        .map(|d| d.join("pixmaps").join(&file))
+        .find(|p| p.is_file())
+}
+
+fn load_png(path: &Path) -> Result<IconImage, IconError> {
+    let file = BufReader::new(File::open(path)?);
+    let limit = (MAX_ICON_SIZE * MAX_ICON_SIZE * 4) as usize;
+    let mut decoder = Decoder::new_with_limits(file, Limits { bytes: limit });
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    if info.width != info.height {
+        return Err(IconError::NotSquare);
+    }
+    if info.width > MAX_ICON_SIZE {
+        return Err(IconError::TooLarge);
+    }
+    let bpp = match info.color_type {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        ColorType::Indexed => return Err(IconError::UnsupportedColorType),
+    };
+    let size = info.width as usize;
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for row in buf.chunks(info.line_size).take(size) {
+        for px in row[..size * bpp].chunks_exact(bpp) {
+            let (r, g, b, a) = match *px {
+                [l] => (l, l, l, 255),
+                [l, a] => (l, l, l, a),
+                [r, g, b] => (r, g, b, 255),
+                [r, g, b, a] => (r, g, b, a),
+                _ => unreachable!(),
+            };
+            let pm = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+            pixels.extend([pm(b), pm(g), pm(r), a].map(Cell::new));
+        }
+    }
+    Ok(IconImage::new(size as i32, pixels))
+}
@@ -1,14 +1,25 @@
 pub mod ext_foreign_toplevel_handle_v1;
+pub mod ext_foreign_toplevel_image_capture_source_manager_v1;
 pub mod ext_foreign_toplevel_list_v1;
 pub mod ext_idle_notification_v1;
 pub mod ext_idle_notifier_v1;
+pub mod ext_image_capture_source_v1;
+pub mod ext_image_copy_capture_cursor_session_v1;
+pub mod ext_image_copy_capture_frame_v1;
+pub mod ext_image_copy_capture_manager_v1;
+pub mod ext_image_copy_capture_session_v1;
+pub mod ext_output_image_capture_source_manager_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
+pub mod ext_workspace_group_handle_v1;
+pub mod ext_workspace_handle_v1;
+pub mod ext_workspace_manager_v1;
 pub mod ipc;
 pub mod jay_compositor;
 pub mod jay_damage_tracking;
 pub mod jay_ei_session;
 pub mod jay_ei_session_builder;
+pub mod jay_global_shortcuts;
 pub mod jay_idle;
 pub mod jay_input;
 pub mod jay_log_file;
@@ -40,6 +51,9 @@ pub mod wl_shm_pool;
 pub mod wl_subcompositor;
 pub mod wl_surface;
 pub mod wp_alpha_modifier_v1;
+pub mod wp_color_management_output_v1;
+pub mod wp_color_manager_v1;
+pub mod wp_commit_timing_manager_v1;
 pub mod wp_content_type_manager_v1;
 pub mod wp_content_type_v1;
 pub mod wp_cursor_shape_device_v1;
@@ -48,7 +62,12 @@ pub mod wp_drm_lease_connector_v1;
 pub mod wp_drm_lease_device_v1;
 pub mod wp_drm_lease_request_v1;
 pub mod wp_drm_lease_v1;
+pub mod wp_fifo_manager_v1;
 pub mod wp_fractional_scale_manager_v1;
+pub mod wp_image_description_creator_icc_v1;
+pub mod wp_image_description_creator_params_v1;
+pub mod wp_image_description_info_v1;
+pub mod wp_image_description_v1;
 pub mod wp_linux_drm_syncobj_manager_v1;
 pub mod wp_linux_drm_syncobj_timeline_v1;
 pub mod wp_presentation;
@@ -60,15 +79,28 @@ pub mod wp_tearing_control_manager_v1;
 pub mod wp_viewporter;
 pub mod xdg_activation_token_v1;
 pub mod xdg_activation_v1;
+pub mod xdg_foreign;
 pub mod xdg_positioner;
 pub mod xdg_toplevel_drag_manager_v1;
 pub mod xdg_toplevel_drag_v1;
+pub mod xdg_toplevel_icon_manager_v1;
+pub mod xdg_toplevel_icon_v1;
 pub mod xdg_wm_base;
 pub mod xdg_wm_dialog_v1;
+pub mod zwlr_foreign_toplevel_handle_v1;
+pub mod zwlr_foreign_toplevel_manager_v1;
+pub mod zwlr_gamma_control_manager_v1;
+pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
+pub mod zwlr_output_configuration_head_v1;
+pub mod zwlr_output_configuration_v1;
+pub mod zwlr_output_head_v1;
+pub mod zwlr_output_manager_v1;
+pub mod zwlr_output_mode_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwp_idle_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
 pub mod zwp_linux_buffer_params_v1;
 pub mod zwp_linux_dmabuf_feedback_v1;
 pub mod zwp_linux_dmabuf_v1;
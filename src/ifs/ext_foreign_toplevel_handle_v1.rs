@@ -79,7 +79,11 @@ impl Object for ExtForeignToplevelHandleV1 {
     }
 }
 
-simple_add_obj!(ExtForeignToplevelHandleV1);
+dedicated_add_obj!(
+    ExtForeignToplevelHandleV1,
+    ExtForeignToplevelHandleV1Id,
+    foreign_toplevel_handles
+);
 
 #[derive(Debug, Error)]
 pub enum ExtForeignToplevelHandleV1Error {
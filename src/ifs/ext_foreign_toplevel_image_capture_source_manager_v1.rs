@@ -0,0 +1,121 @@
+use {
+    crate::{
+        client::{Client, ClientCaps, ClientError, CAP_FOREIGN_TOPLEVEL_LIST},
+        globals::{Global, GlobalName},
+        ifs::ext_image_capture_source_v1::{ExtImageCaptureSourceV1, ImageCaptureSource},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{
+            ext_foreign_toplevel_image_capture_source_manager_v1::*,
+            ExtForeignToplevelImageCaptureSourceManagerV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ExtForeignToplevelImageCaptureSourceManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ExtForeignToplevelImageCaptureSourceManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtForeignToplevelImageCaptureSourceManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ExtForeignToplevelImageCaptureSourceManagerV1Error> {
+        let mgr = Rc::new(ExtForeignToplevelImageCaptureSourceManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, mgr);
+        client.add_client_obj(&mgr)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtForeignToplevelImageCaptureSourceManagerV1Global,
+    ExtForeignToplevelImageCaptureSourceManagerV1,
+    ExtForeignToplevelImageCaptureSourceManagerV1Error
+);
+
+simple_add_global!(ExtForeignToplevelImageCaptureSourceManagerV1Global);
+
+impl Global for ExtForeignToplevelImageCaptureSourceManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_FOREIGN_TOPLEVEL_LIST
+    }
+}
+
+pub struct ExtForeignToplevelImageCaptureSourceManagerV1 {
+    pub id: ExtForeignToplevelImageCaptureSourceManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ExtForeignToplevelImageCaptureSourceManagerV1RequestHandler
+    for ExtForeignToplevelImageCaptureSourceManagerV1
+{
+    type Error = ExtForeignToplevelImageCaptureSourceManagerV1Error;
+
+    fn create_source(&self, req: CreateSource, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let handle = self.client.lookup(req.toplevel_handle)?;
+        let toplevel = handle
+            .toplevel
+            .tl_data()
+            .handles
+            .contains(&(self.client.id, handle.id))
+            .then(|| handle.toplevel.clone());
+        let source = Rc::new(ExtImageCaptureSourceV1 {
+            id: req.source,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            ty: ImageCaptureSource::Toplevel(toplevel),
+        });
+        track!(self.client, source);
+        self.client.add_client_obj(&source)?;
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtForeignToplevelImageCaptureSourceManagerV1;
+    version = self.version;
+}
+
+impl Object for ExtForeignToplevelImageCaptureSourceManagerV1 {}
+
+simple_add_obj!(ExtForeignToplevelImageCaptureSourceManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ExtForeignToplevelImageCaptureSourceManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(
+    ExtForeignToplevelImageCaptureSourceManagerV1Error,
+    ClientError
+);
@@ -42,6 +42,9 @@ impl ExtForeignToplevelListV1Global {
         track!(client, obj);
         client.add_client_obj(&obj)?;
         ToplevelVisitor { list: &obj }.visit_display(&client.state.root);
+        for tl in client.state.scratchpad.windows() {
+            tl.tl_data().send(tl.clone(), &obj);
+        }
         client.state.toplevel_lists.set((client.id, id), obj);
         Ok(())
     }
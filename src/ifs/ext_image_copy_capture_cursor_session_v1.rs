@@ -0,0 +1,228 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cursor::{Cursor, DEFAULT_CURSOR_SIZE},
+        cursor_user::CursorUserGroup,
+        fixed::Fixed,
+        ifs::{
+            ext_image_capture_source_v1::ImageCaptureSource,
+            ext_image_copy_capture_session_v1::{
+                toplevel_scale, CaptureTarget, ExtImageCopyCaptureSessionV1,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        rect::Rect,
+        scale::Scale,
+        utils::clonecell::CloneCell,
+        wire::{ext_image_copy_capture_cursor_session_v1::*, ExtImageCopyCaptureCursorSessionV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ExtImageCopyCaptureCursorSessionV1 {
+    pub id: ExtImageCopyCaptureCursorSessionV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub source: ImageCaptureSource,
+    pub group: Rc<CursorUserGroup>,
+    pub session: CloneCell<Option<Rc<ExtImageCopyCaptureSessionV1>>>,
+    pub have_session: Cell<bool>,
+    pub entered: Cell<bool>,
+    pub position: Cell<Option<(i32, i32)>>,
+    pub hotspot: Cell<Option<(i32, i32)>>,
+    pub image_size: Cell<(i32, i32)>,
+}
+
+impl ExtImageCopyCaptureCursorSessionV1 {
+    pub fn new(
+        id: ExtImageCopyCaptureCursorSessionV1Id,
+        client: &Rc<Client>,
+        version: Version,
+        source: ImageCaptureSource,
+        group: &Rc<CursorUserGroup>,
+    ) -> Self {
+        let size = *DEFAULT_CURSOR_SIZE as i32;
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            source,
+            group: group.clone(),
+            session: Default::default(),
+            have_session: Cell::new(false),
+            entered: Cell::new(false),
+            position: Cell::new(None),
+            hotspot: Cell::new(None),
+            image_size: Cell::new((size, size)),
+        }
+    }
+
+    pub fn send_enter(&self) {
+        self.client.event(Enter { self_id: self.id });
+    }
+
+    pub fn send_leave(&self) {
+        self.client.event(Leave { self_id: self.id });
+    }
+
+    pub fn send_position(&self, x: i32, y: i32) {
+        self.client.event(Position {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    pub fn send_hotspot(&self, x: i32, y: i32) {
+        self.client.event(Hotspot {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    fn source_geometry(&self) -> Option<(Rect, Scale)> {
+        match &self.source {
+            ImageCaptureSource::Output(o) => {
+                let node = o.node()?;
+                Some((node.global.pos.get(), node.global.persistent.scale.get()))
+            }
+            ImageCaptureSource::Toplevel(tl) => {
+                let tl = tl.as_ref()?;
+                if !tl.node_visible() {
+                    return None;
+                }
+                Some((tl.node_absolute_position(), toplevel_scale(&**tl)))
+            }
+        }
+    }
+
+    /// Returns the cursor image if the cursor is currently within the source.
+    pub fn cursor(&self) -> Option<(Rc<dyn Cursor>, Scale)> {
+        if !self.entered.get() {
+            return None;
+        }
+        let (_, scale) = self.source_geometry()?;
+        let cursor = self.group.active()?.get()?;
+        Some((cursor, scale))
+    }
+
+    pub fn image_size(&self) -> (i32, i32) {
+        self.image_size.get()
+    }
+
+    pub fn update(&self, mut image_changed: bool) {
+        let mut current = None;
+        if let Some((rect, scale)) = self.source_geometry() {
+            if let Some(user) = self.group.active() {
+                if let Some(cursor) = user.get() {
+                    let (x, y) = user.position();
+                    if rect.contains(x.round_down(), y.round_down()) {
+                        current = Some((cursor, x, y, rect, scale));
+                    }
+                }
+            }
+        }
+        let Some((cursor, x, y, rect, scale)) = current else {
+            if self.entered.replace(false) {
+                self.send_leave();
+            }
+            return;
+        };
+        if !self.entered.replace(true) {
+            self.send_enter();
+            self.position.take();
+            self.hotspot.take();
+            image_changed = true;
+        }
+        let scalef = scale.to_f64();
+        let position = (
+            ((x - Fixed::from_int(rect.x1())).to_f64() * scalef).round() as i32,
+            ((y - Fixed::from_int(rect.y1())).to_f64() * scalef).round() as i32,
+        );
+        if self.position.replace(Some(position)) != Some(position) {
+            self.send_position(position.0, position.1);
+        }
+        let extents = cursor.extents_at_scale(scale);
+        let hotspot = (-extents.x1(), -extents.y1());
+        if self.hotspot.replace(Some(hotspot)) != Some(hotspot) {
+            self.send_hotspot(hotspot.0, hotspot.1);
+        }
+        if !extents.is_empty() {
+            self.image_size.set(extents.size());
+        }
+        if image_changed {
+            if let Some(session) = self.session.get() {
+                session.update_constraints();
+                session.damage_full();
+                session.perform_cursor_capture();
+            }
+        }
+    }
+
+    fn detach(&self) {
+        self.group
+            .capture_sessions
+            .remove(&(self.client.id, self.id));
+        if let Some(session) = self.session.take() {
+            session.stop();
+        }
+    }
+}
+
+impl ExtImageCopyCaptureCursorSessionV1RequestHandler for ExtImageCopyCaptureCursorSessionV1 {
+    type Error = ExtImageCopyCaptureCursorSessionV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_capture_session(
+        &self,
+        req: GetCaptureSession,
+        slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if self.have_session.replace(true) {
+            return Err(ExtImageCopyCaptureCursorSessionV1Error::DuplicateSession);
+        }
+        let session = Rc::new(ExtImageCopyCaptureSessionV1::new(
+            req.session,
+            &self.client,
+            self.version,
+            false,
+        ));
+        track!(self.client, session);
+        self.client.add_client_obj(&session)?;
+        self.session.set(Some(session.clone()));
+        session.start(Some(CaptureTarget::Cursor(slf.clone())));
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtImageCopyCaptureCursorSessionV1;
+    version = self.version;
+}
+
+impl Object for ExtImageCopyCaptureCursorSessionV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ExtImageCopyCaptureCursorSessionV1);
+
+#[derive(Debug, Error)]
+pub enum ExtImageCopyCaptureCursorSessionV1Error {
+    #[error("The cursor session already has a capture session")]
+    DuplicateSession,
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtImageCopyCaptureCursorSessionV1Error, ClientError);
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
+            wl_buffer::{WlBuffer, WlBufferError, WlBufferStorage},
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        rect::Rect,
+        utils::clonecell::CloneCell,
+        wire::{ext_image_copy_capture_frame_v1::*, ExtImageCopyCaptureFrameV1Id},
+    },
+    std::{cell::Cell, ops::Deref, rc::Rc},
+    thiserror::Error,
+};
+
+pub const FAILURE_REASON_UNKNOWN: u32 = 0;
+pub const FAILURE_REASON_BUFFER_CONSTRAINTS: u32 = 1;
+pub const FAILURE_REASON_STOPPED: u32 = 2;
+
+pub struct ExtImageCopyCaptureFrameV1 {
+    pub id: ExtImageCopyCaptureFrameV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub session: Rc<ExtImageCopyCaptureSessionV1>,
+    pub buffer: CloneCell<Option<Rc<WlBuffer>>>,
+    pub captured: Cell<bool>,
+    pub finished: Cell<bool>,
+}
+
+impl ExtImageCopyCaptureFrameV1 {
+    pub fn send_transform(&self, transform: u32) {
+        self.client.event(Transform {
+            self_id: self.id,
+            transform,
+        });
+    }
+
+    pub fn send_damage(&self, rect: Rect) {
+        self.client.event(Damage {
+            self_id: self.id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        });
+    }
+
+    pub fn send_presentation_time(&self, tv_sec: u64, tv_nsec: u32) {
+        self.client.event(PresentationTime {
+            self_id: self.id,
+            tv_sec_hi: (tv_sec >> 32) as u32,
+            tv_sec_lo: tv_sec as u32,
+            tv_nsec,
+        });
+    }
+
+    pub fn send_ready(&self) {
+        self.client.event(Ready { self_id: self.id });
+    }
+
+    pub fn send_failed(&self, reason: u32) {
+        self.client.event(Failed {
+            self_id: self.id,
+            reason,
+        });
+    }
+
+    /// Returns whether the client has requested a capture that has not yet completed.
+    pub fn is_pending(&self) -> bool {
+        self.captured.get() && !self.finished.get()
+    }
+
+    pub fn fail(&self, reason: u32) {
+        if self.finished.replace(true) {
+            return;
+        }
+        self.buffer.take();
+        self.send_failed(reason);
+    }
+
+    pub fn complete(&self, damage: Rect) {
+        if self.finished.replace(true) {
+            return;
+        }
+        self.buffer.take();
+        let now = self.client.state.now();
+        self.send_transform(0);
+        self.send_damage(damage);
+        self.send_presentation_time(now.0.tv_sec as _, now.0.tv_nsec as _);
+        self.send_ready();
+    }
+
+    fn buffer_matches_constraints(&self, buffer: &WlBuffer) -> bool {
+        let (width, height) = self.session.size();
+        if buffer.rect.size() != (width, height) {
+            return false;
+        }
+        match buffer.storage.borrow_mut().deref() {
+            Some(WlBufferStorage::Shm { stride, .. }) => {
+                self.session.supports_shm_format(buffer.format) && *stride >= width * 4
+            }
+            Some(WlBufferStorage::Dmabuf { .. }) => {
+                self.session.supports_dmabuf_format(buffer.format)
+            }
+            None => false,
+        }
+    }
+
+    fn detach(&self) {
+        self.buffer.take();
+        self.session.frame_destroyed(self);
+    }
+}
+
+impl ExtImageCopyCaptureFrameV1RequestHandler for ExtImageCopyCaptureFrameV1 {
+    type Error = ExtImageCopyCaptureFrameV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn attach_buffer(&self, req: AttachBuffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.captured.get() {
+            return Err(ExtImageCopyCaptureFrameV1Error::AlreadyCaptured);
+        }
+        let buffer = self.client.lookup(req.buffer)?;
+        self.buffer.set(Some(buffer));
+        Ok(())
+    }
+
+    fn damage_buffer(&self, req: DamageBuffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.captured.get() {
+            return Err(ExtImageCopyCaptureFrameV1Error::AlreadyCaptured);
+        }
+        if req.x < 0 || req.y < 0 || req.width <= 0 || req.height <= 0 {
+            return Err(ExtImageCopyCaptureFrameV1Error::InvalidBufferDamage);
+        }
+        Ok(())
+    }
+
+    fn capture(&self, _req: Capture, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.captured.replace(true) {
+            return Err(ExtImageCopyCaptureFrameV1Error::AlreadyCaptured);
+        }
+        let Some(buffer) = self.buffer.get() else {
+            return Err(ExtImageCopyCaptureFrameV1Error::NoBuffer);
+        };
+        if self.session.is_stopped() {
+            self.fail(FAILURE_REASON_STOPPED);
+            return Ok(());
+        }
+        if !self.buffer_matches_constraints(&buffer) {
+            self.fail(FAILURE_REASON_BUFFER_CONSTRAINTS);
+            return Ok(());
+        }
+        buffer.update_framebuffer()?;
+        self.session.frame_captured();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtImageCopyCaptureFrameV1;
+    version = self.version;
+}
+
+impl Object for ExtImageCopyCaptureFrameV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ExtImageCopyCaptureFrameV1);
+
+#[derive(Debug, Error)]
+pub enum ExtImageCopyCaptureFrameV1Error {
+    #[error("The frame has no buffer attached")]
+    NoBuffer,
+    #[error("The buffer damage is invalid")]
+    InvalidBufferDamage,
+    #[error("The frame has already been captured")]
+    AlreadyCaptured,
+    #[error(transparent)]
+    WlBufferError(Box<WlBufferError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtImageCopyCaptureFrameV1Error, WlBufferError);
+efrom!(ExtImageCopyCaptureFrameV1Error, ClientError);
@@ -0,0 +1,148 @@
+use {
+    crate::{
+        client::{Client, ClientCaps, ClientError, CAP_IMAGE_COPY_CAPTURE_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::{
+            ext_image_capture_source_v1::ImageCaptureSource,
+            ext_image_copy_capture_cursor_session_v1::ExtImageCopyCaptureCursorSessionV1,
+            ext_image_copy_capture_session_v1::{CaptureTarget, ExtImageCopyCaptureSessionV1},
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{ext_image_copy_capture_manager_v1::*, ExtImageCopyCaptureManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const OPTIONS_PAINT_CURSORS: u32 = 1;
+
+pub struct ExtImageCopyCaptureManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ExtImageCopyCaptureManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtImageCopyCaptureManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ExtImageCopyCaptureManagerV1Error> {
+        let mgr = Rc::new(ExtImageCopyCaptureManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, mgr);
+        client.add_client_obj(&mgr)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtImageCopyCaptureManagerV1Global,
+    ExtImageCopyCaptureManagerV1,
+    ExtImageCopyCaptureManagerV1Error
+);
+
+simple_add_global!(ExtImageCopyCaptureManagerV1Global);
+
+impl Global for ExtImageCopyCaptureManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_IMAGE_COPY_CAPTURE_MANAGER
+    }
+}
+
+pub struct ExtImageCopyCaptureManagerV1 {
+    pub id: ExtImageCopyCaptureManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ExtImageCopyCaptureManagerV1RequestHandler for ExtImageCopyCaptureManagerV1 {
+    type Error = ExtImageCopyCaptureManagerV1Error;
+
+    fn create_session(&self, req: CreateSession, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.options & !OPTIONS_PAINT_CURSORS != 0 {
+            return Err(ExtImageCopyCaptureManagerV1Error::InvalidOption(
+                req.options,
+            ));
+        }
+        let source = self.client.lookup(req.source)?;
+        let session = Rc::new(ExtImageCopyCaptureSessionV1::new(
+            req.session,
+            &self.client,
+            self.version,
+            req.options & OPTIONS_PAINT_CURSORS != 0,
+        ));
+        track!(self.client, session);
+        self.client.add_client_obj(&session)?;
+        let target = match &source.ty {
+            ImageCaptureSource::Output(o) => o.node().map(CaptureTarget::Output),
+            ImageCaptureSource::Toplevel(tl) => tl.clone().map(CaptureTarget::Toplevel),
+        };
+        session.start(target);
+        Ok(())
+    }
+
+    fn create_pointer_cursor_session(
+        &self,
+        req: CreatePointerCursorSession,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let source = self.client.lookup(req.source)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let group = pointer.seat.global.cursor_group();
+        let session = Rc::new(ExtImageCopyCaptureCursorSessionV1::new(
+            req.session,
+            &self.client,
+            self.version,
+            source.ty.clone(),
+            group,
+        ));
+        track!(self.client, session);
+        self.client.add_client_obj(&session)?;
+        group
+            .capture_sessions
+            .set((self.client.id, session.id), session.clone());
+        session.update(false);
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtImageCopyCaptureManagerV1;
+    version = self.version;
+}
+
+impl Object for ExtImageCopyCaptureManagerV1 {}
+
+simple_add_obj!(ExtImageCopyCaptureManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ExtImageCopyCaptureManagerV1Error {
+    #[error("Unknown options {0:#x}")]
+    InvalidOption(u32),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtImageCopyCaptureManagerV1Error, ClientError);
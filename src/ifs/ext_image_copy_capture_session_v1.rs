@@ -0,0 +1,594 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        format::{Format, ARGB8888, XRGB8888},
+        gfx_api::{GfxError, GfxFramebuffer, GfxTexture, SyncFile},
+        ifs::{
+            ext_image_copy_capture_cursor_session_v1::ExtImageCopyCaptureCursorSessionV1,
+            ext_image_copy_capture_frame_v1::{
+                ExtImageCopyCaptureFrameV1, FAILURE_REASON_BUFFER_CONSTRAINTS,
+                FAILURE_REASON_STOPPED, FAILURE_REASON_UNKNOWN,
+            },
+            wl_buffer::WlBufferStorage,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        rect::Rect,
+        scale::Scale,
+        state::{ShmScreencopyError, State},
+        tree::{OutputNode, ToplevelNode},
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            errorfmt::ErrorFmt,
+        },
+        video::Modifier,
+        wire::{ext_image_copy_capture_session_v1::*, ExtImageCopyCaptureSessionV1Id},
+    },
+    jay_config::video::Transform,
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const FORMATS: [&Format; 2] = [ARGB8888, XRGB8888];
+
+pub async fn perform_toplevel_image_copies(state: Rc<State>) {
+    loop {
+        let session = state.pending_toplevel_image_copies.pop().await;
+        session.capture_scheduled.set(false);
+        session.perform_toplevel_capture();
+    }
+}
+
+#[derive(Clone)]
+pub enum CaptureTarget {
+    Output(Rc<OutputNode>),
+    Toplevel(Rc<dyn ToplevelNode>),
+    Cursor(Rc<ExtImageCopyCaptureCursorSessionV1>),
+}
+
+unsafe impl UnsafeCellCloneSafe for CaptureTarget {}
+
+pub struct ExtImageCopyCaptureSessionV1 {
+    pub id: ExtImageCopyCaptureSessionV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub target: CloneCell<Option<CaptureTarget>>,
+    pub paint_cursors: bool,
+    pub size: Cell<(i32, i32)>,
+    pub damage: Cell<Option<Rect>>,
+    pub frame: CloneCell<Option<Rc<ExtImageCopyCaptureFrameV1>>>,
+    pub dmabuf_formats: RefCell<Vec<&'static Format>>,
+    pub capture_scheduled: Cell<bool>,
+}
+
+pub fn toplevel_scale(tl: &dyn ToplevelNode) -> Scale {
+    match tl.tl_data().workspace.get() {
+        None => Scale::default(),
+        Some(ws) => ws.output.get().global.persistent.scale.get(),
+    }
+}
+
+fn scale_rect(rect: Rect, scale: Scale) -> Rect {
+    if scale == 1 {
+        return rect;
+    }
+    let scale = scale.to_f64();
+    Rect::new(
+        (rect.x1() as f64 * scale).floor() as _,
+        (rect.y1() as f64 * scale).floor() as _,
+        (rect.x2() as f64 * scale).ceil() as _,
+        (rect.y2() as f64 * scale).ceil() as _,
+    )
+    .unwrap()
+}
+
+impl ExtImageCopyCaptureSessionV1 {
+    pub fn new(
+        id: ExtImageCopyCaptureSessionV1Id,
+        client: &Rc<Client>,
+        version: Version,
+        paint_cursors: bool,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            target: Default::default(),
+            paint_cursors,
+            size: Cell::new((0, 0)),
+            damage: Cell::new(None),
+            frame: Default::default(),
+            dmabuf_formats: Default::default(),
+            capture_scheduled: Cell::new(false),
+        }
+    }
+
+    /// Attaches the session to its target and sends the initial buffer constraints.
+    ///
+    /// If the target no longer exists, the session is stopped immediately.
+    pub fn start(self: &Rc<Self>, target: Option<CaptureTarget>) {
+        let Some(target) = target else {
+            self.send_stopped();
+            return;
+        };
+        let key = (self.client.id, self.id);
+        match &target {
+            CaptureTarget::Output(o) => {
+                o.image_copy_sessions.set(key, self.clone());
+            }
+            CaptureTarget::Toplevel(tl) => {
+                tl.tl_data().image_copy_sessions.set(key, self.clone());
+            }
+            CaptureTarget::Cursor(_) => {}
+        }
+        if !matches!(target, CaptureTarget::Cursor(_)) {
+            self.client.state.image_copy_sessions.set(key, self.clone());
+        }
+        let output = match &target {
+            CaptureTarget::Output(o) => Some(o.clone()),
+            _ => None,
+        };
+        self.target.set(Some(target));
+        if let Some(output) = output {
+            output.screencast_changed();
+        }
+        self.send_constraints();
+    }
+
+    pub fn send_buffer_size(&self, width: i32, height: i32) {
+        self.client.event(BufferSize {
+            self_id: self.id,
+            width: width as _,
+            height: height as _,
+        });
+    }
+
+    pub fn send_shm_format(&self, format: &Format) {
+        if let Some(format) = format.wl_id {
+            self.client.event(ShmFormat {
+                self_id: self.id,
+                format,
+            });
+        }
+    }
+
+    pub fn send_dmabuf_device(&self, device: uapi::c::dev_t) {
+        self.client.event(DmabufDevice {
+            self_id: self.id,
+            device: uapi::as_bytes(&device),
+        });
+    }
+
+    pub fn send_dmabuf_format(&self, format: &Format, modifiers: &[Modifier]) {
+        self.client.event(DmabufFormat {
+            self_id: self.id,
+            format: format.drm,
+            modifiers,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_stopped(&self) {
+        self.client.event(Stopped { self_id: self.id });
+    }
+
+    pub fn size(&self) -> (i32, i32) {
+        self.size.get()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.target.is_none()
+    }
+
+    pub fn supports_shm_format(&self, format: &Format) -> bool {
+        FORMATS.iter().any(|f| f.drm == format.drm)
+    }
+
+    pub fn supports_dmabuf_format(&self, format: &Format) -> bool {
+        self.dmabuf_formats
+            .borrow()
+            .iter()
+            .any(|f| f.drm == format.drm)
+    }
+
+    fn target_size(&self) -> (i32, i32) {
+        match self.target.get() {
+            None => (0, 0),
+            Some(CaptureTarget::Output(o)) => o.global.pixel_size(),
+            Some(CaptureTarget::Toplevel(tl)) => {
+                let (width, height) = tl.tl_data().desired_extents.get().size();
+                toplevel_scale(&*tl).pixel_size(width, height)
+            }
+            Some(CaptureTarget::Cursor(c)) => c.image_size(),
+        }
+    }
+
+    pub fn send_constraints(&self) {
+        let (width, height) = self.target_size();
+        self.size.set((width, height));
+        self.damage.set(Rect::new_sized(0, 0, width, height));
+        self.send_buffer_size(width, height);
+        for format in FORMATS {
+            self.send_shm_format(format);
+        }
+        let mut dmabuf_formats = self.dmabuf_formats.borrow_mut();
+        dmabuf_formats.clear();
+        let state = &self.client.state;
+        if let (Some(ctx), Some(feedback)) = (state.render_ctx.get(), state.drm_feedback.get()) {
+            self.send_dmabuf_device(feedback.shared.main_device);
+            let formats = ctx.formats();
+            for format in FORMATS {
+                if let Some(f) = formats.get(&format.drm) {
+                    if f.write_modifiers.is_empty() {
+                        continue;
+                    }
+                    let modifiers: Vec<_> = f.write_modifiers.iter().copied().collect();
+                    self.send_dmabuf_format(format, &modifiers);
+                    dmabuf_formats.push(format);
+                }
+            }
+        }
+        self.send_done();
+    }
+
+    /// Re-sends the buffer constraints if the size of the source has changed.
+    pub fn update_constraints(&self) {
+        if self.is_stopped() {
+            return;
+        }
+        if self.target_size() != self.size.get() {
+            self.send_constraints();
+            if let Some(frame) = self.pending_frame() {
+                frame.fail(FAILURE_REASON_BUFFER_CONSTRAINTS);
+            }
+        }
+    }
+
+    /// Accumulates damage given in compositor coordinates.
+    pub fn damage_logical(&self, rect: Rect) {
+        let (pos, scale, transform) = match self.target.get() {
+            Some(CaptureTarget::Output(o)) => (
+                o.global.pos.get(),
+                o.global.persistent.scale.get(),
+                o.global.persistent.transform.get(),
+            ),
+            Some(CaptureTarget::Toplevel(tl)) => (
+                tl.node_absolute_position(),
+                toplevel_scale(&*tl),
+                Transform::None,
+            ),
+            _ => return,
+        };
+        if !pos.intersects(&rect) {
+            return;
+        }
+        let damage = if transform != Transform::None {
+            let (width, height) = self.size.get();
+            Rect::new_sized_unchecked(0, 0, width, height)
+        } else {
+            let rect = rect.intersect(pos).move_(-pos.x1(), -pos.y1());
+            scale_rect(rect, scale)
+        };
+        self.add_damage(damage);
+    }
+
+    pub fn damage_full(&self) {
+        let (width, height) = self.size.get();
+        self.add_damage(Rect::new_sized_unchecked(0, 0, width, height));
+    }
+
+    fn add_damage(&self, rect: Rect) {
+        let damage = match self.damage.get() {
+            Some(d) => d.union(rect),
+            None => rect,
+        };
+        self.damage.set(Some(damage));
+    }
+
+    fn pending_frame(&self) -> Option<Rc<ExtImageCopyCaptureFrameV1>> {
+        self.frame.get().filter(|f| f.is_pending())
+    }
+
+    pub fn frame_destroyed(&self, frame: &ExtImageCopyCaptureFrameV1) {
+        if let Some(f) = self.frame.get() {
+            if f.id == frame.id {
+                self.frame.take();
+            }
+        }
+    }
+
+    pub fn frame_captured(self: &Rc<Self>) {
+        if self.damage.get().is_none() {
+            return;
+        }
+        match self.target.get() {
+            None => {}
+            Some(CaptureTarget::Output(o)) => {
+                o.global.connector.connector.damage();
+            }
+            Some(CaptureTarget::Toplevel(tl)) => {
+                if tl.node_visible() {
+                    self.client.state.damage(tl.node_absolute_position());
+                } else {
+                    self.schedule_toplevel_capture();
+                }
+            }
+            Some(CaptureTarget::Cursor(_)) => {
+                self.perform_cursor_capture();
+            }
+        }
+    }
+
+    pub fn schedule_toplevel_capture(self: &Rc<Self>) {
+        if self.pending_frame().is_none() || self.capture_scheduled.replace(true) {
+            return;
+        }
+        self.client
+            .state
+            .pending_toplevel_image_copies
+            .push(self.clone());
+    }
+
+    fn complete_frame(
+        &self,
+        copy: impl FnOnce(&WlBufferStorage, &'static Format) -> Result<(), CaptureError>,
+    ) {
+        let Some(frame) = self.pending_frame() else {
+            return;
+        };
+        let Some(damage) = self.damage.get() else {
+            return;
+        };
+        if self.size.get() != self.target_size() {
+            self.update_constraints();
+            return;
+        }
+        let Some(buffer) = frame.buffer.get() else {
+            frame.fail(FAILURE_REASON_UNKNOWN);
+            return;
+        };
+        if buffer.destroyed() {
+            frame.fail(FAILURE_REASON_UNKNOWN);
+            return;
+        }
+        let res = match buffer.storage.borrow_mut().deref() {
+            Some(storage) => copy(storage, buffer.format),
+            None => Err(CaptureError::NoStorage),
+        };
+        if let Err(e) = res {
+            log::warn!("Could not perform image copy: {}", ErrorFmt(e));
+            frame.fail(FAILURE_REASON_UNKNOWN);
+            return;
+        }
+        self.damage.set(None);
+        let (width, height) = self.size.get();
+        frame.complete(damage.intersect(Rect::new_sized_unchecked(0, 0, width, height)));
+    }
+
+    pub fn copy_texture(
+        &self,
+        on: &OutputNode,
+        tex: &Rc<dyn GfxTexture>,
+        render_hardware_cursors: bool,
+        x_off: i32,
+        y_off: i32,
+        size: Option<(i32, i32)>,
+    ) {
+        let state = &self.client.state;
+        let position = on.global.pos.get();
+        let transform = on.global.persistent.transform.get();
+        let color_transform = on.screencopy_color_transform();
+        let render_hardware_cursors = render_hardware_cursors && self.paint_cursors;
+        self.complete_frame(|storage, format| match storage {
+            WlBufferStorage::Shm { mem, stride } => {
+                let (width, height) = self.size.get();
+                state.perform_shm_screencopy(
+                    tex,
+                    position,
+                    render_hardware_cursors,
+                    x_off,
+                    y_off,
+                    size,
+                    Rect::new_sized_unchecked(0, 0, width, height),
+                    &self.client,
+                    mem,
+                    *stride,
+                    format,
+                    transform,
+                    color_transform,
+                )?;
+                Ok(())
+            }
+            WlBufferStorage::Dmabuf { fb, .. } => {
+                let fb = fb.as_ref().ok_or(CaptureError::NoFramebuffer)?;
+                state.perform_screencopy(
+                    tex,
+                    fb,
+                    position,
+                    render_hardware_cursors,
+                    x_off,
+                    y_off,
+                    size,
+                    transform,
+                    color_transform,
+                )?;
+                Ok(())
+            }
+        });
+    }
+
+    fn perform_toplevel_capture(&self) {
+        let Some(CaptureTarget::Toplevel(tl)) = self.target.get() else {
+            return;
+        };
+        let state = &self.client.state;
+        let scale = toplevel_scale(&*tl);
+        let render = |fb: &Rc<dyn GfxFramebuffer>| {
+            fb.render_node(
+                tl.tl_as_node(),
+                state,
+                Some(tl.node_absolute_position()),
+                None,
+                scale,
+                self.paint_cursors,
+                self.paint_cursors,
+                false,
+                Transform::None,
+            )
+        };
+        self.complete_frame(|storage, format| self.copy_rendered(storage, format, render));
+    }
+
+    pub fn perform_cursor_capture(&self) {
+        let Some(CaptureTarget::Cursor(cs)) = self.target.get() else {
+            return;
+        };
+        let Some((cursor, scale)) = cs.cursor() else {
+            return;
+        };
+        let state = &self.client.state;
+        let render = |fb: &Rc<dyn GfxFramebuffer>| {
+            fb.render_hardware_cursor(
+                cursor.deref(),
+                state,
+                scale,
+                Transform::None,
+                state.color_manager.srgb(),
+            )
+        };
+        self.complete_frame(|storage, format| self.copy_rendered(storage, format, render));
+    }
+
+    fn copy_rendered(
+        &self,
+        storage: &WlBufferStorage,
+        format: &'static Format,
+        render: impl FnOnce(&Rc<dyn GfxFramebuffer>) -> Result<Option<SyncFile>, GfxError>,
+    ) -> Result<(), CaptureError> {
+        match storage {
+            WlBufferStorage::Shm { mem, stride } => {
+                let (width, height) = self.size.get();
+                self.client.state.perform_shm_bridge_copy(
+                    &self.client,
+                    mem,
+                    width,
+                    height,
+                    *stride,
+                    format,
+                    render,
+                )?;
+            }
+            WlBufferStorage::Dmabuf { fb, .. } => {
+                let fb = fb.as_ref().ok_or(CaptureError::NoFramebuffer)?;
+                render(fb)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        if self.is_stopped() {
+            return;
+        }
+        self.detach();
+        if let Some(frame) = self.pending_frame() {
+            frame.fail(FAILURE_REASON_STOPPED);
+        }
+        self.send_stopped();
+    }
+
+    fn detach(&self) {
+        let Some(target) = self.target.take() else {
+            return;
+        };
+        let key = (self.client.id, self.id);
+        self.client.state.image_copy_sessions.remove(&key);
+        match target {
+            CaptureTarget::Output(o) => {
+                o.image_copy_sessions.remove(&key);
+                o.screencast_changed();
+            }
+            CaptureTarget::Toplevel(tl) => {
+                tl.tl_data().image_copy_sessions.remove(&key);
+            }
+            CaptureTarget::Cursor(c) => {
+                c.session.take();
+            }
+        }
+    }
+}
+
+impl ExtImageCopyCaptureSessionV1RequestHandler for ExtImageCopyCaptureSessionV1 {
+    type Error = ExtImageCopyCaptureSessionV1Error;
+
+    fn create_frame(&self, req: CreateFrame, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.frame.is_some() {
+            return Err(ExtImageCopyCaptureSessionV1Error::DuplicateFrame);
+        }
+        let frame = Rc::new(ExtImageCopyCaptureFrameV1 {
+            id: req.frame,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            session: slf.clone(),
+            buffer: Default::default(),
+            captured: Cell::new(false),
+            finished: Cell::new(false),
+        });
+        track!(self.client, frame);
+        self.client.add_client_obj(&frame)?;
+        self.frame.set(Some(frame));
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.frame.take();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtImageCopyCaptureSessionV1;
+    version = self.version;
+}
+
+impl Object for ExtImageCopyCaptureSessionV1 {
+    fn break_loops(&self) {
+        self.detach();
+        self.frame.take();
+    }
+}
+
+simple_add_obj!(ExtImageCopyCaptureSessionV1);
+
+#[derive(Debug, Error)]
+enum CaptureError {
+    #[error("The buffer has no storage")]
+    NoStorage,
+    #[error("The buffer has no framebuffer")]
+    NoFramebuffer,
+    #[error(transparent)]
+    ShmScreencopyError(#[from] ShmScreencopyError),
+    #[error(transparent)]
+    GfxError(#[from] GfxError),
+}
+
+#[derive(Debug, Error)]
+pub enum ExtImageCopyCaptureSessionV1Error {
+    #[error("The session already has a frame")]
+    DuplicateFrame,
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtImageCopyCaptureSessionV1Error, ClientError);
@@ -0,0 +1,127 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ext_workspace_handle_v1::ExtWorkspaceHandleV1,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::OutputNode,
+        utils::clonecell::CloneCell,
+        wire::{ext_workspace_group_handle_v1::*, ExtWorkspaceGroupHandleV1Id, WlOutputId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const GROUP_CAPABILITIES_CREATE_WORKSPACE: u32 = 1;
+
+pub struct ExtWorkspaceGroupHandleV1 {
+    pub id: ExtWorkspaceGroupHandleV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub manager: Rc<ExtWorkspaceManagerV1>,
+    pub output: CloneCell<Option<Rc<OutputNode>>>,
+}
+
+impl ExtWorkspaceGroupHandleV1 {
+    pub fn detach(&self) {
+        if let Some(output) = self.output.take() {
+            output
+                .ext_workspace_groups
+                .remove(&(self.client.id, self.id));
+            self.manager.groups.remove(&output.id);
+        }
+        for handle in self.manager.workspaces.lock().values() {
+            if handle.group.get().map(|g| g.id) == Some(self.id) {
+                handle.group.take();
+            }
+        }
+    }
+
+    /// Sends the removed event after the output has been disconnected.
+    pub fn output_removed(&self) {
+        self.detach();
+        self.send_removed();
+        self.manager.schedule_done();
+    }
+
+    pub fn send_capabilities(&self, capabilities: u32) {
+        self.client.event(Capabilities {
+            self_id: self.id,
+            capabilities,
+        });
+    }
+
+    pub fn send_output_enter(&self, output: WlOutputId) {
+        self.client.event(OutputEnter {
+            self_id: self.id,
+            output,
+        });
+    }
+
+    pub fn send_workspace_enter(&self, handle: &ExtWorkspaceHandleV1) {
+        self.client.event(WorkspaceEnter {
+            self_id: self.id,
+            workspace: handle.id,
+        });
+    }
+
+    pub fn send_workspace_leave(&self, handle: &ExtWorkspaceHandleV1) {
+        self.client.event(WorkspaceLeave {
+            self_id: self.id,
+            workspace: handle.id,
+        });
+    }
+
+    fn send_removed(&self) {
+        self.client.event(Removed { self_id: self.id });
+    }
+}
+
+impl ExtWorkspaceGroupHandleV1RequestHandler for ExtWorkspaceGroupHandleV1 {
+    type Error = ExtWorkspaceGroupHandleV1Error;
+
+    fn create_workspace(
+        &self,
+        req: CreateWorkspace<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if let Some(output) = self.output.get() {
+            self.manager.create(&output, req.workspace);
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtWorkspaceGroupHandleV1;
+    version = self.version;
+}
+
+impl Object for ExtWorkspaceGroupHandleV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+dedicated_add_obj!(
+    ExtWorkspaceGroupHandleV1,
+    ExtWorkspaceGroupHandleV1Id,
+    workspace_groups
+);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceGroupHandleV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceGroupHandleV1Error, ClientError);
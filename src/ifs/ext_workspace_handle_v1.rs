@@ -0,0 +1,185 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::WorkspaceNode,
+        utils::clonecell::CloneCell,
+        wire::{ext_workspace_handle_v1::*, ExtWorkspaceHandleV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub const STATE_ACTIVE: u32 = 1;
+pub const STATE_URGENT: u32 = 2;
+
+pub const WORKSPACE_CAPABILITIES_ACTIVATE: u32 = 1;
+pub const WORKSPACE_CAPABILITIES_REMOVE: u32 = 4;
+pub const WORKSPACE_CAPABILITIES_ASSIGN: u32 = 8;
+
+pub struct ExtWorkspaceHandleV1 {
+    pub id: ExtWorkspaceHandleV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub manager: Rc<ExtWorkspaceManagerV1>,
+    pub workspace: CloneCell<Option<Rc<WorkspaceNode>>>,
+    pub group: CloneCell<Option<Rc<ExtWorkspaceGroupHandleV1>>>,
+    pub state: Cell<u32>,
+}
+
+impl ExtWorkspaceHandleV1 {
+    pub fn detach(&self) {
+        if let Some(ws) = self.workspace.take() {
+            ws.ext_workspaces.remove(&(self.client.id, self.id));
+            self.manager.workspaces.remove(&ws.id);
+        }
+        self.group.take();
+    }
+
+    pub fn compute_state(&self, ws: &WorkspaceNode) -> u32 {
+        let mut state = 0;
+        if ws.visible.get() {
+            state |= STATE_ACTIVE;
+        }
+        if ws.attention_requests.active() {
+            state |= STATE_URGENT;
+        }
+        state
+    }
+
+    pub fn update_state(&self) {
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let state = self.compute_state(&ws);
+        if self.state.get() != state {
+            self.send_state(state);
+            self.manager.schedule_done();
+        }
+    }
+
+    /// Moves the workspace into the group of its current output.
+    pub fn update_group(&self) {
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let group = self.manager.groups.get(&ws.output.get().id);
+        let old = self.group.get();
+        if old.as_ref().map(|g| g.id) == group.as_ref().map(|g| g.id) {
+            return;
+        }
+        if let Some(old) = old {
+            old.send_workspace_leave(self);
+        }
+        if let Some(group) = &group {
+            group.send_workspace_enter(self);
+        }
+        self.group.set(group);
+        self.manager.schedule_done();
+    }
+
+    /// Sends the removed event after the workspace has been destroyed.
+    pub fn workspace_removed(&self) {
+        if let Some(group) = self.group.get() {
+            group.send_workspace_leave(self);
+        }
+        self.detach();
+        self.send_removed();
+        self.manager.schedule_done();
+    }
+
+    pub fn send_id(&self, id: &str) {
+        self.client.event(Id {
+            self_id: self.id,
+            id,
+        });
+    }
+
+    pub fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    pub fn send_state(&self, state: u32) {
+        self.state.set(state);
+        self.client.event(State {
+            self_id: self.id,
+            state,
+        });
+    }
+
+    pub fn send_capabilities(&self, capabilities: u32) {
+        self.client.event(Capabilities {
+            self_id: self.id,
+            capabilities,
+        });
+    }
+
+    fn send_removed(&self) {
+        self.client.event(Removed { self_id: self.id });
+    }
+}
+
+impl ExtWorkspaceHandleV1RequestHandler for ExtWorkspaceHandleV1 {
+    type Error = ExtWorkspaceHandleV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn activate(&self, _req: Activate, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(ws) = self.workspace.get() {
+            self.manager.activate(&ws);
+        }
+        Ok(())
+    }
+
+    fn deactivate(&self, _req: Deactivate, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn assign(&self, req: Assign, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let group = self.client.lookup(req.workspace_group)?;
+        if let (Some(ws), Some(output)) = (self.workspace.get(), group.output.get()) {
+            self.manager.assign(&ws, &output);
+        }
+        Ok(())
+    }
+
+    fn remove(&self, _req: Remove, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(ws) = self.workspace.get() {
+            self.manager.remove(&ws);
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtWorkspaceHandleV1;
+    version = self.version;
+}
+
+impl Object for ExtWorkspaceHandleV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ExtWorkspaceHandleV1);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceHandleV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceHandleV1Error, ClientError);
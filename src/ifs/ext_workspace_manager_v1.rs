@@ -0,0 +1,380 @@
+use {
+    crate::{
+        client::{Client, ClientCaps, ClientError, CAP_WORKSPACE_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::{
+            ext_workspace_group_handle_v1::{
+                ExtWorkspaceGroupHandleV1, GROUP_CAPABILITIES_CREATE_WORKSPACE,
+            },
+            ext_workspace_handle_v1::{
+                ExtWorkspaceHandleV1, WORKSPACE_CAPABILITIES_ACTIVATE,
+                WORKSPACE_CAPABILITIES_ASSIGN, WORKSPACE_CAPABILITIES_REMOVE,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        state::State,
+        tree::{
+            move_ws_to_output, OutputNode, OutputNodeId, WorkspaceNode, WorkspaceNodeId,
+            WsMoveConfig,
+        },
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap},
+        wire::{
+            ext_workspace_manager_v1::*, ExtWorkspaceGroupHandleV1Id, ExtWorkspaceHandleV1Id,
+            ExtWorkspaceManagerV1Id,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub async fn workspace_manager_done(state: Rc<State>) {
+    loop {
+        let manager = state.pending_workspace_manager_done.pop().await;
+        manager.done_scheduled.set(false);
+        if !manager.stopped.get() {
+            manager.send_done();
+        }
+    }
+}
+
+pub struct ExtWorkspaceManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ExtWorkspaceManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtWorkspaceManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ExtWorkspaceManagerV1Error> {
+        let obj = Rc::new(ExtWorkspaceManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            groups: Default::default(),
+            workspaces: Default::default(),
+            pending: Default::default(),
+            done_scheduled: Cell::new(false),
+            stopped: Cell::new(false),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        let state = &client.state;
+        let outputs: Vec<_> = state.root.outputs.lock().values().cloned().collect();
+        for output in &outputs {
+            obj.announce_output(output);
+        }
+        for output in outputs.iter().chain(state.dummy_output.get().as_ref()) {
+            for ws in output.workspaces.iter() {
+                obj.announce_workspace(&ws);
+            }
+        }
+        obj.schedule_done();
+        state.workspace_managers.set((client.id, id), obj);
+        Ok(())
+    }
+}
+
+enum PendingAction {
+    Activate(Rc<WorkspaceNode>),
+    Assign(Rc<WorkspaceNode>, Rc<OutputNode>),
+    Remove(Rc<WorkspaceNode>),
+    Create(Rc<OutputNode>, String),
+}
+
+pub struct ExtWorkspaceManagerV1 {
+    pub id: ExtWorkspaceManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub groups: CopyHashMap<OutputNodeId, Rc<ExtWorkspaceGroupHandleV1>>,
+    pub workspaces: CopyHashMap<WorkspaceNodeId, Rc<ExtWorkspaceHandleV1>>,
+    pending: RefCell<Vec<PendingAction>>,
+    done_scheduled: Cell<bool>,
+    pub stopped: Cell<bool>,
+}
+
+impl ExtWorkspaceManagerV1 {
+    fn detach(&self) {
+        self.client
+            .state
+            .workspace_managers
+            .remove(&(self.client.id, self.id));
+        for group in self.groups.clear().into_values() {
+            group.detach();
+        }
+        for handle in self.workspaces.clear().into_values() {
+            handle.detach();
+        }
+        self.pending.borrow_mut().clear();
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn send_workspace_group(&self, group: &ExtWorkspaceGroupHandleV1) {
+        self.client.event(WorkspaceGroup {
+            self_id: self.id,
+            workspace_group: group.id,
+        });
+    }
+
+    fn send_workspace(&self, handle: &ExtWorkspaceHandleV1) {
+        self.client.event(Workspace {
+            self_id: self.id,
+            workspace: handle.id,
+        });
+    }
+
+    pub fn schedule_done(self: &Rc<Self>) {
+        if !self.done_scheduled.replace(true) {
+            self.client
+                .state
+                .pending_workspace_manager_done
+                .push(self.clone());
+        }
+    }
+
+    pub fn announce_output(self: &Rc<Self>, output: &Rc<OutputNode>) {
+        if output.is_dummy {
+            return;
+        }
+        let id: ExtWorkspaceGroupHandleV1Id = match self.client.new_id() {
+            Ok(i) => i,
+            Err(e) => {
+                self.client.error(e);
+                return;
+            }
+        };
+        let group = Rc::new(ExtWorkspaceGroupHandleV1 {
+            id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            manager: self.clone(),
+            output: CloneCell::new(Some(output.clone())),
+        });
+        track!(self.client, group);
+        self.client.add_server_obj(&group);
+        self.send_workspace_group(&group);
+        group.send_capabilities(GROUP_CAPABILITIES_CREATE_WORKSPACE);
+        output
+            .global
+            .for_each_binding(self.client.id, |b| group.send_output_enter(b.id));
+        output
+            .ext_workspace_groups
+            .set((self.client.id, group.id), group.clone());
+        self.groups.set(output.id, group);
+        self.schedule_done();
+    }
+
+    pub fn announce_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+        if ws.is_dummy {
+            return;
+        }
+        let id: ExtWorkspaceHandleV1Id = match self.client.new_id() {
+            Ok(i) => i,
+            Err(e) => {
+                self.client.error(e);
+                return;
+            }
+        };
+        let handle = Rc::new(ExtWorkspaceHandleV1 {
+            id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            manager: self.clone(),
+            workspace: CloneCell::new(Some(ws.clone())),
+            group: Default::default(),
+            state: Cell::new(0),
+        });
+        track!(self.client, handle);
+        self.client.add_server_obj(&handle);
+        self.send_workspace(&handle);
+        handle.send_id(&ws.name);
+        handle.send_name(&ws.name);
+        handle.send_capabilities(
+            WORKSPACE_CAPABILITIES_ACTIVATE
+                | WORKSPACE_CAPABILITIES_REMOVE
+                | WORKSPACE_CAPABILITIES_ASSIGN,
+        );
+        handle.send_state(handle.compute_state(ws));
+        ws.ext_workspaces
+            .set((self.client.id, handle.id), handle.clone());
+        self.workspaces.set(ws.id, handle.clone());
+        handle.update_group();
+        self.schedule_done();
+    }
+
+    fn push_action(&self, action: PendingAction) {
+        self.pending.borrow_mut().push(action);
+    }
+
+    pub fn activate(&self, ws: &Rc<WorkspaceNode>) {
+        self.push_action(PendingAction::Activate(ws.clone()));
+    }
+
+    pub fn assign(&self, ws: &Rc<WorkspaceNode>, output: &Rc<OutputNode>) {
+        self.push_action(PendingAction::Assign(ws.clone(), output.clone()));
+    }
+
+    pub fn remove(&self, ws: &Rc<WorkspaceNode>) {
+        self.push_action(PendingAction::Remove(ws.clone()));
+    }
+
+    pub fn create(&self, output: &Rc<OutputNode>, name: &str) {
+        self.push_action(PendingAction::Create(output.clone(), name.to_string()));
+    }
+
+    fn perform_activate(&self, ws: &Rc<WorkspaceNode>) {
+        if ws.output_link.borrow().is_none() {
+            return;
+        }
+        let output = ws.output.get();
+        if output.is_dummy || !output.show_workspace(ws) {
+            return;
+        }
+        ws.flush_jay_workspaces();
+        output.schedule_update_render_data();
+        self.client.state.tree_changed();
+    }
+
+    fn perform_assign(&self, ws: &Rc<WorkspaceNode>, output: &Rc<OutputNode>) {
+        if ws.is_dummy || output.is_dummy || output.global.destroyed.get() {
+            return;
+        }
+        if ws.output.get().id == output.id {
+            return;
+        }
+        let link = match &*ws.output_link.borrow() {
+            None => return,
+            Some(l) => l.to_ref(),
+        };
+        let config = WsMoveConfig {
+            make_visible_if_empty: true,
+            source_is_destroyed: false,
+        };
+        move_ws_to_output(&link, output, config);
+        ws.desired_output.set(output.global.output_id.clone());
+        self.client.state.tree_changed();
+    }
+
+    fn perform_remove(&self, ws: &Rc<WorkspaceNode>) {
+        if ws.output_link.borrow().is_none() || !ws.is_empty() {
+            return;
+        }
+        let output = ws.output.get();
+        if output.is_dummy {
+            return;
+        }
+        // Empty workspaces are destroyed as soon as they are no longer visible.
+        let Some(next) = output.workspaces.iter().find(|w| w.id != ws.id) else {
+            return;
+        };
+        if output.show_workspace(&next) {
+            next.flush_jay_workspaces();
+            output.schedule_update_render_data();
+            self.client.state.tree_changed();
+        }
+    }
+
+    fn perform_create(&self, output: &Rc<OutputNode>, name: &str) {
+        if output.is_dummy || output.global.destroyed.get() {
+            return;
+        }
+        if self.client.state.workspaces.contains(name) {
+            return;
+        }
+        let ws = output.create_workspace(name);
+        output.show_workspace(&ws);
+        ws.flush_jay_workspaces();
+        output.schedule_update_render_data();
+        self.client.state.tree_changed();
+    }
+}
+
+impl ExtWorkspaceManagerV1RequestHandler for ExtWorkspaceManagerV1 {
+    type Error = ExtWorkspaceManagerV1Error;
+
+    fn commit(&self, _req: Commit, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let actions = mem::take(&mut *self.pending.borrow_mut());
+        for action in actions {
+            match action {
+                PendingAction::Activate(ws) => self.perform_activate(&ws),
+                PendingAction::Assign(ws, output) => self.perform_assign(&ws, &output),
+                PendingAction::Remove(ws) => self.perform_remove(&ws),
+                PendingAction::Create(output, name) => self.perform_create(&output, &name),
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&self, _req: Stop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.stopped.replace(true) {
+            return Ok(());
+        }
+        self.detach();
+        self.send_finished();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtWorkspaceManagerV1Global,
+    ExtWorkspaceManagerV1,
+    ExtWorkspaceManagerV1Error
+);
+
+impl Global for ExtWorkspaceManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_WORKSPACE_MANAGER
+    }
+}
+
+simple_add_global!(ExtWorkspaceManagerV1Global);
+
+object_base! {
+    self = ExtWorkspaceManagerV1;
+    version = self.version;
+}
+
+impl Object for ExtWorkspaceManagerV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ExtWorkspaceManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceManagerV1Error, ClientError);
@@ -5,6 +5,7 @@ use {
         globals::{Global, GlobalName},
         ifs::{
             jay_ei_session_builder::JayEiSessionBuilder,
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_idle::JayIdle,
             jay_input::JayInput,
             jay_log_file::JayLogFile,
@@ -21,7 +22,11 @@ use {
         },
         leaks::Tracker,
         object::{Object, Version},
-        screenshoter::take_screenshot,
+        screenshoter::{
+            take_output_screenshot, take_screenshot, take_toplevel_screenshot, ScreenshooterError,
+            Screenshot,
+        },
+        state::State,
         utils::errorfmt::ErrorFmt,
         wire::{jay_compositor::*, JayCompositorId, JayScreenshotId},
     },
@@ -32,6 +37,8 @@ use {
 };
 
 pub const CREATE_EI_SESSION_SINCE: Version = Version(5);
+pub const CREATE_GLOBAL_SHORTCUTS_SINCE: Version = Version(8);
+pub const TAKE_OUTPUT_SCREENSHOT_SINCE: Version = Version(9);
 
 pub struct JayCompositorGlobal {
     name: GlobalName,
@@ -69,7 +76,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        5
+        11
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -105,7 +112,7 @@ impl JayCompositor {
     fn take_screenshot_impl(
         &self,
         id: JayScreenshotId,
-        include_cursor: bool,
+        f: impl FnOnce(&State) -> Result<Screenshot, ScreenshooterError>,
     ) -> Result<(), JayCompositorError> {
         let ss = Rc::new(JayScreenshot {
             id,
@@ -114,7 +121,7 @@ impl JayCompositor {
         });
         track!(self.client, ss);
         self.client.add_client_obj(&ss)?;
-        match take_screenshot(&self.client.state, include_cursor) {
+        match f(&self.client.state) {
             Ok(s) => {
                 let dmabuf = s.bo.dmabuf();
                 let plane = &dmabuf.planes[0];
@@ -184,11 +191,12 @@ impl JayCompositorRequestHandler for JayCompositor {
     }
 
     fn take_screenshot(&self, req: TakeScreenshot, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        self.take_screenshot_impl(req.id, false)
+        self.take_screenshot_impl(req.id, |state| take_screenshot(state, false))
     }
 
     fn take_screenshot2(&self, req: TakeScreenshot2, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        self.take_screenshot_impl(req.id, req.include_cursor != 0)
+        let include_cursor = req.include_cursor != 0;
+        self.take_screenshot_impl(req.id, |state| take_screenshot(state, include_cursor))
     }
 
     fn get_idle(&self, req: GetIdle, _slf: &Rc<Self>) -> Result<(), Self::Error> {
@@ -393,6 +401,53 @@ impl JayCompositorRequestHandler for JayCompositor {
         self.client.add_client_obj(&obj)?;
         Ok(())
     }
+
+    fn create_global_shortcuts(
+        &self,
+        req: CreateGlobalShortcuts,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayGlobalShortcuts {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            bindings: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.client
+            .state
+            .global_shortcuts
+            .set((self.client.id, req.id), obj);
+        Ok(())
+    }
+
+    fn take_output_screenshot(
+        &self,
+        req: TakeOutputScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let include_cursor = req.include_cursor != 0;
+        self.take_screenshot_impl(req.id, |state| match output.global.node() {
+            Some(node) => take_output_screenshot(state, &node, include_cursor),
+            _ => Err(ScreenshooterError::OutputRemoved),
+        })
+    }
+
+    fn take_toplevel_screenshot(
+        &self,
+        req: TakeToplevelScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let toplevel = self.client.lookup(req.toplevel)?;
+        let include_cursor = req.include_cursor != 0;
+        self.take_screenshot_impl(req.id, |state| match toplevel.destroyed.get() {
+            true => Err(ScreenshooterError::ToplevelDestroyed),
+            false => take_toplevel_screenshot(state, &*toplevel.toplevel, include_cursor),
+        })
+    }
 }
 
 object_base! {
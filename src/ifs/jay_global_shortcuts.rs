@@ -0,0 +1,86 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::copyhashmap::CopyHashMap,
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub bindings: CopyHashMap<u32, (Modifiers, KeySym)>,
+}
+
+impl JayGlobalShortcuts {
+    pub fn send_activated(&self, id: u32, time_usec: u64) {
+        self.client.event(Activated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    pub fn send_deactivated(&self, id: u32, time_usec: u64) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .global_shortcuts
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayGlobalShortcutsRequestHandler for JayGlobalShortcuts {
+    type Error = JayGlobalShortcutsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn bind(&self, req: Bind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.bindings
+            .set(req.id, (Modifiers(req.mods), KeySym(req.keysym)));
+        Ok(())
+    }
+
+    fn unbind(&self, req: Unbind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.bindings.remove(&req.id);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayGlobalShortcuts;
+    version = self.version;
+}
+
+impl Object for JayGlobalShortcuts {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayGlobalShortcuts);
+
+#[derive(Debug, Error)]
+pub enum JayGlobalShortcutsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayGlobalShortcutsError, ClientError);
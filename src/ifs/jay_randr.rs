@@ -1,14 +1,19 @@
 use {
     crate::{
-        backend,
+        backend::{
+            self, MAX_COLOR_TEMPERATURE, MAX_SDR_REFERENCE_WHITE, MIN_BRIGHTNESS,
+            MIN_COLOR_TEMPERATURE, MIN_SDR_REFERENCE_WHITE,
+        },
         client::{Client, ClientError},
         compositor::MAX_EXTENTS,
         leaks::Tracker,
         object::{Object, Version},
+        output_mirror::OutputMirror,
         scale::Scale,
         state::{ConnectorData, DrmDevData, OutputData},
         tree::{OutputNode, TearingMode, VrrMode},
         utils::{gfx_api_ext::GfxApiExt, transform_ext::TransformExt},
+        virtual_output::{validate_mode, MAX_SIZE},
         wire::{jay_randr::*, JayRandrId},
     },
     jay_config::video::{
@@ -27,6 +32,9 @@ pub struct JayRandr {
 
 const VRR_CAPABLE_SINCE: Version = Version(2);
 const TEARING_SINCE: Version = Version(3);
+const COLOR_TRANSFORM_SINCE: Version = Version(6);
+const HDR_SINCE: Version = Version(7);
+const MIRROR_SINCE: Version = Version(11);
 
 impl JayRandr {
     pub fn new(id: JayRandrId, client: &Rc<Client>, version: Version) -> Self {
@@ -78,6 +86,13 @@ impl JayRandr {
         let node = match &output.node {
             Some(n) => n,
             None => {
+                let mirror = self.client.state.mirrors.get(&data.connector.id());
+                if let Some(mirror) = mirror {
+                    if self.version >= MIRROR_SINCE {
+                        self.send_mirror_output(&output, &mirror);
+                        return;
+                    }
+                }
                 self.client.event(NonDesktopOutput {
                     self_id: self.id,
                     manufacturer: &output.monitor_info.manufacturer,
@@ -125,6 +140,22 @@ impl JayRandr {
                 mode: node.global.persistent.tearing_mode.get().to_config().0,
             });
         }
+        if self.version >= COLOR_TRANSFORM_SINCE {
+            self.client.event(ColorTransform {
+                self_id: self.id,
+                temperature: node.global.persistent.color_temperature.get(),
+                brightness: node.global.persistent.brightness.get(),
+            });
+        }
+        if self.version >= HDR_SINCE {
+            self.client.event(HdrState {
+                self_id: self.id,
+                capable: output.monitor_info.hdr_metadata.is_some() as _,
+                enabled: node.global.persistent.hdr_enabled.get() as _,
+                active: node.hdr_active.get() as _,
+                sdr_reference_white: node.global.persistent.sdr_reference_white.get(),
+            });
+        }
         let current_mode = global.mode.get();
         for mode in &global.modes {
             self.client.event(Mode {
@@ -137,6 +168,35 @@ impl JayRandr {
         }
     }
 
+    fn send_mirror_output(&self, output: &OutputData, mirror: &OutputMirror) {
+        let source = self
+            .client
+            .state
+            .connectors
+            .get(&mirror.source)
+            .map(|c| c.name.clone())
+            .unwrap_or_default();
+        self.client.event(MirrorOutput {
+            self_id: self.id,
+            source: &source,
+            manufacturer: &output.monitor_info.manufacturer,
+            product: &output.monitor_info.product,
+            serial_number: &output.monitor_info.serial_number,
+            width_mm: output.monitor_info.width_mm,
+            height_mm: output.monitor_info.height_mm,
+        });
+        let current_mode = mirror.mode();
+        for mode in &output.monitor_info.modes {
+            self.client.event(Mode {
+                self_id: self.id,
+                width: mode.width,
+                height: mode.height,
+                refresh_rate_millihz: mode.refresh_rate_millihz,
+                current: (mode == &current_mode) as _,
+            });
+        }
+    }
+
     fn send_error(&self, msg: &str) {
         self.client.event(Error {
             self_id: self.id,
@@ -310,6 +370,7 @@ impl JayRandrRequestHandler for JayRandr {
             return Ok(());
         };
         c.connector.set_enabled(req.enabled != 0);
+        self.client.state.update_output_managers();
         Ok(())
     }
 
@@ -335,6 +396,7 @@ impl JayRandrRequestHandler for JayRandr {
         };
         c.global.persistent.vrr_mode.set(mode);
         c.update_presentation_type();
+        self.client.state.update_output_managers();
         return Ok(());
     }
 
@@ -365,6 +427,137 @@ impl JayRandrRequestHandler for JayRandr {
         c.update_presentation_type();
         return Ok(());
     }
+
+    fn set_color_temperature(
+        &self,
+        req: SetColorTemperature<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if !(MIN_COLOR_TEMPERATURE..=MAX_COLOR_TEMPERATURE).contains(&req.kelvin) {
+            self.send_error(&format!(
+                "Color temperature must be between {MIN_COLOR_TEMPERATURE}K and {MAX_COLOR_TEMPERATURE}K"
+            ));
+            return Ok(());
+        }
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        c.set_color_temperature(req.kelvin);
+        Ok(())
+    }
+
+    fn set_brightness(&self, req: SetBrightness<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if !(MIN_BRIGHTNESS..=1.0).contains(&req.brightness) {
+            self.send_error(&format!(
+                "Brightness must be between {MIN_BRIGHTNESS} and 1"
+            ));
+            return Ok(());
+        }
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        c.set_brightness(req.brightness);
+        Ok(())
+    }
+
+    fn set_hdr_enabled(&self, req: SetHdrEnabled<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        c.set_hdr_enabled(req.enabled != 0);
+        Ok(())
+    }
+
+    fn set_sdr_reference_white(
+        &self,
+        req: SetSdrReferenceWhite<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if !(MIN_SDR_REFERENCE_WHITE..=MAX_SDR_REFERENCE_WHITE).contains(&req.nits) {
+            self.send_error(&format!(
+                "SDR reference white must be between {MIN_SDR_REFERENCE_WHITE} and {MAX_SDR_REFERENCE_WHITE} cd/m²"
+            ));
+            return Ok(());
+        }
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        c.set_sdr_reference_white(req.nits);
+        Ok(())
+    }
+
+    fn create_virtual_output(
+        &self,
+        req: CreateVirtualOutput,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let mode = backend::Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh_rate_millihz,
+        };
+        if !validate_mode(mode) {
+            self.send_error(&format!(
+                "Width and height must be between 1 and {MAX_SIZE} and the refresh \
+                 rate must be between 1 mHz and 1000 Hz",
+            ));
+            return Ok(());
+        }
+        let output = self.client.state.create_virtual_output(mode);
+        self.client.event(VirtualOutputCreated {
+            self_id: self.id,
+            name: &backend::Connector::kernel_id(&*output).to_string(),
+        });
+        Ok(())
+    }
+
+    fn remove_virtual_output(
+        &self,
+        req: RemoveVirtualOutput<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        if !self.client.state.remove_virtual_output(c.connector.id()) {
+            self.send_error(&format!("Connector {} is not a virtual output", c.name));
+        }
+        Ok(())
+    }
+
+    fn set_mirror(&self, req: SetMirror<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let Some(source) = self.get_connector(req.source) else {
+            return Ok(());
+        };
+        if c.connector.id() == source.connector.id() {
+            self.send_error(&format!("Connector {} cannot mirror itself", c.name));
+            return Ok(());
+        }
+        if !self
+            .client
+            .state
+            .can_mirror(c.connector.id(), source.connector.id())
+        {
+            self.send_error(&format!(
+                "Connector {} cannot mirror {} because mirrors cannot be chained",
+                c.name, source.name,
+            ));
+            return Ok(());
+        }
+        c.set_mirror(Some(source.connector.id()));
+        Ok(())
+    }
+
+    fn unset_mirror(&self, req: UnsetMirror<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        c.set_mirror(None);
+        Ok(())
+    }
 }
 
 object_base! {
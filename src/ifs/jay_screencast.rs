@@ -297,6 +297,7 @@ impl JayScreencast {
                     y_off,
                     size,
                     on.global.persistent.transform.get(),
+                    on.screencopy_color_transform(),
                 );
                 match res {
                     Ok(_) => {
@@ -94,6 +94,10 @@ pub struct PersistentOutputState {
     pub vrr_mode: Cell<&'static VrrMode>,
     pub vrr_cursor_hz: Cell<Option<f64>>,
     pub tearing_mode: Cell<&'static TearingMode>,
+    pub color_temperature: Cell<u32>,
+    pub brightness: Cell<f64>,
+    pub hdr_enabled: Cell<bool>,
+    pub sdr_reference_white: Cell<f64>,
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -220,6 +224,14 @@ impl WlOutputGlobal {
         if obj.version >= SEND_DONE_SINCE {
             obj.send_done();
         }
+        if let Some(node) = self.opt.node() {
+            for group in node.ext_workspace_groups.lock().values() {
+                if group.client.id == client.id {
+                    group.send_output_enter(id);
+                    group.manager.schedule_done();
+                }
+            }
+        }
         Ok(())
     }
 
@@ -10,6 +10,8 @@ mod touch_owner;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
+pub mod zwlr_virtual_pointer_manager_v1;
+pub mod zwlr_virtual_pointer_v1;
 pub mod zwp_pointer_constraints_v1;
 pub mod zwp_pointer_gesture_hold_v1;
 pub mod zwp_pointer_gesture_pinch_v1;
@@ -44,6 +46,7 @@ use {
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
                 DynDataSource, IpcError,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 gesture_owner::GestureOwnerHolder,
@@ -72,8 +75,8 @@ use {
         rect::Rect,
         state::{DeviceHandlerData, State},
         tree::{
-            generic_node_visitor, ContainerNode, ContainerSplit, Direction, FoundNode, Node,
-            OutputNode, ToplevelNode, WorkspaceNode,
+            generic_node_visitor, ContainerLayout, ContainerNode, ContainerSplit, Direction,
+            FoundNode, Node, OutputNode, ToplevelNode, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent, bindings::PerClientBindings, clonecell::CloneCell,
@@ -89,6 +92,7 @@ use {
         xkbcommon::{DynKeyboardState, KeyboardState, KeymapId, XkbKeymap, XkbState},
     },
     ahash::AHashMap,
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -106,7 +110,7 @@ pub use {
 };
 
 pub const POINTER: u32 = 1;
-const KEYBOARD: u32 = 2;
+pub const KEYBOARD: u32 = 2;
 const TOUCH: u32 = 4;
 
 #[allow(dead_code)]
@@ -181,6 +185,8 @@ pub struct WlSeatGlobal {
     touch_owner: TouchOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
     shortcuts: RefCell<AHashMap<u32, SmallMap<u32, u32, 2>>>,
+    shortcuts_inhibit_escape: Cell<Option<(Modifiers, KeySym)>>,
+    active_global_shortcuts: RefCell<AHashMap<u32, SmallVec<[(Rc<JayGlobalShortcuts>, u32); 1]>>>,
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
@@ -249,6 +255,8 @@ impl WlSeatGlobal {
             touch_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            shortcuts_inhibit_escape: Cell::new(None),
+            active_global_shortcuts: Default::default(),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
@@ -380,38 +388,7 @@ impl WlSeatGlobal {
             Some(tl) => tl,
             _ => return,
         };
-        if tl.tl_data().is_fullscreen.get() {
-            return;
-        }
-        let old_ws = match tl.tl_data().workspace.get() {
-            Some(ws) => ws,
-            _ => return,
-        };
-        if old_ws.id == ws.id {
-            return;
-        }
-        let cn = match tl.tl_data().parent.get() {
-            Some(cn) => cn,
-            _ => return,
-        };
-        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
-        cn.cnode_remove_child2(tl.tl_as_node(), true);
-        if !ws.visible.get() {
-            for focus in kb_foci {
-                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
-            }
-        }
-        if tl.tl_data().is_floating.get() {
-            self.state.map_floating(
-                tl.clone(),
-                tl.tl_data().float_width.get(),
-                tl.tl_data().float_height.get(),
-                ws,
-                None,
-            );
-        } else {
-            self.state.map_tiled_on(tl, ws);
-        }
+        self.state.set_tl_workspace(tl, ws);
     }
 
     pub fn mark_last_active(self: &Rc<Self>) {
@@ -434,6 +411,10 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn has_pointer_constraint(&self) -> bool {
+        self.constraint.is_some()
+    }
+
     fn maybe_constrain_pointer_node(&self) {
         if let Some(pn) = self.pointer_node() {
             if let Some(surface) = pn.node_into_surface() {
@@ -477,6 +458,10 @@ impl WlSeatGlobal {
         false
     }
 
+    pub fn keyboard_node(&self) -> Rc<dyn Node> {
+        self.keyboard_node.get()
+    }
+
     pub fn set_seat_keymap(&self, keymap: &Rc<XkbKeymap>) {
         let Some(xkb_state) = self.get_xkb_state(keymap) else {
             return;
@@ -550,6 +535,10 @@ impl WlSeatGlobal {
         self.kb_parent_container().map(|c| c.split.get())
     }
 
+    pub fn get_layout(&self) -> Option<ContainerLayout> {
+        self.kb_parent_container().map(|c| c.layout())
+    }
+
     pub fn set_mono(&self, mono: bool) {
         if let Some(tl) = self.keyboard_node.get().node_toplevel() {
             if let Some(parent) = tl.tl_data().parent.get() {
@@ -561,6 +550,16 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn set_layout(&self, layout: ContainerLayout) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            if let Some(parent) = tl.tl_data().parent.get() {
+                if let Some(container) = parent.node_into_container() {
+                    container.set_layout(layout, tl.deref());
+                }
+            }
+        }
+    }
+
     pub fn set_split(&self, axis: ContainerSplit) {
         if let Some(c) = self.kb_parent_container() {
             c.set_split(axis);
@@ -611,29 +610,7 @@ impl WlSeatGlobal {
             Some(tl) => tl,
             _ => return,
         };
-        self.set_tl_floating(tl, floating);
-    }
-
-    pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
-        let data = tl.tl_data();
-        if data.is_fullscreen.get() {
-            return;
-        }
-        if data.is_floating.get() == floating {
-            return;
-        }
-        let parent = match data.parent.get() {
-            Some(p) => p,
-            _ => return,
-        };
-        if !floating {
-            parent.cnode_remove_child2(tl.tl_as_node(), true);
-            self.state.map_tiled(tl);
-        } else if let Some(ws) = data.workspace.get() {
-            parent.cnode_remove_child2(tl.tl_as_node(), true);
-            let (width, height) = data.float_size(&ws);
-            self.state.map_floating(tl, width, height, &ws, None);
-        }
+        self.state.set_tl_floating(tl, floating);
     }
 
     pub fn get_rate(&self) -> (i32, i32) {
@@ -658,6 +635,26 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn move_to_scratchpad(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            self.state.scratchpad.hide(&self.state, tl);
+        }
+    }
+
+    pub fn show_scratchpad(self: &Rc<Self>) {
+        self.state.scratchpad.show(&self.state, self);
+    }
+
+    pub fn toggle_scratchpad(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            if tl.tl_data().is_scratchpad.get() {
+                self.state.scratchpad.hide(&self.state, tl);
+                return;
+            }
+        }
+        self.show_scratchpad();
+    }
+
     pub fn close(self: &Rc<Self>) {
         let kb_node = self.keyboard_node.get();
         if let Some(tl) = kb_node.node_toplevel() {
@@ -887,6 +884,7 @@ impl WlSeatGlobal {
         self.kb_owner.clear();
         self.touch_owner.clear();
         *self.dropped_dnd.borrow_mut() = None;
+        self.active_global_shortcuts.borrow_mut().clear();
         self.queue_link.take();
         self.tree_changed_handler.set(None);
         self.constraint.take();
@@ -803,6 +803,7 @@ impl WlSeatGlobal {
             }
         };
         let mut shortcuts = SmallVec::<[_; 1]>::new();
+        let mut global_shortcuts = SmallVec::<[_; 1]>::new();
         let new_mods;
         {
             let mut mods = xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0);
@@ -810,6 +811,8 @@ impl WlSeatGlobal {
                 mods |= RELEASE.0;
             }
             let scs = &*self.shortcuts.borrow();
+            let inhibited = self.shortcuts_inhibited();
+            let escape = self.shortcuts_inhibit_escape.get();
             let keysyms = xkb_state.unmodified_keysyms(key);
             for &sym in keysyms {
                 if sym == SYM_Escape.0 && mods == 0 {
@@ -818,6 +821,9 @@ impl WlSeatGlobal {
                 if !self.state.lock.locked.get() {
                     if let Some(key_mods) = scs.get(&sym) {
                         for (key_mods, mask) in key_mods {
+                            if inhibited && escape != Some((Modifiers(key_mods), KeySym(sym))) {
+                                continue;
+                            }
                             if mods & mask == key_mods {
                                 shortcuts.push(InvokedShortcut {
                                     unmasked_mods: Modifiers(mods),
@@ -829,6 +835,19 @@ impl WlSeatGlobal {
                     }
                 }
             }
+            if state == wl_keyboard::PRESSED
+                && shortcuts.is_empty()
+                && !inhibited
+                && !self.state.lock.locked.get()
+            {
+                for gs in self.state.global_shortcuts.lock().values() {
+                    for (&id, &(key_mods, sym)) in gs.bindings.lock().iter() {
+                        if key_mods.0 == mods && keysyms.contains(&sym.0) {
+                            global_shortcuts.push((gs.clone(), id));
+                        }
+                    }
+                }
+            }
             new_mods = xkb_state.update(key, xkb_dir);
         }
         self.state.for_each_seat_tester(|t| {
@@ -844,6 +863,9 @@ impl WlSeatGlobal {
                 drop(xkb_state);
                 for shortcut in shortcuts {
                     config.invoke_shortcut(self.id(), &shortcut);
+                    self.state
+                        .i3_ipc_clients
+                        .binding_event(shortcut.effective_mods, shortcut.sym);
                 }
                 xkb_state_rc = get_state();
                 xkb_state = xkb_state_rc.borrow_mut();
@@ -853,6 +875,28 @@ impl WlSeatGlobal {
             }
             forward = self.forward.get();
         }
+        match key_state {
+            KeyState::Pressed => {
+                if global_shortcuts.is_not_empty() {
+                    for (gs, id) in &global_shortcuts {
+                        gs.send_activated(*id, time_usec);
+                    }
+                    self.active_global_shortcuts
+                        .borrow_mut()
+                        .insert(key, global_shortcuts);
+                    forward = false;
+                }
+            }
+            KeyState::Released => {
+                let active = self.active_global_shortcuts.borrow_mut().remove(&key);
+                if let Some(active) = active {
+                    for (gs, id) in &active {
+                        gs.send_deactivated(*id, time_usec);
+                    }
+                    forward = false;
+                }
+            }
+        }
         if forward {
             match &input_method_grab {
                 Some(g) => g.on_key(time_usec, key, state, &xkb_state.kb_state),
@@ -1085,6 +1129,33 @@ impl WlSeatGlobal {
             .insert(mods.0, mod_mask.0);
     }
 
+    pub fn set_shortcuts_inhibit_escape(&self, escape: Option<(Modifiers, KeySym)>) {
+        self.shortcuts_inhibit_escape.set(escape);
+        if let Some(surface) = self.keyboard_node.get().node_into_surface() {
+            if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+                inhibitor.update();
+            }
+        }
+    }
+
+    /// Returns whether clients can inhibit the shortcuts of this seat.
+    ///
+    /// Without an escape shortcut, the user could not regain control over the
+    /// compositor while a client inhibits the shortcuts.
+    pub fn shortcuts_inhibit_possible(&self) -> bool {
+        self.shortcuts_inhibit_escape.get().is_some()
+    }
+
+    fn shortcuts_inhibited(&self) -> bool {
+        let Some(surface) = self.keyboard_node.get().node_into_surface() else {
+            return false;
+        };
+        match surface.shortcuts_inhibitors.get(&self.id) {
+            Some(inhibitor) => inhibitor.active.get(),
+            _ => false,
+        }
+    }
+
     pub fn remove_shortcut(&self, mods: Modifiers, keysym: KeySym) {
         if let Entry::Occupied(mut oe) = self.shortcuts.borrow_mut().entry(keysym.0) {
             oe.get_mut().remove(&mods.0);
@@ -1268,6 +1339,10 @@ impl WlSeatGlobal {
             }
         }
 
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.set_focused(false);
+        }
+
         let serial = surface.client.next_serial();
         self.surface_kb_event(Version::ALL, surface, |k| k.send_leave(serial, surface.id))
     }
@@ -1300,6 +1375,10 @@ impl WlSeatGlobal {
                 ti.send_done();
             }
         }
+
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.set_focused(true);
+        }
     }
 }
 
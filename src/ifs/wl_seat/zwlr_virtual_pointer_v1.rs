@@ -0,0 +1,157 @@
+use {
+    crate::{
+        backend::{self, KeyState, ScrollAxis, AXIS_120},
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{
+            wl_output::OutputGlobalOpt,
+            wl_seat::{
+                wl_pointer::{
+                    CONTINUOUS, FINGER, HORIZONTAL_SCROLL, PRESSED, RELEASED, VERTICAL_SCROLL,
+                    WHEEL,
+                },
+                WlSeatGlobal, PX_PER_SCROLL,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_virtual_pointer_v1::*, ZwlrVirtualPointerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrVirtualPointerV1 {
+    pub id: ZwlrVirtualPointerV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub output: Option<Rc<OutputGlobalOpt>>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub axis_pending: Cell<bool>,
+}
+
+impl ZwlrVirtualPointerV1 {
+    fn now(&self) -> u64 {
+        self.client.state.now_usec()
+    }
+
+    fn parse_axis(&self, axis: u32) -> Result<ScrollAxis, ZwlrVirtualPointerV1Error> {
+        let axis = match axis {
+            HORIZONTAL_SCROLL => ScrollAxis::Horizontal,
+            VERTICAL_SCROLL => ScrollAxis::Vertical,
+            _ => return Err(ZwlrVirtualPointerV1Error::InvalidAxis(axis)),
+        };
+        Ok(axis)
+    }
+}
+
+impl ZwlrVirtualPointerV1RequestHandler for ZwlrVirtualPointerV1 {
+    type Error = ZwlrVirtualPointerV1Error;
+
+    fn motion(&self, req: Motion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.seat
+            .motion_event(self.now(), req.dx, req.dy, req.dx, req.dy);
+        Ok(())
+    }
+
+    fn motion_absolute(&self, req: MotionAbsolute, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.x_extent == 0 || req.y_extent == 0 {
+            return Ok(());
+        }
+        let rect = match &self.output {
+            Some(output) => match output.node() {
+                Some(node) => node.global.pos.get(),
+                _ => return Ok(()),
+            },
+            _ => self.client.state.root.extents.get(),
+        };
+        let x = rect.x1() as f64 + req.x as f64 * rect.width() as f64 / req.x_extent as f64;
+        let y = rect.y1() as f64 + req.y as f64 * rect.height() as f64 / req.y_extent as f64;
+        self.seat
+            .motion_event_abs(self.now(), Fixed::from_f64(x), Fixed::from_f64(y));
+        Ok(())
+    }
+
+    fn button(&self, req: Button, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let state = match req.state {
+            RELEASED => KeyState::Released,
+            PRESSED => KeyState::Pressed,
+            _ => return Err(ZwlrVirtualPointerV1Error::InvalidButtonState(req.state)),
+        };
+        self.seat.button_event(self.now(), req.button, state);
+        Ok(())
+    }
+
+    fn axis(&self, req: Axis, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = self.parse_axis(req.axis)?;
+        self.seat.axis_px(req.value, axis, false);
+        self.axis_pending.set(true);
+        Ok(())
+    }
+
+    fn frame(&self, _req: Frame, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.axis_pending.take() {
+            self.seat.axis_frame(PX_PER_SCROLL, self.now());
+        }
+        Ok(())
+    }
+
+    fn axis_source(&self, req: AxisSource, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let source = match req.axis_source {
+            WHEEL => backend::AxisSource::Wheel,
+            FINGER => backend::AxisSource::Finger,
+            CONTINUOUS => backend::AxisSource::Continuous,
+            _ => {
+                return Err(ZwlrVirtualPointerV1Error::InvalidAxisSource(
+                    req.axis_source,
+                ))
+            }
+        };
+        self.seat.axis_source(source);
+        self.axis_pending.set(true);
+        Ok(())
+    }
+
+    fn axis_stop(&self, req: AxisStop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = self.parse_axis(req.axis)?;
+        self.seat.axis_stop(axis);
+        self.axis_pending.set(true);
+        Ok(())
+    }
+
+    fn axis_discrete(&self, req: AxisDiscrete, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = self.parse_axis(req.axis)?;
+        self.seat.axis_px(req.value, axis, false);
+        self.seat.axis_120(req.discrete * AXIS_120, axis, false);
+        self.axis_pending.set(true);
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrVirtualPointerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrVirtualPointerV1 {}
+
+simple_add_obj!(ZwlrVirtualPointerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrVirtualPointerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Unknown axis {0}")]
+    InvalidAxis(u32),
+    #[error("Unknown axis source {0}")]
+    InvalidAxisSource(u32),
+    #[error("Unknown button state {0}")]
+    InvalidButtonState(u32),
+}
+efrom!(ZwlrVirtualPointerV1Error, ClientError);
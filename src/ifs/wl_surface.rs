@@ -4,6 +4,10 @@ pub mod dnd_icon;
 pub mod ext_session_lock_surface_v1;
 pub mod wl_subsurface;
 pub mod wp_alpha_modifier_surface_v1;
+pub mod wp_color_management_surface_feedback_v1;
+pub mod wp_color_management_surface_v1;
+pub mod wp_commit_timer_v1;
+pub mod wp_fifo_v1;
 pub mod wp_fractional_scale_v1;
 pub mod wp_linux_drm_syncobj_surface_v1;
 pub mod wp_tearing_control_v1;
@@ -14,11 +18,14 @@ pub mod xwayland_shell_v1;
 pub mod zwlr_layer_surface_v1;
 pub mod zwp_idle_inhibitor_v1;
 pub mod zwp_input_popup_surface_v2;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
 
 use {
     crate::{
+        async_engine::SpawnedFuture,
         backend::KeyState,
         client::{Client, ClientError},
+        color_management::ColorDescription,
         cursor_user::{CursorUser, CursorUserId},
         drm_feedback::DrmFeedback,
         fixed::Fixed,
@@ -45,12 +52,18 @@ use {
                 dnd_icon::DndIcon,
                 wl_subsurface::{PendingSubsurfaceData, SubsurfaceId, WlSubsurface},
                 wp_alpha_modifier_surface_v1::WpAlphaModifierSurfaceV1,
+                wp_color_management_surface_feedback_v1::WpColorManagementSurfaceFeedbackV1,
+                wp_color_management_surface_v1::WpColorManagementSurfaceV1,
+                wp_commit_timer_v1::WpCommitTimerV1,
+                wp_fifo_v1::WpFifoV1,
                 wp_fractional_scale_v1::WpFractionalScaleV1,
                 wp_linux_drm_syncobj_surface_v1::WpLinuxDrmSyncobjSurfaceV1,
                 wp_tearing_control_v1::WpTearingControlV1,
                 wp_viewport::WpViewport,
                 x_surface::{xwindow::Xwindow, XSurface},
-                xdg_surface::{xdg_toplevel::XdgToplevel, PendingXdgSurfaceData, XdgSurfaceError},
+                xdg_surface::{
+                    xdg_toplevel::XdgToplevel, PendingXdgSurfaceData, XdgSurface, XdgSurfaceError,
+                },
                 zwlr_layer_surface_v1::{PendingLayerSurfaceData, ZwlrLayerSurfaceV1Error},
             },
             wp_content_type_v1::ContentType,
@@ -75,8 +88,8 @@ use {
             drm::sync_obj::{SyncObj, SyncObjPoint},
         },
         wire::{
-            wl_surface::*, WlOutputId, WlSurfaceId, ZwpIdleInhibitorV1Id,
-            ZwpLinuxDmabufFeedbackV1Id,
+            wl_surface::*, WlOutputId, WlSurfaceId, WpColorManagementSurfaceFeedbackV1Id,
+            ZwpIdleInhibitorV1Id, ZwpLinuxDmabufFeedbackV1Id,
         },
         xkbcommon::KeyboardState,
         xwayland::XWaylandEvent,
@@ -94,6 +107,7 @@ use {
     },
     thiserror::Error,
     zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1,
+    zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
 };
 
 #[allow(dead_code)]
@@ -143,6 +157,9 @@ impl SurfaceSendPreferredScaleVisitor {
         for sc in tl.tl_data().jay_screencasts.lock().values() {
             sc.schedule_realloc();
         }
+        for session in tl.tl_data().image_copy_sessions.lock().values() {
+            session.update_constraints();
+        }
     }
 }
 
@@ -181,6 +198,14 @@ impl NodeVisitorBase for SurfaceSendPreferredTransformVisitor {
     }
 }
 
+pub struct SurfaceSendPreferredColorDescriptionVisitor;
+impl NodeVisitorBase for SurfaceSendPreferredColorDescriptionVisitor {
+    fn visit_surface(&mut self, node: &Rc<WlSurface>) {
+        node.send_preferred_color_description();
+        node.node_visit_children(self);
+    }
+}
+
 struct SurfaceBufferExplicitRelease {
     sync_obj: Rc<SyncObj>,
     point: SyncObjPoint,
@@ -285,6 +310,7 @@ pub struct WlSurface {
     output: CloneCell<Rc<OutputNode>>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
+    pub shortcuts_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
     pub tearing: Cell<bool>,
@@ -297,7 +323,15 @@ pub struct WlSurface {
     commit_timeline: CommitTimeline,
     alpha_modifier: CloneCell<Option<Rc<WpAlphaModifierSurfaceV1>>>,
     alpha: Cell<Option<f32>>,
+    color_management_surface: CloneCell<Option<Rc<WpColorManagementSurfaceV1>>>,
+    color_feedback:
+        CopyHashMap<WpColorManagementSurfaceFeedbackV1Id, Rc<WpColorManagementSurfaceFeedbackV1>>,
+    pub color_description: CloneCell<Option<Rc<ColorDescription>>>,
     pub text_input_connections: SmallMap<SeatId, Rc<TextInputConnection>, 1>,
+    fifo: CloneCell<Option<Rc<WpFifoV1>>>,
+    fifo_barrier_set: Cell<bool>,
+    fifo_barrier_timeout: Cell<Option<SpawnedFuture<()>>>,
+    commit_timer: CloneCell<Option<Rc<WpCommitTimerV1>>>,
 }
 
 impl Debug for WlSurface {
@@ -374,6 +408,10 @@ trait SurfaceExt {
         None
     }
 
+    fn into_xdg_surface(self: Rc<Self>) -> Option<Rc<XdgSurface>> {
+        None
+    }
+
     fn consume_pending_child(
         &self,
         surface: &WlSurface,
@@ -418,7 +456,11 @@ struct PendingState {
     acquire_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
     release_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
     alpha_multiplier: Option<Option<f32>>,
+    color_description: Option<Option<Rc<ColorDescription>>>,
     explicit_sync: bool,
+    fifo_barrier: bool,
+    fifo_wait: bool,
+    commit_time: Option<u64>,
 }
 
 struct AttachedSubsurfaceState {
@@ -469,6 +511,8 @@ impl PendingState {
         opt!(tearing);
         opt!(content_type);
         opt!(alpha_multiplier);
+        opt!(color_description);
+        opt!(commit_time);
         {
             let (dx1, dy1) = self.offset;
             let (dx2, dy2) = mem::take(&mut next.offset);
@@ -476,6 +520,8 @@ impl PendingState {
         }
         self.frame_request.append(&mut next.frame_request);
         self.damage_full |= mem::take(&mut next.damage_full);
+        self.fifo_barrier |= mem::take(&mut next.fifo_barrier);
+        self.fifo_wait |= mem::take(&mut next.fifo_wait);
         if !self.damage_full {
             if self.buffer_damage.len() + next.buffer_damage.len() > MAX_DAMAGE {
                 self.damage_full();
@@ -598,6 +644,7 @@ impl WlSurface {
             output: CloneCell::new(client.state.dummy_output.get().unwrap()),
             fractional_scale: Default::default(),
             constraints: Default::default(),
+            shortcuts_inhibitors: Default::default(),
             xwayland_serial: Default::default(),
             tearing_control: Default::default(),
             tearing: Cell::new(false),
@@ -610,7 +657,14 @@ impl WlSurface {
             commit_timeline: client.commit_timelines.create_timeline(),
             alpha_modifier: Default::default(),
             alpha: Default::default(),
+            color_management_surface: Default::default(),
+            color_feedback: Default::default(),
+            color_description: Default::default(),
             text_input_connections: Default::default(),
+            fifo: Default::default(),
+            fifo_barrier_set: Default::default(),
+            fifo_barrier_timeout: Default::default(),
+            commit_timer: Default::default(),
         }
     }
 
@@ -649,6 +703,9 @@ impl WlSurface {
         if old.global.persistent.transform.get() != output.global.persistent.transform.get() {
             self.send_preferred_buffer_transform();
         }
+        if old.color_description.get().id != output.color_description.get().id {
+            self.send_preferred_color_description();
+        }
         let children = self.children.borrow_mut();
         if let Some(children) = &*children {
             for ss in children.subsurfaces.values() {
@@ -668,6 +725,10 @@ impl WlSurface {
         self.toplevel.get()
     }
 
+    pub fn xdg_toplevel(&self) -> Option<Rc<XdgToplevel>> {
+        self.ext.get().into_xdg_surface()?.toplevel()
+    }
+
     pub fn xwayland_serial(&self) -> Option<u64> {
         self.xwayland_serial.get()
     }
@@ -759,6 +820,13 @@ impl WlSurface {
         }
     }
 
+    pub fn send_preferred_color_description(&self) {
+        let cd = self.output.get().color_description.get();
+        for feedback in self.color_feedback.lock().values() {
+            feedback.send_preferred_changed(&cd);
+        }
+    }
+
     fn set_toplevel(&self, tl: Option<Rc<dyn ToplevelNode>>) {
         let ch = self.children.borrow();
         if let Some(ch) = &*ch {
@@ -887,11 +955,15 @@ impl WlSurface {
 
 const MAX_DAMAGE: usize = 32;
 
+/// The time after which a fifo barrier is cleared if the surface has not been presented.
+const FIFO_BARRIER_TIMEOUT_MS: u64 = 100;
+
 impl WlSurfaceRequestHandler for WlSurface {
     type Error = WlSurfaceError;
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.commit_timeline.clear(ClearReason::Destroy);
+        self.clear_fifo_barrier();
         self.unset_dnd_icons();
         self.unset_cursors();
         self.ext.get().on_surface_destroy()?;
@@ -917,6 +989,7 @@ impl WlSurfaceRequestHandler for WlSurface {
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
         self.constraints.take();
+        self.shortcuts_inhibitors.take();
         self.destroyed.set(true);
         Ok(())
     }
@@ -1061,10 +1134,18 @@ impl WlSurface {
             alpha_changed = true;
             self.alpha.set(alpha);
         }
+        let mut color_description_changed = false;
+        if let Some(cd) = pending.color_description.take() {
+            let old = self.color_description.set(cd.clone());
+            color_description_changed = old.map(|c| c.id) != cd.map(|c| c.id);
+        }
         let buffer_abs_pos = self.buffer_abs_pos.get();
         let mut max_surface_size = buffer_abs_pos.size();
-        let mut damage_full =
-            scale_changed || buffer_transform_changed || viewport_changed || alpha_changed;
+        let mut damage_full = scale_changed
+            || buffer_transform_changed
+            || viewport_changed
+            || alpha_changed
+            || color_description_changed;
         let mut buffer_changed = false;
         let mut old_raw_size = None;
         let (dx, dy) = mem::take(&mut pending.offset);
@@ -1315,6 +1396,9 @@ impl WlSurface {
                 }
             }
         }
+        if mem::take(&mut pending.fifo_barrier) {
+            self.set_fifo_barrier();
+        }
         Ok(())
     }
 
@@ -1478,6 +1562,36 @@ impl WlSurface {
         }
     }
 
+    fn set_fifo_barrier(self: &Rc<Self>) {
+        self.fifo_barrier_set.set(true);
+        let output = self.output.get();
+        if self.visible.get() && !output.is_dummy {
+            output.fifo_barriers.set(self.id, self.clone());
+            output.schedule.request_vblank();
+        }
+        // Clear the barrier eventually even if the surface is not being presented.
+        let slf = self.clone();
+        let timeout = self.client.state.eng.spawn(async move {
+            let _ = slf
+                .client
+                .state
+                .wheel
+                .timeout(FIFO_BARRIER_TIMEOUT_MS)
+                .await;
+            slf.clear_fifo_barrier();
+        });
+        self.fifo_barrier_timeout.set(Some(timeout));
+    }
+
+    pub fn clear_fifo_barrier(&self) {
+        self.fifo_barrier_timeout.take();
+        if self.fifo_barrier_set.replace(false) {
+            if let Err(e) = self.commit_timeline.fifo_barrier_cleared() {
+                self.client.error(e);
+            }
+        }
+    }
+
     pub fn detach_node(&self, set_invisible: bool) {
         for (_, constraint) in &self.constraints {
             constraint.deactivate();
@@ -1571,9 +1685,15 @@ impl Object for WlSurface {
         self.fractional_scale.take();
         self.tearing_control.take();
         self.constraints.clear();
+        self.shortcuts_inhibitors.clear();
         self.drm_feedback.clear();
         self.commit_timeline.clear(ClearReason::BreakLoops);
         self.alpha_modifier.take();
+        self.color_management_surface.take();
+        self.color_feedback.clear();
+        self.fifo.take();
+        self.fifo_barrier_timeout.take();
+        self.commit_timer.take();
     }
 }
 
@@ -1,9 +1,11 @@
 use {
     crate::{
+        async_engine::SpawnedFuture,
         ifs::wl_surface::{PendingState, WlSurface, WlSurfaceError},
         utils::{
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
             hash_map_ext::HashMapExt,
             linkedlist::{LinkedList, LinkedNode, NodeRef},
             numcell::NumCell,
@@ -14,7 +16,6 @@ use {
             DrmError,
         },
     },
-    isnt::std_1::primitive::IsntSliceExt,
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -41,6 +42,7 @@ pub struct CommitTimeline {
     own_timeline: Rc<Inner>,
     effective_timeline: CloneCell<Rc<Inner>>,
     effective_timeline_id: Cell<CommitTimelineId>,
+    fifo_waiters: RefCell<Vec<NodeRef<Entry>>>,
 }
 
 struct Inner {
@@ -99,6 +101,7 @@ impl CommitTimelines {
             own_timeline: timeline.clone(),
             effective_timeline: CloneCell::new(timeline),
             effective_timeline_id: Cell::new(id),
+            fifo_waiters: Default::default(),
         }
     }
 
@@ -119,6 +122,7 @@ fn break_loops(list: &LinkedList<Entry>) {
         entry.link.take();
         if let EntryKind::Commit(c) = &entry.kind {
             c.wait_handles.take();
+            c.timer.take();
         }
     }
 }
@@ -126,7 +130,10 @@ fn break_loops(list: &LinkedList<Entry>) {
 impl CommitTimeline {
     pub fn clear(&self, reason: ClearReason) {
         match reason {
-            ClearReason::BreakLoops => break_loops(&self.own_timeline.entries),
+            ClearReason::BreakLoops => {
+                break_loops(&self.own_timeline.entries);
+                self.fifo_waiters.take();
+            }
             ClearReason::Destroy => {
                 if self.own_timeline.entries.is_not_empty() {
                     let list = LinkedList::new();
@@ -145,7 +152,12 @@ impl CommitTimeline {
     ) -> Result<(), CommitTimelineError> {
         let mut points = SmallVec::new();
         consume_acquire_points(pending, &mut points);
-        if points.is_empty() && self.own_timeline.entries.is_empty() {
+        let mut fifo_surfaces = SmallVec::new();
+        let mut commit_time = None;
+        consume_presentation_constraints(surface, pending, &mut fifo_surfaces, &mut commit_time);
+        let blocked = fifo_surfaces.iter().any(|s| s.fifo_barrier_set.get())
+            || commit_time.is_some_and(|t| !latch_time_reached(surface, t));
+        if points.is_empty() && self.own_timeline.entries.is_empty() && !blocked {
             return surface
                 .apply_state(pending)
                 .map_err(CommitTimelineError::ImmediateCommit);
@@ -162,23 +174,35 @@ impl CommitTimeline {
                 pending: RefCell::new(mem::take(pending)),
                 sync_obj: NumCell::new(points.len()),
                 wait_handles: Cell::new(Default::default()),
+                fifo_surfaces,
+                commit_time: Cell::new(commit_time),
+                timer: Default::default(),
             }),
         );
-        if points.is_not_empty() {
-            let mut wait_handles = SmallVec::new();
-            let noderef = Rc::new(noderef);
-            for (sync_obj, point) in points {
-                let handle = self
-                    .shared
-                    .wfs
-                    .wait(&sync_obj, point, true, noderef.clone())
-                    .map_err(CommitTimelineError::RegisterWait)?;
-                wait_handles.push(handle);
-            }
-            let EntryKind::Commit(commit) = &noderef.kind else {
-                unreachable!();
-            };
-            commit.wait_handles.set(wait_handles);
+        if points.is_empty() {
+            return flush_from(noderef).map_err(CommitTimelineError::ImmediateCommit);
+        }
+        let mut wait_handles = SmallVec::new();
+        let noderef = Rc::new(noderef);
+        for (sync_obj, point) in points {
+            let handle = self
+                .shared
+                .wfs
+                .wait(&sync_obj, point, true, noderef.clone())
+                .map_err(CommitTimelineError::RegisterWait)?;
+            wait_handles.push(handle);
+        }
+        let EntryKind::Commit(commit) = &noderef.kind else {
+            unreachable!();
+        };
+        commit.wait_handles.set(wait_handles);
+        Ok(())
+    }
+
+    pub(super) fn fifo_barrier_cleared(&self) -> Result<(), CommitTimelineError> {
+        let waiters = mem::take(&mut *self.fifo_waiters.borrow_mut());
+        for waiter in waiters {
+            flush_from(waiter).map_err(CommitTimelineError::DelayedCommit)?;
         }
         Ok(())
     }
@@ -221,6 +245,60 @@ struct Commit {
     pending: RefCell<Box<PendingState>>,
     sync_obj: NumCell<usize>,
     wait_handles: Cell<SmallVec<[WaitForSyncObjHandle; 1]>>,
+    fifo_surfaces: SmallVec<[Rc<WlSurface>; 1]>,
+    commit_time: Cell<Option<u64>>,
+    timer: RefCell<Option<SpawnedFuture<()>>>,
+}
+
+impl Commit {
+    fn fifo_ready(&self, entry: &NodeRef<Entry>) -> bool {
+        for surface in &self.fifo_surfaces {
+            if surface.fifo_barrier_set.get() {
+                surface
+                    .commit_timeline
+                    .fifo_waiters
+                    .borrow_mut()
+                    .push(entry.clone());
+                return false;
+            }
+        }
+        true
+    }
+
+    fn time_ready(&self, entry: &NodeRef<Entry>) -> bool {
+        let Some(time) = self.commit_time.get() else {
+            return true;
+        };
+        if latch_time_reached(&self.surface, time) {
+            self.commit_time.take();
+            return true;
+        }
+        if self.timer.borrow().is_some() {
+            return false;
+        }
+        let state = &self.surface.client.state;
+        let ring = state.ring.clone();
+        let latch_time = latch_time(&self.surface, time);
+        let entry = entry.clone();
+        let timer = state.eng.spawn(async move {
+            if let Err(e) = ring.timeout(latch_time).await {
+                log::error!("Could not wait for the commit time: {}", ErrorFmt(e));
+            }
+            let EntryKind::Commit(commit) = &entry.kind else {
+                unreachable!();
+            };
+            commit.timer.take();
+            commit.commit_time.take();
+            if let Err(e) = flush_from(entry.clone()) {
+                commit
+                    .surface
+                    .client
+                    .error(CommitTimelineError::DelayedCommit(e));
+            }
+        });
+        *self.timer.borrow_mut() = Some(timer);
+        false
+    }
 }
 
 fn flush_from(mut point: NodeRef<Entry>) -> Result<(), WlSurfaceError> {
@@ -243,7 +321,7 @@ impl NodeRef<Entry> {
         }
         match &self.kind {
             EntryKind::Commit(c) => {
-                if c.sync_obj.get() > 0 {
+                if c.sync_obj.get() > 0 || !c.fifo_ready(self) || !c.time_ready(self) {
                     return Ok(false);
                 }
                 c.surface.apply_state(c.pending.borrow_mut().deref_mut())?;
@@ -279,6 +357,42 @@ fn consume_acquire_points(pending: &mut PendingState, points: &mut SmallVec<[Poi
     }
 }
 
+fn consume_presentation_constraints(
+    surface: &Rc<WlSurface>,
+    pending: &mut PendingState,
+    fifo_surfaces: &mut SmallVec<[Rc<WlSurface>; 1]>,
+    commit_time: &mut Option<u64>,
+) {
+    if mem::take(&mut pending.fifo_wait) {
+        fifo_surfaces.push(surface.clone());
+    }
+    if let Some(time) = pending.commit_time.take() {
+        *commit_time = Some(commit_time.unwrap_or(0).max(time));
+    }
+    for ss in pending.subsurfaces.values_mut() {
+        if let Some(state) = &mut ss.pending.state {
+            consume_presentation_constraints(
+                &ss.subsurface.surface,
+                state,
+                fifo_surfaces,
+                commit_time,
+            );
+        }
+    }
+}
+
+fn latch_time(surface: &WlSurface, commit_time: u64) -> u64 {
+    let output = surface.output.get();
+    let refresh_rate_millihz = output.global.mode.get().refresh_rate_millihz;
+    output
+        .schedule
+        .latch_time(commit_time, refresh_rate_millihz)
+}
+
+fn latch_time_reached(surface: &WlSurface, commit_time: u64) -> bool {
+    surface.client.state.now_nsec() >= latch_time(surface, commit_time)
+}
+
 fn set_effective_timeline(
     timeline: &CommitTimeline,
     pending: &PendingState,
@@ -0,0 +1,97 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        color_management::ColorDescription,
+        ifs::{wl_surface::WlSurface, wp_image_description_v1::WpImageDescriptionV1},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{
+            wp_color_management_surface_feedback_v1::*, WpColorManagementSurfaceFeedbackV1Id,
+            WpImageDescriptionV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpColorManagementSurfaceFeedbackV1 {
+    pub id: WpColorManagementSurfaceFeedbackV1Id,
+    pub version: Version,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpColorManagementSurfaceFeedbackV1 {
+    pub fn new(
+        id: WpColorManagementSurfaceFeedbackV1Id,
+        surface: &Rc<WlSurface>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            version,
+            client: surface.client.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) {
+        self.surface.color_feedback.set(self.id, self.clone());
+    }
+
+    pub fn send_preferred_changed(&self, cd: &ColorDescription) {
+        self.client.event(PreferredChanged {
+            self_id: self.id,
+            identity: cd.id.raw(),
+        });
+    }
+
+    fn create_preferred(&self, id: WpImageDescriptionV1Id) -> Result<(), ClientError> {
+        let cd = self.surface.output.get().color_description.get();
+        WpImageDescriptionV1::create(&self.client, id, self.version, Ok(cd), true)?;
+        Ok(())
+    }
+}
+
+impl WpColorManagementSurfaceFeedbackV1RequestHandler for WpColorManagementSurfaceFeedbackV1 {
+    type Error = WpColorManagementSurfaceFeedbackV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.surface.color_feedback.remove(&self.id);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_preferred(&self, req: GetPreferred, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.create_preferred(req.image_description)?;
+        Ok(())
+    }
+
+    fn get_preferred_parametric(
+        &self,
+        req: GetPreferredParametric,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        // Output descriptions are always parametric.
+        self.create_preferred(req.image_description)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpColorManagementSurfaceFeedbackV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagementSurfaceFeedbackV1 {}
+
+simple_add_obj!(WpColorManagementSurfaceFeedbackV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagementSurfaceFeedbackV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(WpColorManagementSurfaceFeedbackV1Error, ClientError);
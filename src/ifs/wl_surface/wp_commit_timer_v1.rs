@@ -0,0 +1,92 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_surface::WlSurface,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_commit_timer_v1::*, WlSurfaceId, WpCommitTimerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpCommitTimerV1 {
+    pub id: WpCommitTimerV1Id,
+    pub version: Version,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpCommitTimerV1 {
+    pub fn new(id: WpCommitTimerV1Id, surface: &Rc<WlSurface>, version: Version) -> Self {
+        Self {
+            id,
+            version,
+            client: surface.client.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) -> Result<(), WpCommitTimerV1Error> {
+        if self.surface.commit_timer.is_some() {
+            return Err(WpCommitTimerV1Error::Exists(self.surface.id));
+        }
+        self.surface.commit_timer.set(Some(self.clone()));
+        Ok(())
+    }
+}
+
+impl WpCommitTimerV1RequestHandler for WpCommitTimerV1 {
+    type Error = WpCommitTimerV1Error;
+
+    fn set_timestamp(&self, req: SetTimestamp, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.surface.destroyed.get() {
+            return Err(WpCommitTimerV1Error::SurfaceDestroyed);
+        }
+        if req.tv_nsec >= 1_000_000_000 {
+            return Err(WpCommitTimerV1Error::InvalidTimestamp);
+        }
+        let sec = ((req.tv_sec_hi as u64) << 32) | req.tv_sec_lo as u64;
+        let nsec = sec
+            .saturating_mul(1_000_000_000)
+            .saturating_add(req.tv_nsec as u64);
+        let pending = &mut *self.surface.pending.borrow_mut();
+        if pending.commit_time.is_some() {
+            return Err(WpCommitTimerV1Error::TimestampExists);
+        }
+        pending.commit_time = Some(nsec);
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.surface.commit_timer.take();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpCommitTimerV1;
+    version = self.version;
+}
+
+impl Object for WpCommitTimerV1 {}
+
+simple_add_obj!(WpCommitTimerV1);
+
+#[derive(Debug, Error)]
+pub enum WpCommitTimerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Surface {0} already has a wp_commit_timer_v1")]
+    Exists(WlSurfaceId),
+    #[error("The timestamp is invalid")]
+    InvalidTimestamp,
+    #[error("The surface already has a pending timestamp")]
+    TimestampExists,
+    #[error("The surface has been destroyed")]
+    SurfaceDestroyed,
+}
+efrom!(WpCommitTimerV1Error, ClientError);
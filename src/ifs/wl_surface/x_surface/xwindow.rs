@@ -15,6 +15,7 @@ use {
             StackedNode, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
         utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, linkedlist::LinkedNode},
+        window_rules::WindowProperties,
         wire::WlSurfaceId,
         wire_xcon::CreateNotify,
         xwayland::XWaylandEvent,
@@ -110,6 +111,7 @@ pub struct XwindowInfo {
     pub input_model: Cell<XInputModel>,
     pub mapped: Cell<bool>,
     pub wants_floating: Cell<bool>,
+    pub is_dialog: Cell<bool>,
 }
 
 pub struct XwindowData {
@@ -250,6 +252,43 @@ impl Xwindow {
         }
     }
 
+    fn map_with_rules(self: &Rc<Self>) -> bool {
+        let rules = self
+            .data
+            .state
+            .window_rules
+            .evaluate(self.deref(), || self.window_properties());
+        let Some(rules) = rules else {
+            return false;
+        };
+        let ext = self.data.info.pending_extents.get();
+        rules.map(
+            &self.data.state,
+            self.clone(),
+            self.data.info.wants_floating.get(),
+            None,
+            Some((ext.width(), ext.height())),
+            None,
+        );
+        true
+    }
+
+    pub fn window_properties(&self) -> WindowProperties {
+        let info = &self.data.info;
+        let string = |s: &RefCell<Option<BString>>| s.borrow().as_ref().map(|s| s.to_string());
+        WindowProperties {
+            app_id: self.toplevel_data.app_id.borrow().clone(),
+            title: self.toplevel_data.title.borrow().clone(),
+            class: string(&info.class),
+            instance: string(&info.instance),
+            role: string(&info.role),
+            pid: info.pid.get().map(|pid| pid as _),
+            floating: info.wants_floating.get(),
+            dialog: info.is_dialog.get(),
+            ..Default::default()
+        }
+    }
+
     pub fn map_status_changed(self: &Rc<Self>) {
         let map_change = self.map_change();
         let override_redirect = self.data.info.override_redirect.get();
@@ -269,6 +308,9 @@ impl Xwindow {
                     Some(self.data.state.root.stacked.add_last(self.clone()));
                 self.data.state.tree_changed();
             }
+            Change::Map if self.map_with_rules() => {
+                self.data.title_changed();
+            }
             Change::Map if self.data.info.wants_floating.get() => {
                 let ws = self.data.state.float_map_ws();
                 let ext = self.data.info.pending_extents.get();
@@ -289,6 +331,9 @@ impl Xwindow {
                     self.tl_set_visible(true);
                 }
                 self.toplevel_data.broadcast(self.clone());
+                if !override_redirect {
+                    self.toplevel_data.register(self.clone());
+                }
             }
             Change::None => {}
         }
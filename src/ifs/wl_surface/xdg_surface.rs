@@ -171,6 +171,10 @@ pub trait XdgSurfaceExt: Debug {
     fn geometry_changed(&self) {
         // nothing
     }
+
+    fn into_xdg_toplevel(self: Rc<Self>) -> Option<Rc<XdgToplevel>> {
+        None
+    }
 }
 
 impl XdgSurface {
@@ -268,6 +272,10 @@ impl XdgSurface {
         self.surface.client.state.damage(extents.move_(x, y));
     }
 
+    pub fn toplevel(&self) -> Option<Rc<XdgToplevel>> {
+        self.ext.get()?.into_xdg_toplevel()
+    }
+
     pub fn geometry(&self) -> Option<Rect> {
         self.geometry.get()
     }
@@ -525,6 +533,10 @@ impl SurfaceExt for XdgSurface {
     fn extents_changed(&self) {
         self.update_extents();
     }
+
+    fn into_xdg_surface(self: Rc<Self>) -> Option<Rc<XdgSurface>> {
+        Some(self)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -30,6 +30,7 @@ use {
             WorkspaceNode,
         },
         utils::{clonecell::CloneCell, hash_map_ext::HashMapExt},
+        window_rules::WindowProperties,
         wire::{xdg_toplevel::*, XdgToplevelId},
     },
     ahash::{AHashMap, AHashSet},
@@ -38,6 +39,7 @@ use {
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
         mem,
+        ops::Deref,
         rc::Rc,
     },
     thiserror::Error,
@@ -242,13 +244,16 @@ impl XdgToplevelRequestHandler for XdgToplevel {
         if req.parent.is_some() {
             parent = Some(self.xdg.surface.client.lookup(req.parent)?);
         }
-        self.parent.set(parent);
+        self.set_parent_toplevel(parent);
         Ok(())
     }
 
-    fn set_title(&self, req: SetTitle, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_title(&self, req: SetTitle, slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.toplevel_data.set_title(req.title);
         self.tl_title_changed();
+        self.state
+            .window_rules
+            .title_changed(&self.state, slf.clone(), || self.window_properties());
         Ok(())
     }
 
@@ -350,6 +355,13 @@ impl XdgToplevelRequestHandler for XdgToplevel {
 }
 
 impl XdgToplevel {
+    /// Sets the parent of this toplevel.
+    ///
+    /// The parent can belong to a different client if it was imported via xdg-foreign.
+    pub fn set_parent_toplevel(&self, parent: Option<Rc<XdgToplevel>>) {
+        self.parent.set(parent);
+    }
+
     fn map_floating(self: &Rc<Self>, workspace: &Rc<WorkspaceNode>, abs_pos: Option<(i32, i32)>) {
         let (width, height) = self.toplevel_data.float_size(workspace);
         self.state
@@ -372,6 +384,38 @@ impl XdgToplevel {
         self.state.map_tiled(self.clone());
     }
 
+    fn map_with_rules(self: &Rc<Self>) -> bool {
+        let rules = self
+            .state
+            .window_rules
+            .evaluate(self.deref(), || self.window_properties());
+        let Some(rules) = rules else {
+            return false;
+        };
+        let workspace = self.parent.get().and_then(|p| p.xdg.workspace.get());
+        rules.map(
+            &self.state,
+            self.clone(),
+            workspace.is_some(),
+            workspace,
+            None,
+            None,
+        );
+        true
+    }
+
+    fn window_properties(&self) -> WindowProperties {
+        let is_child = self.parent.is_some();
+        WindowProperties {
+            app_id: self.toplevel_data.app_id.borrow().clone(),
+            title: self.toplevel_data.title.borrow().clone(),
+            pid: Some(self.xdg.surface.client.pid_info.pid),
+            floating: is_child,
+            dialog: is_child || self.dialog.is_some(),
+            ..Default::default()
+        }
+    }
+
     pub fn prepare_toplevel_drag(&self) {
         if self.toplevel_data.parent.get().is_none() {
             return;
@@ -433,10 +477,12 @@ impl XdgToplevel {
             }
             self.state.tree_changed();
         } else {
-            if let Some(parent) = self.parent.get() {
-                self.map_child(&parent, pos);
-            } else {
-                self.map_tiled();
+            if pos.is_some() || !self.map_with_rules() {
+                if let Some(parent) = self.parent.get() {
+                    self.map_child(&parent, pos);
+                } else {
+                    self.map_tiled();
+                }
             }
             self.extents_changed();
             if let Some(workspace) = self.xdg.workspace.get() {
@@ -451,6 +497,7 @@ impl XdgToplevel {
             // }
             self.state.tree_changed();
             self.toplevel_data.broadcast(self.clone());
+            self.toplevel_data.register(self.clone());
         }
     }
 }
@@ -687,6 +734,10 @@ impl XdgSurfaceExt for XdgToplevel {
             .state
             .damage(self.node_absolute_position());
     }
+
+    fn into_xdg_toplevel(self: Rc<Self>) -> Option<Rc<XdgToplevel>> {
+        Some(self)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -41,10 +41,10 @@ const KI_NONE: u32 = 0;
 const KI_EXCLUSIVE: u32 = 1;
 const KI_ON_DEMAND: u32 = 2;
 
-const TOP: u32 = 1;
-const BOTTOM: u32 = 2;
-const LEFT: u32 = 4;
-const RIGHT: u32 = 8;
+pub const TOP: u32 = 1;
+pub const BOTTOM: u32 = 2;
+pub const LEFT: u32 = 4;
+pub const RIGHT: u32 = 8;
 
 tree_id!(ZwlrLayerSurfaceV1NodeId);
 pub struct ZwlrLayerSurfaceV1 {
@@ -585,6 +585,10 @@ impl SurfaceExt for ZwlrLayerSurfaceV1 {
             self.mapped.set(true);
             self.compute_position();
             self.update_exclusive_size();
+            let state = &self.client.state;
+            state
+                .animations
+                .window_opened(self.node_id.into(), output.id, state.now_nsec());
         }
         if self.mapped.get() != was_mapped {
             output.update_visible();
@@ -0,0 +1,89 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::Node,
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub active: Cell<bool>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    pub fn install(self: &Rc<Self>) {
+        self.surface
+            .shortcuts_inhibitors
+            .insert(self.seat.id(), self.clone());
+        self.update();
+    }
+
+    /// Activates or deactivates the inhibitor after the keyboard focus of the seat changed.
+    pub fn set_focused(&self, focused: bool) {
+        let active = focused
+            && self.client.shortcuts_inhibit_allowed.get()
+            && self.seat.shortcuts_inhibit_possible();
+        if self.active.replace(active) == active {
+            return;
+        }
+        match active {
+            true => self.client.event(Active { self_id: self.id }),
+            false => self.client.event(Inactive { self_id: self.id }),
+        }
+    }
+
+    pub fn update(&self) {
+        let focused = self.seat.keyboard_node().node_id() == self.surface.node_id();
+        self.set_focused(focused);
+    }
+
+    fn detach(&self) {
+        self.surface.shortcuts_inhibitors.remove(&self.seat.id());
+        self.active.set(false);
+    }
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1RequestHandler for ZwpKeyboardShortcutsInhibitorV1 {
+    type Error = ZwpKeyboardShortcutsInhibitorV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitorV1;
+    version = self.version;
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+dedicated_add_obj!(
+    ZwpKeyboardShortcutsInhibitorV1,
+    ZwpKeyboardShortcutsInhibitorV1Id,
+    shortcuts_inhibitors
+);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
@@ -0,0 +1,90 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            wl_output::OutputGlobalOpt,
+            wp_image_description_v1::{
+                ImageDescriptionFailure, WpImageDescriptionV1, CAUSE_NO_OUTPUT,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_color_management_output_v1::*, WpColorManagementOutputV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpColorManagementOutputV1 {
+    pub id: WpColorManagementOutputV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub output: Rc<OutputGlobalOpt>,
+}
+
+impl WpColorManagementOutputV1 {
+    fn detach(&self) {
+        if let Some(node) = self.output.node() {
+            node.color_management_outputs
+                .remove(&(self.client.id, self.id));
+        }
+    }
+
+    pub fn send_image_description_changed(&self) {
+        self.client
+            .event(ImageDescriptionChanged { self_id: self.id });
+    }
+}
+
+impl WpColorManagementOutputV1RequestHandler for WpColorManagementOutputV1 {
+    type Error = WpColorManagementOutputV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_image_description(
+        &self,
+        req: GetImageDescription,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let description = match self.output.node() {
+            Some(node) => Ok(node.color_description.get()),
+            None => Err(ImageDescriptionFailure {
+                cause: CAUSE_NO_OUTPUT,
+                msg: "The output has been disconnected".to_string(),
+            }),
+        };
+        WpImageDescriptionV1::create(
+            &self.client,
+            req.image_description,
+            self.version,
+            description,
+            true,
+        )?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpColorManagementOutputV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagementOutputV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(WpColorManagementOutputV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagementOutputV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(WpColorManagementOutputV1Error, ClientError);
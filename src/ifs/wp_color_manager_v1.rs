@@ -0,0 +1,355 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        color_management::{ColorDescriptionData, Luminance, NamedPrimaries, TransferFunction},
+        globals::{Global, GlobalName},
+        ifs::{
+            wl_surface::{
+                wp_color_management_surface_feedback_v1::WpColorManagementSurfaceFeedbackV1,
+                wp_color_management_surface_v1::{
+                    WpColorManagementSurfaceV1, WpColorManagementSurfaceV1Error,
+                },
+            },
+            wp_color_management_output_v1::WpColorManagementOutputV1,
+            wp_image_description_creator_icc_v1::WpImageDescriptionCreatorIccV1,
+            wp_image_description_creator_params_v1::WpImageDescriptionCreatorParamsV1,
+            wp_image_description_v1::WpImageDescriptionV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_color_manager_v1::*, WpColorManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const RENDER_INTENT_PERCEPTUAL: u32 = 0;
+pub const RENDER_INTENT_RELATIVE: u32 = 1;
+
+const FEATURE_ICC_V2_V4: u32 = 0;
+const FEATURE_PARAMETRIC: u32 = 1;
+const FEATURE_SET_PRIMARIES: u32 = 2;
+const FEATURE_SET_TF_POWER: u32 = 3;
+const FEATURE_SET_LUMINANCES: u32 = 4;
+const FEATURE_SET_MASTERING_DISPLAY_PRIMARIES: u32 = 5;
+const FEATURE_WINDOWS_SCRGB: u32 = 7;
+
+pub const PRIMARIES_SRGB: u32 = 1;
+pub const PRIMARIES_PAL_M: u32 = 2;
+pub const PRIMARIES_PAL: u32 = 3;
+pub const PRIMARIES_NTSC: u32 = 4;
+pub const PRIMARIES_GENERIC_FILM: u32 = 5;
+pub const PRIMARIES_BT2020: u32 = 6;
+pub const PRIMARIES_CIE1931_XYZ: u32 = 7;
+pub const PRIMARIES_DCI_P3: u32 = 8;
+pub const PRIMARIES_DISPLAY_P3: u32 = 9;
+pub const PRIMARIES_ADOBE_RGB: u32 = 10;
+
+pub const TRANSFER_FUNCTION_BT1886: u32 = 1;
+pub const TRANSFER_FUNCTION_GAMMA22: u32 = 2;
+pub const TRANSFER_FUNCTION_GAMMA28: u32 = 3;
+pub const TRANSFER_FUNCTION_EXT_LINEAR: u32 = 5;
+pub const TRANSFER_FUNCTION_SRGB: u32 = 9;
+pub const TRANSFER_FUNCTION_EXT_SRGB: u32 = 10;
+pub const TRANSFER_FUNCTION_ST2084_PQ: u32 = 11;
+
+pub fn named_primaries_from_wire(primaries: u32) -> Option<NamedPrimaries> {
+    let p = match primaries {
+        PRIMARIES_SRGB => NamedPrimaries::Srgb,
+        PRIMARIES_PAL_M => NamedPrimaries::PalM,
+        PRIMARIES_PAL => NamedPrimaries::Pal,
+        PRIMARIES_NTSC => NamedPrimaries::Ntsc,
+        PRIMARIES_GENERIC_FILM => NamedPrimaries::GenericFilm,
+        PRIMARIES_BT2020 => NamedPrimaries::Bt2020,
+        PRIMARIES_CIE1931_XYZ => NamedPrimaries::Cie1931Xyz,
+        PRIMARIES_DCI_P3 => NamedPrimaries::DciP3,
+        PRIMARIES_DISPLAY_P3 => NamedPrimaries::DisplayP3,
+        PRIMARIES_ADOBE_RGB => NamedPrimaries::AdobeRgb,
+        _ => return None,
+    };
+    Some(p)
+}
+
+pub fn named_primaries_to_wire(primaries: NamedPrimaries) -> u32 {
+    match primaries {
+        NamedPrimaries::Srgb => PRIMARIES_SRGB,
+        NamedPrimaries::PalM => PRIMARIES_PAL_M,
+        NamedPrimaries::Pal => PRIMARIES_PAL,
+        NamedPrimaries::Ntsc => PRIMARIES_NTSC,
+        NamedPrimaries::GenericFilm => PRIMARIES_GENERIC_FILM,
+        NamedPrimaries::Bt2020 => PRIMARIES_BT2020,
+        NamedPrimaries::Cie1931Xyz => PRIMARIES_CIE1931_XYZ,
+        NamedPrimaries::DciP3 => PRIMARIES_DCI_P3,
+        NamedPrimaries::DisplayP3 => PRIMARIES_DISPLAY_P3,
+        NamedPrimaries::AdobeRgb => PRIMARIES_ADOBE_RGB,
+    }
+}
+
+pub fn transfer_function_from_wire(tf: u32) -> Option<TransferFunction> {
+    let tf = match tf {
+        TRANSFER_FUNCTION_BT1886 => TransferFunction::Bt1886,
+        TRANSFER_FUNCTION_GAMMA22 => TransferFunction::Gamma22,
+        TRANSFER_FUNCTION_GAMMA28 => TransferFunction::Gamma28,
+        TRANSFER_FUNCTION_EXT_LINEAR => TransferFunction::ExtLinear,
+        TRANSFER_FUNCTION_SRGB => TransferFunction::Srgb,
+        TRANSFER_FUNCTION_EXT_SRGB => TransferFunction::ExtSrgb,
+        TRANSFER_FUNCTION_ST2084_PQ => TransferFunction::St2084Pq,
+        _ => return None,
+    };
+    Some(tf)
+}
+
+pub fn transfer_function_to_wire(tf: TransferFunction) -> Option<u32> {
+    let tf = match tf {
+        TransferFunction::Srgb => TRANSFER_FUNCTION_SRGB,
+        TransferFunction::ExtSrgb => TRANSFER_FUNCTION_EXT_SRGB,
+        TransferFunction::Bt1886 => TRANSFER_FUNCTION_BT1886,
+        TransferFunction::Gamma22 => TRANSFER_FUNCTION_GAMMA22,
+        TransferFunction::Gamma28 => TRANSFER_FUNCTION_GAMMA28,
+        TransferFunction::ExtLinear => TRANSFER_FUNCTION_EXT_LINEAR,
+        TransferFunction::St2084Pq => TRANSFER_FUNCTION_ST2084_PQ,
+        TransferFunction::Power(_) => return None,
+    };
+    Some(tf)
+}
+
+pub struct WpColorManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl WpColorManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: WpColorManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), WpColorManagerV1Error> {
+        let obj = Rc::new(WpColorManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        for intent in [RENDER_INTENT_PERCEPTUAL, RENDER_INTENT_RELATIVE] {
+            obj.send_supported_intent(intent);
+        }
+        for feature in [
+            FEATURE_ICC_V2_V4,
+            FEATURE_PARAMETRIC,
+            FEATURE_SET_PRIMARIES,
+            FEATURE_SET_TF_POWER,
+            FEATURE_SET_LUMINANCES,
+            FEATURE_SET_MASTERING_DISPLAY_PRIMARIES,
+            FEATURE_WINDOWS_SCRGB,
+        ] {
+            obj.send_supported_feature(feature);
+        }
+        for tf in TransferFunction::NAMED {
+            if let Some(tf) = transfer_function_to_wire(tf) {
+                obj.send_supported_tf_named(tf);
+            }
+        }
+        for primaries in NamedPrimaries::ALL {
+            obj.send_supported_primaries_named(named_primaries_to_wire(primaries));
+        }
+        obj.send_done();
+        Ok(())
+    }
+}
+
+pub struct WpColorManagerV1 {
+    pub id: WpColorManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl WpColorManagerV1 {
+    fn send_supported_intent(&self, render_intent: u32) {
+        self.client.event(SupportedIntent {
+            self_id: self.id,
+            render_intent,
+        });
+    }
+
+    fn send_supported_feature(&self, feature: u32) {
+        self.client.event(SupportedFeature {
+            self_id: self.id,
+            feature,
+        });
+    }
+
+    fn send_supported_tf_named(&self, tf: u32) {
+        self.client.event(SupportedTfNamed {
+            self_id: self.id,
+            tf,
+        });
+    }
+
+    fn send_supported_primaries_named(&self, primaries: u32) {
+        self.client.event(SupportedPrimariesNamed {
+            self_id: self.id,
+            primaries,
+        });
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+}
+
+impl WpColorManagerV1RequestHandler for WpColorManagerV1 {
+    type Error = WpColorManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_output(&self, req: GetOutput, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let obj = Rc::new(WpColorManagementOutputV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            output: output.global.clone(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        if let Some(node) = output.global.node() {
+            node.color_management_outputs
+                .set((self.client.id, req.id), obj.clone());
+        }
+        Ok(())
+    }
+
+    fn get_surface(&self, req: GetSurface, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let obj = Rc::new(WpColorManagementSurfaceV1::new(
+            req.id,
+            &surface,
+            self.version,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.install()?;
+        Ok(())
+    }
+
+    fn get_surface_feedback(
+        &self,
+        req: GetSurfaceFeedback,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let obj = Rc::new(WpColorManagementSurfaceFeedbackV1::new(
+            req.id,
+            &surface,
+            self.version,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.install();
+        Ok(())
+    }
+
+    fn create_icc_creator(
+        &self,
+        req: CreateIccCreator,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(WpImageDescriptionCreatorIccV1 {
+            id: req.obj,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            icc: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_parametric_creator(
+        &self,
+        req: CreateParametricCreator,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(WpImageDescriptionCreatorParamsV1::new(
+            req.obj,
+            &self.client,
+            self.version,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_windows_scrgb(
+        &self,
+        req: CreateWindowsScrgb,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let data = ColorDescriptionData {
+            transfer_function: TransferFunction::ExtLinear,
+            luminance: Luminance {
+                min: 0.0,
+                max: 80.0,
+                white: 80.0,
+            },
+            ..ColorDescriptionData::SRGB
+        };
+        let cd = self.client.state.color_manager.get_description(data);
+        WpImageDescriptionV1::create(
+            &self.client,
+            req.image_description,
+            self.version,
+            Ok(cd),
+            false,
+        )?;
+        Ok(())
+    }
+}
+
+global_base!(
+    WpColorManagerV1Global,
+    WpColorManagerV1,
+    WpColorManagerV1Error
+);
+
+impl Global for WpColorManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(WpColorManagerV1Global);
+
+object_base! {
+    self = WpColorManagerV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagerV1 {}
+
+simple_add_obj!(WpColorManagerV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    WpColorManagementSurfaceV1Error(#[from] WpColorManagementSurfaceV1Error),
+}
+efrom!(WpColorManagerV1Error, ClientError);
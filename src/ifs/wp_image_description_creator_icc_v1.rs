@@ -0,0 +1,128 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        color_management::{icc, ColorDescriptionData, Luminance},
+        ifs::wp_image_description_v1::{
+            ImageDescriptionFailure, WpImageDescriptionV1, CAUSE_UNSUPPORTED,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::{errorfmt::ErrorFmt, oserror::OsError},
+        wire::{wp_image_description_creator_icc_v1::*, WpImageDescriptionCreatorIccV1Id},
+    },
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+const MAX_ICC_SIZE: u32 = 32 * 1024 * 1024;
+
+pub struct WpImageDescriptionCreatorIccV1 {
+    pub id: WpImageDescriptionCreatorIccV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub icc: RefCell<Option<Vec<u8>>>,
+}
+
+impl WpImageDescriptionCreatorIccV1 {
+    fn read_icc(
+        &self,
+        fd: &OwnedFd,
+        offset: u32,
+        length: u32,
+    ) -> Result<Vec<u8>, WpImageDescriptionCreatorIccV1Error> {
+        if length == 0 || length > MAX_ICC_SIZE {
+            return Err(WpImageDescriptionCreatorIccV1Error::BadSize(length));
+        }
+        let mut buf = vec![0u8; length as usize];
+        let mut pos = 0;
+        while pos < buf.len() {
+            let n = match uapi::pread(fd.raw(), &mut buf[pos..], offset as i64 + pos as i64) {
+                Ok(b) => b.len(),
+                Err(e) => return Err(WpImageDescriptionCreatorIccV1Error::BadFd(e.into())),
+            };
+            if n == 0 {
+                return Err(WpImageDescriptionCreatorIccV1Error::OutOfFile);
+            }
+            pos += n;
+        }
+        Ok(buf)
+    }
+}
+
+impl WpImageDescriptionCreatorIccV1RequestHandler for WpImageDescriptionCreatorIccV1 {
+    type Error = WpImageDescriptionCreatorIccV1Error;
+
+    fn create(&self, req: Create, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(icc) = self.icc.borrow_mut().take() else {
+            return Err(WpImageDescriptionCreatorIccV1Error::IncompleteSet);
+        };
+        let description = match icc::parse(&icc) {
+            Ok(profile) => {
+                let data = ColorDescriptionData {
+                    named_primaries: None,
+                    primaries: profile.primaries,
+                    transfer_function: profile.transfer_function,
+                    luminance: Luminance::SRGB,
+                    target_primaries: None,
+                    target_luminance: None,
+                    max_cll: None,
+                    max_fall: None,
+                };
+                Ok(self.client.state.color_manager.get_description(data))
+            }
+            Err(e) => {
+                log::debug!("Could not use ICC profile: {}", ErrorFmt(&e));
+                Err(ImageDescriptionFailure {
+                    cause: CAUSE_UNSUPPORTED,
+                    msg: e.to_string(),
+                })
+            }
+        };
+        WpImageDescriptionV1::create(
+            &self.client,
+            req.image_description,
+            self.version,
+            description,
+            false,
+        )?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_icc_file(&self, req: SetIccFile, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.icc.borrow().is_some() {
+            return Err(WpImageDescriptionCreatorIccV1Error::AlreadySet);
+        }
+        let icc = self.read_icc(&req.icc_profile, req.offset, req.length)?;
+        *self.icc.borrow_mut() = Some(icc);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpImageDescriptionCreatorIccV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionCreatorIccV1 {}
+
+simple_add_obj!(WpImageDescriptionCreatorIccV1);
+
+#[derive(Debug, Error)]
+pub enum WpImageDescriptionCreatorIccV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The ICC file has not been set")]
+    IncompleteSet,
+    #[error("The ICC file has already been set")]
+    AlreadySet,
+    #[error("Could not read the ICC file")]
+    BadFd(#[source] OsError),
+    #[error("The ICC file size {0} is invalid")]
+    BadSize(u32),
+    #[error("The ICC file range exceeds the size of the file")]
+    OutOfFile,
+}
+efrom!(WpImageDescriptionCreatorIccV1Error, ClientError);
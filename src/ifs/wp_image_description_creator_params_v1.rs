@@ -0,0 +1,245 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        color_management::{
+            ColorDescriptionData, Luminance, NamedPrimaries, Primaries, TargetLuminance,
+            TransferFunction,
+        },
+        ifs::{
+            wp_color_manager_v1::{named_primaries_from_wire, transfer_function_from_wire},
+            wp_image_description_v1::{
+                ImageDescriptionFailure, WpImageDescriptionV1, CAUSE_UNSUPPORTED,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_creator_params_v1::*, WpImageDescriptionCreatorParamsV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct WpImageDescriptionCreatorParamsV1 {
+    pub id: WpImageDescriptionCreatorParamsV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    transfer_function: Cell<Option<TransferFunction>>,
+    primaries: Cell<Option<(Option<NamedPrimaries>, Primaries)>>,
+    luminance: Cell<Option<Luminance>>,
+    target_primaries: Cell<Option<Primaries>>,
+    target_luminance: Cell<Option<TargetLuminance>>,
+    max_cll: Cell<Option<u32>>,
+    max_fall: Cell<Option<u32>>,
+}
+
+fn primaries(
+    r_x: i32,
+    r_y: i32,
+    g_x: i32,
+    g_y: i32,
+    b_x: i32,
+    b_y: i32,
+    w_x: i32,
+    w_y: i32,
+) -> Primaries {
+    let c = |x: i32, y: i32| (x as f64 / 1_000_000.0, y as f64 / 1_000_000.0);
+    Primaries {
+        r: c(r_x, r_y),
+        g: c(g_x, g_y),
+        b: c(b_x, b_y),
+        wp: c(w_x, w_y),
+    }
+}
+
+impl WpImageDescriptionCreatorParamsV1 {
+    pub fn new(
+        id: WpImageDescriptionCreatorParamsV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            transfer_function: Default::default(),
+            primaries: Default::default(),
+            luminance: Default::default(),
+            target_primaries: Default::default(),
+            target_luminance: Default::default(),
+            max_cll: Default::default(),
+            max_fall: Default::default(),
+        }
+    }
+
+    fn set<T: Copy>(
+        &self,
+        field: &Cell<Option<T>>,
+        value: T,
+    ) -> Result<(), WpImageDescriptionCreatorParamsV1Error> {
+        if field.replace(Some(value)).is_some() {
+            return Err(WpImageDescriptionCreatorParamsV1Error::AlreadySet);
+        }
+        Ok(())
+    }
+
+    fn build(&self) -> Result<ColorDescriptionData, ImageDescriptionFailure> {
+        let unsupported = |msg: &str| ImageDescriptionFailure {
+            cause: CAUSE_UNSUPPORTED,
+            msg: msg.to_string(),
+        };
+        let transfer_function = self.transfer_function.get().unwrap();
+        let (named_primaries, primaries) = self.primaries.get().unwrap();
+        if !primaries.is_valid() {
+            return Err(unsupported("The primaries are degenerate"));
+        }
+        if let Some(target) = self.target_primaries.get() {
+            if !target.is_valid() {
+                return Err(unsupported("The target primaries are degenerate"));
+            }
+        }
+        Ok(ColorDescriptionData {
+            named_primaries,
+            primaries,
+            transfer_function,
+            luminance: self
+                .luminance
+                .get()
+                .unwrap_or_else(|| Luminance::default_for(transfer_function)),
+            target_primaries: self.target_primaries.get(),
+            target_luminance: self.target_luminance.get(),
+            max_cll: self.max_cll.get(),
+            max_fall: self.max_fall.get(),
+        })
+    }
+}
+
+impl WpImageDescriptionCreatorParamsV1RequestHandler for WpImageDescriptionCreatorParamsV1 {
+    type Error = WpImageDescriptionCreatorParamsV1Error;
+
+    fn create(&self, req: Create, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.transfer_function.get().is_none() || self.primaries.get().is_none() {
+            return Err(WpImageDescriptionCreatorParamsV1Error::IncompleteSet);
+        }
+        let description = self
+            .build()
+            .map(|data| self.client.state.color_manager.get_description(data));
+        WpImageDescriptionV1::create(
+            &self.client,
+            req.image_description,
+            self.version,
+            description,
+            false,
+        )?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_tf_named(&self, req: SetTfNamed, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(tf) = transfer_function_from_wire(req.tf) else {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidTf(req.tf));
+        };
+        self.set(&self.transfer_function, tf)
+    }
+
+    fn set_tf_power(&self, req: SetTfPower, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.eexp < 10000 || req.eexp > 100000 {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidTfPower(
+                req.eexp,
+            ));
+        }
+        let exp = req.eexp as f64 / 10000.0;
+        self.set(&self.transfer_function, TransferFunction::Power(exp))
+    }
+
+    fn set_primaries_named(
+        &self,
+        req: SetPrimariesNamed,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(named) = named_primaries_from_wire(req.primaries) else {
+            return Err(
+                WpImageDescriptionCreatorParamsV1Error::InvalidPrimariesNamed(req.primaries),
+            );
+        };
+        self.set(&self.primaries, (Some(named), named.primaries()))
+    }
+
+    fn set_primaries(&self, req: SetPrimaries, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let p = primaries(
+            req.r_x, req.r_y, req.g_x, req.g_y, req.b_x, req.b_y, req.w_x, req.w_y,
+        );
+        self.set(&self.primaries, (None, p))
+    }
+
+    fn set_luminances(&self, req: SetLuminances, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let min = req.min_lum as f64 / 10000.0;
+        let max = req.max_lum as f64;
+        let white = req.reference_lum as f64;
+        if max <= min || white <= min {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidLuminance);
+        }
+        self.set(&self.luminance, Luminance { min, max, white })
+    }
+
+    fn set_mastering_display_primaries(
+        &self,
+        req: SetMasteringDisplayPrimaries,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let p = primaries(
+            req.r_x, req.r_y, req.g_x, req.g_y, req.b_x, req.b_y, req.w_x, req.w_y,
+        );
+        self.set(&self.target_primaries, p)
+    }
+
+    fn set_mastering_luminance(
+        &self,
+        req: SetMasteringLuminance,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let min = req.min_lum as f64 / 10000.0;
+        let max = req.max_lum as f64;
+        if max <= min {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidLuminance);
+        }
+        self.set(&self.target_luminance, TargetLuminance { min, max })
+    }
+
+    fn set_max_cll(&self, req: SetMaxCll, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.set(&self.max_cll, req.max_cll)
+    }
+
+    fn set_max_fall(&self, req: SetMaxFall, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.set(&self.max_fall, req.max_fall)
+    }
+}
+
+object_base! {
+    self = WpImageDescriptionCreatorParamsV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionCreatorParamsV1 {}
+
+simple_add_obj!(WpImageDescriptionCreatorParamsV1);
+
+#[derive(Debug, Error)]
+pub enum WpImageDescriptionCreatorParamsV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The transfer function or the primaries have not been set")]
+    IncompleteSet,
+    #[error("The property has already been set")]
+    AlreadySet,
+    #[error("Unsupported transfer function {0}")]
+    InvalidTf(u32),
+    #[error("The transfer function exponent {0} is out of range")]
+    InvalidTfPower(u32),
+    #[error("Unsupported named primaries {0}")]
+    InvalidPrimariesNamed(u32),
+    #[error("The luminances are invalid")]
+    InvalidLuminance,
+}
+efrom!(WpImageDescriptionCreatorParamsV1Error, ClientError);
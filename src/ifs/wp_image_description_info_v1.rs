@@ -0,0 +1,169 @@
+use {
+    crate::{
+        client::Client,
+        color_management::{self, ColorDescription, TransferFunction},
+        ifs::wp_color_manager_v1::{named_primaries_to_wire, transfer_function_to_wire},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_info_v1::*, WpImageDescriptionInfoV1Id},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct WpImageDescriptionInfoV1 {
+    pub id: WpImageDescriptionInfoV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+/// Converts chromaticity coordinates to the fixed-point representation of the protocol.
+fn coordinates(p: &color_management::Primaries) -> [i32; 8] {
+    let c = |v: f64| (v * 1_000_000.0).round() as i32;
+    [
+        c(p.r.0),
+        c(p.r.1),
+        c(p.g.0),
+        c(p.g.1),
+        c(p.b.0),
+        c(p.b.1),
+        c(p.wp.0),
+        c(p.wp.1),
+    ]
+}
+
+impl WpImageDescriptionInfoV1 {
+    /// Sends all information events followed by the done event.
+    pub fn send_description(&self, cd: &ColorDescription) {
+        let data = &cd.data;
+        self.send_primaries(&data.primaries);
+        if let Some(named) = data.named_primaries {
+            self.send_primaries_named(named_primaries_to_wire(named));
+        }
+        match data.transfer_function {
+            TransferFunction::Power(exp) => self.send_tf_power((exp * 10000.0).round() as u32),
+            tf => {
+                if let Some(tf) = transfer_function_to_wire(tf) {
+                    self.send_tf_named(tf);
+                }
+            }
+        }
+        let lum = &data.luminance;
+        self.send_luminances(
+            (lum.min * 10000.0).round() as u32,
+            lum.max.round() as u32,
+            lum.white.round() as u32,
+        );
+        self.send_target_primaries(data.target_primaries.as_ref().unwrap_or(&data.primaries));
+        match data.target_luminance {
+            Some(t) => self.send_target_luminance(t.min, t.max),
+            None => self.send_target_luminance(lum.min, lum.max),
+        }
+        if let Some(max_cll) = data.max_cll {
+            self.send_target_max_cll(max_cll);
+        }
+        if let Some(max_fall) = data.max_fall {
+            self.send_target_max_fall(max_fall);
+        }
+        self.send_done();
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn send_primaries(&self, p: &color_management::Primaries) {
+        let [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y] = coordinates(p);
+        self.client.event(Primaries {
+            self_id: self.id,
+            r_x,
+            r_y,
+            g_x,
+            g_y,
+            b_x,
+            b_y,
+            w_x,
+            w_y,
+        });
+    }
+
+    fn send_primaries_named(&self, primaries: u32) {
+        self.client.event(PrimariesNamed {
+            self_id: self.id,
+            primaries,
+        });
+    }
+
+    fn send_tf_power(&self, eexp: u32) {
+        self.client.event(TfPower {
+            self_id: self.id,
+            eexp,
+        });
+    }
+
+    fn send_tf_named(&self, tf: u32) {
+        self.client.event(TfNamed {
+            self_id: self.id,
+            tf,
+        });
+    }
+
+    fn send_luminances(&self, min_lum: u32, max_lum: u32, reference_lum: u32) {
+        self.client.event(Luminances {
+            self_id: self.id,
+            min_lum,
+            max_lum,
+            reference_lum,
+        });
+    }
+
+    fn send_target_primaries(&self, p: &color_management::Primaries) {
+        let [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y] = coordinates(p);
+        self.client.event(TargetPrimaries {
+            self_id: self.id,
+            r_x,
+            r_y,
+            g_x,
+            g_y,
+            b_x,
+            b_y,
+            w_x,
+            w_y,
+        });
+    }
+
+    fn send_target_luminance(&self, min: f64, max: f64) {
+        self.client.event(TargetLuminance {
+            self_id: self.id,
+            min_lum: (min * 10000.0).round() as u32,
+            max_lum: max.round() as u32,
+        });
+    }
+
+    fn send_target_max_cll(&self, max_cll: u32) {
+        self.client.event(TargetMaxCll {
+            self_id: self.id,
+            max_cll,
+        });
+    }
+
+    fn send_target_max_fall(&self, max_fall: u32) {
+        self.client.event(TargetMaxFall {
+            self_id: self.id,
+            max_fall,
+        });
+    }
+}
+
+impl WpImageDescriptionInfoV1RequestHandler for WpImageDescriptionInfoV1 {
+    type Error = Infallible;
+}
+
+object_base! {
+    self = WpImageDescriptionInfoV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionInfoV1 {}
+
+simple_add_obj!(WpImageDescriptionInfoV1);
@@ -0,0 +1,129 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        color_management::ColorDescription,
+        ifs::wp_image_description_info_v1::WpImageDescriptionInfoV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_v1::*, WpImageDescriptionV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+#[allow(dead_code)]
+pub const CAUSE_LOW_VERSION: u32 = 0;
+pub const CAUSE_UNSUPPORTED: u32 = 1;
+#[allow(dead_code)]
+pub const CAUSE_OPERATING_SYSTEM: u32 = 2;
+pub const CAUSE_NO_OUTPUT: u32 = 3;
+
+/// The reason why an image description could not be created.
+pub struct ImageDescriptionFailure {
+    pub cause: u32,
+    pub msg: String,
+}
+
+pub struct WpImageDescriptionV1 {
+    pub id: WpImageDescriptionV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub description: Option<Rc<ColorDescription>>,
+    /// Only image descriptions created by the compositor can be inspected.
+    pub allow_information: bool,
+}
+
+impl WpImageDescriptionV1 {
+    pub fn create(
+        client: &Rc<Client>,
+        id: WpImageDescriptionV1Id,
+        version: Version,
+        description: Result<Rc<ColorDescription>, ImageDescriptionFailure>,
+        allow_information: bool,
+    ) -> Result<Rc<Self>, ClientError> {
+        let obj = Rc::new(Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            description: description.as_ref().ok().cloned(),
+            allow_information,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        match &description {
+            Ok(d) => obj.send_ready(d),
+            Err(f) => obj.send_failed(f.cause, &f.msg),
+        }
+        Ok(obj)
+    }
+
+    fn send_ready(&self, description: &ColorDescription) {
+        self.client.event(Ready {
+            self_id: self.id,
+            identity: description.id.raw(),
+        });
+    }
+
+    fn send_failed(&self, cause: u32, msg: &str) {
+        self.client.event(Failed {
+            self_id: self.id,
+            cause,
+            msg,
+        });
+    }
+}
+
+impl WpImageDescriptionV1RequestHandler for WpImageDescriptionV1 {
+    type Error = WpImageDescriptionV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_information(&self, req: GetInformation, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(description) = &self.description else {
+            return Err(WpImageDescriptionV1Error::NotReady);
+        };
+        if !self.allow_information {
+            return Err(WpImageDescriptionV1Error::NoInformation);
+        }
+        let obj = Rc::new(WpImageDescriptionInfoV1 {
+            id: req.information,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.send_description(description);
+        self.client.remove_obj(&*obj)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpImageDescriptionV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionV1 {}
+
+dedicated_add_obj!(
+    WpImageDescriptionV1,
+    WpImageDescriptionV1Id,
+    image_descriptions
+);
+
+#[derive(Debug, Error)]
+pub enum WpImageDescriptionV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The image description is not ready")]
+    NotReady,
+    #[error("The image description does not allow retrieving its information")]
+    NoInformation,
+}
+efrom!(WpImageDescriptionV1Error, ClientError);
@@ -0,0 +1,136 @@
+pub mod zxdg_exported_v1;
+pub mod zxdg_exported_v2;
+pub mod zxdg_exporter_v1;
+pub mod zxdg_exporter_v2;
+pub mod zxdg_imported_v1;
+pub mod zxdg_imported_v2;
+pub mod zxdg_importer_v1;
+pub mod zxdg_importer_v2;
+
+use {
+    crate::{
+        client::{Client, ClientError, ClientId},
+        ifs::wl_surface::{xdg_surface::xdg_toplevel::XdgToplevel, WlSurface},
+        object::ObjectId,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, opaque::opaque},
+        wire::{WlSurfaceId, XdgToplevelId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+type ImportKey = (ClientId, ObjectId);
+
+pub trait XdgForeignImport {
+    fn export_destroyed(&self);
+}
+
+/// A surface exported via zxdg_exporter_v1 or zxdg_exporter_v2.
+pub struct XdgForeignExport {
+    pub handle: String,
+    pub surface: Rc<WlSurface>,
+    imports: CopyHashMap<ImportKey, Rc<dyn XdgForeignImport>>,
+}
+
+impl XdgForeignExport {
+    pub fn new(surface: &Rc<WlSurface>) -> Rc<Self> {
+        let export = Rc::new(Self {
+            handle: opaque().to_string(),
+            surface: surface.clone(),
+            imports: Default::default(),
+        });
+        surface
+            .client
+            .state
+            .xdg_foreign_exports
+            .set(export.handle.clone(), export.clone());
+        export
+    }
+
+    pub fn destroy(&self) {
+        self.surface
+            .client
+            .state
+            .xdg_foreign_exports
+            .remove(&self.handle);
+        for import in self.imports.clear().into_values() {
+            import.export_destroyed();
+        }
+    }
+}
+
+/// The state shared by zxdg_imported_v1 and zxdg_imported_v2.
+pub struct XdgForeignImported {
+    key: ImportKey,
+    client: Rc<Client>,
+    export: CloneCell<Option<Rc<XdgForeignExport>>>,
+    children: CopyHashMap<XdgToplevelId, Rc<XdgToplevel>>,
+}
+
+impl XdgForeignImported {
+    pub fn new(client: &Rc<Client>, id: ObjectId) -> Self {
+        Self {
+            key: (client.id, id),
+            client: client.clone(),
+            export: Default::default(),
+            children: Default::default(),
+        }
+    }
+
+    /// Links this object to the export with the given handle.
+    ///
+    /// Returns false if the handle is not valid.
+    pub fn import(&self, handle: &str, import: Rc<dyn XdgForeignImport>) -> bool {
+        let Some(export) = self.client.state.xdg_foreign_exports.get(handle) else {
+            return false;
+        };
+        export.imports.set(self.key, import);
+        self.export.set(Some(export));
+        true
+    }
+
+    pub fn set_parent_of(&self, surface: WlSurfaceId) -> Result<(), XdgForeignError> {
+        let surface = self.client.lookup(surface)?;
+        let Some(child) = surface.xdg_toplevel() else {
+            return Err(XdgForeignError::NotAToplevel(surface.id));
+        };
+        let Some(export) = self.export.get() else {
+            return Ok(());
+        };
+        let Some(parent) = export.surface.xdg_toplevel() else {
+            return Ok(());
+        };
+        if Rc::ptr_eq(&parent, &child) {
+            return Ok(());
+        }
+        child.set_parent_toplevel(Some(parent));
+        self.children.set(child.id, child);
+        Ok(())
+    }
+
+    /// Invalidates all relationships that were set up via this object.
+    pub fn detach(&self) {
+        let Some(export) = self.export.take() else {
+            return;
+        };
+        export.imports.remove(&self.key);
+        for child in self.children.clear().into_values() {
+            let is_parent = child
+                .parent
+                .get()
+                .is_some_and(|p| Rc::ptr_eq(&p.xdg.surface, &export.surface));
+            if is_parent {
+                child.set_parent_toplevel(None);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum XdgForeignError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Surface {0} is not an xdg_toplevel")]
+    NotAToplevel(WlSurfaceId),
+}
+efrom!(XdgForeignError, ClientError);
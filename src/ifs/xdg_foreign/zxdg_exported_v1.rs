@@ -0,0 +1,58 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::xdg_foreign::XdgForeignExport,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zxdg_exported_v1::*, ZxdgExportedV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZxdgExportedV1 {
+    pub id: ZxdgExportedV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub export: Rc<XdgForeignExport>,
+}
+
+impl ZxdgExportedV1 {
+    pub fn send_handle(&self) {
+        self.client.event(Handle {
+            self_id: self.id,
+            handle: &self.export.handle,
+        });
+    }
+}
+
+impl ZxdgExportedV1RequestHandler for ZxdgExportedV1 {
+    type Error = ZxdgExportedV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.export.destroy();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZxdgExportedV1;
+    version = self.version;
+}
+
+impl Object for ZxdgExportedV1 {
+    fn break_loops(&self) {
+        self.export.destroy();
+    }
+}
+
+simple_add_obj!(ZxdgExportedV1);
+
+#[derive(Debug, Error)]
+pub enum ZxdgExportedV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZxdgExportedV1Error, ClientError);
@@ -0,0 +1,100 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::xdg_foreign::{zxdg_exported_v1::ZxdgExportedV1, XdgForeignExport},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zxdg_exporter_v1::*, ZxdgExporterV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZxdgExporterV1Global {
+    pub name: GlobalName,
+}
+
+impl ZxdgExporterV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZxdgExporterV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZxdgExporterV1Error> {
+        let obj = Rc::new(ZxdgExporterV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(ZxdgExporterV1Global, ZxdgExporterV1, ZxdgExporterV1Error);
+
+impl Global for ZxdgExporterV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZxdgExporterV1Global);
+
+pub struct ZxdgExporterV1 {
+    pub id: ZxdgExporterV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZxdgExporterV1RequestHandler for ZxdgExporterV1 {
+    type Error = ZxdgExporterV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn export(&self, req: Export, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let exported = Rc::new(ZxdgExportedV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            export: XdgForeignExport::new(&surface),
+        });
+        track!(self.client, exported);
+        self.client.add_client_obj(&exported)?;
+        exported.send_handle();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZxdgExporterV1;
+    version = self.version;
+}
+
+impl Object for ZxdgExporterV1 {}
+
+simple_add_obj!(ZxdgExporterV1);
+
+#[derive(Debug, Error)]
+pub enum ZxdgExporterV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZxdgExporterV1Error, ClientError);
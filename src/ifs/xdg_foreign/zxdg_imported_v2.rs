@@ -0,0 +1,69 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::xdg_foreign::{XdgForeignError, XdgForeignImport, XdgForeignImported},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zxdg_imported_v2::*, ZxdgImportedV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZxdgImportedV2 {
+    pub id: ZxdgImportedV2Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub imported: XdgForeignImported,
+}
+
+impl ZxdgImportedV2 {
+    pub fn send_destroyed(&self) {
+        self.client.event(Destroyed { self_id: self.id });
+    }
+}
+
+impl XdgForeignImport for ZxdgImportedV2 {
+    fn export_destroyed(&self) {
+        self.imported.detach();
+        self.send_destroyed();
+    }
+}
+
+impl ZxdgImportedV2RequestHandler for ZxdgImportedV2 {
+    type Error = ZxdgImportedV2Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.imported.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_parent_of(&self, req: SetParentOf, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.imported.set_parent_of(req.surface)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZxdgImportedV2;
+    version = self.version;
+}
+
+impl Object for ZxdgImportedV2 {
+    fn break_loops(&self) {
+        self.imported.detach();
+    }
+}
+
+simple_add_obj!(ZxdgImportedV2);
+
+#[derive(Debug, Error)]
+pub enum ZxdgImportedV2Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    XdgForeignError(#[from] XdgForeignError),
+}
+efrom!(ZxdgImportedV2Error, ClientError);
@@ -0,0 +1,101 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::xdg_foreign::{zxdg_imported_v1::ZxdgImportedV1, XdgForeignImported},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zxdg_importer_v1::*, ZxdgImporterV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZxdgImporterV1Global {
+    pub name: GlobalName,
+}
+
+impl ZxdgImporterV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZxdgImporterV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZxdgImporterV1Error> {
+        let obj = Rc::new(ZxdgImporterV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(ZxdgImporterV1Global, ZxdgImporterV1, ZxdgImporterV1Error);
+
+impl Global for ZxdgImporterV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZxdgImporterV1Global);
+
+pub struct ZxdgImporterV1 {
+    pub id: ZxdgImporterV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZxdgImporterV1RequestHandler for ZxdgImporterV1 {
+    type Error = ZxdgImporterV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn import(&self, req: Import, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let imported = Rc::new(ZxdgImportedV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            imported: XdgForeignImported::new(&self.client, req.id.into()),
+        });
+        track!(self.client, imported);
+        self.client.add_client_obj(&imported)?;
+        if !imported.imported.import(req.handle, imported.clone()) {
+            imported.send_destroyed();
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZxdgImporterV1;
+    version = self.version;
+}
+
+impl Object for ZxdgImporterV1 {}
+
+simple_add_obj!(ZxdgImporterV1);
+
+#[derive(Debug, Error)]
+pub enum ZxdgImporterV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZxdgImporterV1Error, ClientError);
@@ -0,0 +1,136 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        icons::title_icon_size,
+        ifs::xdg_toplevel_icon_v1::{XdgToplevelIconV1, XdgToplevelIconV1Error},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::ToplevelNodeBase,
+        wire::{xdg_toplevel_icon_manager_v1::*, XdgToplevelIconManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct XdgToplevelIconManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl XdgToplevelIconManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: XdgToplevelIconManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), XdgToplevelIconManagerV1Error> {
+        let mgr = Rc::new(XdgToplevelIconManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, mgr);
+        client.add_client_obj(&mgr)?;
+        let th = client.state.theme.sizes.title_height.get();
+        mgr.send_icon_size(title_icon_size(th));
+        mgr.send_done();
+        Ok(())
+    }
+}
+
+global_base!(
+    XdgToplevelIconManagerV1Global,
+    XdgToplevelIconManagerV1,
+    XdgToplevelIconManagerV1Error
+);
+
+simple_add_global!(XdgToplevelIconManagerV1Global);
+
+impl Global for XdgToplevelIconManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+pub struct XdgToplevelIconManagerV1 {
+    pub id: XdgToplevelIconManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl XdgToplevelIconManagerV1 {
+    fn send_icon_size(&self, size: i32) {
+        self.client.event(IconSize {
+            self_id: self.id,
+            size,
+        });
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+}
+
+impl XdgToplevelIconManagerV1RequestHandler for XdgToplevelIconManagerV1 {
+    type Error = XdgToplevelIconManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn create_icon(&self, req: CreateIcon, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let icon = Rc::new(XdgToplevelIconV1::new(req.id, &self.client, self.version));
+        track!(self.client, icon);
+        self.client.add_client_obj(&icon)?;
+        Ok(())
+    }
+
+    fn set_icon(&self, req: SetIcon, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let toplevel = self.client.lookup(req.toplevel)?;
+        let mut icon = None;
+        if req.icon.is_some() {
+            let obj = self.client.lookup(req.icon)?;
+            obj.immutable.set(true);
+            let state = &self.client.state;
+            let size = title_icon_size(state.theme.sizes.title_height.get());
+            let scale = state
+                .scales
+                .lock()
+                .iter()
+                .map(|(s, _)| s.to_f64())
+                .fold(1.0, f64::max);
+            icon = obj.build((size as f64 * scale).round() as i32)?;
+        }
+        toplevel.tl_data().set_icon(&*toplevel, icon);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = XdgToplevelIconManagerV1;
+    version = self.version;
+}
+
+impl Object for XdgToplevelIconManagerV1 {}
+
+simple_add_obj!(XdgToplevelIconManagerV1);
+
+#[derive(Debug, Error)]
+pub enum XdgToplevelIconManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    XdgToplevelIconV1Error(#[from] XdgToplevelIconV1Error),
+}
+efrom!(XdgToplevelIconManagerV1Error, ClientError);
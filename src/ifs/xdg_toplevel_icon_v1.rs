@@ -0,0 +1,165 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        clientmem::ClientMemError,
+        format::{ARGB8888, XRGB8888},
+        icons::{Icon, IconImage},
+        ifs::wl_buffer::{WlBuffer, WlBufferStorage},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::errorfmt::ErrorFmt,
+        wire::{xdg_toplevel_icon_v1::*, XdgToplevelIconV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct XdgToplevelIconV1 {
+    pub id: XdgToplevelIconV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub immutable: Cell<bool>,
+    name: RefCell<Option<String>>,
+    buffers: RefCell<Vec<(i32, Rc<WlBuffer>)>>,
+}
+
+impl XdgToplevelIconV1 {
+    pub fn new(id: XdgToplevelIconV1Id, client: &Rc<Client>, version: Version) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            immutable: Cell::new(false),
+            name: Default::default(),
+            buffers: Default::default(),
+        }
+    }
+
+    /// Creates the icon that should be displayed at the given size in pixels.
+    ///
+    /// Named icons take precedence. The buffers are used if the named icon cannot be
+    /// loaded from the icon theme.
+    pub fn build(&self, size: i32) -> Result<Option<Rc<Icon>>, XdgToplevelIconV1Error> {
+        if let Some(name) = &*self.name.borrow() {
+            match Icon::load(name, size) {
+                Ok(icon) => return Ok(Some(icon)),
+                Err(e) => log::debug!("Could not load icon {}: {}", name, ErrorFmt(e)),
+            }
+        }
+        let mut images = vec![];
+        for (_, buffer) in &*self.buffers.borrow() {
+            images.push(read_buffer(buffer)?);
+        }
+        Ok(Icon::new(images))
+    }
+}
+
+fn read_buffer(buffer: &WlBuffer) -> Result<IconImage, XdgToplevelIconV1Error> {
+    if buffer.destroyed() {
+        return Err(XdgToplevelIconV1Error::NoBuffer);
+    }
+    let storage = buffer.storage.borrow();
+    let Some(WlBufferStorage::Shm { mem, stride }) = &*storage else {
+        return Err(XdgToplevelIconV1Error::NoBuffer);
+    };
+    let size = buffer.rect.width() as usize;
+    let stride = *stride as usize;
+    let opaque = buffer.format.drm == XRGB8888.drm;
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    mem.access(|mem| {
+        for y in 0..size {
+            let row = &mem[y * stride..][..size * 4];
+            for px in row.chunks_exact(4) {
+                pixels.push(Cell::new(px[0].get()));
+                pixels.push(Cell::new(px[1].get()));
+                pixels.push(Cell::new(px[2].get()));
+                pixels.push(Cell::new(if opaque { 255 } else { px[3].get() }));
+            }
+        }
+    })?;
+    Ok(IconImage::new(size as i32, pixels))
+}
+
+impl XdgToplevelIconV1RequestHandler for XdgToplevelIconV1 {
+    type Error = XdgToplevelIconV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_name(&self, req: SetName<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.immutable.get() {
+            return Err(XdgToplevelIconV1Error::Immutable);
+        }
+        *self.name.borrow_mut() = Some(req.icon_name.to_string());
+        Ok(())
+    }
+
+    fn add_buffer(&self, req: AddBuffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.immutable.get() {
+            return Err(XdgToplevelIconV1Error::Immutable);
+        }
+        let buffer = self.client.lookup(req.buffer)?;
+        let is_shm = matches!(&*buffer.storage.borrow(), Some(WlBufferStorage::Shm { .. }));
+        if !is_shm {
+            return Err(XdgToplevelIconV1Error::NotShm);
+        }
+        if buffer.format.drm != ARGB8888.drm && buffer.format.drm != XRGB8888.drm {
+            return Err(XdgToplevelIconV1Error::UnsupportedFormat(
+                buffer.format.name,
+            ));
+        }
+        if buffer.rect.width() != buffer.rect.height() {
+            return Err(XdgToplevelIconV1Error::NotSquare);
+        }
+        if req.scale < 1 {
+            return Err(XdgToplevelIconV1Error::InvalidScale(req.scale));
+        }
+        let mut buffers = self.buffers.borrow_mut();
+        let size = buffer.rect.width();
+        buffers.retain(|(scale, b)| *scale != req.scale || b.rect.width() != size);
+        buffers.push((req.scale, buffer));
+        Ok(())
+    }
+}
+
+object_base! {
+    self = XdgToplevelIconV1;
+    version = self.version;
+}
+
+impl Object for XdgToplevelIconV1 {
+    fn break_loops(&self) {
+        self.buffers.borrow_mut().clear();
+    }
+}
+
+dedicated_add_obj!(XdgToplevelIconV1, XdgToplevelIconV1Id, toplevel_icons);
+
+#[derive(Debug, Error)]
+pub enum XdgToplevelIconV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The icon has already been assigned to a toplevel")]
+    Immutable,
+    #[error("The buffer is not a shm buffer")]
+    NotShm,
+    #[error("The buffer format {0} is not supported")]
+    UnsupportedFormat(&'static str),
+    #[error("The buffer is not square")]
+    NotSquare,
+    #[error("The scale {0} is invalid")]
+    InvalidScale(i32),
+    #[error("A buffer has been destroyed before the icon was assigned")]
+    NoBuffer,
+    #[error(transparent)]
+    ClientMemError(Box<ClientMemError>),
+}
+efrom!(XdgToplevelIconV1Error, ClientError);
+efrom!(XdgToplevelIconV1Error, ClientMemError);
@@ -0,0 +1,237 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_output::WlOutputGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::ToplevelNode,
+        utils::clonecell::CloneCell,
+        wire::{zwlr_foreign_toplevel_handle_v1::*, WlOutputId, ZwlrForeignToplevelHandleV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+#[allow(dead_code)]
+pub const STATE_MAXIMIZED: u32 = 0;
+pub const STATE_MINIMIZED: u32 = 1;
+pub const STATE_ACTIVATED: u32 = 2;
+pub const STATE_FULLSCREEN: u32 = 3;
+
+pub struct ZwlrForeignToplevelHandleV1 {
+    pub id: ZwlrForeignToplevelHandleV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub toplevel: Rc<dyn ToplevelNode>,
+    pub version: Version,
+    pub output: CloneCell<Option<Rc<WlOutputGlobal>>>,
+    pub closed: Cell<bool>,
+}
+
+impl ZwlrForeignToplevelHandleV1 {
+    fn detach(&self) {
+        self.toplevel
+            .tl_data()
+            .wlr_handles
+            .remove(&(self.client.id, self.id));
+    }
+
+    /// Returns the toplevel unless it has been closed.
+    fn toplevel(&self) -> Option<&Rc<dyn ToplevelNode>> {
+        match self.closed.get() {
+            true => None,
+            false => Some(&self.toplevel),
+        }
+    }
+
+    /// Sends output_enter/output_leave events. Returns whether the output changed.
+    pub fn set_output(&self, output: Option<&Rc<WlOutputGlobal>>) -> bool {
+        let old = self.output.get();
+        if old.as_ref().map(|o| o.name) == output.map(|o| o.name) {
+            return false;
+        }
+        if let Some(old) = old {
+            old.for_each_binding(self.client.id, |b| self.send_output_leave(b.id));
+        }
+        if let Some(output) = output {
+            output.for_each_binding(self.client.id, |b| self.send_output_enter(b.id));
+        }
+        self.output.set(output.cloned());
+        true
+    }
+
+    pub fn send_closed(&self) {
+        self.closed.set(true);
+        self.client.event(Closed { self_id: self.id });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_title(&self, title: &str) {
+        self.client.event(Title {
+            self_id: self.id,
+            title,
+        });
+    }
+
+    pub fn send_app_id(&self, app_id: &str) {
+        self.client.event(AppId {
+            self_id: self.id,
+            app_id,
+        });
+    }
+
+    pub fn send_state(&self, state: &[u32]) {
+        self.client.event(State {
+            self_id: self.id,
+            state,
+        });
+    }
+
+    fn send_output_enter(&self, output: WlOutputId) {
+        self.client.event(OutputEnter {
+            self_id: self.id,
+            output,
+        });
+    }
+
+    fn send_output_leave(&self, output: WlOutputId) {
+        self.client.event(OutputLeave {
+            self_id: self.id,
+            output,
+        });
+    }
+}
+
+impl ZwlrForeignToplevelHandleV1RequestHandler for ZwlrForeignToplevelHandleV1 {
+    type Error = ZwlrForeignToplevelHandleV1Error;
+
+    fn set_maximized(&self, _req: SetMaximized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        // Jay has no maximized state. Windows are never reported as maximized and these
+        // requests are ignored.
+        Ok(())
+    }
+
+    fn unset_maximized(&self, _req: UnsetMaximized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_minimized(&self, _req: SetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(tl) = self.toplevel() {
+            let state = &self.client.state;
+            state.scratchpad.hide(state, tl.clone());
+        }
+        Ok(())
+    }
+
+    fn unset_minimized(&self, _req: UnsetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(tl) = self.toplevel() else {
+            return Ok(());
+        };
+        let state = &self.client.state;
+        if tl.tl_data().scratchpad_link.borrow().is_none() {
+            return Ok(());
+        }
+        if let Some(seat) = state.seat_queue.last() {
+            state.scratchpad.show_window(state, &seat, tl.clone());
+        }
+        Ok(())
+    }
+
+    fn activate(&self, req: Activate, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let Some(tl) = self.toplevel() else {
+            return Ok(());
+        };
+        let state = &self.client.state;
+        let data = tl.tl_data();
+        if data.scratchpad_link.borrow().is_some() {
+            state
+                .scratchpad
+                .show_window(state, &seat.global, tl.clone());
+            return Ok(());
+        }
+        if let Some(ws) = data.workspace.get() {
+            if !ws.visible.get() {
+                state.show_workspace(&seat.global, &ws.name);
+            }
+        }
+        seat.global.focus_toplevel(tl.clone());
+        Ok(())
+    }
+
+    fn close(&self, _req: Close, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(tl) = self.toplevel() {
+            tl.clone().tl_close();
+        }
+        Ok(())
+    }
+
+    fn set_rectangle(&self, req: SetRectangle, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.width < 0 || req.height < 0 {
+            return Err(ZwlrForeignToplevelHandleV1Error::InvalidRectangle);
+        }
+        self.client.lookup(req.surface)?;
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_fullscreen(&self, req: SetFullscreen, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = if req.output.is_some() {
+            Some(self.client.lookup(req.output)?)
+        } else {
+            None
+        };
+        let Some(tl) = self.toplevel() else {
+            return Ok(());
+        };
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return Ok(());
+        }
+        match output.and_then(|o| o.global.node()) {
+            Some(output) => data.set_fullscreen(&self.client.state, tl.clone(), &output),
+            _ => tl.clone().tl_set_fullscreen(true),
+        }
+        Ok(())
+    }
+
+    fn unset_fullscreen(&self, _req: UnsetFullscreen, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(tl) = self.toplevel() {
+            if tl.tl_data().is_fullscreen.get() {
+                tl.clone().tl_set_fullscreen(false);
+            }
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrForeignToplevelHandleV1;
+    version = self.version;
+}
+
+impl Object for ZwlrForeignToplevelHandleV1 {
+    fn break_loops(&self) {
+        self.detach();
+        self.output.take();
+    }
+}
+
+simple_add_obj!(ZwlrForeignToplevelHandleV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrForeignToplevelHandleV1Error {
+    #[error("The rectangle has a negative size")]
+    InvalidRectangle,
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrForeignToplevelHandleV1Error, ClientError);
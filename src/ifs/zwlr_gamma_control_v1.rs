@@ -0,0 +1,124 @@
+use {
+    crate::{
+        backend::GammaLut,
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        state::ConnectorData,
+        utils::errorfmt::ErrorFmt,
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+pub struct ZwlrGammaControlV1 {
+    pub id: ZwlrGammaControlV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub connector: Option<Rc<ConnectorData>>,
+    pub size: u32,
+    pub active: Cell<bool>,
+}
+
+impl ZwlrGammaControlV1 {
+    pub fn send_gamma_size(&self) {
+        self.client.event(GammaSize {
+            self_id: self.id,
+            size: self.size,
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    /// Releases the connector and restores the compositor's gamma LUT.
+    fn detach(&self) {
+        if !self.active.replace(false) {
+            return;
+        }
+        if let Some(connector) = &self.connector {
+            connector.gamma_control.take();
+            connector.update_gamma_lut();
+        }
+    }
+
+    /// Invalidates this object, e.g. because the output was disconnected.
+    pub fn fail(&self) {
+        if self.active.get() {
+            self.detach();
+            self.send_failed();
+        }
+    }
+
+    fn read_lut(&self, fd: &OwnedFd) -> Result<Option<GammaLut>, ZwlrGammaControlV1Error> {
+        let size = self.size as usize;
+        let mut buf = vec![0u16; 3 * size];
+        let len = match uapi::pread(fd.raw(), &mut buf[..], 0) {
+            Ok(b) => b.len(),
+            Err(e) => {
+                log::warn!("Could not read gamma ramps: {}", ErrorFmt(e));
+                return Ok(None);
+            }
+        };
+        if len != 3 * size * size_of::<u16>() {
+            return Err(ZwlrGammaControlV1Error::InvalidGamma);
+        }
+        let blue = buf.split_off(2 * size);
+        let green = buf.split_off(size);
+        Ok(Some(GammaLut {
+            red: buf,
+            green,
+            blue,
+        }))
+    }
+}
+
+impl ZwlrGammaControlV1RequestHandler for ZwlrGammaControlV1 {
+    type Error = ZwlrGammaControlV1Error;
+
+    fn set_gamma(&self, req: SetGamma, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if !self.active.get() {
+            return Ok(());
+        }
+        let Some(connector) = &self.connector else {
+            return Ok(());
+        };
+        match self.read_lut(&req.fd)? {
+            Some(lut) => connector.connector.set_gamma_lut(Some(Rc::new(lut))),
+            _ => self.fail(),
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrGammaControlV1;
+    version = self.version;
+}
+
+impl Object for ZwlrGammaControlV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The gamma ramps have the wrong size")]
+    InvalidGamma,
+}
+efrom!(ZwlrGammaControlV1Error, ClientError);
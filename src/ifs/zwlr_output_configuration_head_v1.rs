@@ -0,0 +1,164 @@
+use {
+    crate::{
+        backend::Mode,
+        client::{Client, ClientError},
+        ifs::zwlr_output_head_v1::{
+            ZwlrOutputHeadV1, ADAPTIVE_SYNC_DISABLED, ADAPTIVE_SYNC_ENABLED,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        scale::Scale,
+        utils::transform_ext::TransformExt,
+        wire::{zwlr_output_configuration_head_v1::*, ZwlrOutputConfigurationHeadV1Id},
+    },
+    jay_config::video::Transform,
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const INVALID_SCALE: u32 = 5;
+
+const MIN_SCALE: f64 = 0.1;
+const MAX_SCALE: f64 = 1000.0;
+
+pub struct ZwlrOutputConfigurationHeadV1 {
+    pub id: ZwlrOutputConfigurationHeadV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub head: Rc<ZwlrOutputHeadV1>,
+    pub mode: Cell<Option<Mode>>,
+    pub position: Cell<Option<(i32, i32)>>,
+    pub transform: Cell<Option<Transform>>,
+    pub scale: Cell<Option<Scale>>,
+    pub adaptive_sync: Cell<Option<bool>>,
+}
+
+impl ZwlrOutputConfigurationHeadV1 {
+    fn set_mode_(&self, mode: Mode) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        if self.mode.replace(Some(mode)).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("mode"));
+        }
+        Ok(())
+    }
+}
+
+impl ZwlrOutputConfigurationHeadV1RequestHandler for ZwlrOutputConfigurationHeadV1 {
+    type Error = ZwlrOutputConfigurationHeadV1Error;
+
+    fn set_mode(&self, req: SetMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mode = self.client.lookup(req.mode)?;
+        let belongs_to_head = self
+            .head
+            .modes
+            .borrow()
+            .iter()
+            .any(|m| Rc::ptr_eq(m, &mode));
+        if !belongs_to_head {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidMode);
+        }
+        self.set_mode_(mode.mode)
+    }
+
+    fn set_custom_mode(&self, req: SetCustomMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.width <= 0 || req.height <= 0 || req.refresh < 0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidCustomMode);
+        }
+        // Custom modes are not supported. Use a matching mode of the head instead and let
+        // the configuration fail if there is none.
+        let hs = self.head.head_state.borrow();
+        let mut same_size = hs
+            .modes
+            .iter()
+            .filter(|m| m.width == req.width && m.height == req.height);
+        let matching = match req.refresh as u32 {
+            0 => same_size.max_by_key(|m| m.refresh_rate_millihz),
+            r => same_size.find(|m| m.refresh_rate_millihz.abs_diff(r) < 1000),
+        };
+        let mode = matching.copied().unwrap_or(Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh as _,
+        });
+        self.set_mode_(mode)
+    }
+
+    fn set_position(&self, req: SetPosition, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.position.replace(Some((req.x, req.y))).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("position"));
+        }
+        Ok(())
+    }
+
+    fn set_transform(&self, req: SetTransform, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(transform) = Transform::from_wl(req.transform) else {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidTransform(
+                req.transform,
+            ));
+        };
+        if self.transform.replace(Some(transform)).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("transform"));
+        }
+        Ok(())
+    }
+
+    fn set_scale(&self, req: SetScale, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let scale = req.scale.to_f64();
+        if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+            self.client.protocol_error(
+                self,
+                INVALID_SCALE,
+                &format!("Scale {scale} is not in the range {MIN_SCALE}..={MAX_SCALE}"),
+            );
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidScale(scale));
+        }
+        if self.scale.replace(Some(Scale::from_f64(scale))).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("scale"));
+        }
+        Ok(())
+    }
+
+    fn set_adaptive_sync(&self, req: SetAdaptiveSync, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let enabled = match req.state {
+            ADAPTIVE_SYNC_DISABLED => false,
+            ADAPTIVE_SYNC_ENABLED => true,
+            _ => {
+                return Err(ZwlrOutputConfigurationHeadV1Error::InvalidAdaptiveSyncState(req.state))
+            }
+        };
+        if self.adaptive_sync.replace(Some(enabled)).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet(
+                "adaptive sync",
+            ));
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationHeadV1 {}
+
+simple_add_obj!(ZwlrOutputConfigurationHeadV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The {0} has already been set")]
+    AlreadySet(&'static str),
+    #[error("The mode does not belong to the head")]
+    InvalidMode,
+    #[error("The custom mode is invalid")]
+    InvalidCustomMode,
+    #[error("Unknown transform {0}")]
+    InvalidTransform(i32),
+    #[error("Scale {0} is out of range")]
+    InvalidScale(f64),
+    #[error("Unknown adaptive sync state {0}")]
+    InvalidAdaptiveSyncState(u32),
+}
+efrom!(ZwlrOutputConfigurationHeadV1Error, ClientError);
@@ -0,0 +1,238 @@
+use {
+    crate::{
+        backend::{ConnectorChange, ConnectorId, Mode},
+        client::{Client, ClientError},
+        compositor::MAX_EXTENTS,
+        ifs::{
+            zwlr_output_configuration_head_v1::ZwlrOutputConfigurationHeadV1,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::VrrMode,
+        utils::errorfmt::ErrorFmt,
+        wire::{zwlr_output_configuration_v1::*, ZwlrOutputConfigurationV1Id},
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputConfigurationV1 {
+    pub id: ZwlrOutputConfigurationV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub manager: Rc<ZwlrOutputManagerV1>,
+    pub serial: u32,
+    /// The configured heads. `None` if the head is to be disabled.
+    pub heads: RefCell<AHashMap<ConnectorId, Option<Rc<ZwlrOutputConfigurationHeadV1>>>>,
+    pub used: Cell<bool>,
+}
+
+impl ZwlrOutputConfigurationV1 {
+    fn send_succeeded(&self) {
+        self.client.event(Succeeded { self_id: self.id });
+    }
+
+    fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn configure_head(
+        &self,
+        connector: ConnectorId,
+        head: Option<Rc<ZwlrOutputConfigurationHeadV1>>,
+    ) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.get() {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyUsed);
+        }
+        let mut heads = self.heads.borrow_mut();
+        if heads.contains_key(&connector) {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyConfiguredHead);
+        }
+        heads.insert(connector, head);
+        Ok(())
+    }
+
+    fn finish(&self, test_only: bool) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.replace(true) {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyUsed);
+        }
+        if self.manager.stopped.get() || self.manager.serial.get() != self.serial {
+            self.send_cancelled();
+            return Ok(());
+        }
+        let heads = self.heads.borrow();
+        for head in self.manager.heads.lock().values() {
+            if !head.released.get() && !heads.contains_key(&head.connector) {
+                return Err(ZwlrOutputConfigurationV1Error::UnconfiguredHead);
+            }
+        }
+        match self.apply_configuration(&heads, test_only) {
+            Ok(()) => self.send_succeeded(),
+            Err(e) => {
+                log::warn!("Could not apply output configuration: {}", ErrorFmt(e));
+                self.send_failed();
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_configuration(
+        &self,
+        heads: &AHashMap<ConnectorId, Option<Rc<ZwlrOutputConfigurationHeadV1>>>,
+        test_only: bool,
+    ) -> Result<(), ApplyError> {
+        let state = &self.client.state;
+        let mut changes = vec![];
+        for (id, head) in heads {
+            let Some(connector) = state.connectors.get(id) else {
+                return Err(ApplyError::UnknownConnector(*id));
+            };
+            let mut mode = None;
+            if let Some(head) = head {
+                mode = head.mode.get();
+                if let Some(mode) = mode {
+                    if !head.head.head_state.borrow().modes.contains(&mode) {
+                        return Err(ApplyError::UnsupportedMode(connector.name.clone(), mode));
+                    }
+                }
+                if let Some((x, y)) = head.position.get() {
+                    if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
+                        return Err(ApplyError::InvalidPosition(connector.name.clone()));
+                    }
+                }
+            }
+            changes.push(ConnectorChange {
+                connector: connector.connector.clone(),
+                enabled: head.is_some(),
+                mode,
+            });
+        }
+        state
+            .backend
+            .get()
+            .apply_connector_changes(&changes, test_only)
+            .map_err(ApplyError::Backend)?;
+        if test_only {
+            return Ok(());
+        }
+        // Outputs that were disabled before have not been connected yet. The remaining
+        // properties only apply to outputs that are already connected.
+        for (id, head) in heads {
+            let (Some(head), Some(node)) = (head, state.root.outputs.get(id)) else {
+                continue;
+            };
+            if let Some(transform) = head.transform.get() {
+                node.update_transform(transform);
+            }
+            if let Some(scale) = head.scale.get() {
+                node.set_preferred_scale(scale);
+            }
+            if let Some((x, y)) = head.position.get() {
+                node.set_position(x, y);
+            }
+            if let Some(enabled) = head.adaptive_sync.get() {
+                let vrr_mode = node.global.persistent.vrr_mode.get();
+                if enabled == (vrr_mode == VrrMode::NEVER) {
+                    let vrr_mode = match enabled {
+                        true => VrrMode::ALWAYS,
+                        false => VrrMode::NEVER,
+                    };
+                    node.global.persistent.vrr_mode.set(vrr_mode);
+                    node.update_presentation_type();
+                }
+            }
+        }
+        state.update_output_managers();
+        Ok(())
+    }
+}
+
+impl ZwlrOutputConfigurationV1RequestHandler for ZwlrOutputConfigurationV1 {
+    type Error = ZwlrOutputConfigurationV1Error;
+
+    fn enable_head(&self, req: EnableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        let obj = Rc::new(ZwlrOutputConfigurationHeadV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            head: head.clone(),
+            mode: Default::default(),
+            position: Default::default(),
+            transform: Default::default(),
+            scale: Default::default(),
+            adaptive_sync: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.configure_head(head.connector, Some(obj))
+    }
+
+    fn disable_head(&self, req: DisableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        self.configure_head(head.connector, None)
+    }
+
+    fn apply(&self, _req: Apply, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.finish(false)
+    }
+
+    fn test(&self, _req: Test, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.finish(true)
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.heads.borrow_mut().clear();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationV1 {
+    fn break_loops(&self) {
+        self.heads.borrow_mut().clear();
+    }
+}
+
+simple_add_obj!(ZwlrOutputConfigurationV1);
+
+#[derive(Debug, Error)]
+enum ApplyError {
+    #[error("Connector {0} does not exist")]
+    UnknownConnector(ConnectorId),
+    #[error("Output {0} does not support mode {1:?}")]
+    UnsupportedMode(String, Mode),
+    #[error("The position of output {0} is out of bounds")]
+    InvalidPosition(String),
+    #[error("The backend could not apply the configuration")]
+    Backend(#[source] Box<dyn std::error::Error>),
+}
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The head has already been configured")]
+    AlreadyConfiguredHead,
+    #[error("Not all heads have been configured")]
+    UnconfiguredHead,
+    #[error("The configuration has already been applied or tested")]
+    AlreadyUsed,
+}
+efrom!(ZwlrOutputConfigurationV1Error, ClientError);
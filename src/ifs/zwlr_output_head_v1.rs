@@ -0,0 +1,257 @@
+use {
+    crate::{
+        backend::{self, ConnectorId},
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{zwlr_output_manager_v1::HeadState, zwlr_output_mode_v1::ZwlrOutputModeV1},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::transform_ext::TransformExt,
+        wire::{zwlr_output_head_v1::*, ZwlrOutputHeadV1Id, ZwlrOutputModeV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub const ADAPTIVE_SYNC_DISABLED: u32 = 0;
+pub const ADAPTIVE_SYNC_ENABLED: u32 = 1;
+
+const MAKE_SINCE: Version = Version(2);
+const ADAPTIVE_SYNC_SINCE: Version = Version(4);
+
+pub struct ZwlrOutputHeadV1 {
+    pub id: ZwlrOutputHeadV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub connector: ConnectorId,
+    pub head_state: RefCell<HeadState>,
+    pub modes: RefCell<Vec<Rc<ZwlrOutputModeV1>>>,
+    pub released: Cell<bool>,
+}
+
+impl ZwlrOutputHeadV1 {
+    pub fn send_initial(&self) {
+        let hs = self.head_state.borrow();
+        self.client.event(Name {
+            self_id: self.id,
+            name: &hs.name,
+        });
+        self.client.event(Description {
+            self_id: self.id,
+            description: &hs.description,
+        });
+        if hs.physical_size != (0, 0) {
+            self.client.event(PhysicalSize {
+                self_id: self.id,
+                width: hs.physical_size.0,
+                height: hs.physical_size.1,
+            });
+        }
+        self.create_modes(&hs.modes);
+        self.send_enabled(hs.enabled);
+        if hs.enabled {
+            self.send_current_mode(hs.current_mode);
+            self.send_position(hs.position);
+            self.send_transform(&hs);
+            self.send_scale(&hs);
+            self.send_adaptive_sync(hs.adaptive_sync);
+        }
+        if self.version >= MAKE_SINCE {
+            if !hs.make.is_empty() {
+                self.client.event(Make {
+                    self_id: self.id,
+                    make: &hs.make,
+                });
+            }
+            if !hs.model.is_empty() {
+                self.client.event(Model {
+                    self_id: self.id,
+                    model: &hs.model,
+                });
+            }
+            if !hs.serial_number.is_empty() {
+                self.client.event(SerialNumber {
+                    self_id: self.id,
+                    serial_number: &hs.serial_number,
+                });
+            }
+        }
+    }
+
+    /// Sends the properties that differ from the previous state.
+    ///
+    /// Returns whether the state has changed.
+    pub fn update(&self, hs: HeadState) -> bool {
+        let mut old = self.head_state.borrow_mut();
+        if *old == hs {
+            return false;
+        }
+        if self.released.get() {
+            *old = hs;
+            return true;
+        }
+        let modes_changed = old.modes != hs.modes;
+        if modes_changed {
+            for mode in self.modes.borrow_mut().drain(..) {
+                mode.send_finished();
+            }
+            self.create_modes(&hs.modes);
+        }
+        if old.enabled != hs.enabled {
+            self.send_enabled(hs.enabled);
+        }
+        if hs.enabled {
+            let all = modes_changed || !old.enabled;
+            if all || old.current_mode != hs.current_mode {
+                self.send_current_mode(hs.current_mode);
+            }
+            if all || old.position != hs.position {
+                self.send_position(hs.position);
+            }
+            if all || old.transform != hs.transform {
+                self.send_transform(&hs);
+            }
+            if all || old.scale != hs.scale {
+                self.send_scale(&hs);
+            }
+            if all || old.adaptive_sync != hs.adaptive_sync {
+                self.send_adaptive_sync(hs.adaptive_sync);
+            }
+        }
+        *old = hs;
+        true
+    }
+
+    fn create_modes(&self, modes: &[backend::Mode]) {
+        for &mode in modes {
+            let id: ZwlrOutputModeV1Id = match self.client.new_id() {
+                Ok(i) => i,
+                Err(e) => {
+                    self.client.error(e);
+                    return;
+                }
+            };
+            let obj = Rc::new(ZwlrOutputModeV1 {
+                id,
+                client: self.client.clone(),
+                tracker: Default::default(),
+                version: self.version,
+                mode,
+                released: Cell::new(false),
+            });
+            track!(self.client, obj);
+            self.client.add_server_obj(&obj);
+            self.client.event(Mode {
+                self_id: self.id,
+                mode: id,
+            });
+            obj.send_size();
+            obj.send_refresh();
+            self.modes.borrow_mut().push(obj);
+        }
+    }
+
+    pub fn find_mode(&self, mode: backend::Mode) -> Option<Rc<ZwlrOutputModeV1>> {
+        self.modes.borrow().iter().find(|m| m.mode == mode).cloned()
+    }
+
+    fn send_enabled(&self, enabled: bool) {
+        self.client.event(Enabled {
+            self_id: self.id,
+            enabled: enabled as _,
+        });
+    }
+
+    fn send_current_mode(&self, mode: Option<backend::Mode>) {
+        let Some(mode) = mode.and_then(|m| self.find_mode(m)) else {
+            return;
+        };
+        if mode.released.get() {
+            return;
+        }
+        self.client.event(CurrentMode {
+            self_id: self.id,
+            mode: mode.id,
+        });
+    }
+
+    fn send_position(&self, (x, y): (i32, i32)) {
+        self.client.event(Position {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    fn send_transform(&self, hs: &HeadState) {
+        self.client.event(Transform {
+            self_id: self.id,
+            transform: hs.transform.to_wl(),
+        });
+    }
+
+    fn send_scale(&self, hs: &HeadState) {
+        self.client.event(Scale {
+            self_id: self.id,
+            scale: Fixed::from_f64(hs.scale.to_f64()),
+        });
+    }
+
+    fn send_adaptive_sync(&self, enabled: bool) {
+        if self.version < ADAPTIVE_SYNC_SINCE {
+            return;
+        }
+        let state = match enabled {
+            true => ADAPTIVE_SYNC_ENABLED,
+            false => ADAPTIVE_SYNC_DISABLED,
+        };
+        self.client.event(AdaptiveSync {
+            self_id: self.id,
+            state,
+        });
+    }
+
+    pub fn send_finished(&self) {
+        for mode in self.modes.borrow_mut().drain(..) {
+            mode.send_finished();
+        }
+        if self.released.get() {
+            return;
+        }
+        self.client.event(Finished { self_id: self.id });
+    }
+}
+
+impl ZwlrOutputHeadV1RequestHandler for ZwlrOutputHeadV1 {
+    type Error = ZwlrOutputHeadV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputHeadV1 {
+    fn break_loops(&self) {
+        self.modes.take();
+    }
+}
+
+dedicated_add_obj!(ZwlrOutputHeadV1, ZwlrOutputHeadV1Id, output_heads);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputHeadV1Error, ClientError);
@@ -0,0 +1,324 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        client::{Client, ClientCaps, ClientError, CAP_OUTPUT_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::{
+            zwlr_output_configuration_v1::ZwlrOutputConfigurationV1,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        scale::Scale,
+        state::State,
+        tree::VrrMode,
+        utils::copyhashmap::CopyHashMap,
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputHeadV1Id, ZwlrOutputManagerV1Id},
+    },
+    ahash::AHashMap,
+    jay_config::video::Transform,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrOutputManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let obj = Rc::new(ZwlrOutputManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            serial: Cell::new(0),
+            heads: Default::default(),
+            stopped: Cell::new(false),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        client
+            .state
+            .output_managers
+            .set((client.id, id), obj.clone());
+        obj.update();
+        Ok(())
+    }
+}
+
+/// The state of a connector as advertised to output management clients.
+#[derive(Clone, PartialEq)]
+pub struct HeadState {
+    pub name: String,
+    pub description: String,
+    pub physical_size: (i32, i32),
+    pub make: String,
+    pub model: String,
+    pub serial_number: String,
+    pub modes: Vec<Mode>,
+    pub enabled: bool,
+    pub current_mode: Option<Mode>,
+    pub position: (i32, i32),
+    pub transform: Transform,
+    pub scale: Scale,
+    pub adaptive_sync: bool,
+}
+
+impl HeadState {
+    /// Returns whether both states describe the same monitor.
+    ///
+    /// These properties can only be sent once per head.
+    fn same_monitor(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.physical_size == other.physical_size
+            && self.make == other.make
+            && self.model == other.model
+            && self.serial_number == other.serial_number
+    }
+}
+
+fn head_states(state: &State) -> AHashMap<ConnectorId, HeadState> {
+    let mut states = AHashMap::new();
+    for connector in state.connectors.lock().values() {
+        let id = connector.connector.id();
+        let head = if let Some(output) = state.outputs.get(&id) {
+            let Some(node) = &output.node else {
+                continue;
+            };
+            let global = &node.global;
+            let info = &output.monitor_info;
+            let pos = global.pos.get();
+            let mut description = String::new();
+            for part in [&info.manufacturer, &info.product, &info.serial_number] {
+                if !part.is_empty() {
+                    description.push_str(part);
+                    description.push(' ');
+                }
+            }
+            description.push_str(&format!("({})", connector.name));
+            HeadState {
+                name: connector.name.clone(),
+                description,
+                physical_size: (global.width_mm, global.height_mm),
+                make: info.manufacturer.clone(),
+                model: info.product.clone(),
+                serial_number: info.serial_number.clone(),
+                modes: global.modes.clone(),
+                enabled: true,
+                current_mode: Some(global.mode.get()),
+                position: (pos.x1(), pos.y1()),
+                transform: global.persistent.transform.get(),
+                scale: global.persistent.scale.get(),
+                adaptive_sync: global.persistent.vrr_mode.get() != VrrMode::NEVER,
+            }
+        } else if !connector.connector.enabled() {
+            HeadState {
+                name: connector.name.clone(),
+                description: connector.name.clone(),
+                physical_size: (0, 0),
+                make: String::new(),
+                model: String::new(),
+                serial_number: String::new(),
+                modes: vec![],
+                enabled: false,
+                current_mode: None,
+                position: (0, 0),
+                transform: Transform::None,
+                scale: Scale::default(),
+                adaptive_sync: false,
+            }
+        } else {
+            continue;
+        };
+        states.insert(id, head);
+    }
+    states
+}
+
+pub struct ZwlrOutputManagerV1 {
+    pub id: ZwlrOutputManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub serial: Cell<u32>,
+    pub heads: CopyHashMap<ConnectorId, Rc<ZwlrOutputHeadV1>>,
+    pub stopped: Cell<bool>,
+}
+
+impl ZwlrOutputManagerV1 {
+    fn detach(&self) {
+        self.client
+            .state
+            .output_managers
+            .remove(&(self.client.id, self.id));
+        self.heads.clear();
+    }
+
+    fn send_head(&self, head: &ZwlrOutputHeadV1) {
+        self.client.event(Head {
+            self_id: self.id,
+            head: head.id,
+        });
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done {
+            self_id: self.id,
+            serial: self.serial.get(),
+        });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    /// Sends the changes since the last update to the client.
+    pub fn update(self: &Rc<Self>) {
+        if self.stopped.get() {
+            return;
+        }
+        let mut states = head_states(&self.client.state);
+        let mut changed = false;
+        let heads: Vec<_> = self.heads.lock().values().cloned().collect();
+        for head in heads {
+            match states.remove(&head.connector) {
+                Some(hs) if head.head_state.borrow().same_monitor(&hs) => {
+                    changed |= head.update(hs);
+                }
+                hs => {
+                    self.heads.remove(&head.connector);
+                    head.send_finished();
+                    if let Some(hs) = hs {
+                        states.insert(head.connector, hs);
+                    }
+                    changed = true;
+                }
+            }
+        }
+        for (connector, hs) in states {
+            if let Err(e) = self.create_head(connector, hs) {
+                self.client.error(e);
+                return;
+            }
+            changed = true;
+        }
+        if changed {
+            self.serial.set(self.serial.get().wrapping_add(1));
+            self.send_done();
+        }
+    }
+
+    fn create_head(
+        &self,
+        connector: ConnectorId,
+        hs: HeadState,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let id: ZwlrOutputHeadV1Id = self.client.new_id()?;
+        let head = Rc::new(ZwlrOutputHeadV1 {
+            id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            connector,
+            head_state: RefCell::new(hs),
+            modes: Default::default(),
+            released: Cell::new(false),
+        });
+        track!(self.client, head);
+        self.client.add_server_obj(&head);
+        self.send_head(&head);
+        head.send_initial();
+        self.heads.set(connector, head);
+        Ok(())
+    }
+}
+
+impl ZwlrOutputManagerV1RequestHandler for ZwlrOutputManagerV1 {
+    type Error = ZwlrOutputManagerV1Error;
+
+    fn create_configuration(
+        &self,
+        req: CreateConfiguration,
+        slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(ZwlrOutputConfigurationV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            manager: slf.clone(),
+            serial: req.serial,
+            heads: Default::default(),
+            used: Cell::new(false),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn stop(&self, _req: Stop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.stopped.replace(true) {
+            return Ok(());
+        }
+        self.detach();
+        self.send_finished();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrOutputManagerV1Global,
+    ZwlrOutputManagerV1,
+    ZwlrOutputManagerV1Error
+);
+
+impl Global for ZwlrOutputManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        4
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_OUTPUT_MANAGER
+    }
+}
+
+simple_add_global!(ZwlrOutputManagerV1Global);
+
+object_base! {
+    self = ZwlrOutputManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputManagerV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrOutputManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputManagerV1Error, ClientError);
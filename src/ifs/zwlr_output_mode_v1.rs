@@ -0,0 +1,70 @@
+use {
+    crate::{
+        backend::Mode,
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_output_mode_v1::*, ZwlrOutputModeV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputModeV1 {
+    pub id: ZwlrOutputModeV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub mode: Mode,
+    pub released: Cell<bool>,
+}
+
+impl ZwlrOutputModeV1 {
+    pub fn send_size(&self) {
+        self.client.event(Size {
+            self_id: self.id,
+            width: self.mode.width,
+            height: self.mode.height,
+        });
+    }
+
+    pub fn send_refresh(&self) {
+        self.client.event(Refresh {
+            self_id: self.id,
+            refresh: self.mode.refresh_rate_millihz as _,
+        });
+    }
+
+    pub fn send_finished(&self) {
+        if self.released.get() {
+            return;
+        }
+        self.client.event(Finished { self_id: self.id });
+    }
+}
+
+impl ZwlrOutputModeV1RequestHandler for ZwlrOutputModeV1 {
+    type Error = ZwlrOutputModeV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputModeV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputModeV1 {}
+
+dedicated_add_obj!(ZwlrOutputModeV1, ZwlrOutputModeV1Id, output_modes);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputModeV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputModeV1Error, ClientError);
@@ -120,6 +120,8 @@ fn run_test(it_run: &ItRun, test: &'static dyn TestCase, cfg: Rc<TestConfig>) {
     let errors2 = errors.clone();
     let res = crate::compositor::start_compositor_for_test(Box::new(move |state| {
         let state = state.clone();
+        // Screenshots taken by the tests must not depend on the timing of the test.
+        state.animations.set_enabled(false);
         let server_addr = {
             let mut addr: c::sockaddr_un = uapi::pod_zeroed();
             addr.sun_family = c::AF_UNIX as _;
@@ -3,12 +3,13 @@ use {
         async_engine::SpawnedFuture,
         backend::{
             AxisSource, Backend, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
+            ConnectorKernelId, DrmDeviceId, GammaLut, InputDevice, InputDeviceAccelProfile,
             InputDeviceCapability, InputDeviceId, InputEvent, KeyState, Mode, MonitorInfo,
             ScrollAxis, TransformMatrix,
         },
         compositor::TestFuture,
         drm_feedback::DrmFeedback,
+        edid::HdrStaticMetadata,
         fixed::Fixed,
         gfx_api::GfxError,
         it::{
@@ -61,6 +62,8 @@ impl TestBackend {
             },
             events: Default::default(),
             feedback: Default::default(),
+            gamma_lut: Default::default(),
+            hdr_enabled: Default::default(),
         });
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
@@ -111,6 +114,14 @@ impl TestBackend {
             height_mm: 60,
             non_desktop: false,
             vrr_capable: false,
+            primaries: None,
+            hdr_metadata: Some(Box::new(HdrStaticMetadata {
+                eotf_traditional_sdr: true,
+                eotf_st2084: true,
+                static_metadata_type1: true,
+                max_luminance: Some(600.0),
+                ..Default::default()
+            })),
         };
         Self {
             state: state.clone(),
@@ -226,11 +237,15 @@ impl Backend for TestBackend {
     }
 }
 
+pub const TEST_GAMMA_LUT_SIZE: u32 = 4;
+
 pub struct TestConnector {
     pub id: ConnectorId,
     pub kernel_id: ConnectorKernelId,
     pub events: OnChange<ConnectorEvent>,
     pub feedback: CloneCell<Option<Rc<DrmFeedback>>>,
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub hdr_enabled: Cell<bool>,
 }
 
 impl Connector for TestConnector {
@@ -265,6 +280,20 @@ impl Connector for TestConnector {
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         self.feedback.get()
     }
+
+    fn gamma_lut_size(&self) -> Option<u32> {
+        Some(TEST_GAMMA_LUT_SIZE)
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+    }
+
+    fn set_hdr_enabled(&self, enabled: bool) {
+        if self.hdr_enabled.replace(enabled) != enabled {
+            self.events.send_event(ConnectorEvent::HdrChanged(enabled));
+        }
+    }
 }
 
 pub struct TestMouseClick {
@@ -1,9 +1,10 @@
 use {
     crate::{
-        backend::InputDeviceId,
+        backend::{ConnectorId, InputDeviceId},
+        client::ClientId,
         ifs::wl_seat::SeatId,
         it::test_error::{TestError, TestResult},
-        tree::OutputNode,
+        tree::{NodeId, OutputNode},
         utils::{copyhashmap::CopyHashMap, stack::Stack},
     },
     bincode::Options,
@@ -12,12 +13,15 @@ use {
         _private::{
             bincode_ops,
             ipc::{ClientMessage, Response, ServerMessage},
-            ConfigEntry, VERSION,
+            ConfigEntry, WireMode, VERSION,
         },
+        client::Client,
         input::{InputDevice, Seat},
         keyboard::{Keymap, ModifiedKeySym},
+        theme::animations::{Animation, Curve},
         video::{Connector, Transform},
-        Axis, Direction,
+        window::{Window, WindowActions, WindowMatch, WindowRule},
+        Axis, Direction, Layout, Workspace,
     },
     std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
@@ -121,6 +125,9 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::InterestReady { .. } => {}
         ServerMessage::Features { .. } => {}
         ServerMessage::SwitchEvent { .. } => {}
+        ServerMessage::NewWindow { .. } => {}
+        ServerMessage::WindowClosed { .. } => {}
+        ServerMessage::WindowTitleChanged { .. } => {}
     }
 }
 
@@ -185,9 +192,14 @@ impl TestConfig {
         Ok(SeatId::from_raw(seat.0 as _))
     }
 
-    pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
+    pub fn get_workspace(&self, name: &str) -> Result<Workspace, TestError> {
         let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
         get_response!(reply, GetWorkspace { workspace });
+        Ok(workspace)
+    }
+
+    pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
+        let workspace = self.get_workspace(name)?;
         self.send(ClientMessage::ShowWorkspace {
             seat: Seat(seat.raw() as _),
             workspace,
@@ -224,6 +236,13 @@ impl TestConfig {
         })
     }
 
+    pub fn set_layout(&self, seat: SeatId, layout: Layout) -> TestResult {
+        self.send(ClientMessage::SetLayout {
+            seat: Seat(seat.raw() as _),
+            layout,
+        })
+    }
+
     pub fn add_shortcut<T: Into<ModifiedKeySym>>(
         &self,
         seat: SeatId,
@@ -269,6 +288,144 @@ impl TestConfig {
         })
     }
 
+    pub fn add_window_rule(
+        &self,
+        criteria: WindowMatch,
+        actions: WindowActions,
+    ) -> Result<WindowRule, TestError> {
+        let reply = self.send_with_reply(ClientMessage::AddWindowRule { criteria, actions })?;
+        get_response!(reply, AddWindowRule { rule });
+        Ok(rule)
+    }
+
+    pub fn remove_window_rule(&self, rule: WindowRule) -> TestResult {
+        self.send(ClientMessage::RemoveWindowRule { rule })
+    }
+
+    pub fn get_windows(&self) -> Result<Vec<NodeId>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindows)?;
+        get_response!(reply, GetWindows { windows });
+        Ok(windows.into_iter().map(|w| NodeId(w.0 as _)).collect())
+    }
+
+    pub fn window_exists(&self, window: NodeId) -> Result<bool, TestError> {
+        let reply = self.send_with_reply(ClientMessage::WindowExists {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, WindowExists { exists });
+        Ok(exists)
+    }
+
+    pub fn get_window_title(&self, window: NodeId) -> Result<String, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowTitle {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, GetWindowTitle { title });
+        Ok(title)
+    }
+
+    pub fn get_window_app_id(&self, window: NodeId) -> Result<String, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowAppId {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, GetWindowAppId { app_id });
+        Ok(app_id)
+    }
+
+    pub fn get_window_client(&self, window: NodeId) -> Result<ClientId, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowClient {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, GetWindowClient { client });
+        Ok(ClientId::from_raw(client.0))
+    }
+
+    pub fn get_window_workspace(&self, window: NodeId) -> Result<Workspace, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowWorkspace {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, GetWindowWorkspace { workspace });
+        Ok(workspace)
+    }
+
+    pub fn get_window_floating(&self, window: NodeId) -> Result<bool, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowFloating {
+            window: Window(window.raw() as _),
+        })?;
+        get_response!(reply, GetWindowFloating { floating });
+        Ok(floating)
+    }
+
+    pub fn set_window_floating(&self, window: NodeId, floating: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowFloating {
+            window: Window(window.raw() as _),
+            floating,
+        })
+    }
+
+    pub fn close_window(&self, window: NodeId) -> TestResult {
+        self.send(ClientMessage::CloseWindow {
+            window: Window(window.raw() as _),
+        })
+    }
+
+    pub fn set_window_workspace(&self, window: NodeId, workspace: &str) -> TestResult {
+        let workspace = self.get_workspace(workspace)?;
+        self.send(ClientMessage::SetWindowWorkspace {
+            window: Window(window.raw() as _),
+            workspace,
+        })
+    }
+
+    pub fn focus_window(&self, seat: SeatId, window: NodeId) -> TestResult {
+        self.send(ClientMessage::FocusWindow {
+            seat: Seat(seat.raw() as _),
+            window: Window(window.raw() as _),
+        })
+    }
+
+    pub fn client_exists(&self, client: ClientId) -> Result<bool, TestError> {
+        let reply = self.send_with_reply(ClientMessage::ClientExists {
+            client: Client(client.raw()),
+        })?;
+        get_response!(reply, ClientExists { exists });
+        Ok(exists)
+    }
+
+    pub fn get_client_pid(&self, client: ClientId) -> Result<i32, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetClientPid {
+            client: Client(client.raw()),
+        })?;
+        get_response!(reply, GetClientPid { pid });
+        Ok(pid)
+    }
+
+    pub fn get_client_is_xwayland(&self, client: ClientId) -> Result<bool, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetClientIsXwayland {
+            client: Client(client.raw()),
+        })?;
+        get_response!(reply, GetClientIsXwayland { xwayland });
+        Ok(xwayland)
+    }
+
+    pub fn move_to_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::MoveToScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn show_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::ShowScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn toggle_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::ToggleScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
@@ -277,6 +434,51 @@ impl TestConfig {
         }
     }
 
+    pub fn create_virtual_output(
+        &self,
+        width: i32,
+        height: i32,
+        refresh_millihz: u32,
+    ) -> Result<ConnectorId, TestError> {
+        let reply = self.send_with_reply(ClientMessage::CreateVirtualOutput {
+            mode: WireMode {
+                width,
+                height,
+                refresh_millihz,
+            },
+        })?;
+        get_response!(reply, CreateVirtualOutput { connector });
+        Ok(ConnectorId::from_raw(connector.0 as _))
+    }
+
+    pub fn remove_virtual_output(&self, connector: ConnectorId) -> TestResult {
+        self.send(ClientMessage::RemoveVirtualOutput {
+            connector: Connector(connector.raw() as _),
+        })
+    }
+
+    pub fn set_mirror(&self, connector: ConnectorId, source: Option<ConnectorId>) -> TestResult {
+        self.send(ClientMessage::ConnectorSetMirror {
+            connector: Connector(connector.raw() as _),
+            source: source.map(|s| Connector(s.raw() as _)),
+        })
+    }
+
+    pub fn set_animations_enabled(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetAnimationsEnabled { enabled })
+    }
+
+    pub fn set_animation_duration(&self, animation: Animation, duration: Duration) -> TestResult {
+        self.send(ClientMessage::SetAnimationDuration {
+            animation,
+            duration,
+        })
+    }
+
+    pub fn set_animation_curve(&self, animation: Animation, curve: Curve) -> TestResult {
+        self.send(ClientMessage::SetAnimationCurve { animation, curve })
+    }
+
     pub fn set_scale(&self, output: &OutputNode, scale: f64) -> TestResult {
         self.send(ClientMessage::ConnectorSetScale {
             connector: Connector(output.global.connector.connector.id().raw() as _),
@@ -290,6 +492,59 @@ impl TestConfig {
             transform,
         })
     }
+
+    pub fn set_color_temperature(&self, output: &OutputNode, kelvin: u32) -> TestResult {
+        self.send(ClientMessage::ConnectorSetColorTemperature {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            kelvin,
+        })
+    }
+
+    pub fn set_brightness(&self, output: &OutputNode, brightness: f64) -> TestResult {
+        self.send(ClientMessage::ConnectorSetBrightness {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            brightness,
+        })
+    }
+
+    pub fn set_hdr_enabled(&self, output: &OutputNode, enabled: bool) -> TestResult {
+        self.send(ClientMessage::ConnectorSetHdrEnabled {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            enabled,
+        })
+    }
+
+    pub fn set_sdr_reference_white(&self, output: &OutputNode, nits: f64) -> TestResult {
+        self.send(ClientMessage::ConnectorSetSdrReferenceWhite {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            nits,
+        })
+    }
+
+    pub fn set_shortcuts_inhibit_allowed(&self, client: ClientId, allowed: bool) -> TestResult {
+        self.send(ClientMessage::SetClientShortcutsInhibitAllowed {
+            client: Client(client.raw()),
+            allowed,
+        })
+    }
+
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(
+        &self,
+        seat: SeatId,
+        key: T,
+    ) -> TestResult {
+        self.send(ClientMessage::SeatSetShortcutsInhibitEscape {
+            seat: Seat(seat.raw() as _),
+            escape: Some(key.into()),
+        })
+    }
+
+    pub fn unset_shortcuts_inhibit_escape(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatSetShortcutsInhibitEscape {
+            seat: Seat(seat.raw() as _),
+            escape: None,
+        })
+    }
 }
 
 impl Drop for TestConfig {
@@ -2,6 +2,10 @@ pub mod test_alpha_modifier;
 pub mod test_alpha_modifier_surface;
 pub mod test_buffer;
 pub mod test_callback;
+pub mod test_color_management_surface;
+pub mod test_color_manager;
+pub mod test_commit_timer;
+pub mod test_commit_timing_manager;
 pub mod test_compositor;
 pub mod test_content_type;
 pub mod test_content_type_manager;
@@ -20,12 +24,34 @@ pub mod test_dmabuf;
 pub mod test_dmabuf_feedback;
 pub mod test_ext_foreign_toplevel_handle;
 pub mod test_ext_foreign_toplevel_list;
+pub mod test_fifo;
+pub mod test_fifo_manager;
+pub mod test_foreign_toplevel_handle;
+pub mod test_foreign_toplevel_manager;
+pub mod test_gamma_control;
+pub mod test_gamma_control_manager;
+pub mod test_global_shortcuts;
+pub mod test_image_capture_source;
+pub mod test_image_copy_capture_frame;
+pub mod test_image_copy_capture_manager;
+pub mod test_image_copy_capture_session;
+pub mod test_image_description;
+pub mod test_image_description_creator_params;
 pub mod test_input_method;
 pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
 pub mod test_jay_compositor;
 pub mod test_keyboard;
+pub mod test_keyboard_shortcuts_inhibit_manager;
+pub mod test_keyboard_shortcuts_inhibitor;
+pub mod test_output;
+pub mod test_output_configuration;
+pub mod test_output_configuration_head;
+pub mod test_output_head;
+pub mod test_output_image_capture_source_manager;
+pub mod test_output_manager;
+pub mod test_output_mode;
 pub mod test_pointer;
 pub mod test_region;
 pub mod test_registry;
@@ -45,12 +71,24 @@ pub mod test_text_input;
 pub mod test_text_input_manager;
 pub mod test_toplevel_drag;
 pub mod test_toplevel_drag_manager;
+pub mod test_toplevel_icon;
+pub mod test_toplevel_icon_manager;
+pub mod test_toplevel_image_capture_source_manager;
 pub mod test_viewport;
 pub mod test_viewporter;
 pub mod test_virtual_keyboard;
 pub mod test_virtual_keyboard_manager;
+pub mod test_virtual_pointer;
+pub mod test_virtual_pointer_manager;
+pub mod test_workspace_group;
+pub mod test_workspace_handle;
+pub mod test_workspace_manager;
 pub mod test_xdg_activation;
 pub mod test_xdg_activation_token;
 pub mod test_xdg_base;
+pub mod test_xdg_exported;
+pub mod test_xdg_exporter;
+pub mod test_xdg_imported;
+pub mod test_xdg_importer;
 pub mod test_xdg_surface;
 pub mod test_xdg_toplevel;
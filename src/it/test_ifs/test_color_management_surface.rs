@@ -0,0 +1,53 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_image_description::TestImageDescription,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{wp_color_management_surface_v1::*, WpColorManagementSurfaceV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestColorManagementSurface {
+    pub id: WpColorManagementSurfaceV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestColorManagementSurface {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_image_description(
+        &self,
+        image_description: &TestImageDescription,
+        render_intent: u32,
+    ) -> Result<(), TestError> {
+        self.tran.send(SetImageDescription {
+            self_id: self.id,
+            image_description: image_description.id,
+            render_intent,
+        })
+    }
+
+    pub fn unset_image_description(&self) -> Result<(), TestError> {
+        self.tran.send(UnsetImageDescription { self_id: self.id })
+    }
+}
+
+impl Drop for TestColorManagementSurface {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestColorManagementSurface, WpColorManagementSurfaceV1;
+}
+
+impl TestObject for TestColorManagementSurface {}
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::{
+                test_color_management_surface::TestColorManagementSurface,
+                test_image_description_creator_params::TestImageDescriptionCreatorParams,
+                test_surface::TestSurface,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wp_color_manager_v1::*, WpColorManagerV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestColorManager {
+    pub id: WpColorManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub intents: RefCell<Vec<u32>>,
+    pub features: RefCell<Vec<u32>>,
+    pub tfs: RefCell<Vec<u32>>,
+    pub primaries: RefCell<Vec<u32>>,
+    pub done: Cell<bool>,
+}
+
+impl TestColorManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            intents: Default::default(),
+            features: Default::default(),
+            tfs: Default::default(),
+            primaries: Default::default(),
+            done: Default::default(),
+        }
+    }
+
+    pub fn get_surface(&self, surface: &TestSurface) -> TestResult<Rc<TestColorManagementSurface>> {
+        let obj = Rc::new(TestColorManagementSurface {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(GetSurface {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+        })?;
+        Ok(obj)
+    }
+
+    pub fn create_parametric_creator(&self) -> TestResult<Rc<TestImageDescriptionCreatorParams>> {
+        let obj = Rc::new(TestImageDescriptionCreatorParams {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(CreateParametricCreator {
+            self_id: self.id,
+            obj: obj.id,
+        })?;
+        Ok(obj)
+    }
+
+    fn handle_supported_intent(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = SupportedIntent::parse_full(parser)?;
+        self.intents.borrow_mut().push(ev.render_intent);
+        Ok(())
+    }
+
+    fn handle_supported_feature(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = SupportedFeature::parse_full(parser)?;
+        self.features.borrow_mut().push(ev.feature);
+        Ok(())
+    }
+
+    fn handle_supported_tf_named(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = SupportedTfNamed::parse_full(parser)?;
+        self.tfs.borrow_mut().push(ev.tf);
+        Ok(())
+    }
+
+    fn handle_supported_primaries_named(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = SupportedPrimariesNamed::parse_full(parser)?;
+        self.primaries.borrow_mut().push(ev.primaries);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestColorManager, WpColorManagerV1;
+
+    SUPPORTED_INTENT => handle_supported_intent,
+    SUPPORTED_FEATURE => handle_supported_feature,
+    SUPPORTED_TF_NAMED => handle_supported_tf_named,
+    SUPPORTED_PRIMARIES_NAMED => handle_supported_primaries_named,
+    DONE => handle_done,
+}
+
+impl TestObject for TestColorManager {}
@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{wp_commit_timer_v1::*, WpCommitTimerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestCommitTimer {
+    pub id: WpCommitTimerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestCommitTimer {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_timestamp(&self, nsec: u64) -> Result<(), TestError> {
+        let sec = nsec / 1_000_000_000;
+        self.tran.send(SetTimestamp {
+            self_id: self.id,
+            tv_sec_hi: (sec >> 32) as u32,
+            tv_sec_lo: sec as u32,
+            tv_nsec: (nsec % 1_000_000_000) as u32,
+        })
+    }
+}
+
+impl Drop for TestCommitTimer {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestCommitTimer, WpCommitTimerV1;
+}
+
+impl TestObject for TestCommitTimer {}
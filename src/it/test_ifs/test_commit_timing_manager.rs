@@ -0,0 +1,47 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{test_commit_timer::TestCommitTimer, test_surface::TestSurface},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{wp_commit_timing_manager_v1::*, WpCommitTimingManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestCommitTimingManager {
+    pub id: WpCommitTimingManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestCommitTimingManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn get_timer(&self, surface: &TestSurface) -> TestResult<Rc<TestCommitTimer>> {
+        let obj = Rc::new(TestCommitTimer {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(GetTimer {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestCommitTimingManager, WpCommitTimingManagerV1;
+}
+
+impl TestObject for TestCommitTimingManager {}
@@ -0,0 +1,42 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{wp_fifo_v1::*, WpFifoV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestFifo {
+    pub id: WpFifoV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestFifo {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_barrier(&self) -> Result<(), TestError> {
+        self.tran.send(SetBarrier { self_id: self.id })
+    }
+
+    pub fn wait_barrier(&self) -> Result<(), TestError> {
+        self.tran.send(WaitBarrier { self_id: self.id })
+    }
+}
+
+impl Drop for TestFifo {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestFifo, WpFifoV1;
+}
+
+impl TestObject for TestFifo {}
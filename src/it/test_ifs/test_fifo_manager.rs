@@ -0,0 +1,47 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{test_fifo::TestFifo, test_surface::TestSurface},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{wp_fifo_manager_v1::*, WpFifoManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestFifoManager {
+    pub id: WpFifoManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestFifoManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn get_fifo(&self, surface: &TestSurface) -> TestResult<Rc<TestFifo>> {
+        let obj = Rc::new(TestFifo {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(GetFifo {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestFifoManager, WpFifoManagerV1;
+}
+
+impl TestObject for TestFifoManager {}
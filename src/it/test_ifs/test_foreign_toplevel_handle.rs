@@ -0,0 +1,121 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{test_output::TestOutput, test_seat::TestSeat},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{zwlr_foreign_toplevel_handle_v1::*, WlOutputId, ZwlrForeignToplevelHandleV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestForeignToplevelHandle {
+    pub id: ZwlrForeignToplevelHandleV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub closed: Cell<bool>,
+    pub title: RefCell<Option<String>>,
+    pub state: RefCell<Vec<u32>>,
+    pub outputs: CopyHashMap<WlOutputId, ()>,
+}
+
+impl TestForeignToplevelHandle {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_minimized(&self) -> Result<(), TestError> {
+        self.tran.send(SetMinimized { self_id: self.id })
+    }
+
+    pub fn activate(&self, seat: &TestSeat) -> Result<(), TestError> {
+        self.tran.send(Activate {
+            self_id: self.id,
+            seat: seat.id,
+        })
+    }
+
+    pub fn close(&self) -> Result<(), TestError> {
+        self.tran.send(Close { self_id: self.id })
+    }
+
+    pub fn set_fullscreen(&self, output: Option<&TestOutput>) -> Result<(), TestError> {
+        self.tran.send(SetFullscreen {
+            self_id: self.id,
+            output: output.map(|o| o.id).unwrap_or(WlOutputId::NONE),
+        })
+    }
+
+    pub fn unset_fullscreen(&self) -> Result<(), TestError> {
+        self.tran.send(UnsetFullscreen { self_id: self.id })
+    }
+
+    pub fn has_state(&self, state: u32) -> bool {
+        self.state.borrow().contains(&state)
+    }
+
+    fn handle_title(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Title::parse_full(parser)?;
+        *self.title.borrow_mut() = Some(ev.title.to_string());
+        Ok(())
+    }
+
+    fn handle_app_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = AppId::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_output_enter(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = OutputEnter::parse_full(parser)?;
+        self.outputs.set(ev.output, ());
+        Ok(())
+    }
+
+    fn handle_output_leave(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = OutputLeave::parse_full(parser)?;
+        self.outputs.remove(&ev.output);
+        Ok(())
+    }
+
+    fn handle_state(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = State::parse_full(parser)?;
+        *self.state.borrow_mut() = ev.state.to_vec();
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_closed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Closed::parse_full(parser)?;
+        self.closed.set(true);
+        self.destroy()?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestForeignToplevelHandle, ZwlrForeignToplevelHandleV1;
+
+    TITLE => handle_title,
+    APP_ID => handle_app_id,
+    OUTPUT_ENTER => handle_output_enter,
+    OUTPUT_LEAVE => handle_output_leave,
+    STATE => handle_state,
+    DONE => handle_done,
+    CLOSED => handle_closed,
+}
+
+impl TestObject for TestForeignToplevelHandle {}
@@ -0,0 +1,69 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::test_foreign_toplevel_handle::TestForeignToplevelHandle,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_foreign_toplevel_manager_v1::*, ZwlrForeignToplevelManagerV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestForeignToplevelManager {
+    pub id: ZwlrForeignToplevelManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub toplevels: RefCell<Vec<Rc<TestForeignToplevelHandle>>>,
+    pub finished: Cell<bool>,
+}
+
+impl TestForeignToplevelManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            toplevels: RefCell::new(vec![]),
+            finished: Cell::new(false),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn stop(&self) -> Result<(), TestError> {
+        self.tran.send(Stop { self_id: self.id })
+    }
+
+    fn handle_toplevel(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Toplevel::parse_full(parser)?;
+        let tl = Rc::new(TestForeignToplevelHandle {
+            id: ev.toplevel,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            closed: Cell::new(false),
+            title: Default::default(),
+            state: Default::default(),
+            outputs: Default::default(),
+        });
+        self.tran.add_obj(tl.clone())?;
+        self.toplevels.borrow_mut().push(tl);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        self.finished.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestForeignToplevelManager, ZwlrForeignToplevelManagerV1;
+
+    TOPLEVEL => handle_toplevel,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestForeignToplevelManager {}
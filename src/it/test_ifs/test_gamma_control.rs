@@ -0,0 +1,65 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, io::Write, rc::Rc},
+    uapi::c,
+};
+
+pub struct TestGammaControl {
+    pub id: ZwlrGammaControlV1Id,
+    pub tran: Rc<TestTransport>,
+    pub size: Cell<Option<u32>>,
+    pub failed: Cell<bool>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestGammaControl {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_gamma(&self, ramps: &[u16]) -> Result<(), TestError> {
+        let mut memfd = uapi::memfd_create("gamma", c::MFD_CLOEXEC).unwrap();
+        memfd.write_all(uapi::as_bytes(ramps)).unwrap();
+        self.tran.send(SetGamma {
+            self_id: self.id,
+            fd: Rc::new(memfd),
+        })
+    }
+
+    fn handle_gamma_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = GammaSize::parse_full(parser)?;
+        self.size.set(Some(ev.size));
+        Ok(())
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Failed::parse_full(parser)?;
+        self.failed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestGammaControl {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestGammaControl, ZwlrGammaControlV1;
+
+    GAMMA_SIZE => handle_gamma_size,
+    FAILED => handle_failed,
+}
+
+impl TestObject for TestGammaControl {}
@@ -0,0 +1,75 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub activated: TEEH<u32>,
+    pub deactivated: TEEH<u32>,
+}
+
+impl TestGlobalShortcuts {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn bind(&self, id: u32, mods: Modifiers, keysym: KeySym) -> Result<(), TestError> {
+        self.tran.send(Bind {
+            self_id: self.id,
+            id,
+            mods: mods.0,
+            keysym: keysym.0,
+        })
+    }
+
+    pub fn unbind(&self, id: u32) -> Result<(), TestError> {
+        self.tran.send(Unbind {
+            self_id: self.id,
+            id,
+        })
+    }
+
+    fn handle_activated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Activated::parse_full(parser)?;
+        self.activated.push(ev.id);
+        Ok(())
+    }
+
+    fn handle_deactivated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Deactivated::parse_full(parser)?;
+        self.deactivated.push(ev.id);
+        Ok(())
+    }
+}
+
+impl Drop for TestGlobalShortcuts {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => handle_activated,
+    DEACTIVATED => handle_deactivated,
+}
+
+impl TestObject for TestGlobalShortcuts {}
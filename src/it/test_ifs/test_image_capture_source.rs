@@ -0,0 +1,34 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{ext_image_capture_source_v1::*, ExtImageCaptureSourceV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestImageCaptureSource {
+    pub id: ExtImageCaptureSourceV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestImageCaptureSource {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TestImageCaptureSource {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestImageCaptureSource, ExtImageCaptureSourceV1;
+}
+
+impl TestObject for TestImageCaptureSource {}
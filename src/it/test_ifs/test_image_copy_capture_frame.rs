@@ -0,0 +1,84 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_buffer::TestBuffer, test_object::TestObject,
+            test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{ext_image_copy_capture_frame_v1::*, ExtImageCopyCaptureFrameV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestImageCopyCaptureFrame {
+    pub id: ExtImageCopyCaptureFrameV1Id,
+    pub tran: Rc<TestTransport>,
+    pub ready: Cell<bool>,
+    pub failed: Cell<Option<u32>>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestImageCopyCaptureFrame {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn attach_buffer(&self, buffer: &TestBuffer) -> Result<(), TestError> {
+        self.tran.send(AttachBuffer {
+            self_id: self.id,
+            buffer: buffer.id,
+        })
+    }
+
+    pub fn capture(&self) -> Result<(), TestError> {
+        self.tran.send(Capture { self_id: self.id })
+    }
+
+    fn handle_transform(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Transform::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_damage(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Damage::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_presentation_time(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = PresentationTime::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_ready(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Ready::parse_full(parser)?;
+        self.ready.set(true);
+        Ok(())
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Failed::parse_full(parser)?;
+        self.failed.set(Some(ev.reason));
+        Ok(())
+    }
+}
+
+impl Drop for TestImageCopyCaptureFrame {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestImageCopyCaptureFrame, ExtImageCopyCaptureFrameV1;
+
+    TRANSFORM => handle_transform,
+    DAMAGE => handle_damage,
+    PRESENTATION_TIME => handle_presentation_time,
+    READY => handle_ready,
+    FAILED => handle_failed,
+}
+
+impl TestObject for TestImageCopyCaptureFrame {}
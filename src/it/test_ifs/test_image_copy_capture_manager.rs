@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{
+                test_image_capture_source::TestImageCaptureSource,
+                test_image_copy_capture_session::TestImageCopyCaptureSession,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{ext_image_copy_capture_manager_v1::*, ExtImageCopyCaptureManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestImageCopyCaptureManager {
+    pub id: ExtImageCopyCaptureManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestImageCopyCaptureManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn create_session(
+        &self,
+        source: &TestImageCaptureSource,
+        options: u32,
+    ) -> Result<Rc<TestImageCopyCaptureSession>, TestError> {
+        let obj = Rc::new(TestImageCopyCaptureSession {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            buffer_size: Cell::new(None),
+            shm_formats: Default::default(),
+            done: Cell::new(false),
+            stopped: Cell::new(false),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateSession {
+            self_id: self.id,
+            session: obj.id,
+            source: source.id,
+            options,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestImageCopyCaptureManager, ExtImageCopyCaptureManagerV1;
+}
+
+impl TestObject for TestImageCopyCaptureManager {}
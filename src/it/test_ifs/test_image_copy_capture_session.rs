@@ -0,0 +1,100 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::test_image_copy_capture_frame::TestImageCopyCaptureFrame,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{ext_image_copy_capture_session_v1::*, ExtImageCopyCaptureSessionV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestImageCopyCaptureSession {
+    pub id: ExtImageCopyCaptureSessionV1Id,
+    pub tran: Rc<TestTransport>,
+    pub buffer_size: Cell<Option<(u32, u32)>>,
+    pub shm_formats: CopyHashMap<u32, ()>,
+    pub done: Cell<bool>,
+    pub stopped: Cell<bool>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestImageCopyCaptureSession {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_frame(&self) -> Result<Rc<TestImageCopyCaptureFrame>, TestError> {
+        let obj = Rc::new(TestImageCopyCaptureFrame {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            ready: Cell::new(false),
+            failed: Cell::new(None),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateFrame {
+            self_id: self.id,
+            frame: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    fn handle_buffer_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = BufferSize::parse_full(parser)?;
+        self.buffer_size.set(Some((ev.width, ev.height)));
+        Ok(())
+    }
+
+    fn handle_shm_format(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = ShmFormat::parse_full(parser)?;
+        self.shm_formats.set(ev.format, ());
+        Ok(())
+    }
+
+    fn handle_dmabuf_device(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = DmabufDevice::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_dmabuf_format(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = DmabufFormat::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(true);
+        Ok(())
+    }
+
+    fn handle_stopped(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Stopped::parse_full(parser)?;
+        self.stopped.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestImageCopyCaptureSession {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestImageCopyCaptureSession, ExtImageCopyCaptureSessionV1;
+
+    BUFFER_SIZE => handle_buffer_size,
+    SHM_FORMAT => handle_shm_format,
+    DMABUF_DEVICE => handle_dmabuf_device,
+    DMABUF_FORMAT => handle_dmabuf_format,
+    DONE => handle_done,
+    STOPPED => handle_stopped,
+}
+
+impl TestObject for TestImageCopyCaptureSession {}
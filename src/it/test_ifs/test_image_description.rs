@@ -0,0 +1,67 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wp_image_description_v1::*, WpImageDescriptionV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestImageDescription {
+    pub id: WpImageDescriptionV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub identity: Cell<Option<u32>>,
+    pub failed: RefCell<Option<(u32, String)>>,
+}
+
+impl TestImageDescription {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn identity(&self) -> TestResult<u32> {
+        match self.identity.get() {
+            Some(i) => Ok(i),
+            _ => bail!("Image description is not ready"),
+        }
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Failed::parse_full(parser)?;
+        *self.failed.borrow_mut() = Some((ev.cause, ev.msg.to_string()));
+        Ok(())
+    }
+
+    fn handle_ready(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Ready::parse_full(parser)?;
+        self.identity.set(Some(ev.identity));
+        Ok(())
+    }
+}
+
+test_object! {
+    TestImageDescription, WpImageDescriptionV1;
+
+    FAILED => handle_failed,
+    READY => handle_ready,
+}
+
+impl TestObject for TestImageDescription {}
+
+impl Drop for TestImageDescription {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
@@ -0,0 +1,58 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::test_image_description::TestImageDescription,
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{wp_image_description_creator_params_v1::*, WpImageDescriptionCreatorParamsV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestImageDescriptionCreatorParams {
+    pub id: WpImageDescriptionCreatorParamsV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestImageDescriptionCreatorParams {
+    pub fn set_tf_named(&self, tf: u32) -> Result<(), TestError> {
+        self.tran.send(SetTfNamed {
+            self_id: self.id,
+            tf,
+        })
+    }
+
+    pub fn set_primaries_named(&self, primaries: u32) -> Result<(), TestError> {
+        self.tran.send(SetPrimariesNamed {
+            self_id: self.id,
+            primaries,
+        })
+    }
+
+    pub fn create(&self) -> TestResult<Rc<TestImageDescription>> {
+        let obj = Rc::new(TestImageDescription {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            identity: Cell::new(None),
+            failed: RefCell::new(None),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(Create {
+            self_id: self.id,
+            image_description: obj.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestImageDescriptionCreatorParams, WpImageDescriptionCreatorParamsV1;
+}
+
+impl TestObject for TestImageDescriptionCreatorParams {}
@@ -3,7 +3,10 @@ use {
         client::ClientId,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_screenshot::TestJayScreenshot,
+            test_ifs::{
+                test_global_shortcuts::TestGlobalShortcuts, test_output::TestOutput,
+                test_screenshot::TestJayScreenshot,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
@@ -59,6 +62,28 @@ impl TestJayCompositor {
             id: js.id,
             include_cursor: include_cursor as _,
         })?;
+        self.screenshot_result(js).await
+    }
+
+    pub async fn take_output_screenshot(
+        &self,
+        output: &TestOutput,
+        include_cursor: bool,
+    ) -> Result<Dmabuf, TestError> {
+        let js = Rc::new(TestJayScreenshot {
+            id: self.tran.id(),
+            result: Cell::new(None),
+        });
+        self.tran.send(TakeOutputScreenshot {
+            self_id: self.id,
+            id: js.id,
+            output: output.id,
+            include_cursor: include_cursor as _,
+        })?;
+        self.screenshot_result(js).await
+    }
+
+    async fn screenshot_result(&self, js: Rc<TestJayScreenshot>) -> Result<Dmabuf, TestError> {
         self.tran.add_obj(js.clone())?;
         self.tran.sync().await;
         match js.result.take() {
@@ -68,6 +93,22 @@ impl TestJayCompositor {
         }
     }
 
+    pub fn create_global_shortcuts(&self) -> Result<Rc<TestGlobalShortcuts>, TestError> {
+        let obj = Rc::new(TestGlobalShortcuts {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            activated: Default::default(),
+            deactivated: Default::default(),
+        });
+        self.tran.send(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
     fn handle_client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = jay_compositor::ClientId::parse_full(parser)?;
         self.client_id.set(Some(ClientId::from_raw(ev.client_id)));
@@ -0,0 +1,37 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wl_output::*, WlOutputId},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestOutput {
+    pub id: WlOutputId,
+    pub _tran: Rc<TestTransport>,
+}
+
+impl TestOutput {
+    fn handle_geometry(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Geometry::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_mode(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Mode::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutput, WlOutput;
+
+    GEOMETRY => handle_geometry,
+    MODE => handle_mode,
+}
+
+impl TestObject for TestOutput {}
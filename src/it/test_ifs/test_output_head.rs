@@ -0,0 +1,145 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::test_output_mode::TestOutputMode,
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_output_head_v1::*, ZwlrOutputHeadV1Id, ZwlrOutputModeV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestOutputHead {
+    pub id: ZwlrOutputHeadV1Id,
+    pub tran: Rc<TestTransport>,
+    pub name: RefCell<String>,
+    pub enabled: Cell<bool>,
+    pub position: Cell<(i32, i32)>,
+    pub transform: Cell<i32>,
+    pub scale: Cell<f64>,
+    pub modes: RefCell<Vec<Rc<TestOutputMode>>>,
+    pub current_mode: Cell<Option<ZwlrOutputModeV1Id>>,
+    pub finished: Cell<bool>,
+}
+
+impl TestOutputHead {
+    #[allow(dead_code)]
+    pub fn release(&self) -> TestResult {
+        self.tran.send(Release { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Name::parse_full(parser)?;
+        *self.name.borrow_mut() = ev.name.to_string();
+        Ok(())
+    }
+
+    fn handle_description(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Description::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_physical_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = PhysicalSize::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_mode(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Mode::parse_full(parser)?;
+        let mode = Rc::new(TestOutputMode {
+            id: ev.mode,
+            _tran: self.tran.clone(),
+            size: Cell::new((0, 0)),
+            refresh: Cell::new(0),
+            finished: Cell::new(false),
+        });
+        self.tran.add_obj(mode.clone())?;
+        self.modes.borrow_mut().push(mode);
+        Ok(())
+    }
+
+    fn handle_enabled(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Enabled::parse_full(parser)?;
+        self.enabled.set(ev.enabled != 0);
+        Ok(())
+    }
+
+    fn handle_current_mode(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = CurrentMode::parse_full(parser)?;
+        self.current_mode.set(Some(ev.mode));
+        Ok(())
+    }
+
+    fn handle_position(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Position::parse_full(parser)?;
+        self.position.set((ev.x, ev.y));
+        Ok(())
+    }
+
+    fn handle_transform(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Transform::parse_full(parser)?;
+        self.transform.set(ev.transform);
+        Ok(())
+    }
+
+    fn handle_scale(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Scale::parse_full(parser)?;
+        self.scale.set(ev.scale.to_f64());
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        self.finished.set(true);
+        Ok(())
+    }
+
+    fn handle_make(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Make::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_model(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Model::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_serial_number(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = SerialNumber::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_adaptive_sync(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = AdaptiveSync::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputHead, ZwlrOutputHeadV1;
+
+    NAME => handle_name,
+    DESCRIPTION => handle_description,
+    PHYSICAL_SIZE => handle_physical_size,
+    MODE => handle_mode,
+    ENABLED => handle_enabled,
+    CURRENT_MODE => handle_current_mode,
+    POSITION => handle_position,
+    TRANSFORM => handle_transform,
+    SCALE => handle_scale,
+    FINISHED => handle_finished,
+    MAKE => handle_make,
+    MODEL => handle_model,
+    SERIAL_NUMBER => handle_serial_number,
+    ADAPTIVE_SYNC => handle_adaptive_sync,
+}
+
+impl TestObject for TestOutputHead {}
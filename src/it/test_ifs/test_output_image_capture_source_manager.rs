@@ -0,0 +1,54 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{
+                test_image_capture_source::TestImageCaptureSource, test_output::TestOutput,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{
+            ext_output_image_capture_source_manager_v1::*, ExtOutputImageCaptureSourceManagerV1Id,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestOutputImageCaptureSourceManager {
+    pub id: ExtOutputImageCaptureSourceManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestOutputImageCaptureSourceManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn create_source(
+        &self,
+        output: &TestOutput,
+    ) -> Result<Rc<TestImageCaptureSource>, TestError> {
+        let obj = Rc::new(TestImageCaptureSource {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateSource {
+            self_id: self.id,
+            source: obj.id,
+            output: output.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestOutputImageCaptureSourceManager, ExtOutputImageCaptureSourceManagerV1;
+}
+
+impl TestObject for TestOutputImageCaptureSourceManager {}
@@ -5,28 +5,42 @@ use {
         it::{
             test_error::TestError,
             test_ifs::{
-                test_alpha_modifier::TestAlphaModifier, test_compositor::TestCompositor,
-                test_content_type_manager::TestContentTypeManager,
+                test_alpha_modifier::TestAlphaModifier, test_color_manager::TestColorManager,
+                test_commit_timing_manager::TestCommitTimingManager,
+                test_compositor::TestCompositor, test_content_type_manager::TestContentTypeManager,
                 test_cursor_shape_manager::TestCursorShapeManager,
                 test_data_control_manager::TestDataControlManager,
                 test_data_device_manager::TestDataDeviceManager, test_dmabuf::TestDmabuf,
                 test_ext_foreign_toplevel_list::TestExtForeignToplevelList,
+                test_fifo_manager::TestFifoManager,
+                test_foreign_toplevel_manager::TestForeignToplevelManager,
+                test_gamma_control_manager::TestGammaControlManager,
+                test_image_copy_capture_manager::TestImageCopyCaptureManager,
                 test_input_method_manager::TestInputMethodManager,
-                test_jay_compositor::TestJayCompositor, test_shm::TestShm,
+                test_jay_compositor::TestJayCompositor,
+                test_keyboard_shortcuts_inhibit_manager::TestKeyboardShortcutsInhibitManager,
+                test_output::TestOutput,
+                test_output_image_capture_source_manager::TestOutputImageCaptureSourceManager,
+                test_output_manager::TestOutputManager, test_shm::TestShm,
                 test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_syncobj_manager::TestSyncobjManager,
                 test_text_input_manager::TestTextInputManager,
                 test_toplevel_drag_manager::TestToplevelDragManager,
+                test_toplevel_icon_manager::TestToplevelIconManager,
+                test_toplevel_image_capture_source_manager::TestToplevelImageCaptureSourceManager,
                 test_viewporter::TestViewporter,
                 test_virtual_keyboard_manager::TestVirtualKeyboardManager,
+                test_virtual_pointer_manager::TestVirtualPointerManager,
+                test_workspace_manager::TestWorkspaceManager,
                 test_xdg_activation::TestXdgActivation, test_xdg_base::TestXdgWmBase,
+                test_xdg_exporter::TestXdgExporter, test_xdg_importer::TestXdgImporter,
             },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
         },
         utils::{buffd::MsgParser, clonecell::CloneCell, copyhashmap::CopyHashMap},
-        wire::{wl_registry::*, WlRegistryId, WlSeat},
+        wire::{wl_registry::*, WlOutput, WlRegistryId, WlSeat},
     },
     std::rc::Rc,
 };
@@ -58,6 +72,21 @@ pub struct TestRegistrySingletons {
     pub zwp_virtual_keyboard_manager_v1: u32,
     pub zwp_input_method_manager_v2: u32,
     pub zwp_text_input_manager_v3: u32,
+    pub zwlr_output_manager_v1: u32,
+    pub zwlr_gamma_control_manager_v1: u32,
+    pub ext_output_image_capture_source_manager_v1: u32,
+    pub ext_foreign_toplevel_image_capture_source_manager_v1: u32,
+    pub ext_image_copy_capture_manager_v1: u32,
+    pub zwlr_foreign_toplevel_manager_v1: u32,
+    pub zwp_keyboard_shortcuts_inhibit_manager_v1: u32,
+    pub ext_workspace_manager_v1: u32,
+    pub zwlr_virtual_pointer_manager_v1: u32,
+    pub zxdg_exporter_v2: u32,
+    pub zxdg_importer_v2: u32,
+    pub wp_color_manager_v1: u32,
+    pub wp_fifo_manager_v1: u32,
+    pub wp_commit_timing_manager_v1: u32,
+    pub xdg_toplevel_icon_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -85,6 +114,23 @@ pub struct TestRegistry {
     pub virtual_keyboard_manager: CloneCell<Option<Rc<TestVirtualKeyboardManager>>>,
     pub input_method_manager: CloneCell<Option<Rc<TestInputMethodManager>>>,
     pub text_input_manager: CloneCell<Option<Rc<TestTextInputManager>>>,
+    pub output_manager: CloneCell<Option<Rc<TestOutputManager>>>,
+    pub gamma_control_manager: CloneCell<Option<Rc<TestGammaControlManager>>>,
+    pub output_image_capture_source_manager:
+        CloneCell<Option<Rc<TestOutputImageCaptureSourceManager>>>,
+    pub toplevel_image_capture_source_manager:
+        CloneCell<Option<Rc<TestToplevelImageCaptureSourceManager>>>,
+    pub image_copy_capture_manager: CloneCell<Option<Rc<TestImageCopyCaptureManager>>>,
+    pub foreign_toplevel_manager: CloneCell<Option<Rc<TestForeignToplevelManager>>>,
+    pub shortcuts_inhibit_manager: CloneCell<Option<Rc<TestKeyboardShortcutsInhibitManager>>>,
+    pub workspace_manager: CloneCell<Option<Rc<TestWorkspaceManager>>>,
+    pub virtual_pointer_manager: CloneCell<Option<Rc<TestVirtualPointerManager>>>,
+    pub xdg_exporter: CloneCell<Option<Rc<TestXdgExporter>>>,
+    pub xdg_importer: CloneCell<Option<Rc<TestXdgImporter>>>,
+    pub color_manager: CloneCell<Option<Rc<TestColorManager>>>,
+    pub fifo_manager: CloneCell<Option<Rc<TestFifoManager>>>,
+    pub commit_timing_manager: CloneCell<Option<Rc<TestCommitTimingManager>>>,
+    pub toplevel_icon_manager: CloneCell<Option<Rc<TestToplevelIconManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -156,6 +202,21 @@ impl TestRegistry {
             zwp_virtual_keyboard_manager_v1,
             zwp_input_method_manager_v2,
             zwp_text_input_manager_v3,
+            zwlr_output_manager_v1,
+            zwlr_gamma_control_manager_v1,
+            ext_output_image_capture_source_manager_v1,
+            ext_foreign_toplevel_image_capture_source_manager_v1,
+            ext_image_copy_capture_manager_v1,
+            zwlr_foreign_toplevel_manager_v1,
+            zwp_keyboard_shortcuts_inhibit_manager_v1,
+            ext_workspace_manager_v1,
+            zwlr_virtual_pointer_manager_v1,
+            zxdg_exporter_v2,
+            zxdg_importer_v2,
+            wp_color_manager_v1,
+            wp_fifo_manager_v1,
+            wp_commit_timing_manager_v1,
+            xdg_toplevel_icon_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -165,7 +226,7 @@ impl TestRegistry {
         get_jay_compositor,
         jay_compositor,
         jay_compositor,
-        1,
+        9,
         TestJayCompositor
     );
     create_singleton!(get_compositor, compositor, wl_compositor, 6, TestCompositor);
@@ -271,6 +332,130 @@ impl TestRegistry {
         1,
         TestTextInputManager
     );
+    create_singleton!(
+        get_output_manager,
+        output_manager,
+        zwlr_output_manager_v1,
+        4,
+        TestOutputManager
+    );
+    create_singleton!(
+        get_gamma_control_manager,
+        gamma_control_manager,
+        zwlr_gamma_control_manager_v1,
+        1,
+        TestGammaControlManager
+    );
+    create_singleton!(
+        get_output_image_capture_source_manager,
+        output_image_capture_source_manager,
+        ext_output_image_capture_source_manager_v1,
+        1,
+        TestOutputImageCaptureSourceManager
+    );
+    create_singleton!(
+        get_toplevel_image_capture_source_manager,
+        toplevel_image_capture_source_manager,
+        ext_foreign_toplevel_image_capture_source_manager_v1,
+        1,
+        TestToplevelImageCaptureSourceManager
+    );
+    create_singleton!(
+        get_image_copy_capture_manager,
+        image_copy_capture_manager,
+        ext_image_copy_capture_manager_v1,
+        1,
+        TestImageCopyCaptureManager
+    );
+    create_singleton!(
+        get_foreign_toplevel_manager,
+        foreign_toplevel_manager,
+        zwlr_foreign_toplevel_manager_v1,
+        2,
+        TestForeignToplevelManager
+    );
+    create_singleton!(
+        get_shortcuts_inhibit_manager,
+        shortcuts_inhibit_manager,
+        zwp_keyboard_shortcuts_inhibit_manager_v1,
+        1,
+        TestKeyboardShortcutsInhibitManager
+    );
+    create_singleton!(
+        get_workspace_manager,
+        workspace_manager,
+        ext_workspace_manager_v1,
+        1,
+        TestWorkspaceManager
+    );
+    create_singleton!(
+        get_virtual_pointer_manager,
+        virtual_pointer_manager,
+        zwlr_virtual_pointer_manager_v1,
+        2,
+        TestVirtualPointerManager
+    );
+    create_singleton!(
+        get_xdg_exporter,
+        xdg_exporter,
+        zxdg_exporter_v2,
+        1,
+        TestXdgExporter
+    );
+    create_singleton!(
+        get_xdg_importer,
+        xdg_importer,
+        zxdg_importer_v2,
+        1,
+        TestXdgImporter
+    );
+    create_singleton!(
+        get_color_manager,
+        color_manager,
+        wp_color_manager_v1,
+        1,
+        TestColorManager
+    );
+    create_singleton!(
+        get_fifo_manager,
+        fifo_manager,
+        wp_fifo_manager_v1,
+        1,
+        TestFifoManager
+    );
+    create_singleton!(
+        get_commit_timing_manager,
+        commit_timing_manager,
+        wp_commit_timing_manager_v1,
+        1,
+        TestCommitTimingManager
+    );
+    create_singleton!(
+        get_toplevel_icon_manager,
+        toplevel_icon_manager,
+        xdg_toplevel_icon_manager_v1,
+        1,
+        TestToplevelIconManager
+    );
+
+    pub async fn get_output(&self) -> Result<Rc<TestOutput>, TestError> {
+        self.tran.sync().await;
+        let name = self
+            .globals
+            .lock()
+            .values()
+            .find(|g| g.interface == WlOutput.name())
+            .map(|g| g.name);
+        let Some(name) = name else {
+            bail!("Compositor did not send an output global");
+        };
+        let output = Rc::new(TestOutput {
+            id: self.tran.id(),
+            _tran: self.tran.clone(),
+        });
+        self.bind(&output, name, 1)?;
+        Ok(output)
+    }
 
     pub fn bind<O: TestObject>(
         &self,
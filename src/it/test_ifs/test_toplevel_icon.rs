@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_buffer::TestBuffer, test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{xdg_toplevel_icon_v1::*, XdgToplevelIconV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestToplevelIcon {
+    pub id: XdgToplevelIconV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestToplevelIcon {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_name(&self, name: &str) -> Result<(), TestError> {
+        self.tran.send(SetName {
+            self_id: self.id,
+            icon_name: name,
+        })
+    }
+
+    pub fn add_buffer(&self, buffer: &TestBuffer, scale: i32) -> Result<(), TestError> {
+        self.tran.send(AddBuffer {
+            self_id: self.id,
+            buffer: buffer.id,
+            scale,
+        })
+    }
+}
+
+impl Drop for TestToplevelIcon {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestToplevelIcon, XdgToplevelIconV1;
+}
+
+impl TestObject for TestToplevelIcon {}
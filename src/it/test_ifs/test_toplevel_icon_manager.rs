@@ -0,0 +1,83 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::{test_toplevel_icon::TestToplevelIcon, test_xdg_toplevel::TestXdgToplevel},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{xdg_toplevel_icon_manager_v1::*, XdgToplevelIconManagerV1Id, XdgToplevelIconV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestToplevelIconManager {
+    pub id: XdgToplevelIconManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub icon_sizes: RefCell<Vec<i32>>,
+    pub done: Cell<bool>,
+}
+
+impl TestToplevelIconManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            icon_sizes: Default::default(),
+            done: Cell::new(false),
+        }
+    }
+
+    pub fn create_icon(&self) -> TestResult<Rc<TestToplevelIcon>> {
+        let obj = Rc::new(TestToplevelIcon {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(CreateIcon {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        Ok(obj)
+    }
+
+    pub fn set_icon(
+        &self,
+        toplevel: &TestXdgToplevel,
+        icon: Option<&TestToplevelIcon>,
+    ) -> TestResult {
+        self.tran.send(SetIcon {
+            self_id: self.id,
+            toplevel: toplevel.core.id,
+            icon: icon.map(|i| i.id).unwrap_or(XdgToplevelIconV1Id::NONE),
+        })?;
+        Ok(())
+    }
+
+    fn handle_icon_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = IconSize::parse_full(parser)?;
+        self.icon_sizes.borrow_mut().push(ev.size);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestToplevelIconManager, XdgToplevelIconManagerV1;
+
+    ICON_SIZE => handle_icon_size,
+    DONE => handle_done,
+}
+
+impl TestObject for TestToplevelIconManager {}
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{
+                test_ext_foreign_toplevel_handle::TestExtForeignToplevelHandle,
+                test_image_capture_source::TestImageCaptureSource,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{
+            ext_foreign_toplevel_image_capture_source_manager_v1::*,
+            ExtForeignToplevelImageCaptureSourceManagerV1Id,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestToplevelImageCaptureSourceManager {
+    pub id: ExtForeignToplevelImageCaptureSourceManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestToplevelImageCaptureSourceManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn create_source(
+        &self,
+        toplevel: &TestExtForeignToplevelHandle,
+    ) -> Result<Rc<TestImageCaptureSource>, TestError> {
+        let obj = Rc::new(TestImageCaptureSource {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateSource {
+            self_id: self.id,
+            source: obj.id,
+            toplevel_handle: toplevel.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestToplevelImageCaptureSourceManager, ExtForeignToplevelImageCaptureSourceManagerV1;
+}
+
+impl TestObject for TestToplevelImageCaptureSourceManager {}
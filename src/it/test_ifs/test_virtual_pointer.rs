@@ -0,0 +1,84 @@
+use {
+    crate::{
+        backend::KeyState,
+        fixed::Fixed,
+        ifs::wl_seat::wl_pointer,
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{zwlr_virtual_pointer_v1::*, ZwlrVirtualPointerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestVirtualPointer {
+    pub id: ZwlrVirtualPointerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestVirtualPointer {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn time(&self) -> u32 {
+        self.tran.run.state.now_msec() as u32
+    }
+
+    pub fn motion(&self, dx: f64, dy: f64) -> Result<(), TestError> {
+        self.tran.send(Motion {
+            self_id: self.id,
+            time: self.time(),
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+        })
+    }
+
+    pub fn motion_absolute(
+        &self,
+        x: u32,
+        y: u32,
+        x_extent: u32,
+        y_extent: u32,
+    ) -> Result<(), TestError> {
+        self.tran.send(MotionAbsolute {
+            self_id: self.id,
+            time: self.time(),
+            x,
+            y,
+            x_extent,
+            y_extent,
+        })
+    }
+
+    pub fn button(&self, button: u32, state: KeyState) -> Result<(), TestError> {
+        let state = match state {
+            KeyState::Released => wl_pointer::RELEASED,
+            KeyState::Pressed => wl_pointer::PRESSED,
+        };
+        self.tran.send(Button {
+            self_id: self.id,
+            time: self.time(),
+            button,
+            state,
+        })
+    }
+
+    pub fn frame(&self) -> Result<(), TestError> {
+        self.tran.send(Frame { self_id: self.id })
+    }
+}
+
+impl Drop for TestVirtualPointer {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestVirtualPointer, ZwlrVirtualPointerV1;
+}
+
+impl TestObject for TestVirtualPointer {}
@@ -0,0 +1,49 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{test_seat::TestSeat, test_virtual_pointer::TestVirtualPointer},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zwlr_virtual_pointer_manager_v1::*, ZwlrVirtualPointerManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestVirtualPointerManager {
+    pub id: ZwlrVirtualPointerManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub _destroyed: Cell<bool>,
+}
+
+impl TestVirtualPointerManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            _destroyed: Cell::new(false),
+        }
+    }
+
+    pub fn create_virtual_pointer(&self, seat: &TestSeat) -> TestResult<Rc<TestVirtualPointer>> {
+        let obj = Rc::new(TestVirtualPointer {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(CreateVirtualPointer {
+            self_id: self.id,
+            seat: seat.id,
+            id: obj.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestVirtualPointerManager, ZwlrVirtualPointerManagerV1;
+}
+
+impl TestObject for TestVirtualPointerManager {}
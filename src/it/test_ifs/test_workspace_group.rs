@@ -0,0 +1,95 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{
+            ext_workspace_group_handle_v1::*, ExtWorkspaceGroupHandleV1Id, ExtWorkspaceHandleV1Id,
+            WlOutputId,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestWorkspaceGroup {
+    pub id: ExtWorkspaceGroupHandleV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub removed: Cell<bool>,
+    pub capabilities: Cell<u32>,
+    pub outputs: CopyHashMap<WlOutputId, ()>,
+    pub workspaces: CopyHashMap<ExtWorkspaceHandleV1Id, ()>,
+}
+
+impl TestWorkspaceGroup {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_workspace(&self, name: &str) -> Result<(), TestError> {
+        self.tran.send(CreateWorkspace {
+            self_id: self.id,
+            workspace: name,
+        })
+    }
+
+    fn handle_capabilities(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Capabilities::parse_full(parser)?;
+        self.capabilities.set(ev.capabilities);
+        Ok(())
+    }
+
+    fn handle_output_enter(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = OutputEnter::parse_full(parser)?;
+        self.outputs.set(ev.output, ());
+        Ok(())
+    }
+
+    fn handle_output_leave(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = OutputLeave::parse_full(parser)?;
+        self.outputs.remove(&ev.output);
+        Ok(())
+    }
+
+    fn handle_workspace_enter(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = WorkspaceEnter::parse_full(parser)?;
+        self.workspaces.set(ev.workspace, ());
+        Ok(())
+    }
+
+    fn handle_workspace_leave(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = WorkspaceLeave::parse_full(parser)?;
+        self.workspaces.remove(&ev.workspace);
+        Ok(())
+    }
+
+    fn handle_removed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Removed::parse_full(parser)?;
+        self.removed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestWorkspaceGroup {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestWorkspaceGroup, ExtWorkspaceGroupHandleV1;
+
+    CAPABILITIES => handle_capabilities,
+    OUTPUT_ENTER => handle_output_enter,
+    OUTPUT_LEAVE => handle_output_leave,
+    WORKSPACE_ENTER => handle_workspace_enter,
+    WORKSPACE_LEAVE => handle_workspace_leave,
+    REMOVED => handle_removed,
+}
+
+impl TestObject for TestWorkspaceGroup {}
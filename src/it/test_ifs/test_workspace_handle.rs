@@ -0,0 +1,88 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{ext_workspace_handle_v1::*, ExtWorkspaceHandleV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestWorkspaceHandle {
+    pub id: ExtWorkspaceHandleV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub removed: Cell<bool>,
+    pub name: RefCell<Option<String>>,
+    pub state: Cell<u32>,
+}
+
+impl TestWorkspaceHandle {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn activate(&self) -> Result<(), TestError> {
+        self.tran.send(Activate { self_id: self.id })
+    }
+
+    fn handle_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Id::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Name::parse_full(parser)?;
+        *self.name.borrow_mut() = Some(ev.name.to_string());
+        Ok(())
+    }
+
+    fn handle_coordinates(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Coordinates::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_state(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = State::parse_full(parser)?;
+        self.state.set(ev.state);
+        Ok(())
+    }
+
+    fn handle_capabilities(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Capabilities::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_removed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Removed::parse_full(parser)?;
+        self.removed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestWorkspaceHandle {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestWorkspaceHandle, ExtWorkspaceHandleV1;
+
+    ID => handle_id,
+    NAME => handle_name,
+    COORDINATES => handle_coordinates,
+    STATE => handle_state,
+    CAPABILITIES => handle_capabilities,
+    REMOVED => handle_removed,
+}
+
+impl TestObject for TestWorkspaceHandle {}
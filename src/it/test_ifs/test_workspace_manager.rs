@@ -0,0 +1,109 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{
+                test_workspace_group::TestWorkspaceGroup,
+                test_workspace_handle::TestWorkspaceHandle,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{ext_workspace_manager_v1::*, ExtWorkspaceManagerV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestWorkspaceManager {
+    pub id: ExtWorkspaceManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub groups: RefCell<Vec<Rc<TestWorkspaceGroup>>>,
+    pub workspaces: RefCell<Vec<Rc<TestWorkspaceHandle>>>,
+    pub done: Cell<u32>,
+    pub finished: Cell<bool>,
+}
+
+impl TestWorkspaceManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            groups: Default::default(),
+            workspaces: Default::default(),
+            done: Cell::new(0),
+            finished: Cell::new(false),
+        }
+    }
+
+    pub fn commit(&self) -> Result<(), TestError> {
+        self.tran.send(Commit { self_id: self.id })
+    }
+
+    /// Returns the live workspace with the given name.
+    pub fn workspace(&self, name: &str) -> Option<Rc<TestWorkspaceHandle>> {
+        self.workspaces
+            .borrow()
+            .iter()
+            .find(|ws| !ws.removed.get() && ws.name.borrow().as_deref() == Some(name))
+            .cloned()
+    }
+
+    fn handle_workspace_group(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = WorkspaceGroup::parse_full(parser)?;
+        let group = Rc::new(TestWorkspaceGroup {
+            id: ev.workspace_group,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            removed: Cell::new(false),
+            capabilities: Cell::new(0),
+            outputs: Default::default(),
+            workspaces: Default::default(),
+        });
+        self.tran.add_obj(group.clone())?;
+        self.groups.borrow_mut().push(group);
+        Ok(())
+    }
+
+    fn handle_workspace(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Workspace::parse_full(parser)?;
+        let ws = Rc::new(TestWorkspaceHandle {
+            id: ev.workspace,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            removed: Cell::new(false),
+            name: Default::default(),
+            state: Cell::new(0),
+        });
+        self.tran.add_obj(ws.clone())?;
+        self.workspaces.borrow_mut().push(ws);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(self.done.get() + 1);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        self.finished.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestWorkspaceManager, ExtWorkspaceManagerV1;
+
+    WORKSPACE_GROUP => handle_workspace_group,
+    WORKSPACE => handle_workspace,
+    DONE => handle_done,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestWorkspaceManager {}
@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zxdg_exported_v2::*, ZxdgExportedV2Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestXdgExported {
+    pub id: ZxdgExportedV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub handle: RefCell<Option<String>>,
+}
+
+impl TestXdgExported {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn handle(&self) -> TestResult<String> {
+        match self.handle.borrow().clone() {
+            Some(h) => Ok(h),
+            _ => bail!("Server did not send a handle"),
+        }
+    }
+
+    fn handle_handle(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Handle::parse_full(parser)?;
+        *self.handle.borrow_mut() = Some(ev.handle.to_string());
+        Ok(())
+    }
+}
+
+test_object! {
+    TestXdgExported, ZxdgExportedV2;
+
+    HANDLE => handle_handle,
+}
+
+impl TestObject for TestXdgExported {}
+
+impl Drop for TestXdgExported {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
@@ -0,0 +1,67 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{test_surface::TestSurface, test_xdg_exported::TestXdgExported},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zxdg_exporter_v2::*, ZxdgExporterV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgExporter {
+    pub id: ZxdgExporterV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestXdgExporter {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            destroyed: Cell::new(false),
+        }
+    }
+
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub async fn export_toplevel(
+        &self,
+        surface: &TestSurface,
+    ) -> Result<Rc<TestXdgExported>, TestError> {
+        let obj = Rc::new(TestXdgExported {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            handle: Default::default(),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(ExportToplevel {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+        })?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestXdgExporter, ZxdgExporterV2;
+}
+
+impl TestObject for TestXdgExporter {}
+
+impl Drop for TestXdgExporter {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
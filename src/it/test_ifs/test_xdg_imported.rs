@@ -0,0 +1,54 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_surface::TestSurface, test_object::TestObject,
+            test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zxdg_imported_v2::*, ZxdgImportedV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgImported {
+    pub id: ZxdgImportedV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub server_destroyed: Cell<bool>,
+}
+
+impl TestXdgImported {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_parent_of(&self, surface: &TestSurface) -> Result<(), TestError> {
+        self.tran.send(SetParentOf {
+            self_id: self.id,
+            surface: surface.id,
+        })
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Destroyed::parse_full(parser)?;
+        self.server_destroyed.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestXdgImported, ZxdgImportedV2;
+
+    DESTROYED => handle_destroyed,
+}
+
+impl TestObject for TestXdgImported {}
+
+impl Drop for TestXdgImported {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
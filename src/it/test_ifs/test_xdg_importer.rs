@@ -0,0 +1,61 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_xdg_imported::TestXdgImported,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zxdg_importer_v2::*, ZxdgImporterV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgImporter {
+    pub id: ZxdgImporterV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestXdgImporter {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            destroyed: Cell::new(false),
+        }
+    }
+
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn import_toplevel(&self, handle: &str) -> Result<Rc<TestXdgImported>, TestError> {
+        let obj = Rc::new(TestXdgImported {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            server_destroyed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(ImportToplevel {
+            self_id: self.id,
+            id: obj.id,
+            handle,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestXdgImporter, ZxdgImporterV2;
+}
+
+impl TestObject for TestXdgImporter {}
+
+impl Drop for TestXdgImporter {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
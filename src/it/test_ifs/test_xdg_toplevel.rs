@@ -76,6 +76,14 @@ impl TestXdgToplevelCore {
         Ok(())
     }
 
+    pub fn set_app_id(&self, app_id: &str) -> Result<(), TestError> {
+        self.tran.send(SetAppId {
+            self_id: self.id,
+            app_id,
+        })?;
+        Ok(())
+    }
+
     fn handle_configure(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = Configure::parse_full(parser)?;
         self.width.set(ev.width);
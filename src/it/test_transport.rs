@@ -73,6 +73,21 @@ impl TestTransport {
             virtual_keyboard_manager: Default::default(),
             input_method_manager: Default::default(),
             text_input_manager: Default::default(),
+            output_manager: Default::default(),
+            gamma_control_manager: Default::default(),
+            output_image_capture_source_manager: Default::default(),
+            toplevel_image_capture_source_manager: Default::default(),
+            image_copy_capture_manager: Default::default(),
+            foreign_toplevel_manager: Default::default(),
+            shortcuts_inhibit_manager: Default::default(),
+            workspace_manager: Default::default(),
+            virtual_pointer_manager: Default::default(),
+            xdg_exporter: Default::default(),
+            xdg_importer: Default::default(),
+            color_manager: Default::default(),
+            fifo_manager: Default::default(),
+            commit_timing_manager: Default::default(),
+            toplevel_icon_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
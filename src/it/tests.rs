@@ -74,6 +74,30 @@ mod t0039_alpha_modifier;
 mod t0040_virtual_keyboard;
 mod t0041_input_method;
 mod t0042_toplevel_select;
+mod t0043_container_stacked;
+mod t0044_i3_ipc;
+mod t0045_output_management;
+mod t0046_gamma_control;
+mod t0047_color_temperature;
+mod t0048_image_copy_capture;
+mod t0049_foreign_toplevel_manager;
+mod t0050_keyboard_shortcuts_inhibit;
+mod t0051_workspace_manager;
+mod t0052_virtual_pointer;
+mod t0053_xdg_foreign;
+mod t0054_color_management;
+mod t0055_hdr;
+mod t0056_fifo_commit_timing;
+mod t0057_toplevel_icon;
+mod t0058_global_shortcuts;
+mod t0059_output_screenshot;
+mod t0060_virtual_output;
+mod t0061_output_mirror;
+mod t0062_animations;
+mod t0063_window_rules;
+mod t0064_window_handles;
+mod t0065_scratchpad;
+mod t0066_wl_usr;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -135,5 +159,29 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0040_virtual_keyboard,
         t0041_input_method,
         t0042_toplevel_select,
+        t0043_container_stacked,
+        t0044_i3_ipc,
+        t0045_output_management,
+        t0046_gamma_control,
+        t0047_color_temperature,
+        t0048_image_copy_capture,
+        t0049_foreign_toplevel_manager,
+        t0050_keyboard_shortcuts_inhibit,
+        t0051_workspace_manager,
+        t0052_virtual_pointer,
+        t0053_xdg_foreign,
+        t0054_color_management,
+        t0055_hdr,
+        t0056_fifo_commit_timing,
+        t0057_toplevel_icon,
+        t0058_global_shortcuts,
+        t0059_output_screenshot,
+        t0060_virtual_output,
+        t0061_output_mirror,
+        t0062_animations,
+        t0063_window_rules,
+        t0064_window_handles,
+        t0065_scratchpad,
+        t0066_wl_usr,
     }
 }
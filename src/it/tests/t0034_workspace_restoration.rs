@@ -29,6 +29,8 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         },
         events: Default::default(),
         feedback: Default::default(),
+        gamma_lut: Default::default(),
+        hdr_enabled: Default::default(),
     });
     let new_monitor_info = MonitorInfo {
         modes: vec![],
@@ -44,6 +46,8 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         height_mm: 0,
         non_desktop: false,
         vrr_capable: false,
+        primaries: None,
+        hdr_metadata: None,
     };
     run.backend
         .state
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+        tree::{ContainerLayout, ToplevelNodeBase},
+    },
+    jay_config::Layout,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the titles of a stacked container are stacked and that clicking a title
+/// activates the window
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let dss = client.get_default_seat().await?;
+
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+
+    run.cfg.set_layout(ds.seat.id(), Layout::Stacked)?;
+    client.sync().await;
+
+    let container = w2.tl.container_parent()?;
+    tassert_eq!(container.layout(), ContainerLayout::Stacked);
+
+    let th = run.state.theme.sizes.title_height.get();
+    tassert_eq!(container.mono_body.get().y1(), 2 * (th + 1));
+
+    let w1_title = container.render_data.borrow_mut().title_rects[0];
+    let w2_title = container.render_data.borrow_mut().active_title_rects[0];
+    tassert_eq!(w1_title.y1(), 0);
+    tassert_eq!(w2_title.y1(), th + 1);
+    tassert_eq!(w1_title.width(), w2_title.width());
+
+    let enters = dss.kb.enter.expect()?;
+
+    let container_pos = container.tl_data().pos.get();
+    let w1_title = w1_title.move_(container_pos.x1(), container_pos.y1());
+    ds.mouse
+        .abs(&ds.connector, w1_title.x1() as _, w1_title.y1() as _);
+    ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+
+    let enter = enters.next().with_context(|| "no enter event")?;
+    tassert_eq!(enter.surface, w1.surface.id);
+
+    run.cfg.set_layout(ds.seat.id(), Layout::Split)?;
+    client.sync().await;
+    tassert_eq!(container.layout(), ContainerLayout::Split);
+
+    run.cfg.set_mono(ds.seat.id(), true)?;
+    client.sync().await;
+    tassert_eq!(container.layout(), ContainerLayout::Tabbed);
+    tassert_eq!(container.mono_body.get().y1(), th + 1);
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+        utils::{buf::Buf, buffd::BufFdIn, oserror::OsErrorExt},
+    },
+    serde_json::Value,
+    std::rc::Rc,
+    uapi::{c, OwnedFd},
+};
+
+testcase!();
+
+const RUN_COMMAND: u32 = 0;
+const GET_WORKSPACES: u32 = 1;
+const SUBSCRIBE: u32 = 2;
+const EVENT_WINDOW: u32 = 0x80000003;
+
+/// Test the i3 IPC requests and events
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+
+    let (server, socket) =
+        uapi::socketpair(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0).to_os_error()?;
+    run.state.i3_ipc_clients.spawn(&run.state, Rc::new(server));
+    let socket = Rc::new(socket);
+    let mut buf = BufFdIn::new(&socket, &run.state.ring);
+
+    let (ty, workspaces) = request(&run, &socket, &mut buf, GET_WORKSPACES, "").await?;
+    tassert_eq!(ty, GET_WORKSPACES);
+    let Some(workspaces) = workspaces.as_array() else {
+        bail!("GET_WORKSPACES did not return an array");
+    };
+    tassert_eq!(workspaces.len(), 1);
+    tassert_eq!(workspaces[0]["focused"].as_bool(), Some(true));
+
+    let (_, reply) = request(&run, &socket, &mut buf, SUBSCRIBE, r#"["window"]"#).await?;
+    tassert_eq!(reply["success"].as_bool(), Some(true));
+
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+    let (ty, event) = read(&mut buf).await?;
+    tassert_eq!(ty, EVENT_WINDOW);
+    tassert_eq!(event["change"].as_str(), Some("new"));
+    tassert_eq!(
+        event["container"]["id"].as_u64(),
+        Some(w2.tl.server.node_id().raw() as u64)
+    );
+
+    let (_, reply) = request(&run, &socket, &mut buf, RUN_COMMAND, "floating toggle").await?;
+    tassert_eq!(reply[0]["success"].as_bool(), Some(true));
+    tassert!(w2.tl.server.tl_data().is_floating.get());
+
+    let (_, reply) = request(&run, &socket, &mut buf, RUN_COMMAND, "frobnicate").await?;
+    tassert_eq!(reply[0]["success"].as_bool(), Some(false));
+
+    let (_, reply) = request(&run, &socket, &mut buf, RUN_COMMAND, "exec true; nop").await?;
+    tassert_eq!(reply[0]["success"].as_bool(), Some(false));
+    tassert_eq!(reply[1]["success"].as_bool(), Some(true));
+
+    Ok(())
+}
+
+async fn request(
+    run: &Rc<TestRun>,
+    socket: &Rc<OwnedFd>,
+    buf: &mut BufFdIn,
+    ty: u32,
+    payload: &str,
+) -> TestResult<(u32, Value)> {
+    let mut msg = b"i3-ipc".to_vec();
+    msg.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+    msg.extend_from_slice(&ty.to_ne_bytes());
+    msg.extend_from_slice(payload.as_bytes());
+    let mut msg = Buf::from_slice(&msg);
+    let mut start = 0;
+    while start < msg.len() {
+        start += run
+            .state
+            .ring
+            .write(socket, msg.slice(start..), None)
+            .await?;
+    }
+    loop {
+        let (reply_ty, value) = read(buf).await?;
+        if reply_ty == ty {
+            return Ok((reply_ty, value));
+        }
+    }
+}
+
+async fn read(buf: &mut BufFdIn) -> TestResult<(u32, Value)> {
+    let mut hdr = [0u8; 14];
+    buf.read_full(&mut hdr[..]).await?;
+    tassert_eq!(&hdr[..6], b"i3-ipc");
+    let len = u32::from_ne_bytes([hdr[6], hdr[7], hdr[8], hdr[9]]) as usize;
+    let ty = u32::from_ne_bytes([hdr[10], hdr[11], hdr[12], hdr[13]]);
+    let mut payload = vec![0u8; len];
+    buf.read_full(&mut payload[..]).await?;
+    Ok((ty, serde_json::from_slice(&payload)?))
+}
@@ -0,0 +1,77 @@
+use {
+    crate::it::{
+        test_error::TestResult, test_ifs::test_output_configuration::TestOutputConfigurationResult,
+        testrun::TestRun,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that outputs can be configured via zwlr_output_manager_v1
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let manager = client.registry.get_output_manager().await?;
+    client.sync().await;
+
+    let heads = manager.heads.borrow().clone();
+    tassert_eq!(heads.len(), 1);
+    let head = &heads[0];
+    tassert!(head.enabled.get());
+    tassert_eq!(head.position.get(), (0, 0));
+    let Some(serial) = manager.serial.get() else {
+        bail!("Compositor did not send a done event");
+    };
+
+    let config = manager.create_configuration(serial)?;
+    let ch = config.enable_head(head)?;
+    ch.set_position(100, 50)?;
+    ch.set_scale(2.0)?;
+    config.test()?;
+    client.sync().await;
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Succeeded)
+    );
+    tassert_eq!(ds.output.global.pos.get().position(), (0, 0));
+    config.destroy()?;
+
+    let config = manager.create_configuration(serial)?;
+    let ch = config.enable_head(head)?;
+    ch.set_position(100, 50)?;
+    ch.set_scale(2.0)?;
+    config.apply()?;
+    client.sync().await;
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Succeeded)
+    );
+    tassert_eq!(ds.output.global.pos.get().position(), (100, 50));
+    tassert_eq!(ds.output.global.persistent.scale.get().to_f64(), 2.0);
+    tassert_eq!(head.position.get(), (100, 50));
+    tassert_eq!(head.scale.get(), 2.0);
+    tassert!(manager.serial.get() != Some(serial));
+
+    let config = manager.create_configuration(serial)?;
+    config.enable_head(head)?;
+    config.apply()?;
+    client.sync().await;
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Cancelled)
+    );
+
+    let config = manager.create_configuration(manager.serial.get().unwrap())?;
+    let ch = config.enable_head(head)?;
+    ch.set_custom_mode(12345, 678, 0)?;
+    config.apply()?;
+    client.sync().await;
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Failed)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use {
+    crate::it::{test_backend::TEST_GAMMA_LUT_SIZE, test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that zwlr_gamma_control_v1 sets and restores the gamma LUT of a connector
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let manager = client.registry.get_gamma_control_manager().await?;
+    let output = client.registry.get_output().await?;
+    let gc = manager.get_gamma_control(&output)?;
+    client.sync().await;
+    tassert!(!gc.failed.get());
+    tassert_eq!(gc.size.get(), Some(TEST_GAMMA_LUT_SIZE));
+
+    let ramps = [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23];
+    gc.set_gamma(&ramps)?;
+    client.sync().await;
+    let Some(lut) = ds.connector.gamma_lut.get() else {
+        bail!("Gamma LUT was not applied");
+    };
+    tassert_eq!(&lut.red[..], [0, 1, 2, 3]);
+    tassert_eq!(&lut.green[..], [10, 11, 12, 13]);
+    tassert_eq!(&lut.blue[..], [20, 21, 22, 23]);
+
+    let client2 = run.create_client().await?;
+    let manager2 = client2.registry.get_gamma_control_manager().await?;
+    let output2 = client2.registry.get_output().await?;
+    let gc2 = manager2.get_gamma_control(&output2)?;
+    client2.sync().await;
+    tassert!(gc2.failed.get());
+    tassert!(ds.connector.gamma_lut.is_some());
+
+    gc.destroy()?;
+    client.sync().await;
+    tassert!(ds.connector.gamma_lut.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        utils::rc_eq::rc_eq,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the color temperature and brightness of an output are applied via its gamma LUT
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    tassert!(ds.connector.gamma_lut.is_none());
+
+    run.cfg.set_color_temperature(&ds.output, 3000)?;
+    run.sync().await;
+    let Some(lut) = ds.connector.gamma_lut.get() else {
+        bail!("Color temperature was not applied");
+    };
+    let last = lut.red.len() - 1;
+    tassert_eq!(lut.red[last], u16::MAX);
+    tassert!(lut.green[last] < lut.red[last]);
+    tassert!(lut.blue[last] < lut.green[last]);
+
+    run.cfg.set_color_temperature(&ds.output, 6500)?;
+    run.cfg.set_brightness(&ds.output, 0.5)?;
+    run.sync().await;
+    let Some(lut) = ds.connector.gamma_lut.get() else {
+        bail!("Brightness was not applied");
+    };
+    tassert_eq!(lut.red[last], u16::MAX / 2 + 1);
+    tassert_eq!(&lut.red, &lut.green);
+    tassert_eq!(&lut.red, &lut.blue);
+
+    let client = run.create_client().await?;
+    let manager = client.registry.get_gamma_control_manager().await?;
+    let output = client.registry.get_output().await?;
+    let gc = manager.get_gamma_control(&output)?;
+    gc.set_gamma(&[0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3])?;
+    client.sync().await;
+    let Some(client_lut) = ds.connector.gamma_lut.get() else {
+        bail!("Gamma LUT was not applied");
+    };
+    tassert_eq!(&client_lut.red[..], [0, 1, 2, 3]);
+
+    run.cfg.set_brightness(&ds.output, 0.8)?;
+    run.sync().await;
+    tassert!(ds
+        .connector
+        .gamma_lut
+        .get()
+        .is_some_and(|l| rc_eq(&l, &client_lut)));
+
+    gc.destroy()?;
+    client.sync().await;
+    let Some(lut) = ds.connector.gamma_lut.get() else {
+        bail!("Brightness was not restored");
+    };
+    tassert_eq!(lut.red[last], (u16::MAX as f64 * 0.8).round() as u16);
+
+    run.cfg.set_brightness(&ds.output, 1.0)?;
+    run.sync().await;
+    tassert!(ds.connector.gamma_lut.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{
+        format::{ARGB8888, XRGB8888},
+        ifs::ext_image_copy_capture_frame_v1::FAILURE_REASON_BUFFER_CONSTRAINTS,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+        wire::WlBufferId,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test the buffer constraints and lifetime of image copy capture sessions
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let capture = client.registry.get_image_copy_capture_manager().await?;
+
+    let output_sources = client
+        .registry
+        .get_output_image_capture_source_manager()
+        .await?;
+    let output = client.registry.get_output().await?;
+    let source = output_sources.create_source(&output)?;
+    let session = capture.create_session(&source, 0)?;
+    client.sync().await;
+    tassert!(session.done.get());
+    let (width, height) = ds.output.global.pixel_size();
+    tassert_eq!(
+        session.buffer_size.get(),
+        Some((width as u32, height as u32))
+    );
+    tassert!(session.shm_formats.contains(&ARGB8888.drm));
+    tassert!(session.shm_formats.contains(&XRGB8888.drm));
+
+    let list = client.registry.get_foreign_toplevel_list().await?;
+    let win = client.create_window().await?;
+    win.map().await?;
+    client.sync().await;
+    let tls = list.toplevels.take();
+    tassert_eq!(tls.len(), 1);
+
+    let toplevel_sources = client
+        .registry
+        .get_toplevel_image_capture_source_manager()
+        .await?;
+    let source = toplevel_sources.create_source(&tls[0])?;
+    let session = capture.create_session(&source, 0)?;
+    client.sync().await;
+    tassert!(session.done.get());
+    let (width, height) = win.tl.server.tl_data().desired_extents.get().size();
+    tassert_eq!(
+        session.buffer_size.get(),
+        Some((width as u32, height as u32))
+    );
+
+    let buffer = client.shm.create_buffer(width + 1, height)?;
+    let frame = session.create_frame()?;
+    frame.attach_buffer(&buffer.buffer)?;
+    frame.capture()?;
+    client.sync().await;
+    tassert_eq!(frame.failed.get(), Some(FAILURE_REASON_BUFFER_CONSTRAINTS));
+    tassert!(!frame.ready.get());
+    frame.destroy()?;
+
+    win.surface.attach(WlBufferId::NONE)?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert!(session.stopped.get());
+
+    Ok(())
+}
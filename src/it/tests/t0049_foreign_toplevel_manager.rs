@@ -0,0 +1,76 @@
+use {
+    crate::{
+        ifs::zwlr_foreign_toplevel_handle_v1::{
+            STATE_ACTIVATED, STATE_FULLSCREEN, STATE_MINIMIZED,
+        },
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+        wire::WlBufferId,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that wlr-foreign-toplevel handles reflect and control the state of windows
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let output = client.registry.get_output().await?;
+    let manager = client.registry.get_foreign_toplevel_manager().await?;
+
+    let win = client.create_window().await?;
+    win.tl.core.set_title("a")?;
+    win.map().await?;
+    client.sync().await;
+
+    let tls = manager.toplevels.take();
+    tassert_eq!(tls.len(), 1);
+    let tl = &tls[0];
+    tassert_eq!(tl.title.borrow().as_deref(), Some("a"));
+    tassert!(tl.outputs.contains(&output.id));
+
+    win.tl.core.set_title("b")?;
+    client.sync().await;
+    tassert_eq!(tl.title.borrow().as_deref(), Some("b"));
+
+    tl.activate(&seat.seat)?;
+    client.sync().await;
+    tassert!(tl.has_state(STATE_ACTIVATED));
+
+    tl.set_fullscreen(None)?;
+    client.sync().await;
+    tassert!(win.tl.server.tl_data().is_fullscreen.get());
+    tassert!(tl.has_state(STATE_FULLSCREEN));
+
+    tl.unset_fullscreen()?;
+    client.sync().await;
+    tassert!(!win.tl.server.tl_data().is_fullscreen.get());
+    tassert!(!tl.has_state(STATE_FULLSCREEN));
+
+    tl.set_minimized()?;
+    client.sync().await;
+    tassert!(!win.tl.server.node_visible());
+    tassert!(tl.has_state(STATE_MINIMIZED));
+    tassert!(tl.outputs.is_empty());
+
+    tl.activate(&seat.seat)?;
+    client.sync().await;
+    tassert!(win.tl.server.node_visible());
+    tassert!(!tl.has_state(STATE_MINIMIZED));
+    tassert!(tl.has_state(STATE_ACTIVATED));
+    tassert!(tl.outputs.contains(&output.id));
+
+    tl.close()?;
+    client.sync().await;
+    tassert!(win.tl.core.close_requested.get());
+
+    win.surface.attach(WlBufferId::NONE)?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert!(tl.closed.get());
+
+    Ok(())
+}
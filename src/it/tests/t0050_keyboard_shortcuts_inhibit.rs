@@ -0,0 +1,91 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::{SYM_F13, SYM_F14},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a focused surface can inhibit shortcuts except for the escape shortcut
+/// and only while an escape shortcut is set
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9;
+          <2> = 10;
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+        key <2> { [ F14 ] };
+    };
+};
+    "#;
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F14)?;
+    run.cfg
+        .set_shortcuts_inhibit_escape(ds.seat.id(), SYM_F14)?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let manager = client.registry.get_shortcuts_inhibit_manager().await?;
+
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let inhibitor = manager.inhibit_shortcuts(&win.surface.surface, &seat.seat)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    ds.kb.press(1);
+    ds.kb.press(2);
+    run.sync().await;
+    tassert!(!run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F13.into())));
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F14.into())));
+
+    run.cfg
+        .set_shortcuts_inhibit_allowed(client.server.id, false)?;
+    client.sync().await;
+    tassert!(!inhibitor.active.get());
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F13.into())));
+
+    run.cfg
+        .set_shortcuts_inhibit_allowed(client.server.id, true)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    run.cfg.unset_shortcuts_inhibit_escape(ds.seat.id())?;
+    client.sync().await;
+    tassert!(!inhibitor.active.get());
+
+    run.cfg
+        .set_shortcuts_inhibit_escape(ds.seat.id(), SYM_F14)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.sync().await;
+    tassert!(!inhibitor.active.get());
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use {
+    crate::{
+        ifs::{
+            ext_workspace_group_handle_v1::GROUP_CAPABILITIES_CREATE_WORKSPACE,
+            ext_workspace_handle_v1::STATE_ACTIVE,
+        },
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that ext-workspace reflects and controls the workspaces of outputs
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+
+    let client = run.create_client().await?;
+    let output = client.registry.get_output().await?;
+    let manager = client.registry.get_workspace_manager().await?;
+    run.sync().await;
+    client.sync().await;
+    tassert!(manager.done.get() > 0);
+
+    let group = {
+        let groups = manager.groups.borrow();
+        tassert_eq!(groups.len(), 1);
+        groups[0].clone()
+    };
+    tassert_eq!(
+        group.capabilities.get(),
+        GROUP_CAPABILITIES_CREATE_WORKSPACE
+    );
+    tassert!(group.outputs.contains(&output.id));
+
+    let ws1 = manager.workspace("1").unwrap();
+    tassert!(ws1.state.get() & STATE_ACTIVE != 0);
+    tassert!(group.workspaces.contains(&ws1.id));
+
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.sync().await;
+    client.sync().await;
+    let ws2 = manager.workspace("2").unwrap();
+    tassert!(ws2.state.get() & STATE_ACTIVE != 0);
+    tassert!(group.workspaces.contains(&ws2.id));
+    tassert!(ws1.state.get() & STATE_ACTIVE == 0);
+
+    ws1.activate()?;
+    manager.commit()?;
+    client.sync().await;
+    tassert!(ws1.state.get() & STATE_ACTIVE != 0);
+    tassert!(ws2.removed.get());
+    tassert!(!group.workspaces.contains(&ws2.id));
+    tassert!(run.state.workspaces.get("2").is_none());
+
+    group.create_workspace("3")?;
+    manager.commit()?;
+    client.sync().await;
+    let ws3 = manager.workspace("3").unwrap();
+    tassert!(ws3.state.get() & STATE_ACTIVE != 0);
+    tassert!(group.workspaces.contains(&ws3.id));
+    tassert!(ws1.state.get() & STATE_ACTIVE == 0);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use {
+    crate::{
+        backend::KeyState,
+        fixed::Fixed,
+        ifs::wl_seat::BTN_LEFT,
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a virtual pointer moves the seat cursor and sends button events
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let manager = client.registry.get_virtual_pointer_manager().await?;
+    let pointer = manager.create_virtual_pointer(&seat.seat)?;
+
+    let rect = ds.output.global.pos.get();
+    let (cx, cy) = (rect.x1() + rect.width() / 2, rect.y1() + rect.height() / 2);
+
+    pointer.motion_absolute(1, 1, 2, 2)?;
+    pointer.frame()?;
+    client.sync().await;
+    tassert_eq!(
+        ds.seat.pointer_cursor().position(),
+        (Fixed::from_int(cx), Fixed::from_int(cy))
+    );
+
+    pointer.motion(10.0, 5.0)?;
+    pointer.frame()?;
+    client.sync().await;
+    tassert_eq!(
+        ds.seat.pointer_cursor().position(),
+        (Fixed::from_int(cx + 10), Fixed::from_int(cy + 5))
+    );
+
+    let buttons = seat.pointer.button.expect()?;
+    pointer.button(BTN_LEFT, KeyState::Pressed)?;
+    pointer.frame()?;
+    pointer.button(BTN_LEFT, KeyState::Released)?;
+    pointer.frame()?;
+    client.sync().await;
+
+    let press = buttons.next().with_context(|| "press")?;
+    tassert_eq!((press.button, press.state), (BTN_LEFT, 1));
+    let release = buttons.next().with_context(|| "release")?;
+    tassert_eq!((release.button, release.state), (BTN_LEFT, 0));
+    tassert!(buttons.next().is_err());
+
+    Ok(())
+}
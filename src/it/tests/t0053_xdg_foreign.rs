@@ -0,0 +1,49 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a toplevel imported via xdg-foreign becomes the parent of a dialog of another
+/// client
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let parent_client = run.create_client().await?;
+    let parent = parent_client.create_window().await?;
+    parent.map2().await?;
+    let exporter = parent_client.registry.get_xdg_exporter().await?;
+    let exported = exporter.export_toplevel(&parent.surface).await?;
+    let handle = exported.handle()?;
+
+    let child_client = run.create_client().await?;
+    let importer = child_client.registry.get_xdg_importer().await?;
+
+    let invalid = importer.import_toplevel("invalid")?;
+    child_client.sync().await;
+    tassert!(invalid.server_destroyed.get());
+
+    let imported = importer.import_toplevel(&handle)?;
+    let child = child_client.create_window().await?;
+    imported.set_parent_of(&child.surface)?;
+    child.map2().await?;
+    child_client.sync().await;
+    tassert!(!imported.server_destroyed.get());
+
+    let child_parent = child.tl.server.parent.get();
+    tassert!(child_parent.is_some_and(|p| Rc::ptr_eq(&p, &parent.tl.server)));
+    tassert!(child.tl.float_parent().is_ok());
+    let parent_ws = parent.tl.server.xdg.workspace.get().map(|w| w.id);
+    let child_ws = child.tl.server.xdg.workspace.get().map(|w| w.id);
+    tassert!(parent_ws.is_some());
+    tassert_eq!(child_ws, parent_ws);
+
+    exported.destroy()?;
+    parent_client.sync().await;
+    child_client.sync().await;
+    tassert!(imported.server_destroyed.get());
+    tassert!(child.tl.server.parent.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use {
+    crate::{
+        ifs::wp_color_manager_v1::{
+            PRIMARIES_BT2020, RENDER_INTENT_PERCEPTUAL, TRANSFER_FUNCTION_ST2084_PQ,
+        },
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a parametric image description can be attached to a surface
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let cm = client.registry.get_color_manager().await?;
+    client.sync().await;
+    tassert!(cm.done.get());
+    tassert!(cm.tfs.borrow().contains(&TRANSFER_FUNCTION_ST2084_PQ));
+    tassert!(cm.primaries.borrow().contains(&PRIMARIES_BT2020));
+
+    let win = client.create_window().await?;
+    win.map2().await?;
+    tassert!(win.surface.server.color_description.is_none());
+
+    let creator = cm.create_parametric_creator()?;
+    creator.set_tf_named(TRANSFER_FUNCTION_ST2084_PQ)?;
+    creator.set_primaries_named(PRIMARIES_BT2020)?;
+    let desc = creator.create()?;
+    client.sync().await;
+    let identity = desc.identity()?;
+
+    let cms = cm.get_surface(&win.surface)?;
+    cms.set_image_description(&desc, RENDER_INTENT_PERCEPTUAL)?;
+    client.sync().await;
+    tassert!(win.surface.server.color_description.is_none());
+    win.surface.commit()?;
+    client.sync().await;
+    let cd = win.surface.server.color_description.get();
+    tassert_eq!(cd.map(|cd| cd.id.raw()), Some(identity));
+
+    cms.unset_image_description()?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert!(win.surface.server.color_description.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use {
+    crate::{
+        backend::DEFAULT_SDR_REFERENCE_WHITE,
+        color_management::{NamedPrimaries, TransferFunction},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that enabling HDR switches the output to BT.2020/PQ
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    tassert!(!ds.output.hdr_active.get());
+    tassert_eq!(
+        ds.output.color_description.get().data.transfer_function,
+        TransferFunction::Srgb
+    );
+
+    run.cfg.set_hdr_enabled(&ds.output, true)?;
+    run.sync().await;
+    tassert!(ds.connector.hdr_enabled.get());
+    tassert!(ds.output.hdr_active.get());
+    let data = ds.output.color_description.get().data.clone();
+    tassert_eq!(data.transfer_function, TransferFunction::St2084Pq);
+    tassert_eq!(data.named_primaries, Some(NamedPrimaries::Bt2020));
+    tassert_eq!(data.luminance.white, DEFAULT_SDR_REFERENCE_WHITE);
+    tassert_eq!(data.target_luminance.map(|l| l.max), Some(600.0));
+
+    run.cfg.set_sdr_reference_white(&ds.output, 300.0)?;
+    run.sync().await;
+    tassert_eq!(
+        ds.output.color_description.get().data.luminance.white,
+        300.0
+    );
+
+    run.cfg.set_hdr_enabled(&ds.output, false)?;
+    run.sync().await;
+    tassert!(!ds.connector.hdr_enabled.get());
+    tassert!(!ds.output.hdr_active.get());
+    tassert_eq!(
+        ds.output.color_description.get().data.transfer_function,
+        TransferFunction::Srgb
+    );
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        theme::Color,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that commits are held until the fifo barrier clears or the target time is reached
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let fifo = client
+        .registry
+        .get_fifo_manager()
+        .await?
+        .get_fifo(&win.surface)?;
+    let timer = client
+        .registry
+        .get_commit_timing_manager()
+        .await?
+        .get_timer(&win.surface)?;
+
+    let buffer = || win.surface.server.buffer.get().map(|b| b.buffer.id);
+    let buf1 = client.spbm.create_buffer(Color::from_rgb(255, 0, 0))?;
+    let buf2 = client.spbm.create_buffer(Color::from_rgb(0, 255, 0))?;
+    let buf3 = client.spbm.create_buffer(Color::from_rgb(0, 0, 255))?;
+
+    win.surface.attach(buf1.id)?;
+    fifo.set_barrier()?;
+    win.surface.commit()?;
+    win.surface.attach(buf2.id)?;
+    fifo.wait_barrier()?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert_eq!(buffer(), Some(buf1.id));
+
+    ds.output.vblank();
+    client.sync().await;
+    tassert_eq!(buffer(), Some(buf2.id));
+
+    win.surface.attach(buf3.id)?;
+    timer.set_timestamp(run.state.now_nsec() + 200_000_000)?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert_eq!(buffer(), Some(buf2.id));
+
+    run.state.wheel.timeout(300).await?;
+    client.sync().await;
+    tassert_eq!(buffer(), Some(buf3.id));
+
+    Ok(())
+}
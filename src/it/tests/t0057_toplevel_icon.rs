@@ -0,0 +1,82 @@
+use {
+    crate::{
+        icons::title_icon_size,
+        it::{test_error::TestResult, testrun::TestRun},
+        scale::Scale,
+        theme::Color,
+        tree::ToplevelNodeBase,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that toplevel icons are stored on the toplevel and rendered in the title bar
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_title("a")?;
+    win.map2().await?;
+
+    let th = run.state.theme.sizes.title_height.get();
+    let manager = client.registry.get_toplevel_icon_manager().await?;
+    client.sync().await;
+    tassert!(manager.done.get());
+    tassert_eq!(
+        manager.icon_sizes.borrow().clone(),
+        vec![title_icon_size(th)]
+    );
+
+    let container = win.tl.container_parent()?;
+    let server_icon = || win.tl.server.tl_data().icon.get();
+    let num_icons = || {
+        let rd = container.render_data.borrow();
+        rd.icons
+            .get(&Scale::from_int(1))
+            .map(|i| i.len())
+            .unwrap_or(0)
+    };
+    let title_x = || {
+        let rd = container.render_data.borrow();
+        rd.titles.get(&Scale::from_int(1)).map(|t| t[0].x)
+    };
+
+    let buf16 = client.shm.create_buffer(16, 16)?;
+    buf16.fill(Color::from_rgb(255, 0, 0));
+    let buf32 = client.shm.create_buffer(32, 32)?;
+    buf32.fill(Color::from_rgb(0, 255, 0));
+    let icon = manager.create_icon()?;
+    icon.add_buffer(&buf16.buffer, 1)?;
+    icon.add_buffer(&buf32.buffer, 1)?;
+    manager.set_icon(&win.tl, Some(&icon))?;
+    client.sync().await;
+    let Some(si) = server_icon() else {
+        bail!("The toplevel has no icon");
+    };
+    tassert_eq!(si.image(1).size, 16);
+    tassert_eq!(si.image(17).size, 32);
+    tassert_eq!(si.image(64).size, 32);
+    tassert_eq!(num_icons(), 1);
+    tassert_eq!(title_x(), Some(th));
+
+    let unknown = manager.create_icon()?;
+    unknown.set_name("jay-test-icon-that-does-not-exist")?;
+    manager.set_icon(&win.tl, Some(&unknown))?;
+    client.sync().await;
+    tassert!(server_icon().is_none());
+    tassert_eq!(num_icons(), 0);
+    tassert_eq!(title_x(), Some(0));
+
+    manager.set_icon(&win.tl, Some(&icon))?;
+    client.sync().await;
+    tassert!(server_icon().is_some());
+
+    manager.set_icon(&win.tl, None)?;
+    client.sync().await;
+    tassert!(server_icon().is_none());
+    tassert_eq!(num_icons(), 0);
+
+    Ok(())
+}
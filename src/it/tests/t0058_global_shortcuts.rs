@@ -0,0 +1,77 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::{
+        mods::{Modifiers, CTRL},
+        syms::{SYM_F13, SYM_F14},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that global shortcuts are activated and that config shortcuts take precedence
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9;
+          <2> = 10;
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+        key <2> { [ F14 ] };
+    };
+};
+    "#;
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F14)?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let shortcuts = client.jc.create_global_shortcuts()?;
+    shortcuts.bind(0, Modifiers(0), SYM_F13)?;
+    shortcuts.bind(1, Modifiers(0), SYM_F14)?;
+    shortcuts.bind(2, CTRL, SYM_F13)?;
+    client.sync().await;
+
+    let activated = shortcuts.activated.expect()?;
+    let deactivated = shortcuts.deactivated.expect()?;
+    let key = seat.kb.key.expect()?;
+
+    let pressed = ds.kb.press(1);
+    client.sync().await;
+    tassert_eq!(activated.next()?, 0);
+    activated.none()?;
+    deactivated.none()?;
+    drop(pressed);
+    client.sync().await;
+    tassert_eq!(deactivated.next()?, 0);
+    key.none()?;
+
+    ds.kb.press(2);
+    client.sync().await;
+    activated.none()?;
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F14.into())));
+
+    shortcuts.unbind(0)?;
+    client.sync().await;
+    ds.kb.press(1);
+    client.sync().await;
+    activated.none()?;
+    tassert!(key.next().is_ok());
+
+    Ok(())
+}
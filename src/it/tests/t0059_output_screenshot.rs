@@ -0,0 +1,39 @@
+use {
+    crate::{
+        cli::{screenshot::buf_to_bytes, ScreenshotFormat},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that output screenshots are taken at the scale of the output
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let output = client.registry.get_output().await?;
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 255);
+    win.map2().await?;
+
+    let output_shot = client.jc.take_output_screenshot(&output, false).await?;
+    tassert_eq!((output_shot.width, output_shot.height), (800, 600));
+    let output_qoi = buf_to_bytes(
+        &client.server.state.dma_buf_ids,
+        &output_shot,
+        ScreenshotFormat::Qoi,
+    );
+    tassert!(output_qoi == client.take_screenshot(false).await?);
+
+    run.cfg.set_scale(&ds.output, 2.0)?;
+    client.sync().await;
+
+    let output_shot = client.jc.take_output_screenshot(&output, false).await?;
+    tassert_eq!((output_shot.width, output_shot.height), (800, 600));
+    let full_shot = client.jc.take_screenshot(false).await?;
+    tassert_eq!((full_shot.width, full_shot.height), (400, 300));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that virtual outputs can be created and removed at runtime
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let id = run.cfg.create_virtual_output(1024, 768, 30_000)?;
+    run.sync().await;
+
+    let Some(connector) = run.state.connectors.get(&id) else {
+        bail!("virtual connector does not exist");
+    };
+    tassert_eq!(connector.name.as_str(), "Virtual-1");
+    let Some(output) = run.state.root.outputs.get(&id) else {
+        bail!("virtual output does not exist");
+    };
+    let mode = output.global.mode.get();
+    tassert_eq!((mode.width, mode.height), (1024, 768));
+    tassert_eq!(mode.refresh_rate_millihz, 30_000);
+    tassert!(output.global.pos.get().x1() >= ds.output.global.pos.get().x2());
+
+    run.cfg.set_scale(&output, 2.0)?;
+    tassert_eq!(output.global.pos.get().width(), 512);
+
+    run.cfg.remove_virtual_output(id)?;
+    run.sync().await;
+    tassert!(run.state.root.outputs.get(&id).is_none());
+    tassert!(run.state.connectors.get(&id).is_none());
+
+    run.cfg.remove_virtual_output(ds.connector.id)?;
+    run.sync().await;
+    tassert!(run.state.connectors.get(&ds.connector.id).is_some());
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a connector can mirror another connector
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let id = run.cfg.create_virtual_output(1024, 768, 30_000)?;
+    run.sync().await;
+    tassert!(run.state.root.outputs.get(&id).is_some());
+
+    run.cfg.set_mirror(id, Some(id))?;
+    run.sync().await;
+    tassert!(run.state.mirrors.get(&id).is_none());
+
+    run.cfg.set_mirror(id, Some(ds.connector.id))?;
+    run.sync().await;
+    tassert!(run.state.root.outputs.get(&id).is_none());
+    tassert!(run.state.root.outputs.get(&ds.connector.id).is_some());
+    let Some(mirror) = run.state.mirrors.get(&id) else {
+        bail!("mirror does not exist");
+    };
+    tassert_eq!(mirror.source, ds.connector.id);
+    tassert_eq!((mirror.mode().width, mirror.mode().height), (1024, 768));
+    let Some(output) = run.state.outputs.get(&id) else {
+        bail!("mirroring output does not exist");
+    };
+    tassert!(output.node.is_none());
+
+    run.cfg.set_mirror(ds.connector.id, Some(id))?;
+    run.sync().await;
+    tassert!(run.state.mirrors.get(&ds.connector.id).is_none());
+    tassert!(run.state.root.outputs.get(&ds.connector.id).is_some());
+
+    let id2 = run.cfg.create_virtual_output(800, 600, 60_000)?;
+    run.sync().await;
+    run.cfg.set_mirror(id2, Some(id))?;
+    run.sync().await;
+    tassert!(run.state.mirrors.get(&id2).is_none());
+    tassert!(run.state.root.outputs.get(&id2).is_some());
+
+    run.cfg.set_mirror(id2, Some(ds.connector.id))?;
+    run.sync().await;
+    tassert!(run.state.mirrors.get(&id2).is_some());
+    run.cfg.set_mirror(id2, None)?;
+    run.sync().await;
+
+    run.cfg.set_mirror(id, None)?;
+    run.sync().await;
+    tassert!(run.state.mirrors.get(&id).is_none());
+    let Some(output) = run.state.root.outputs.get(&id) else {
+        bail!("output was not restored");
+    };
+    let mode = output.global.mode.get();
+    tassert_eq!((mode.width, mode.height), (1024, 768));
+
+    Ok(())
+}
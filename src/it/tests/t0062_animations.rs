@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::theme::animations::{Curve, WINDOW_MOVE, WINDOW_OPEN},
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that window animations are started and can be disabled
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let hour = Duration::from_secs(3600);
+    run.cfg.set_animations_enabled(true)?;
+    run.cfg.set_animation_duration(WINDOW_OPEN, hour)?;
+    run.cfg.set_animation_duration(WINDOW_MOVE, hour)?;
+    run.cfg.set_animation_curve(WINDOW_MOVE, Curve::LINEAR)?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let animations = &run.state.animations;
+    let id1 = win1.tl.server.node_id();
+    tassert!(animations.window_opacity(id1, run.state.now_nsec()) < 1.0);
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let Some((current, target)) = animations.window_rect(id1, run.state.now_nsec()) else {
+        bail!("window is not moving");
+    };
+    tassert_eq!(target, win1.tl.server.tl_data().desired_extents.get());
+    tassert!(current.width() > target.width());
+
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    client.sync().await;
+    let float = win2.tl.float_parent()?;
+    tassert!(animations
+        .window_rect(float.node_id(), run.state.now_nsec())
+        .is_some());
+    tassert!(animations.tick(ds.output.id, run.state.now_nsec()));
+
+    run.cfg.set_animations_enabled(false)?;
+    client.sync().await;
+    tassert_eq!(animations.window_opacity(id1, run.state.now_nsec()), 1.0);
+    tassert!(animations.window_rect(id1, run.state.now_nsec()).is_none());
+    tassert!(!animations.tick(ds.output.id, run.state.now_nsec()));
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::window::{WindowActions, WindowMatch},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that window rules are applied when a window is mapped
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let rule = run.cfg.add_window_rule(
+        WindowMatch {
+            app_id: Some("float-me".to_string()),
+            ..Default::default()
+        },
+        WindowActions {
+            workspace: Some(run.cfg.get_workspace("2")?),
+            floating: Some(true),
+            size: Some((300, 200)),
+            ..Default::default()
+        },
+    )?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.tl.core.set_app_id("float-me")?;
+    win1.map2().await?;
+    let data = win1.tl.server.tl_data();
+    tassert!(data.is_floating.get());
+    let Some(ws) = data.workspace.get() else {
+        bail!("window has no workspace");
+    };
+    tassert_eq!(ws.name.as_str(), "2");
+    tassert_eq!(win1.tl.core.width.get(), 300);
+    tassert_eq!(win1.tl.core.height.get(), 200);
+
+    let win2 = client.create_window().await?;
+    win2.tl.core.set_app_id("tile-me")?;
+    win2.map2().await?;
+    let data = win2.tl.server.tl_data();
+    tassert!(!data.is_floating.get());
+    tassert!(data.workspace.get().is_some_and(|ws| ws.name != "2"));
+
+    run.cfg.remove_window_rule(rule)?;
+    let win3 = client.create_window().await?;
+    win3.tl.core.set_app_id("float-me")?;
+    win3.map2().await?;
+    tassert!(!win3.tl.server.tl_data().is_floating.get());
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test the window and client handles of the config API
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.tl.core.set_app_id("app")?;
+    win1.tl.core.set_title("title")?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let id1 = win1.tl.server.node_id();
+    let id2 = win2.tl.server.node_id();
+
+    let windows = run.cfg.get_windows()?;
+    tassert!(windows.contains(&id1));
+    tassert!(windows.contains(&id2));
+    tassert!(run.cfg.window_exists(id1)?);
+    tassert_eq!(run.cfg.get_window_title(id1)?, "title");
+    tassert_eq!(run.cfg.get_window_app_id(id1)?, "app");
+    tassert_eq!(run.cfg.get_window_client(id1)?, client.server.id);
+    let Some(ws) = win1.tl.server.tl_data().workspace.get() else {
+        bail!("window has no workspace");
+    };
+    tassert_eq!(
+        run.cfg.get_window_workspace(id1)?,
+        run.cfg.get_workspace(&ws.name)?
+    );
+
+    tassert!(!run.cfg.get_window_floating(id1)?);
+    run.cfg.set_window_floating(id1, true)?;
+    tassert!(run.cfg.get_window_floating(id1)?);
+    tassert!(win1.tl.float_parent().is_ok());
+
+    run.cfg.set_window_workspace(id1, "new")?;
+    let Some(ws) = win1.tl.server.tl_data().workspace.get() else {
+        bail!("window has no workspace");
+    };
+    tassert_eq!(ws.name.as_str(), "new");
+    tassert_eq!(ws.output.get().id, ds.output.id);
+    tassert!(!ws.visible.get());
+
+    run.cfg.focus_window(ds.seat.id(), id1)?;
+    tassert!(ws.visible.get());
+
+    tassert!(run.cfg.client_exists(client.server.id)?);
+    tassert_eq!(
+        run.cfg.get_client_pid(client.server.id)?,
+        client.server.pid_info.pid
+    );
+    tassert!(!run.cfg.get_client_is_xwayland(client.server.id)?);
+
+    run.cfg.close_window(id2)?;
+    client.sync().await;
+    tassert!(win2.tl.core.close_requested.get());
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test moving windows to and showing windows from the scratchpad
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let data = win2.tl.server.tl_data();
+
+    run.cfg.move_to_scratchpad(ds.seat.id())?;
+    client.sync().await;
+    tassert!(data.parent.is_none());
+    tassert!(data.is_scratchpad.get());
+    tassert!(!win2.tl.server.node_visible());
+    tassert_eq!(run.state.scratchpad.windows().len(), 1);
+
+    run.cfg.show_scratchpad(ds.seat.id())?;
+    client.sync().await;
+    tassert!(win2.tl.float_parent().is_ok());
+    tassert!(win2.tl.server.node_visible());
+    tassert!(data.is_scratchpad.get());
+    tassert!(run.state.scratchpad.windows().is_empty());
+
+    run.cfg.toggle_scratchpad(ds.seat.id())?;
+    client.sync().await;
+    tassert!(data.parent.is_none());
+    tassert_eq!(run.state.scratchpad.windows().len(), 1);
+
+    run.cfg.toggle_scratchpad(ds.seat.id())?;
+    client.sync().await;
+    tassert!(win2.tl.float_parent().is_ok());
+
+    let id2 = win2.tl.server.node_id();
+    run.cfg.set_window_workspace(id2, "2")?;
+    client.sync().await;
+    tassert!(!data.is_scratchpad.get());
+    run.cfg.focus_window(ds.seat.id(), id2)?;
+    run.cfg.toggle_scratchpad(ds.seat.id())?;
+    client.sync().await;
+    tassert!(win2.tl.float_parent().is_ok());
+    tassert!(win2.tl.server.node_visible());
+
+    run.cfg.move_to_scratchpad(ds.seat.id())?;
+    run.cfg.show_scratchpad(ds.seat.id())?;
+    run.cfg.set_floating(ds.seat.id(), false)?;
+    client.sync().await;
+    tassert!(!data.is_scratchpad.get());
+    tassert!(win2.tl.container_parent().is_ok());
+
+    Ok(())
+}
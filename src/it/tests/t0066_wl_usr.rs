@@ -0,0 +1,261 @@
+use {
+    crate::{
+        format::XRGB8888,
+        ifs::wl_seat::{KEYBOARD, POINTER},
+        it::{
+            test_error::{TestError, TestResult},
+            test_mem::TestMem,
+            test_utils::test_rect_ext::TestRectExt,
+            testrun::TestRun,
+        },
+        object::{Interface, Version},
+        tree::Node,
+        utils::asyncevent::AsyncEvent,
+        wire::{
+            wl_keyboard::{Enter as KeyboardEnter, Key},
+            wl_pointer::Enter,
+            zwp_relative_pointer_v1::RelativeMotion,
+            WlCompositor, WlSeat, WlShm, WlSurfaceId, XdgWmBase, ZwpRelativePointerManagerV1,
+        },
+        wl_usr::{
+            usr_ifs::{
+                usr_wl_compositor::UsrWlCompositor,
+                usr_wl_keyboard::UsrWlKeyboardOwner,
+                usr_wl_pointer::UsrWlPointerOwner,
+                usr_wl_registry::UsrWlRegistryOwner,
+                usr_wl_seat::{UsrWlSeat, UsrWlSeatOwner},
+                usr_wl_shm::UsrWlShm,
+                usr_xdg_surface::UsrXdgSurfaceOwner,
+                usr_xdg_toplevel::UsrXdgToplevelOwner,
+                usr_xdg_wm_base::UsrXdgWmBase,
+                usr_zwp_relative_pointer::UsrZwpRelativePointerOwner,
+                usr_zwp_relative_pointer_manager::UsrZwpRelativePointerManager,
+            },
+            UsrCon,
+        },
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
+};
+
+testcase!();
+
+#[derive(Default)]
+struct Events {
+    globals: RefCell<AHashMap<String, u32>>,
+    capabilities: Cell<u32>,
+    seat_name: RefCell<String>,
+    configured: Cell<bool>,
+    size: Cell<(i32, i32)>,
+    closed: Cell<bool>,
+    pointer_focus: Cell<Option<WlSurfaceId>>,
+    keyboard_focus: Cell<Option<WlSurfaceId>>,
+    keys: RefCell<Vec<(u32, u32)>>,
+    relative_motion: Cell<(f64, f64)>,
+}
+
+impl UsrWlRegistryOwner for Events {
+    fn global(self: Rc<Self>, name: u32, interface: &str, _version: u32) {
+        self.globals
+            .borrow_mut()
+            .insert(interface.to_string(), name);
+    }
+}
+
+impl UsrWlSeatOwner for Events {
+    fn capabilities(self: Rc<Self>, value: u32) {
+        self.capabilities.set(value);
+    }
+
+    fn name(&self, name: &str) {
+        *self.seat_name.borrow_mut() = name.to_string();
+    }
+}
+
+impl UsrXdgSurfaceOwner for Events {
+    fn configure(&self) {
+        self.configured.set(true);
+    }
+}
+
+impl UsrXdgToplevelOwner for Events {
+    fn configure(&self, width: i32, height: i32) {
+        self.size.set((width, height));
+    }
+
+    fn close(&self) {
+        self.closed.set(true);
+    }
+}
+
+impl UsrWlPointerOwner for Events {
+    fn enter(&self, ev: &Enter) {
+        self.pointer_focus.set(Some(ev.surface));
+    }
+}
+
+impl UsrWlKeyboardOwner for Events {
+    fn enter(&self, ev: &KeyboardEnter) {
+        self.keyboard_focus.set(Some(ev.surface));
+    }
+
+    fn key(&self, ev: &Key) {
+        self.keys.borrow_mut().push((ev.key, ev.state));
+    }
+}
+
+impl UsrZwpRelativePointerOwner for Events {
+    fn relative_motion(&self, ev: &RelativeMotion) {
+        self.relative_motion
+            .set((ev.dx_unaccelerated.to_f64(), ev.dy_unaccelerated.to_f64()));
+    }
+}
+
+async fn roundtrip(con: &Rc<UsrCon>) {
+    let sync = Rc::new(AsyncEvent::default());
+    let sync2 = sync.clone();
+    con.sync(move || sync2.trigger());
+    sync.triggered().await;
+}
+
+/// Test the wl_usr interfaces used by the wayland backend
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let path: Vec<u8> = run
+        .server_addr
+        .sun_path
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    let con = UsrCon::new(
+        &run.state.ring,
+        &run.state.wheel,
+        &run.state.eng,
+        &run.state.dma_buf_ids,
+        &String::from_utf8_lossy(&path),
+        0,
+    )
+    .await?;
+    let events = Rc::new(Events::default());
+    let registry = con.get_registry();
+    registry.owner.set(Some(events.clone()));
+    roundtrip(&con).await;
+
+    let global = |interface: Interface| {
+        let name = events.globals.borrow().get(interface.name()).copied();
+        name.ok_or_else(|| TestError::new(format!("{} is not available", interface.name())))
+    };
+    let comp = Rc::new(UsrWlCompositor {
+        id: con.id(),
+        con: con.clone(),
+        version: Version(4),
+    });
+    con.add_object(comp.clone());
+    registry.request_bind(global(WlCompositor)?, 4, comp.deref());
+    let wm_base = Rc::new(UsrXdgWmBase {
+        id: con.id(),
+        con: con.clone(),
+        version: Version(1),
+    });
+    con.add_object(wm_base.clone());
+    registry.request_bind(global(XdgWmBase)?, 1, wm_base.deref());
+    let shm = Rc::new(UsrWlShm {
+        id: con.id(),
+        con: con.clone(),
+        formats: Default::default(),
+        version: Version(1),
+    });
+    con.add_object(shm.clone());
+    registry.request_bind(global(WlShm)?, 1, shm.deref());
+    let seat = Rc::new(UsrWlSeat {
+        id: con.id(),
+        con: con.clone(),
+        owner: Default::default(),
+        version: Version(5),
+    });
+    seat.owner.set(Some(events.clone()));
+    con.add_object(seat.clone());
+    registry.request_bind(global(WlSeat)?, 5, seat.deref());
+    let rpm = Rc::new(UsrZwpRelativePointerManager {
+        id: con.id(),
+        con: con.clone(),
+        version: Version(1),
+    });
+    con.add_object(rpm.clone());
+    registry.request_bind(global(ZwpRelativePointerManagerV1)?, 1, rpm.deref());
+    roundtrip(&con).await;
+
+    tassert_eq!(
+        events.capabilities.get() & (POINTER | KEYBOARD),
+        POINTER | KEYBOARD
+    );
+    tassert_eq!(events.seat_name.borrow().as_str(), "default");
+
+    let pointer = seat.get_pointer();
+    pointer.owner.set(Some(events.clone()));
+    let rp = rpm.get_relative_pointer(&pointer);
+    rp.owner.set(Some(events.clone()));
+    let keyboard = seat.get_keyboard();
+    keyboard.owner.set(Some(events.clone()));
+
+    let surface = comp.create_surface();
+    let xdg = wm_base.get_xdg_surface(&surface);
+    xdg.owner.set(Some(events.clone()));
+    let tl = xdg.get_toplevel();
+    tl.owner.set(Some(events.clone()));
+    tl.set_title("nested");
+    tl.set_app_id("jay");
+    surface.commit();
+    roundtrip(&con).await;
+    tassert!(events.configured.get());
+
+    let (width, height) = (100, 100);
+    let mem = TestMem::new((width * height * 4) as usize)?;
+    let pool = shm.create_pool(&mem.fd, width * height * 4);
+    let buffer = pool.create_buffer(0, width, height, width * 4, XRGB8888);
+    surface.attach(&buffer);
+    surface.damage_buffer(width, height);
+    surface.commit();
+    roundtrip(&con).await;
+
+    let Some(window) = run
+        .cfg
+        .get_windows()?
+        .into_iter()
+        .find(|&w| run.cfg.get_window_title(w).ok().as_deref() == Some("nested"))
+    else {
+        bail!("The toplevel was not mapped");
+    };
+    tassert_eq!(run.cfg.get_window_app_id(window)?, "jay");
+    roundtrip(&con).await;
+    let (width, height) = events.size.get();
+    tassert!(width > 0 && height > 0);
+
+    let (x, y) = ds.output.node_absolute_position().center();
+    ds.move_to(x, y);
+    run.cfg.focus_window(ds.seat.id(), window)?;
+    roundtrip(&con).await;
+    tassert_eq!(events.pointer_focus.get(), Some(surface.id));
+    tassert_eq!(events.keyboard_focus.get(), Some(surface.id));
+
+    ds.mouse.rel(3.0, 4.0);
+    roundtrip(&con).await;
+    tassert_eq!(events.relative_motion.get(), (3.0, 4.0));
+
+    ds.kb.press(30);
+    roundtrip(&con).await;
+    tassert_eq!(events.keys.borrow().as_slice(), &[(30, 1), (30, 0)]);
+
+    run.cfg.close_window(window)?;
+    roundtrip(&con).await;
+    tassert!(events.closed.get());
+
+    con.kill();
+    Ok(())
+}
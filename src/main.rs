@@ -43,6 +43,7 @@ mod macros;
 #[macro_use]
 mod leaks;
 mod acceptor;
+mod animation;
 mod async_engine;
 mod backend;
 mod backends;
@@ -50,6 +51,7 @@ mod bugs;
 mod cli;
 mod client;
 mod clientmem;
+mod color_management;
 mod compositor;
 mod config;
 mod cursor;
@@ -65,6 +67,8 @@ mod format;
 mod gfx_api;
 mod gfx_apis;
 mod globals;
+mod i3_ipc;
+mod icons;
 mod ifs;
 mod io_uring;
 #[cfg(feature = "it")]
@@ -73,6 +77,8 @@ mod libinput;
 mod logger;
 mod logind;
 mod object;
+mod offscreen_image;
+mod output_mirror;
 mod output_schedule;
 mod pango;
 mod pipewire;
@@ -95,7 +101,9 @@ mod user_session;
 mod utils;
 mod version;
 mod video;
+mod virtual_output;
 mod wheel;
+mod window_rules;
 mod wire;
 mod wire_dbus;
 mod wire_ei;
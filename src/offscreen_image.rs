@@ -0,0 +1,67 @@
+use {
+    crate::{
+        format::Format,
+        gfx_api::{GfxContext, GfxError, GfxFramebuffer, GfxTexture},
+        video::{
+            dmabuf::DmaBufIds,
+            gbm::{GbmBo, GbmError, GBM_BO_USE_RENDERING},
+            Modifier,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum OffscreenImageError {
+    #[error("Could not allocate a buffer")]
+    Allocate(#[source] GbmError),
+    #[error("Could not create a graphics API image from a dma-buf")]
+    CreateImage(#[source] GfxError),
+    #[error("Could not create a framebuffer from a graphics API image")]
+    CreateFramebuffer(#[source] GfxError),
+    #[error("Could not create a texture from a graphics API image")]
+    CreateTexture(#[source] GfxError),
+}
+
+/// A buffer that can be rendered to and sampled from.
+pub struct OffscreenImage {
+    pub bo: GbmBo,
+    pub fb: Rc<dyn GfxFramebuffer>,
+    pub tex: Rc<dyn GfxTexture>,
+}
+
+impl OffscreenImage {
+    pub fn new<'a>(
+        ctx: &Rc<dyn GfxContext>,
+        dma_buf_ids: &DmaBufIds,
+        width: i32,
+        height: i32,
+        format: &'static Format,
+        modifiers: impl IntoIterator<Item = &'a Modifier>,
+    ) -> Result<Self, OffscreenImageError> {
+        let bo = ctx
+            .gbm()
+            .create_bo(
+                dma_buf_ids,
+                width,
+                height,
+                format,
+                modifiers,
+                GBM_BO_USE_RENDERING,
+            )
+            .map_err(OffscreenImageError::Allocate)?;
+        let img = ctx
+            .clone()
+            .dmabuf_img(bo.dmabuf())
+            .map_err(OffscreenImageError::CreateImage)?;
+        let fb = img
+            .clone()
+            .to_framebuffer()
+            .map_err(OffscreenImageError::CreateFramebuffer)?;
+        let tex = img
+            .to_texture()
+            .map_err(OffscreenImageError::CreateTexture)?;
+        Ok(Self { bo, fb, tex })
+    }
+}
@@ -0,0 +1,189 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        format::XRGB8888,
+        gfx_api::{
+            AcquireSync, GfxContext, GfxError, GfxFramebuffer, GfxTexture, ReleaseSync, SyncFile,
+        },
+        offscreen_image::{OffscreenImage, OffscreenImageError},
+        state::{ConnectorData, State},
+        tree::OutputNode,
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum OutputMirrorError {
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Render device does not support XRGB8888 format")]
+    XRGB8888,
+    #[error(transparent)]
+    Image(#[from] OffscreenImageError),
+}
+
+/// A connector that shows the contents of another output.
+///
+/// The connector is not part of the desktop. Whenever the source output presents a frame,
+/// the frame is scaled into an offscreen image of the size of this connector. The backend
+/// of the connector then copies this image into its own framebuffers, which also takes
+/// care of copying it to a different GPU.
+///
+/// Frames are scaled into the back image which then becomes the front image. The backend
+/// only copies from the front image so that a new frame never overwrites the frame that
+/// is being presented.
+pub struct OutputMirror {
+    pub connector: Rc<ConnectorData>,
+    pub source: ConnectorId,
+    state: Rc<State>,
+    mode: Cell<Mode>,
+    front: CloneCell<Option<Rc<MirrorImage>>>,
+    back: CloneCell<Option<Rc<MirrorImage>>>,
+}
+
+struct MirrorImage {
+    ctx: Rc<dyn GfxContext>,
+    mode: Mode,
+    image: OffscreenImage,
+    sync_file: RefCell<Option<SyncFile>>,
+}
+
+impl OutputMirror {
+    pub fn new(
+        state: &Rc<State>,
+        connector: &Rc<ConnectorData>,
+        source: ConnectorId,
+        mode: Mode,
+    ) -> Rc<Self> {
+        Rc::new(Self {
+            connector: connector.clone(),
+            source,
+            state: state.clone(),
+            mode: Cell::new(mode),
+            front: Default::default(),
+            back: Default::default(),
+        })
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode.get()
+    }
+
+    pub fn set_mode(&self, mode: Mode) {
+        self.mode.set(mode);
+        self.damage_source();
+    }
+
+    pub fn clear(&self) {
+        self.front.take();
+        self.back.take();
+    }
+
+    /// Causes the source output to present a new frame.
+    pub fn damage_source(&self) {
+        if let Some(node) = self.state.root.outputs.get(&self.source) {
+            self.state.damage(node.global.pos.get());
+        }
+    }
+
+    /// Copies a frame that was presented on the source output.
+    ///
+    /// The arguments are the same as those of [`OutputNode::perform_screencopies`].
+    pub fn copy_frame(
+        &self,
+        source: &OutputNode,
+        tex: &Rc<dyn GfxTexture>,
+        render_hardware_cursor: bool,
+        x_off: i32,
+        y_off: i32,
+        size: Option<(i32, i32)>,
+    ) {
+        let image = match self.get_back_image() {
+            Ok(i) => i,
+            Err(e) => {
+                log::error!("Could not allocate a mirror buffer: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let res = self.state.perform_mirror_copy(
+            source,
+            tex,
+            &image.image.fb,
+            render_hardware_cursor,
+            x_off,
+            y_off,
+            size,
+        );
+        match res {
+            Ok(sf) => *image.sync_file.borrow_mut() = sf,
+            Err(e) => {
+                log::error!("Could not copy the mirrored frame: {}", ErrorFmt(e));
+                self.back.set(Some(image));
+                return;
+            }
+        }
+        self.back.set(self.front.set(Some(image)));
+        self.connector.connector.damage();
+    }
+
+    /// Copies the most recent frame of the source output into `fb`.
+    ///
+    /// If no frame is available, `fb` is cleared.
+    pub fn present(&self, fb: &Rc<dyn GfxFramebuffer>) -> Result<Option<SyncFile>, GfxError> {
+        if let Some(image) = self.front.get() {
+            if image.mode == self.mode.get() && fb.physical_size() == image.image.fb.physical_size()
+            {
+                if let Some(ctx) = self.state.render_ctx.get() {
+                    if Rc::ptr_eq(&image.ctx, &ctx) {
+                        let sync_file = image.sync_file.borrow_mut().take();
+                        return fb.copy_texture(
+                            &image.image.tex,
+                            AcquireSync::from_sync_file(sync_file),
+                            ReleaseSync::Implicit,
+                            0,
+                            0,
+                        );
+                    }
+                }
+            }
+        }
+        fb.clear_with(0.0, 0.0, 0.0, 1.0)
+    }
+
+    fn get_back_image(&self) -> Result<Rc<MirrorImage>, OutputMirrorError> {
+        let Some(ctx) = self.state.render_ctx.get() else {
+            return Err(OutputMirrorError::NoRenderContext);
+        };
+        let mode = self.mode.get();
+        if let Some(image) = self.back.take() {
+            if Rc::ptr_eq(&image.ctx, &ctx) && image.mode == mode {
+                return Ok(image);
+            }
+        }
+        let formats = ctx.formats();
+        let format = match formats.get(&XRGB8888.drm) {
+            Some(f) => f,
+            None => return Err(OutputMirrorError::XRGB8888),
+        };
+        let image = OffscreenImage::new(
+            &ctx,
+            &self.state.dma_buf_ids,
+            mode.width,
+            mode.height,
+            XRGB8888,
+            &format.write_modifiers,
+        )?;
+        let image = Rc::new(MirrorImage {
+            ctx,
+            mode,
+            image,
+            sync_file: Default::default(),
+        });
+        Ok(image)
+    }
+}
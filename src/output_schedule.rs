@@ -14,6 +14,8 @@ use {
     std::{cell::Cell, rc::Rc},
 };
 
+const DEFAULT_REFRESH_NSEC: u64 = 16_666_667;
+
 pub struct OutputSchedule {
     changed: AsyncEvent,
     run: Cell<bool>,
@@ -34,6 +36,7 @@ pub struct OutputSchedule {
     present_scheduled: Cell<bool>,
     needs_hardware_cursor_commit: Cell<bool>,
     needs_software_cursor_damage: Cell<bool>,
+    needs_animation_frame: Cell<bool>,
 
     iteration: NumCell<u64>,
 }
@@ -55,6 +58,7 @@ impl OutputSchedule {
             present_scheduled: Cell::new(true),
             needs_hardware_cursor_commit: Default::default(),
             needs_software_cursor_damage: Default::default(),
+            needs_animation_frame: Default::default(),
             hardware_cursor: Default::default(),
             persistent: persistent.clone(),
             last_present_nsec: Default::default(),
@@ -91,9 +95,17 @@ impl OutputSchedule {
         self.last_present_nsec.set(self.eng.now().nsec());
         self.present_scheduled.set(false);
         self.iteration.fetch_add(1);
+        if self.needs_animation_frame.take() {
+            self.connector.damage();
+        }
         self.trigger();
     }
 
+    /// Causes another frame to be rendered after the current frame has been presented.
+    pub fn request_animation_frame(&self) {
+        self.needs_animation_frame.set(true);
+    }
+
     pub fn vrr_enabled(&self) -> bool {
         self.vrr_enabled.get()
     }
@@ -136,6 +148,24 @@ impl OutputSchedule {
         }
     }
 
+    /// Ensures that the output is refreshed even if nothing has been damaged.
+    pub fn request_vblank(&self) {
+        self.connector.damage();
+    }
+
+    /// Returns the time at which a content update must be applied to be presented as
+    /// close as possible to `target_nsec`.
+    pub fn latch_time(&self, target_nsec: u64, refresh_rate_millihz: u32) -> u64 {
+        if self.vrr_enabled.get() {
+            return target_nsec;
+        }
+        let refresh_nsec = match refresh_rate_millihz {
+            0 => DEFAULT_REFRESH_NSEC,
+            r => 1_000_000_000_000 / r as u64,
+        };
+        target_nsec.saturating_sub(refresh_nsec / 2)
+    }
+
     async fn run_once(&self) {
         if self.present_scheduled.get() {
             return;
@@ -1,7 +1,9 @@
 mod ptl_display;
+mod ptl_global_shortcuts;
 mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
+mod ptl_screenshot;
 mod ptr_gui;
 
 use {
@@ -18,9 +20,11 @@ use {
         pipewire::pw_con::{PwConHolder, PwConOwner},
         portal::{
             ptl_display::{watch_displays, PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::{add_global_shortcuts_dbus_members, GlobalShortcutsSession},
             ptl_remote_desktop::{add_remote_desktop_dbus_members, RemoteDesktopSession},
             ptl_render_ctx::PortalRenderCtx,
             ptl_screencast::{add_screencast_dbus_members, ScreencastSession},
+            ptl_screenshot::{add_screenshot_dbus_members, ScreenshotRequest},
         },
         utils::{
             clone3::{fork_with_pidfd, Forked},
@@ -49,8 +53,9 @@ use {
     uapi::{c, getpid, OwnedFd},
 };
 
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
 const PORTAL_SUCCESS: u32 = 0;
-#[allow(dead_code)]
 const PORTAL_CANCELLED: u32 = 1;
 #[allow(dead_code)]
 const PORTAL_ENDED: u32 = 2;
@@ -198,6 +203,8 @@ async fn run_async(
         dbus,
         screencasts: Default::default(),
         remote_desktop_sessions: Default::default(),
+        global_shortcuts_sessions: Default::default(),
+        screenshots: Default::default(),
         next_id: NumCell::new(1),
         render_ctxs: Default::default(),
         dma_buf_ids: Default::default(),
@@ -206,14 +213,13 @@ async fn run_async(
         pw_con.con.owner.set(Some(state.clone()));
     }
     let _root = {
-        let obj = state
-            .dbus
-            .add_object("/org/freedesktop/portal/desktop")
-            .unwrap();
+        let obj = state.dbus.add_object(PORTAL_PATH).unwrap();
         if let Some(pw_con) = &pw_con {
             add_screencast_dbus_members(&state, &pw_con.con, &obj);
         }
         add_remote_desktop_dbus_members(&state, &obj);
+        add_global_shortcuts_dbus_members(&state, &obj);
+        add_screenshot_dbus_members(&state, &obj);
         obj
     };
     watch_displays(state.clone()).await;
@@ -293,6 +299,8 @@ struct PortalState {
     dbus: Rc<DbusSocket>,
     screencasts: CopyHashMap<String, Rc<ScreencastSession>>,
     remote_desktop_sessions: CopyHashMap<String, Rc<RemoteDesktopSession>>,
+    global_shortcuts_sessions: CopyHashMap<String, Rc<GlobalShortcutsSession>>,
+    screenshots: CopyHashMap<String, Rc<ScreenshotRequest>>,
     next_id: NumCell<u32>,
     render_ctxs: CopyHashMap<c::dev_t, Weak<PortalRenderCtx>>,
     dma_buf_ids: Rc<DmaBufIds>,
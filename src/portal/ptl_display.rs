@@ -4,8 +4,9 @@ use {
         ifs::wl_seat::POINTER,
         object::Version,
         portal::{
-            ptl_remote_desktop::RemoteDesktopSession, ptl_render_ctx::PortalRenderCtx,
-            ptl_screencast::ScreencastSession, ptr_gui::WindowData, PortalState,
+            ptl_global_shortcuts::GlobalShortcutsSession, ptl_remote_desktop::RemoteDesktopSession,
+            ptl_render_ctx::PortalRenderCtx, ptl_screencast::ScreencastSession,
+            ptl_screenshot::ScreenshotRequest, ptr_gui::WindowData, PortalState,
         },
         utils::{
             bitflags::BitflagsExt, clonecell::CloneCell, copyhashmap::CopyHashMap,
@@ -75,6 +76,8 @@ pub struct PortalDisplay {
     pub windows: CopyHashMap<WlSurfaceId, Rc<WindowData>>,
     pub screencasts: CopyHashMap<String, Rc<ScreencastSession>>,
     pub remote_desktop_sessions: CopyHashMap<String, Rc<RemoteDesktopSession>>,
+    pub global_shortcuts_sessions: CopyHashMap<String, Rc<GlobalShortcutsSession>>,
+    pub screenshots: CopyHashMap<String, Rc<ScreenshotRequest>>,
 }
 
 pub struct PortalOutput {
@@ -199,6 +202,12 @@ impl UsrConOwner for PortalDisplay {
         for sc in self.screencasts.lock().drain_values() {
             sc.kill();
         }
+        for session in self.global_shortcuts_sessions.lock().drain_values() {
+            session.kill();
+        }
+        for request in self.screenshots.lock().drain_values() {
+            request.kill();
+        }
         self.windows.clear();
         self.state.displays.remove(&self.id);
     }
@@ -304,7 +313,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
                     con: dpy.con.clone(),
                     owner: Default::default(),
                     caps: Default::default(),
-                    version: Version(version.min(5)),
+                    version: Version(version.min(9)),
                 });
                 dpy.con.add_object(jc.clone());
                 dpy.registry.request_bind(name, version, jc.deref());
@@ -397,6 +406,8 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
         windows: Default::default(),
         screencasts: Default::default(),
         remote_desktop_sessions: Default::default(),
+        global_shortcuts_sessions: Default::default(),
+        screenshots: Default::default(),
     });
 
     dpy.state.displays.set(dpy.id, dpy.clone());
@@ -0,0 +1,545 @@
+mod global_shortcuts_gui;
+
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply,
+        },
+        ifs::jay_compositor::CREATE_GLOBAL_SHORTCUTS_SINCE,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::global_shortcuts_gui::ShortcutsGui,
+            PortalState, PORTAL_PATH, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                global_shortcuts::{
+                    Activated, BindShortcuts, BindShortcutsReply, CreateSession,
+                    CreateSessionReply, Deactivated, ListShortcuts, ListShortcutsReply,
+                },
+                session::{CloseReply as SessionCloseReply, Closed},
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+        xkbcommon::{keysym_from_name, keysym_name},
+    },
+    jay_config::keyboard::{
+        mods::{Modifiers, ALT, CTRL, LOGO, NUM, SHIFT},
+        syms::KeySym,
+    },
+    std::{
+        borrow::Cow,
+        cell::RefCell,
+        collections::BTreeMap,
+        fs::{self, DirBuilder},
+        ops::Deref,
+        os::unix::fs::DirBuilderExt,
+        path::PathBuf,
+        rc::Rc,
+    },
+};
+
+shared_ids!(GlobalShortcutsSessionId);
+pub struct GlobalShortcutsSession {
+    _id: GlobalShortcutsSessionId,
+    state: Rc<PortalState>,
+    pub app: String,
+    session_obj: DbusObject,
+    shortcuts: RefCell<Vec<Shortcut>>,
+    pub phase: CloneCell<GlobalShortcutsPhase>,
+}
+
+#[derive(Clone)]
+pub enum GlobalShortcutsPhase {
+    Init,
+    Selecting(Rc<SelectingShortcuts>),
+    Bound(Rc<BoundShortcuts>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for GlobalShortcutsPhase {}
+
+pub struct SelectingShortcuts {
+    pub session: Rc<GlobalShortcutsSession>,
+    pub _request_obj: Rc<DbusObject>,
+    pub reply: Rc<PendingReply<BindShortcutsReply<'static>>>,
+    pub shortcuts: Vec<Shortcut>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ShortcutsGui>>,
+}
+
+pub struct BoundShortcuts {
+    session: Rc<GlobalShortcutsSession>,
+    dpy: Rc<PortalDisplay>,
+    shortcuts: Rc<UsrJayGlobalShortcuts>,
+}
+
+#[derive(Clone)]
+pub struct Shortcut {
+    pub id: String,
+    pub description: String,
+    pub trigger: Option<Trigger>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct Trigger {
+    pub mods: Modifiers,
+    pub sym: KeySym,
+}
+
+impl Trigger {
+    /// Parses a trigger in the format of the shortcuts specification, e.g. `CTRL+SHIFT+a`.
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts: Vec<_> = s.split('+').collect();
+        let key = parts.pop()?;
+        let mut mods = Modifiers(0);
+        for part in parts {
+            mods |= match &*part.to_ascii_uppercase() {
+                "CTRL" => CTRL,
+                "ALT" => ALT,
+                "SHIFT" => SHIFT,
+                "LOGO" => LOGO,
+                // The compositor ignores the state of num lock when matching shortcuts.
+                "NUM" => NUM,
+                _ => return None,
+            };
+        }
+        let sym = keysym_from_name(key)?;
+        Some(Self {
+            mods: Modifiers(mods.0 & !NUM.0),
+            sym: KeySym(sym),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        let mut res = String::new();
+        for (m, name) in [
+            (CTRL, "CTRL"),
+            (ALT, "ALT"),
+            (SHIFT, "SHIFT"),
+            (LOGO, "LOGO"),
+        ] {
+            if self.mods.0 & m.0 != 0 {
+                res.push_str(name);
+                res.push('+');
+            }
+        }
+        match keysym_name(self.sym.0) {
+            Some(name) => res.push_str(&name),
+            _ => res.push_str(&format!("{:#x}", self.sym.0)),
+        }
+        res
+    }
+}
+
+/// The bindings that the user has accepted, indexed by app id and shortcut id.
+type PersistentShortcuts = BTreeMap<String, BTreeMap<String, String>>;
+
+fn persistent_shortcuts_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("jay");
+    path.push("global-shortcuts.json");
+    Some(path)
+}
+
+fn load_persistent_shortcuts() -> PersistentShortcuts {
+    let Some(path) = persistent_shortcuts_path() else {
+        return Default::default();
+    };
+    let Ok(contents) = fs::read(&path) else {
+        return Default::default();
+    };
+    match serde_json::from_slice(&contents) {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Could not parse {}: {}", path.display(), ErrorFmt(e));
+            Default::default()
+        }
+    }
+}
+
+fn persist_shortcuts(app: &str, shortcuts: &[Shortcut]) {
+    if app.is_empty() {
+        return;
+    }
+    let Some(path) = persistent_shortcuts_path() else {
+        return;
+    };
+    let mut persistent = load_persistent_shortcuts();
+    let entry = persistent.entry(app.to_string()).or_default();
+    for shortcut in shortcuts {
+        if let Some(trigger) = shortcut.trigger {
+            entry.insert(shortcut.id.clone(), trigger.describe());
+        }
+    }
+    let res = (|| {
+        if let Some(dir) = path.parent() {
+            DirBuilder::new().recursive(true).mode(0o755).create(dir)?;
+        }
+        fs::write(&path, serde_json::to_vec_pretty(&persistent)?)
+    })();
+    if let Err(e) = res {
+        log::error!("Could not write {}: {}", path.display(), ErrorFmt(e));
+    }
+}
+
+fn shortcuts_variant(shortcuts: &[Shortcut]) -> Variant<'static> {
+    let dict_type = DynamicType::DictEntry(
+        Box::new(DynamicType::String),
+        Box::new(DynamicType::Variant),
+    );
+    let shortcut_type = DynamicType::Struct(vec![
+        DynamicType::String,
+        DynamicType::Array(Box::new(dict_type.clone())),
+    ]);
+    let entry = |key: &'static str, value: String| {
+        Variant::DictEntry(
+            Box::new(Variant::String(key.into())),
+            Box::new(Variant::Variant(Box::new(Variant::String(value.into())))),
+        )
+    };
+    let shortcuts = shortcuts
+        .iter()
+        .map(|s| {
+            let trigger = s.trigger.map(|t| t.describe()).unwrap_or_default();
+            Variant::Struct(vec![
+                Variant::String(s.id.clone().into()),
+                Variant::Array(
+                    dict_type.clone(),
+                    vec![
+                        entry("description", s.description.clone()),
+                        entry("trigger_description", trigger),
+                    ],
+                ),
+            ])
+        })
+        .collect();
+    Variant::Array(shortcut_type, shortcuts)
+}
+
+impl SelectingShortcuts {
+    pub fn bind(&self, dpy: &Rc<PortalDisplay>) {
+        persist_shortcuts(&self.session.app, &self.shortcuts);
+        self.session.bind(dpy, self.shortcuts.clone(), &self.reply);
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for BoundShortcuts {
+    fn activated(&self, id: u32, time_usec: u64) {
+        self.session.emit(id, time_usec, true);
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        self.session.emit(id, time_usec, false);
+    }
+}
+
+impl GlobalShortcutsSession {
+    pub(super) fn kill(&self) {
+        self.session_obj.emit_signal(&Closed);
+        self.state
+            .global_shortcuts_sessions
+            .remove(self.session_obj.path());
+        self.detach();
+        if let GlobalShortcutsPhase::Selecting(s) = self.phase.set(GlobalShortcutsPhase::Terminated)
+        {
+            s.reply.err("Session has been terminated");
+            for gui in s.guis.lock().drain_values() {
+                gui.kill(false);
+            }
+        }
+    }
+
+    fn detach(&self) {
+        if let GlobalShortcutsPhase::Bound(b) = self.phase.set(GlobalShortcutsPhase::Init) {
+            b.shortcuts.con.remove_obj(b.shortcuts.deref());
+            b.dpy
+                .global_shortcuts_sessions
+                .remove(self.session_obj.path());
+        }
+    }
+
+    fn emit(&self, id: u32, time_usec: u64, activated: bool) {
+        let shortcuts = self.shortcuts.borrow();
+        let Some(shortcut) = shortcuts.get(id as usize) else {
+            return;
+        };
+        let session_handle = self.session_obj.path().into();
+        let shortcut_id = shortcut.id.deref().into();
+        let timestamp = time_usec / 1000;
+        let options = Default::default();
+        if activated {
+            self.state.dbus.emit_signal(
+                PORTAL_PATH,
+                &Activated {
+                    session_handle: ObjectPath(session_handle),
+                    shortcut_id,
+                    timestamp,
+                    options,
+                },
+            );
+        } else {
+            self.state.dbus.emit_signal(
+                PORTAL_PATH,
+                &Deactivated {
+                    session_handle: ObjectPath(session_handle),
+                    shortcut_id,
+                    timestamp,
+                    options,
+                },
+            );
+        }
+    }
+
+    fn bind(
+        self: &Rc<Self>,
+        dpy: &Rc<PortalDisplay>,
+        shortcuts: Vec<Shortcut>,
+        reply: &PendingReply<BindShortcutsReply<'static>>,
+    ) {
+        let obj = dpy.jc.create_global_shortcuts();
+        for (id, shortcut) in shortcuts.iter().enumerate() {
+            if let Some(trigger) = shortcut.trigger {
+                obj.bind(id as u32, trigger.mods, trigger.sym);
+            }
+        }
+        let bound = Rc::new(BoundShortcuts {
+            session: self.clone(),
+            dpy: dpy.clone(),
+            shortcuts: obj,
+        });
+        bound.shortcuts.owner.set(Some(bound.clone()));
+        dpy.global_shortcuts_sessions
+            .set(self.session_obj.path().to_owned(), self.clone());
+        self.phase.set(GlobalShortcutsPhase::Bound(bound));
+        let variants = [DictEntry {
+            key: "shortcuts".into(),
+            value: shortcuts_variant(&shortcuts),
+        }];
+        *self.shortcuts.borrow_mut() = shortcuts;
+        reply.ok(&BindShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&variants[..]),
+        });
+    }
+
+    fn dbus_bind_shortcuts(
+        self: &Rc<Self>,
+        req: BindShortcuts<'_>,
+        reply: PendingReply<BindShortcutsReply<'static>>,
+    ) {
+        match self.phase.get() {
+            GlobalShortcutsPhase::Init | GlobalShortcutsPhase::Bound(_) => {}
+            _ => {
+                self.kill();
+                reply.err("Session is not in the correct phase for binding shortcuts");
+                return;
+            }
+        }
+        let persistent = load_persistent_shortcuts();
+        let persistent = match self.app.is_empty() {
+            true => None,
+            false => persistent.get(&self.app),
+        };
+        let mut all_persistent = true;
+        let mut shortcuts = vec![];
+        for (id, options) in req.shortcuts.iter() {
+            let mut description = String::new();
+            let mut preferred_trigger = None;
+            for option in options.iter() {
+                match (&*option.key, &option.value) {
+                    ("description", Variant::String(s)) => description = s.to_string(),
+                    ("preferred_trigger", Variant::String(s)) => {
+                        preferred_trigger = Trigger::parse(s)
+                    }
+                    _ => {}
+                }
+            }
+            let trigger = match persistent.and_then(|p| p.get(id.deref())) {
+                Some(t) => Trigger::parse(t),
+                None => {
+                    all_persistent = false;
+                    preferred_trigger
+                }
+            };
+            shortcuts.push(Shortcut {
+                id: id.to_string(),
+                description,
+                trigger,
+            });
+        }
+        self.detach();
+        if all_persistent {
+            let dpy = self
+                .state
+                .displays
+                .lock()
+                .values()
+                .find(|d| d.jc.version >= CREATE_GLOBAL_SHORTCUTS_SINCE)
+                .cloned();
+            if let Some(dpy) = dpy {
+                log::info!("Restoring the shortcuts of `{}`", self.app);
+                self.bind(&dpy, shortcuts, &reply);
+                return;
+            }
+        }
+        let request_obj = match self.state.dbus.add_object(req.handle.to_string()) {
+            Ok(r) => r,
+            Err(_) => {
+                self.kill();
+                reply.err("Request handle is not unique");
+                return;
+            }
+        };
+        {
+            use org::freedesktop::impl_::portal::request::*;
+            request_obj.add_method::<Close, _>({
+                let slf = self.clone();
+                move |_, pr| {
+                    slf.kill();
+                    pr.ok(&CloseReply);
+                }
+            });
+        }
+        let guis = CopyHashMap::new();
+        for dpy in self.state.displays.lock().values() {
+            if dpy.outputs.len() > 0 && dpy.jc.version >= CREATE_GLOBAL_SHORTCUTS_SINCE {
+                guis.set(dpy.id, ShortcutsGui::new(self, dpy, &shortcuts));
+            }
+        }
+        if guis.is_empty() {
+            self.kill();
+            reply.err("There are no running displays");
+            return;
+        }
+        self.phase.set(GlobalShortcutsPhase::Selecting(Rc::new(
+            SelectingShortcuts {
+                session: self.clone(),
+                _request_obj: Rc::new(request_obj),
+                reply: Rc::new(reply),
+                shortcuts,
+                guis,
+            },
+        )));
+    }
+
+    fn dbus_list_shortcuts(
+        self: &Rc<Self>,
+        _req: ListShortcuts<'_>,
+        reply: PendingReply<ListShortcutsReply<'static>>,
+    ) {
+        let variants = [DictEntry {
+            key: "shortcuts".into(),
+            value: shortcuts_variant(&self.shortcuts.borrow()),
+        }];
+        reply.ok(&ListShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&variants[..]),
+        });
+    }
+}
+
+pub(super) fn add_global_shortcuts_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::global_shortcuts::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<BindShortcuts, _>(move |req, pr| {
+        dbus_bind_shortcuts(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<ListShortcuts, _>(move |req, pr| {
+        dbus_list_shortcuts(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create global shortcuts session {:#?}", req);
+    if state
+        .global_shortcuts_sessions
+        .contains(req.session_handle.0.deref())
+    {
+        reply.err("Session already exists");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(GlobalShortcutsSession {
+        _id: state.id(),
+        state: state.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        shortcuts: Default::default(),
+        phase: CloneCell::new(GlobalShortcutsPhase::Init),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    state
+        .global_shortcuts_sessions
+        .set(req.session_handle.0.to_string(), session);
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn dbus_bind_shortcuts(
+    state: &Rc<PortalState>,
+    req: BindShortcuts,
+    reply: PendingReply<BindShortcutsReply<'static>>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_bind_shortcuts(req, reply);
+    }
+}
+
+fn dbus_list_shortcuts(
+    state: &Rc<PortalState>,
+    req: ListShortcuts,
+    reply: PendingReply<ListShortcutsReply<'static>>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_list_shortcuts(req, reply);
+    }
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<GlobalShortcutsSession>> {
+    let res = state.global_shortcuts_sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Global shortcuts session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
@@ -0,0 +1,500 @@
+mod area_gui;
+mod screenshot_gui;
+
+use {
+    crate::{
+        cli::screenshot::{encode_png, read_dmabuf, ScreenshotImage},
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, FALSE},
+        ifs::jay_compositor::TAKE_OUTPUT_SCREENSHOT_SINCE,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_screenshot::{
+                area_gui::{AreaGui, AreaMode},
+                screenshot_gui::ScreenshotGui,
+            },
+            PortalState, PORTAL_CANCELLED, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        wire::jay_screenshot::Dmabuf,
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::screenshot::{
+                PickColor, PickColorReply, Screenshot, ScreenshotReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_jay_compositor::UsrJayCompositor,
+            usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+            usr_jay_select_toplevel::UsrJaySelectToplevel,
+            usr_jay_select_workspace::UsrJaySelectWorkspace,
+        },
+    },
+    chrono::Local,
+    std::{
+        borrow::Cow,
+        cell::RefCell,
+        ops::Deref,
+        os::unix::ffi::OsStrExt,
+        path::{Path, PathBuf},
+        rc::Rc,
+    },
+};
+
+shared_ids!(ScreenshotRequestId);
+pub struct ScreenshotRequest {
+    _id: ScreenshotRequestId,
+    state: Rc<PortalState>,
+    pub app: String,
+    pub kind: ScreenshotKind,
+    handle: String,
+    request_obj: RefCell<Option<DbusObject>>,
+    reply: ScreenshotRequestReply,
+    pub phase: CloneCell<ScreenshotPhase>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ScreenshotKind {
+    Screenshot,
+    PickColor,
+}
+
+enum ScreenshotRequestReply {
+    Screenshot(PendingReply<ScreenshotReply<'static>>),
+    PickColor(PendingReply<PickColorReply<'static>>),
+}
+
+#[derive(Clone)]
+pub enum ScreenshotPhase {
+    Selecting(Rc<SelectingScreenshot>),
+    SelectingWindow(Rc<SelectingWindowScreenshot>),
+    SelectingWorkspace(Rc<SelectingWorkspaceScreenshot>),
+    SelectingArea(Rc<SelectingAreaScreenshot>),
+    Capturing(Rc<CapturingScreenshot>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for ScreenshotPhase {}
+
+pub struct SelectingScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ScreenshotGui>>,
+}
+
+pub struct SelectingWindowScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectToplevel>,
+}
+
+pub struct SelectingWorkspaceScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectWorkspace>,
+}
+
+pub struct SelectingAreaScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<AreaGui>>,
+}
+
+pub struct CapturingScreenshot {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    screenshot: Rc<UsrJayScreenshot>,
+    selection: Option<Selection>,
+}
+
+/// A part of an output selected by the user.
+///
+/// All coordinates are logical coordinates relative to the output. If the selection is
+/// a single point, `x1 == x2` and `y1 == y2`.
+#[derive(Copy, Clone, Debug)]
+pub struct Selection {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub output_width: f32,
+    pub output_height: f32,
+}
+
+impl Selection {
+    /// Returns the selection in the pixel coordinates of a screenshot of the output.
+    fn pixel_rect(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let sx = width as f32 / self.output_width;
+        let sy = height as f32 / self.output_height;
+        let x = |v: f32| (v * sx).round().clamp(0.0, width as f32) as u32;
+        let y = |v: f32| (v * sy).round().clamp(0.0, height as f32) as u32;
+        (
+            x(self.x1.min(self.x2)),
+            y(self.y1.min(self.y2)),
+            x(self.x1.max(self.x2)),
+            y(self.y1.max(self.y2)),
+        )
+    }
+
+    /// Returns the pixel of a screenshot of the output that contains the first point.
+    fn pixel(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let x = self.x1 * width as f32 / self.output_width;
+        let y = self.y1 * height as f32 / self.output_height;
+        let x = (x.max(0.0) as u32).min(width - 1);
+        let y = (y.max(0.0) as u32).min(height - 1);
+        Some((x, y))
+    }
+}
+
+impl ScreenshotRequestReply {
+    fn ok<'a>(&self, response: u32, results: &'a [DictEntry<Cow<'a, str>, Variant<'a>>]) {
+        let results = Cow::Borrowed(results);
+        match self {
+            ScreenshotRequestReply::Screenshot(r) => r.ok(&ScreenshotReply { response, results }),
+            ScreenshotRequestReply::PickColor(r) => r.ok(&PickColorReply { response, results }),
+        }
+    }
+
+    fn err(&self, msg: &str) {
+        match self {
+            ScreenshotRequestReply::Screenshot(r) => r.err(msg),
+            ScreenshotRequestReply::PickColor(r) => r.err(msg),
+        }
+    }
+}
+
+impl ScreenshotRequest {
+    pub fn kill(&self) {
+        if self.terminate() {
+            self.reply.ok(PORTAL_CANCELLED, &[]);
+        }
+    }
+
+    fn fail(&self, msg: &str) {
+        if self.terminate() {
+            self.reply.err(msg);
+        }
+    }
+
+    fn complete(&self, results: &[DictEntry<Cow<'_, str>, Variant<'_>>]) {
+        if self.terminate() {
+            self.reply.ok(PORTAL_SUCCESS, results);
+        }
+    }
+
+    /// Returns `false` if the request has already been terminated.
+    fn terminate(&self) -> bool {
+        let terminated = self.clear_phase();
+        self.state.screenshots.remove(&self.handle);
+        self.request_obj.take();
+        terminated
+    }
+
+    /// Releases all resources of the current phase.
+    ///
+    /// Returns `false` if the request has already been terminated.
+    fn clear_phase(&self) -> bool {
+        match self.phase.set(ScreenshotPhase::Terminated) {
+            ScreenshotPhase::Selecting(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::SelectingWindow(s) => {
+                s.dpy.screenshots.remove(&self.handle);
+                s.dpy.con.remove_obj(s.selector.deref());
+            }
+            ScreenshotPhase::SelectingWorkspace(s) => {
+                s.dpy.screenshots.remove(&self.handle);
+                s.dpy.con.remove_obj(s.selector.deref());
+            }
+            ScreenshotPhase::SelectingArea(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::Capturing(s) => {
+                s.dpy.screenshots.remove(&self.handle);
+                s.screenshot.owner.take();
+            }
+            ScreenshotPhase::Terminated => return false,
+        }
+        true
+    }
+
+    fn eligible_displays(&self) -> Vec<Rc<PortalDisplay>> {
+        self.state
+            .displays
+            .lock()
+            .values()
+            .filter(|dpy| dpy.outputs.len() > 0)
+            .filter(|dpy| dpy.jc.version >= TAKE_OUTPUT_SCREENSHOT_SINCE)
+            .cloned()
+            .collect()
+    }
+
+    fn select(self: &Rc<Self>) {
+        let guis = CopyHashMap::new();
+        for dpy in self.eligible_displays() {
+            guis.set(dpy.id, ScreenshotGui::new(self, &dpy));
+        }
+        if guis.is_empty() {
+            self.fail("There are no running displays");
+            return;
+        }
+        self.phase
+            .set(ScreenshotPhase::Selecting(Rc::new(SelectingScreenshot {
+                guis,
+            })));
+    }
+
+    pub fn select_area(self: &Rc<Self>, displays: &[Rc<PortalDisplay>], mode: AreaMode) {
+        if !self.clear_phase() {
+            return;
+        }
+        let guis = CopyHashMap::new();
+        for dpy in displays {
+            guis.set(dpy.id, AreaGui::new(self, dpy, mode));
+        }
+        if guis.is_empty() {
+            self.fail("There are no running displays");
+            return;
+        }
+        self.phase.set(ScreenshotPhase::SelectingArea(Rc::new(
+            SelectingAreaScreenshot { guis },
+        )));
+    }
+
+    fn capture_all(self: &Rc<Self>) {
+        match self.eligible_displays().first() {
+            Some(dpy) => self.capture(dpy, None, |jc| jc.take_screenshot(false)),
+            _ => self.fail("There are no running displays"),
+        }
+    }
+
+    /// Takes a screenshot with `f`.
+    ///
+    /// All windows of the portal are destroyed before the screenshot is requested so
+    /// that they do not show up in the screenshot.
+    pub fn capture(
+        self: &Rc<Self>,
+        dpy: &Rc<PortalDisplay>,
+        selection: Option<Selection>,
+        f: impl FnOnce(&UsrJayCompositor) -> Rc<UsrJayScreenshot>,
+    ) {
+        if !self.clear_phase() {
+            return;
+        }
+        let capturing = Rc::new(CapturingScreenshot {
+            request: self.clone(),
+            dpy: dpy.clone(),
+            screenshot: f(&dpy.jc),
+            selection,
+        });
+        capturing.screenshot.owner.set(Some(capturing.clone()));
+        dpy.screenshots.set(self.handle.clone(), self.clone());
+        self.phase.set(ScreenshotPhase::Capturing(capturing));
+    }
+
+    fn save(&self, image: &ScreenshotImage, selection: Option<Selection>) {
+        let (x1, y1, x2, y2) = match selection {
+            Some(s) => s.pixel_rect(image.width, image.height),
+            _ => (0, 0, image.width, image.height),
+        };
+        if x1 >= x2 || y1 >= y2 {
+            self.fail("The selected area is empty");
+            return;
+        }
+        let offset = y1 as usize * image.stride as usize + x1 as usize * 4;
+        let png = encode_png(&image.data[offset..], x2 - x1, y2 - y1, image.stride);
+        let Some(path) = screenshot_path() else {
+            self.fail("Could not determine the pictures directory");
+            return;
+        };
+        if let Err(e) = std::fs::write(&path, png) {
+            let msg = format!("Could not write `{}`: {}", path.display(), ErrorFmt(e));
+            log::error!("{}", msg);
+            self.fail(&msg);
+            return;
+        }
+        log::info!("Saved screenshot to {}", path.display());
+        let results = [DictEntry {
+            key: "uri".into(),
+            value: Variant::String(file_uri(&path).into()),
+        }];
+        self.complete(&results);
+    }
+
+    fn pick(&self, image: &ScreenshotImage, selection: Option<Selection>) {
+        let pixel = selection.and_then(|s| s.pixel(image.width, image.height));
+        let Some((x, y)) = pixel else {
+            self.fail("No pixel has been selected");
+            return;
+        };
+        let offset = y as usize * image.stride as usize + x as usize * 4;
+        let px = &image.data[offset..offset + 4];
+        let c = |v: u8| Variant::F64(v as f64 / 255.0);
+        let results = [DictEntry {
+            key: "color".into(),
+            value: Variant::Struct(vec![c(px[2]), c(px[1]), c(px[0])]),
+        }];
+        self.complete(&results);
+    }
+}
+
+impl UsrJayScreenshotOwner for CapturingScreenshot {
+    fn dmabuf(&self, ev: &Dmabuf) {
+        let image = match read_dmabuf(&self.dpy.state.dma_buf_ids, ev) {
+            Ok(i) => i,
+            Err(e) => {
+                let msg = ErrorFmt(e).to_string();
+                log::error!("Could not read the screenshot: {}", msg);
+                self.request.fail(&msg);
+                return;
+            }
+        };
+        match self.request.kind {
+            ScreenshotKind::Screenshot => self.request.save(&image, self.selection),
+            ScreenshotKind::PickColor => self.request.pick(&image, self.selection),
+        }
+    }
+
+    fn error(&self, msg: &str) {
+        log::error!("Could not take a screenshot: {}", msg);
+        self.request.fail(msg);
+    }
+}
+
+fn screenshot_path() -> Option<PathBuf> {
+    let mut path = dirs::picture_dir().or_else(dirs::home_dir)?;
+    path.push(Local::now().format("%Y-%m-%d-%H%M%S_jay.png").to_string());
+    Some(path)
+}
+
+fn file_uri(path: &Path) -> String {
+    let mut uri = "file://".to_string();
+    for &b in path.as_os_str().as_bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(b as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
+
+pub(super) fn add_screenshot_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::screenshot::*;
+    let state = state_.clone();
+    object.add_method::<Screenshot, _>(move |req, pr| {
+        dbus_screenshot(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<PickColor, _>(move |req, pr| {
+        dbus_pick_color(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn dbus_screenshot(
+    state: &Rc<PortalState>,
+    req: Screenshot,
+    reply: PendingReply<ScreenshotReply<'static>>,
+) {
+    log::info!("Screenshot {:#?}", req);
+    let mut interactive = false;
+    let mut permission_store_checked = false;
+    for option in req.options.iter() {
+        match (&*option.key, &option.value) {
+            ("interactive", Variant::Bool(b)) => interactive = *b != FALSE,
+            ("permission_store_checked", Variant::Bool(b)) => {
+                permission_store_checked = *b != FALSE
+            }
+            _ => {}
+        }
+    }
+    let reply = ScreenshotRequestReply::Screenshot(reply);
+    let Some(request) = create_request(
+        state,
+        &req.handle.0,
+        &req.app_id,
+        ScreenshotKind::Screenshot,
+        reply,
+    ) else {
+        return;
+    };
+    if !interactive && permission_store_checked {
+        request.capture_all();
+    } else {
+        request.select();
+    }
+}
+
+fn dbus_pick_color(
+    state: &Rc<PortalState>,
+    req: PickColor,
+    reply: PendingReply<PickColorReply<'static>>,
+) {
+    log::info!("Pick color {:#?}", req);
+    let reply = ScreenshotRequestReply::PickColor(reply);
+    let Some(request) = create_request(
+        state,
+        &req.handle.0,
+        &req.app_id,
+        ScreenshotKind::PickColor,
+        reply,
+    ) else {
+        return;
+    };
+    let displays = request.eligible_displays();
+    request.select_area(&displays, AreaMode::Point);
+}
+
+fn create_request(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app_id: &str,
+    kind: ScreenshotKind,
+    reply: ScreenshotRequestReply,
+) -> Option<Rc<ScreenshotRequest>> {
+    if state.screenshots.contains(handle) {
+        reply.err("Request already exists");
+        return None;
+    }
+    let obj = match state.dbus.add_object(handle.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return None;
+        }
+    };
+    let request = Rc::new(ScreenshotRequest {
+        _id: state.id(),
+        state: state.clone(),
+        app: app_id.to_string(),
+        kind,
+        handle: handle.to_string(),
+        request_obj: Default::default(),
+        reply,
+        phase: CloneCell::new(ScreenshotPhase::Selecting(Rc::new(SelectingScreenshot {
+            guis: Default::default(),
+        }))),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        obj.add_method::<Close, _>({
+            let request = request.clone();
+            move |_, pr| {
+                request.kill();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    *request.request_obj.borrow_mut() = Some(obj);
+    state.screenshots.set(handle.to_string(), request.clone());
+    Some(request)
+}
@@ -0,0 +1,239 @@
+use {
+    crate::{
+        cursor::KnownCursor,
+        gfx_api::GfxContext,
+        ifs::wl_seat::{
+            wl_pointer::{PRESSED, RELEASED},
+            BTN_LEFT, BTN_RIGHT,
+        },
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{ScreenshotPhase, ScreenshotRequest, Selection},
+            ptr_gui::{GuiElement, GuiElementData, OverlayWindow, OverlayWindowOwner},
+        },
+        renderer::renderer_base::RendererBase,
+        theme::Color,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+const BORDER: f32 = 2.0;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum AreaMode {
+    /// The user drags a rectangle.
+    Area,
+    /// The user clicks on a single point.
+    Point,
+}
+
+pub struct AreaGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<AreaGuiSurface>>,
+}
+
+pub struct AreaGuiSurface {
+    gui: Rc<AreaGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct AreaSelector {
+    data: GuiElementData,
+    mode: AreaMode,
+    owner: CloneCell<Option<Rc<AreaGuiSurface>>>,
+    pos: Cell<(f32, f32)>,
+    anchor: Cell<Option<(f32, f32)>>,
+}
+
+impl AreaGui {
+    pub fn new(
+        request: &Rc<ScreenshotRequest>,
+        dpy: &Rc<PortalDisplay>,
+        mode: AreaMode,
+    ) -> Rc<Self> {
+        let gui = Rc::new(AreaGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let ags = Rc::new(AreaGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new_fullscreen(output),
+            });
+            ags.overlay.owner.set(Some(ags.clone()));
+            let element = Rc::new(AreaSelector {
+                data: Default::default(),
+                mode,
+                owner: CloneCell::new(Some(ags.clone())),
+                pos: Default::default(),
+                anchor: Default::default(),
+            });
+            ags.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(ags.overlay.data.surface.id, ags.overlay.data.clone());
+            gui.surfaces.set(output.global_id, ags);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::SelectingArea(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.kill();
+            }
+        }
+    }
+}
+
+impl OverlayWindowOwner for AreaGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl AreaGuiSurface {
+    fn selected(&self, selection: Selection) {
+        let request = &self.gui.request;
+        if !matches!(request.phase.get(), ScreenshotPhase::SelectingArea(_)) {
+            return;
+        }
+        log::info!("User has selected {:?}", selection);
+        let output = &self.output;
+        request.capture(&self.gui.dpy, Some(selection), |jc| {
+            jc.take_output_screenshot(&output.wl, false)
+        });
+    }
+}
+
+impl AreaSelector {
+    fn rect(&self) -> Option<(f32, f32, f32, f32)> {
+        let (x1, y1) = self.anchor.get()?;
+        let (x2, y2) = self.pos.get();
+        Some((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
+    }
+
+    fn selection(&self, (x1, y1): (f32, f32), (x2, y2): (f32, f32)) -> Selection {
+        Selection {
+            x1,
+            y1,
+            x2,
+            y2,
+            output_width: self.data.width.get(),
+            output_height: self.data.height.get(),
+        }
+    }
+
+    fn schedule_render(&self) {
+        if let Some(owner) = self.owner.get() {
+            owner.overlay.data.schedule_render();
+        }
+    }
+}
+
+impl GuiElement for AreaSelector {
+    fn data(&self) -> &GuiElementData {
+        &self.data
+    }
+
+    fn layout(
+        &self,
+        _ctx: &Rc<dyn GfxContext>,
+        _scale: f32,
+        max_width: f32,
+        max_height: f32,
+    ) -> (f32, f32) {
+        if max_width.is_finite() && max_height.is_finite() {
+            (max_width, max_height)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    fn render_at(&self, r: &mut RendererBase, x: f32, y: f32) {
+        if self.mode == AreaMode::Point {
+            return;
+        }
+        let width = self.data.width.get();
+        let height = self.data.height.get();
+        let shade = Color::from_rgba_straight(0, 0, 0, 100);
+        let Some((x1, y1, x2, y2)) = self.rect() else {
+            r.fill_boxes2_f(&[(0.0, 0.0, width, height)], &shade, x, y);
+            return;
+        };
+        let shaded = [
+            (0.0, 0.0, width, y1),
+            (0.0, y2, width, height),
+            (0.0, y1, x1, y2),
+            (x2, y1, width, y2),
+        ];
+        r.fill_boxes2_f(&shaded, &shade, x, y);
+        let border = [
+            (x1 - BORDER, y1 - BORDER, x2 + BORDER, y1),
+            (x1 - BORDER, y2, x2 + BORDER, y2 + BORDER),
+            (x1 - BORDER, y1, x1, y2),
+            (x2, y1, x2 + BORDER, y2),
+        ];
+        r.fill_boxes2_f(&border, &Color::from_gray(255), x, y);
+    }
+
+    fn child_at(&self, _x: f32, _y: f32) -> Option<Rc<dyn GuiElement>> {
+        None
+    }
+
+    fn hover_cursor(&self) -> KnownCursor {
+        KnownCursor::Crosshair
+    }
+
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        let Some(owner) = self.owner.get() else {
+            return;
+        };
+        if button == BTN_RIGHT && state == PRESSED {
+            log::info!("User has aborted the selection");
+            owner.gui.request.kill();
+            return;
+        }
+        if button != BTN_LEFT {
+            return;
+        }
+        let pos = self.pos.get();
+        match (self.mode, state) {
+            (AreaMode::Point, PRESSED) => owner.selected(self.selection(pos, pos)),
+            (AreaMode::Area, PRESSED) => {
+                self.anchor.set(Some(pos));
+                self.schedule_render();
+            }
+            (AreaMode::Area, RELEASED) => {
+                if let Some(anchor) = self.anchor.take() {
+                    owner.selected(self.selection(anchor, pos));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn motion(&self, _seat: &PortalSeat, x: f32, y: f32) {
+        self.pos.set((x, y));
+        if self.anchor.get().is_some() {
+            self.schedule_render();
+        }
+    }
+
+    fn destroy(&self) {
+        self.owner.take();
+    }
+}
@@ -0,0 +1,285 @@
+use {
+    crate::{
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{
+                area_gui::AreaMode, ScreenshotPhase, ScreenshotRequest, SelectingWindowScreenshot,
+                SelectingWorkspaceScreenshot,
+            },
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+        wl_usr::usr_ifs::{
+            usr_jay_select_toplevel::UsrJaySelectToplevelOwner,
+            usr_jay_select_workspace::UsrJaySelectWorkspaceOwner, usr_jay_toplevel::UsrJayToplevel,
+            usr_jay_workspace::UsrJayWorkspace,
+        },
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct ScreenshotGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ScreenshotGuiSurface>>,
+}
+
+pub struct ScreenshotGuiSurface {
+    gui: Rc<ScreenshotGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<ScreenshotGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Output,
+    Everything,
+    SelectWorkspace,
+    SelectWindow,
+    SelectArea,
+    Reject,
+}
+
+impl ScreenshotGui {
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::Selecting(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.kill();
+            }
+        }
+    }
+}
+
+fn create_accept_gui(surface: &Rc<ScreenshotGuiSurface>) -> Rc<dyn GuiElement> {
+    let app = &surface.gui.request.app;
+    let text = if app.is_empty() {
+        format!("An application wants to take a screenshot")
+    } else {
+        format!("`{}` wants to take a screenshot", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let output_button = static_button(surface, ButtonRole::Output, "Capture This Output");
+    let everything_button = static_button(surface, ButtonRole::Everything, "Capture Everything");
+    let workspace_button =
+        static_button(surface, ButtonRole::SelectWorkspace, "Capture A Workspace");
+    let window_button = static_button(surface, ButtonRole::SelectWindow, "Capture A Window");
+    let area_button = static_button(surface, ButtonRole::SelectArea, "Capture An Area");
+    let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
+    for button in [
+        &output_button,
+        &everything_button,
+        &workspace_button,
+        &window_button,
+        &area_button,
+        &reject_button,
+    ] {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    for button in [
+        &output_button,
+        &everything_button,
+        &workspace_button,
+        &window_button,
+        &area_button,
+    ] {
+        button.bg_color.set(Color::from_rgb(170, 200, 170));
+        button.bg_hover_color.set(Color::from_rgb(170, 255, 170));
+    }
+    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label, output_button, everything_button];
+    if surface.gui.dpy.jc.caps.select_workspace.get() {
+        elements.push(workspace_button);
+    }
+    if surface.gui.dpy.jc.caps.window_capture.get() {
+        elements.push(window_button);
+    }
+    elements.push(area_button);
+    elements.push(reject_button);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+impl OverlayWindowOwner for ScreenshotGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ScreenshotGui {
+    pub fn new(request: &Rc<ScreenshotRequest>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ScreenshotGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let sgs = Rc::new(ScreenshotGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            sgs.overlay.owner.set(Some(sgs.clone()));
+            let element = create_accept_gui(&sgs);
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let request = &self.surface.gui.request;
+        if !matches!(request.phase.get(), ScreenshotPhase::Selecting(_)) {
+            return;
+        }
+        let dpy = &self.surface.output.dpy;
+        match self.role {
+            ButtonRole::Output => {
+                log::info!("User has selected an output");
+                let output = &self.surface.output;
+                request.capture(dpy, None, |jc| jc.take_output_screenshot(&output.wl, false));
+            }
+            ButtonRole::Everything => {
+                log::info!("User has selected all outputs");
+                request.capture(dpy, None, |jc| jc.take_screenshot(false));
+            }
+            ButtonRole::SelectWorkspace => {
+                log::info!("User wants to select a workspace");
+                request.clear_phase();
+                let selector = dpy.jc.select_workspace(&seat.wl);
+                let selecting = Rc::new(SelectingWorkspaceScreenshot {
+                    request: request.clone(),
+                    dpy: dpy.clone(),
+                    selector: selector.clone(),
+                });
+                selector.owner.set(Some(selecting.clone()));
+                dpy.screenshots.set(request.handle.clone(), request.clone());
+                request
+                    .phase
+                    .set(ScreenshotPhase::SelectingWorkspace(selecting));
+            }
+            ButtonRole::SelectWindow => {
+                log::info!("User wants to select a window");
+                request.clear_phase();
+                let selector = dpy.jc.select_toplevel(&seat.wl);
+                let selecting = Rc::new(SelectingWindowScreenshot {
+                    request: request.clone(),
+                    dpy: dpy.clone(),
+                    selector: selector.clone(),
+                });
+                selector.owner.set(Some(selecting.clone()));
+                dpy.screenshots.set(request.handle.clone(), request.clone());
+                request
+                    .phase
+                    .set(ScreenshotPhase::SelectingWindow(selecting));
+            }
+            ButtonRole::SelectArea => {
+                log::info!("User wants to select an area");
+                request.select_area(std::slice::from_ref(dpy), AreaMode::Area);
+            }
+            ButtonRole::Reject => {
+                log::info!("User has rejected the screenshot request");
+                request.kill();
+            }
+        }
+    }
+}
+
+impl UsrJaySelectToplevelOwner for SelectingWindowScreenshot {
+    fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
+        let Some(tl) = tl else {
+            log::info!("User has aborted the selection");
+            self.request.kill();
+            return;
+        };
+        if !matches!(
+            self.request.phase.get(),
+            ScreenshotPhase::SelectingWindow(_)
+        ) {
+            self.dpy.con.remove_obj(&*tl);
+            return;
+        }
+        log::info!("User has selected a window");
+        self.request.capture(&self.dpy, None, |jc| {
+            jc.take_toplevel_screenshot(&tl, false)
+        });
+        self.dpy.con.remove_obj(&*tl);
+    }
+}
+
+impl UsrJaySelectWorkspaceOwner for SelectingWorkspaceScreenshot {
+    fn done(&self, output: u32, ws: Option<Rc<UsrJayWorkspace>>) {
+        let Some(ws) = ws else {
+            log::info!("User has aborted the selection");
+            self.request.kill();
+            return;
+        };
+        self.dpy.con.remove_obj(&*ws);
+        if !matches!(
+            self.request.phase.get(),
+            ScreenshotPhase::SelectingWorkspace(_)
+        ) {
+            return;
+        }
+        log::info!("User has selected a workspace");
+        let Some(output) = self.dpy.outputs.get(&output) else {
+            log::warn!("Workspace does not belong to any known output");
+            self.request
+                .fail("Workspace does not belong to any known output");
+            return;
+        };
+        self.request.capture(&self.dpy, None, |jc| {
+            jc.take_output_screenshot(&output.wl, false)
+        });
+    }
+}
+
+fn static_button(surface: &Rc<ScreenshotGuiSurface>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
@@ -5,7 +5,10 @@ use {
         fixed::Fixed,
         format::ARGB8888,
         gfx_api::{AcquireSync, GfxContext, GfxFramebuffer, ReleaseSync},
-        ifs::zwlr_layer_shell_v1::OVERLAY,
+        ifs::{
+            wl_surface::zwlr_layer_surface_v1::{BOTTOM, LEFT, RIGHT, TOP},
+            zwlr_layer_shell_v1::OVERLAY,
+        },
         portal::ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
         renderer::renderer_base::RendererBase,
         scale::Scale,
@@ -74,6 +77,14 @@ pub trait GuiElement {
         false
     }
 
+    /// Called when the pointer moves over this element. The coordinates are relative to
+    /// the window.
+    fn motion(&self, seat: &PortalSeat, x: f32, y: f32) {
+        let _ = seat;
+        let _ = x;
+        let _ = y;
+    }
+
     fn destroy(&self) {}
 }
 
@@ -473,6 +484,7 @@ pub struct OverlayWindow {
     pub layer_surface: Rc<UsrWlrLayerSurface>,
     pub data: Rc<WindowData>,
     pub owner: CloneCell<Option<Rc<dyn OverlayWindowOwner>>>,
+    fullscreen: bool,
 }
 
 pub trait OverlayWindowOwner {
@@ -488,6 +500,8 @@ pub struct WindowData {
     pub render_task: Cell<Option<SpawnedFuture<()>>>,
     pub dpy: Rc<PortalDisplay>,
     pub content: CloneCell<Option<Rc<dyn GuiElement>>>,
+    pub bg_color: Cell<Color>,
+    pub max_size: Cell<(f32, f32)>,
     pub surface: Rc<UsrWlSurface>,
     pub viewport: Rc<UsrWpViewport>,
     pub fractional_scale: Rc<UsrWpFractionalScale>,
@@ -514,8 +528,10 @@ pub trait WindowDataOwner {
 
 impl WindowDataOwner for OverlayWindow {
     fn post_layout(&self) {
-        self.layer_surface
-            .set_size(self.data.width.get(), self.data.height.get());
+        if !self.fullscreen {
+            self.layer_surface
+                .set_size(self.data.width.get(), self.data.height.get());
+        }
         self.data.surface.commit();
     }
 
@@ -533,16 +549,35 @@ const NUM_BUFFERS: usize = 2;
 
 impl OverlayWindow {
     pub fn new(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::new_(output, false)
+    }
+
+    /// Creates a transparent window that covers the entire output.
+    ///
+    /// The content is laid out with the size of the output as its maximum size.
+    pub fn new_fullscreen(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::new_(output, true)
+    }
+
+    fn new_(output: &Rc<PortalOutput>, fullscreen: bool) -> Rc<Self> {
         let data = WindowData::new(&output.dpy);
         let layer_surface = output
             .dpy
             .ls
             .get_layer_surface(&data.surface, &output.wl, OVERLAY);
-        layer_surface.set_size(1, 1);
+        if fullscreen {
+            data.bg_color.set(Color::TRANSPARENT);
+            layer_surface.set_anchor(TOP | BOTTOM | LEFT | RIGHT);
+            layer_surface.set_exclusive_zone(-1);
+            layer_surface.set_size(0, 0);
+        } else {
+            layer_surface.set_size(1, 1);
+        }
         let slf = Rc::new(Self {
             layer_surface,
             data,
             owner: Default::default(),
+            fullscreen,
         });
         slf.data.owner.set(Some(slf.clone()));
         slf.layer_surface.owner.set(Some(slf.clone()));
@@ -574,6 +609,8 @@ impl WindowData {
             render_task: Cell::new(None),
             dpy: dpy.clone(),
             content: Default::default(),
+            bg_color: Cell::new(Color::from_gray(0)),
+            max_size: Cell::new((f32::INFINITY, f32::INFINITY)),
             surface,
             viewport,
             scale: Cell::new(Scale::from_int(1)),
@@ -599,7 +636,8 @@ impl WindowData {
             Some(c) => c,
             _ => return,
         };
-        let (mut width, mut height) = content.layout(&ctx.ctx, scale, f32::INFINITY, f32::INFINITY);
+        let (max_width, max_height) = self.max_size.get();
+        let (mut width, mut height) = content.layout(&ctx.ctx, scale, max_width, max_height);
         content.data().width.set(width);
         content.data().height.set(height);
         width = width.max(1.0);
@@ -637,7 +675,7 @@ impl WindowData {
 
         let res = buf
             .fb
-            .render_custom(self.scale.get(), Some(&Color::from_gray(0)), &mut |r| {
+            .render_custom(self.scale.get(), Some(&self.bg_color.get()), &mut |r| {
                 if let Some(content) = self.content.get() {
                     content.render_at(r, 0.0, 0.0)
                 }
@@ -826,6 +864,10 @@ impl WindowData {
         if seat.cursor.replace(Some(cursor)) != Some(cursor) {
             pseat.jay_pointer.set_known_cursor(cursor);
         }
+
+        if let Some(e) = &new_element {
+            e.motion(pseat, x, y);
+        }
     }
 
     pub fn button(&self, pseat: &PortalSeat, button: u32, state: u32) {
@@ -879,7 +921,14 @@ impl UsrWpFractionalScaleOwner for WindowData {
 }
 
 impl UsrWlrLayerSurfaceOwner for OverlayWindow {
-    fn configure(&self, _ev: &Configure) {
+    fn configure(&self, ev: &Configure) {
+        if self.fullscreen {
+            let size = (ev.width as f32, ev.height as f32);
+            if self.data.max_size.replace(size) != size && !self.data.first_scale.get() {
+                self.data.layout();
+                self.data.allocate_buffers();
+            }
+        }
         self.data.schedule_render();
     }
 
@@ -1,6 +1,8 @@
 use {
     crate::{
-        gfx_api::{AcquireSync, GfxApiOpt, ReleaseSync, SampleRect},
+        color_management::ColorDescription,
+        gfx_api::{AcquireSync, GfxApiOpt, GfxTexture, ReleaseSync, SampleRect},
+        icons::title_icon_size,
         ifs::{
             wl_callback::WlCallback,
             wl_surface::{
@@ -17,8 +19,8 @@ use {
         state::State,
         theme::Color,
         tree::{
-            ContainerNode, DisplayNode, FloatNode, OutputNode, OutputNodeId, PlaceholderNode,
-            ToplevelData, ToplevelNodeBase, WorkspaceNode,
+            ContainerNode, DisplayNode, FloatNode, Node, OutputNode, OutputNodeId, PlaceholderNode,
+            ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
     },
     std::{
@@ -75,6 +77,9 @@ pub struct Renderer<'a> {
     pub result: Option<&'a mut RenderResult>,
     pub logical_extents: Rect,
     pub pixel_extents: Rect,
+    pub color_description: Rc<ColorDescription>,
+    /// Multiplied into the alpha value of all surfaces.
+    pub opacity: f32,
 }
 
 impl Renderer<'_> {
@@ -125,6 +130,7 @@ impl Renderer<'_> {
         }
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
+        let mut slide_dx = 0;
         if let Some(fs) = fullscreen {
             fs.tl_as_node().node_render(self, x, y, None);
         } else {
@@ -196,9 +202,10 @@ impl Renderer<'_> {
                 }
             }
             if let Some(ws) = output.workspace.get() {
-                self.render_workspace(&ws, x, y + th + 1);
+                slide_dx = self.render_output_workspace(output, &ws, x, y + th + 1);
             }
         }
+        let slide_ws = output.workspace.get().map(|ws| ws.id);
         macro_rules! render_stacked {
             ($stack:expr) => {
                 for stacked in $stack.iter() {
@@ -206,7 +213,14 @@ impl Renderer<'_> {
                         self.base.ops.push(GfxApiOpt::Sync);
                         let pos = stacked.node_absolute_position();
                         if pos.intersects(&opos) {
-                            let (x, y) = opos.translate(pos.x1(), pos.y1());
+                            let (mut x, y) = opos.translate(pos.x1(), pos.y1());
+                            if slide_dx != 0 {
+                                if let Some(float) = stacked.deref().clone().node_into_float() {
+                                    if Some(float.workspace.get().id) == slide_ws {
+                                        x += slide_dx;
+                                    }
+                                }
+                            }
                             stacked.node_render(self, x, y, None);
                         }
                     }
@@ -224,6 +238,46 @@ impl Renderer<'_> {
                 self.base.fill_boxes(&[bounds], &color);
             }
         }
+        if self.result.is_some() && self.state.animations.tick(output.id, self.state.now_nsec()) {
+            output.schedule.request_animation_frame();
+        }
+    }
+
+    /// Renders the active workspace of the output and its animations.
+    ///
+    /// Returns the horizontal offset of the workspace while it is sliding in.
+    fn render_output_workspace(
+        &mut self,
+        output: &OutputNode,
+        ws: &WorkspaceNode,
+        x: i32,
+        y: i32,
+    ) -> i32 {
+        let animations = &self.state.animations;
+        let now = self.state.now_nsec();
+        let mut dx = 0;
+        if let Some((slide, p)) = animations.workspace_slide(output.id, ws.id, now) {
+            let width = ws.position.get().width();
+            let offset = (p * width as f64).round() as i32;
+            let old_x = match slide.forward {
+                true => {
+                    dx = width - offset;
+                    x - offset
+                }
+                false => {
+                    dx = offset - width;
+                    x + offset
+                }
+            };
+            slide.snapshot.render(self, old_x, y, 1.0);
+        }
+        self.render_workspace(ws, x + dx, y);
+        let pos = ws.position.get();
+        for (closing, alpha) in animations.closing_windows(ws.id, now) {
+            let (cx, cy) = pos.translate(closing.rect.x1(), closing.rect.y1());
+            closing.snapshot.render(self, x + dx + cx, y + cy, alpha);
+        }
+        dx
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
@@ -287,6 +341,11 @@ impl Renderer<'_> {
                     .get();
                 self.base.fill_boxes2(std::slice::from_ref(lar), &c, x, y);
             }
+            if let Some(icons) = rd.icons.get(&self.base.scale) {
+                for icon in icons {
+                    self.render_title_icon(&icon.tex, x + icon.x, y + icon.y, icon.size);
+                }
+            }
             if let Some(titles) = rd.titles.get(&self.base.scale) {
                 for title in titles {
                     let (x, y) = self.base.scale_point(x + title.x, y + title.y);
@@ -307,29 +366,50 @@ impl Renderer<'_> {
             }
         }
         if let Some(child) = container.mono_child.get() {
-            let body = container.mono_body.get().move_(x, y);
-            let body = self.base.scale_rect(body);
+            let body = container.mono_body.get();
             let content = container.mono_content.get();
-            child
-                .node
-                .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+            self.render_container_child(&*child.node, body, content, x, y);
         } else {
             for child in container.children.iter() {
                 let body = child.body.get();
                 if body.x1() >= container.width.get() || body.y1() >= container.height.get() {
                     break;
                 }
-                let body = body.move_(x, y);
-                let body = self.base.scale_rect(body);
                 let content = child.content.get();
-                child
-                    .node
-                    .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+                self.render_container_child(&*child.node, body, content, x, y);
             }
         }
         self.render_tl_aux(container.tl_data(), None, false);
     }
 
+    fn render_container_child(
+        &mut self,
+        node: &dyn ToplevelNode,
+        mut body: Rect,
+        content: Rect,
+        x: i32,
+        y: i32,
+    ) {
+        let animations = &self.state.animations;
+        let now = self.state.now_nsec();
+        let (mut dx, mut dy) = (0, 0);
+        if let Some((current, target)) = animations.window_rect(node.node_id(), now) {
+            dx = current.x1() - target.x1();
+            dy = current.y1() - target.y1();
+            body = current.at_point(body.x1() + dx, body.y1() + dy);
+        }
+        let body = self.base.scale_rect(body.move_(x, y));
+        let opacity = self.opacity;
+        self.opacity *= animations.window_opacity(node.node_id(), now);
+        node.tl_as_node().node_render(
+            self,
+            x + content.x1() + dx,
+            y + content.y1() + dy,
+            Some(&body),
+        );
+        self.opacity = opacity;
+    }
+
     pub fn render_xwindow(&mut self, tl: &Xwindow, x: i32, y: i32, bounds: Option<&Rect>) {
         self.render_surface(&tl.x.surface, x, y, bounds);
         self.render_tl_aux(tl.tl_data(), bounds, true);
@@ -364,6 +444,9 @@ impl Renderer<'_> {
             for screencast in tl_data.jay_screencasts.lock().values() {
                 screencast.schedule_toplevel_screencast();
             }
+            for session in tl_data.image_copy_sessions.lock().values() {
+                session.schedule_toplevel_capture();
+            }
         }
         if render_highlight {
             self.render_highlight(tl_data, bounds);
@@ -415,7 +498,10 @@ impl Renderer<'_> {
         } else {
             size = self.base.scale_point(size.0, size.1);
         }
-        let alpha = surface.alpha();
+        let mut alpha = surface.alpha();
+        if self.opacity < 1.0 {
+            alpha = Some(alpha.unwrap_or(1.0) * self.opacity);
+        }
         if let Some(children) = children.deref() {
             macro_rules! render {
                 ($children:expr) => {
@@ -467,7 +553,15 @@ impl Renderer<'_> {
         bounds: Option<&Rect>,
     ) {
         if let Some(tex) = buffer.buffer.get_texture(surface) {
-            self.base.render_texture(
+            let color_transform = match surface.color_description.get() {
+                Some(cd) => cd.transform_to(&self.color_description),
+                None => self
+                    .state
+                    .color_manager
+                    .srgb()
+                    .transform_to(&self.color_description),
+            };
+            self.base.render_texture_with_color_transform(
                 &tex,
                 alpha,
                 x,
@@ -479,6 +573,7 @@ impl Renderer<'_> {
                 Some(buffer.clone()),
                 buffer.sync.clone(),
                 buffer.release_sync,
+                color_transform,
             );
         } else if let Some(color) = &buffer.buffer.color {
             if let Some(rect) = Rect::new_sized(x, y, tsize.0, tsize.1) {
@@ -500,12 +595,19 @@ impl Renderer<'_> {
         }
     }
 
-    pub fn render_floating(&mut self, floating: &FloatNode, x: i32, y: i32) {
+    pub fn render_floating(&mut self, floating: &FloatNode, mut x: i32, mut y: i32) {
         let child = match floating.child.get() {
             Some(c) => c,
             _ => return,
         };
-        let pos = floating.position.get();
+        let animations = &self.state.animations;
+        let now = self.state.now_nsec();
+        let mut pos = floating.position.get();
+        if let Some((current, target)) = animations.window_rect(floating.node_id(), now) {
+            x += current.x1() - target.x1();
+            y += current.y1() - target.y1();
+            pos = current;
+        }
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
@@ -530,8 +632,13 @@ impl Renderer<'_> {
         let title_underline =
             [Rect::new_sized(x + bw, y + bw + th, pos.width() - 2 * bw, 1).unwrap()];
         self.base.fill_boxes(&title_underline, &uc);
+        let mut text_offset = 0;
+        if let Some(icon) = floating.icon_textures.get(&self.base.scale) {
+            self.render_title_icon(&icon, x + bw + 1, y + bw + 1, title_icon_size(th));
+            text_offset = th;
+        }
         if let Some(title) = floating.title_textures.get(&self.base.scale) {
-            let (x, y) = self.base.scale_point(x + bw, y + bw);
+            let (x, y) = self.base.scale_point(x + bw + text_offset, y + bw);
             self.base.render_texture(
                 &title.texture,
                 None,
@@ -554,11 +661,40 @@ impl Renderer<'_> {
         )
         .unwrap();
         let scissor_body = self.base.scale_rect(body);
+        let opacity = self.opacity;
+        self.opacity *= animations.window_opacity(child.node_id(), now);
         child.node_render(self, body.x1(), body.y1(), Some(&scissor_body));
+        self.opacity = opacity;
+    }
+
+    fn render_title_icon(&mut self, tex: &Rc<dyn GfxTexture>, x: i32, y: i32, size: i32) {
+        let Some(rect) = Rect::new_sized(x, y, size, size) else {
+            return;
+        };
+        let rect = self.base.scale_rect(rect);
+        self.base.render_texture(
+            tex,
+            None,
+            rect.x1(),
+            rect.y1(),
+            None,
+            Some((rect.width(), rect.height())),
+            self.base.scale,
+            None,
+            None,
+            AcquireSync::None,
+            ReleaseSync::None,
+        );
     }
 
     pub fn render_layer_surface(&mut self, surface: &ZwlrLayerSurfaceV1, x: i32, y: i32) {
         let (dx, dy) = surface.surface.extents.get().position();
+        let opacity = self.opacity;
+        self.opacity *= self
+            .state
+            .animations
+            .window_opacity(surface.node_id(), self.state.now_nsec());
         self.render_surface(&surface.surface, x - dx, y - dy, None);
+        self.opacity = opacity;
     }
 }
@@ -1,5 +1,6 @@
 use {
     crate::{
+        color_management::ColorTransform,
         gfx_api::{
             AcquireSync, BufferResv, CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxTexture,
             ReleaseSync, SampleRect,
@@ -21,6 +22,8 @@ pub struct RendererBase<'a> {
     pub transform: Transform,
     pub fb_width: f32,
     pub fb_height: f32,
+    /// The transform from sRGB to the color space of the framebuffer.
+    pub srgb_transform: Option<ColorTransform>,
 }
 
 impl RendererBase<'_> {
@@ -28,6 +31,16 @@ impl RendererBase<'_> {
         self.scale
     }
 
+    pub fn map_color(&self, color: &Color) -> Color {
+        match &self.srgb_transform {
+            None => *color,
+            Some(t) => {
+                let [r, g, b, a] = t.apply([color.r, color.g, color.b, color.a]);
+                Color { r, g, b, a }
+            }
+        }
+    }
+
     pub fn scale_point(&self, mut x: i32, mut y: i32) -> (i32, i32) {
         if self.scaled {
             x = (x as f64 * self.scalef).round() as _;
@@ -82,6 +95,7 @@ impl RendererBase<'_> {
         if boxes.is_empty() || *color == Color::TRANSPARENT {
             return;
         }
+        let color = self.map_color(color);
         let (dx, dy) = self.scale_point(dx, dy);
         for bx in boxes {
             let bx = match scaled {
@@ -98,7 +112,7 @@ impl RendererBase<'_> {
                     self.fb_width,
                     self.fb_height,
                 ),
-                color: *color,
+                color,
             }));
         }
     }
@@ -117,6 +131,7 @@ impl RendererBase<'_> {
         if boxes.is_empty() || *color == Color::TRANSPARENT {
             return;
         }
+        let color = self.map_color(color);
         let (dx, dy) = self.scale_point_f(dx, dy);
         for bx in boxes {
             let (x1, y1, x2, y2) = self.scale_rect_f(*bx);
@@ -130,7 +145,7 @@ impl RendererBase<'_> {
                     self.fb_width,
                     self.fb_height,
                 ),
-                color: *color,
+                color,
             }));
         }
     }
@@ -148,6 +163,37 @@ impl RendererBase<'_> {
         buffer_resv: Option<Rc<dyn BufferResv>>,
         acquire_sync: AcquireSync,
         release_sync: ReleaseSync,
+    ) {
+        self.render_texture_with_color_transform(
+            texture,
+            alpha,
+            x,
+            y,
+            tpoints,
+            tsize,
+            tscale,
+            bounds,
+            buffer_resv,
+            acquire_sync,
+            release_sync,
+            self.srgb_transform,
+        );
+    }
+
+    pub fn render_texture_with_color_transform(
+        &mut self,
+        texture: &Rc<dyn GfxTexture>,
+        alpha: Option<f32>,
+        x: i32,
+        y: i32,
+        tpoints: Option<SampleRect>,
+        tsize: Option<(i32, i32)>,
+        tscale: Scale,
+        bounds: Option<&Rect>,
+        buffer_resv: Option<Rc<dyn BufferResv>>,
+        acquire_sync: AcquireSync,
+        release_sync: ReleaseSync,
+        color_transform: Option<ColorTransform>,
     ) {
         let mut texcoord = tpoints.unwrap_or_else(SampleRect::identity);
 
@@ -190,6 +236,8 @@ impl RendererBase<'_> {
             buffer_resv,
             acquire_sync,
             release_sync,
+            gamma_lut: None,
+            color_transform,
         }));
     }
 }
@@ -2,8 +2,10 @@ use {
     crate::{
         format::XRGB8888,
         gfx_api::GfxError,
+        rect::Rect,
         scale::Scale,
         state::State,
+        tree::{Node, OutputNode, ToplevelNode},
         video::{
             drm::DrmError,
             gbm::{GbmBo, GbmError, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
@@ -22,6 +24,12 @@ pub enum ScreenshooterError {
     NoRenderContext,
     #[error("Display is empty")]
     EmptyDisplay,
+    #[error("The output has been removed")]
+    OutputRemoved,
+    #[error("The toplevel has been destroyed")]
+    ToplevelDestroyed,
+    #[error("The toplevel is empty")]
+    EmptyToplevel,
     #[error(transparent)]
     GbmError(#[from] GbmError),
     #[error(transparent)]
@@ -43,14 +51,74 @@ pub fn take_screenshot(
     state: &State,
     include_cursor: bool,
 ) -> Result<Screenshot, ScreenshooterError> {
-    let ctx = match state.render_ctx.get() {
-        Some(ctx) => ctx,
-        _ => return Err(ScreenshooterError::NoRenderContext),
-    };
     let extents = state.root.extents.get();
     if extents.is_empty() {
         return Err(ScreenshooterError::EmptyDisplay);
     }
+    render_screenshot(
+        state,
+        state.root.deref(),
+        extents,
+        Scale::from_int(1),
+        (extents.width(), extents.height()),
+        include_cursor,
+        false,
+    )
+}
+
+pub fn take_output_screenshot(
+    state: &State,
+    output: &OutputNode,
+    include_cursor: bool,
+) -> Result<Screenshot, ScreenshooterError> {
+    render_screenshot(
+        state,
+        output,
+        output.global.pos.get(),
+        output.global.persistent.scale.get(),
+        output.global.pixel_size(),
+        include_cursor,
+        output.has_fullscreen(),
+    )
+}
+
+pub fn take_toplevel_screenshot(
+    state: &State,
+    tl: &dyn ToplevelNode,
+    include_cursor: bool,
+) -> Result<Screenshot, ScreenshooterError> {
+    let rect = tl.node_absolute_position();
+    if rect.is_empty() {
+        return Err(ScreenshooterError::EmptyToplevel);
+    }
+    let scale = match tl.tl_data().workspace.get() {
+        None => Scale::default(),
+        Some(w) => w.output.get().global.persistent.scale.get(),
+    };
+    render_screenshot(
+        state,
+        tl.tl_as_node(),
+        rect,
+        scale,
+        scale.pixel_size(rect.width(), rect.height()),
+        include_cursor,
+        false,
+    )
+}
+
+fn render_screenshot(
+    state: &State,
+    node: &dyn Node,
+    rect: Rect,
+    scale: Scale,
+    (width, height): (i32, i32),
+    include_cursor: bool,
+    black_background: bool,
+) -> Result<Screenshot, ScreenshooterError> {
+    let ctx = match state.render_ctx.get() {
+        Some(ctx) => ctx,
+        _ => return Err(ScreenshooterError::NoRenderContext),
+    };
     let formats = ctx.formats();
     let mut usage = GBM_BO_USE_RENDERING;
     let modifiers = match formats.get(&XRGB8888.drm) {
@@ -65,22 +133,22 @@ pub fn take_screenshot(
     let gbm = ctx.gbm();
     let bo = gbm.create_bo(
         &state.dma_buf_ids,
-        extents.width(),
-        extents.height(),
+        width,
+        height,
         XRGB8888,
         modifiers,
         usage,
     )?;
     let fb = ctx.clone().dmabuf_fb(bo.dmabuf())?;
     fb.render_node(
-        state.root.deref(),
+        node,
         state,
-        Some(state.root.extents.get()),
+        Some(rect),
         None,
-        Scale::from_int(1),
+        scale,
         include_cursor,
         true,
-        false,
+        black_background,
         Transform::None,
     )?;
     let drm = gbm.drm.dup_render()?.fd().clone();
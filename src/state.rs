@@ -1,17 +1,19 @@
 use {
     crate::{
         acceptor::Acceptor,
+        animation::Animations,
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
             Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
-            DrmDeviceId, DrmDeviceIds, InputDevice, InputDeviceGroupIds, InputDeviceId,
-            InputDeviceIds, MonitorInfo,
+            DrmDeviceId, DrmDeviceIds, GammaLut, InputDevice, InputDeviceGroupIds, InputDeviceId,
+            InputDeviceIds, Mode, MonitorInfo,
         },
         backends::dummy::DummyBackend,
         cli::RunArgs,
         client::{Client, ClientId, Clients, SerialRange, NUM_CACHED_SERIAL_RANGES},
         clientmem::ClientMemOffset,
-        compositor::LIBEI_SOCKET,
+        color_management::{ColorManager, ColorTransform},
+        compositor::{I3SOCK, LIBEI_SOCKET, SWAYSOCK},
         config::ConfigProxy,
         cursor::{Cursor, ServerCursors},
         cursor_user::{CursorUserGroup, CursorUserGroupId, CursorUserGroupIds, CursorUserIds},
@@ -31,10 +33,14 @@ use {
         },
         gfx_apis::create_gfx_context,
         globals::{Globals, GlobalsError, RemovableWaylandGlobal, WaylandGlobal},
+        i3_ipc::{i3_ipc_acceptor::I3IpcAcceptor, I3IpcClients, I3WindowChange},
         ifs::{
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
+            ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             ext_session_lock_v1::ExtSessionLockV1,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1,
             ipc::{x_data_device::XIpcDeviceIds, DataOfferIds, DataSourceIds},
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -43,7 +49,7 @@ use {
             wl_output::{OutputGlobalOpt, OutputId, PersistentOutputState},
             wl_seat::{
                 tablet::{TabletIds, TabletInit, TabletPadIds, TabletPadInit, TabletToolIds},
-                SeatIds, WlSeatGlobal,
+                collect_kb_foci, SeatIds, WlSeatGlobal,
             },
             wl_surface::{
                 wl_subsurface::SubsurfaceIds,
@@ -54,13 +60,17 @@ use {
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
             wp_drm_lease_device_v1::WpDrmLeaseDeviceV1Global,
             wp_linux_drm_syncobj_manager_v1::WpLinuxDrmSyncobjManagerV1Global,
-            zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
+            xdg_foreign::XdgForeignExport,
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
+            zwlr_gamma_control_v1::ZwlrGammaControlV1,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1,
             zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
         },
         io_uring::IoUring,
         leaks::Tracker,
         logger::Logger,
+        output_mirror::OutputMirror,
         rect::Rect,
         renderer::{RenderResult, Renderer},
         scale::Scale,
@@ -68,8 +78,8 @@ use {
         theme::{Color, Theme},
         time::Time,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeIds,
-            NodeVisitorBase, OutputNode, PlaceholderNode, TearingMode, ToplevelNode,
+            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeId, NodeIds,
+            NodeVisitorBase, OutputNode, PlaceholderNode, Scratchpad, TearingMode, ToplevelNode,
             ToplevelNodeBase, VrrMode, WorkspaceNode,
         },
         utils::{
@@ -86,10 +96,13 @@ use {
                 Drm,
             },
         },
+        virtual_output::VirtualOutput,
         wheel::Wheel,
+        window_rules::WindowRules,
         wire::{
-            ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
-            ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, ExtImageCopyCaptureSessionV1Id, ExtWorkspaceManagerV1Id,
+            JayGlobalShortcutsId, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
+            ZwlrForeignToplevelManagerV1Id, ZwlrOutputManagerV1Id, ZwpLinuxDmabufFeedbackV1Id,
         },
         xkbcommon::{KeyboardStateIds, XkbContext, XkbKeymap, XkbState},
         xwayland::{self, XWaylandEvent},
@@ -146,6 +159,7 @@ pub struct State {
     pub tree_changed_sent: Cell<bool>,
     pub config: CloneCell<Option<Rc<ConfigProxy>>>,
     pub theme: Theme,
+    pub animations: Animations,
     pub pending_container_layout: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_data: AsyncQueue<Rc<ContainerNode>>,
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
@@ -154,12 +168,18 @@ pub struct State {
     pub pending_input_popup_positioning: AsyncQueue<Rc<ZwpInputPopupSurfaceV2>>,
     pub pending_toplevel_screencasts: AsyncQueue<Rc<JayScreencast>>,
     pub pending_toplevel_screencast_reallocs: AsyncQueue<Rc<JayScreencast>>,
+    pub pending_toplevel_image_copies: AsyncQueue<Rc<ExtImageCopyCaptureSessionV1>>,
+    pub pending_workspace_manager_done: AsyncQueue<Rc<ExtWorkspaceManagerV1>>,
+    pub image_copy_sessions:
+        CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
     pub dbus: Dbus,
     pub fdcloser: Arc<FdCloser>,
     pub logger: Option<Arc<Logger>>,
     pub connectors: CopyHashMap<ConnectorId, Rc<ConnectorData>>,
     pub outputs: CopyHashMap<ConnectorId, Rc<OutputData>>,
     pub drm_devs: CopyHashMap<DrmDeviceId, Rc<DrmDevData>>,
+    pub virtual_outputs: CopyHashMap<ConnectorId, Rc<VirtualOutput>>,
+    pub mirrors: CopyHashMap<ConnectorId, Rc<OutputMirror>>,
     pub status: CloneCell<Rc<String>>,
     pub idle: IdleState,
     pub run_args: RunArgs,
@@ -180,12 +200,19 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub global_shortcuts: CopyHashMap<(ClientId, JayGlobalShortcutsId), Rc<JayGlobalShortcuts>>,
     pub default_workspace_capture: Cell<bool>,
     pub default_gfx_api: Cell<GfxApi>,
     pub activation_tokens: CopyHashMap<ActivationToken, ()>,
     pub toplevel_lists:
         CopyHashMap<(ClientId, ExtForeignToplevelListV1Id), Rc<ExtForeignToplevelListV1>>,
-    pub dma_buf_ids: DmaBufIds,
+    pub foreign_toplevel_managers:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelManagerV1Id), Rc<ZwlrForeignToplevelManagerV1>>,
+    pub workspace_managers:
+        CopyHashMap<(ClientId, ExtWorkspaceManagerV1Id), Rc<ExtWorkspaceManagerV1>>,
+    pub xdg_foreign_exports: CopyHashMap<String, Rc<XdgForeignExport>>,
+    pub color_manager: ColorManager,
+    pub dma_buf_ids: Rc<DmaBufIds>,
     pub drm_feedback_ids: DrmFeedbackIds,
     pub direct_scanout_enabled: Cell<bool>,
     pub persistent_output_states: CopyHashMap<Rc<OutputId>, Rc<PersistentOutputState>>,
@@ -214,6 +241,14 @@ pub struct State {
     pub enable_ei_acceptor: Cell<bool>,
     pub ei_clients: EiClients,
     pub slow_ei_clients: AsyncQueue<Rc<EiClient>>,
+    pub window_rules: WindowRules,
+    pub toplevels: CopyHashMap<NodeId, Rc<dyn ToplevelNode>>,
+    pub scratchpad: Scratchpad,
+    pub i3_ipc_acceptor: CloneCell<Option<Rc<I3IpcAcceptor>>>,
+    pub i3_ipc_acceptor_future: CloneCell<Option<SpawnedFuture<()>>>,
+    pub enable_i3_ipc_acceptor: Cell<bool>,
+    pub i3_ipc_clients: I3IpcClients,
+    pub output_managers: CopyHashMap<(ClientId, ZwlrOutputManagerV1Id), Rc<ZwlrOutputManagerV1>>,
 }
 
 // impl Drop for State {
@@ -298,6 +333,28 @@ pub struct ConnectorData {
     pub name: String,
     pub drm_dev: Option<Rc<DrmDevData>>,
     pub async_event: Rc<AsyncEvent>,
+    pub gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+    pub color_gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    /// The connector whose output is shown on this connector.
+    pub mirror: Cell<Option<ConnectorId>>,
+}
+
+impl ConnectorData {
+    /// Applies the compositor's color transform unless a gamma control client has
+    /// taken over the connector.
+    pub fn update_gamma_lut(&self) {
+        if self.gamma_control.is_none() {
+            self.connector.set_gamma_lut(self.color_gamma_lut.get());
+        }
+    }
+
+    /// Makes this connector show the output of another connector instead of being part
+    /// of the desktop.
+    pub fn set_mirror(&self, source: Option<ConnectorId>) {
+        if self.mirror.replace(source) != source {
+            self.async_event.trigger();
+        }
+    }
 }
 
 pub struct OutputData {
@@ -426,6 +483,7 @@ impl State {
     pub fn set_render_ctx(&self, ctx: Option<Rc<dyn GfxContext>>) {
         self.render_ctx.set(ctx.clone());
         self.render_ctx_version.fetch_add(1);
+        self.animations.clear();
         self.cursors.set(None);
         self.drm_feedback.set(None);
         self.wait_for_sync_obj
@@ -452,6 +510,7 @@ impl State {
             impl NodeVisitorBase for Walker {
                 fn visit_container(&mut self, node: &Rc<ContainerNode>) {
                     node.render_data.borrow_mut().titles.clear();
+                    node.render_data.borrow_mut().icons.clear();
                     node.children.iter().for_each(|c| c.title_tex.clear());
                     node.node_visit_children(self);
                 }
@@ -467,6 +526,7 @@ impl State {
                 }
                 fn visit_float(&mut self, node: &Rc<FloatNode>) {
                     node.title_textures.clear();
+                    node.icon_textures.clear();
                     node.node_visit_children(self);
                 }
                 fn visit_placeholder(&mut self, node: &Rc<PlaceholderNode>) {
@@ -651,6 +711,68 @@ impl State {
         }
     }
 
+    pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        if data.is_floating.get() == floating {
+            return;
+        }
+        let parent = match data.parent.get() {
+            Some(p) => p,
+            _ => return,
+        };
+        if !floating {
+            data.is_scratchpad.set(false);
+            parent.cnode_remove_child2(tl.tl_as_node(), true);
+            self.map_tiled(tl.clone());
+        } else if let Some(ws) = data.workspace.get() {
+            parent.cnode_remove_child2(tl.tl_as_node(), true);
+            let (width, height) = data.float_size(&ws);
+            self.map_floating(tl.clone(), width, height, &ws, None);
+        }
+        data.send_i3_ipc_event(tl.node_id(), I3WindowChange::Floating);
+    }
+
+    pub fn set_tl_workspace(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, ws: &Rc<WorkspaceNode>) {
+        if tl.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let old_ws = match tl.tl_data().workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if old_ws.id == ws.id {
+            return;
+        }
+        let cn = match tl.tl_data().parent.get() {
+            Some(cn) => cn,
+            _ => return,
+        };
+        tl.tl_data().is_scratchpad.set(false);
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        cn.cnode_remove_child2(tl.tl_as_node(), true);
+        if !ws.visible.get() {
+            for focus in kb_foci {
+                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+            }
+        }
+        if tl.tl_data().is_floating.get() {
+            self.map_floating(
+                tl.clone(),
+                tl.tl_data().float_width.get(),
+                tl.tl_data().float_height.get(),
+                ws,
+                None,
+            );
+        } else {
+            self.map_tiled_on(tl.clone(), ws);
+        }
+        tl.tl_data()
+            .send_i3_ipc_event(tl.node_id(), I3WindowChange::Move);
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
@@ -752,6 +874,9 @@ impl State {
             return;
         }
         self.damage_visualizer.add(rect);
+        for session in self.image_copy_sessions.lock().values() {
+            session.damage_logical(rect);
+        }
         for output in self.root.outputs.lock().values() {
             if output.global.pos.get().intersects(&rect) {
                 if cursor && output.schedule.defer_cursor_updates() {
@@ -813,15 +938,30 @@ impl State {
         self.pending_input_popup_positioning.clear();
         self.pending_toplevel_screencasts.clear();
         self.pending_toplevel_screencast_reallocs.clear();
+        self.pending_toplevel_image_copies.clear();
+        self.pending_workspace_manager_done.clear();
+        self.image_copy_sessions.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.global_shortcuts.clear();
         self.toplevel_lists.clear();
+        self.foreign_toplevel_managers.clear();
+        self.workspace_managers.clear();
+        self.xdg_foreign_exports.clear();
+        self.toplevels.clear();
+        self.scratchpad.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
         for h in self.input_device_handlers.borrow_mut().drain_values() {
             h.async_event.clear();
         }
         self.backend_events.clear();
+        for output in self.virtual_outputs.lock().drain_values() {
+            output.clear();
+        }
+        for mirror in self.mirrors.lock().drain_values() {
+            mirror.clear();
+        }
         self.workspaces.clear();
         {
             let seats = mem::take(self.globals.seats.lock().deref_mut());
@@ -841,6 +981,11 @@ impl State {
         self.ei_acceptor_future.take();
         self.ei_clients.clear();
         self.slow_ei_clients.clear();
+        self.i3_ipc_acceptor.take();
+        self.i3_ipc_acceptor_future.take();
+        self.i3_ipc_clients.clear();
+        self.output_managers.clear();
+        self.window_rules.clear();
     }
 
     pub fn disable_hardware_cursors(&self) {
@@ -890,6 +1035,25 @@ impl State {
         Ok(sync_file)
     }
 
+    pub fn create_virtual_output(self: &Rc<Self>, mode: Mode) -> Rc<VirtualOutput> {
+        let output = VirtualOutput::new(self, mode, None);
+        output.connect();
+        output
+    }
+
+    /// Removes the virtual output with the given connector id.
+    ///
+    /// Returns `false` if the connector does not belong to a virtual output.
+    pub fn remove_virtual_output(&self, id: ConnectorId) -> bool {
+        match self.virtual_outputs.get(&id) {
+            Some(output) => {
+                output.remove();
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn perform_screencopy(
         &self,
         src: &Rc<dyn GfxTexture>,
@@ -900,6 +1064,7 @@ impl State {
         y_off: i32,
         size: Option<(i32, i32)>,
         transform: Transform,
+        color_transform: Option<ColorTransform>,
     ) -> Result<Option<SyncFile>, GfxError> {
         let mut ops = target.take_render_ops();
         let mut renderer = Renderer {
@@ -911,10 +1076,12 @@ impl State {
                 let (width, height) = target.logical_size(Transform::None);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            color_description: self.color_manager.srgb().clone(),
+            opacity: 1.0,
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
-        renderer.base.render_texture(
+        renderer.base.render_texture_with_color_transform(
             src,
             None,
             x_off,
@@ -926,6 +1093,7 @@ impl State {
             None,
             AcquireSync::None,
             ReleaseSync::Implicit,
+            color_transform,
         );
         if render_hardware_cursors {
             if let Some(cursor_user_group) = self.cursor_user_group_hardware_cursor.get() {
@@ -942,6 +1110,95 @@ impl State {
         target.render(ops, Some(&Color::SOLID_BLACK))
     }
 
+    /// Returns whether `connector` can mirror `source`.
+    ///
+    /// Only outputs that are part of the desktop can be mirrored. Therefore mirrors
+    /// cannot be chained: the source must not be a mirror itself and the connector must
+    /// not be the source of another mirror.
+    pub fn can_mirror(&self, connector: ConnectorId, source: ConnectorId) -> bool {
+        if connector == source {
+            return false;
+        }
+        if let Some(source) = self.connectors.get(&source) {
+            if source.mirror.get().is_some() {
+                return false;
+            }
+        }
+        !self
+            .connectors
+            .lock()
+            .values()
+            .any(|c| c.mirror.get() == Some(connector))
+    }
+
+    /// Renders a frame of `source` into `target`.
+    ///
+    /// The frame is scaled to fit into the target while preserving its aspect ratio. The
+    /// remaining area is filled with black.
+    pub fn perform_mirror_copy(
+        &self,
+        source: &OutputNode,
+        src: &Rc<dyn GfxTexture>,
+        target: &Rc<dyn GfxFramebuffer>,
+        render_hardware_cursors: bool,
+        x_off: i32,
+        y_off: i32,
+        size: Option<(i32, i32)>,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        let (src_width, src_height) = source.global.pixel_size();
+        let (width, height) = target.logical_size(Transform::None);
+        if src_width <= 0 || src_height <= 0 {
+            return target.clear_with(0.0, 0.0, 0.0, 1.0);
+        }
+        let factor = (width as f64 / src_width as f64).min(height as f64 / src_height as f64);
+        let scaled = |v: i32| (v as f64 * factor).round() as i32;
+        let dx = (width - scaled(src_width)) / 2;
+        let dy = (height - scaled(src_height)) / 2;
+        let (size_width, size_height) = size.unwrap_or((src_width, src_height));
+        let extents = Rect::new_sized(0, 0, width, height).unwrap();
+        let mut ops = target.take_render_ops();
+        let mut renderer = Renderer {
+            base: target.renderer_base(&mut ops, Scale::from_int(1), Transform::None),
+            state: self,
+            result: None,
+            logical_extents: extents,
+            pixel_extents: extents,
+            color_description: self.color_manager.srgb().clone(),
+            opacity: 1.0,
+        };
+        let mut sample_rect = SampleRect::identity();
+        sample_rect.buffer_transform = source.global.persistent.transform.get();
+        renderer.base.render_texture_with_color_transform(
+            src,
+            None,
+            dx + scaled(x_off),
+            dy + scaled(y_off),
+            Some(sample_rect),
+            Some((scaled(size_width), scaled(size_height))),
+            Scale::from_int(1),
+            None,
+            None,
+            AcquireSync::None,
+            ReleaseSync::Implicit,
+            source.screencopy_color_transform(),
+        );
+        if render_hardware_cursors {
+            if let Some(cursor_user_group) = self.cursor_user_group_hardware_cursor.get() {
+                if let Some(cursor_user) = cursor_user_group.active() {
+                    if let Some(cursor) = cursor_user.get() {
+                        let position = source.global.pos.get();
+                        let factor = scaled(src_width) as f64 / position.width().max(1) as f64;
+                        let (x, y) = cursor_user.position();
+                        let x = (x.to_f64() - position.x1() as f64) * factor + dx as f64;
+                        let y = (y.to_f64() - position.y1() as f64) * factor + dy as f64;
+                        cursor.render(&mut renderer, Fixed::from_f64(x), Fixed::from_f64(y));
+                    }
+                }
+            }
+        }
+        target.render(ops, Some(&Color::SOLID_BLACK))
+    }
+
     fn have_hardware_cursor(&self) -> bool {
         if let Some(group) = self.cursor_user_group_hardware_cursor.get() {
             if let Some(user) = group.active() {
@@ -957,73 +1214,96 @@ impl State {
         &self,
         src: &Rc<dyn GfxTexture>,
         position: Rect,
+        render_hardware_cursors: bool,
         x_off: i32,
         y_off: i32,
         size: Option<(i32, i32)>,
-        capture: &ZwlrScreencopyFrameV1,
+        rect: Rect,
+        client: &Client,
         mem: &ClientMemOffset,
         stride: i32,
         format: &'static Format,
         transform: Transform,
+        color_transform: Option<ColorTransform>,
     ) -> Result<(), ShmScreencopyError> {
         let (src_width, src_height) = src.size();
-        let mut needs_copy = capture.rect.x1() < x_off
-            || capture.rect.x2() > x_off + src_width
-            || capture.rect.y1() < y_off
-            || capture.rect.y2() > y_off + src_height
-            || self.have_hardware_cursor()
-            || transform != Transform::None;
+        let mut needs_copy = rect.x1() < x_off
+            || rect.x2() > x_off + src_width
+            || rect.y1() < y_off
+            || rect.y2() > y_off + src_height
+            || (render_hardware_cursors && self.have_hardware_cursor())
+            || transform != Transform::None
+            || color_transform.is_some();
         if let Some((target_width, target_height)) = size {
             if (target_width, target_height) != (src_width, src_height) {
                 needs_copy = true;
             }
         }
-        let acc = if needs_copy {
-            let Some(ctx) = self.render_ctx.get() else {
-                return Err(ShmScreencopyError::NoRenderContext);
-            };
-            let fb = ctx
-                .create_fb(capture.rect.width(), capture.rect.height(), stride, format)
-                .map_err(ShmScreencopyError::CreateTemporaryFb)?;
-            self.perform_screencopy(
-                src,
-                &fb,
-                position,
-                true,
-                x_off - capture.rect.x1(),
-                y_off - capture.rect.y1(),
-                size,
-                transform,
+        if needs_copy {
+            return self.perform_shm_bridge_copy(
+                client,
+                mem,
+                rect.width(),
+                rect.height(),
+                stride,
+                format,
+                |fb| {
+                    self.perform_screencopy(
+                        src,
+                        fb,
+                        position,
+                        render_hardware_cursors,
+                        x_off - rect.x1(),
+                        y_off - rect.y1(),
+                        size,
+                        transform,
+                        color_transform,
+                    )
+                },
+            );
+        }
+        let acc = mem.access(|mem| {
+            src.clone().read_pixels(
+                rect.x1() - x_off,
+                rect.y1() - y_off,
+                rect.width(),
+                rect.height(),
+                stride,
+                format,
+                mem,
             )
-            .map_err(ShmScreencopyError::CopyToTemporary)?;
-            mem.access(|mem| {
-                fb.copy_to_shm(
-                    0,
-                    0,
-                    capture.rect.width(),
-                    capture.rect.height(),
-                    stride,
-                    format,
-                    mem,
-                )
-            })
-        } else {
-            mem.access(|mem| {
-                src.clone().read_pixels(
-                    capture.rect.x1() - x_off,
-                    capture.rect.y1() - y_off,
-                    capture.rect.width(),
-                    capture.rect.height(),
-                    stride,
-                    format,
-                    mem,
-                )
-            })
+        });
+        match acc {
+            Ok(res) => res.map_err(ShmScreencopyError::ReadPixels),
+            Err(e) => {
+                client.error(e);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn perform_shm_bridge_copy(
+        &self,
+        client: &Client,
+        mem: &ClientMemOffset,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: &'static Format,
+        render: impl FnOnce(&Rc<dyn GfxFramebuffer>) -> Result<Option<SyncFile>, GfxError>,
+    ) -> Result<(), ShmScreencopyError> {
+        let Some(ctx) = self.render_ctx.get() else {
+            return Err(ShmScreencopyError::NoRenderContext);
         };
+        let fb = ctx
+            .create_fb(width, height, stride, format)
+            .map_err(ShmScreencopyError::CreateTemporaryFb)?;
+        render(&fb).map_err(ShmScreencopyError::CopyToTemporary)?;
+        let acc = mem.access(|mem| fb.copy_to_shm(0, 0, width, height, stride, format, mem));
         match acc {
             Ok(res) => res.map_err(ShmScreencopyError::ReadPixels),
             Err(e) => {
-                capture.client.error(e);
+                client.error(e);
                 Ok(())
             }
         }
@@ -1116,6 +1396,14 @@ impl State {
         for seat in self.globals.seats.lock().values() {
             seat.output_extents_changed();
         }
+        self.update_output_managers();
+    }
+
+    pub fn update_output_managers(&self) {
+        let managers: Vec<_> = self.output_managers.lock().values().cloned().collect();
+        for manager in managers {
+            manager.update();
+        }
     }
 
     pub fn update_ei_acceptor(self: &Rc<Self>) {
@@ -1152,6 +1440,43 @@ impl State {
             self.ei_acceptor_future.take();
         }
     }
+
+    pub fn update_i3_ipc_acceptor(self: &Rc<Self>) {
+        self.update_i3_ipc_acceptor2();
+        if let Some(forker) = self.forker.get() {
+            match self.i3_ipc_acceptor.get() {
+                None => {
+                    forker.unsetenv(I3SOCK.as_bytes());
+                    forker.unsetenv(SWAYSOCK.as_bytes());
+                }
+                Some(s) => {
+                    forker.setenv(I3SOCK.as_bytes(), s.socket_path().as_bytes());
+                    forker.setenv(SWAYSOCK.as_bytes(), s.socket_path().as_bytes());
+                }
+            }
+        }
+    }
+
+    fn update_i3_ipc_acceptor2(self: &Rc<Self>) {
+        if self.i3_ipc_acceptor.is_some() == self.enable_i3_ipc_acceptor.get() {
+            return;
+        }
+        if self.enable_i3_ipc_acceptor.get() {
+            let (acceptor, future) = match I3IpcAcceptor::spawn(self) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("Could not create i3 IPC socket: {}", ErrorFmt(e));
+                    return;
+                }
+            };
+            self.i3_ipc_acceptor.set(Some(acceptor));
+            self.i3_ipc_acceptor_future.set(Some(future));
+        } else {
+            log::info!("Disabling i3 IPC socket");
+            self.i3_ipc_acceptor.take();
+            self.i3_ipc_acceptor_future.take();
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -1,8 +1,13 @@
 use {
     crate::{
-        backend::{Connector, ConnectorEvent, ConnectorId, MonitorInfo},
+        backend::{
+            Connector, ConnectorEvent, ConnectorId, HardwareCursor, MonitorInfo,
+            DEFAULT_COLOR_TEMPERATURE, DEFAULT_SDR_REFERENCE_WHITE,
+        },
+        color_management::ColorDescriptionData,
         globals::GlobalName,
         ifs::wl_output::{OutputId, PersistentOutputState, WlOutputGlobal},
+        output_mirror::OutputMirror,
         output_schedule::OutputSchedule,
         state::{ConnectorData, OutputData, State},
         tree::{move_ws_to_output, OutputNode, OutputRenderData, WsMoveConfig},
@@ -31,6 +36,9 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         name: connector.kernel_id().to_string(),
         drm_dev: drm_dev.clone(),
         async_event: Rc::new(AsyncEvent::default()),
+        gamma_control: Default::default(),
+        color_gamma_lut: Default::default(),
+        mirror: Default::default(),
     });
     if let Some(dev) = drm_dev {
         dev.connectors.set(id, data.clone());
@@ -39,6 +47,8 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         id,
         state: state.clone(),
         data: data.clone(),
+        hardware_cursor: Default::default(),
+        announced: Cell::new(false),
     };
     let future = state.eng.spawn(oh.handle());
     data.handler.set(Some(future));
@@ -51,6 +61,8 @@ struct ConnectorHandler {
     id: ConnectorId,
     state: Rc<State>,
     data: Rc<ConnectorData>,
+    hardware_cursor: CloneCell<Option<Rc<dyn HardwareCursor>>>,
+    announced: Cell<bool>,
 }
 
 impl ConnectorHandler {
@@ -80,12 +92,12 @@ impl ConnectorHandler {
         }
         self.data.handler.set(None);
         self.state.connectors.remove(&self.id);
+        self.state.update_output_managers();
     }
 
     async fn handle_connected(&self, info: MonitorInfo) {
         log::info!("Connector {} connected", self.data.connector.kernel_id());
         self.data.connected.set(true);
-        let name = self.state.globals.name();
         let output_id = Rc::new(OutputId {
             connector: self.data.name.clone(),
             manufacturer: info.manufacturer.clone(),
@@ -95,18 +107,52 @@ impl ConnectorHandler {
         if info.non_desktop {
             self.handle_non_desktop_connected(info).await;
         } else {
-            self.handle_desktop_connected(info, name, output_id).await;
+            let mut info = Some(info);
+            while let Some(i) = info.take() {
+                info = match self.data.mirror.get() {
+                    Some(source) => self.handle_mirror_connected(i, source).await,
+                    None => {
+                        let name = self.state.globals.name();
+                        self.handle_desktop_connected(i, name, output_id.clone())
+                            .await
+                    }
+                };
+            }
         }
+        self.hardware_cursor.take();
         self.data.connected.set(false);
+        if let Some(gc) = self.data.gamma_control.get() {
+            gc.fail();
+        }
         log::info!("Connector {} disconnected", self.data.connector.kernel_id());
     }
 
+    fn connector_connected(&self) {
+        if self.announced.replace(true) {
+            return;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.connector_connected(self.id);
+        }
+    }
+
+    fn connector_disconnected(&self) {
+        if !self.announced.replace(false) {
+            return;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.connector_disconnected(self.id);
+        }
+    }
+
+    /// Returns the monitor info if the connector is still connected but has started
+    /// mirroring another connector.
     async fn handle_desktop_connected(
         &self,
         info: MonitorInfo,
         name: GlobalName,
         output_id: Rc<OutputId>,
-    ) {
+    ) -> Option<MonitorInfo> {
         let desired_state = match self.state.persistent_output_states.get(&output_id) {
             Some(ds) => ds,
             _ => {
@@ -126,6 +172,10 @@ impl ConnectorHandler {
                     vrr_mode: Cell::new(self.state.default_vrr_mode.get()),
                     vrr_cursor_hz: Cell::new(self.state.default_vrr_cursor_hz.get()),
                     tearing_mode: Cell::new(self.state.default_tearing_mode.get()),
+                    color_temperature: Cell::new(DEFAULT_COLOR_TEMPERATURE),
+                    brightness: Cell::new(1.0),
+                    hdr_enabled: Cell::new(false),
+                    sdr_reference_white: Cell::new(DEFAULT_SDR_REFERENCE_WHITE),
                 });
                 self.state
                     .persistent_output_states
@@ -151,6 +201,13 @@ impl ConnectorHandler {
             &desired_state,
         ));
         let _schedule = self.state.eng.spawn(schedule.clone().drive());
+        let color_description = self
+            .state
+            .color_manager
+            .get_description(ColorDescriptionData {
+                target_primaries: info.primaries,
+                ..ColorDescriptionData::SRGB
+            });
         let on = Rc::new(OutputNode {
             id: self.state.node_ids.next(),
             workspaces: Default::default(),
@@ -183,11 +240,18 @@ impl ConnectorHandler {
             update_render_data_scheduled: Cell::new(false),
             hardware_cursor_needs_render: Cell::new(false),
             screencopies: Default::default(),
+            image_copy_sessions: Default::default(),
+            ext_workspace_groups: Default::default(),
+            color_description: CloneCell::new(color_description),
+            color_management_outputs: Default::default(),
+            hdr_active: Cell::new(false),
+            fifo_barriers: Default::default(),
             title_visible: Default::default(),
             schedule,
         });
         on.update_visible();
         on.update_rects();
+        on.update_gamma_lut();
         self.state
             .add_output_scale(on.global.persistent.scale.get());
         let output_data = Rc::new(OutputData {
@@ -196,12 +260,19 @@ impl ConnectorHandler {
             node: Some(on.clone()),
             lease_connectors: Default::default(),
         });
-        self.state.outputs.set(self.id, output_data);
+        self.state.outputs.set(self.id, output_data.clone());
+        if let Some(hc) = self.hardware_cursor.get() {
+            on.schedule.set_hardware_cursor(&Some(hc.clone()));
+            on.hardware_cursor.set(Some(hc));
+        }
         on.schedule_update_render_data();
         self.state.root.outputs.set(self.id, on.clone());
         self.state.output_extents_changed();
         global.opt.node.set(Some(on.clone()));
         global.opt.global.set(Some(global.clone()));
+        for manager in self.state.workspace_managers.lock().values() {
+            manager.announce_output(&on);
+        }
         let mut ws_to_move = VecDeque::new();
         if self.state.root.outputs.len() == 1 {
             for seat in self.state.globals.seats.lock().values() {
@@ -238,34 +309,51 @@ impl ConnectorHandler {
             };
             move_ws_to_output(&ws, &on, config);
         }
-        if let Some(config) = self.state.config.get() {
-            config.connector_connected(self.id);
-        }
+        self.connector_connected();
         self.state.add_global(&global);
         self.state.tree_changed();
+        self.state.i3_ipc_clients.output_event();
         on.update_presentation_type();
+        self.data
+            .connector
+            .set_hdr_enabled(desired_state.hdr_enabled.get());
+        if on.hardware_cursor.is_some() {
+            self.state.refresh_hardware_cursors();
+        }
+        let mut disconnected = false;
         'outer: loop {
             while let Some(event) = self.data.connector.event() {
                 match event {
-                    ConnectorEvent::Disconnected => break 'outer,
+                    ConnectorEvent::Disconnected => {
+                        disconnected = true;
+                        break 'outer;
+                    }
                     ConnectorEvent::HardwareCursor(hc) => {
                         on.schedule.set_hardware_cursor(&hc);
-                        on.hardware_cursor.set(hc);
+                        on.hardware_cursor.set(hc.clone());
+                        self.hardware_cursor.set(hc);
                         self.state.refresh_hardware_cursors();
                     }
                     ConnectorEvent::ModeChanged(mode) => {
                         on.update_mode(mode);
+                        self.state.i3_ipc_clients.output_event();
                     }
                     ConnectorEvent::VrrChanged(enabled) => {
                         on.schedule.set_vrr_enabled(enabled);
                     }
+                    ConnectorEvent::HdrChanged(enabled) => {
+                        on.set_hdr_active(enabled);
+                    }
                     ev => unreachable!("received unexpected event {:?}", ev),
                 }
             }
+            if self.data.mirror.get().is_some() {
+                break;
+            }
             self.data.async_event.triggered().await;
         }
-        if let Some(config) = self.state.config.get() {
-            config.connector_disconnected(self.id);
+        if disconnected {
+            self.connector_disconnected();
         }
         global.clear();
         for jo in on.jay_outputs.lock().drain_values() {
@@ -282,6 +370,8 @@ impl ConnectorHandler {
         self.state.root.outputs.remove(&self.id);
         self.state.output_extents_changed();
         self.state.outputs.remove(&self.id);
+        self.state.i3_ipc_clients.output_event();
+        self.state.update_output_managers();
         on.lock_surface.take();
         {
             let mut surfaces = vec![];
@@ -307,6 +397,11 @@ impl ConnectorHandler {
             };
             move_ws_to_output(&ws, &target, config);
         }
+        for group in on.ext_workspace_groups.clear().into_values() {
+            group.output_removed();
+        }
+        on.color_management_outputs.clear();
+        on.vblank();
         for seat in self.state.globals.seats.lock().values() {
             seat.cursor_group().output_disconnected(&on, &target);
         }
@@ -315,6 +410,80 @@ impl ConnectorHandler {
         let _ = self.state.remove_global(&*global);
         self.state.tree_changed();
         self.state.damage(self.state.root.extents.get());
+        if disconnected {
+            return None;
+        }
+        Some(MonitorInfo {
+            initial_mode: global.mode.get(),
+            ..output_data.monitor_info.clone()
+        })
+    }
+
+    /// Returns the monitor info if the connector is still connected but the mirror
+    /// source has changed.
+    async fn handle_mirror_connected(
+        &self,
+        info: MonitorInfo,
+        source: ConnectorId,
+    ) -> Option<MonitorInfo> {
+        let mirror = OutputMirror::new(&self.state, &self.data, source, info.initial_mode);
+        let output_data = Rc::new(OutputData {
+            connector: self.data.clone(),
+            monitor_info: info,
+            node: None,
+            lease_connectors: Default::default(),
+        });
+        self.state.outputs.set(self.id, output_data.clone());
+        self.state.mirrors.set(self.id, mirror.clone());
+        if let Some(hc) = self.hardware_cursor.get() {
+            hc.set_enabled(false);
+            hc.commit(true);
+        }
+        self.data.connector.set_hdr_enabled(false);
+        self.connector_connected();
+        mirror.damage_source();
+        self.data.connector.damage();
+        let mut disconnected = false;
+        'outer: loop {
+            while let Some(event) = self.data.connector.event() {
+                match event {
+                    ConnectorEvent::Disconnected => {
+                        disconnected = true;
+                        break 'outer;
+                    }
+                    ConnectorEvent::HardwareCursor(hc) => {
+                        if let Some(hc) = &hc {
+                            hc.set_enabled(false);
+                            hc.commit(true);
+                        }
+                        self.hardware_cursor.set(hc);
+                    }
+                    ConnectorEvent::ModeChanged(mode) => {
+                        mirror.set_mode(mode);
+                    }
+                    ConnectorEvent::VrrChanged(_) | ConnectorEvent::HdrChanged(_) => {}
+                    ev => unreachable!("received unexpected event {:?}", ev),
+                }
+            }
+            if self.data.mirror.get() != Some(source) {
+                break;
+            }
+            self.data.async_event.triggered().await;
+        }
+        if disconnected {
+            self.connector_disconnected();
+        }
+        self.state.mirrors.remove(&self.id);
+        mirror.clear();
+        self.state.outputs.remove(&self.id);
+        self.state.update_output_managers();
+        if disconnected {
+            return None;
+        }
+        Some(MonitorInfo {
+            initial_mode: mirror.mode(),
+            ..output_data.monitor_info.clone()
+        })
     }
 
     async fn handle_non_desktop_connected(&self, monitor_info: MonitorInfo) {
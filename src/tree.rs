@@ -27,8 +27,8 @@ use {
     },
 };
 pub use {
-    container::*, containing::*, display::*, float::*, output::*, placeholder::*, stacked::*,
-    toplevel::*, walker::*, workspace::*,
+    container::*, containing::*, display::*, float::*, output::*, placeholder::*, scratchpad::*,
+    stacked::*, toplevel::*, walker::*, workspace::*,
 };
 
 mod container;
@@ -37,6 +37,7 @@ mod display;
 mod float;
 mod output;
 mod placeholder;
+mod scratchpad;
 mod stacked;
 mod toplevel;
 mod walker;
@@ -4,6 +4,8 @@ use {
         cursor::KnownCursor,
         cursor_user::CursorUser,
         fixed::Fixed,
+        gfx_api::GfxTexture,
+        icons::title_icon_size,
         ifs::wl_seat::{
             collect_kb_foci, collect_kb_foci2,
             tablet::{TabletTool, TabletToolChanges, TabletToolId},
@@ -33,7 +35,7 @@ use {
         },
     },
     ahash::AHashMap,
-    jay_config::Axis,
+    jay_config::{Axis, Layout},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -69,6 +71,33 @@ impl Into<Axis> for ContainerSplit {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContainerLayout {
+    Split,
+    Tabbed,
+    Stacked,
+}
+
+impl From<Layout> for ContainerLayout {
+    fn from(l: Layout) -> Self {
+        match l {
+            Layout::Split => Self::Split,
+            Layout::Tabbed => Self::Tabbed,
+            Layout::Stacked => Self::Stacked,
+        }
+    }
+}
+
+impl From<ContainerLayout> for Layout {
+    fn from(l: ContainerLayout) -> Self {
+        match l {
+            ContainerLayout::Split => Self::Split,
+            ContainerLayout::Tabbed => Self::Tabbed,
+            ContainerLayout::Stacked => Self::Stacked,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ContainerFocus {
@@ -85,6 +114,13 @@ pub struct ContainerTitle {
     pub tex: TextTexture,
 }
 
+pub struct ContainerIcon {
+    pub x: i32,
+    pub y: i32,
+    pub size: i32,
+    pub tex: Rc<dyn GfxTexture>,
+}
+
 #[derive(Default)]
 pub struct ContainerRenderData {
     pub title_rects: Vec<Rect>,
@@ -94,6 +130,7 @@ pub struct ContainerRenderData {
     pub border_rects: Vec<Rect>,
     pub underline_rects: Vec<Rect>,
     pub titles: SmallMapMut<Scale, Vec<ContainerTitle>, 2>,
+    pub icons: SmallMapMut<Scale, Vec<ContainerIcon>, 2>,
 }
 
 pub struct ContainerNode {
@@ -102,6 +139,7 @@ pub struct ContainerNode {
     pub mono_child: CloneCell<Option<NodeRef<ContainerChild>>>,
     pub mono_body: Cell<Rect>,
     pub mono_content: Cell<Rect>,
+    pub mono_stacked: Cell<bool>,
     pub abs_x1: Cell<i32>,
     pub abs_y1: Cell<i32>,
     pub width: Cell<i32>,
@@ -206,6 +244,7 @@ impl ContainerNode {
             mono_child: CloneCell::new(None),
             mono_body: Cell::new(Default::default()),
             mono_content: Cell::new(Default::default()),
+            mono_stacked: Cell::new(false),
             abs_x1: Cell::new(0),
             abs_y1: Cell::new(0),
             width: Cell::new(0),
@@ -401,6 +440,15 @@ impl ContainerNode {
             .set(child.content.get().at_point(mb.x1(), mb.y1()));
 
         let th = self.state.theme.sizes.title_height.get();
+        if self.mono_stacked.get() {
+            for (i, child) in self.children.iter().enumerate() {
+                let y1 = i as i32 * (th + 1);
+                child
+                    .title_rect
+                    .set(Rect::new_sized(0, y1, self.width.get(), th).unwrap());
+            }
+            return;
+        }
         let bw = self.state.theme.sizes.border_width.get();
         let num_children = self.num_children.get() as i32;
         let content_width = self.width.get().sub(bw * (num_children - 1)).max(0);
@@ -528,12 +576,16 @@ impl ContainerNode {
                 self.content_width.set(self.width.get());
             }
         }
+        let titles_height = match self.mono_stacked.get() {
+            true => nc as i32 * (title_height + 1),
+            false => title_height + 1,
+        };
         self.mono_body.set(
             Rect::new_sized(
                 0,
-                title_height + 1,
+                titles_height,
                 self.width.get(),
-                self.height.get().sub(title_height + 1).max(0),
+                self.height.get().sub(titles_height).max(0),
             )
             .unwrap(),
         );
@@ -643,8 +695,9 @@ impl ContainerNode {
     fn update_title(&self) {
         let mut title = self.toplevel_data.title.borrow_mut();
         title.clear();
-        let split = match (self.mono_child.is_some(), self.split.get()) {
-            (true, _) => "T",
+        let split = match (self.layout(), self.split.get()) {
+            (ContainerLayout::Tabbed, _) => "T",
+            (ContainerLayout::Stacked, _) => "S",
             (_, ContainerSplit::Horizontal) => "H",
             (_, ContainerSplit::Vertical) => "V",
         };
@@ -681,6 +734,9 @@ impl ContainerNode {
         for (_, v) in rd.titles.iter_mut() {
             v.clear();
         }
+        for (_, v) in rd.icons.iter_mut() {
+            v.clear();
+        }
         rd.title_rects.clear();
         rd.active_title_rects.clear();
         rd.attention_title_rects.clear();
@@ -689,6 +745,7 @@ impl ContainerNode {
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let mono = self.mono_child.is_some();
+        let stacked = mono && self.mono_stacked.get();
         let split = self.split.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let scales = self.state.scales.lock();
@@ -699,7 +756,7 @@ impl ContainerNode {
             if self.toplevel_data.visible.get() {
                 self.state.damage(rect.move_(abs_x, abs_y));
             }
-            if i > 0 {
+            if i > 0 && !stacked {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
@@ -722,18 +779,37 @@ impl ContainerNode {
                 rd.title_rects.push(rect);
                 theme.colors.unfocused_title_text.get()
             };
-            if !mono {
+            if !mono || stacked {
                 let rect = Rect::new_sized(rect.x1(), rect.y2(), rect.width(), 1).unwrap();
                 rd.underline_rects.push(rect);
             }
             let title = child.title.borrow_mut();
+            let icon = child.node.tl_data().icon.get();
+            let icon_size = title_icon_size(th);
+            let mut text_offset = 0;
+            if icon.is_some() && icon_size > 0 && rect.width() > th {
+                text_offset = th;
+            }
             for (scale, _) in scales.iter() {
+                if let Some(icon) = &icon {
+                    if text_offset > 0 {
+                        let size = (icon_size as f64 * scale.to_f64()).round() as i32;
+                        if let Some(tex) = icon.image(size).texture(&self.state) {
+                            rd.icons.get_or_default_mut(*scale).push(ContainerIcon {
+                                x: rect.x1() + 1,
+                                y: rect.y1() + 1,
+                                size: icon_size,
+                                tex,
+                            });
+                        }
+                    }
+                }
                 let old_tex = child.title_tex.remove(scale);
                 let titles = rd.titles.get_or_default_mut(*scale);
                 'render_title: {
                     let mut th = th;
                     let mut scalef = None;
-                    let mut width = rect.width();
+                    let mut width = rect.width() - text_offset;
                     if *scale != 1 {
                         let scale = scale.to_f64();
                         th = (th as f64 * scale).round() as _;
@@ -757,7 +833,7 @@ impl ContainerNode {
                             Ok(t) => {
                                 child.title_tex.insert(*scale, t.clone());
                                 titles.push(ContainerTitle {
-                                    x: rect.x1(),
+                                    x: rect.x1() + text_offset,
                                     y: rect.y1(),
                                     tex: t,
                                 })
@@ -770,11 +846,12 @@ impl ContainerNode {
                 }
             }
         }
-        if mono {
+        if mono && !stacked {
             rd.underline_rects
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
         rd.titles.remove_if(|_, v| v.is_empty());
+        rd.icons.remove_if(|_, v| v.is_empty());
     }
 
     fn activate_child(self: &Rc<Self>, child: &NodeRef<ContainerChild>) {
@@ -846,6 +923,9 @@ impl ContainerNode {
                 }
             }
         }
+        if child.is_none() && self.mono_stacked.replace(false) {
+            self.update_content_size();
+        }
         self.mono_child.set(child);
         // log::info!("set_mono");
         self.schedule_layout();
@@ -861,6 +941,38 @@ impl ContainerNode {
         }
     }
 
+    pub fn layout(&self) -> ContainerLayout {
+        match (self.mono_child.is_some(), self.mono_stacked.get()) {
+            (false, _) => ContainerLayout::Split,
+            (true, false) => ContainerLayout::Tabbed,
+            (true, true) => ContainerLayout::Stacked,
+        }
+    }
+
+    pub fn set_layout(self: &Rc<Self>, layout: ContainerLayout, child: &dyn ToplevelNode) {
+        let stacked = match layout {
+            ContainerLayout::Split => {
+                self.set_mono(None);
+                return;
+            }
+            ContainerLayout::Tabbed => false,
+            ContainerLayout::Stacked => true,
+        };
+        if self.mono_stacked.replace(stacked) != stacked {
+            self.update_content_size();
+            self.schedule_layout();
+            self.update_title();
+        }
+        self.set_mono(Some(child));
+    }
+
+    fn mono_axis(&self) -> ContainerSplit {
+        match self.mono_stacked.get() {
+            true => ContainerSplit::Vertical,
+            false => ContainerSplit::Horizontal,
+        }
+    }
+
     fn parent_container(&self) -> Option<Rc<ContainerNode>> {
         self.toplevel_data
             .parent
@@ -879,15 +991,13 @@ impl ContainerNode {
             _ => return,
         };
         let mc = self.mono_child.get();
-        let in_line = if mc.is_some() {
-            matches!(direction, Direction::Left | Direction::Right)
-        } else {
-            match self.split.get() {
-                ContainerSplit::Horizontal => {
-                    matches!(direction, Direction::Left | Direction::Right)
-                }
-                ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
-            }
+        let split = match mc.is_some() {
+            true => self.mono_axis(),
+            false => self.split.get(),
+        };
+        let in_line = match split {
+            ContainerSplit::Horizontal => matches!(direction, Direction::Left | Direction::Right),
+            ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
         };
         if !in_line {
             if let Some(c) = self.parent_container() {
@@ -937,9 +1047,7 @@ impl ContainerNode {
         }
         let (split, prev) = direction_to_split(direction);
         // CASE 2: We're moving the child within the container.
-        if split == self.split.get()
-            || (split == ContainerSplit::Horizontal && self.mono_child.is_some())
-        {
+        if split == self.split.get() || (split == self.mono_axis() && self.mono_child.is_some()) {
             let cc = match self.child_nodes.borrow().get(&child.node_id()) {
                 Some(l) => l.to_ref(),
                 None => return,
@@ -1142,7 +1250,7 @@ impl ContainerNode {
                 && kind == SeatOpKind::Move
             {
                 drop(seat_datas);
-                seat.set_tl_floating(child.node.clone(), true);
+                self.state.set_tl_floating(child.node.clone(), true);
                 return;
             }
             seat_data.op = Some(SeatOp { child, kind })
@@ -1323,13 +1431,13 @@ impl Node for ContainerNode {
             Some(s) => s,
             _ => return,
         };
-        if seat_data.y > self.state.theme.sizes.title_height.get() {
-            return;
-        }
         let cur_mc = match self.mono_child.get() {
             Some(mc) => mc,
             _ => return,
         };
+        if seat_data.y >= self.mono_body.get().y1() {
+            return;
+        }
         let discrete = match self.scroller.handle(event) {
             Some(d) => d,
             _ => return,
@@ -1570,13 +1678,19 @@ impl ContainingNode for ContainerNode {
         self.workspace.get()
     }
 
+    fn cnode_child_icon_changed(self: Rc<Self>, child: &dyn Node) {
+        if self.child_nodes.borrow().contains_key(&child.node_id()) {
+            self.schedule_compute_render_data();
+        }
+    }
+
     fn cnode_set_child_position(self: Rc<Self>, child: &dyn Node, x: i32, y: i32) {
         let Some(parent) = self.toplevel_data.parent.get() else {
             return;
         };
-        let th = self.state.theme.sizes.title_height.get();
         if self.mono_child.is_some() {
-            parent.cnode_set_child_position(&*self, x, y - th - 1);
+            let th = self.mono_body.get().y1();
+            parent.cnode_set_child_position(&*self, x, y - th);
         } else {
             let children = self.child_nodes.borrow();
             let Some(child) = children.get(&child.node_id()) else {
@@ -1603,7 +1717,9 @@ impl ContainingNode for ContainerNode {
         let mut right_outside = false;
         let mut top_outside = false;
         let mut bottom_outside = false;
+        let mut titles_height = th + 1;
         if self.mono_child.is_some() {
+            titles_height = self.mono_body.get().y1();
             top_outside = true;
             right_outside = true;
             bottom_outside = true;
@@ -1707,10 +1823,10 @@ impl ContainingNode for ContainerNode {
             x2 = new_x2.map(|v| v.max(x1.unwrap_or(pos.x1())));
         }
         if top_outside {
-            y1 = new_y1.map(|v| (v - th - 1).min(pos.y2() - th - 1));
+            y1 = new_y1.map(|v| (v - titles_height).min(pos.y2() - titles_height));
         }
         if bottom_outside {
-            y2 = new_y2.map(|v| v.max(y1.unwrap_or(pos.y1()) + th + 1));
+            y2 = new_y2.map(|v| v.max(y1.unwrap_or(pos.y1()) + titles_height));
         }
         if (x1.is_some() && x1 != Some(pos.x1()))
             || (x2.is_some() && x2 != Some(pos.x2()))
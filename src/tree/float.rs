@@ -4,6 +4,8 @@ use {
         cursor::KnownCursor,
         cursor_user::CursorUser,
         fixed::Fixed,
+        gfx_api::GfxTexture,
+        icons::title_icon_size,
         ifs::wl_seat::{
             tablet::{TabletTool, TabletToolChanges, TabletToolId},
             NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT,
@@ -48,6 +50,7 @@ pub struct FloatNode {
     pub render_titles_scheduled: Cell<bool>,
     pub title: RefCell<String>,
     pub title_textures: CopyHashMap<Scale, TextTexture>,
+    pub icon_textures: CopyHashMap<Scale, Rc<dyn GfxTexture>>,
     cursors: RefCell<AHashMap<CursorType, CursorState>>,
     pub attention_requested: Cell<bool>,
 }
@@ -108,6 +111,7 @@ impl FloatNode {
         position: Rect,
         child: Rc<dyn ToplevelNode>,
     ) -> Rc<Self> {
+        let prev = child.tl_data().desired_extents.get();
         let floater = Rc::new(FloatNode {
             id: state.node_ids.next(),
             state: state.clone(),
@@ -123,6 +127,7 @@ impl FloatNode {
             render_titles_scheduled: Cell::new(false),
             title: Default::default(),
             title_textures: Default::default(),
+            icon_textures: Default::default(),
             cursors: Default::default(),
             attention_requested: Cell::new(false),
         });
@@ -137,6 +142,14 @@ impl FloatNode {
         floater.schedule_layout();
         if floater.visible.get() {
             state.damage(position);
+            if !prev.is_empty() {
+                // The window was already mapped. Move it from its previous position.
+                let now = state.now_nsec();
+                let output = ws.output.get().id;
+                state
+                    .animations
+                    .window_moved(floater.id.into(), output, prev, position, now);
+            }
         }
         floater
     }
@@ -194,6 +207,7 @@ impl FloatNode {
         let font = theme.font.borrow_mut();
         let title = self.title.borrow_mut();
         let pos = self.position.get();
+        self.icon_textures.clear();
         if pos.width() <= 2 * bw || title.is_empty() {
             return;
         }
@@ -203,11 +217,23 @@ impl FloatNode {
         };
         let scales = self.state.scales.lock();
         let tr = Rect::new_sized(pos.x1() + bw, pos.y1() + bw, pos.width() - 2 * bw, th).unwrap();
+        let icon = self.child.get().and_then(|c| c.tl_data().icon.get());
+        let icon_size = title_icon_size(th);
         for (scale, _) in scales.iter() {
+            let mut text_offset = 0;
+            if let Some(icon) = &icon {
+                if icon_size > 0 && tr.width() > th {
+                    let size = (icon_size as f64 * scale.to_f64()).round() as i32;
+                    if let Some(tex) = icon.image(size).texture(&self.state) {
+                        self.icon_textures.set(*scale, tex);
+                        text_offset = th;
+                    }
+                }
+            }
             let old_tex = self.title_textures.remove(scale);
             let mut th = tr.height();
             let mut scalef = None;
-            let mut width = tr.width();
+            let mut width = tr.width() - text_offset;
             if *scale != 1 {
                 let scale = scale.to_f64();
                 th = (th as f64 * scale).round() as _;
@@ -461,7 +487,7 @@ impl FloatNode {
             {
                 if let Some(tl) = self.child.get() {
                     drop(cursors);
-                    seat.set_tl_floating(tl, false);
+                    self.state.set_tl_floating(tl, false);
                     return;
                 }
             }
@@ -726,6 +752,10 @@ impl ContainingNode for FloatNode {
         self.workspace.get()
     }
 
+    fn cnode_child_icon_changed(self: Rc<Self>, _child: &dyn Node) {
+        self.schedule_render_titles();
+    }
+
     fn cnode_set_child_position(self: Rc<Self>, _child: &dyn Node, x: i32, y: i32) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
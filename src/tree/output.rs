@@ -1,11 +1,19 @@
 use {
     crate::{
-        backend::{HardwareCursor, KeyState, Mode},
+        animation::{AnimationKind, Snapshot},
+        backend::{GammaLut, HardwareCursor, KeyState, Mode, DEFAULT_COLOR_TEMPERATURE},
         client::ClientId,
+        color_management::{
+            ColorDescription, ColorDescriptionData, ColorTransform, Luminance, NamedPrimaries,
+            Primaries, TargetLuminance, TransferFunction,
+        },
         cursor::KnownCursor,
         fixed::Fixed,
         gfx_api::GfxTexture,
+        i3_ipc::I3WorkspaceChange,
         ifs::{
+            ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
+            ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
             jay_output::JayOutput,
             jay_screencast::JayScreencast,
             wl_buffer::WlBufferStorage,
@@ -19,8 +27,10 @@ use {
             wl_surface::{
                 ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
                 zwlr_layer_surface_v1::{ExclusiveSize, ZwlrLayerSurfaceV1},
-                SurfaceSendPreferredScaleVisitor, SurfaceSendPreferredTransformVisitor,
+                SurfaceSendPreferredColorDescriptionVisitor, SurfaceSendPreferredScaleVisitor,
+                SurfaceSendPreferredTransformVisitor, WlSurface,
             },
+            wp_color_management_output_v1::WpColorManagementOutputV1,
             wp_content_type_v1::ContentType,
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
@@ -40,7 +50,10 @@ use {
             hash_map_ext::HashMapExt, linkedlist::LinkedList, scroller::Scroller,
             transform_ext::TransformExt,
         },
-        wire::{JayOutputId, JayScreencastId, ZwlrScreencopyFrameV1Id},
+        wire::{
+            ExtImageCopyCaptureSessionV1Id, ExtWorkspaceGroupHandleV1Id, JayOutputId,
+            JayScreencastId, WlSurfaceId, WpColorManagementOutputV1Id, ZwlrScreencopyFrameV1Id,
+        },
     },
     ahash::AHashMap,
     jay_config::video::{TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode},
@@ -53,6 +66,9 @@ use {
     },
 };
 
+/// The number of entries in the gamma LUT used for color temperature and brightness.
+const COLOR_TRANSFORM_LUT_SIZE: usize = 256;
+
 tree_id!(OutputNodeId);
 pub struct OutputNode {
     pub id: OutputNodeId,
@@ -78,6 +94,15 @@ pub struct OutputNode {
     pub update_render_data_scheduled: Cell<bool>,
     pub screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
     pub screencopies: CopyHashMap<(ClientId, ZwlrScreencopyFrameV1Id), Rc<ZwlrScreencopyFrameV1>>,
+    pub image_copy_sessions:
+        CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
+    pub ext_workspace_groups:
+        CopyHashMap<(ClientId, ExtWorkspaceGroupHandleV1Id), Rc<ExtWorkspaceGroupHandleV1>>,
+    pub color_description: CloneCell<Rc<ColorDescription>>,
+    pub color_management_outputs:
+        CopyHashMap<(ClientId, WpColorManagementOutputV1Id), Rc<WpColorManagementOutputV1>>,
+    pub hdr_active: Cell<bool>,
+    pub fifo_barriers: CopyHashMap<WlSurfaceId, Rc<WlSurface>>,
     pub title_visible: Cell<bool>,
     pub schedule: Rc<OutputSchedule>,
 }
@@ -148,6 +173,12 @@ impl OutputNode {
         y_off: i32,
         size: Option<(i32, i32)>,
     ) {
+        let connector = self.global.connector.connector.id();
+        for mirror in self.state.mirrors.lock().values() {
+            if mirror.source == connector {
+                mirror.copy_frame(self, tex, render_hardware_cursor, x_off, y_off, size);
+            }
+        }
         if let Some(workspace) = self.workspace.get() {
             if !workspace.may_capture.get() {
                 return;
@@ -157,6 +188,16 @@ impl OutputNode {
         for sc in self.screencasts.lock().values() {
             sc.copy_texture(self, tex, render_hardware_cursor, x_off, y_off, size);
         }
+        for session in self.image_copy_sessions.lock().values() {
+            session.copy_texture(self, tex, render_hardware_cursor, x_off, y_off, size);
+        }
+    }
+
+    /// Returns the transform from the color space of the framebuffer to sRGB.
+    pub fn screencopy_color_transform(&self) -> Option<ColorTransform> {
+        self.color_description
+            .get()
+            .transform_to(self.state.color_manager.srgb())
     }
 
     pub fn perform_wlr_screencopies(
@@ -190,14 +231,17 @@ impl OutputNode {
                         let res = self.state.perform_shm_screencopy(
                             tex,
                             self.global.pos.get(),
+                            render_hardware_cursors,
                             x_off,
                             y_off,
                             size,
-                            &capture,
+                            capture.rect,
+                            &capture.client,
                             mem,
                             *stride,
                             wl_buffer.format,
                             Transform::None,
+                            self.screencopy_color_transform(),
                         );
                         if let Err(e) = res {
                             log::warn!("Could not perform shm screencopy: {}", ErrorFmt(e));
@@ -223,6 +267,7 @@ impl OutputNode {
                             y_off - capture.rect.y1(),
                             size,
                             Transform::None,
+                            self.screencopy_color_transform(),
                         );
                         if let Err(e) = res {
                             log::warn!("Could not perform screencopy: {}", ErrorFmt(e));
@@ -252,6 +297,19 @@ impl OutputNode {
         self.jay_outputs.clear();
         self.screencasts.clear();
         self.screencopies.clear();
+        for session in self.image_copy_sessions.clear().into_values() {
+            session.stop();
+        }
+        self.ext_workspace_groups.clear();
+        self.color_management_outputs.clear();
+        self.fifo_barriers.clear();
+    }
+
+    pub fn vblank(&self) {
+        let surfaces: Vec<_> = self.fifo_barriers.lock().drain_values().collect();
+        for surface in surfaces {
+            surface.clear_fifo_barrier();
+        }
     }
 
     pub fn on_spaces_changed(self: &Rc<Self>) {
@@ -450,9 +508,63 @@ impl OutputNode {
         self.create_workspace(&name)
     }
 
+    /// Takes a snapshot of the old workspace so that it can slide out.
+    fn snapshot_workspace(&self, old: &WorkspaceNode, new: &WorkspaceNode) {
+        let state = &self.state;
+        let animate = state.animations.enabled(AnimationKind::WorkspaceSwitch)
+            && self.node_visible()
+            && !state.lock.locked.get()
+            && old.fullscreen.is_none()
+            && new.fullscreen.is_none();
+        if !animate {
+            return;
+        }
+        let rect = self.workspace_rect.get();
+        if rect.is_empty() {
+            return;
+        }
+        let mut forward = true;
+        for workspace in self.workspaces.iter() {
+            if workspace.id == old.id {
+                break;
+            }
+            if workspace.id == new.id {
+                forward = false;
+                break;
+            }
+        }
+        let scale = self.global.persistent.scale.get();
+        let res = Snapshot::new(state, scale, rect.width(), rect.height(), |renderer| {
+            renderer.render_workspace(old, 0, 0);
+            for stacked in old.stacked.iter() {
+                if stacked.node_visible() {
+                    let pos = stacked.node_absolute_position();
+                    let (x, y) = rect.translate(pos.x1(), pos.y1());
+                    stacked.node_render(renderer, x, y, None);
+                }
+            }
+        });
+        match res {
+            Ok(snapshot) => {
+                let now = state.now_nsec();
+                state
+                    .animations
+                    .workspace_switched(self.id, new.id, forward, snapshot, now);
+            }
+            Err(e) => log::warn!("Could not snapshot the workspace: {}", ErrorFmt(e)),
+        }
+    }
+
     pub fn show_workspace(&self, ws: &Rc<WorkspaceNode>) -> bool {
         let mut seats = SmallVec::new();
-        if let Some(old) = self.workspace.set(Some(ws.clone())) {
+        let mut old_destroyed = false;
+        if let Some(old) = self.workspace.get() {
+            if old.id != ws.id {
+                self.snapshot_workspace(&old, ws);
+            }
+        }
+        let old = self.workspace.set(Some(ws.clone()));
+        if let Some(old) = &old {
             if old.id == ws.id {
                 return false;
             }
@@ -462,8 +574,12 @@ impl OutputNode {
                     jw.send_destroyed();
                     jw.workspace.set(None);
                 }
+                for handle in old.ext_workspaces.clear().into_values() {
+                    handle.workspace_removed();
+                }
                 old.clear();
                 self.state.workspaces.remove(&old.name);
+                old_destroyed = true;
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
@@ -480,6 +596,13 @@ impl OutputNode {
         if self.node_visible() {
             self.state.damage(self.global.pos.get());
         }
+        let ipc = &self.state.i3_ipc_clients;
+        ipc.workspace_event(I3WorkspaceChange::Focus, ws, old.as_ref());
+        if let Some(old) = &old {
+            if old_destroyed {
+                ipc.workspace_event(I3WorkspaceChange::Empty, old, None);
+            }
+        }
         true
     }
 
@@ -500,6 +623,7 @@ impl OutputNode {
             visible_on_desired_output: Cell::new(false),
             desired_output: CloneCell::new(self.global.output_id.clone()),
             jay_workspaces: Default::default(),
+            ext_workspaces: Default::default(),
             may_capture: self.state.default_workspace_capture.clone(),
             has_capture: Cell::new(false),
             title_texture: Default::default(),
@@ -509,6 +633,9 @@ impl OutputNode {
         ws.update_has_captures();
         *ws.output_link.borrow_mut() = Some(self.workspaces.add_last(ws.clone()));
         self.state.workspaces.set(name.to_string(), ws.clone());
+        self.state
+            .i3_ipc_clients
+            .workspace_event(I3WorkspaceChange::Init, &ws, None);
         if self.workspace.is_none() {
             self.show_workspace(&ws);
         }
@@ -521,6 +648,9 @@ impl OutputNode {
         for (client, e) in clients_to_kill.values() {
             client.error(e);
         }
+        for manager in self.state.workspace_managers.lock().values() {
+            manager.announce_workspace(&ws);
+        }
         self.schedule_update_render_data();
         ws
     }
@@ -583,6 +713,9 @@ impl OutputNode {
             for sc in self.screencasts.lock().values() {
                 sc.schedule_realloc();
             }
+            for session in self.image_copy_sessions.lock().values() {
+                session.update_constraints();
+            }
         }
 
         if transform != old_transform {
@@ -789,6 +922,108 @@ impl OutputNode {
         self.state.tree_changed();
     }
 
+    pub fn set_color_temperature(&self, kelvin: u32) {
+        self.global.persistent.color_temperature.set(kelvin);
+        self.update_gamma_lut();
+    }
+
+    pub fn set_brightness(&self, brightness: f64) {
+        self.global.persistent.brightness.set(brightness);
+        self.update_gamma_lut();
+    }
+
+    pub fn update_gamma_lut(&self) {
+        let persistent = &self.global.persistent;
+        let kelvin = persistent.color_temperature.get();
+        let brightness = persistent.brightness.get();
+        let mut lut = None;
+        if kelvin != DEFAULT_COLOR_TEMPERATURE || brightness != 1.0 {
+            lut = Some(Rc::new(GammaLut::color_transform(
+                kelvin,
+                brightness,
+                COLOR_TRANSFORM_LUT_SIZE,
+            )));
+        }
+        let connector = &self.global.connector;
+        connector.color_gamma_lut.set(lut);
+        connector.update_gamma_lut();
+    }
+
+    pub fn set_hdr_enabled(&self, enabled: bool) {
+        self.global.persistent.hdr_enabled.set(enabled);
+        self.global.connector.connector.set_hdr_enabled(enabled);
+    }
+
+    pub fn set_sdr_reference_white(self: &Rc<Self>, nits: f64) {
+        self.global.persistent.sdr_reference_white.set(nits);
+        if self.hdr_active.get() {
+            self.update_color_description();
+        }
+    }
+
+    pub fn set_hdr_active(self: &Rc<Self>, active: bool) {
+        if self.hdr_active.replace(active) != active {
+            let msg = match active {
+                true => "Enabled",
+                false => "Disabled",
+            };
+            log::info!("{msg} HDR on output {}", self.global.connector.name);
+        }
+        self.update_color_description();
+    }
+
+    fn update_color_description(self: &Rc<Self>) {
+        let connector_id = self.global.connector.connector.id();
+        let Some(output) = self.state.outputs.get(&connector_id) else {
+            return;
+        };
+        let info = &output.monitor_info;
+        let data = match (self.hdr_active.get(), info.hdr_metadata.as_deref()) {
+            (true, Some(hdr)) => ColorDescriptionData {
+                named_primaries: Some(NamedPrimaries::Bt2020),
+                primaries: Primaries::BT2020,
+                transfer_function: TransferFunction::St2084Pq,
+                luminance: Luminance {
+                    white: self.global.persistent.sdr_reference_white.get(),
+                    ..Luminance::ST2084_PQ
+                },
+                target_primaries: info.primaries,
+                target_luminance: hdr.max_luminance.map(|max| TargetLuminance {
+                    min: hdr.min_luminance.unwrap_or(0.0),
+                    max,
+                }),
+                max_cll: hdr.max_luminance.map(|l| l.round() as u32),
+                max_fall: hdr.max_frame_average_luminance.map(|l| l.round() as u32),
+            },
+            _ => ColorDescriptionData {
+                target_primaries: info.primaries,
+                ..ColorDescriptionData::SRGB
+            },
+        };
+        let cd = self.state.color_manager.get_description(data);
+        self.set_color_description(&cd);
+    }
+
+    pub fn set_color_description(self: &Rc<Self>, cd: &Rc<ColorDescription>) {
+        if self.color_description.get().data == cd.data {
+            return;
+        }
+        self.color_description.set(cd.clone());
+        for output in self.color_management_outputs.lock().values() {
+            output.send_image_description_changed();
+        }
+        let mut visitor = SurfaceSendPreferredColorDescriptionVisitor;
+        self.node_visit_children(&mut visitor);
+        for ws in self.workspaces.iter() {
+            for stacked in ws.stacked.iter() {
+                stacked.deref().clone().node_visit(&mut visitor);
+            }
+        }
+        self.hardware_cursor_needs_render.set(true);
+        self.state.refresh_hardware_cursors();
+        self.state.damage(self.global.pos.get());
+    }
+
     pub fn update_presentation_type(&self) {
         self.update_vrr_state();
         self.update_tearing();
@@ -0,0 +1,94 @@
+use {
+    crate::{
+        ifs::wl_seat::{collect_kb_foci, WlSeatGlobal},
+        state::State,
+        tree::{Direction, Node, ToplevelNode},
+        utils::linkedlist::LinkedList,
+    },
+    std::{ops::Deref, rc::Rc},
+};
+
+/// Windows that have been moved to the scratchpad.
+///
+/// Windows in the scratchpad are not part of any workspace and are not visible. When a
+/// seat shows the scratchpad, the window that has been hidden for the longest time is
+/// mapped floating on the current output of the seat. Windows that have been shown this
+/// way remain scratchpad windows until they are tiled.
+#[derive(Default)]
+pub struct Scratchpad {
+    windows: LinkedList<Rc<dyn ToplevelNode>>,
+}
+
+impl Scratchpad {
+    pub fn clear(&self) {
+        let windows: Vec<_> = self.windows.iter().map(|w| w.deref().clone()).collect();
+        for window in windows {
+            window.tl_data().scratchpad_link.take();
+        }
+    }
+
+    pub fn windows(&self) -> Vec<Rc<dyn ToplevelNode>> {
+        self.windows.iter().map(|w| w.deref().clone()).collect()
+    }
+
+    pub fn hide(&self, state: &Rc<State>, tl: Rc<dyn ToplevelNode>) {
+        if tl.node_is_container() || tl.node_is_placeholder() {
+            return;
+        }
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        let Some(parent) = data.parent.get() else {
+            return;
+        };
+        let old_ws = data.workspace.get();
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        data.parent.take();
+        data.workspace.take();
+        tl.tl_set_visible(false);
+        if let Some(old_ws) = old_ws {
+            for seat in kb_foci {
+                old_ws.clone().node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
+        data.is_scratchpad.set(true);
+        *data.scratchpad_link.borrow_mut() = Some(self.windows.add_last(tl.clone()));
+        data.update_wlr_output();
+        data.update_wlr_state();
+        state.tree_changed();
+    }
+
+    pub fn show(&self, state: &Rc<State>, seat: &Rc<WlSeatGlobal>) {
+        if let Some(tl) = self.windows.first().map(|w| w.deref().clone()) {
+            self.show_window(state, seat, tl);
+        }
+    }
+
+    /// Shows a specific window of the scratchpad on the current output of the seat.
+    pub fn show_window(
+        &self,
+        state: &Rc<State>,
+        seat: &Rc<WlSeatGlobal>,
+        tl: Rc<dyn ToplevelNode>,
+    ) {
+        let output = seat.get_output();
+        if output.is_dummy {
+            log::warn!("Not showing scratchpad because seat is on dummy output");
+            return;
+        }
+        let data = tl.tl_data();
+        if data.scratchpad_link.take().is_none() {
+            return;
+        }
+        data.update_wlr_state();
+        let ws = output.ensure_workspace();
+        let (width, height) = data.float_size(&ws);
+        state.map_floating(tl.clone(), width, height, &ws, None);
+        if tl.node_visible() {
+            tl.node_do_focus(seat, Direction::Unspecified);
+        }
+        state.tree_changed();
+    }
+}
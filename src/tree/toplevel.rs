@@ -1,27 +1,43 @@
 use {
     crate::{
+        animation::{AnimationKind, Snapshot},
         client::{Client, ClientId},
+        i3_ipc::I3WindowChange,
+        icons::Icon,
         ifs::{
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
+            ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             jay_screencast::JayScreencast,
             jay_toplevel::JayToplevel,
+            wl_output::WlOutputGlobal,
             wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
             wl_surface::WlSurface,
+            zwlr_foreign_toplevel_handle_v1::{
+                ZwlrForeignToplevelHandleV1, STATE_ACTIVATED, STATE_FULLSCREEN, STATE_MINIMIZED,
+            },
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
         },
         rect::Rect,
         state::State,
-        tree::{ContainingNode, Direction, Node, OutputNode, PlaceholderNode, WorkspaceNode},
+        tree::{
+            ContainingNode, Direction, Node, NodeId, OutputNode, PlaceholderNode, WorkspaceNode,
+        },
         utils::{
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
             hash_map_ext::HashMapExt,
+            linkedlist::LinkedNode,
             numcell::NumCell,
             smallmap::SmallMap,
             threshold_counter::ThresholdCounter,
             toplevel_identifier::{toplevel_identifier, ToplevelIdentifier},
         },
-        wire::{ExtForeignToplevelHandleV1Id, JayScreencastId, JayToplevelId},
+        wire::{
+            ExtForeignToplevelHandleV1Id, ExtImageCopyCaptureSessionV1Id, JayScreencastId,
+            JayToplevelId, ZwlrForeignToplevelHandleV1Id,
+        },
     },
     std::{
         cell::{Cell, RefCell},
@@ -80,18 +96,27 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
 
     fn tl_title_changed(&self) {
         let data = self.tl_data();
-        let title = data.title.borrow_mut();
-        if let Some(parent) = data.parent.get() {
-            parent.node_child_title_changed(self, &title);
+        {
+            let title = data.title.borrow_mut();
+            if let Some(parent) = data.parent.get() {
+                parent.node_child_title_changed(self, &title);
+            }
+            if let Some(data) = data.fullscrceen_data.borrow_mut().deref() {
+                data.placeholder
+                    .tl_data()
+                    .title
+                    .borrow_mut()
+                    .clone_from(&title);
+                data.placeholder.tl_title_changed();
+            }
         }
-        if let Some(data) = data.fullscrceen_data.borrow_mut().deref() {
-            data.placeholder
-                .tl_data()
-                .title
-                .borrow_mut()
-                .clone_from(&title);
-            data.placeholder.tl_title_changed();
+        let id = self.node_id();
+        if data.state.toplevels.contains(&id) {
+            if let Some(config) = data.state.config.get() {
+                config.window_title_changed(id);
+            }
         }
+        data.send_i3_ipc_event(id, I3WindowChange::Title);
     }
 
     fn tl_set_parent(&self, parent: Rc<dyn ContainingNode>) {
@@ -113,16 +138,35 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
     fn tl_set_workspace(&self, ws: &Rc<WorkspaceNode>) {
         let data = self.tl_data();
         data.workspace.set(Some(ws.clone()));
+        data.update_wlr_output();
         self.tl_set_workspace_ext(ws);
     }
 
     fn tl_change_extents(self: Rc<Self>, rect: &Rect) {
         let data = self.tl_data();
         let prev = data.desired_extents.replace(*rect);
+        let animate = prev != *rect
+            && !prev.is_empty()
+            && data.visible.get()
+            && !data.is_floating.get()
+            && !data.is_fullscreen.get()
+            && !self.tl_admits_children();
+        if animate {
+            if let Some(ws) = data.workspace.get() {
+                let output = ws.output.get().id;
+                let now = data.state.now_nsec();
+                data.state
+                    .animations
+                    .window_moved(self.node_id(), output, prev, *rect, now);
+            }
+        }
         if prev.size() != rect.size() {
             for sc in data.jay_screencasts.lock().values() {
                 sc.schedule_realloc();
             }
+            for session in data.image_copy_sessions.lock().values() {
+                session.update_constraints();
+            }
         }
         if data.is_floating.get() {
             data.float_width.set(rect.width());
@@ -137,6 +181,7 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
     }
 
     fn tl_destroy(&self) {
+        self.tl_data().snapshot_closing(self);
         self.tl_data().destroy_node(self);
         self.tl_destroy_impl();
     }
@@ -217,12 +262,20 @@ pub struct ToplevelData {
     pub wants_attention: Cell<bool>,
     pub requested_attention: Cell<bool>,
     pub app_id: RefCell<String>,
+    pub icon: CloneCell<Option<Rc<Icon>>>,
     pub identifier: Cell<ToplevelIdentifier>,
     pub handles:
         CopyHashMap<(ClientId, ExtForeignToplevelHandleV1Id), Rc<ExtForeignToplevelHandleV1>>,
+    pub wlr_handles:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelHandleV1Id), Rc<ZwlrForeignToplevelHandleV1>>,
     pub render_highlight: NumCell<u32>,
     pub jay_toplevels: CopyHashMap<(ClientId, JayToplevelId), Rc<JayToplevel>>,
     pub jay_screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
+    pub image_copy_sessions:
+        CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
+    pub matched_window_rules: CopyHashMap<u64, ()>,
+    pub is_scratchpad: Cell<bool>,
+    pub scratchpad_link: RefCell<Option<LinkedNode<Rc<dyn ToplevelNode>>>>,
 }
 
 impl ToplevelData {
@@ -248,11 +301,17 @@ impl ToplevelData {
             wants_attention: Cell::new(false),
             requested_attention: Cell::new(false),
             app_id: Default::default(),
+            icon: Default::default(),
             identifier: Cell::new(toplevel_identifier()),
             handles: Default::default(),
+            wlr_handles: Default::default(),
             render_highlight: Default::default(),
             jay_toplevels: Default::default(),
             jay_screencasts: Default::default(),
+            image_copy_sessions: Default::default(),
+            matched_window_rules: Default::default(),
+            is_scratchpad: Cell::new(false),
+            scratchpad_link: Default::default(),
         }
     }
 
@@ -269,6 +328,17 @@ impl ToplevelData {
             if let Some(parent) = self.parent.get() {
                 parent.node_child_active_changed(tl.tl_as_node(), active_new, 1);
             }
+            if active_new {
+                self.send_i3_ipc_event(tl.node_id(), I3WindowChange::Focus);
+            }
+            self.update_wlr_state();
+        }
+    }
+
+    /// Sends an i3 IPC window event if the window is visible to the config.
+    pub fn send_i3_ipc_event(&self, id: NodeId, change: I3WindowChange) {
+        if let Some(tl) = self.state.toplevels.get(&id) {
+            self.state.i3_ipc_clients.window_event(change, &tl);
         }
     }
 
@@ -296,16 +366,93 @@ impl ToplevelData {
         for screencast in self.jay_screencasts.lock().drain_values() {
             screencast.do_destroy();
         }
+        for session in self.image_copy_sessions.clear().into_values() {
+            session.stop();
+        }
         self.identifier.set(toplevel_identifier());
+        self.matched_window_rules.clear();
+        self.is_scratchpad.set(false);
+        self.scratchpad_link.take();
+        self.unregister(node.node_id());
+        self.state.animations.remove_window(node.node_id());
         {
             let mut handles = self.handles.lock();
             for handle in handles.drain_values() {
                 handle.send_closed();
             }
         }
+        for handle in self.wlr_handles.clear().into_values() {
+            handle.send_closed();
+        }
         self.detach_node(node);
     }
 
+    /// Takes a snapshot of the node so that it can fade out after it has been destroyed.
+    fn snapshot_closing(&self, node: &dyn ToplevelNode) {
+        let state = &self.state;
+        let animate = state.animations.enabled(AnimationKind::WindowClose)
+            && self.visible.get()
+            && !self.is_fullscreen.get()
+            && !node.tl_admits_children();
+        if !animate {
+            return;
+        }
+        match node.tl_scanout_surface() {
+            Some(surface) if surface.buffer.is_some() => {}
+            _ => return,
+        }
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let rect = self.desired_extents.get();
+        if rect.is_empty() {
+            return;
+        }
+        let output = ws.output.get();
+        let scale = output.global.persistent.scale.get();
+        let res = Snapshot::new(state, scale, rect.width(), rect.height(), |renderer| {
+            node.tl_as_node().node_render(renderer, 0, 0, None);
+        });
+        match res {
+            Ok(snapshot) => {
+                let now = state.now_nsec();
+                state
+                    .animations
+                    .window_closed(rect, output.id, ws.id, snapshot, now);
+            }
+            Err(e) => log::warn!("Could not snapshot the closing window: {}", ErrorFmt(e)),
+        }
+    }
+
+    /// Makes a mapped window visible to the config.
+    pub fn register(&self, toplevel: Rc<dyn ToplevelNode>) {
+        let id = toplevel.node_id();
+        if self.state.toplevels.set(id, toplevel.clone()).is_none() {
+            if let Some(ws) = self.workspace.get() {
+                self.state
+                    .animations
+                    .window_opened(id, ws.output.get().id, self.state.now_nsec());
+            }
+            if let Some(config) = self.state.config.get() {
+                config.new_window(id);
+            }
+            self.state
+                .i3_ipc_clients
+                .window_event(I3WindowChange::New, &toplevel);
+        }
+    }
+
+    fn unregister(&self, id: NodeId) {
+        if let Some(tl) = self.state.toplevels.remove(&id) {
+            if let Some(config) = self.state.config.get() {
+                config.window_closed(id);
+            }
+            self.state
+                .i3_ipc_clients
+                .window_event(I3WindowChange::Close, &tl);
+        }
+    }
+
     pub fn detach_node(&self, node: &dyn Node) {
         if let Some(fd) = self.fullscrceen_data.borrow_mut().take() {
             fd.placeholder.tl_destroy();
@@ -325,6 +472,9 @@ impl ToplevelData {
         for list in self.state.toplevel_lists.lock().values() {
             self.send_once(&toplevel, list, &id, &title, &app_id);
         }
+        for manager in self.state.foreign_toplevel_managers.lock().values() {
+            self.send_wlr_once(&toplevel, manager, &title, &app_id);
+        }
     }
 
     pub fn send(&self, toplevel: Rc<dyn ToplevelNode>, list: &ExtForeignToplevelListV1) {
@@ -354,12 +504,87 @@ impl ToplevelData {
             .set((handle.client.id, handle.id), handle.clone());
     }
 
+    pub fn send_wlr(&self, toplevel: Rc<dyn ToplevelNode>, manager: &ZwlrForeignToplevelManagerV1) {
+        let title = self.title.borrow();
+        let app_id = self.app_id.borrow();
+        self.send_wlr_once(&toplevel, manager, &title, &app_id);
+    }
+
+    fn send_wlr_once(
+        &self,
+        toplevel: &Rc<dyn ToplevelNode>,
+        manager: &ZwlrForeignToplevelManagerV1,
+        title: &str,
+        app_id: &str,
+    ) {
+        let handle = match manager.publish_toplevel(toplevel) {
+            None => return,
+            Some(handle) => handle,
+        };
+        handle.send_title(title);
+        handle.send_app_id(app_id);
+        handle.set_output(self.wlr_output().as_ref());
+        handle.send_state(&self.wlr_state());
+        handle.send_done();
+        self.wlr_handles
+            .set((handle.client.id, handle.id), handle.clone());
+    }
+
+    fn wlr_output(&self) -> Option<Rc<WlOutputGlobal>> {
+        self.workspace
+            .get()
+            .map(|ws| ws.output.get().global.clone())
+    }
+
+    fn wlr_state(&self) -> Vec<u32> {
+        let mut state = vec![];
+        if self.active() {
+            state.push(STATE_ACTIVATED);
+        }
+        if self.is_fullscreen.get() {
+            state.push(STATE_FULLSCREEN);
+        }
+        if self.scratchpad_link.borrow().is_some() {
+            state.push(STATE_MINIMIZED);
+        }
+        state
+    }
+
+    /// Sends the current state to all wlr-foreign-toplevel handles.
+    pub fn update_wlr_state(&self) {
+        if self.wlr_handles.is_empty() {
+            return;
+        }
+        let state = self.wlr_state();
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_state(&state);
+            handle.send_done();
+        }
+    }
+
+    /// Sends output_enter/output_leave events to all wlr-foreign-toplevel handles.
+    pub fn update_wlr_output(&self) {
+        if self.wlr_handles.is_empty() {
+            return;
+        }
+        let output = self.wlr_output();
+        for handle in self.wlr_handles.lock().values() {
+            if handle.set_output(output.as_ref()) {
+                handle.send_done();
+            }
+        }
+    }
+
     pub fn set_title(&self, title: &str) {
         *self.title.borrow_mut() = title.to_string();
         for handle in self.handles.lock().values() {
             handle.send_title(title);
             handle.send_done();
         }
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_title(title);
+            handle.send_done();
+        }
     }
 
     pub fn set_app_id(&self, app_id: &str) {
@@ -368,6 +593,17 @@ impl ToplevelData {
             handle.send_app_id(app_id);
             handle.send_done();
         }
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_app_id(app_id);
+            handle.send_done();
+        }
+    }
+
+    pub fn set_icon(&self, node: &dyn Node, icon: Option<Rc<Icon>>) {
+        self.icon.set(icon);
+        if let Some(parent) = self.parent.get() {
+            parent.cnode_child_icon_changed(node);
+        }
     }
 
     pub fn set_fullscreen(
@@ -435,6 +671,8 @@ impl ToplevelData {
                 .tl_into_node()
                 .node_do_focus(&seat, Direction::Unspecified);
         }
+        self.send_i3_ipc_event(node.node_id(), I3WindowChange::FullscreenMode);
+        self.update_wlr_state();
     }
 
     pub fn unset_fullscreen(&self, state: &Rc<State>, node: Rc<dyn ToplevelNode>) {
@@ -450,6 +688,7 @@ impl ToplevelData {
             }
         };
         self.is_fullscreen.set(false);
+        self.update_wlr_state();
         match fd.workspace.fullscreen.get() {
             None => {
                 log::error!("Node is supposed to be fullscreened on a workspace but workspace has not fullscreen node.");
@@ -462,6 +701,7 @@ impl ToplevelData {
             _ => {}
         }
         fd.workspace.remove_fullscreen_node();
+        self.send_i3_ipc_event(node.node_id(), I3WindowChange::FullscreenMode);
         if fd.placeholder.is_destroyed() {
             state.map_tiled(node);
             return;
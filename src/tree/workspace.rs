@@ -3,11 +3,15 @@ use {
         client::ClientId,
         cursor::KnownCursor,
         fixed::Fixed,
+        i3_ipc::I3WorkspaceChange,
         ifs::{
+            ext_workspace_handle_v1::ExtWorkspaceHandleV1,
             jay_workspace::JayWorkspace,
             wl_output::OutputId,
             wl_seat::{tablet::TabletTool, NodeSeatState, WlSeatGlobal},
-            wl_surface::WlSurface,
+            wl_surface::{
+                x_surface::xwindow::Xwindow, xdg_surface::xdg_toplevel::XdgToplevel, WlSurface,
+            },
         },
         rect::Rect,
         renderer::Renderer,
@@ -16,7 +20,7 @@ use {
         tree::{
             container::ContainerNode, walker::NodeVisitor, ContainingNode, Direction,
             FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitorBase, OutputNode,
-            StackedNode, ToplevelNode,
+            StackedNode, ToplevelNode, ToplevelNodeBase,
         },
         utils::{
             clonecell::CloneCell,
@@ -25,7 +29,7 @@ use {
             numcell::NumCell,
             threshold_counter::ThresholdCounter,
         },
-        wire::JayWorkspaceId,
+        wire::{ExtWorkspaceHandleV1Id, JayWorkspaceId},
     },
     std::{
         cell::{Cell, RefCell},
@@ -53,6 +57,7 @@ pub struct WorkspaceNode {
     pub visible_on_desired_output: Cell<bool>,
     pub desired_output: CloneCell<Rc<OutputId>>,
     pub jay_workspaces: CopyHashMap<(ClientId, JayWorkspaceId), Rc<JayWorkspace>>,
+    pub ext_workspaces: CopyHashMap<(ClientId, ExtWorkspaceHandleV1Id), Rc<ExtWorkspaceHandleV1>>,
     pub may_capture: Cell<bool>,
     pub has_capture: Cell<bool>,
     pub title_texture: Cell<Option<TextTexture>>,
@@ -66,6 +71,7 @@ impl WorkspaceNode {
         *self.output_link.borrow_mut() = None;
         self.fullscreen.set(None);
         self.jay_workspaces.clear();
+        self.ext_workspaces.clear();
     }
 
     pub fn update_has_captures(&self) {
@@ -81,7 +87,7 @@ impl WorkspaceNode {
                     break 'update;
                 }
             }
-            if output.screencopies.is_not_empty() {
+            if output.screencopies.is_not_empty() || output.image_copy_sessions.is_not_empty() {
                 has_capture = true;
             }
         }
@@ -96,12 +102,25 @@ impl WorkspaceNode {
         for jw in self.jay_workspaces.lock().values() {
             jw.send_output(output);
         }
+        for handle in self.ext_workspaces.lock().values() {
+            handle.update_group();
+        }
         self.update_has_captures();
         struct OutputSetter<'a>(&'a Rc<OutputNode>);
         impl NodeVisitorBase for OutputSetter<'_> {
             fn visit_surface(&mut self, node: &Rc<WlSurface>) {
                 node.set_output(self.0);
             }
+
+            fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+                node.tl_data().update_wlr_output();
+                node.node_visit_children(self);
+            }
+
+            fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+                node.tl_data().update_wlr_output();
+                node.node_visit_children(self);
+            }
         }
         let mut visitor = OutputSetter(output);
         self.node_visit_children(&mut visitor);
@@ -149,6 +168,9 @@ impl WorkspaceNode {
         for jw in self.jay_workspaces.lock().values() {
             jw.send_visible(visible);
         }
+        for handle in self.ext_workspaces.lock().values() {
+            handle.update_state();
+        }
         for stacked in self.stacked.iter() {
             stacked.stacked_prepare_set_visible();
         }
@@ -215,6 +237,9 @@ impl WorkspaceNode {
         let crossed_threshold = self.attention_requests.adj(set);
         if crossed_threshold {
             self.output.get().schedule_update_render_data();
+            for handle in self.ext_workspaces.lock().values() {
+                handle.update_state();
+            }
         }
     }
 }
@@ -400,4 +425,8 @@ pub fn move_ws_to_output(
     if target.node_visible() {
         target.state.damage(target.global.pos.get());
     }
+    target
+        .state
+        .i3_ipc_clients
+        .workspace_event(I3WorkspaceChange::Move, ws, None);
 }
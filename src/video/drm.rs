@@ -8,13 +8,14 @@ use {
         video::drm::sys::{
             create_lease, drm_event, drm_event_vblank, gem_close, get_cap,
             get_device_name_from_fd2, get_minor_name_from_fd, get_node_type_from_fd, get_nodes,
-            mode_addfb2, mode_atomic, mode_create_blob, mode_destroy_blob, mode_get_resources,
-            mode_getconnector, mode_getencoder, mode_getplane, mode_getplaneresources,
-            mode_getprobblob, mode_getproperty, mode_obj_getproperties, mode_rmfb,
-            prime_fd_to_handle, set_client_cap, DRM_DISPLAY_MODE_LEN, DRM_MODE_ATOMIC_TEST_ONLY,
-            DRM_MODE_FB_MODIFIERS, DRM_MODE_OBJECT_BLOB, DRM_MODE_OBJECT_CONNECTOR,
-            DRM_MODE_OBJECT_CRTC, DRM_MODE_OBJECT_ENCODER, DRM_MODE_OBJECT_FB,
-            DRM_MODE_OBJECT_MODE, DRM_MODE_OBJECT_PLANE, DRM_MODE_OBJECT_PROPERTY,
+            get_nodes_of_dev, mode_addfb2, mode_atomic, mode_create_blob, mode_destroy_blob,
+            mode_get_resources, mode_getconnector, mode_getencoder, mode_getplane,
+            mode_getplaneresources, mode_getprobblob, mode_getproperty, mode_obj_getproperties,
+            mode_rmfb, prime_fd_to_handle, set_client_cap, DRM_DISPLAY_MODE_LEN,
+            DRM_MODE_ATOMIC_TEST_ONLY, DRM_MODE_FB_MODIFIERS, DRM_MODE_OBJECT_BLOB,
+            DRM_MODE_OBJECT_CONNECTOR, DRM_MODE_OBJECT_CRTC, DRM_MODE_OBJECT_ENCODER,
+            DRM_MODE_OBJECT_FB, DRM_MODE_OBJECT_MODE, DRM_MODE_OBJECT_PLANE,
+            DRM_MODE_OBJECT_PROPERTY,
         },
     },
     ahash::AHashMap,
@@ -48,8 +49,10 @@ use crate::{
     },
 };
 pub use sys::{
-    drm_mode_modeinfo, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
-    DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT,
+    drm_color_lut, drm_mode_modeinfo, hdr_metadata_infoframe, hdr_output_metadata,
+    DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK,
+    DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT, HDMI_EOTF_SMPTE_ST2084,
+    HDMI_STATIC_METADATA_TYPE1,
 };
 
 #[derive(Debug, Error)]
@@ -188,6 +191,25 @@ impl Drm {
         })
     }
 
+    /// Opens the device with the given device number.
+    ///
+    /// The render node of the device is preferred over the primary node.
+    pub fn open_dev(dev: c::dev_t) -> Result<Self, DrmError> {
+        let nodes =
+            get_nodes_of_dev(uapi::major(dev), uapi::minor(dev)).map_err(DrmError::GetNodes)?;
+        let path = match nodes
+            .get(&NodeType::Render)
+            .or_else(|| nodes.get(&NodeType::Primary))
+        {
+            Some(p) => p,
+            _ => return Err(DrmError::GetNodes(OsError(c::ENODEV))),
+        };
+        match uapi::open(path.as_c_str(), c::O_RDWR | c::O_CLOEXEC, 0) {
+            Ok(f) => Ok(Self { fd: Rc::new(f) }),
+            Err(e) => Err(DrmError::ReopenNode(e.into())),
+        }
+    }
+
     pub fn fd(&self) -> &Rc<OwnedFd> {
         &self.fd
     }
@@ -367,7 +389,7 @@ impl DrmMaster {
         res
     }
 
-    pub fn create_blob<T>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
+    pub fn create_blob<T: ?Sized>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
         match mode_create_blob(self.raw(), t) {
             Ok(b) => Ok(PropBlob {
                 master: self.clone(),
@@ -851,7 +873,6 @@ pub struct ObjectChange<'a> {
 }
 
 impl Change {
-    #[allow(dead_code)]
     pub fn test(&self, flags: u32) -> Result<(), DrmError> {
         mode_atomic(
             self.master.raw(),
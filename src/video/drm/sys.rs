@@ -172,7 +172,10 @@ pub fn get_device_name_from_fd2(fd: c::c_int) -> Result<Ustring, OsError> {
 
 pub fn get_nodes(fd: c::c_int) -> Result<AHashMap<NodeType, CString>, OsError> {
     let (_, maj, min) = drm_stat(fd)?;
+    get_nodes_of_dev(maj, min)
+}
 
+pub fn get_nodes_of_dev(maj: u64, min: u64) -> Result<AHashMap<NodeType, CString>, OsError> {
     let dir = device_dir(maj, min);
     let mut dir = uapi::opendir(dir)?;
 
@@ -717,6 +720,44 @@ pub struct drm_mode_modeinfo {
 
 unsafe impl Pod for drm_mode_modeinfo {}
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct drm_color_lut {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub reserved: u16,
+}
+
+unsafe impl Pod for drm_color_lut {}
+
+pub const HDMI_STATIC_METADATA_TYPE1: u8 = 0;
+
+pub const HDMI_EOTF_SMPTE_ST2084: u8 = 2;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct hdr_metadata_infoframe {
+    pub eotf: u8,
+    pub metadata_type: u8,
+    pub display_primaries: [[u16; 2]; 3],
+    pub white_point: [u16; 2],
+    pub max_display_mastering_luminance: u16,
+    pub min_display_mastering_luminance: u16,
+    pub max_cll: u16,
+    pub max_fall: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct hdr_output_metadata {
+    pub metadata_type: u32,
+    pub hdmi_metadata_type1: hdr_metadata_infoframe,
+    pub _padding: u16,
+}
+
+unsafe impl Pod for hdr_output_metadata {}
+
 impl Into<DrmModeInfo> for drm_mode_modeinfo {
     fn into(self) -> DrmModeInfo {
         DrmModeInfo {
@@ -919,9 +960,9 @@ struct drm_mode_create_blob {
 
 const DRM_IOCTL_MODE_CREATEPROPBLOB: u64 = drm_iowr::<drm_mode_create_blob>(0xbd);
 
-pub fn mode_create_blob<T>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
+pub fn mode_create_blob<T: ?Sized>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
     let mut res = drm_mode_create_blob {
-        data: t as *const T as _,
+        data: t as *const T as *const u8 as _,
         length: mem::size_of_val(t) as _,
         blob_id: 0,
     };
@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        async_engine::{Phase, SpawnedFuture},
+        backend::{
+            BackendEvent, Connector, ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId,
+            Mode, MonitorInfo,
+        },
+        format::XRGB8888,
+        gfx_api::GfxContext,
+        offscreen_image::{OffscreenImage, OffscreenImageError},
+        renderer::RenderResult,
+        state::State,
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell, errorfmt::ErrorFmt, on_change::OnChange,
+        },
+        video::drm::ConnectorType,
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+/// The maximum width and height of a virtual output.
+pub const MAX_SIZE: i32 = 8192;
+/// The maximum refresh rate of a virtual output.
+const MAX_REFRESH_RATE_MILLIHZ: u32 = 1_000_000;
+
+/// Modes that are offered in addition to the initial mode of a virtual output.
+const COMMON_MODES: [(i32, i32); 5] = [
+    (1024, 768),
+    (1280, 720),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+#[derive(Debug, Error)]
+pub enum VirtualOutputError {
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Render device does not support XRGB8888 format")]
+    XRGB8888,
+    #[error(transparent)]
+    Image(#[from] OffscreenImageError),
+}
+
+/// An output that is not backed by a display.
+///
+/// The output is rendered into an offscreen buffer and can only be observed via
+/// screencasts and screenshots.
+pub struct VirtualOutput {
+    pub id: ConnectorId,
+    pub idx: u32,
+    state: Rc<State>,
+    drm_dev: Option<DrmDeviceId>,
+    events: OnChange<ConnectorEvent>,
+    mode: Cell<Mode>,
+    image: CloneCell<Option<Rc<VirtualImage>>>,
+    render_result: RefCell<RenderResult>,
+    damaged: AsyncEvent,
+    present_task: Cell<Option<SpawnedFuture<()>>>,
+}
+
+struct VirtualImage {
+    ctx: Rc<dyn GfxContext>,
+    mode: Mode,
+    image: OffscreenImage,
+}
+
+/// Parses a mode of the form `WIDTHxHEIGHT` or `WIDTHxHEIGHT@REFRESH`.
+///
+/// The refresh rate is given in Hz and defaults to 60.
+pub fn parse_mode(s: &str) -> Option<Mode> {
+    let (size, refresh) = match s.split_once('@') {
+        Some((size, refresh)) => (size, Some(refresh)),
+        _ => (s, None),
+    };
+    let (width, height) = size.split_once('x')?;
+    let width: i32 = width.trim().parse().ok()?;
+    let height: i32 = height.trim().parse().ok()?;
+    let refresh_rate_millihz = match refresh {
+        Some(r) => (r.trim().parse::<f64>().ok()? * 1000.0).round() as u32,
+        _ => 60_000,
+    };
+    let mode = Mode {
+        width,
+        height,
+        refresh_rate_millihz,
+    };
+    validate_mode(mode).then_some(mode)
+}
+
+pub fn validate_mode(mode: Mode) -> bool {
+    (1..=MAX_SIZE).contains(&mode.width)
+        && (1..=MAX_SIZE).contains(&mode.height)
+        && (1..=MAX_REFRESH_RATE_MILLIHZ).contains(&mode.refresh_rate_millihz)
+}
+
+impl VirtualOutput {
+    pub fn new(state: &Rc<State>, mode: Mode, drm_dev: Option<DrmDeviceId>) -> Rc<Self> {
+        let mut idx = 1;
+        {
+            let outputs = state.virtual_outputs.lock();
+            while outputs.values().any(|o| o.idx == idx) {
+                idx += 1;
+            }
+        }
+        let slf = Rc::new(Self {
+            id: state.connector_ids.next(),
+            idx,
+            state: state.clone(),
+            drm_dev,
+            events: Default::default(),
+            mode: Cell::new(mode),
+            image: Default::default(),
+            render_result: Default::default(),
+            damaged: Default::default(),
+            present_task: Default::default(),
+        });
+        state.virtual_outputs.set(slf.id, slf.clone());
+        slf
+    }
+
+    /// Announces the output to the compositor.
+    pub fn connect(self: &Rc<Self>) {
+        self.state
+            .backend_events
+            .push(BackendEvent::NewConnector(self.clone()));
+        let initial_mode = self.mode.get();
+        let mut modes = vec![initial_mode];
+        for (width, height) in COMMON_MODES {
+            let mode = Mode {
+                width,
+                height,
+                refresh_rate_millihz: initial_mode.refresh_rate_millihz,
+            };
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+        self.events
+            .send_event(ConnectorEvent::Connected(MonitorInfo {
+                modes,
+                manufacturer: "jay".to_string(),
+                product: "Virtual".to_string(),
+                serial_number: self.idx.to_string(),
+                initial_mode,
+                width_mm: 0,
+                height_mm: 0,
+                non_desktop: false,
+                vrr_capable: false,
+                primaries: None,
+                hdr_metadata: None,
+            }));
+        let task = self
+            .state
+            .eng
+            .spawn2(Phase::Present, self.clone().present_loop());
+        self.present_task.set(Some(task));
+        self.damaged.trigger();
+    }
+
+    /// Disconnects the output and removes the connector.
+    pub fn remove(&self) {
+        self.state.virtual_outputs.remove(&self.id);
+        self.clear();
+        self.events.send_event(ConnectorEvent::Disconnected);
+        self.events.send_event(ConnectorEvent::Removed);
+    }
+
+    pub fn clear(&self) {
+        self.present_task.take();
+        self.image.take();
+    }
+
+    async fn present_loop(self: Rc<Self>) {
+        loop {
+            self.damaged.triggered().await;
+            self.present();
+            let refresh_ms = 1_000_000 / self.mode.get().refresh_rate_millihz as u64;
+            if let Err(e) = self.state.wheel.timeout(refresh_ms.max(1)).await {
+                log::error!("Could not wait for the next frame: {}", ErrorFmt(e));
+                return;
+            }
+            if let Some(node) = self.state.root.outputs.get(&self.id) {
+                node.vblank();
+            }
+        }
+    }
+
+    fn present(&self) {
+        let Some(node) = self.state.root.outputs.get(&self.id) else {
+            return;
+        };
+        let image = match self.get_image() {
+            Ok(i) => i,
+            Err(e) => {
+                log::error!(
+                    "Could not allocate a virtual output buffer: {}",
+                    ErrorFmt(e)
+                );
+                return;
+            }
+        };
+        let res = self.state.present_output(
+            &node,
+            &image.image.fb,
+            &image.image.tex,
+            &mut self.render_result.borrow_mut(),
+            true,
+        );
+        if let Err(e) = res {
+            log::error!("Could not render screen: {}", ErrorFmt(e));
+            return;
+        }
+        node.schedule.presented();
+        self.state.set_backend_idle(false);
+    }
+
+    fn get_image(&self) -> Result<Rc<VirtualImage>, VirtualOutputError> {
+        let Some(ctx) = self.state.render_ctx.get() else {
+            return Err(VirtualOutputError::NoRenderContext);
+        };
+        let mode = self.mode.get();
+        if let Some(image) = self.image.get() {
+            if Rc::ptr_eq(&image.ctx, &ctx) && image.mode == mode {
+                return Ok(image);
+            }
+        }
+        self.image.take();
+        let formats = ctx.formats();
+        let format = match formats.get(&XRGB8888.drm) {
+            Some(f) => f,
+            None => return Err(VirtualOutputError::XRGB8888),
+        };
+        let image = OffscreenImage::new(
+            &ctx,
+            &self.state.dma_buf_ids,
+            mode.width,
+            mode.height,
+            XRGB8888,
+            &format.write_modifiers,
+        )?;
+        let image = Rc::new(VirtualImage { ctx, mode, image });
+        self.image.set(Some(image.clone()));
+        Ok(image)
+    }
+}
+
+impl Connector for VirtualOutput {
+    fn id(&self) -> ConnectorId {
+        self.id
+    }
+
+    fn kernel_id(&self) -> ConnectorKernelId {
+        ConnectorKernelId {
+            ty: ConnectorType::VIRTUAL,
+            idx: self.idx,
+        }
+    }
+
+    fn event(&self) -> Option<ConnectorEvent> {
+        self.events.events.pop()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.events.on_change.set(Some(cb));
+    }
+
+    fn damage(&self) {
+        self.damaged.trigger();
+    }
+
+    fn drm_dev(&self) -> Option<DrmDeviceId> {
+        self.drm_dev
+    }
+
+    fn set_mode(&self, mode: Mode) {
+        if mode == self.mode.get() {
+            return;
+        }
+        if !validate_mode(mode) {
+            log::warn!("Ignoring invalid mode {:?}", mode);
+            return;
+        }
+        self.mode.set(mode);
+        self.events.send_event(ConnectorEvent::ModeChanged(mode));
+        self.damaged.trigger();
+    }
+}
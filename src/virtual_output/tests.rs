@@ -0,0 +1,35 @@
+use crate::{
+    backend::Mode,
+    virtual_output::{parse_mode, validate_mode, MAX_SIZE},
+};
+
+fn mode(width: i32, height: i32, refresh_rate_millihz: u32) -> Mode {
+    Mode {
+        width,
+        height,
+        refresh_rate_millihz,
+    }
+}
+
+#[test]
+fn parse() {
+    assert_eq!(parse_mode("1920x1080"), Some(mode(1920, 1080, 60_000)));
+    assert_eq!(parse_mode("1280x720@144"), Some(mode(1280, 720, 144_000)));
+    assert_eq!(parse_mode("800x600@59.94"), Some(mode(800, 600, 59_940)));
+    assert_eq!(parse_mode("1920"), None);
+    assert_eq!(parse_mode("-1920x1080"), None);
+    assert_eq!(parse_mode("1920x1080@"), None);
+}
+
+#[test]
+fn limits() {
+    assert!(validate_mode(mode(1, 1, 1)));
+    assert!(validate_mode(mode(MAX_SIZE, MAX_SIZE, 1_000_000)));
+    assert!(!validate_mode(mode(0, 1080, 60_000)));
+    assert!(!validate_mode(mode(1920, 0, 60_000)));
+    assert!(!validate_mode(mode(1920, 1080, 0)));
+    assert!(!validate_mode(mode(MAX_SIZE + 1, 1080, 60_000)));
+    assert!(!validate_mode(mode(1920, i32::MAX, 60_000)));
+    assert!(!validate_mode(mode(1920, 1080, 1_000_001)));
+    assert_eq!(parse_mode("100000x100000"), None);
+}
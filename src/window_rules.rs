@@ -0,0 +1,358 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        backend::ConnectorId,
+        ifs::wl_seat::collect_kb_foci,
+        state::State,
+        tree::{Direction, Node, ToplevelNode, WorkspaceNode},
+    },
+    once_cell::unsync::OnceCell,
+    regex::Regex,
+    std::{cell::RefCell, rc::Rc},
+    uapi::c,
+};
+
+#[derive(Default)]
+pub struct WindowRules {
+    rules: RefCell<Vec<Rc<WindowRule>>>,
+}
+
+pub struct WindowRule {
+    pub id: u64,
+    pub criteria: WindowCriteria,
+    pub actions: WindowRuleActions,
+}
+
+#[derive(Default)]
+pub struct WindowCriteria {
+    pub app_id: Option<String>,
+    pub title: Option<Regex>,
+    pub class: Option<String>,
+    pub instance: Option<String>,
+    pub role: Option<String>,
+    pub pid: Option<c::pid_t>,
+    pub exe: Option<String>,
+    pub floating: Option<bool>,
+    pub dialog: Option<bool>,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct WindowRuleActions {
+    pub workspace: Option<Rc<String>>,
+    pub output: Option<ConnectorId>,
+    pub floating: Option<bool>,
+    pub size: Option<(i32, i32)>,
+    pub position: Option<(i32, i32)>,
+    pub fullscreen: Option<bool>,
+    pub workspace_capture: Option<bool>,
+    pub shortcuts_inhibit: Option<bool>,
+    pub reevaluate_on_title_change: bool,
+}
+
+/// The properties of a window that can be matched by window rules.
+#[derive(Default)]
+pub struct WindowProperties {
+    pub app_id: String,
+    pub title: String,
+    pub class: Option<String>,
+    pub instance: Option<String>,
+    pub role: Option<String>,
+    pub pid: Option<c::pid_t>,
+    pub floating: bool,
+    pub dialog: bool,
+    pub exe: OnceCell<Option<String>>,
+}
+
+impl WindowProperties {
+    fn exe(&self) -> Option<&str> {
+        self.exe
+            .get_or_init(|| {
+                let pid = self.pid?;
+                let exe = std::fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+                Some(exe.to_string_lossy().into_owned())
+            })
+            .as_deref()
+    }
+}
+
+impl WindowRules {
+    pub fn is_empty(&self) -> bool {
+        self.rules.borrow().is_empty()
+    }
+
+    pub fn add(&self, rule: WindowRule) {
+        self.rules.borrow_mut().push(Rc::new(rule));
+    }
+
+    pub fn remove(&self, id: u64) {
+        self.rules.borrow_mut().retain(|r| r.id != id);
+    }
+
+    pub fn clear(&self) {
+        self.rules.borrow_mut().clear();
+    }
+
+    /// Evaluates all rules for a window that is about to be mapped.
+    ///
+    /// Returns `None` if no rule matches the window.
+    pub fn evaluate(
+        &self,
+        node: &dyn ToplevelNode,
+        props: impl FnOnce() -> WindowProperties,
+    ) -> Option<WindowRuleActions> {
+        if self.is_empty() {
+            return None;
+        }
+        let props = props();
+        let matched = &node.tl_data().matched_window_rules;
+        let mut res = None::<WindowRuleActions>;
+        for rule in self.rules.borrow().iter() {
+            if rule.criteria.matches(&props) {
+                matched.set(rule.id, ());
+                res.get_or_insert_with(Default::default)
+                    .merge(&rule.actions);
+            }
+        }
+        res
+    }
+
+    /// Evaluates the rules that should be evaluated again after the title of a mapped
+    /// window has changed and applies the actions of the rules that did not match the
+    /// window before.
+    pub fn title_changed(
+        &self,
+        state: &Rc<State>,
+        node: Rc<dyn ToplevelNode>,
+        props: impl FnOnce() -> WindowProperties,
+    ) {
+        if self.is_empty() || node.tl_data().parent.is_none() {
+            return;
+        }
+        let props = props();
+        let matched = &node.tl_data().matched_window_rules;
+        let mut res = None::<WindowRuleActions>;
+        for rule in self.rules.borrow().iter() {
+            if !rule.actions.reevaluate_on_title_change {
+                continue;
+            }
+            if !rule.criteria.matches(&props) {
+                matched.remove(&rule.id);
+                continue;
+            }
+            if matched.set(rule.id, ()).is_none() {
+                res.get_or_insert_with(Default::default)
+                    .merge(&rule.actions);
+            }
+        }
+        if let Some(actions) = res {
+            actions.apply(state, node);
+        }
+    }
+}
+
+impl WindowCriteria {
+    fn matches(&self, props: &WindowProperties) -> bool {
+        macro_rules! string {
+            ($field:ident) => {
+                if let Some(v) = &self.$field {
+                    if props.$field.as_deref() != Some(v.as_str()) {
+                        return false;
+                    }
+                }
+            };
+        }
+        macro_rules! bool {
+            ($field:ident) => {
+                if let Some(v) = self.$field {
+                    if props.$field != v {
+                        return false;
+                    }
+                }
+            };
+        }
+        if let Some(app_id) = &self.app_id {
+            if props.app_id != *app_id {
+                return false;
+            }
+        }
+        if let Some(title) = &self.title {
+            if !title.is_match(&props.title) {
+                return false;
+            }
+        }
+        string!(class);
+        string!(instance);
+        string!(role);
+        if let Some(pid) = self.pid {
+            if props.pid != Some(pid) {
+                return false;
+            }
+        }
+        if let Some(exe) = &self.exe {
+            if props.exe() != Some(exe.as_str()) {
+                return false;
+            }
+        }
+        bool!(floating);
+        bool!(dialog);
+        true
+    }
+}
+
+impl WindowRuleActions {
+    fn merge(&mut self, other: &Self) {
+        macro_rules! merge {
+            ($($field:ident,)*) => {
+                $(
+                    if other.$field.is_some() {
+                        self.$field.clone_from(&other.$field);
+                    }
+                )*
+            };
+        }
+        merge! {
+            workspace,
+            output,
+            floating,
+            size,
+            position,
+            fullscreen,
+            workspace_capture,
+            shortcuts_inhibit,
+        }
+    }
+
+    fn has_placement(&self) -> bool {
+        self.workspace.is_some()
+            || self.output.is_some()
+            || self.floating.is_some()
+            || self.size.is_some()
+            || self.position.is_some()
+    }
+
+    fn workspace(
+        &self,
+        state: &Rc<State>,
+        default: Option<Rc<WorkspaceNode>>,
+    ) -> Rc<WorkspaceNode> {
+        let output = self
+            .output
+            .and_then(|id| state.outputs.get(&id))
+            .and_then(|o| o.node.clone());
+        if let Some(name) = &self.workspace {
+            if let Some(ws) = state.workspaces.get(name.as_str()) {
+                return ws;
+            }
+            let output = output
+                .clone()
+                .or_else(|| state.seat_queue.last().map(|s| s.get_output()));
+            if let Some(output) = output {
+                if !output.is_dummy {
+                    return output.create_workspace(name);
+                }
+            }
+        }
+        if let Some(output) = output {
+            return output.ensure_workspace();
+        }
+        default.unwrap_or_else(|| state.float_map_ws())
+    }
+
+    fn place(
+        &self,
+        state: &Rc<State>,
+        node: Rc<dyn ToplevelNode>,
+        ws: &Rc<WorkspaceNode>,
+        floating: bool,
+        size: Option<(i32, i32)>,
+        abs_pos: Option<(i32, i32)>,
+    ) {
+        if self.floating.unwrap_or(floating) {
+            let (width, height) = self
+                .size
+                .or(size)
+                .unwrap_or_else(|| node.tl_data().float_size(ws));
+            let abs_pos = match self.position {
+                Some((x, y)) => {
+                    let pos = ws.output.get().global.pos.get();
+                    let bw = state.theme.sizes.border_width.get();
+                    let th = state.theme.sizes.title_height.get();
+                    Some((pos.x1() + x + bw, pos.y1() + y + bw + th + 1))
+                }
+                None => abs_pos,
+            };
+            state.map_floating(node, width, height, ws, abs_pos);
+        } else {
+            state.map_tiled_on(node.clone(), ws);
+            if node.node_visible() {
+                if let Some(seat) = state.seat_queue.last() {
+                    node.node_do_focus(&seat, Direction::Unspecified);
+                }
+            }
+        }
+    }
+
+    fn apply_flags(&self, node: Rc<dyn ToplevelNode>) {
+        let data = node.tl_data();
+        if let Some(capture) = self.workspace_capture {
+            if let Some(ws) = data.workspace.get() {
+                ws.may_capture.set(capture);
+                ws.update_has_captures();
+            }
+        }
+        if let Some(allowed) = self.shortcuts_inhibit {
+            if let Some(client) = node.node_client() {
+                client.set_shortcuts_inhibit_allowed(allowed);
+            }
+        }
+        if let Some(fullscreen) = self.fullscreen {
+            if data.is_fullscreen.get() != fullscreen {
+                node.tl_set_fullscreen(fullscreen);
+            }
+        }
+    }
+
+    /// Maps a window that has not been mapped before.
+    ///
+    /// `floating`, `workspace`, `size`, and `abs_pos` describe where the window would
+    /// be mapped in the absence of window rules.
+    pub fn map(
+        &self,
+        state: &Rc<State>,
+        node: Rc<dyn ToplevelNode>,
+        floating: bool,
+        workspace: Option<Rc<WorkspaceNode>>,
+        size: Option<(i32, i32)>,
+        abs_pos: Option<(i32, i32)>,
+    ) {
+        let ws = self.workspace(state, workspace);
+        self.place(state, node.clone(), &ws, floating, size, abs_pos);
+        self.apply_flags(node);
+    }
+
+    fn apply(&self, state: &Rc<State>, node: Rc<dyn ToplevelNode>) {
+        let data = node.tl_data();
+        if self.fullscreen == Some(false) && data.is_fullscreen.get() {
+            node.clone().tl_set_fullscreen(false);
+        }
+        if self.has_placement() && !data.is_fullscreen.get() {
+            let (Some(old_ws), Some(parent)) = (data.workspace.get(), data.parent.get()) else {
+                return;
+            };
+            let floating = data.is_floating.get();
+            let ws = self.workspace(state, Some(old_ws.clone()));
+            let kb_foci = collect_kb_foci(node.clone().tl_into_node());
+            parent.cnode_remove_child2(node.tl_as_node(), true);
+            if !ws.visible.get() {
+                for seat in kb_foci {
+                    old_ws.clone().node_do_focus(&seat, Direction::Unspecified);
+                }
+            }
+            self.place(state, node.clone(), &ws, floating, None, None);
+            state.tree_changed();
+        }
+        self.apply_flags(node);
+    }
+}
@@ -0,0 +1,113 @@
+use {
+    crate::window_rules::{WindowCriteria, WindowProperties, WindowRuleActions},
+    once_cell::unsync::OnceCell,
+    regex::Regex,
+    std::rc::Rc,
+};
+
+fn props() -> WindowProperties {
+    WindowProperties {
+        app_id: "firefox".to_string(),
+        title: "Mozilla Firefox".to_string(),
+        class: Some("Firefox".to_string()),
+        instance: Some("Navigator".to_string()),
+        role: None,
+        pid: Some(1234),
+        floating: false,
+        dialog: true,
+        exe: OnceCell::with_value(Some("/usr/bin/firefox".to_string())),
+    }
+}
+
+#[test]
+fn empty_criteria_match_everything() {
+    assert!(WindowCriteria::default().matches(&props()));
+    assert!(WindowCriteria::default().matches(&WindowProperties::default()));
+}
+
+#[test]
+fn all_criteria_must_match() {
+    let criteria = WindowCriteria {
+        app_id: Some("firefox".to_string()),
+        title: Some(Regex::new("Fire").unwrap()),
+        class: Some("Firefox".to_string()),
+        instance: Some("Navigator".to_string()),
+        pid: Some(1234),
+        exe: Some("/usr/bin/firefox".to_string()),
+        floating: Some(false),
+        dialog: Some(true),
+        ..Default::default()
+    };
+    assert!(criteria.matches(&props()));
+    let mismatches = [
+        WindowCriteria {
+            app_id: Some("chromium".to_string()),
+            ..Default::default()
+        },
+        WindowCriteria {
+            title: Some(Regex::new("^Fire").unwrap()),
+            ..Default::default()
+        },
+        WindowCriteria {
+            class: Some("firefox".to_string()),
+            ..Default::default()
+        },
+        WindowCriteria {
+            role: Some("browser".to_string()),
+            ..Default::default()
+        },
+        WindowCriteria {
+            pid: Some(1),
+            ..Default::default()
+        },
+        WindowCriteria {
+            exe: Some("/usr/bin/chromium".to_string()),
+            ..Default::default()
+        },
+        WindowCriteria {
+            floating: Some(true),
+            ..Default::default()
+        },
+        WindowCriteria {
+            dialog: Some(false),
+            ..Default::default()
+        },
+    ];
+    for criteria in mismatches {
+        assert!(!criteria.matches(&props()));
+    }
+}
+
+#[test]
+fn x_criteria_do_not_match_wayland_windows() {
+    let props = WindowProperties {
+        app_id: "foot".to_string(),
+        ..Default::default()
+    };
+    let criteria = WindowCriteria {
+        class: Some("foot".to_string()),
+        ..Default::default()
+    };
+    assert!(!criteria.matches(&props));
+}
+
+#[test]
+fn merge_prefers_later_rules() {
+    let mut actions = WindowRuleActions {
+        workspace: Some(Rc::new("1".to_string())),
+        floating: Some(true),
+        size: Some((100, 100)),
+        ..Default::default()
+    };
+    actions.merge(&WindowRuleActions {
+        workspace: Some(Rc::new("2".to_string())),
+        fullscreen: Some(true),
+        ..Default::default()
+    });
+    assert_eq!(actions.workspace.as_deref().map(|s| s.as_str()), Some("2"));
+    assert_eq!(actions.floating, Some(true));
+    assert_eq!(actions.size, Some((100, 100)));
+    assert_eq!(actions.fullscreen, Some(true));
+    assert!(actions.has_placement());
+    assert!(!WindowRuleActions::default().has_placement());
+}
@@ -1,10 +1,12 @@
 pub mod usr_jay_compositor;
 pub mod usr_jay_ei_session;
 pub mod usr_jay_ei_session_builder;
+pub mod usr_jay_global_shortcuts;
 pub mod usr_jay_output;
 pub mod usr_jay_pointer;
 pub mod usr_jay_render_ctx;
 pub mod usr_jay_screencast;
+pub mod usr_jay_screenshot;
 pub mod usr_jay_select_toplevel;
 pub mod usr_jay_select_workspace;
 pub mod usr_jay_toplevel;
@@ -12,10 +14,12 @@ pub mod usr_jay_workspace;
 pub mod usr_jay_workspace_watcher;
 pub mod usr_linux_buffer_params;
 pub mod usr_linux_dmabuf;
+pub mod usr_linux_dmabuf_feedback;
 pub mod usr_wl_buffer;
 pub mod usr_wl_callback;
 pub mod usr_wl_compositor;
 pub mod usr_wl_display;
+pub mod usr_wl_keyboard;
 pub mod usr_wl_output;
 pub mod usr_wl_pointer;
 pub mod usr_wl_registry;
@@ -29,5 +33,12 @@ pub mod usr_wp_fractional_scale;
 pub mod usr_wp_fractional_scale_manager;
 pub mod usr_wp_viewport;
 pub mod usr_wp_viewporter;
+pub mod usr_xdg_surface;
+pub mod usr_xdg_toplevel;
+pub mod usr_xdg_wm_base;
 pub mod usr_zwlr_screencopy_frame;
 pub mod usr_zwlr_screencopy_manager;
+pub mod usr_zwp_locked_pointer;
+pub mod usr_zwp_pointer_constraints;
+pub mod usr_zwp_relative_pointer;
+pub mod usr_zwp_relative_pointer_manager;
@@ -6,11 +6,12 @@ use {
         wire::{jay_compositor::*, JayCompositorId},
         wl_usr::{
             usr_ifs::{
-                usr_jay_ei_session_builder::UsrJayEiSessionBuilder, usr_jay_output::UsrJayOutput,
+                usr_jay_ei_session_builder::UsrJayEiSessionBuilder,
+                usr_jay_global_shortcuts::UsrJayGlobalShortcuts, usr_jay_output::UsrJayOutput,
                 usr_jay_pointer::UsrJayPointer, usr_jay_render_ctx::UsrJayRenderCtx,
-                usr_jay_screencast::UsrJayScreencast,
+                usr_jay_screencast::UsrJayScreencast, usr_jay_screenshot::UsrJayScreenshot,
                 usr_jay_select_toplevel::UsrJaySelectToplevel,
-                usr_jay_select_workspace::UsrJaySelectWorkspace,
+                usr_jay_select_workspace::UsrJaySelectWorkspace, usr_jay_toplevel::UsrJayToplevel,
                 usr_jay_workspace_watcher::UsrJayWorkspaceWatcher, usr_wl_output::UsrWlOutput,
                 usr_wl_seat::UsrWlSeat,
             },
@@ -171,6 +172,72 @@ impl UsrJayCompositor {
         self.con.add_object(obj.clone());
         obj
     }
+
+    pub fn create_global_shortcuts(&self) -> Rc<UsrJayGlobalShortcuts> {
+        let obj = Rc::new(UsrJayGlobalShortcuts {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.request(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: obj.id,
+        });
+        self.con.add_object(obj.clone());
+        obj
+    }
+
+    pub fn take_screenshot(&self, include_cursor: bool) -> Rc<UsrJayScreenshot> {
+        let obj = self.screenshot();
+        self.con.request(TakeScreenshot2 {
+            self_id: self.id,
+            id: obj.id,
+            include_cursor: include_cursor as _,
+        });
+        obj
+    }
+
+    pub fn take_output_screenshot(
+        &self,
+        output: &UsrWlOutput,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let obj = self.screenshot();
+        self.con.request(TakeOutputScreenshot {
+            self_id: self.id,
+            id: obj.id,
+            output: output.id,
+            include_cursor: include_cursor as _,
+        });
+        obj
+    }
+
+    pub fn take_toplevel_screenshot(
+        &self,
+        toplevel: &UsrJayToplevel,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let obj = self.screenshot();
+        self.con.request(TakeToplevelScreenshot {
+            self_id: self.id,
+            id: obj.id,
+            toplevel: toplevel.id,
+            include_cursor: include_cursor as _,
+        });
+        obj
+    }
+
+    fn screenshot(&self) -> Rc<UsrJayScreenshot> {
+        let obj = Rc::new(UsrJayScreenshot {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.add_object(obj.clone());
+        obj
+    }
 }
 
 impl JayCompositorEventHandler for UsrJayCompositor {
@@ -0,0 +1,73 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayGlobalShortcutsOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrJayGlobalShortcutsOwner {
+    fn activated(&self, id: u32, time_usec: u64) {
+        let _ = id;
+        let _ = time_usec;
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        let _ = id;
+        let _ = time_usec;
+    }
+}
+
+impl UsrJayGlobalShortcuts {
+    pub fn bind(&self, id: u32, mods: Modifiers, keysym: KeySym) {
+        self.con.request(Bind {
+            self_id: self.id,
+            id,
+            mods: mods.0,
+            keysym: keysym.0,
+        });
+    }
+}
+
+impl JayGlobalShortcutsEventHandler for UsrJayGlobalShortcuts {
+    type Error = Infallible;
+
+    fn activated(&self, ev: Activated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.activated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn deactivated(&self, ev: Deactivated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.deactivated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayGlobalShortcuts = JayGlobalShortcuts;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayGlobalShortcuts {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{jay_screenshot::*, JayScreenshotId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrJayScreenshot {
+    pub id: JayScreenshotId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayScreenshotOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrJayScreenshotOwner {
+    fn dmabuf(&self, ev: &Dmabuf);
+    fn error(&self, msg: &str);
+}
+
+impl JayScreenshotEventHandler for UsrJayScreenshot {
+    type Error = Infallible;
+
+    fn dmabuf(&self, ev: Dmabuf, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.dmabuf(&ev);
+        }
+        self.con.remove_obj(self);
+        Ok(())
+    }
+
+    fn error(&self, ev: Error<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.error(ev.msg);
+        }
+        self.con.remove_obj(self);
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayScreenshot = JayScreenshot;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayScreenshot {
+    fn destroy(&self) {
+        // nothing
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
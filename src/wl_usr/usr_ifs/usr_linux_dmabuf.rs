@@ -8,7 +8,12 @@ use {
             ZwpLinuxDmabufV1Id,
         },
         wl_usr::{
-            usr_ifs::usr_linux_buffer_params::UsrLinuxBufferParams, usr_object::UsrObject, UsrCon,
+            usr_ifs::{
+                usr_linux_buffer_params::UsrLinuxBufferParams,
+                usr_linux_dmabuf_feedback::UsrLinuxDmabufFeedback,
+            },
+            usr_object::UsrObject,
+            UsrCon,
         },
     },
     std::{convert::Infallible, rc::Rc},
@@ -43,6 +48,24 @@ impl UsrLinuxDmabuf {
         self.con.add_object(params.clone());
         params
     }
+
+    pub fn get_default_feedback(&self) -> Rc<UsrLinuxDmabufFeedback> {
+        let feedback = Rc::new(UsrLinuxDmabufFeedback {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            format_table: Default::default(),
+            main_device: Default::default(),
+            pending_formats: Default::default(),
+        });
+        self.con.request(GetDefaultFeedback {
+            self_id: self.id,
+            id: feedback.id,
+        });
+        self.con.add_object(feedback.clone());
+        feedback
+    }
 }
 
 impl ZwpLinuxDmabufV1EventHandler for UsrLinuxDmabuf {
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        object::Version,
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt},
+        video::Modifier,
+        wire::{zwp_linux_dmabuf_feedback_v1::*, ZwpLinuxDmabufFeedbackV1Id},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    byteorder::{NativeEndian, ReadBytesExt},
+    std::{
+        cell::{Cell, RefCell},
+        convert::Infallible,
+        mem,
+        rc::Rc,
+    },
+    uapi::c,
+};
+
+pub struct UsrLinuxDmabufFeedback {
+    pub id: ZwpLinuxDmabufFeedbackV1Id,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrLinuxDmabufFeedbackOwner>>>,
+    pub version: Version,
+    pub format_table: RefCell<Vec<(u32, Modifier)>>,
+    pub main_device: Cell<c::dev_t>,
+    pub pending_formats: RefCell<Vec<(u32, Modifier)>>,
+}
+
+pub trait UsrLinuxDmabufFeedbackOwner {
+    /// Called once all feedback has been sent. `formats` contains the format/modifier
+    /// pairs of all tranches.
+    fn done(&self, main_device: c::dev_t, formats: &[(u32, Modifier)]) {
+        let _ = main_device;
+        let _ = formats;
+    }
+}
+
+impl ZwpLinuxDmabufFeedbackV1EventHandler for UsrLinuxDmabufFeedback {
+    type Error = Infallible;
+
+    fn done(&self, _ev: Done, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let formats = mem::take(&mut *self.pending_formats.borrow_mut());
+        if let Some(owner) = self.owner.get() {
+            owner.done(self.main_device.get(), &formats);
+        }
+        Ok(())
+    }
+
+    fn format_table(&self, ev: FormatTable, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mut buf = vec![0u8; ev.size as usize];
+        let mut data = match uapi::pread(ev.fd.raw(), &mut buf[..], 0) {
+            Ok(b) => b,
+            Err(e) => {
+                log::warn!("Could not read the dmabuf format table: {}", ErrorFmt(e));
+                &[]
+            }
+        };
+        let mut table = vec![];
+        while data.len() >= 16 {
+            let format = data.read_u32::<NativeEndian>().unwrap();
+            let _padding = data.read_u32::<NativeEndian>().unwrap();
+            let modifier = data.read_u64::<NativeEndian>().unwrap();
+            table.push((format, modifier));
+        }
+        *self.format_table.borrow_mut() = table;
+        Ok(())
+    }
+
+    fn main_device(&self, ev: MainDevice, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.main_device.set(ev.device);
+        Ok(())
+    }
+
+    fn tranche_done(&self, _ev: TrancheDone, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn tranche_target_device(
+        &self,
+        _ev: TrancheTargetDevice,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn tranche_formats(&self, ev: TrancheFormats<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let table = self.format_table.borrow();
+        let pending = &mut *self.pending_formats.borrow_mut();
+        for &idx in ev.indices {
+            if let Some(&format) = table.get(idx as usize) {
+                pending.push(format);
+            }
+        }
+        Ok(())
+    }
+
+    fn tranche_flags(&self, _ev: TrancheFlags, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrLinuxDmabufFeedback = ZwpLinuxDmabufFeedbackV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrLinuxDmabufFeedback {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
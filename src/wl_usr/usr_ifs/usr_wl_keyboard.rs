@@ -0,0 +1,82 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{wl_keyboard::*, WlKeyboardId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrWlKeyboard {
+    pub id: WlKeyboardId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrWlKeyboardOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrWlKeyboardOwner {
+    fn enter(&self, ev: &Enter) {
+        let _ = ev;
+    }
+
+    fn leave(&self, ev: &Leave) {
+        let _ = ev;
+    }
+
+    fn key(&self, ev: &Key) {
+        let _ = ev;
+    }
+}
+
+impl WlKeyboardEventHandler for UsrWlKeyboard {
+    type Error = Infallible;
+
+    fn keymap(&self, _ev: Keymap, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn enter(&self, ev: Enter<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.enter(&ev);
+        }
+        Ok(())
+    }
+
+    fn leave(&self, ev: Leave, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.leave(&ev);
+        }
+        Ok(())
+    }
+
+    fn key(&self, ev: Key, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.key(&ev);
+        }
+        Ok(())
+    }
+
+    fn modifiers(&self, _ev: Modifiers, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn repeat_info(&self, _ev: RepeatInfo, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrWlKeyboard = WlKeyboard;
+    version = self.version;
+}
+
+impl UsrObject for UsrWlKeyboard {
+    fn destroy(&self) {
+        self.con.request(Release { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
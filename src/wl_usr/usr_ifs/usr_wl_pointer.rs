@@ -3,7 +3,7 @@ use {
         ifs::wl_seat::wl_pointer::PendingScroll,
         object::Version,
         utils::clonecell::CloneCell,
-        wire::{wl_pointer::*, WlPointerId},
+        wire::{wl_pointer::*, WlPointerId, WlSurfaceId},
         wl_usr::{usr_ifs::usr_wl_surface::UsrWlSurface, usr_object::UsrObject, UsrCon},
     },
     std::{cell::Cell, convert::Infallible, rc::Rc},
@@ -51,6 +51,16 @@ impl UsrWlPointer {
             hotspot_y: hot_y,
         });
     }
+
+    pub fn hide_cursor(&self, serial: u32) {
+        self.con.request(SetCursor {
+            self_id: self.id,
+            serial,
+            surface: WlSurfaceId::NONE,
+            hotspot_x: 0,
+            hotspot_y: 0,
+        });
+    }
 }
 
 impl WlPointerEventHandler for UsrWlPointer {
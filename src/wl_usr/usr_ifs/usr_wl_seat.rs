@@ -3,7 +3,11 @@ use {
         object::Version,
         utils::clonecell::CloneCell,
         wire::{wl_seat::*, WlSeatId},
-        wl_usr::{usr_ifs::usr_wl_pointer::UsrWlPointer, usr_object::UsrObject, UsrCon},
+        wl_usr::{
+            usr_ifs::{usr_wl_keyboard::UsrWlKeyboard, usr_wl_pointer::UsrWlPointer},
+            usr_object::UsrObject,
+            UsrCon,
+        },
     },
     std::{cell::Cell, convert::Infallible, rc::Rc},
 };
@@ -42,6 +46,21 @@ impl UsrWlSeat {
         });
         ptr
     }
+
+    pub fn get_keyboard(&self) -> Rc<UsrWlKeyboard> {
+        let kb = Rc::new(UsrWlKeyboard {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.add_object(kb.clone());
+        self.con.request(GetKeyboard {
+            self_id: self.id,
+            id: kb.id,
+        });
+        kb
+    }
 }
 
 impl WlSeatEventHandler for UsrWlSeat {
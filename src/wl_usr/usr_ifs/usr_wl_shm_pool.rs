@@ -1,8 +1,9 @@
 use {
     crate::{
+        format::Format,
         object::Version,
         wire::{wl_shm_pool::*, WlShmPoolId},
-        wl_usr::{usr_object::UsrObject, UsrCon},
+        wl_usr::{usr_ifs::usr_wl_buffer::UsrWlBuffer, usr_object::UsrObject, UsrCon},
     },
     std::{convert::Infallible, rc::Rc},
 };
@@ -14,6 +15,34 @@ pub struct UsrWlShmPool {
 }
 
 impl UsrWlShmPool {
+    #[allow(dead_code)]
+    pub fn create_buffer(
+        &self,
+        offset: i32,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: &Format,
+    ) -> Rc<UsrWlBuffer> {
+        let buffer = Rc::new(UsrWlBuffer {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.request(CreateBuffer {
+            self_id: self.id,
+            id: buffer.id,
+            offset,
+            width,
+            height,
+            stride,
+            format: format.wl_id.unwrap_or(format.drm),
+        });
+        self.con.add_object(buffer.clone());
+        buffer
+    }
+
     #[allow(dead_code)]
     pub fn resize(&self, size: i32) {
         self.con.request(Resize {
@@ -27,6 +27,16 @@ impl UsrWlSurface {
         });
     }
 
+    pub fn damage_buffer(&self, width: i32, height: i32) {
+        self.con.request(DamageBuffer {
+            self_id: self.id,
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+    }
+
     pub fn frame<F>(&self, f: F)
     where
         F: FnOnce() + 'static,
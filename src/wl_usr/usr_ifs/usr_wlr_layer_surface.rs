@@ -32,6 +32,20 @@ impl UsrWlrLayerSurface {
         });
     }
 
+    pub fn set_anchor(&self, anchor: u32) {
+        self.con.request(SetAnchor {
+            self_id: self.id,
+            anchor,
+        });
+    }
+
+    pub fn set_exclusive_zone(&self, zone: i32) {
+        self.con.request(SetExclusiveZone {
+            self_id: self.id,
+            zone,
+        });
+    }
+
     #[allow(dead_code)]
     pub fn set_keyboard_interactivity(&self, ki: u32) {
         self.con.request(SetKeyboardInteractivity {
@@ -0,0 +1,37 @@
+use {
+    crate::{
+        object::Version,
+        wire::{zwp_locked_pointer_v1::*, ZwpLockedPointerV1Id},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrZwpLockedPointer {
+    pub id: ZwpLockedPointerV1Id,
+    pub con: Rc<UsrCon>,
+    pub version: Version,
+}
+
+impl ZwpLockedPointerV1EventHandler for UsrZwpLockedPointer {
+    type Error = Infallible;
+
+    fn locked(&self, _ev: Locked, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn unlocked(&self, _ev: Unlocked, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrZwpLockedPointer = ZwpLockedPointerV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrZwpLockedPointer {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        object::Version,
+        wire::{zwp_pointer_constraints_v1::*, WlRegionId, ZwpPointerConstraintsV1Id},
+        wl_usr::{
+            usr_ifs::{
+                usr_wl_pointer::UsrWlPointer, usr_wl_surface::UsrWlSurface,
+                usr_zwp_locked_pointer::UsrZwpLockedPointer,
+            },
+            usr_object::UsrObject,
+            UsrCon,
+        },
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub const LIFETIME_PERSISTENT: u32 = 2;
+
+pub struct UsrZwpPointerConstraints {
+    pub id: ZwpPointerConstraintsV1Id,
+    pub con: Rc<UsrCon>,
+    pub version: Version,
+}
+
+impl UsrZwpPointerConstraints {
+    pub fn lock_pointer(
+        &self,
+        surface: &UsrWlSurface,
+        pointer: &UsrWlPointer,
+        lifetime: u32,
+    ) -> Rc<UsrZwpLockedPointer> {
+        let lp = Rc::new(UsrZwpLockedPointer {
+            id: self.con.id(),
+            con: self.con.clone(),
+            version: self.version,
+        });
+        self.con.add_object(lp.clone());
+        self.con.request(LockPointer {
+            self_id: self.id,
+            id: lp.id,
+            surface: surface.id,
+            pointer: pointer.id,
+            region: WlRegionId::NONE,
+            lifetime,
+        });
+        lp
+    }
+}
+
+impl ZwpPointerConstraintsV1EventHandler for UsrZwpPointerConstraints {
+    type Error = Infallible;
+}
+
+usr_object_base! {
+    self = UsrZwpPointerConstraints = ZwpPointerConstraintsV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrZwpPointerConstraints {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
@@ -0,0 +1,48 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{zwp_relative_pointer_v1::*, ZwpRelativePointerV1Id},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrZwpRelativePointer {
+    pub id: ZwpRelativePointerV1Id,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrZwpRelativePointerOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrZwpRelativePointerOwner {
+    fn relative_motion(&self, ev: &RelativeMotion) {
+        let _ = ev;
+    }
+}
+
+impl ZwpRelativePointerV1EventHandler for UsrZwpRelativePointer {
+    type Error = Infallible;
+
+    fn relative_motion(&self, ev: RelativeMotion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.relative_motion(&ev);
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrZwpRelativePointer = ZwpRelativePointerV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrZwpRelativePointer {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -0,0 +1,53 @@
+use {
+    crate::{
+        object::Version,
+        wire::{zwp_relative_pointer_manager_v1::*, ZwpRelativePointerManagerV1Id},
+        wl_usr::{
+            usr_ifs::{
+                usr_wl_pointer::UsrWlPointer, usr_zwp_relative_pointer::UsrZwpRelativePointer,
+            },
+            usr_object::UsrObject,
+            UsrCon,
+        },
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrZwpRelativePointerManager {
+    pub id: ZwpRelativePointerManagerV1Id,
+    pub con: Rc<UsrCon>,
+    pub version: Version,
+}
+
+impl UsrZwpRelativePointerManager {
+    pub fn get_relative_pointer(&self, pointer: &UsrWlPointer) -> Rc<UsrZwpRelativePointer> {
+        let rp = Rc::new(UsrZwpRelativePointer {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.add_object(rp.clone());
+        self.con.request(GetRelativePointer {
+            self_id: self.id,
+            id: rp.id,
+            pointer: pointer.id,
+        });
+        rp
+    }
+}
+
+impl ZwpRelativePointerManagerV1EventHandler for UsrZwpRelativePointerManager {
+    type Error = Infallible;
+}
+
+usr_object_base! {
+    self = UsrZwpRelativePointerManager = ZwpRelativePointerManagerV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrZwpRelativePointerManager {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
@@ -13,7 +13,7 @@ use {
     isnt::std_1::primitive::IsntConstPtrExt,
     std::{
         cell::{Ref, RefCell},
-        ffi::CStr,
+        ffi::{CStr, CString},
         io::Write,
         ops::Deref,
         ptr,
@@ -117,8 +117,12 @@ extern "C" {
         latched_layout: xkb_layout_index_t,
         locked_layout: xkb_layout_index_t,
     ) -> xkb_state_component;
+    fn xkb_keysym_get_name(keysym: xkb_keysym_t, buffer: *mut c::c_char, size: usize) -> c::c_int;
+    fn xkb_keysym_from_name(name: *const c::c_char, flags: c::c_int) -> xkb_keysym_t;
 }
 
+const XKB_KEYSYM_CASE_INSENSITIVE: c::c_int = 1 << 0;
+
 pub struct XkbContext {
     context: *mut xkb_context,
     ids: KeymapIds,
@@ -403,6 +407,25 @@ impl XkbState {
     }
 }
 
+pub fn keysym_name(keysym: u32) -> Option<String> {
+    let mut buf = [0u8; 64];
+    let res = unsafe { xkb_keysym_get_name(keysym, buf.as_mut_ptr().cast(), buf.len()) };
+    if res < 0 || res as usize >= buf.len() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&buf[..res as usize]).into_owned())
+}
+
+/// Looks up a keysym by name. Lowercase keysyms are preferred if the name is ambiguous.
+pub fn keysym_from_name(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    let res = unsafe { xkb_keysym_from_name(name.as_ptr(), XKB_KEYSYM_CASE_INSENSITIVE) };
+    match res {
+        0 => None,
+        _ => Some(res),
+    }
+}
+
 impl Drop for XkbState {
     fn drop(&mut self) {
         unsafe {
@@ -1158,6 +1158,11 @@ impl Wm {
         if let Some(window) = data.window.get() {
             window.toplevel_data.set_title(&title);
             window.tl_title_changed();
+            self.state.window_rules.title_changed(
+                &self.state,
+                window.clone(),
+                || window.window_properties(),
+            );
         }
         *data.info.title.borrow_mut() = Some(title);
         data.title_changed();
@@ -2476,6 +2481,12 @@ impl Wm {
     }
 
     fn update_wants_floating(&self, data: &Rc<XwindowData>) {
+        let is_dialog = data.info.modal.get()
+            || data
+                .info
+                .window_types
+                .contains(&self.atoms._NET_WM_WINDOW_TYPE_DIALOG);
+        data.info.is_dialog.set(is_dialog);
         let res = data.info.modal.get()
             || data
                 .info
@@ -5,6 +5,8 @@ mod keysyms;
 mod parser;
 mod parsers;
 mod spanned;
+#[cfg(test)]
+mod tests;
 mod value;
 
 use {
@@ -21,9 +23,12 @@ use {
         keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::LogLevel,
         status::MessageFormat,
-        theme::Color,
+        theme::{
+            animations::{Animation, Curve},
+            Color,
+        },
         video::{GfxApi, TearingMode, Transform, VrrMode},
-        Axis, Direction, Workspace,
+        Axis, Direction, Layout, Workspace,
     },
     std::{
         error::Error,
@@ -52,6 +57,11 @@ pub enum SimpleCommand {
     ToggleSplit,
     Forward(bool),
     EnableWindowManagement(bool),
+    MoveToScratchpad,
+    ShowScratchpad,
+    ToggleScratchpad,
+    SetLayout(Layout),
+    ToggleLayout,
 }
 
 #[derive(Debug, Clone)]
@@ -208,6 +218,41 @@ pub struct Output {
     pub mode: Option<Mode>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
+    pub color_temperature: Option<u32>,
+    pub brightness: Option<f64>,
+    pub hdr: Option<bool>,
+    pub sdr_reference_white: Option<f64>,
+    pub mirror: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum WindowMatch {
+    Any(Vec<WindowMatch>),
+    All {
+        app_id: Option<String>,
+        title: Option<String>,
+        class: Option<String>,
+        instance: Option<String>,
+        role: Option<String>,
+        pid: Option<i32>,
+        exe: Option<String>,
+        floating: Option<bool>,
+        dialog: Option<bool>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowRule {
+    pub match_: WindowMatch,
+    pub workspace: Option<String>,
+    pub output: Option<OutputMatch>,
+    pub floating: Option<bool>,
+    pub size: Option<(i32, i32)>,
+    pub position: Option<(i32, i32)>,
+    pub fullscreen: Option<bool>,
+    pub workspace_capture: Option<bool>,
+    pub shortcuts_inhibit: Option<bool>,
+    pub reevaluate_on_title_change: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -304,6 +349,23 @@ pub struct Libei {
     pub enable_socket: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct I3Ipc {
+    pub enable_socket: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Animations {
+    pub enabled: Option<bool>,
+    pub animations: Vec<(Animation, AnimationSettings)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationSettings {
+    pub duration: Option<Duration>,
+    pub curve: Option<Curve>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Shortcut {
     pub mask: Modifiers,
@@ -337,9 +399,13 @@ pub struct Config {
     pub explicit_sync_enabled: Option<bool>,
     pub focus_follows_mouse: bool,
     pub window_management_key: Option<ModifiedKeySym>,
+    pub shortcuts_inhibit_escape: Option<ModifiedKeySym>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
     pub libei: Libei,
+    pub window_rules: Vec<WindowRule>,
+    pub i3_ipc: I3Ipc,
+    pub animations: Animations,
 }
 
 #[derive(Debug, Error)]
@@ -7,6 +7,7 @@ use {
 };
 
 pub mod action;
+mod animations;
 mod color;
 pub mod config;
 mod connector;
@@ -16,6 +17,7 @@ mod drm_device_match;
 mod env;
 pub mod exec;
 mod gfx_api;
+mod i3_ipc;
 mod idle;
 mod input;
 mod input_match;
@@ -32,6 +34,8 @@ mod status;
 mod tearing;
 mod theme;
 mod vrr;
+mod window_match;
+mod window_rule;
 
 #[derive(Debug, Error)]
 pub enum StringParserError {
@@ -34,6 +34,7 @@ use {
     jay_config::{
         get_workspace,
         Axis::{Horizontal, Vertical},
+        Layout,
     },
     thiserror::Error,
 };
@@ -113,6 +114,12 @@ impl ActionParser<'_> {
             "consume" => Forward(false),
             "enable-window-management" => EnableWindowManagement(true),
             "disable-window-management" => EnableWindowManagement(false),
+            "move-to-scratchpad" => MoveToScratchpad,
+            "show-scratchpad" => ShowScratchpad,
+            "toggle-scratchpad" => ToggleScratchpad,
+            "layout-tabbed" => SetLayout(Layout::Tabbed),
+            "layout-stacking" => SetLayout(Layout::Stacked),
+            "layout-toggle-split" => ToggleLayout,
             _ => {
                 return Err(ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span))
             }
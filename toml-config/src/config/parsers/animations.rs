@@ -0,0 +1,157 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, n64, opt, recover, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            AnimationSettings, Animations,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::theme::animations::{
+        Curve, WINDOW_CLOSE, WINDOW_MOVE, WINDOW_OPEN, WORKSPACE_SWITCH,
+    },
+    std::time::Duration,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum AnimationsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct AnimationsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for AnimationsParser<'_> {
+    type Value = Animations;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (enabled, window_open, window_close, window_move, workspace_switch) = ext.extract((
+            recover(opt(bol("enabled"))),
+            opt(val("window-open")),
+            opt(val("window-close")),
+            opt(val("window-move")),
+            opt(val("workspace-switch")),
+        ))?;
+        let mut animations = vec![];
+        let values = [
+            ("window-open", WINDOW_OPEN, window_open),
+            ("window-close", WINDOW_CLOSE, window_close),
+            ("window-move", WINDOW_MOVE, window_move),
+            ("workspace-switch", WORKSPACE_SWITCH, workspace_switch),
+        ];
+        for (name, animation, value) in values {
+            let Some(value) = value else {
+                continue;
+            };
+            match value.parse(&mut AnimationParser(self.0)) {
+                Ok(v) => animations.push((animation, v)),
+                Err(e) => {
+                    log::warn!("Could not parse the {name} animation: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(Animations {
+            enabled: enabled.despan(),
+            animations,
+        })
+    }
+}
+
+struct AnimationParser<'a>(&'a Context<'a>);
+
+impl Parser for AnimationParser<'_> {
+    type Value = AnimationSettings;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (duration_ms, curve_val) =
+            ext.extract((recover(opt(n64("duration-ms"))), opt(val("curve"))))?;
+        let mut curve = None;
+        if let Some(value) = curve_val {
+            match value.parse(&mut CurveParser) {
+                Ok(v) => curve = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the curve: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(AnimationSettings {
+            duration: duration_ms.despan().map(Duration::from_millis),
+            curve,
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CurveParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown curve {0}")]
+    UnknownCurve(String),
+    #[error("Expected 4 control point coordinates but found {0}")]
+    Length(usize),
+    #[error("Control point coordinates must be numbers")]
+    NotANumber,
+    #[error("The x coordinates of the control points must be between 0 and 1")]
+    XRange,
+}
+
+struct CurveParser;
+
+impl Parser for CurveParser {
+    type Value = Curve;
+    type Error = CurveParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String, DataType::Array];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let curve = match string {
+            "linear" => Curve::LINEAR,
+            "ease" => Curve::EASE,
+            "ease-in" => Curve::EASE_IN,
+            "ease-out" => Curve::EASE_OUT,
+            "ease-in-out" => Curve::EASE_IN_OUT,
+            _ => return Err(CurveParserError::UnknownCurve(string.to_string()).spanned(span)),
+        };
+        Ok(curve)
+    }
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        if array.len() != 4 {
+            return Err(CurveParserError::Length(array.len()).spanned(span));
+        }
+        let mut points = [0.0; 4];
+        for (point, value) in points.iter_mut().zip(array) {
+            *point = match value.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as f64,
+                _ => return Err(CurveParserError::NotANumber.spanned(value.span)),
+            };
+        }
+        let [x1, y1, x2, y2] = points;
+        if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+            return Err(CurveParserError::XRange.spanned(span));
+        }
+        Ok(Curve::new(x1, y1, x2, y2))
+    }
+}
@@ -6,11 +6,13 @@ use {
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                animations::AnimationsParser,
                 connector::ConnectorsParser,
                 drm_device::DrmDevicesParser,
                 drm_device_match::DrmDeviceMatchParser,
                 env::EnvParser,
                 gfx_api::GfxApiParser,
+                i3_ipc::I3IpcParser,
                 idle::IdleParser,
                 input::InputsParser,
                 keymap::KeymapParser,
@@ -26,9 +28,10 @@ use {
                 tearing::TearingParser,
                 theme::ThemeParser,
                 vrr::VrrParser,
+                window_rule::WindowRulesParser,
             },
             spanned::SpannedErrorExt,
-            Action, Config, Libei, Theme,
+            Action, Animations, Config, I3Ipc, Libei, Theme,
         },
         toml::{
             toml_span::{DespanExt, Span, Spanned},
@@ -112,7 +115,10 @@ impl Parser for ConfigParser<'_> {
                 vrr_val,
                 tearing_val,
                 libei_val,
+                windows_val,
+                i3_ipc_val,
             ),
+            (animations_val, shortcuts_inhibit_escape_val),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -147,6 +153,12 @@ impl Parser for ConfigParser<'_> {
                 opt(val("vrr")),
                 opt(val("tearing")),
                 opt(val("libei")),
+                opt(val("windows")),
+                opt(val("i3-ipc")),
+            ),
+            (
+                opt(val("animations")),
+                recover(opt(str("shortcuts-inhibit-escape"))),
             ),
         ))?;
         let mut keymap = None;
@@ -311,6 +323,12 @@ impl Parser for ConfigParser<'_> {
                 window_management_key = Some(key);
             }
         }
+        let mut shortcuts_inhibit_escape = None;
+        if let Some(value) = shortcuts_inhibit_escape_val {
+            if let Some(key) = parse_modified_keysym_str(self.0, value.span, value.value) {
+                shortcuts_inhibit_escape = Some(key);
+            }
+        }
         let mut vrr = None;
         if let Some(value) = vrr_val {
             match value.parse(&mut VrrParser(self.0)) {
@@ -338,6 +356,33 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut i3_ipc = I3Ipc::default();
+        if let Some(value) = i3_ipc_val {
+            match value.parse(&mut I3IpcParser(self.0)) {
+                Ok(v) => i3_ipc = v,
+                Err(e) => {
+                    log::warn!("Could not parse i3-ipc setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut animations = Animations::default();
+        if let Some(value) = animations_val {
+            match value.parse(&mut AnimationsParser(self.0)) {
+                Ok(v) => animations = v,
+                Err(e) => {
+                    log::warn!("Could not parse animations setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut window_rules = vec![];
+        if let Some(value) = windows_val {
+            match value.parse(&mut WindowRulesParser(self.0)) {
+                Ok(v) => window_rules = v,
+                Err(e) => {
+                    log::warn!("Could not parse the window rules: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
@@ -362,9 +407,13 @@ impl Parser for ConfigParser<'_> {
             idle,
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
             window_management_key,
+            shortcuts_inhibit_escape,
             vrr,
             tearing,
             libei,
+            window_rules,
+            i3_ipc,
+            animations,
         })
     }
 }
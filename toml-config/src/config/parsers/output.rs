@@ -2,7 +2,9 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{
+                bol, fltorint, n32, opt, recover, s32, str, val, Extractor, ExtractorError,
+            },
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 mode::ModeParser,
@@ -48,8 +50,11 @@ impl<'a> Parser for OutputParser<'a> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.cx, span, table);
-        let (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val) =
-            ext.extract((
+        let (
+            (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val),
+            (color_temperature, brightness, hdr, sdr_reference_white, mirror),
+        ) = ext.extract((
+            (
                 opt(str("name")),
                 val("match"),
                 recover(opt(s32("x"))),
@@ -59,7 +64,15 @@ impl<'a> Parser for OutputParser<'a> {
                 opt(val("mode")),
                 opt(val("vrr")),
                 opt(val("tearing")),
-            ))?;
+            ),
+            (
+                recover(opt(n32("color-temperature"))),
+                recover(opt(fltorint("brightness"))),
+                recover(opt(bol("hdr"))),
+                recover(opt(fltorint("sdr-reference-white"))),
+                recover(opt(str("mirror"))),
+            ),
+        ))?;
         let transform = match transform {
             None => None,
             Some(t) => match t.value {
@@ -129,6 +142,11 @@ impl<'a> Parser for OutputParser<'a> {
             mode,
             vrr,
             tearing,
+            color_temperature: color_temperature.despan(),
+            brightness: brightness.despan(),
+            hdr: hdr.despan(),
+            sdr_reference_white: sdr_reference_white.despan(),
+            mirror: mirror.despan().map(|v| v.to_string()),
         })
     }
 }
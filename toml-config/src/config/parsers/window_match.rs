@@ -0,0 +1,75 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, opt, s32, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            WindowMatch,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowMatchParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WindowMatchParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowMatchParser<'a> {
+    type Value = WindowMatch;
+    type Error = WindowMatchParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table, DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(self) {
+                Ok(m) => res.push(m),
+                Err(e) => {
+                    log::error!("Could not parse match rule: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(WindowMatch::Any(res))
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (app_id, title, class, instance, role, pid, exe, floating, dialog) = ext.extract((
+            opt(str("app-id")),
+            opt(str("title")),
+            opt(str("class")),
+            opt(str("instance")),
+            opt(str("role")),
+            opt(s32("pid")),
+            opt(str("exe")),
+            opt(bol("floating")),
+            opt(bol("dialog")),
+        ))?;
+        Ok(WindowMatch::All {
+            app_id: app_id.despan_into(),
+            title: title.despan_into(),
+            class: class.despan_into(),
+            instance: instance.despan_into(),
+            role: role.despan_into(),
+            pid: pid.despan(),
+            exe: exe.despan_into(),
+            floating: floating.despan(),
+            dialog: dialog.despan(),
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, opt, recover, s32, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{
+                output_match::OutputMatchParser,
+                window_match::{WindowMatchParser, WindowMatchParserError},
+            },
+            WindowRule,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowRuleParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Match(#[from] WindowMatchParserError),
+}
+
+pub struct WindowRuleParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowRuleParser<'a> {
+    type Value = WindowRule;
+    type Error = WindowRuleParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            (match_val, workspace, output_val, floating, width, height, x, y, fullscreen, workspace_capture),
+            (shortcuts_inhibit, reevaluate),
+        ) = ext.extract((
+            (
+                val("match"),
+                recover(opt(str("workspace"))),
+                opt(val("output")),
+                recover(opt(bol("floating"))),
+                recover(opt(s32("width"))),
+                recover(opt(s32("height"))),
+                recover(opt(s32("x"))),
+                recover(opt(s32("y"))),
+                recover(opt(bol("fullscreen"))),
+                recover(opt(bol("workspace-capture"))),
+            ),
+            (
+                recover(opt(bol("shortcuts-inhibit"))),
+                recover(opt(bol("reevaluate-on-title-change"))),
+            ),
+        ))?;
+        let mut output = None;
+        if let Some(value) = output_val {
+            match value.parse(&mut OutputMatchParser(self.0)) {
+                Ok(v) => output = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the output: {}", self.0.error(e));
+                }
+            }
+        }
+        let size = match (width, height) {
+            (Some(width), Some(height)) => Some((width.value, height.value)),
+            (Some(v), None) | (None, Some(v)) => {
+                log::warn!(
+                    "`width` and `height` must be specified together: {}",
+                    self.0.error3(v.span)
+                );
+                None
+            }
+            (None, None) => None,
+        };
+        let position = match (x, y) {
+            (Some(x), Some(y)) => Some((x.value, y.value)),
+            (Some(v), None) | (None, Some(v)) => {
+                log::warn!(
+                    "`x` and `y` must be specified together: {}",
+                    self.0.error3(v.span)
+                );
+                None
+            }
+            (None, None) => None,
+        };
+        Ok(WindowRule {
+            match_: match_val.parse_map(&mut WindowMatchParser(self.0))?,
+            workspace: workspace.despan_into(),
+            output,
+            floating: floating.despan(),
+            size,
+            position,
+            fullscreen: fullscreen.despan(),
+            workspace_capture: workspace_capture.despan(),
+            shortcuts_inhibit: shortcuts_inhibit.despan(),
+            reevaluate_on_title_change: reevaluate.despan().unwrap_or(false),
+        })
+    }
+}
+
+pub struct WindowRulesParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowRulesParser<'a> {
+    type Value = Vec<WindowRule>;
+    type Error = WindowRuleParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table, DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut WindowRuleParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse window rule: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        log::warn!("`windows` value should be an array: {}", self.0.error3(span));
+        WindowRuleParser(self.0)
+            .parse_table(span, table)
+            .map(|v| vec![v])
+    }
+}
@@ -0,0 +1,131 @@
+use {
+    crate::config::{parse_config, OutputMatch, WindowMatch, WindowRule},
+    jay_config::keyboard::{mods::LOGO, syms::SYM_Escape},
+};
+
+fn parse_window_rules(input: &str) -> Vec<WindowRule> {
+    parse_config(input.as_bytes(), |e| panic!("could not parse config: {e}"))
+        .unwrap()
+        .window_rules
+}
+
+#[test]
+fn window_rule() {
+    let rules = parse_window_rules(
+        r#"
+            [[windows]]
+            match = { app-id = "firefox", title = "^Mozilla", floating = false }
+            workspace = "web"
+            output.connector = "DP-1"
+            floating = true
+            width = 800
+            height = 600
+            x = 10
+            y = 20
+            fullscreen = false
+            workspace-capture = false
+            shortcuts-inhibit = false
+            reevaluate-on-title-change = true
+        "#,
+    );
+    assert_eq!(rules.len(), 1);
+    let rule = &rules[0];
+    let WindowMatch::All {
+        app_id,
+        title,
+        floating,
+        class,
+        pid,
+        ..
+    } = &rule.match_
+    else {
+        panic!("expected a single match: {:?}", rule.match_);
+    };
+    assert_eq!(app_id.as_deref(), Some("firefox"));
+    assert_eq!(title.as_deref(), Some("^Mozilla"));
+    assert_eq!(*floating, Some(false));
+    assert_eq!(*class, None);
+    assert_eq!(*pid, None);
+    assert_eq!(rule.workspace.as_deref(), Some("web"));
+    let Some(OutputMatch::All { connector, .. }) = &rule.output else {
+        panic!("expected an output match: {:?}", rule.output);
+    };
+    assert_eq!(connector.as_deref(), Some("DP-1"));
+    assert_eq!(rule.floating, Some(true));
+    assert_eq!(rule.size, Some((800, 600)));
+    assert_eq!(rule.position, Some((10, 20)));
+    assert_eq!(rule.fullscreen, Some(false));
+    assert_eq!(rule.workspace_capture, Some(false));
+    assert_eq!(rule.shortcuts_inhibit, Some(false));
+    assert!(rule.reevaluate_on_title_change);
+}
+
+#[test]
+fn window_rule_any_match() {
+    let rules = parse_window_rules(
+        r#"
+            [[windows]]
+            match = [{ class = "Steam" }, { instance = "steam", dialog = true }]
+            floating = true
+        "#,
+    );
+    assert_eq!(rules.len(), 1);
+    let WindowMatch::Any(matches) = &rules[0].match_ else {
+        panic!("expected a list of matches: {:?}", rules[0].match_);
+    };
+    assert_eq!(matches.len(), 2);
+    let WindowMatch::All {
+        instance, dialog, ..
+    } = &matches[1]
+    else {
+        panic!("expected a single match: {:?}", matches[1]);
+    };
+    assert_eq!(instance.as_deref(), Some("steam"));
+    assert_eq!(*dialog, Some(true));
+}
+
+#[test]
+fn window_rule_incomplete_size() {
+    let rules = parse_window_rules(
+        r#"
+            [[windows]]
+            match.app-id = "foot"
+            width = 800
+            x = 10
+            workspace = "2"
+        "#,
+    );
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].size, None);
+    assert_eq!(rules[0].position, None);
+    assert_eq!(rules[0].workspace.as_deref(), Some("2"));
+    assert!(!rules[0].reevaluate_on_title_change);
+}
+
+#[test]
+fn window_rule_without_match() {
+    let rules = parse_window_rules(
+        r#"
+            [[windows]]
+            workspace = "2"
+
+            [[windows]]
+            match.app-id = "foot"
+        "#,
+    );
+    assert_eq!(rules.len(), 1);
+}
+
+#[test]
+fn shortcuts_inhibit_escape() {
+    let config = parse_config(
+        br#"
+            shortcuts-inhibit-escape = "logo-Escape"
+        "#,
+        |e| panic!("could not parse config: {e}"),
+    )
+    .unwrap();
+    assert_eq!(config.shortcuts_inhibit_escape, Some(LOGO | SYM_Escape));
+    let config = parse_config(b"", |e| panic!("could not parse config: {e}")).unwrap();
+    assert!(config.shortcuts_inhibit_escape.is_none());
+}
@@ -7,7 +7,7 @@ use {
     crate::config::{
         parse_config, Action, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
         ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output, OutputMatch, Shortcut,
-        SimpleCommand, Status, Theme,
+        SimpleCommand, Status, Theme, WindowMatch, WindowRule,
     },
     ahash::{AHashMap, AHashSet},
     error_reporter::Report,
@@ -24,16 +24,17 @@ use {
         keyboard::{Keymap, ModifiedKeySym},
         logging::set_log_level,
         on_devices_enumerated, on_idle, quit, reload, set_default_workspace_capture,
-        set_explicit_sync_enabled, set_idle,
+        set_explicit_sync_enabled, set_i3_ipc_socket_enabled, set_idle,
         status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
         switch_to_vt,
-        theme::{reset_colors, reset_font, reset_sizes, set_font},
+        theme::{animations, reset_colors, reset_font, reset_sizes, set_font},
         video::{
-            connectors, drm_devices, on_connector_connected, on_connector_disconnected,
-            on_graphics_initialized, on_new_connector, on_new_drm_device,
-            set_direct_scanout_enabled, set_gfx_api, set_tearing_mode, set_vrr_cursor_hz,
-            set_vrr_mode, Connector, DrmDevice,
+            connectors, drm_devices, get_connector, on_connector_connected,
+            on_connector_disconnected, on_graphics_initialized, on_new_connector,
+            on_new_drm_device, set_direct_scanout_enabled, set_gfx_api, set_tearing_mode,
+            set_vrr_cursor_hz, set_vrr_mode, Connector, DrmDevice,
         },
+        window::{add_window_rule, clear_window_rules, WindowActions},
     },
     std::{cell::RefCell, io::ErrorKind, path::PathBuf, rc::Rc},
 };
@@ -94,6 +95,11 @@ impl Action {
                 SimpleCommand::EnableWindowManagement(bool) => {
                     B::new(move || s.set_window_management_enabled(bool))
                 }
+                SimpleCommand::MoveToScratchpad => B::new(move || s.move_to_scratchpad()),
+                SimpleCommand::ShowScratchpad => B::new(move || s.show_scratchpad()),
+                SimpleCommand::ToggleScratchpad => B::new(move || s.toggle_scratchpad()),
+                SimpleCommand::SetLayout(layout) => B::new(move || s.set_layout(layout)),
+                SimpleCommand::ToggleLayout => B::new(move || s.toggle_layout()),
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -505,6 +511,35 @@ impl OutputMatch {
     }
 }
 
+impl WindowMatch {
+    fn flatten(&self, res: &mut Vec<jay_config::window::WindowMatch>) {
+        match self {
+            WindowMatch::Any(m) => m.iter().for_each(|m| m.flatten(res)),
+            WindowMatch::All {
+                app_id,
+                title,
+                class,
+                instance,
+                role,
+                pid,
+                exe,
+                floating,
+                dialog,
+            } => res.push(jay_config::window::WindowMatch {
+                app_id: app_id.clone(),
+                title: title.clone(),
+                class: class.clone(),
+                instance: instance.clone(),
+                role: role.clone(),
+                pid: *pid,
+                exe: exe.clone(),
+                floating: *floating,
+                dialog: *dialog,
+            }),
+        }
+    }
+}
+
 impl ConnectorMatch {
     fn matches(&self, c: Connector) -> bool {
         if !c.exists() {
@@ -532,6 +567,14 @@ impl ConfigConnector {
 
 impl Output {
     fn apply(&self, c: Connector) {
+        if let Some(name) = &self.mirror {
+            let source = get_connector(name.as_str());
+            if source.exists() {
+                c.mirror(source);
+            } else {
+                log::warn!("Cannot mirror output {name}: The output does not exist");
+            }
+        }
         if self.x.is_some() || self.y.is_some() {
             let (old_x, old_y) = c.position();
             c.set_position(self.x.unwrap_or(old_x), self.y.unwrap_or(old_y));
@@ -573,6 +616,18 @@ impl Output {
                 c.set_tearing_mode(mode);
             }
         }
+        if let Some(kelvin) = self.color_temperature {
+            c.set_color_temperature(kelvin);
+        }
+        if let Some(brightness) = self.brightness {
+            c.set_brightness(brightness);
+        }
+        if let Some(nits) = self.sdr_reference_white {
+            c.set_sdr_reference_white(nits);
+        }
+        if let Some(enabled) = self.hdr {
+            c.set_hdr_enabled(enabled);
+        }
     }
 }
 
@@ -586,6 +641,8 @@ struct State {
     io_maps: Vec<(InputMatch, OutputMatch)>,
     io_inputs: RefCell<AHashMap<InputDevice, Vec<bool>>>,
     io_outputs: RefCell<AHashMap<Connector, Vec<bool>>>,
+
+    window_rules: Vec<WindowRule>,
 }
 
 impl Drop for State {
@@ -599,6 +656,36 @@ impl Drop for State {
 type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
 
 impl State {
+    fn apply_window_rules(&self) {
+        clear_window_rules();
+        for rule in &self.window_rules {
+            let output = rule
+                .output
+                .as_ref()
+                .and_then(|m| connectors().into_iter().find(|c| m.matches(*c, self)));
+            let actions = WindowActions {
+                workspace: rule.workspace.as_deref().map(get_workspace),
+                output,
+                floating: rule.floating,
+                size: rule.size,
+                position: rule.position,
+                fullscreen: rule.fullscreen,
+                workspace_capture: rule.workspace_capture,
+                shortcuts_inhibit: rule.shortcuts_inhibit,
+                reevaluate_on_title_change: rule.reevaluate_on_title_change,
+            };
+            let mut criteria = vec![];
+            rule.match_.flatten(&mut criteria);
+            for criteria in criteria {
+                add_window_rule(criteria, actions.clone());
+            }
+        }
+    }
+
+    fn has_output_window_rules(&self) -> bool {
+        self.window_rules.iter().any(|r| r.output.is_some())
+    }
+
     fn unbind_all(&self) {
         let mut binds = self.persistent.binds.borrow_mut();
         for bind in binds.drain() {
@@ -883,7 +970,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         io_maps,
         io_inputs: Default::default(),
         io_outputs: Default::default(),
+        window_rules: std::mem::take(&mut config.window_rules),
     });
+    state.apply_window_rules();
     state.set_status(&config.status);
     let mut switch_actions = vec![];
     for input in &mut config.inputs {
@@ -926,6 +1015,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         move |c| {
             state.add_io_output(c);
             state.map_output_to_input(c);
+            if state.has_output_window_rules() {
+                state.apply_window_rules();
+            }
             let id = OutputId {
                 manufacturer: c.manufacturer(),
                 model: c.model(),
@@ -944,6 +1036,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         let state = state.clone();
         move |c| {
             state.io_outputs.borrow_mut().remove(&c);
+            if state.has_output_window_rules() {
+                state.apply_window_rules();
+            }
         }
     });
     set_default_workspace_capture(config.workspace_capture);
@@ -978,6 +1073,18 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     reset_font();
     reset_sizes();
     state.apply_theme(&config.theme);
+    animations::reset_animations();
+    if let Some(enabled) = config.animations.enabled {
+        animations::set_enabled(enabled);
+    }
+    for (animation, settings) in &config.animations.animations {
+        if let Some(duration) = settings.duration {
+            animation.set_duration(duration);
+        }
+        if let Some(curve) = settings.curve {
+            animation.set_curve(curve);
+        }
+    }
     if let Some(api) = config.gfx_api {
         set_gfx_api(api);
     }
@@ -1035,6 +1142,10 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             .seat
             .set_window_management_key(window_management_key);
     }
+    match config.shortcuts_inhibit_escape {
+        Some(escape) => persistent.seat.set_shortcuts_inhibit_escape(escape),
+        None => persistent.seat.unset_shortcuts_inhibit_escape(),
+    }
     if let Some(vrr) = config.vrr {
         if let Some(mode) = vrr.mode {
             set_vrr_mode(mode);
@@ -1049,6 +1160,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     }
     set_libei_socket_enabled(config.libei.enable_socket.unwrap_or(false));
+    set_i3_ipc_socket_enabled(config.i3_ipc.enable_socket.unwrap_or(false));
 }
 
 fn create_command(exec: &Exec) -> Command {